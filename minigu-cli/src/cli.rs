@@ -1,13 +1,14 @@
 use clap::Parser;
-use miette::Result;
+use miette::{IntoDiagnostic, Result};
 
-use crate::script_executor;
 use crate::shell::ShellArgs;
+use crate::{formatter, script_executor};
 
 #[derive(Debug, Parser)]
 pub enum Cli {
     Shell(ShellArgs),
     Execute { file: String },
+    Format { file: String },
 }
 
 impl Cli {
@@ -18,6 +19,11 @@ pub fn run(self) -> Result<()> {
                 let executor = script_executor::ScriptExecutor {};
                 executor.execute_file(file)
             }
+            Cli::Format { file } => {
+                let content = std::fs::read_to_string(&file).into_diagnostic()?;
+                print!("{}", formatter::format_gql(&content)?);
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,175 @@
+//! A simple formatter for GQL scripts.
+//!
+//! The formatter works on the lossless token stream of a script, so comments are preserved. It
+//! applies the following rules:
+//! - clauses (`MATCH`, `WHERE`, `RETURN`, ...) outside of brackets start on a new line;
+//! - each statement ends with a semicolon followed by a line break;
+//! - reserved words are uppercased;
+//! - commas are followed by exactly one space and never preceded by one;
+//! - any other whitespace between tokens is collapsed into a single space.
+//!
+//! Formatting an already formatted script returns it unchanged.
+
+use gql_parser::error::{Error, TokenErrorKind};
+use gql_parser::{LosslessToken, TokenKind, TriviaKind, tokenize_lossless};
+
+/// Formats the GQL script `input`.
+///
+/// # Errors
+///
+/// Returns an error if `input` contains invalid tokens. The script does not need to be parsable
+/// otherwise.
+pub fn format_gql(input: &str) -> Result<String, Error> {
+    let mut formatter = Formatter::default();
+    for token in tokenize_lossless(input) {
+        match token {
+            LosslessToken::Trivia(trivia) => match trivia.kind() {
+                TriviaKind::Whitespace => formatter.pending_space = true,
+                TriviaKind::Comment => formatter.push_comment(trivia.slice()),
+            },
+            LosslessToken::Error(e)
+                if *e.kind() == TokenErrorKind::InvalidToken && e.slice() == ";" =>
+            {
+                formatter.end_statement()
+            }
+            LosslessToken::Error(e) => return Err(Error::from_tokenize_error(input, e)),
+            LosslessToken::Token(token) => formatter.push_token(token.kind(), token.slice()),
+        }
+    }
+    Ok(formatter.finish())
+}
+
+#[derive(Debug)]
+struct Formatter<'a> {
+    output: String,
+    /// The nesting depth of brackets in the current statement.
+    depth: usize,
+    /// The previous token of the current statement.
+    prev: Option<TokenKind<'a>>,
+    /// Whether whitespace was seen since the previous token.
+    pending_space: bool,
+    line_start: bool,
+}
+
+impl Default for Formatter<'_> {
+    fn default() -> Self {
+        Self {
+            output: String::new(),
+            depth: 0,
+            prev: None,
+            pending_space: false,
+            line_start: true,
+        }
+    }
+}
+
+impl<'a> Formatter<'a> {
+    fn push_token(&mut self, kind: &TokenKind<'a>, slice: &str) {
+        let is_comma = matches!(kind, TokenKind::Comma);
+        if self.starts_clause(kind) {
+            self.new_line();
+        } else if !self.line_start && !is_comma && (self.pending_space || self.after_comma()) {
+            self.output.push(' ');
+        }
+        if kind.is_reserved_word() {
+            self.output.push_str(&slice.to_uppercase());
+        } else {
+            self.output.push_str(slice);
+        }
+        if !slice.starts_with(['\'', '"', '`']) {
+            for c in slice.chars() {
+                match c {
+                    '(' | '[' | '{' => self.depth += 1,
+                    ')' | ']' | '}' => self.depth = self.depth.saturating_sub(1),
+                    _ => (),
+                }
+            }
+        }
+        self.prev = Some(kind.clone());
+        self.pending_space = false;
+        self.line_start = false;
+    }
+
+    fn push_comment(&mut self, comment: &str) {
+        if !self.line_start && (self.pending_space || self.after_comma()) {
+            self.output.push(' ');
+        }
+        self.output.push_str(comment.trim_end());
+        self.pending_space = false;
+        self.line_start = false;
+        // A simple comment extends to the end of the line.
+        if comment.starts_with("//") || comment.starts_with("--") {
+            self.new_line();
+        }
+    }
+
+    fn end_statement(&mut self) {
+        self.output.push(';');
+        self.new_line();
+        self.depth = 0;
+        self.prev = None;
+    }
+
+    fn finish(mut self) -> String {
+        if !self.line_start {
+            self.new_line();
+        }
+        self.output
+    }
+
+    fn new_line(&mut self) {
+        if !self.line_start {
+            self.output.push('\n');
+        }
+        self.line_start = true;
+        self.pending_space = false;
+    }
+
+    fn after_comma(&self) -> bool {
+        self.output.ends_with(',')
+    }
+
+    fn starts_clause(&self, kind: &TokenKind<'a>) -> bool {
+        if self.depth > 0 || !is_clause_keyword(kind) {
+            return false;
+        }
+        // `OPTIONAL MATCH`, `DETACH DELETE` and `SESSION SET` are kept on the same line.
+        !matches!(
+            self.prev,
+            None | Some(
+                TokenKind::Optional | TokenKind::Detach | TokenKind::Nodetach | TokenKind::Session
+            )
+        )
+    }
+}
+
+fn is_clause_keyword(kind: &TokenKind<'_>) -> bool {
+    matches!(
+        kind,
+        TokenKind::Match
+            | TokenKind::Optional
+            | TokenKind::Where
+            | TokenKind::Return
+            | TokenKind::Order
+            | TokenKind::Limit
+            | TokenKind::Offset
+            | TokenKind::Skip
+            | TokenKind::Insert
+            | TokenKind::Set
+            | TokenKind::Remove
+            | TokenKind::Delete
+            | TokenKind::Detach
+            | TokenKind::Nodetach
+            | TokenKind::Filter
+            | TokenKind::Let
+            | TokenKind::For
+            | TokenKind::Call
+            | TokenKind::Yield
+            | TokenKind::Use
+            | TokenKind::Next
+            | TokenKind::Union
+            | TokenKind::Except
+            | TokenKind::Intersect
+            | TokenKind::Otherwise
+    )
+}
@@ -1,6 +1,7 @@
 #![feature(duration_millis_float)]
 
 mod cli;
+mod formatter;
 mod script_executor;
 mod shell;
 
@@ -1,4 +1,5 @@
 use clap::Parser;
+use gql_parser::parse_gql_script;
 use miette::{IntoDiagnostic, Result};
 use minigu::database::{Database, DatabaseConfig};
 
@@ -10,13 +11,14 @@ pub fn execute_file(&self, file: String) -> Result<()> {
         let db = Database::open_in_memory(&DatabaseConfig::default()).unwrap();
         let mut session = db.session().unwrap();
         let content = std::fs::read_to_string(&file).into_diagnostic()?;
-        for line in content.lines() {
-            let line = line.trim();
-            match line {
-                "" => continue,
-                ":quit" => break,
-                line => session.query(line)?,
-            };
+        // Everything after a `:quit` line is ignored.
+        let script = match content.lines().find(|line| line.trim() == ":quit") {
+            Some(line) => &content[..line.as_ptr() as usize - content.as_ptr() as usize],
+            None => content.as_str(),
+        };
+        let programs = parse_gql_script(script)?;
+        for program in programs {
+            session.query(&script[program.span()])?;
         }
         Ok(())
     }
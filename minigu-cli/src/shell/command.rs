@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::error::{ContextKind, ContextValue, ErrorKind};
 use clap::{ColorChoice, Command, CommandFactory, FromArgMatches, Parser, ValueEnum};
 use itertools::Itertools;
@@ -5,6 +7,8 @@
 use strum::{Display, VariantNames};
 
 use super::context::ShellContext;
+use super::parquet::write_parquet;
+use crate::formatter::format_gql;
 use crate::shell::output::OutputMode;
 
 pub fn build_command() -> Command {
@@ -53,6 +57,20 @@ pub enum ShellCommand {
         /// If not provided, the current status will be printed.
         status: Option<CliStatus>,
     },
+
+    /// Format a GQL script file.
+    #[command(name = ":format")]
+    Format {
+        /// The path of the script file to format.
+        file: String,
+    },
+
+    /// Save the result of the last query to a Parquet file.
+    #[command(name = ":save")]
+    Save {
+        /// The path of the Parquet file to write.
+        file: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone, ValueEnum, Display)]
@@ -145,6 +163,8 @@ pub fn execute_from_input(ctx: &mut ShellContext, input: &str) -> Result<()> {
             ShellCommand::History => history(ctx),
             Self::Mode { mode_to_change } => mode(ctx, mode_to_change),
             Self::Metrics { status } => metrics(ctx, status),
+            Self::Format { file } => format(file),
+            Self::Save { file } => save(ctx, file),
         }
     }
 }
@@ -192,3 +212,19 @@ fn metrics(ctx: &mut ShellContext, status: Option<CliStatus>) -> Result<()> {
     }
     Ok(())
 }
+
+fn format(file: String) -> Result<()> {
+    let content = std::fs::read_to_string(&file).into_diagnostic()?;
+    print!("{}", format_gql(&content)?);
+    Ok(())
+}
+
+fn save(ctx: &mut ShellContext, file: PathBuf) -> Result<()> {
+    let result = ctx
+        .last_result
+        .as_ref()
+        .ok_or_else(|| miette::diagnostic!("no query has been run yet"))?;
+    write_parquet(&file, result)?;
+    println!("saved {} rows to {}", result.num_rows(), file.display());
+    Ok(())
+}
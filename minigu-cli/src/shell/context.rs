@@ -1,14 +1,19 @@
+use std::path::PathBuf;
+
 use clap::Command;
 use gql_parser::error::TokenErrorKind;
 use gql_parser::tokenize_full;
 use miette::{IntoDiagnostic, Result};
 use minigu::common::data_chunk::display::{TableBuilder, TableOptions};
+use minigu::result::QueryResult;
 use minigu::session::Session;
 use rustyline::error::ReadlineError;
 
 use super::OutputMode;
 use super::command::ShellCommand;
 use super::editor::ShellEditor;
+use super::parquet::write_parquet;
+use super::progress::ProgressBar;
 
 const PROLOGUE: &str = r#"Enter ":help" for usage hints."#;
 
@@ -21,6 +26,13 @@ pub struct ShellContext {
     pub header: bool,
     pub column_type: bool,
     pub show_metrics: bool,
+    /// The number of digits after the decimal point floats are printed with, if set.
+    pub float_precision: Option<usize>,
+    pub progress: ProgressBar,
+    /// The Parquet file the result of each query is written to, if any.
+    pub output_file: Option<PathBuf>,
+    /// The result of the last query, kept for `:save`.
+    pub last_result: Option<QueryResult>,
 }
 
 impl ShellContext {
@@ -62,10 +74,13 @@ fn execute_query(&mut self, input: &str) -> Result<()> {
     }
 
     fn execute_query_segment(&mut self, segment: &str) -> Result<()> {
-        let result = self.session.query(segment)?;
+        let result = self.session.query(segment);
+        self.progress.finish();
+        let result = result?;
         let options = TableOptions::new()
             .with_style(self.mode.into())
-            .with_type_info(self.column_type);
+            .with_type_info(self.column_type)
+            .with_float_precision(self.float_precision);
         let metrics = result.metrics();
         let compiling_time = metrics.compiling_time().as_millis_f64();
         let execution_time = metrics.execution_time().as_millis_f64();
@@ -76,10 +91,9 @@ fn execute_query_segment(&mut self, segment: &str) -> Result<()> {
                 TableBuilder::new(None, options)
             };
             let mut num_rows = 0;
-            for chunk in result {
-                let chunk = chunk;
+            for chunk in result.iter() {
                 num_rows += chunk.cardinality();
-                builder = builder.append_chunk(&chunk);
+                builder = builder.append_chunk(chunk);
             }
             let table = builder.build();
             println!("{table}");
@@ -88,6 +102,10 @@ fn execute_query_segment(&mut self, segment: &str) -> Result<()> {
         if self.show_metrics {
             println!("(compiling: {compiling_time:.3}ms, execution: {execution_time:.3}ms)");
         }
+        if let (Some(path), Some(_)) = (&self.output_file, result.schema()) {
+            write_parquet(path, &result)?;
+        }
+        self.last_result = Some(result);
         Ok(())
     }
 
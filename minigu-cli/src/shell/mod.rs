@@ -2,7 +2,10 @@
 mod context;
 mod editor;
 mod output;
+mod parquet;
+mod progress;
 
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 use clap::Parser;
@@ -12,6 +15,7 @@
 use miette::Result;
 use minigu::database::{Database, DatabaseConfig};
 use output::OutputMode;
+use progress::ProgressBar;
 
 /// Start local interactive shell.
 #[derive(Debug, Parser, Clone)]
@@ -43,6 +47,21 @@ pub struct ShellArgs {
     /// If set, query metrics will be printed.
     #[arg(long)]
     show_metrics: bool,
+
+    /// The number of rows between two updates of the progress bar of long-running procedures,
+    /// e.g., `import`.
+    #[arg(long, default_value_t = NonZeroUsize::new(100_000).unwrap())]
+    progress_interval: NonZeroUsize,
+
+    /// If set, floats will be printed, and written by exports, with this number of digits after
+    /// the decimal point.
+    #[arg(long)]
+    float_precision: Option<usize>,
+
+    /// If set, the result of each query will also be written to this Parquet file, replacing the
+    /// result of the previous query.
+    #[arg(long)]
+    output_file: Option<PathBuf>,
 }
 
 impl ShellArgs {
@@ -52,7 +71,10 @@ pub fn run(self) -> Result<()> {
         } else {
             Database::open_in_memory(&DatabaseConfig::default())?
         };
-        let session = db.session()?;
+        let mut session = db.session()?;
+        let progress = ProgressBar::new(self.progress_interval.get());
+        session.set_progress_callback(Some(progress.callback()));
+        session.set_float_precision(self.float_precision);
         let editor = build_editor()?;
         let command = build_command();
         let context = ShellContext {
@@ -64,6 +86,10 @@ pub fn run(self) -> Result<()> {
             header: !self.no_header,
             column_type: !self.no_column_type,
             show_metrics: self.show_metrics,
+            float_precision: self.float_precision,
+            progress,
+            output_file: self.output_file,
+            last_result: None,
         };
         context.run()
     }
@@ -0,0 +1,61 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use miette::{IntoDiagnostic, Result};
+use minigu::result::QueryResult;
+use parquet::arrow::ArrowWriter;
+
+/// Writes the rows of `result` to the Parquet file at `path`, replacing it if it exists.
+pub fn write_parquet(path: &Path, result: &QueryResult) -> Result<()> {
+    let (Some(schema), Some(batches)) = (result.schema(), result.to_record_batches()) else {
+        miette::bail!("the query returned no rows to save");
+    };
+    let file = File::create(path).into_diagnostic()?;
+    let mut writer =
+        ArrowWriter::try_new(file, Arc::new(schema.to_arrow_schema()), None).into_diagnostic()?;
+    for batch in &batches {
+        writer.write(batch).into_diagnostic()?;
+    }
+    writer.close().into_diagnostic()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use minigu::database::{Database, DatabaseConfig};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_write_parquet() {
+        let db = Database::open_in_memory(&DatabaseConfig::default()).unwrap();
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 5)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("result.parquet");
+        write_parquet(&path, &result).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let read: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+        let written = result.to_record_batches().unwrap();
+        assert_eq!(read.iter().map(|b| b.num_rows()).sum::<usize>(), 5);
+        assert_eq!(read.len(), written.len());
+        for (read, written) in read.iter().zip(&written) {
+            assert_eq!(read.schema().fields(), written.schema().fields());
+            assert_eq!(read.columns(), written.columns());
+        }
+
+        // Statements without rows cannot be saved.
+        let result = session.query("SESSION SET GRAPH test").unwrap();
+        assert!(write_parquet(&path, &result).is_err());
+    }
+}
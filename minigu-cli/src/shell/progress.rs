@@ -0,0 +1,51 @@
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use minigu::progress::{Progress, ProgressCallback};
+
+/// A single-line progress bar of the rows processed by long-running procedures, e.g., `import`,
+/// redrawn on stderr as the rows are reported.
+#[derive(Debug, Clone)]
+pub struct ProgressBar {
+    interval: usize,
+    drawn: Arc<AtomicBool>,
+}
+
+impl ProgressBar {
+    pub fn new(interval: usize) -> Self {
+        Self {
+            interval,
+            drawn: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the callback redrawing the bar every `interval` rows.
+    pub fn callback(&self) -> ProgressCallback {
+        let interval = self.interval;
+        let drawn = self.drawn.clone();
+        ProgressCallback::new(interval, move |progress: Progress| {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(
+                stderr,
+                "\r[{}] {} rows ({:.1}s)",
+                spinner(progress.rows / interval),
+                progress.rows,
+                progress.elapsed.as_secs_f64()
+            );
+            let _ = stderr.flush();
+            drawn.store(true, Ordering::Relaxed);
+        })
+    }
+
+    /// Ends the line of the bar, if it has been drawn since the last call.
+    pub fn finish(&self) {
+        if self.drawn.swap(false, Ordering::Relaxed) {
+            eprintln!();
+        }
+    }
+}
+
+fn spinner(step: usize) -> char {
+    ['|', '/', '-', '\\'][step % 4]
+}
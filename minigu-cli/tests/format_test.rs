@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+fn format_file(path: &Path) -> String {
+    let output = common::run_cli().arg("format").arg(path).output().unwrap();
+    assert!(
+        output.status.success(),
+        "failed to format {}",
+        path.display()
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn check(name: &str) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/format");
+    let input = dir.join(format!("{name}.gql"));
+    let expected_path = dir.join(format!("{name}.expected.gql"));
+    let expected = fs::read_to_string(&expected_path).unwrap();
+    assert_eq!(format_file(&input), expected);
+    // Formatting is idempotent.
+    assert_eq!(format_file(&expected_path), expected);
+}
+
+#[test]
+fn test_format_clauses() {
+    check("clauses");
+}
+
+#[test]
+fn test_format_comments() {
+    check("comments");
+}
+
+#[test]
+fn test_format_nested() {
+    check("nested");
+}
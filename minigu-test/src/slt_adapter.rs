@@ -90,6 +90,8 @@ fn from(logical_type: &LogicalType) -> Self {
             LogicalType::Vertex(_) => Self::Vertex,
             LogicalType::Edge(_) => Self::Edge,
             LogicalType::Record(_) => Self::Any,
+            LogicalType::List(_) => Self::Any,
+            LogicalType::Path(..) => Self::Any,
             LogicalType::Null => Self::Any,
         }
     }
@@ -186,6 +188,7 @@ fn convert_scalar_value_to_string(value: &minigu::common::value::ScalarValue) ->
         }),
         ScalarValue::Vertex(opt) => opt_to_string(opt, |v| format!("{:?}", v)),
         ScalarValue::Edge(opt) => opt_to_string(opt, |v| format!("{:?}", v)),
+        ScalarValue::List(_) | ScalarValue::Record(_) | ScalarValue::Path(_) => format!("{value}"),
     }
 }
 
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The ID of a string interned by an [`Interner`].
+///
+/// Symbols are only meaningful to the interner that created them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    #[inline]
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+/// Deduplicates names (e.g., of labels and properties) into [`Symbol`]s, so that each distinct
+/// name is stored once and names can be compared by their symbols.
+///
+/// Strings are reference-counted: each [`intern`](Self::intern) of a string must be matched by a
+/// [`release`](Self::release) of its symbol once the string is no longer used, and the string is
+/// freed after its last release. Symbols are never reused, so the symbol of a freed string
+/// resolves to nothing.
+#[derive(Debug, Default)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    /// The string of each symbol along with its number of references, or `None` once freed.
+    strings: Vec<Option<(Arc<str>, usize)>>,
+}
+
+impl Interner {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an interner with space for at least `capacity` distinct strings.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            symbols: HashMap::with_capacity(capacity),
+            strings: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the symbol of `string`, interning it if it is not interned yet, and takes a
+    /// reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` strings are interned.
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(string) {
+            if let Some((_, count)) = &mut self.strings[symbol.0 as usize] {
+                *count += 1;
+            }
+            return *symbol;
+        }
+        let symbol = Symbol(
+            self.strings
+                .len()
+                .try_into()
+                .expect("too many strings are interned"),
+        );
+        let string: Arc<str> = string.into();
+        self.strings.push(Some((string.clone(), 1)));
+        self.symbols.insert(string, symbol);
+        symbol
+    }
+
+    /// Drops a reference to the string of `symbol`, freeing the string if it was the last one.
+    ///
+    /// Returns `false` if `symbol` was not created by this interner, or its string is already
+    /// freed.
+    pub fn release(&mut self, symbol: Symbol) -> bool {
+        let Some(entry) = self.strings.get_mut(symbol.0 as usize) else {
+            return false;
+        };
+        let Some((string, count)) = entry else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            self.symbols.remove(string);
+            *entry = None;
+        }
+        true
+    }
+
+    /// Returns the symbol of `string`, if it has been interned.
+    #[inline]
+    pub fn get(&self, string: &str) -> Option<Symbol> {
+        self.symbols.get(string).copied()
+    }
+
+    /// Returns the string of `symbol`, if it was created by this interner and is not freed.
+    #[inline]
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.strings
+            .get(symbol.0 as usize)?
+            .as_ref()
+            .map(|(string, _)| string.as_ref())
+    }
+
+    /// Returns the number of distinct strings interned and not freed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = Interner::with_capacity(2);
+        let person = interner.intern("Person");
+        let company = interner.intern("Company");
+        assert_ne!(person, company);
+        assert_eq!(interner.intern("Person"), person);
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.get("Person"), Some(person));
+        assert_eq!(interner.get("City"), None);
+        assert_eq!(interner.resolve(person), Some("Person"));
+        assert_eq!(interner.resolve(company), Some("Company"));
+    }
+
+    #[test]
+    fn test_release() {
+        let mut interner = Interner::new();
+        let person = interner.intern("Person");
+        assert_eq!(interner.intern("Person"), person);
+
+        // The string is referenced twice.
+        assert!(interner.release(person));
+        assert_eq!(interner.resolve(person), Some("Person"));
+        assert!(interner.release(person));
+        assert_eq!(interner.resolve(person), None);
+        assert_eq!(interner.get("Person"), None);
+        assert!(interner.is_empty());
+        assert!(!interner.release(person));
+
+        // Symbols of freed strings are not reused.
+        assert_ne!(interner.intern("Person"), person);
+    }
+
+    #[test]
+    fn test_resolve_unknown_symbol() {
+        let mut other = Interner::new();
+        other.intern("a");
+        let symbol = other.intern("b");
+        let interner = Interner::new();
+        assert!(interner.is_empty());
+        assert_eq!(interner.resolve(symbol), None);
+    }
+}
@@ -25,6 +25,12 @@ pub fn len(&self) -> usize {
     pub fn contains(&self, label: LabelId) -> bool {
         self.0.binary_search(&label).is_ok()
     }
+
+    /// Returns an iterator over the labels of the set, in ascending order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = LabelId> + '_ {
+        self.0.iter().copied()
+    }
 }
 
 impl FromIterator<LabelId> for LabelSet {
@@ -1,6 +1,8 @@
 pub mod error;
+pub mod interner;
 pub mod label_set;
 pub mod memory;
 pub mod named_ref;
 pub mod property;
 pub mod provider;
+pub mod ttl;
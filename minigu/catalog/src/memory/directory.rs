@@ -1,14 +1,15 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
-use std::sync::{RwLock, Weak};
+use std::sync::{Arc, RwLock, Weak};
 
+use super::fork_node;
 use crate::error::CatalogResult;
 use crate::provider::{DirectoryOrSchema, DirectoryProvider, DirectoryRef};
 
 #[derive(Debug)]
 pub struct MemoryDirectoryCatalog {
     parent: Option<Weak<dyn DirectoryProvider>>,
-    children: RwLock<HashMap<String, DirectoryOrSchema>>,
+    children: RwLock<Arc<HashMap<String, DirectoryOrSchema>>>,
 }
 
 impl MemoryDirectoryCatalog {
@@ -16,18 +17,17 @@ impl MemoryDirectoryCatalog {
     pub fn new(parent: Option<Weak<dyn DirectoryProvider>>) -> Self {
         Self {
             parent,
-            children: RwLock::new(HashMap::new()),
+            children: RwLock::new(Arc::new(HashMap::new())),
         }
     }
 
     #[inline]
     pub fn add_child(&self, name: String, child: DirectoryOrSchema) -> bool {
-        match self
+        let mut children = self
             .children
             .write()
-            .expect("the write lock should be acquired successfully")
-            .entry(name)
-        {
+            .expect("the write lock should be acquired successfully");
+        match Arc::make_mut(&mut children).entry(name) {
             Entry::Occupied(_) => false,
             Entry::Vacant(e) => {
                 e.insert(child);
@@ -38,11 +38,32 @@ pub fn add_child(&self, name: String, child: DirectoryOrSchema) -> bool {
 
     #[inline]
     pub fn remove_child(&self, name: &str) -> bool {
-        self.children
+        let mut children = self
+            .children
             .write()
-            .expect("the write lock should be acquired successfully")
-            .remove(name)
-            .is_some()
+            .expect("the write lock should be acquired successfully");
+        Arc::make_mut(&mut children).remove(name).is_some()
+    }
+
+    /// Returns a fork of the directory, as a child of `parent`, whose children are forked in turn
+    /// (see [`MemoryCatalog::fork`](super::MemoryCatalog::fork)).
+    pub fn fork(&self, parent: Option<Weak<dyn DirectoryProvider>>) -> Arc<Self> {
+        let children = self
+            .children
+            .read()
+            .expect("the read lock should be acquired successfully")
+            .clone();
+        Arc::new_cyclic(|this: &Weak<Self>| {
+            let this: Weak<dyn DirectoryProvider> = this.clone();
+            let children = children
+                .iter()
+                .map(|(name, child)| (name.clone(), fork_node(child, Some(this.clone()))))
+                .collect();
+            Self {
+                parent,
+                children: RwLock::new(Arc::new(children)),
+            }
+        })
     }
 }
 
@@ -2,22 +2,27 @@
 use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
-use minigu_common::types::{LabelId, PropertyId};
+use minigu_common::types::{LabelId, PropertyId, PropertyIndexKey};
 
 use crate::error::CatalogResult;
+use crate::interner::{Interner, Symbol};
 use crate::label_set::LabelSet;
 use crate::property::Property;
 use crate::provider::{
     EdgeTypeProvider, EdgeTypeRef, GraphTypeProvider, PropertiesProvider, VertexTypeProvider,
     VertexTypeRef,
 };
+use crate::ttl::Ttl;
 
 #[derive(Debug)]
 pub struct MemoryGraphTypeCatalog {
     next_label_id: LabelId,
-    label_map: HashMap<String, LabelId>,
+    /// Names of the labels and properties of the graph type.
+    interner: Interner,
+    label_map: HashMap<Symbol, LabelId>,
     vertex_type_map: HashMap<LabelSet, Arc<MemoryVertexTypeCatalog>>,
     edge_type_map: HashMap<LabelSet, Arc<MemoryEdgeTypeCatalog>>,
+    property_index_map: HashMap<String, PropertyIndexKey>,
 }
 
 impl Default for MemoryGraphTypeCatalog {
@@ -30,30 +35,50 @@ fn default() -> Self {
 impl MemoryGraphTypeCatalog {
     #[inline]
     pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a graph type catalog with space for at least `num_names` distinct label and
+    /// property names.
+    #[inline]
+    pub fn with_capacity(num_names: usize) -> Self {
         Self {
             next_label_id: LabelId::new(1).expect("label id should be non-zero"),
+            interner: Interner::with_capacity(num_names),
             label_map: HashMap::new(),
             vertex_type_map: HashMap::new(),
             edge_type_map: HashMap::new(),
+            property_index_map: HashMap::new(),
         }
     }
 
     #[inline]
     pub fn add_label(&mut self, name: String) -> Option<LabelId> {
-        let label_id = self.next_label_id;
-        match self.label_map.entry(name) {
-            Entry::Occupied(_) => None,
-            Entry::Vacant(e) => {
-                self.next_label_id = self.next_label_id.checked_add(1)?;
-                e.insert(label_id);
-                Some(label_id)
-            }
+        let is_added = self
+            .interner
+            .get(&name)
+            .is_some_and(|symbol| self.label_map.contains_key(&symbol));
+        if is_added {
+            return None;
         }
+        let label_id = self.next_label_id;
+        self.next_label_id = self.next_label_id.checked_add(1)?;
+        let symbol = self.interner.intern(&name);
+        self.label_map.insert(symbol, label_id);
+        Some(label_id)
     }
 
+    /// Removes the label `name`, freeing its name unless it is also the name of a property.
     #[inline]
     pub fn remove_label(&mut self, name: &str) -> bool {
-        self.label_map.remove(name).is_some()
+        let Some(symbol) = self.interner.get(name) else {
+            return false;
+        };
+        if self.label_map.remove(&symbol).is_none() {
+            return false;
+        }
+        self.interner.release(symbol);
+        true
     }
 
     #[inline]
@@ -62,18 +87,21 @@ pub fn add_vertex_type(
         label_set: LabelSet,
         vertex_type: Arc<MemoryVertexTypeCatalog>,
     ) -> bool {
-        match self.vertex_type_map.entry(label_set) {
-            Entry::Occupied(_) => false,
-            Entry::Vacant(e) => {
-                e.insert(vertex_type);
-                true
-            }
+        if self.vertex_type_map.contains_key(&label_set) {
+            return false;
         }
+        self.intern_properties(&vertex_type.properties);
+        self.vertex_type_map.insert(label_set, vertex_type);
+        true
     }
 
     #[inline]
     pub fn remove_vertex_type(&mut self, label_set: &LabelSet) -> bool {
-        self.vertex_type_map.remove(label_set).is_some()
+        let Some(vertex_type) = self.vertex_type_map.remove(label_set) else {
+            return false;
+        };
+        self.release_properties(&vertex_type.properties);
+        true
     }
 
     #[inline]
@@ -82,30 +110,82 @@ pub fn add_edge_type(
         label_set: LabelSet,
         edge_type: Arc<MemoryEdgeTypeCatalog>,
     ) -> bool {
-        match self.edge_type_map.entry(label_set) {
+        if self.edge_type_map.contains_key(&label_set) {
+            return false;
+        }
+        self.intern_properties(&edge_type.properties);
+        self.edge_type_map.insert(label_set, edge_type);
+        true
+    }
+
+    #[inline]
+    pub fn remove_edge_type(&mut self, label_set: &LabelSet) -> bool {
+        let Some(edge_type) = self.edge_type_map.remove(label_set) else {
+            return false;
+        };
+        self.release_properties(&edge_type.properties);
+        true
+    }
+
+    /// Declares the property index `name` on the property `key.property_id` of the vertices
+    /// labeled `key.label_id`.
+    #[inline]
+    pub fn add_property_index(&mut self, name: String, key: PropertyIndexKey) -> bool {
+        match self.property_index_map.entry(name) {
             Entry::Occupied(_) => false,
             Entry::Vacant(e) => {
-                e.insert(edge_type);
+                e.insert(key);
                 true
             }
         }
     }
 
     #[inline]
-    pub fn remove_edge_type(&mut self, label_set: &LabelSet) -> bool {
-        self.edge_type_map.remove(label_set).is_some()
+    pub fn remove_property_index(&mut self, name: &str) -> bool {
+        self.property_index_map.remove(name).is_some()
+    }
+
+    fn intern_properties(&mut self, properties: &[Property]) {
+        for property in properties {
+            self.interner.intern(property.name());
+        }
+    }
+
+    fn release_properties(&mut self, properties: &[Property]) {
+        for property in properties {
+            if let Some(symbol) = self.interner.get(property.name()) {
+                self.interner.release(symbol);
+            }
+        }
     }
 }
 
 impl GraphTypeProvider for MemoryGraphTypeCatalog {
     #[inline]
     fn get_label_id(&self, name: &str) -> CatalogResult<Option<LabelId>> {
-        Ok(self.label_map.get(name).copied())
+        Ok(self
+            .interner
+            .get(name)
+            .and_then(|symbol| self.label_map.get(&symbol).copied()))
     }
 
     #[inline]
     fn label_names(&self) -> Vec<String> {
-        self.label_map.keys().cloned().collect()
+        self.label_map
+            .keys()
+            .filter_map(|symbol| self.interner.resolve(*symbol))
+            .map(String::from)
+            .collect()
+    }
+
+    #[inline]
+    fn get_symbol(&self, name: &str) -> Option<Symbol> {
+        self.interner.get(name)
+    }
+
+    #[inline]
+    fn resolve_symbol(&self, symbol: Symbol) -> Option<&str> {
+        self.interner.resolve(symbol)
     }
 
     #[inline]
@@ -127,12 +207,23 @@ fn get_edge_type(&self, key: &LabelSet) -> CatalogResult<Option<EdgeTypeRef>> {
     fn edge_type_keys(&self) -> Vec<LabelSet> {
         self.edge_type_map.keys().cloned().collect()
     }
+
+    #[inline]
+    fn get_property_index(&self, name: &str) -> CatalogResult<Option<PropertyIndexKey>> {
+        Ok(self.property_index_map.get(name).copied())
+    }
+
+    #[inline]
+    fn property_index_names(&self) -> Vec<String> {
+        self.property_index_map.keys().cloned().collect()
+    }
 }
 
 #[derive(Debug)]
 pub struct MemoryVertexTypeCatalog {
     label_set: LabelSet,
     properties: Vec<Property>,
+    ttl: Option<Ttl>,
 }
 
 impl MemoryVertexTypeCatalog {
@@ -141,8 +232,16 @@ pub fn new(label_set: LabelSet, properties: Vec<Property>) -> Self {
         Self {
             label_set,
             properties,
+            ttl: None,
         }
     }
+
+    /// Makes the vertices of the vertex type expire after `ttl`.
+    #[inline]
+    pub fn with_ttl(mut self, ttl: Ttl) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
 }
 
 impl VertexTypeProvider for MemoryVertexTypeCatalog {
@@ -150,6 +249,11 @@ impl VertexTypeProvider for MemoryVertexTypeCatalog {
     fn label_set(&self) -> LabelSet {
         self.label_set.clone()
     }
+
+    #[inline]
+    fn ttl(&self) -> Option<Ttl> {
+        self.ttl
+    }
 }
 
 impl PropertiesProvider for MemoryVertexTypeCatalog {
@@ -233,3 +337,92 @@ fn properties(&self) -> Vec<(PropertyId, Property)> {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::data_type::LogicalType;
+
+    use super::*;
+
+    #[test]
+    fn test_label_symbols() {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let person = graph_type.add_label("Person".into()).unwrap();
+        assert_eq!(graph_type.get_label_id("Person").unwrap(), Some(person));
+
+        // Looking up the same name twice yields the same symbol.
+        let symbol = graph_type.get_symbol("Person").unwrap();
+        assert_eq!(graph_type.get_symbol("Person"), Some(symbol));
+        assert_eq!(graph_type.resolve_symbol(symbol), Some("Person"));
+        assert_eq!(graph_type.get_symbol("Company"), None);
+
+        assert!(graph_type.remove_label("Person"));
+        assert_eq!(graph_type.get_label_id("Person").unwrap(), None);
+        assert!(graph_type.label_names().is_empty());
+        // The name is freed along with the label.
+        assert_eq!(graph_type.get_symbol("Person"), None);
+        assert_eq!(graph_type.resolve_symbol(symbol), None);
+    }
+
+    #[test]
+    fn test_property_symbols() {
+        let mut graph_type = MemoryGraphTypeCatalog::with_capacity(2);
+        let person = graph_type.add_label("Person".into()).unwrap();
+        let label_set = LabelSet::from_iter([person]);
+        let properties = vec![Property::new("name".into(), LogicalType::String, false)];
+        let vertex_type = MemoryVertexTypeCatalog::new(label_set.clone(), properties);
+        assert!(graph_type.add_vertex_type(label_set, Arc::new(vertex_type)));
+
+        let symbol = graph_type.get_symbol("name").unwrap();
+        assert_ne!(graph_type.get_symbol("Person"), Some(symbol));
+        assert_eq!(graph_type.resolve_symbol(symbol), Some("name"));
+
+        // The name is freed once no type has the property anymore.
+        let company = graph_type.add_label("Company".into()).unwrap();
+        let label_set = LabelSet::from_iter([company]);
+        let properties = vec![Property::new("name".into(), LogicalType::String, false)];
+        let vertex_type = MemoryVertexTypeCatalog::new(label_set.clone(), properties);
+        assert!(graph_type.add_vertex_type(label_set.clone(), Arc::new(vertex_type)));
+        assert!(graph_type.remove_vertex_type(&label_set));
+        assert_eq!(graph_type.get_symbol("name"), Some(symbol));
+        assert!(graph_type.remove_vertex_type(&LabelSet::from_iter([person])));
+        assert_eq!(graph_type.get_symbol("name"), None);
+        assert_eq!(graph_type.resolve_symbol(symbol), None);
+    }
+
+    #[test]
+    fn test_label_and_property_with_the_same_name() {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let person = graph_type.add_label("Person".into()).unwrap();
+        assert_eq!(graph_type.add_label("Person".into()), None);
+        let label_set = LabelSet::from_iter([person]);
+        let properties = vec![Property::new("Person".into(), LogicalType::String, false)];
+        let vertex_type = MemoryVertexTypeCatalog::new(label_set.clone(), properties);
+        assert!(graph_type.add_vertex_type(label_set.clone(), Arc::new(vertex_type)));
+
+        // The name is still used by the property after the label is removed.
+        let symbol = graph_type.get_symbol("Person").unwrap();
+        assert!(graph_type.remove_label("Person"));
+        assert!(!graph_type.remove_label("Person"));
+        assert_eq!(graph_type.resolve_symbol(symbol), Some("Person"));
+        assert!(graph_type.remove_vertex_type(&label_set));
+        assert_eq!(graph_type.resolve_symbol(symbol), None);
+    }
+
+    #[test]
+    fn test_property_indexes() {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let person = graph_type.add_label("Person".into()).unwrap();
+        let key = PropertyIndexKey::new(person, 0);
+        assert!(graph_type.add_property_index("person_name".into(), key));
+        assert!(!graph_type.add_property_index("person_name".into(), key));
+        assert_eq!(
+            graph_type.get_property_index("person_name").unwrap(),
+            Some(key)
+        );
+        assert_eq!(graph_type.property_index_names(), ["person_name"]);
+
+        assert!(graph_type.remove_property_index("person_name"));
+        assert_eq!(graph_type.get_property_index("person_name").unwrap(), None);
+    }
+}
@@ -2,8 +2,12 @@
 pub mod graph_type;
 pub mod schema;
 
+use std::sync::Weak;
+
+use self::directory::MemoryDirectoryCatalog;
+use self::schema::MemorySchemaCatalog;
 use crate::error::CatalogResult;
-use crate::provider::{CatalogProvider, DirectoryOrSchema};
+use crate::provider::{CatalogProvider, DirectoryOrSchema, DirectoryProvider};
 
 #[derive(Debug)]
 pub struct MemoryCatalog {
@@ -14,6 +18,21 @@ impl MemoryCatalog {
     pub fn new(root: DirectoryOrSchema) -> Self {
         Self { root }
     }
+
+    /// Returns a fork of the catalog, which starts with the same directories, schemas, graphs,
+    /// graph types and procedures, but can then diverge from it, e.g., to give each tenant of an
+    /// embedder a catalog of their own.
+    ///
+    /// Adding or removing an entry on either side is not seen by the other: the directories and
+    /// schemas of the fork are new nodes, while the maps they hold are shared with the catalog
+    /// until either side modifies them, at which point only the modified map is copied. The
+    /// entries themselves are shared, so the data of a graph is still the same in both catalogs.
+    /// Directories and schemas that are not in memory are shared as is.
+    pub fn fork(&self) -> Self {
+        Self {
+            root: fork_node(&self.root, None),
+        }
+    }
 }
 
 impl CatalogProvider for MemoryCatalog {
@@ -22,3 +41,76 @@ fn get_root(&self) -> CatalogResult<DirectoryOrSchema> {
         Ok(self.root.clone())
     }
 }
+
+/// Forks `node` as a child of `parent`, if it is in memory.
+fn fork_node(
+    node: &DirectoryOrSchema,
+    parent: Option<Weak<dyn DirectoryProvider>>,
+) -> DirectoryOrSchema {
+    match node.clone() {
+        DirectoryOrSchema::Directory(dir) => match dir.downcast_arc::<MemoryDirectoryCatalog>() {
+            Ok(dir) => DirectoryOrSchema::Directory(dir.fork(parent)),
+            Err(dir) => DirectoryOrSchema::Directory(dir),
+        },
+        DirectoryOrSchema::Schema(schema) => match schema.downcast_arc::<MemorySchemaCatalog>() {
+            Ok(schema) => DirectoryOrSchema::Schema(schema.fork(parent)),
+            Err(schema) => DirectoryOrSchema::Schema(schema),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::graph_type::MemoryGraphTypeCatalog;
+    use super::*;
+    use crate::provider::SchemaProvider;
+
+    /// Returns the in-memory schema named `name` in the root directory of `catalog`.
+    fn schema(catalog: &MemoryCatalog, name: &str) -> Arc<MemorySchemaCatalog> {
+        let root = catalog.get_root().unwrap().into_directory().unwrap();
+        let schema = root
+            .get_child(name)
+            .unwrap()
+            .unwrap()
+            .into_schema()
+            .unwrap();
+        schema.downcast_arc().unwrap()
+    }
+
+    #[test]
+    fn test_fork() {
+        let root = Arc::new(MemoryDirectoryCatalog::new(None));
+        let parent: Arc<dyn DirectoryProvider> = root.clone();
+        let default = Arc::new(MemorySchemaCatalog::new(Some(Arc::downgrade(&parent))));
+        default.add_graph_type("person".into(), Arc::new(MemoryGraphTypeCatalog::new()));
+        root.add_child("default".into(), DirectoryOrSchema::Schema(default));
+        let catalog = MemoryCatalog::new(DirectoryOrSchema::Directory(root));
+
+        let fork = catalog.fork();
+        let forked = schema(&fork, "default");
+        assert_eq!(forked.graph_type_names(), vec!["person".to_string()]);
+        assert!(forked.add_graph_type("company".into(), Arc::new(MemoryGraphTypeCatalog::new())));
+        assert!(forked.remove_graph_type("person"));
+        let fork_root = fork.get_root().unwrap().into_directory().unwrap();
+        let fork_root = fork_root.downcast_arc::<MemoryDirectoryCatalog>().unwrap();
+        assert!(fork_root.add_child(
+            "other".into(),
+            DirectoryOrSchema::Schema(Arc::new(MemorySchemaCatalog::new(None))),
+        ));
+        // The schemas of the fork are children of its own directories.
+        let parent = forked.parent().unwrap();
+        assert!(Arc::ptr_eq(
+            &parent,
+            &(fork_root as Arc<dyn DirectoryProvider>)
+        ));
+
+        // The parent is unchanged.
+        let original = schema(&catalog, "default");
+        assert_eq!(original.graph_type_names(), vec!["person".to_string()]);
+        let root = catalog.get_root().unwrap().into_directory().unwrap();
+        assert_eq!(root.children_names(), vec!["default".to_string()]);
+        assert_eq!(forked.graph_type_names(), vec!["company".to_string()]);
+    }
+}
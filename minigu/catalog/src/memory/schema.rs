@@ -11,9 +11,9 @@
 #[derive(Debug)]
 pub struct MemorySchemaCatalog {
     parent: Option<Weak<dyn DirectoryProvider>>,
-    graph_map: RwLock<HashMap<String, GraphRef>>,
-    graph_type_map: RwLock<HashMap<String, Arc<MemoryGraphTypeCatalog>>>,
-    procedure_map: RwLock<HashMap<String, ProcedureRef>>,
+    graph_map: RwLock<Arc<HashMap<String, GraphRef>>>,
+    graph_type_map: RwLock<Arc<HashMap<String, Arc<MemoryGraphTypeCatalog>>>>,
+    procedure_map: RwLock<Arc<HashMap<String, ProcedureRef>>>,
 }
 
 impl MemorySchemaCatalog {
@@ -21,9 +21,9 @@ impl MemorySchemaCatalog {
     pub fn new(parent: Option<Weak<dyn DirectoryProvider>>) -> Self {
         Self {
             parent,
-            graph_map: RwLock::new(HashMap::new()),
-            graph_type_map: RwLock::new(HashMap::new()),
-            procedure_map: RwLock::new(HashMap::new()),
+            graph_map: RwLock::new(Arc::new(HashMap::new())),
+            graph_type_map: RwLock::new(Arc::new(HashMap::new())),
+            procedure_map: RwLock::new(Arc::new(HashMap::new())),
         }
     }
 
@@ -33,7 +33,7 @@ pub fn add_graph(&self, name: String, graph: GraphRef) -> bool {
             .graph_map
             .write()
             .expect("the write lock should be acquired successfully");
-        match graph_map.entry(name) {
+        match Arc::make_mut(&mut graph_map).entry(name) {
             Entry::Occupied(_) => false,
             Entry::Vacant(e) => {
                 e.insert(graph);
@@ -48,7 +48,7 @@ pub fn remove_graph(&self, name: &str) -> bool {
             .graph_map
             .write()
             .expect("the write lock should be acquired successfully");
-        graph_map.remove(name).is_some()
+        Arc::make_mut(&mut graph_map).remove(name).is_some()
     }
 
     #[inline]
@@ -57,7 +57,7 @@ pub fn add_graph_type(&self, name: String, graph_type: Arc<MemoryGraphTypeCatalo
             .graph_type_map
             .write()
             .expect("the write lock should be acquired successfully");
-        match graph_type_map.entry(name) {
+        match Arc::make_mut(&mut graph_type_map).entry(name) {
             Entry::Occupied(_) => false,
             Entry::Vacant(e) => {
                 e.insert(graph_type);
@@ -72,7 +72,7 @@ pub fn remove_graph_type(&self, name: &str) -> bool {
             .graph_type_map
             .write()
             .expect("the write lock should be acquired successfully");
-        graph_type_map.remove(name).is_some()
+        Arc::make_mut(&mut graph_type_map).remove(name).is_some()
     }
 
     #[inline]
@@ -81,7 +81,7 @@ pub fn add_procedure(&self, name: String, procedure: ProcedureRef) -> bool {
             .procedure_map
             .write()
             .expect("the write lock should be acquired successfully");
-        match procedure_map.entry(name) {
+        match Arc::make_mut(&mut procedure_map).entry(name) {
             Entry::Occupied(_) => false,
             Entry::Vacant(e) => {
                 e.insert(procedure);
@@ -96,7 +96,33 @@ pub fn remove_procedure(&self, name: &str) -> bool {
             .procedure_map
             .write()
             .expect("the write lock should be acquired successfully");
-        procedure_map.remove(name).is_some()
+        Arc::make_mut(&mut procedure_map).remove(name).is_some()
+    }
+
+    /// Returns a fork of the schema, as a child of `parent`, holding the same graphs, graph types
+    /// and procedures (see [`MemoryCatalog::fork`](super::MemoryCatalog::fork)).
+    pub fn fork(&self, parent: Option<Weak<dyn DirectoryProvider>>) -> Arc<Self> {
+        let graph_map = self
+            .graph_map
+            .read()
+            .expect("the read lock should be acquired successfully")
+            .clone();
+        let graph_type_map = self
+            .graph_type_map
+            .read()
+            .expect("the read lock should be acquired successfully")
+            .clone();
+        let procedure_map = self
+            .procedure_map
+            .read()
+            .expect("the read lock should be acquired successfully")
+            .clone();
+        Arc::new(Self {
+            parent,
+            graph_map: RwLock::new(graph_map),
+            graph_type_map: RwLock::new(graph_type_map),
+            procedure_map: RwLock::new(procedure_map),
+        })
     }
 }
 
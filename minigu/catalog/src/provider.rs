@@ -4,11 +4,14 @@
 
 use downcast_rs::{DowncastSync, impl_downcast};
 use minigu_common::data_type::{DataSchemaRef, LogicalType};
-use minigu_common::types::{LabelId, PropertyId};
+use minigu_common::types::{LabelId, PropertyId, PropertyIndexKey};
+use minigu_common::value::ScalarValue;
 
 use crate::error::CatalogResult;
+use crate::interner::Symbol;
 use crate::label_set::LabelSet;
 use crate::property::Property;
+use crate::ttl::Ttl;
 
 pub type DirectoryRef = Arc<dyn DirectoryProvider>;
 pub type SchemaRef = Arc<dyn SchemaProvider>;
@@ -25,7 +28,7 @@ pub trait CatalogProvider: Debug + Send + Sync {
     fn get_root(&self) -> CatalogResult<DirectoryOrSchema>;
 }
 
-pub trait DirectoryProvider: Debug + Send + Sync {
+pub trait DirectoryProvider: Debug + Send + Sync + DowncastSync {
     /// Returns the parent directory ID of the directory.
     fn parent(&self) -> Option<DirectoryRef>;
 
@@ -36,6 +39,8 @@ pub trait DirectoryProvider: Debug + Send + Sync {
     fn children_names(&self) -> Vec<String>;
 }
 
+impl_downcast!(sync DirectoryProvider);
+
 /// Represents a logical schema, which contains graphs and graph type definitions.
 pub trait SchemaProvider: Debug + Send + Sync + DowncastSync {
     /// Returns the parent directory ID of the schema.
@@ -71,6 +76,24 @@ pub trait GraphProvider: Debug + Send + Sync + Any {
 
     /// Returns a reference to the underlying graph.
     fn as_any(&self) -> &dyn Any;
+
+    /// Returns the smallest and largest non-null values of a property of the vertices labeled
+    /// `label_id`, if known.
+    ///
+    /// The range may be wider than the values actually stored, but never narrower.
+    fn vertex_property_range(
+        &self,
+        _label_id: LabelId,
+        _property_id: PropertyId,
+    ) -> Option<(ScalarValue, ScalarValue)> {
+        None
+    }
+
+    /// Returns the number of vertices labeled `label_id`, or of all vertices if `label_id` is
+    /// `None`, as estimated by the latest analysis of the graph, if any.
+    fn estimated_vertex_count(&self, _label_id: Option<LabelId>) -> Option<u64> {
+        None
+    }
 }
 
 /// Represents a graph type, which defines the structure of a graph.
@@ -82,6 +105,15 @@ pub trait GraphTypeProvider: Debug + Send + Sync {
     /// Returns the names of the labels in the graph type.
     fn label_names(&self) -> Vec<String>;
 
+    /// Retrieves the interned symbol of a label or property name of the graph type.
+    ///
+    /// Symbols are cheaper to store and compare than names, and can be turned back into names by
+    /// [`resolve_symbol`](Self::resolve_symbol).
+    fn get_symbol(&self, name: &str) -> Option<Symbol>;
+
+    /// Retrieves the name of a symbol returned by [`get_symbol`](Self::get_symbol).
+    fn resolve_symbol(&self, symbol: Symbol) -> Option<&str>;
+
     /// Retrieves a vertex type by its key label set.
     fn get_vertex_type(&self, key: &LabelSet) -> CatalogResult<Option<VertexTypeRef>>;
 
@@ -93,12 +125,30 @@ pub trait GraphTypeProvider: Debug + Send + Sync {
 
     /// Returns the keys of the edge types in the graph type.
     fn edge_type_keys(&self) -> Vec<LabelSet>;
+
+    /// Retrieves a property index by its name.
+    #[inline]
+    fn get_property_index(&self, _name: &str) -> CatalogResult<Option<PropertyIndexKey>> {
+        Ok(None)
+    }
+
+    /// Returns the names of the property indexes in the graph type.
+    #[inline]
+    fn property_index_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 /// Represents a vertex type, which defines the structure of a vertex.
 pub trait VertexTypeProvider: Debug + Send + Sync + PropertiesProvider {
     /// Returns the label set of the vertex type.
     fn label_set(&self) -> LabelSet;
+
+    /// Returns the time to live of the vertices of the vertex type, if they expire.
+    #[inline]
+    fn ttl(&self) -> Option<Ttl> {
+        None
+    }
 }
 
 /// Represents an edge type, which defines the structure of an edge.
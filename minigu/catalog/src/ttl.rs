@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use minigu_common::types::PropertyId;
+
+/// The time to live of the vertices of a vertex type.
+///
+/// A vertex expires once `duration` has elapsed since its insert timestamp, which is held by the
+/// property `property_id` as an `INT64` number of milliseconds since the Unix epoch. Vertices whose
+/// timestamp is null never expire. Expired vertices are deleted by sweeps, e.g.,
+/// `GraphContainer::sweep_expired_vertices`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ttl {
+    property_id: PropertyId,
+    duration: Duration,
+}
+
+impl Ttl {
+    #[inline]
+    pub fn new(property_id: PropertyId, duration: Duration) -> Self {
+        Self {
+            property_id,
+            duration,
+        }
+    }
+
+    /// Returns the id of the property holding the insert timestamps of the vertices.
+    #[inline]
+    pub fn property_id(&self) -> PropertyId {
+        self.property_id
+    }
+
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns whether a vertex inserted at `timestamp` has expired at `now`, both in milliseconds
+    /// since the Unix epoch.
+    #[inline]
+    pub fn is_expired(&self, timestamp: i64, now: i64) -> bool {
+        let duration = i64::try_from(self.duration.as_millis()).unwrap_or(i64::MAX);
+        timestamp.saturating_add(duration) <= now
+    }
+}
@@ -0,0 +1,25 @@
+/// How integer additions, subtractions and multiplications resolve the results which overflow
+/// their type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// The query fails with an overflow error.
+    #[default]
+    Error,
+    /// The result wraps around the boundary of the type, e.g., `i32::MAX + 1 = i32::MIN`.
+    Wrap,
+    /// The result is clamped to the bounds of the type, e.g., `i32::MAX + 1 = i32::MAX`.
+    Saturate,
+    /// The result is null.
+    Null,
+}
+
+/// How integer divisions and remainders by zero are resolved. Floating-point ones follow IEEE 754
+/// instead, i.e., `x / 0.0` is infinite, or NaN if `x` is zero or NaN, and `x % 0.0` is NaN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DivisionByZeroPolicy {
+    /// The query fails with a division by zero error.
+    #[default]
+    Error,
+    /// The result is null.
+    Null,
+}
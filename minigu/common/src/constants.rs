@@ -3,5 +3,7 @@
 pub const EID_FIELD_NAME: &str = "_eid";
 pub const SRC_FIELD_NAME: &str = "_src";
 pub const DST_FIELD_NAME: &str = "_dst";
+pub const NODES_FIELD_NAME: &str = "_nodes";
+pub const RELATIONSHIPS_FIELD_NAME: &str = "_relationships";
 pub const SESSION_USER: &str = "minigu";
 pub const DEFAULT_SCHEMA_NAME: &str = "default";
@@ -0,0 +1,260 @@
+use std::fmt;
+
+use arrow::array::{
+    ArrayBuilder, ArrayRef, BooleanBuilder, Float32Builder, Float64Builder, Int8Builder,
+    Int16Builder, Int32Builder, Int64Builder, StringBuilder, UInt8Builder, UInt16Builder,
+    UInt32Builder, UInt64Builder, make_builder,
+};
+
+use super::DataChunk;
+use crate::data_type::{DataSchemaRef, LogicalType};
+use crate::value::ScalarValue;
+
+/// The default maximum number of rows of a chunk built by [`DataChunkBuilder`].
+pub const DEFAULT_MAX_ROWS: usize = 2048;
+
+/// A builder that accumulates rows into [`DataChunk`]s.
+///
+/// A row is built by appending a value to every column with the typed `append_*` methods, and then
+/// calling [`finish_row`](Self::finish_row). Once `max_rows` rows are accumulated, `finish_row`
+/// returns them as a chunk and the builder starts over, so that producers can yield chunks as they
+/// go.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use minigu_common::data_chunk::builder::DataChunkBuilder;
+/// # use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+/// let schema = DataSchema::new(vec![DataField::new("a".into(), LogicalType::Int32, false)]);
+/// let mut builder = DataChunkBuilder::new(Arc::new(schema));
+/// builder.append_int32(0, Some(1));
+/// assert!(builder.finish_row().is_none());
+/// let chunk = builder.finish().unwrap();
+/// assert_eq!(chunk.len(), 1);
+/// ```
+pub struct DataChunkBuilder {
+    schema: DataSchemaRef,
+    builders: Vec<Box<dyn ArrayBuilder>>,
+    num_rows: usize,
+    max_rows: usize,
+}
+
+impl DataChunkBuilder {
+    pub fn new(schema: DataSchemaRef) -> Self {
+        Self::with_max_rows(schema, DEFAULT_MAX_ROWS)
+    }
+
+    pub fn with_max_rows(schema: DataSchemaRef, max_rows: usize) -> Self {
+        assert!(max_rows > 0, "max_rows must be positive");
+        assert!(schema.size() > 0, "schema must not be empty");
+        let builders = schema
+            .fields()
+            .iter()
+            .map(|f| make_builder(&f.ty().to_arrow_data_type(), max_rows))
+            .collect();
+        Self {
+            schema,
+            builders,
+            num_rows: 0,
+            max_rows,
+        }
+    }
+
+    #[inline]
+    pub fn schema(&self) -> &DataSchemaRef {
+        &self.schema
+    }
+
+    /// Returns the number of finished rows that have not been returned as a chunk yet.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_rows
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.num_rows == 0
+    }
+
+    /// Appends a string value to `column`.
+    pub fn append_string(&mut self, column: usize, value: Option<&str>) {
+        self.column_builder::<StringBuilder>(column)
+            .append_option(value);
+    }
+
+    /// Appends a null value to `column`.
+    pub fn append_null(&mut self, column: usize) {
+        let schema = self.schema.clone();
+        match schema.fields()[column].ty() {
+            LogicalType::Boolean => self.append_boolean(column, None),
+            LogicalType::Int8 => self.append_int8(column, None),
+            LogicalType::Int16 => self.append_int16(column, None),
+            LogicalType::Int32 => self.append_int32(column, None),
+            LogicalType::Int64 => self.append_int64(column, None),
+            LogicalType::UInt8 => self.append_uint8(column, None),
+            LogicalType::UInt16 => self.append_uint16(column, None),
+            LogicalType::UInt32 => self.append_uint32(column, None),
+            LogicalType::UInt64 => self.append_uint64(column, None),
+            LogicalType::Float32 => self.append_float32(column, None),
+            LogicalType::Float64 => self.append_float64(column, None),
+            LogicalType::String => self.append_string(column, None),
+            ty => unimplemented!("appending values of type {ty} is not supported yet"),
+        }
+    }
+
+    /// Appends `value` to `column`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type of `value` does not match the type of `column`.
+    pub fn append_value(&mut self, column: usize, value: &ScalarValue) {
+        match value {
+            ScalarValue::Null => self.append_null(column),
+            ScalarValue::Boolean(v) => self.append_boolean(column, *v),
+            ScalarValue::Int8(v) => self.append_int8(column, *v),
+            ScalarValue::Int16(v) => self.append_int16(column, *v),
+            ScalarValue::Int32(v) => self.append_int32(column, *v),
+            ScalarValue::Int64(v) => self.append_int64(column, *v),
+            ScalarValue::UInt8(v) => self.append_uint8(column, *v),
+            ScalarValue::UInt16(v) => self.append_uint16(column, *v),
+            ScalarValue::UInt32(v) => self.append_uint32(column, *v),
+            ScalarValue::UInt64(v) => self.append_uint64(column, *v),
+            ScalarValue::Float32(v) => self.append_float32(column, v.map(|v| v.into_inner())),
+            ScalarValue::Float64(v) => self.append_float64(column, v.map(|v| v.into_inner())),
+            ScalarValue::String(v) => self.append_string(column, v.as_deref()),
+            _ => unimplemented!("appending {value:?} is not supported yet"),
+        }
+    }
+
+    /// Completes the current row, returning a chunk if `max_rows` rows have been accumulated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value has not been appended to every column of the row.
+    pub fn finish_row(&mut self) -> Option<DataChunk> {
+        assert!(
+            self.builders.iter().all(|b| b.len() == self.num_rows + 1),
+            "a value should be appended to every column"
+        );
+        self.num_rows += 1;
+        (self.num_rows == self.max_rows).then(|| self.flush())
+    }
+
+    /// Returns the remaining rows as a chunk, or `None` if there are none.
+    pub fn finish(mut self) -> Option<DataChunk> {
+        (self.num_rows > 0).then(|| self.flush())
+    }
+
+    fn flush(&mut self) -> DataChunk {
+        let columns: Vec<ArrayRef> = self.builders.iter_mut().map(|b| b.finish()).collect();
+        self.num_rows = 0;
+        DataChunk::new(columns)
+    }
+
+    fn column_builder<B: ArrayBuilder>(&mut self, column: usize) -> &mut B {
+        let field = &self.schema.fields()[column];
+        self.builders[column]
+            .as_any_mut()
+            .downcast_mut::<B>()
+            .unwrap_or_else(|| panic!("column {} is of type {}", field.name(), field.ty()))
+    }
+}
+
+macro_rules! impl_append_primitive {
+    ($($name:ident: $ty:ty => $builder:ty),* $(,)?) => {
+        impl DataChunkBuilder {
+            $(
+                pastey::paste! {
+                    #[doc = concat!(" Appends a `", stringify!($ty), "` value to `column`.")]
+                    pub fn [<append_$name>](&mut self, column: usize, value: Option<$ty>) {
+                        self.column_builder::<$builder>(column).append_option(value);
+                    }
+                }
+            )*
+        }
+    };
+}
+
+impl_append_primitive!(
+    boolean: bool => BooleanBuilder,
+    int8: i8 => Int8Builder,
+    int16: i16 => Int16Builder,
+    int32: i32 => Int32Builder,
+    int64: i64 => Int64Builder,
+    uint8: u8 => UInt8Builder,
+    uint16: u16 => UInt16Builder,
+    uint32: u32 => UInt32Builder,
+    uint64: u64 => UInt64Builder,
+    float32: f32 => Float32Builder,
+    float64: f64 => Float64Builder,
+);
+
+impl fmt::Debug for DataChunkBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataChunkBuilder")
+            .field("schema", &self.schema)
+            .field("num_rows", &self.num_rows)
+            .field("max_rows", &self.max_rows)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::data_chunk;
+    use crate::data_type::{DataField, DataSchema};
+
+    fn build_schema() -> DataSchemaRef {
+        Arc::new(DataSchema::new(vec![
+            DataField::new("id".into(), LogicalType::Int32, false),
+            DataField::new("name".into(), LogicalType::String, true),
+        ]))
+    }
+
+    #[test]
+    fn test_build_chunks() {
+        let mut builder = DataChunkBuilder::with_max_rows(build_schema(), 2);
+        builder.append_int32(0, Some(1));
+        builder.append_string(1, Some("a"));
+        assert!(builder.finish_row().is_none());
+        builder.append_int32(0, Some(2));
+        builder.append_null(1);
+        let chunk = builder.finish_row().unwrap();
+        assert_eq!(
+            chunk,
+            data_chunk!((Int32, [1, 2]), (Utf8, [Some("a"), None]))
+        );
+        assert!(builder.is_empty());
+
+        builder.append_value(0, &3i32.into());
+        builder.append_value(1, &ScalarValue::String(None));
+        assert!(builder.finish_row().is_none());
+        let chunk = builder.finish().unwrap();
+        assert_eq!(chunk, data_chunk!((Int32, [3]), (Utf8, [None::<&str>])));
+    }
+
+    #[test]
+    fn test_finish_empty() {
+        let builder = DataChunkBuilder::new(build_schema());
+        assert!(builder.finish().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_incomplete_row() {
+        let mut builder = DataChunkBuilder::new(build_schema());
+        builder.append_int32(0, Some(1));
+        builder.finish_row();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_type_mismatch() {
+        let mut builder = DataChunkBuilder::new(build_schema());
+        builder.append_int64(0, Some(1));
+    }
+}
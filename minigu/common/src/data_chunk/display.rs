@@ -1,3 +1,5 @@
+use arrow::array::{Array, AsArray};
+use arrow::datatypes::{DataType, Float32Type, Float64Type};
 use arrow::util::display::{ArrayFormatter, FormatOptions};
 use itertools::Itertools;
 use tabled::builder::Builder;
@@ -23,6 +25,7 @@ pub struct TableOptions {
     style: TableStyle,
     type_info: bool,
     null_str: String,
+    float_precision: Option<usize>,
 }
 
 impl Default for TableOptions {
@@ -39,6 +42,7 @@ pub fn new() -> Self {
             style: TableStyle::default(),
             type_info: true,
             null_str: "".into(),
+            float_precision: None,
         }
     }
 
@@ -59,6 +63,41 @@ pub fn with_null_str(mut self, null_str: String) -> Self {
         self.null_str = null_str;
         self
     }
+
+    /// Formats floats with `float_precision` digits after the decimal point (see
+    /// [`format_float`]), or in the shortest form which parses back to the same value if `None`.
+    #[inline]
+    pub fn with_float_precision(mut self, float_precision: Option<usize>) -> Self {
+        self.float_precision = float_precision;
+        self
+    }
+}
+
+/// Formats `value` with `precision` digits after the decimal point, e.g., `0.3333` for `1.0 / 3.0`
+/// at precision 4. Every output with a float precision formats floats this way, so that a value is
+/// written identically everywhere.
+pub fn format_float(value: f64, precision: usize) -> String {
+    format!("{value:.precision$}")
+}
+
+/// Formats the value at `index` of `array` with `formatter`, unless it is a float and
+/// `float_precision` is set.
+fn format_value(
+    array: &dyn Array,
+    formatter: &ArrayFormatter,
+    index: usize,
+    float_precision: Option<usize>,
+) -> String {
+    match (array.data_type(), float_precision) {
+        (DataType::Float32, Some(precision)) if array.is_valid(index) => format_float(
+            array.as_primitive::<Float32Type>().value(index) as f64,
+            precision,
+        ),
+        (DataType::Float64, Some(precision)) if array.is_valid(index) => {
+            format_float(array.as_primitive::<Float64Type>().value(index), precision)
+        }
+        _ => formatter.value(index).to_string(),
+    }
 }
 
 #[derive(Debug)]
@@ -113,7 +152,7 @@ fn append_header(&mut self, schema: &DataSchema, type_info: bool) {
         }
     }
 
-    fn append_chunk(&mut self, chunk: &DataChunk, null_str: &str) {
+    fn append_chunk(&mut self, chunk: &DataChunk, null_str: &str, float_precision: Option<usize>) {
         match self {
             TableBuilderInner::Tabled(builder) => {
                 let options = FormatOptions::new().with_null(null_str);
@@ -127,7 +166,12 @@ fn append_chunk(&mut self, chunk: &DataChunk, null_str: &str) {
                     .collect_vec();
                 for row in chunk.rows() {
                     let index = row.row_index();
-                    builder.push_record(formatters.iter().map(|f| f.value(index).to_string()));
+                    builder.push_record(
+                        formatters
+                            .iter()
+                            .zip(chunk.columns())
+                            .map(|(f, c)| format_value(c.as_ref(), f, index, float_precision)),
+                    );
                 }
             }
             TableBuilderInner::Csv { rows, .. } => {
@@ -145,7 +189,8 @@ fn append_chunk(&mut self, chunk: &DataChunk, null_str: &str) {
                     let index = row.row_index();
                     let record = formatters
                         .iter()
-                        .map(|f| f.value(index).to_string())
+                        .zip(chunk.columns())
+                        .map(|(f, c)| format_value(c.as_ref(), f, index, float_precision))
                         .collect();
                     rows.push(record);
                 }
@@ -169,10 +214,19 @@ fn append_chunk(&mut self, chunk: &DataChunk, null_str: &str) {
 
                     for (i, f) in formatters.iter().enumerate() {
                         let field_name = &field_names[i];
-                        map.insert(
-                            field_name.clone(),
-                            serde_json::Value::String(f.value(index).to_string()),
-                        );
+                        let column = chunk.columns()[i].as_ref();
+                        let value = match column.data_type() {
+                            DataType::Struct(_) | DataType::List(_) => {
+                                json_value(column, index, &options, float_precision)
+                            }
+                            _ => serde_json::Value::String(format_value(
+                                column,
+                                f,
+                                index,
+                                float_precision,
+                            )),
+                        };
+                        map.insert(field_name.clone(), value);
                     }
 
                     rows.push(serde_json::Value::Object(map));
@@ -182,6 +236,44 @@ fn append_chunk(&mut self, chunk: &DataChunk, null_str: &str) {
     }
 }
 
+/// Formats the value at `index` of `array` as JSON: structs (e.g., graph elements) as objects of
+/// their fields, lists (e.g., the vertices and edges of paths) as arrays of their elements, and
+/// other values as strings.
+fn json_value(
+    array: &dyn Array,
+    index: usize,
+    options: &FormatOptions,
+    float_precision: Option<usize>,
+) -> serde_json::Value {
+    match array.data_type() {
+        DataType::Struct(_) if array.is_valid(index) => {
+            let array = array.as_struct();
+            let map = array
+                .fields()
+                .iter()
+                .zip(array.columns())
+                .map(|(f, c)| {
+                    let value = json_value(c.as_ref(), index, options, float_precision);
+                    (f.name().clone(), value)
+                })
+                .collect();
+            serde_json::Value::Object(map)
+        }
+        DataType::List(_) if array.is_valid(index) => {
+            let elements = array.as_list::<i32>().value(index);
+            let elements = (0..elements.len())
+                .map(|i| json_value(elements.as_ref(), i, options, float_precision))
+                .collect();
+            serde_json::Value::Array(elements)
+        }
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, options)
+                .expect("value should be able to be formatted");
+            serde_json::Value::String(format_value(array, &formatter, index, float_precision))
+        }
+    }
+}
+
 impl TableBuilder {
     #[inline]
     pub fn new(schema: Option<DataSchemaRef>, options: TableOptions) -> Self {
@@ -211,7 +303,8 @@ pub fn new(schema: Option<DataSchemaRef>, options: TableOptions) -> Self {
 
     #[inline]
     pub fn append_chunk(mut self, chunk: &DataChunk) -> Self {
-        self.inner.append_chunk(chunk, &self.options.null_str);
+        self.inner
+            .append_chunk(chunk, &self.options.null_str, self.options.float_precision);
         self
     }
 
@@ -288,6 +381,8 @@ mod tests {
     use super::*;
     use crate::data_chunk;
     use crate::data_type::{DataField, LogicalType};
+    use crate::types::LabelId;
+    use crate::value::{EdgeValue, PathValue, PropertyValue, ScalarValue, VertexValue};
 
     fn build_test_schema() -> DataSchemaRef {
         Arc::new(DataSchema::new(vec![
@@ -492,6 +587,24 @@ fn test_table_csv() {
         ");
     }
 
+    #[test]
+    fn test_table_csv_dictionary() {
+        let schema = build_test_schema();
+        let options = TableOptions::new()
+            .with_style(TableStyle::Csv(b','))
+            .with_type_info(false);
+        let mut chunk = build_test_data_chunk();
+        chunk.dictionary_encode(1);
+        let table = TableBuilder::new(Some(schema), options)
+            .append_chunk(&chunk)
+            .build();
+        assert_snapshot!(table, @r"
+        a,b
+        2,def
+        3,ghi
+        ");
+    }
+
     #[test]
     fn test_table_json() {
         let schema = build_test_schema();
@@ -514,6 +627,131 @@ fn test_table_json() {
     "a": "3",
     "b": "ghi"
   }
+]
+        "#);
+    }
+
+    #[test]
+    fn test_table_float_precision() {
+        let schema = Arc::new(DataSchema::new(vec![DataField::new(
+            "x".into(),
+            LogicalType::Float64,
+            true,
+        )]));
+        let chunk = data_chunk!((Float64, [1.0 / 3.0, 2.0]));
+        let table = |style| {
+            let options = TableOptions::new()
+                .with_style(style)
+                .with_type_info(false)
+                .with_float_precision(Some(4));
+            TableBuilder::new(Some(schema.clone()), options)
+                .append_chunk(&chunk)
+                .build()
+                .to_string()
+        };
+        assert_eq!(table(TableStyle::Csv(b',')), "x\n0.3333\n2.0000\n");
+        assert!(table(TableStyle::Sharp).contains("│ 0.3333 │"));
+        assert!(table(TableStyle::Json).contains(r#""x": "0.3333""#));
+        assert_eq!(format_float(1.0 / 3.0, 4), "0.3333");
+    }
+
+    #[test]
+    fn test_table_json_vertex() {
+        let fields = vec![DataField::new("name".into(), LogicalType::String, false)];
+        let ty = LogicalType::Vertex(fields);
+        let schema = Arc::new(DataSchema::new(vec![DataField::new(
+            "n".into(),
+            ty.clone(),
+            true,
+        )]));
+        let label = LabelId::new(1).unwrap();
+        let vertex = VertexValue::new(
+            7,
+            label,
+            vec![PropertyValue::new("name".into(), "alice".into())],
+        );
+        let mut builder = ScalarValue::to_array_builder(&ty, 2);
+        builder.append(ScalarValue::Vertex(Some(vertex))).unwrap();
+        builder.append(ScalarValue::Vertex(None)).unwrap();
+        let chunk = DataChunk::new(vec![builder.finish()]);
+        let options = TableOptions::new()
+            .with_style(TableStyle::Json)
+            .with_type_info(false);
+        let table = TableBuilder::new(Some(schema), options)
+            .append_chunk(&chunk)
+            .build();
+        assert_snapshot!(table, @r#"
+[
+  {
+    "n": {
+      "_label": "1",
+      "_vid": "7",
+      "name": "alice"
+    }
+  },
+  {
+    "n": ""
+  }
+]
+        "#);
+    }
+
+    #[test]
+    fn test_table_json_path() {
+        let fields = vec![DataField::new("name".into(), LogicalType::String, false)];
+        let ty = LogicalType::Path(Box::new((fields, vec![])));
+        let schema = Arc::new(DataSchema::new(vec![DataField::new(
+            "p".into(),
+            ty.clone(),
+            false,
+        )]));
+        let label = LabelId::new(1).unwrap();
+        let vertex = |id: u64, name: &str| {
+            VertexValue::new(
+                id,
+                label,
+                vec![PropertyValue::new("name".into(), name.into())],
+            )
+        };
+        let path = PathValue::new(
+            vec![vertex(1, "alice"), vertex(2, "bob")],
+            vec![EdgeValue::new(10, 1, 2, label, vec![])],
+        );
+        let mut builder = ScalarValue::to_array_builder(&ty, 1);
+        builder.append(path.into()).unwrap();
+        let chunk = DataChunk::new(vec![builder.finish()]);
+        let options = TableOptions::new()
+            .with_style(TableStyle::Json)
+            .with_type_info(false);
+        let table = TableBuilder::new(Some(schema), options)
+            .append_chunk(&chunk)
+            .build();
+        assert_snapshot!(table, @r#"
+[
+  {
+    "p": {
+      "_nodes": [
+        {
+          "_label": "1",
+          "_vid": "1",
+          "name": "alice"
+        },
+        {
+          "_label": "1",
+          "_vid": "2",
+          "name": "bob"
+        }
+      ],
+      "_relationships": [
+        {
+          "_dst": "2",
+          "_eid": "10",
+          "_label": "1",
+          "_src": "1"
+        }
+      ]
+    }
+  }
 ]
         "#);
     }
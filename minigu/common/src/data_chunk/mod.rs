@@ -1,3 +1,4 @@
+pub mod builder;
 pub mod display;
 pub mod row;
 
@@ -8,11 +9,12 @@
 };
 use arrow::buffer::OffsetBuffer;
 use arrow::compute;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, Schema as ArrowSchema};
 use itertools::Itertools;
-use row::{RowIndexIter, Rows};
+use row::{OwnedRow, RowIndexIter, Rows};
 
 use crate::data_type::DataSchema;
+use crate::value::{ConversionError, ScalarValue};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct DataChunk {
@@ -49,6 +51,47 @@ pub fn new_empty(schema: &DataSchema) -> Self {
         Self::new(columns)
     }
 
+    /// Builds a data chunk of `schema` out of `rows`, which is the inverse of
+    /// [`rows`](Self::rows).
+    ///
+    /// Fails if a value is not of the type of its field, or if it is null while its field is not
+    /// nullable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row does not have exactly one value per field of `schema`.
+    pub fn from_rows(schema: &DataSchema, rows: Vec<OwnedRow>) -> Result<Self, ConversionError> {
+        let mut builders = schema
+            .fields()
+            .iter()
+            .map(|f| ScalarValue::to_array_builder(f.ty(), rows.len()))
+            .collect_vec();
+        for row in rows {
+            assert_eq!(
+                row.len(),
+                builders.len(),
+                "every row must have a value per field"
+            );
+            for ((value, builder), field) in row
+                .into_inner()
+                .into_iter()
+                .zip(&mut builders)
+                .zip(schema.fields())
+            {
+                if value.is_null() && !field.is_nullable() {
+                    return Err(ConversionError::NullValue);
+                }
+                builder.append(value)?;
+            }
+        }
+        Ok(Self::new(
+            builders
+                .iter_mut()
+                .map(|builder| builder.finish())
+                .collect(),
+        ))
+    }
+
     #[inline]
     pub fn with_filter(self, filter: BooleanArray) -> Self {
         assert_eq!(
@@ -100,6 +143,23 @@ pub fn columns(&self) -> &[ArrayRef] {
         &self.columns
     }
 
+    /// Returns an estimate of the memory used by the data chunk, in bytes, i.e., the total size of
+    /// the buffers of its columns and of its filter.
+    ///
+    /// Buffers shared by several arrays, e.g., by a [`slice`](Self::slice) and the chunk it was
+    /// sliced from, are counted in full for each of them.
+    pub fn num_bytes(&self) -> usize {
+        let filter = self
+            .filter
+            .as_ref()
+            .map_or(0, |filter| filter.get_buffer_memory_size());
+        self.columns
+            .iter()
+            .map(|column| column.get_buffer_memory_size())
+            .sum::<usize>()
+            + filter
+    }
+
     pub fn factorized_compact(&mut self, filter_list: &ListArray, unflat_column_indices: &[usize]) {
         // Ensure the filter list has the same number of lists as the chunk has rows.
         assert_eq!(
@@ -178,6 +238,28 @@ pub fn compact(&mut self) {
         }
     }
 
+    /// Returns `true` if `self` and `other` hold the same rows, regardless of whether either of
+    /// them is filtered or compacted.
+    pub fn semantically_eq(&self, other: &DataChunk) -> bool {
+        if self.columns.len() != other.columns.len() || self.cardinality() != other.cardinality() {
+            return false;
+        }
+        let compacted = |chunk: &DataChunk| -> Vec<ArrayRef> {
+            match &chunk.filter {
+                Some(filter) => chunk
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        compute::kernels::filter::filter(column, filter)
+                            .expect("filter should be applied successfully")
+                    })
+                    .collect(),
+                None => chunk.columns.clone(),
+            }
+        };
+        compacted(self) == compacted(other)
+    }
+
     /// Returns a zero-copy slice of this chunk with the indicated offset and length.
     ///
     /// # Panics
@@ -308,17 +390,57 @@ pub fn take(&self, indices: &dyn Array) -> Self {
         }
     }
 
+    /// Dictionary-encodes the string column at `index`: each distinct string is stored once, and
+    /// every row holds the key of its string. This saves memory for columns with few distinct
+    /// values, e.g., label-like properties, and lets them be compared and grouped on the keys.
+    ///
+    /// Encoded columns are read like the original ones, e.g., by [`ScalarValue::from_array`].
+    ///
+    /// [`ScalarValue::from_array`]: crate::value::ScalarValue::from_array
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is neither a string column nor already dictionary-encoded.
+    pub fn dictionary_encode(&mut self, index: usize) {
+        let column = &self.columns[index];
+        match column.data_type() {
+            DataType::Dictionary(..) => (),
+            DataType::Utf8 => {
+                let ty = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+                self.columns[index] =
+                    compute::cast(column, &ty).expect("strings should be dictionary-encoded");
+            }
+            ty => panic!("column of type {ty} cannot be dictionary-encoded"),
+        }
+    }
+
     /// Converts the data chunk to an arrow [`RecordBatch`].
     ///
+    /// Dictionary-encoded columns stay encoded, with the type of the record batch field changed
+    /// accordingly.
+    ///
     /// # Panics
     ///
     /// Panics if the schema does not match the data chunk.
     #[inline]
     pub fn to_arrow_record_batch(&self, schema: &DataSchema) -> RecordBatch {
-        let schema = schema.to_arrow_schema();
         let mut chunk = self.clone();
         chunk.compact();
-        RecordBatch::try_new(Arc::new(schema), chunk.columns)
+        let fields: Vec<_> = schema
+            .fields()
+            .iter()
+            .zip(&chunk.columns)
+            .map(|(field, column)| {
+                let field = field.to_arrow_field();
+                match column.data_type() {
+                    DataType::Dictionary(_, value) if value.as_ref() == field.data_type() => {
+                        field.with_data_type(column.data_type().clone())
+                    }
+                    _ => field,
+                }
+            })
+            .collect();
+        RecordBatch::try_new(Arc::new(ArrowSchema::new(fields)), chunk.columns)
             .expect("`schema` should match the data chunk")
     }
 }
@@ -353,8 +475,9 @@ macro_rules! data_chunk {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use arrow::array::create_array;
-    use row::OwnedRow;
 
     use super::*;
     use crate::data_type::{DataField, LogicalType};
@@ -386,6 +509,50 @@ fn test_rows_2() {
         assert_eq!(rows, expected);
     }
 
+    #[test]
+    fn test_from_rows() {
+        let schema = DataSchema::new(vec![
+            DataField::new("a".to_string(), LogicalType::Int32, true),
+            DataField::new("b".to_string(), LogicalType::String, false),
+        ]);
+        let chunk = data_chunk!(
+            (Int32, [Some(1), None, Some(3)]),
+            (Utf8, ["abc", "def", "ghi"])
+        );
+        let rows = chunk.rows().map(|r| r.into_owned()).collect();
+        assert_eq!(DataChunk::from_rows(&schema, rows).unwrap(), chunk);
+
+        // Only the rows kept by the filter are rebuilt.
+        let mut filtered = data_chunk!(
+            { true, false, true },
+            (Int32, [1, 2, 3]),
+            (Utf8, ["abc", "def", "ghi"])
+        );
+        let rows = filtered.rows().map(|r| r.into_owned()).collect();
+        let chunk = DataChunk::from_rows(&schema, rows).unwrap();
+        filtered.compact();
+        assert_eq!(chunk, filtered);
+    }
+
+    #[test]
+    fn test_from_rows_type_mismatch() {
+        let schema = DataSchema::new(vec![DataField::new(
+            "a".to_string(),
+            LogicalType::Int32,
+            false,
+        )]);
+        let rows = vec![OwnedRow::new(vec!["abc".into()])];
+        assert!(matches!(
+            DataChunk::from_rows(&schema, rows),
+            Err(ConversionError::IncompatibleType)
+        ));
+        let rows = vec![OwnedRow::new(vec![ScalarValue::Int32(None)])];
+        assert!(matches!(
+            DataChunk::from_rows(&schema, rows),
+            Err(ConversionError::NullValue)
+        ));
+    }
+
     #[test]
     fn test_slice() {
         let chunk = data_chunk!(
@@ -465,4 +632,143 @@ fn test_to_arrow_record_batch() {
         assert_eq!(record_batch.num_rows(), 3);
         assert_eq!(record_batch.num_columns(), 2);
     }
+
+    #[test]
+    fn test_to_arrow_record_batch_metadata() {
+        let chunk = data_chunk!((Float64, [1.5, 2.0]));
+        let metadata = HashMap::from([
+            ("unit".to_string(), "km".to_string()),
+            ("description".to_string(), "distance".to_string()),
+        ]);
+        let field = DataField::new("distance".to_string(), LogicalType::Float64, false)
+            .with_metadata(metadata.clone());
+        let schema = DataSchema::new(vec![field]);
+        let record_batch = chunk.to_arrow_record_batch(&schema);
+        assert_eq!(record_batch.schema().field(0).metadata(), &metadata);
+        // Fields without metadata are unaffected.
+        let schema = DataSchema::new(vec![DataField::new(
+            "distance".to_string(),
+            LogicalType::Float64,
+            false,
+        )]);
+        let record_batch = chunk.to_arrow_record_batch(&schema);
+        assert!(record_batch.schema().field(0).metadata().is_empty());
+    }
+
+    #[test]
+    fn test_dictionary_encode() {
+        let mut chunk = data_chunk!(
+            { true, false, true, true },
+            (Int32, [1, 2, 3, 4]),
+            (Utf8, [Some("person"), Some("city"), None, Some("person")])
+        );
+        chunk.dictionary_encode(1);
+        let column = chunk.columns()[1].as_any_dictionary();
+        assert_eq!(column.values().len(), 2);
+        let decoded: Vec<_> = chunk.rows().map(|row| row.get(1).unwrap()).collect();
+        assert_eq!(
+            decoded,
+            [
+                ScalarValue::String(Some("person".into())),
+                ScalarValue::String(None),
+                ScalarValue::String(Some("person".into())),
+            ]
+        );
+        // Compacting and concatenating keep the encoding.
+        let mut compacted = chunk.clone();
+        compacted.compact();
+        assert!(matches!(
+            compacted.columns()[1].data_type(),
+            DataType::Dictionary(..)
+        ));
+        let concatenated = DataChunk::concat([chunk.clone(), chunk.clone()]);
+        assert_eq!(concatenated.len(), 6);
+        assert!(matches!(
+            concatenated.columns()[1].data_type(),
+            DataType::Dictionary(..)
+        ));
+        let schema = DataSchema::new(vec![
+            DataField::new("a".to_string(), LogicalType::Int32, false),
+            DataField::new("b".to_string(), LogicalType::String, true),
+        ]);
+        let record_batch = chunk.to_arrow_record_batch(&schema);
+        assert_eq!(record_batch.num_rows(), 3);
+        assert!(matches!(
+            record_batch.schema().field(1).data_type(),
+            DataType::Dictionary(..)
+        ));
+    }
+
+    #[test]
+    fn test_semantically_eq() {
+        let filtered = data_chunk!(
+            { true, false, true },
+            (Int32, [1, 2, 3]),
+            (Utf8, ["a", "b", "c"])
+        );
+        let mut compacted = filtered.clone();
+        compacted.compact();
+        assert_ne!(filtered, compacted);
+        assert!(filtered.semantically_eq(&compacted));
+        assert!(compacted.semantically_eq(&filtered));
+
+        let other_filtered = data_chunk!(
+            { false, true, true },
+            (Int32, [2, 1, 3]),
+            (Utf8, ["b", "a", "c"])
+        );
+        assert!(filtered.semantically_eq(&other_filtered));
+    }
+
+    #[test]
+    fn test_semantically_ne() {
+        let filtered = data_chunk!({ true, false, true }, (Int32, [1, 2, 3]));
+        // Different rows.
+        assert!(!filtered.semantically_eq(&data_chunk!((Int32, [1, 2]))));
+        // Different number of rows.
+        assert!(!filtered.semantically_eq(&data_chunk!((Int32, [1, 2, 3]))));
+        // Different number of columns.
+        assert!(!filtered.semantically_eq(&data_chunk!((Int32, [1, 3]), (Int32, [1, 3]))));
+    }
+
+    #[test]
+    fn test_get_typed() {
+        let chunk = data_chunk!(
+            { false, true, true },
+            (Int32, [Some(1), Some(2), None]),
+            (Utf8, ["a", "b", "c"])
+        );
+        let rows: Vec<_> = chunk.rows().collect();
+        assert_eq!(rows[0].get_typed::<i32>(0), Some(Some(2)));
+        assert_eq!(rows[0].get_typed::<String>(1), Some(Some("b".to_string())));
+        assert_eq!(rows[1].get_typed::<i32>(0), Some(None));
+        // Wrong type.
+        assert_eq!(rows[1].get_typed::<i64>(0), None);
+        // Out of bounds.
+        assert_eq!(rows[1].get_typed::<String>(2), None);
+    }
+
+    #[test]
+    fn test_num_bytes() {
+        let chunk = |len: i32| {
+            let ints: ArrayRef = Arc::new(arrow::array::Int32Array::from_iter_values(0..len));
+            let strings: ArrayRef = Arc::new(arrow::array::StringArray::from_iter_values(
+                (0..len).map(|i| format!("value-{i}")),
+            ));
+            DataChunk::new(vec![ints, strings])
+        };
+        let small = chunk(10);
+        let large = chunk(10_000);
+        // At least 4 bytes per integer, and 4 bytes of offset and 7 bytes of data per string.
+        assert!(small.num_bytes() >= 10 * (4 + 4 + 7));
+        assert!(large.num_bytes() >= 10_000 * (4 + 4 + 7));
+        assert!(large.num_bytes() > 100 * small.num_bytes());
+        assert!(chunk(20).num_bytes() > small.num_bytes());
+
+        // The filter is accounted for.
+        let filtered = small
+            .clone()
+            .with_filter(BooleanArray::from(vec![true; 10]));
+        assert!(filtered.num_bytes() > small.num_bytes());
+    }
 }
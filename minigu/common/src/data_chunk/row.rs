@@ -5,7 +5,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::DataChunk;
-use crate::value::{ScalarValue, ScalarValueAccessor};
+use crate::value::{FromScalarValue, Nullable, ScalarValue, ScalarValueAccessor};
 
 #[derive(Debug)]
 pub struct Rows<'a> {
@@ -53,6 +53,18 @@ pub fn get(&self, index: usize) -> Option<ScalarValue> {
         Some(column.index(self.row_index))
     }
 
+    /// Returns the value at `index` as a `T`, e.g., `i32` for an `Int32` column, without
+    /// materializing the other values of the row.
+    ///
+    /// Returns `None` if there is no column at `index`, or if its values are not of type `T`.
+    #[inline]
+    pub fn get_typed<T: FromScalarValue>(&self, index: usize) -> Option<Nullable<T>> {
+        match self.get(index)? {
+            ScalarValue::Null => Some(None),
+            value => T::from_scalar_value(value),
+        }
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.chunk.columns.len()
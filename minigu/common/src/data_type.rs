@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, LazyLock};
 
 use arrow::datatypes::{
@@ -9,7 +11,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::constants::{
-    DST_FIELD_NAME, EID_FIELD_NAME, LABEL_FIELD_NAME, SRC_FIELD_NAME, VID_FIELD_NAME,
+    DST_FIELD_NAME, EID_FIELD_NAME, LABEL_FIELD_NAME, NODES_FIELD_NAME, RELATIONSHIPS_FIELD_NAME,
+    SRC_FIELD_NAME, VID_FIELD_NAME,
 };
 
 pub(crate) struct PredefinedFields;
@@ -51,7 +54,7 @@ pub(crate) fn dst() -> ArrowFieldRef {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LogicalType {
     Int8,
     Int16,
@@ -69,6 +72,13 @@ pub enum LogicalType {
     Vertex(Vec<DataField>),
     Edge(Vec<DataField>),
     Record(Vec<DataField>),
+    /// A list of values of the element type, any of which may be null.
+    List(Box<LogicalType>),
+    /// A path of alternating vertices and edges, starting and ending with a vertex, whose vertices
+    /// have the properties of the first fields and whose edges have those of the second.
+    ///
+    /// The fields are boxed so that paths do not make every type larger.
+    Path(Box<(Vec<DataField>, Vec<DataField>)>),
     Null,
 }
 
@@ -120,6 +130,28 @@ pub fn to_arrow_data_type(&self) -> DataType {
                     .collect();
                 DataType::Struct(fields)
             }
+            LogicalType::List(element) => DataType::List(Arc::new(ArrowField::new(
+                "item",
+                element.to_arrow_data_type(),
+                true,
+            ))),
+            LogicalType::Path(path_fields) => {
+                let (vertex_fields, edge_fields) = &**path_fields;
+                // The vertices and the edges are stored in separate lists.
+                let list = |name: &str, element: LogicalType| {
+                    let element = ArrowField::new("item", element.to_arrow_data_type(), false);
+                    let list = DataType::List(Arc::new(element));
+                    Arc::new(ArrowField::new(name.to_string(), list, false))
+                };
+                let fields = [
+                    list(NODES_FIELD_NAME, LogicalType::Vertex(vertex_fields.clone())),
+                    list(
+                        RELATIONSHIPS_FIELD_NAME,
+                        LogicalType::Edge(edge_fields.clone()),
+                    ),
+                ];
+                DataType::Struct(fields.into_iter().collect())
+            }
             LogicalType::Null => DataType::Null,
         }
     }
@@ -151,6 +183,13 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             LogicalType::Record(fields) => {
                 write!(f, "record {{ {} }}", fields.iter().join(","))
             }
+            LogicalType::List(element) => write!(f, "list<{element}>"),
+            LogicalType::Path(path_fields) => write!(
+                f,
+                "path {{ vertex {{ {} }}, edge {{ {} }} }}",
+                path_fields.0.iter().join(","),
+                path_fields.1.iter().join(",")
+            ),
             LogicalType::Null => write!(f, "null"),
         }
     }
@@ -204,12 +243,27 @@ pub struct DataField {
     name: String,
     ty: LogicalType,
     nullable: bool,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    metadata: HashMap<String, String>,
 }
 
 impl DataField {
     #[inline]
     pub fn new(name: String, ty: LogicalType, nullable: bool) -> Self {
-        Self { name, ty, nullable }
+        Self {
+            name,
+            ty,
+            nullable,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Attaches `metadata` to the field, e.g., the unit or the description of its values, which is
+    /// carried over to the metadata of the arrow field.
+    #[inline]
+    pub fn with_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
     }
 
     #[inline]
@@ -227,6 +281,11 @@ pub fn is_nullable(&self) -> bool {
         self.nullable
     }
 
+    #[inline]
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
     #[inline]
     pub fn to_arrow_field(&self) -> ArrowField {
         ArrowField::new(
@@ -234,6 +293,17 @@ pub fn to_arrow_field(&self) -> ArrowField {
             self.ty.to_arrow_data_type(),
             self.nullable,
         )
+        .with_metadata(self.metadata.clone())
+    }
+}
+
+// The metadata is left out of the hash, since `HashMap` is not hashable. Equal fields still have
+// equal hashes.
+impl Hash for DataField {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.ty.hash(state);
+        self.nullable.hash(state);
     }
 }
 
@@ -0,0 +1,44 @@
+use arrow::array::{Array, ArrayRef, Datum};
+
+use crate::value::{ScalarValue, ScalarValueAccessor};
+
+#[derive(Debug, Clone)]
+pub struct DatumRef {
+    array: ArrayRef,
+    is_scalar: bool,
+}
+
+impl DatumRef {
+    pub fn new(array: ArrayRef, is_scalar: bool) -> Self {
+        Self { array, is_scalar }
+    }
+
+    #[inline]
+    pub fn as_array(&self) -> &ArrayRef {
+        &self.array
+    }
+
+    #[inline]
+    pub fn is_scalar(&self) -> bool {
+        self.is_scalar
+    }
+
+    #[inline]
+    pub fn into_array(self) -> ArrayRef {
+        self.array
+    }
+
+    /// Returns the value at row `index`. A scalar has the same value at every row.
+    #[inline]
+    pub fn value_at(&self, index: usize) -> ScalarValue {
+        let index = if self.is_scalar { 0 } else { index };
+        self.array.as_ref().index(index)
+    }
+}
+
+impl Datum for DatumRef {
+    #[inline]
+    fn get(&self) -> (&dyn Array, bool) {
+        (self.array.as_ref(), self.is_scalar)
+    }
+}
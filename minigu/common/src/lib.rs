@@ -1,9 +1,12 @@
 #![feature(impl_trait_in_assoc_type)]
 
+pub mod arithmetic;
 pub mod constants;
 pub mod data_chunk;
 pub mod data_type;
+pub mod datum;
 pub mod error;
+pub mod nulls;
 pub mod ordering;
 pub mod result_set;
 pub mod types;
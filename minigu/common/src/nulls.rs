@@ -0,0 +1,115 @@
+//! Helpers for inspecting the null bitmaps of arrays.
+//!
+//! All of them operate on logical nulls, so that arrays without a null buffer of their own (e.g.,
+//! [`NullArray`](arrow::array::NullArray) or dictionaries) are handled correctly.
+
+use arrow::array::{Array, BooleanArray};
+use arrow::buffer::BooleanBuffer;
+
+/// Returns the number of null values in `array`.
+#[inline]
+pub fn null_count(array: &dyn Array) -> usize {
+    array.logical_null_count()
+}
+
+/// Returns the number of non-null values in `array`.
+#[inline]
+pub fn valid_count(array: &dyn Array) -> usize {
+    array.len() - null_count(array)
+}
+
+/// Returns a mask that is `true` where `array` is not null.
+///
+/// The mask itself has no nulls.
+pub fn is_not_null_mask(array: &dyn Array) -> BooleanArray {
+    let values = match array.logical_nulls() {
+        Some(nulls) => nulls.into_inner(),
+        None => BooleanBuffer::new_set(array.len()),
+    };
+    BooleanArray::new(values, None)
+}
+
+/// Returns a mask that is `true` where `array` is null.
+///
+/// The mask itself has no nulls.
+pub fn is_null_mask(array: &dyn Array) -> BooleanArray {
+    let values = match array.logical_nulls() {
+        Some(nulls) => !nulls.inner(),
+        None => BooleanBuffer::new_unset(array.len()),
+    };
+    BooleanArray::new(values, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{ArrayRef, Int32Array, NullArray, StringArray, create_array};
+
+    use super::*;
+
+    #[test]
+    fn test_no_nulls() {
+        let array: ArrayRef = create_array!(Int32, [1, 2, 3]);
+        assert_eq!(null_count(&array), 0);
+        assert_eq!(valid_count(&array), 3);
+        assert_eq!(
+            is_not_null_mask(&array),
+            BooleanArray::from(vec![true, true, true])
+        );
+        assert_eq!(
+            is_null_mask(&array),
+            BooleanArray::from(vec![false, false, false])
+        );
+    }
+
+    #[test]
+    fn test_some_nulls() {
+        let array = StringArray::from(vec![None, Some("a"), None, Some("b")]);
+        assert_eq!(null_count(&array), 2);
+        assert_eq!(valid_count(&array), 2);
+        assert_eq!(
+            is_not_null_mask(&array),
+            BooleanArray::from(vec![false, true, false, true])
+        );
+        assert_eq!(
+            is_null_mask(&array),
+            BooleanArray::from(vec![true, false, true, false])
+        );
+    }
+
+    #[test]
+    fn test_all_nulls() {
+        let array = Int32Array::from(vec![None, None]);
+        assert_eq!(null_count(&array), 2);
+        assert_eq!(valid_count(&array), 0);
+        assert_eq!(is_not_null_mask(&array), BooleanArray::from(vec![false; 2]));
+        assert_eq!(is_null_mask(&array), BooleanArray::from(vec![true; 2]));
+    }
+
+    #[test]
+    fn test_null_array() {
+        // `NullArray` has no null buffer, but all of its values are logically null.
+        let array = NullArray::new(3);
+        assert_eq!(null_count(&array), 3);
+        assert_eq!(is_not_null_mask(&array), BooleanArray::from(vec![false; 3]));
+        assert_eq!(is_null_mask(&array), BooleanArray::from(vec![true; 3]));
+    }
+
+    #[test]
+    fn test_sliced() {
+        let array = Int32Array::from(vec![None, Some(1), None, Some(2), Some(3)]);
+        let sliced = array.slice(1, 3);
+        assert_eq!(null_count(&sliced), 1);
+        assert_eq!(
+            is_not_null_mask(&sliced),
+            BooleanArray::from(vec![true, false, true])
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let array = Int32Array::from(Vec::<i32>::new());
+        assert_eq!(null_count(&array), 0);
+        assert!(is_not_null_mask(&array).is_empty());
+        assert!(is_null_mask(&array).is_empty());
+    }
+}
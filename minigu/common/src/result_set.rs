@@ -1,7 +1,14 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use arrow::array::UInt64Array;
+use arrow::compute;
+use itertools::Itertools;
+
 use crate::data_chunk::DataChunk;
+use crate::data_chunk::row::OwnedRow;
+use crate::data_type::DataSchemaRef;
+use crate::value::ScalarValueAccessor;
 
 /// Position of a DataChunk within a ResultSet
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,12 +22,18 @@ pub struct DataPos {
 }
 
 /// A collection of DataChunks representing factorized query results
+///
+/// The tuples of a result set are the Cartesian product of the current row of every flat chunk
+/// and the rows of every unflat chunk, each repeated `factor` times. Their columns are those of
+/// the chunks, in order.
 #[derive(Debug, Clone, Default)]
 pub struct ResultSet {
     /// factor for tuple counts
     pub factor: u64,
     /// Vector of DataChunks containing the actual data
     data_chunks: Vec<Arc<DataChunk>>,
+    /// Schema of the columns of all chunks, in order. Cleared whenever the chunks change.
+    schema: Option<DataSchemaRef>,
 }
 
 impl ResultSet {
@@ -29,9 +42,125 @@ pub fn new() -> Self {
         Self {
             factor: 1,
             data_chunks: Vec::new(),
+            schema: None,
+        }
+    }
+
+    /// Sets the schema of the columns of the result set.
+    ///
+    /// Factorized operators do not maintain schemas, so this is meant to be called on their
+    /// output, by whoever knows what the columns are. Adding or removing chunks clears the schema.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schema does not have one field per column.
+    #[inline]
+    pub fn with_schema(mut self, schema: DataSchemaRef) -> Self {
+        assert_eq!(
+            schema.size(),
+            self.num_columns(),
+            "schema must have one field per column"
+        );
+        self.schema = Some(schema);
+        self
+    }
+
+    #[inline]
+    pub fn schema(&self) -> Option<&DataSchemaRef> {
+        self.schema.as_ref()
+    }
+
+    /// Returns the total number of columns of the chunks.
+    #[inline]
+    pub fn num_columns(&self) -> usize {
+        self.data_chunks.iter().map(|c| c.columns().len()).sum()
+    }
+
+    /// Returns the number of tuples of the result set, i.e., the number of rows of
+    /// [`ResultSet::rows`].
+    pub fn num_tuples(&self) -> u64 {
+        if self.data_chunks.is_empty() {
+            return 0;
+        }
+        let all = (0..self.data_chunks.len()).map(DataChunkPos).collect();
+        self.get_num_tuples(&all)
+    }
+
+    /// Returns an iterator over the tuples of the result set, flattening its factorized
+    /// representation. Tuples are ordered by the rows of the chunks, the last chunk varying the
+    /// fastest.
+    pub fn rows(&self) -> impl Iterator<Item = OwnedRow> + '_ {
+        self.row_indices().map(move |indices| {
+            let values = self
+                .data_chunks
+                .iter()
+                .zip(indices)
+                .flat_map(|(chunk, index)| chunk.columns().iter().map(move |c| c.index(index)))
+                .collect();
+            OwnedRow::new(values)
+        })
+    }
+
+    /// Flattens the result set into a single chunk holding the tuples of [`ResultSet::rows`], e.g.,
+    /// to be displayed along with the chunks of non-factorized results.
+    ///
+    /// Returns `None` if the result set has no chunk.
+    pub fn to_data_chunk(&self) -> Option<DataChunk> {
+        if self.data_chunks.is_empty() {
+            return None;
+        }
+        let tuples = self.row_indices().collect_vec();
+        let columns = self
+            .data_chunks
+            .iter()
+            .enumerate()
+            .flat_map(|(pos, chunk)| {
+                let indices = UInt64Array::from_iter_values(tuples.iter().map(|t| t[pos] as u64));
+                chunk
+                    .columns()
+                    .iter()
+                    .map(move |c| compute::take(c, &indices, None).expect("take should succeed"))
+            })
+            .collect();
+        Some(DataChunk::new(columns))
+    }
+
+    /// Returns a result set whose tuples are the Cartesian product of the tuples of `self` and
+    /// `other`, with the columns of `self` first. The chunks are shared, not copied, and are not
+    /// flattened.
+    pub fn cross_product(&self, other: &ResultSet) -> ResultSet {
+        let data_chunks = self
+            .data_chunks
+            .iter()
+            .chain(&other.data_chunks)
+            .cloned()
+            .collect();
+        Self {
+            factor: self.factor * other.factor,
+            data_chunks,
+            schema: None,
         }
     }
 
+    /// Returns, for each tuple, the index of its row in every chunk.
+    fn row_indices(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        let factor = self.factor as usize;
+        // The product of no chunks would otherwise be a single empty tuple.
+        let chunks = (!self.data_chunks.is_empty()).then(|| {
+            self.data_chunks
+                .iter()
+                .map(|chunk| match chunk.cur_idx() {
+                    Some(index) => vec![index],
+                    None => chunk.rows().map(|row| row.row_index()).collect(),
+                })
+                .multi_cartesian_product()
+        });
+        chunks
+            .into_iter()
+            .flatten()
+            .flat_map(move |tuple| std::iter::repeat_n(tuple, factor))
+    }
+
     #[inline]
     pub fn is_chunk_flat(&self, pos: DataChunkPos) -> bool {
         if let Some(chunk) = self.data_chunks.get(pos.0) {
@@ -76,12 +205,14 @@ pub fn with_capacity(capacity: usize) -> Self {
         Self {
             factor: 1,
             data_chunks: Vec::with_capacity(capacity),
+            schema: None,
         }
     }
 
     #[inline]
     pub fn push(&mut self, data_chunk: DataChunk) {
         self.data_chunks.push(Arc::new(data_chunk));
+        self.schema = None;
     }
 
     #[inline]
@@ -152,6 +283,7 @@ pub fn iter(&self) -> impl Iterator<Item = &Arc<DataChunk>> {
     pub fn remove_chunk(&mut self, pos: DataChunkPos) {
         if pos.0 < self.data_chunks.len() {
             self.data_chunks.swap_remove(pos.0);
+            self.schema = None;
         }
     }
 
@@ -211,6 +343,7 @@ fn from_iter<T: IntoIterator<Item = DataChunk>>(iter: T) -> Self {
         Self {
             factor: 1,
             data_chunks,
+            schema: None,
         }
     }
 }
@@ -221,6 +354,7 @@ mod tests {
 
     use super::*;
     use crate::data_chunk;
+    use crate::data_type::{DataField, DataSchema, LogicalType};
 
     #[test]
     fn test_result_set_basic() {
@@ -273,6 +407,82 @@ fn test_get_num_tuples() {
         );
     }
 
+    /// A flat chunk at row 1, and two unflat chunks, the first of which is filtered.
+    fn factorized() -> ResultSet {
+        let mut flat = data_chunk!((Int32, [1, 2]), (Utf8, ["x", "y"]));
+        flat.set_cur_idx(Some(1));
+        let mut unflat1 = data_chunk!({ true, false, true }, (Int32, [10, 20, 30]));
+        unflat1.set_unflat();
+        let mut unflat2 = data_chunk!((Utf8, ["a", "b"]));
+        unflat2.set_unflat();
+        result_set!(flat, unflat1, unflat2)
+    }
+
+    #[test]
+    fn test_rows() {
+        let result_set = factorized();
+        let rows = result_set.rows().collect_vec();
+        let expected = [(10, "a"), (10, "b"), (30, "a"), (30, "b")]
+            .into_iter()
+            .map(|(i, s)| OwnedRow::new(vec![2i32.into(), "y".into(), i.into(), s.into()]))
+            .collect_vec();
+        assert_eq!(rows, expected);
+        let all = (0..3).map(DataChunkPos).collect();
+        assert_eq!(rows.len() as u64, result_set.get_num_tuples(&all));
+    }
+
+    #[test]
+    fn test_rows_with_factor() {
+        let mut result_set = factorized();
+        result_set.factor = 2;
+        assert_eq!(result_set.rows().count(), 8);
+        assert_eq!(result_set.num_tuples(), 8);
+        assert!(ResultSet::new().rows().next().is_none());
+        assert_eq!(ResultSet::new().num_tuples(), 0);
+    }
+
+    #[test]
+    fn test_to_data_chunk() {
+        let chunk = factorized().to_data_chunk().unwrap();
+        let expected = data_chunk!(
+            (Int32, [2, 2, 2, 2]),
+            (Utf8, ["y", "y", "y", "y"]),
+            (Int32, [10, 10, 30, 30]),
+            (Utf8, ["a", "b", "a", "b"])
+        );
+        assert_eq!(chunk, expected);
+        assert!(ResultSet::new().to_data_chunk().is_none());
+    }
+
+    #[test]
+    fn test_cross_product() {
+        let mut other = result_set!(data_chunk!((Int32, [7])));
+        other.factor = 3;
+        let product = factorized().cross_product(&other);
+        assert_eq!(product.num_data_chunks(), 4);
+        assert_eq!(product.factor, 3);
+        assert!(Arc::ptr_eq(
+            product.get_data_chunk(DataChunkPos(3)).unwrap(),
+            other.get_data_chunk(DataChunkPos(0)).unwrap()
+        ));
+        assert_eq!(product.rows().count(), 4 * 3);
+        assert!(product.rows().all(|row| row.get(4) == Some(&7i32.into())));
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = Arc::new(DataSchema::new(vec![
+            DataField::new("a".into(), LogicalType::Int32, false),
+            DataField::new("b".into(), LogicalType::String, false),
+            DataField::new("c".into(), LogicalType::Int32, false),
+            DataField::new("d".into(), LogicalType::String, false),
+        ]));
+        let mut result_set = factorized().with_schema(schema.clone());
+        assert_eq!(result_set.schema(), Some(&schema));
+        result_set.remove_chunk(DataChunkPos(2));
+        assert_eq!(result_set.schema(), None);
+    }
+
     #[test]
     fn test_from_iter() {
         let chunks = vec![data_chunk!((Int32, [1, 2])), data_chunk!((Int32, [3, 4]))];
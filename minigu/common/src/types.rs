@@ -59,6 +59,24 @@ pub fn new(label_id: LabelId, property_id: PropertyId) -> Self {
     }
 }
 
+/// Uses (LabelId, PropertyId) to uniquely identify property indices, which map the values of a
+/// property of the vertices of a label to the vertices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PropertyIndexKey {
+    pub label_id: LabelId,
+    pub property_id: PropertyId,
+}
+
+impl PropertyIndexKey {
+    #[inline]
+    pub fn new(label_id: LabelId, property_id: PropertyId) -> Self {
+        Self {
+            label_id,
+            property_id,
+        }
+    }
+}
+
 /// Vector distance metrics for similarity search
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum VectorMetric {
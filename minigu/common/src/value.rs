@@ -1,24 +1,37 @@
+use std::fmt;
 use std::hash::Hash;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use arrow::array::{
     Array, ArrayRef, AsArray, BooleanArray, FixedSizeListArray, Float32Array, Float64Array,
-    Int8Array, Int16Array, Int32Array, Int64Array, NullArray, NullBufferBuilder, StringArray,
-    UInt8Array, UInt16Array, UInt32Array, UInt64Array,
+    Int8Array, Int16Array, Int32Array, Int64Array, ListArray, NullArray, NullBufferBuilder,
+    StringArray, StructArray, UInt8Array, UInt16Array, UInt32Array, UInt64Array,
+    downcast_dictionary_array, new_null_array,
 };
-use arrow::datatypes::DataType;
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{DataType, FieldRef, UInt32Type, UInt64Type};
+use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
+use crate::constants::{EID_FIELD_NAME, NODES_FIELD_NAME, VID_FIELD_NAME};
+use crate::data_type::{DataField, LogicalType};
 use crate::types::{EdgeId, LabelId, VertexId};
 
 const EPSILON: f64 = 1e-10;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
 pub enum ConversionError {
+    #[error("null value")]
     NullValue,
+    #[error("incompatible type")]
     IncompatibleType,
+    #[error("value out of range")]
     Overflow,
+    #[error("failed to parse {0:?}")]
     ParseError(String),
 }
 
@@ -800,6 +813,10 @@ pub fn to_bool(&self) -> Result<bool, ConversionError> {
     }
 
     // Convert to String
+    //
+    // Unlike the `Display` implementation, this returns the bare string of a string value and fails
+    // on nulls.
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> Result<String, ConversionError> {
         match self {
             ScalarValue::Int8(Some(v)) => Ok(v.to_string()),
@@ -904,10 +921,13 @@ pub enum ScalarValue {
     },
     Vertex(Nullable<VertexValue>),
     Edge(Nullable<EdgeValue>),
+    List(Nullable<Vec<ScalarValue>>),
+    /// A record of named fields, in the order of the fields of its [`LogicalType::Record`].
+    Record(Nullable<Vec<PropertyValue>>),
+    Path(Nullable<PathValue>),
 }
 
 impl ScalarValue {
-    #[allow(unused)]
     pub fn to_scalar_array(&self) -> ArrayRef {
         match self {
             ScalarValue::Null => Arc::new(NullArray::new(1)),
@@ -961,8 +981,70 @@ pub fn to_scalar_array(&self) -> ArrayRef {
                     }
                 }
             }
-            ScalarValue::Vertex(value) => todo!(),
-            ScalarValue::Edge(_value) => todo!(),
+            ScalarValue::Vertex(_)
+            | ScalarValue::Edge(_)
+            | ScalarValue::List(_)
+            | ScalarValue::Record(_)
+            | ScalarValue::Path(_) => build_array(&self.inferred_type(), vec![self.clone()]),
+        }
+    }
+
+    /// Returns the type of the value, as inferred from the value alone.
+    ///
+    /// The properties of vertices and edges, and the fields of records, are nullable. Lists and
+    /// paths take the types of their elements from their first non-null ones. Untyped nulls,
+    /// including nested ones, are of type [`LogicalType::Null`].
+    fn inferred_type(&self) -> LogicalType {
+        let fields = |properties: &[PropertyValue]| {
+            properties
+                .iter()
+                .map(|p| DataField::new(p.name.clone(), p.value.inferred_type(), true))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            ScalarValue::Null => LogicalType::Null,
+            ScalarValue::Boolean(_) => LogicalType::Boolean,
+            ScalarValue::Int8(_) => LogicalType::Int8,
+            ScalarValue::Int16(_) => LogicalType::Int16,
+            ScalarValue::Int32(_) => LogicalType::Int32,
+            ScalarValue::Int64(_) => LogicalType::Int64,
+            ScalarValue::UInt8(_) => LogicalType::UInt8,
+            ScalarValue::UInt16(_) => LogicalType::UInt16,
+            ScalarValue::UInt32(_) => LogicalType::UInt32,
+            ScalarValue::UInt64(_) => LogicalType::UInt64,
+            ScalarValue::Float32(_) => LogicalType::Float32,
+            ScalarValue::Float64(_) => LogicalType::Float64,
+            ScalarValue::String(_) => LogicalType::String,
+            ScalarValue::Vector { dimension, .. } => LogicalType::Vector(*dimension),
+            ScalarValue::Vertex(v) => LogicalType::Vertex(
+                v.as_ref()
+                    .map(|v| fields(&v.properties))
+                    .unwrap_or_default(),
+            ),
+            ScalarValue::Edge(e) => LogicalType::Edge(
+                e.as_ref()
+                    .map(|e| fields(&e.properties))
+                    .unwrap_or_default(),
+            ),
+            ScalarValue::Record(r) => {
+                LogicalType::Record(r.as_deref().map(fields).unwrap_or_default())
+            }
+            ScalarValue::List(values) => {
+                let element = values
+                    .iter()
+                    .flatten()
+                    .find(|v| !v.is_null())
+                    .map_or(LogicalType::Null, ScalarValue::inferred_type);
+                LogicalType::List(Box::new(element))
+            }
+            ScalarValue::Path(p) => {
+                let vertex = p.as_ref().and_then(|p| p.vertices.first());
+                let edge = p.as_ref().and_then(|p| p.edges.first());
+                LogicalType::Path(Box::new((
+                    vertex.map(|v| fields(&v.properties)).unwrap_or_default(),
+                    edge.map(|e| fields(&e.properties)).unwrap_or_default(),
+                )))
+            }
         }
     }
 
@@ -1119,6 +1201,137 @@ pub struct EdgeValue {
     properties: Vec<PropertyValue>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PathValue {
+    vertices: Vec<VertexValue>,
+    edges: Vec<EdgeValue>,
+}
+
+impl PropertyValue {
+    #[inline]
+    pub fn new(name: String, value: ScalarValue) -> Self {
+        Self { name, value }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn value(&self) -> &ScalarValue {
+        &self.value
+    }
+}
+
+impl VertexValue {
+    /// Creates a vertex value, whose properties must be in the order of the fields of its
+    /// [`LogicalType::Vertex`].
+    #[inline]
+    pub fn new(id: VertexId, label: LabelId, properties: Vec<PropertyValue>) -> Self {
+        Self {
+            id,
+            label,
+            properties,
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> VertexId {
+        self.id
+    }
+
+    #[inline]
+    pub fn label(&self) -> LabelId {
+        self.label
+    }
+
+    #[inline]
+    pub fn properties(&self) -> &[PropertyValue] {
+        &self.properties
+    }
+}
+
+impl EdgeValue {
+    /// Creates an edge value, whose properties must be in the order of the fields of its
+    /// [`LogicalType::Edge`].
+    #[inline]
+    pub fn new(
+        id: EdgeId,
+        src: VertexId,
+        dst: VertexId,
+        label: LabelId,
+        properties: Vec<PropertyValue>,
+    ) -> Self {
+        Self {
+            id,
+            src,
+            dst,
+            label,
+            properties,
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> EdgeId {
+        self.id
+    }
+
+    #[inline]
+    pub fn src(&self) -> VertexId {
+        self.src
+    }
+
+    #[inline]
+    pub fn dst(&self) -> VertexId {
+        self.dst
+    }
+
+    #[inline]
+    pub fn label(&self) -> LabelId {
+        self.label
+    }
+
+    #[inline]
+    pub fn properties(&self) -> &[PropertyValue] {
+        &self.properties
+    }
+}
+
+impl PathValue {
+    /// Creates a path that goes through `vertices` in order, the `i`-th edge connecting the `i`-th
+    /// vertex to the next one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one more vertex than edges.
+    #[inline]
+    pub fn new(vertices: Vec<VertexValue>, edges: Vec<EdgeValue>) -> Self {
+        assert_eq!(
+            vertices.len(),
+            edges.len() + 1,
+            "a path should have one more vertex than edges"
+        );
+        Self { vertices, edges }
+    }
+
+    /// Returns the number of edges of the path.
+    #[inline]
+    pub fn length(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    pub fn vertices(&self) -> &[VertexValue] {
+        &self.vertices
+    }
+
+    #[inline]
+    pub fn edges(&self) -> &[EdgeValue] {
+        &self.edges
+    }
+}
+
 macro_rules! for_each_non_null_variant {
     ($m:ident) => {
         $m!(boolean, bool, Boolean);
@@ -1135,6 +1348,9 @@ macro_rules! for_each_non_null_variant {
         $m!(string, String, String);
         $m!(vertex_value, VertexValue, Vertex);
         $m!(edge_value, EdgeValue, Edge);
+        $m!(list, Vec<ScalarValue>, List);
+        $m!(record, Vec<PropertyValue>, Record);
+        $m!(path, PathValue, Path);
     };
 }
 
@@ -1237,6 +1453,25 @@ pub fn [<into_$name>](self) -> Option<Nullable<$ty>> {
 
 for_each_non_null_variant!(impl_into_for_variant);
 
+/// A type of the values of a variant of [`ScalarValue`], which can be extracted from it.
+pub trait FromScalarValue: Sized {
+    /// Downcasts `value` to owned `Nullable<Self>`, returning `None` if it is of another variant.
+    fn from_scalar_value(value: ScalarValue) -> Option<Nullable<Self>>;
+}
+
+macro_rules! impl_from_scalar_value_for_variant {
+    ($name:ident, $ty:ty, $_:ident) => {
+        impl FromScalarValue for $ty {
+            #[inline]
+            fn from_scalar_value(value: ScalarValue) -> Option<Nullable<Self>> {
+                pastey::paste! { value.[<into_$name>]() }
+            }
+        }
+    };
+}
+
+for_each_non_null_variant!(impl_from_scalar_value_for_variant);
+
 impl ScalarValue {
     #[inline]
     pub fn new_vector(dimension: usize, value: Nullable<VectorValue>) -> Self {
@@ -1249,6 +1484,236 @@ pub fn new_vector(dimension: usize, value: Nullable<VectorValue>) -> Self {
         }
         ScalarValue::Vector { dimension, value }
     }
+
+    /// Returns `true` if the value is a null, whether typed or not.
+    pub fn is_null(&self) -> bool {
+        match self {
+            ScalarValue::Null => true,
+            ScalarValue::Boolean(v) => v.is_none(),
+            ScalarValue::Int8(v) => v.is_none(),
+            ScalarValue::Int16(v) => v.is_none(),
+            ScalarValue::Int32(v) => v.is_none(),
+            ScalarValue::Int64(v) => v.is_none(),
+            ScalarValue::UInt8(v) => v.is_none(),
+            ScalarValue::UInt16(v) => v.is_none(),
+            ScalarValue::UInt32(v) => v.is_none(),
+            ScalarValue::UInt64(v) => v.is_none(),
+            ScalarValue::Float32(v) => v.is_none(),
+            ScalarValue::Float64(v) => v.is_none(),
+            ScalarValue::String(v) => v.is_none(),
+            ScalarValue::Vector { value, .. } => value.is_none(),
+            ScalarValue::Vertex(v) => v.is_none(),
+            ScalarValue::Edge(v) => v.is_none(),
+            ScalarValue::List(v) => v.is_none(),
+            ScalarValue::Record(v) => v.is_none(),
+            ScalarValue::Path(v) => v.is_none(),
+        }
+    }
+
+    /// Returns the null value of `ty`, or `None` if `ty` has no scalar representation.
+    pub fn null_of(ty: &LogicalType) -> Option<Self> {
+        let null = match ty {
+            LogicalType::Int8 => ScalarValue::Int8(None),
+            LogicalType::Int16 => ScalarValue::Int16(None),
+            LogicalType::Int32 => ScalarValue::Int32(None),
+            LogicalType::Int64 => ScalarValue::Int64(None),
+            LogicalType::UInt8 => ScalarValue::UInt8(None),
+            LogicalType::UInt16 => ScalarValue::UInt16(None),
+            LogicalType::UInt32 => ScalarValue::UInt32(None),
+            LogicalType::UInt64 => ScalarValue::UInt64(None),
+            LogicalType::Float32 => ScalarValue::Float32(None),
+            LogicalType::Float64 => ScalarValue::Float64(None),
+            LogicalType::Boolean => ScalarValue::Boolean(None),
+            LogicalType::String => ScalarValue::String(None),
+            LogicalType::Vector(dimension) => ScalarValue::new_vector(*dimension, None),
+            LogicalType::List(_) => ScalarValue::List(None),
+            LogicalType::Record(_) => ScalarValue::Record(None),
+            LogicalType::Path(..) => ScalarValue::Path(None),
+            LogicalType::Null => ScalarValue::Null,
+            LogicalType::Vertex(_) | LogicalType::Edge(_) => return None,
+        };
+        Some(null)
+    }
+
+    /// Parses `s` as a value of type `ty`. This is the inverse of the `Display` implementation,
+    /// i.e., `ScalarValue::parse(&format!("{v}"), ty)` returns `v` for every value `v` of type
+    /// `ty`.
+    ///
+    /// `NULL` (case-insensitive) is parsed as the null value of `ty`. Strings must be
+    /// single-quoted, with quotes inside them doubled, so that they can be told apart from
+    /// nulls. Vectors are written as `[x, y, ...]`. Vertices, edges, records, lists and paths
+    /// cannot be parsed.
+    pub fn parse(s: &str, ty: &LogicalType) -> Result<Self, ConversionError> {
+        if s.eq_ignore_ascii_case("null") {
+            return Self::null_of(ty).ok_or(ConversionError::IncompatibleType);
+        }
+        let value = match ty {
+            LogicalType::Int8 => ScalarValue::Int8(Some(parse_int(s)?)),
+            LogicalType::Int16 => ScalarValue::Int16(Some(parse_int(s)?)),
+            LogicalType::Int32 => ScalarValue::Int32(Some(parse_int(s)?)),
+            LogicalType::Int64 => ScalarValue::Int64(Some(parse_int(s)?)),
+            LogicalType::UInt8 => ScalarValue::UInt8(Some(parse_int(s)?)),
+            LogicalType::UInt16 => ScalarValue::UInt16(Some(parse_int(s)?)),
+            LogicalType::UInt32 => ScalarValue::UInt32(Some(parse_int(s)?)),
+            LogicalType::UInt64 => ScalarValue::UInt64(Some(parse_int(s)?)),
+            LogicalType::Float32 => ScalarValue::Float32(Some(parse_float(s)?)),
+            LogicalType::Float64 => ScalarValue::Float64(Some(parse_float(s)?)),
+            LogicalType::Boolean => {
+                let value = if s.eq_ignore_ascii_case("true") {
+                    true
+                } else if s.eq_ignore_ascii_case("false") {
+                    false
+                } else {
+                    return Err(ConversionError::ParseError(s.to_string()));
+                };
+                ScalarValue::Boolean(Some(value))
+            }
+            LogicalType::String => ScalarValue::String(Some(unquote(s)?)),
+            LogicalType::Vector(dimension) => {
+                let data = s
+                    .strip_prefix('[')
+                    .and_then(|s| s.strip_suffix(']'))
+                    .ok_or_else(|| ConversionError::ParseError(s.to_string()))?;
+                let data: Vec<F32> = if data.trim().is_empty() {
+                    vec![]
+                } else {
+                    data.split(',')
+                        .map(|x| parse_float(x.trim()))
+                        .collect::<Result<_, _>>()?
+                };
+                let value =
+                    VectorValue::new(data, *dimension).map_err(ConversionError::ParseError)?;
+                ScalarValue::new_vector(*dimension, Some(value))
+            }
+            LogicalType::Null
+            | LogicalType::Vertex(_)
+            | LogicalType::Edge(_)
+            | LogicalType::Record(_)
+            | LogicalType::List(_)
+            | LogicalType::Path(..) => return Err(ConversionError::IncompatibleType),
+        };
+        Ok(value)
+    }
+}
+
+fn parse_int<T>(s: &str) -> Result<T, ConversionError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    s.parse().map_err(|e: ParseIntError| match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ConversionError::Overflow,
+        _ => ConversionError::ParseError(s.to_string()),
+    })
+}
+
+fn parse_float<T: FromStr>(s: &str) -> Result<OrderedFloat<T>, ConversionError> {
+    s.parse()
+        .map(OrderedFloat)
+        .map_err(|_| ConversionError::ParseError(s.to_string()))
+}
+
+/// Removes the quotes around `s` and undoubles the quotes inside it.
+fn unquote(s: &str) -> Result<String, ConversionError> {
+    let error = || ConversionError::ParseError(s.to_string());
+    let inner = s
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .ok_or_else(error)?;
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\'' && chars.next() != Some('\'') {
+            return Err(error());
+        }
+        unquoted.push(c);
+    }
+    Ok(unquoted)
+}
+
+/// Formats the value such that it can be parsed back with [`ScalarValue::parse`].
+///
+/// Floats are written in the shortest form that parses back to the same value, and strings are
+/// single-quoted. Every null is written as `NULL`.
+impl fmt::Display for ScalarValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalarValue::Null => write!(f, "NULL"),
+            ScalarValue::Boolean(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Int8(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Int16(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Int32(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Int64(Some(v)) => write!(f, "{v}"),
+            ScalarValue::UInt8(Some(v)) => write!(f, "{v}"),
+            ScalarValue::UInt16(Some(v)) => write!(f, "{v}"),
+            ScalarValue::UInt32(Some(v)) => write!(f, "{v}"),
+            ScalarValue::UInt64(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Float32(Some(v)) => write!(f, "{v}"),
+            ScalarValue::Float64(Some(v)) => write!(f, "{v}"),
+            ScalarValue::String(Some(v)) => write!(f, "'{}'", v.replace('\'', "''")),
+            ScalarValue::Vector { value: Some(v), .. } => {
+                write!(f, "[{}]", v.data().iter().join(", "))
+            }
+            ScalarValue::Vertex(Some(v)) => write_vertex(f, v),
+            ScalarValue::Edge(Some(e)) => write_edge(f, e),
+            ScalarValue::List(Some(values)) => write!(f, "[{}]", values.iter().join(", ")),
+            ScalarValue::Record(Some(fields)) => {
+                let fields = fields.iter().map(|p| format!("{}: {}", p.name, p.value));
+                write!(f, "record {{ {} }}", fields.format(", "))
+            }
+            ScalarValue::Path(Some(p)) => {
+                // The vertices and the edges are written in the order they are gone through.
+                write!(f, "path [")?;
+                write_vertex(f, &p.vertices[0])?;
+                for (e, v) in p.edges.iter().zip(&p.vertices[1..]) {
+                    write!(f, ", ")?;
+                    write_edge(f, e)?;
+                    write!(f, ", ")?;
+                    write_vertex(f, v)?;
+                }
+                write!(f, "]")
+            }
+            ScalarValue::Boolean(None)
+            | ScalarValue::Int8(None)
+            | ScalarValue::Int16(None)
+            | ScalarValue::Int32(None)
+            | ScalarValue::Int64(None)
+            | ScalarValue::UInt8(None)
+            | ScalarValue::UInt16(None)
+            | ScalarValue::UInt32(None)
+            | ScalarValue::UInt64(None)
+            | ScalarValue::Float32(None)
+            | ScalarValue::Float64(None)
+            | ScalarValue::String(None)
+            | ScalarValue::Vector { value: None, .. }
+            | ScalarValue::Vertex(None)
+            | ScalarValue::Edge(None)
+            | ScalarValue::List(None)
+            | ScalarValue::Record(None)
+            | ScalarValue::Path(None) => write!(f, "NULL"),
+        }
+    }
+}
+
+fn write_vertex(f: &mut fmt::Formatter<'_>, v: &VertexValue) -> fmt::Result {
+    write!(f, "vertex {{ id: {}, label: {}", v.id, v.label)?;
+    write_properties(f, &v.properties)?;
+    write!(f, " }}")
+}
+
+fn write_edge(f: &mut fmt::Formatter<'_>, e: &EdgeValue) -> fmt::Result {
+    write!(
+        f,
+        "edge {{ id: {}, src: {}, dst: {}, label: {}",
+        e.id, e.src, e.dst, e.label
+    )?;
+    write_properties(f, &e.properties)?;
+    write!(f, " }}")
+}
+
+fn write_properties(f: &mut fmt::Formatter<'_>, properties: &[PropertyValue]) -> fmt::Result {
+    properties
+        .iter()
+        .try_for_each(|p| write!(f, ", {}: {}", p.name, p.value))
 }
 
 pub trait ScalarValueAccessor {
@@ -1344,15 +1809,433 @@ fn index(&self, index: usize) -> ScalarValue {
                     }
                 }
             }
+            DataType::List(_) => {
+                let array = self.as_list::<i32>();
+                let values = array.is_valid(index).then(|| {
+                    let values = array.value(index);
+                    (0..values.len()).map(|i| values.index(i)).collect()
+                });
+                ScalarValue::List(values)
+            }
+            DataType::Struct(fields) => {
+                let array = self.as_struct();
+                let properties = |offset: usize| {
+                    fields[offset..]
+                        .iter()
+                        .zip(&array.columns()[offset..])
+                        .map(|(field, column)| PropertyValue {
+                            name: field.name().clone(),
+                            value: column.as_ref().index(index),
+                        })
+                        .collect::<Vec<_>>()
+                };
+                let id = |i: usize| array.column(i).as_primitive::<UInt64Type>().value(index);
+                let label = |i: usize| {
+                    let label = array.column(i).as_primitive::<UInt32Type>().value(index);
+                    LabelId::new(label).expect("label id should be non-zero")
+                };
+                match fields.first().map(|f| f.name().as_str()) {
+                    Some(NODES_FIELD_NAME) => {
+                        let elements = |i: usize| {
+                            let elements = array.column(i).as_list::<i32>().value(index);
+                            (0..elements.len())
+                                .map(|j| elements.index(j))
+                                .collect::<Vec<_>>()
+                        };
+                        ScalarValue::Path(array.is_valid(index).then(|| {
+                            let vertices = elements(0)
+                                .into_iter()
+                                .map(|v| v.into_vertex_value().flatten())
+                                .collect::<Option<_>>()
+                                .expect("vertices of a path should not be null");
+                            let edges = elements(1)
+                                .into_iter()
+                                .map(|e| e.into_edge_value().flatten())
+                                .collect::<Option<_>>()
+                                .expect("edges of a path should not be null");
+                            PathValue { vertices, edges }
+                        }))
+                    }
+                    Some(VID_FIELD_NAME) => {
+                        ScalarValue::Vertex(array.is_valid(index).then(|| VertexValue {
+                            id: id(0),
+                            label: label(1),
+                            properties: properties(2),
+                        }))
+                    }
+                    Some(EID_FIELD_NAME) => {
+                        ScalarValue::Edge(array.is_valid(index).then(|| EdgeValue {
+                            id: id(0),
+                            label: label(1),
+                            src: id(2),
+                            dst: id(3),
+                            properties: properties(4),
+                        }))
+                    }
+                    _ => ScalarValue::Record(array.is_valid(index).then(|| properties(0))),
+                }
+            }
+            // Dictionary-encoded columns hold the values of their value type, e.g., `String`s.
+            DataType::Dictionary(..) => {
+                let array: &dyn Array = self;
+                downcast_dictionary_array! {
+                    array => match array.key(index) {
+                        Some(key) => array.values().as_ref().index(key),
+                        // A null key has no value to take the type of the null from.
+                        None => new_null_array(array.values().data_type(), 1).as_ref().index(0),
+                    },
+                    ty => unreachable!("{ty} is not a dictionary type"),
+                }
+            }
             _ => todo!(),
         }
     }
 }
 
+impl ScalarValue {
+    /// Returns the value at `index` of `array`. Nulls are returned as typed nulls, e.g.,
+    /// `Int32(None)` for an `Int32` array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, or if the type of `array` has no scalar representation.
+    #[inline]
+    pub fn from_array(array: &dyn Array, index: usize) -> Self {
+        array.index(index)
+    }
+
+    /// Creates a builder of an array of type `ty`, with space for `capacity` values.
+    ///
+    /// This is the inverse of [`ScalarValue::from_array`].
+    #[inline]
+    pub fn to_array_builder(ty: &LogicalType, capacity: usize) -> ScalarArrayBuilder {
+        ScalarArrayBuilder {
+            ty: ty.clone(),
+            values: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns `true` if the value can be stored in an array of type `ty`. Untyped nulls can be
+    /// stored in arrays of any type.
+    pub fn is_of_type(&self, ty: &LogicalType) -> bool {
+        let properties_match = |properties: &[PropertyValue], fields: &[DataField]| {
+            properties.len() == fields.len()
+                && properties.iter().zip(fields).all(|(p, f)| {
+                    p.name == f.name()
+                        && p.value.is_of_type(f.ty())
+                        && (f.is_nullable() || !p.value.is_null())
+                })
+        };
+        match (self, ty) {
+            (ScalarValue::Null, _) => true,
+            (ScalarValue::Boolean(_), LogicalType::Boolean)
+            | (ScalarValue::Int8(_), LogicalType::Int8)
+            | (ScalarValue::Int16(_), LogicalType::Int16)
+            | (ScalarValue::Int32(_), LogicalType::Int32)
+            | (ScalarValue::Int64(_), LogicalType::Int64)
+            | (ScalarValue::UInt8(_), LogicalType::UInt8)
+            | (ScalarValue::UInt16(_), LogicalType::UInt16)
+            | (ScalarValue::UInt32(_), LogicalType::UInt32)
+            | (ScalarValue::UInt64(_), LogicalType::UInt64)
+            | (ScalarValue::Float32(_), LogicalType::Float32)
+            | (ScalarValue::Float64(_), LogicalType::Float64)
+            | (ScalarValue::String(_), LogicalType::String) => true,
+            (ScalarValue::Vector { dimension, .. }, LogicalType::Vector(d)) => dimension == d,
+            (ScalarValue::Vertex(v), LogicalType::Vertex(fields)) => v
+                .as_ref()
+                .is_none_or(|v| properties_match(&v.properties, fields)),
+            (ScalarValue::Edge(e), LogicalType::Edge(fields)) => e
+                .as_ref()
+                .is_none_or(|e| properties_match(&e.properties, fields)),
+            (ScalarValue::Record(r), LogicalType::Record(fields)) => {
+                r.as_ref().is_none_or(|r| properties_match(r, fields))
+            }
+            (ScalarValue::List(values), LogicalType::List(element)) => values
+                .as_ref()
+                .is_none_or(|values| values.iter().all(|v| v.is_of_type(element))),
+            (ScalarValue::Path(p), LogicalType::Path(path_fields)) => {
+                let (vertex_fields, edge_fields) = &**path_fields;
+                p.as_ref().is_none_or(|p| {
+                    p.vertices
+                        .iter()
+                        .all(|v| properties_match(&v.properties, vertex_fields))
+                        && p.edges
+                            .iter()
+                            .all(|e| properties_match(&e.properties, edge_fields))
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A builder of an array of a given [`LogicalType`] from [`ScalarValue`]s, created by
+/// [`ScalarValue::to_array_builder`].
+///
+/// Values are type-checked when they are appended, and the array is built by
+/// [`finish`](Self::finish).
+#[derive(Debug, Clone)]
+pub struct ScalarArrayBuilder {
+    ty: LogicalType,
+    values: Vec<ScalarValue>,
+}
+
+impl ScalarArrayBuilder {
+    #[inline]
+    pub fn logical_type(&self) -> &LogicalType {
+        &self.ty
+    }
+
+    /// Appends `value`, failing if it cannot be stored in an array of the builder's type.
+    pub fn append(&mut self, value: ScalarValue) -> Result<(), ConversionError> {
+        if !value.is_of_type(&self.ty) {
+            return Err(ConversionError::IncompatibleType);
+        }
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Returns the number of values appended since the last call to [`finish`](Self::finish).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Builds an array of the appended values, and resets the builder.
+    pub fn finish(&mut self) -> ArrayRef {
+        build_array(&self.ty, std::mem::take(&mut self.values))
+    }
+}
+
+/// Builds an array of type `ty` from `values`, all of which must be of type `ty`.
+fn build_array(ty: &LogicalType, values: Vec<ScalarValue>) -> ArrayRef {
+    let values = values.into_iter();
+    match ty {
+        LogicalType::Boolean => Arc::new(BooleanArray::from_iter(
+            values.map(|v| v.into_boolean().flatten()),
+        )),
+        LogicalType::Int8 => Arc::new(Int8Array::from_iter(
+            values.map(|v| v.into_int8().flatten()),
+        )),
+        LogicalType::Int16 => Arc::new(Int16Array::from_iter(
+            values.map(|v| v.into_int16().flatten()),
+        )),
+        LogicalType::Int32 => Arc::new(Int32Array::from_iter(
+            values.map(|v| v.into_int32().flatten()),
+        )),
+        LogicalType::Int64 => Arc::new(Int64Array::from_iter(
+            values.map(|v| v.into_int64().flatten()),
+        )),
+        LogicalType::UInt8 => Arc::new(UInt8Array::from_iter(
+            values.map(|v| v.into_uint8().flatten()),
+        )),
+        LogicalType::UInt16 => Arc::new(UInt16Array::from_iter(
+            values.map(|v| v.into_uint16().flatten()),
+        )),
+        LogicalType::UInt32 => Arc::new(UInt32Array::from_iter(
+            values.map(|v| v.into_uint32().flatten()),
+        )),
+        LogicalType::UInt64 => Arc::new(UInt64Array::from_iter(
+            values.map(|v| v.into_uint64().flatten()),
+        )),
+        LogicalType::Float32 => Arc::new(Float32Array::from_iter(
+            values.map(|v| v.into_float32().flatten().map(|f| f.into_inner())),
+        )),
+        LogicalType::Float64 => Arc::new(Float64Array::from_iter(
+            values.map(|v| v.into_float64().flatten().map(|f| f.into_inner())),
+        )),
+        LogicalType::String => Arc::new(StringArray::from_iter(
+            values.map(|v| v.into_string().flatten()),
+        )),
+        LogicalType::Vector(dimension) => {
+            let DataType::FixedSizeList(field, size) = ty.to_arrow_data_type() else {
+                unreachable!("vectors should be fixed-size lists");
+            };
+            let mut validity = Vec::with_capacity(values.len());
+            let mut data = Vec::with_capacity(values.len() * dimension);
+            for value in values {
+                match value {
+                    ScalarValue::Vector {
+                        value: Some(vector),
+                        ..
+                    } => {
+                        validity.push(true);
+                        data.extend(vector.data().iter().map(|f| f.into_inner()));
+                    }
+                    _ => {
+                        validity.push(false);
+                        data.extend(std::iter::repeat_n(0.0, *dimension));
+                    }
+                }
+            }
+            let data = Arc::new(Float32Array::from(data));
+            let nulls = Some(NullBuffer::from(validity));
+            Arc::new(FixedSizeListArray::new(field, size, data, nulls))
+        }
+        LogicalType::Vertex(fields) => {
+            let (ids, labels, properties): (Vec<_>, Vec<_>, Vec<_>) = values
+                .map(|v| match v {
+                    ScalarValue::Vertex(Some(v)) => (Some(v.id), Some(v.label), Some(v.properties)),
+                    _ => (None, None, None),
+                })
+                .multiunzip();
+            let columns = [ids_to_array(&ids), labels_to_array(&labels)];
+            build_struct_array(ty, columns, properties, fields)
+        }
+        LogicalType::Edge(fields) => {
+            let (ids, labels, endpoints, properties): (Vec<_>, Vec<_>, Vec<_>, Vec<_>) = values
+                .map(|v| match v {
+                    ScalarValue::Edge(Some(e)) => (
+                        Some(e.id),
+                        Some(e.label),
+                        Some((e.src, e.dst)),
+                        Some(e.properties),
+                    ),
+                    _ => (None, None, None, None),
+                })
+                .multiunzip();
+            let src: Vec<_> = endpoints.iter().map(|e| e.map(|(src, _)| src)).collect();
+            let dst: Vec<_> = endpoints.iter().map(|e| e.map(|(_, dst)| dst)).collect();
+            let columns = [
+                ids_to_array(&ids),
+                labels_to_array(&labels),
+                ids_to_array(&src),
+                ids_to_array(&dst),
+            ];
+            build_struct_array(ty, columns, properties, fields)
+        }
+        LogicalType::Record(fields) => {
+            let fields_of_rows = values.map(|v| v.into_record().flatten()).collect();
+            build_struct_array(ty, [], fields_of_rows, fields)
+        }
+        LogicalType::List(element) => {
+            let DataType::List(field) = ty.to_arrow_data_type() else {
+                unreachable!("lists should be lists");
+            };
+            let mut validity = Vec::with_capacity(values.len());
+            let mut lengths = Vec::with_capacity(values.len());
+            let mut elements = vec![];
+            for value in values {
+                let value = value.into_list().flatten();
+                validity.push(value.is_some());
+                let value = value.unwrap_or_default();
+                lengths.push(value.len());
+                elements.extend(value);
+            }
+            let offsets = OffsetBuffer::from_lengths(lengths);
+            let elements = build_array(element, elements);
+            let nulls = Some(NullBuffer::from(validity));
+            Arc::new(ListArray::new(field, offsets, elements, nulls))
+        }
+        LogicalType::Path(path_fields) => {
+            let (vertex_fields, edge_fields) = &**path_fields;
+            let DataType::Struct(fields) = ty.to_arrow_data_type() else {
+                unreachable!("paths should be structs");
+            };
+            let mut validity = Vec::with_capacity(values.len());
+            let mut vertices = vec![];
+            let mut edges = vec![];
+            let mut vertex_lengths = Vec::with_capacity(values.len());
+            let mut edge_lengths = Vec::with_capacity(values.len());
+            for value in values {
+                // Null paths have no vertices nor edges.
+                let value = value.into_path().flatten();
+                validity.push(value.is_some());
+                let (v, e) = value.map_or_else(Default::default, |p| (p.vertices, p.edges));
+                vertex_lengths.push(v.len());
+                edge_lengths.push(e.len());
+                vertices.extend(v.into_iter().map(ScalarValue::from));
+                edges.extend(e.into_iter().map(ScalarValue::from));
+            }
+            let list = |field: &FieldRef, lengths: Vec<usize>, elements: ArrayRef| -> ArrayRef {
+                let DataType::List(element) = field.data_type() else {
+                    unreachable!("the elements of paths should be lists");
+                };
+                let offsets = OffsetBuffer::from_lengths(lengths);
+                Arc::new(ListArray::new(element.clone(), offsets, elements, None))
+            };
+            let vertices = build_array(&LogicalType::Vertex(vertex_fields.clone()), vertices);
+            let edges = build_array(&LogicalType::Edge(edge_fields.clone()), edges);
+            let columns = vec![
+                list(&fields[0], vertex_lengths, vertices),
+                list(&fields[1], edge_lengths, edges),
+            ];
+            let nulls = Some(NullBuffer::from(validity));
+            Arc::new(StructArray::new(fields, columns, nulls))
+        }
+        LogicalType::Null => Arc::new(NullArray::new(values.len())),
+    }
+}
+
+/// Builds a non-nullable id column of a struct array, where the ids of null rows are masked by the
+/// struct.
+fn ids_to_array(ids: &[Option<u64>]) -> ArrayRef {
+    Arc::new(UInt64Array::from_iter_values(
+        ids.iter().map(|id| id.unwrap_or_default()),
+    ))
+}
+
+fn labels_to_array(labels: &[Option<LabelId>]) -> ArrayRef {
+    Arc::new(UInt32Array::from_iter_values(
+        labels.iter().map(|label| label.map_or(0, LabelId::get)),
+    ))
+}
+
+/// Builds the struct array of a vertex, edge or record type, from its predefined `columns` and the
+/// `properties` of each row (`None` for null rows).
+fn build_struct_array<const N: usize>(
+    ty: &LogicalType,
+    columns: [ArrayRef; N],
+    properties: Vec<Option<Vec<PropertyValue>>>,
+    fields: &[DataField],
+) -> ArrayRef {
+    let DataType::Struct(arrow_fields) = ty.to_arrow_data_type() else {
+        unreachable!("vertices, edges and records should be structs");
+    };
+    let validity: Vec<_> = properties.iter().map(Option::is_some).collect();
+    if arrow_fields.is_empty() {
+        // The length of a struct without fields cannot be taken from its columns.
+        let nulls = Some(NullBuffer::from(validity));
+        return Arc::new(StructArray::new_empty_fields(properties.len(), nulls));
+    }
+    let mut property_columns: Vec<Vec<ScalarValue>> =
+        vec![Vec::with_capacity(properties.len()); fields.len()];
+    for row in properties {
+        match row {
+            Some(row) => {
+                for (column, property) in property_columns.iter_mut().zip(row) {
+                    column.push(property.value);
+                }
+            }
+            None => property_columns
+                .iter_mut()
+                .for_each(|column| column.push(ScalarValue::Null)),
+        }
+    }
+    let columns = columns
+        .into_iter()
+        .chain(
+            fields
+                .iter()
+                .zip(property_columns)
+                .map(|(field, values)| build_array(field.ty(), values)),
+        )
+        .collect();
+    let nulls = Some(NullBuffer::from(validity));
+    Arc::new(StructArray::new(arrow_fields, columns, nulls))
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
+    use arrow::array::DictionaryArray;
+    use arrow::datatypes::Int32Type;
     use ordered_float::OrderedFloat;
 
     use super::{ConversionError, ScalarValue, *};
@@ -1656,4 +2539,410 @@ fn test_vector_from_conversion() {
         let scalar: ScalarValue = (1usize, None).into();
         assert_eq!(scalar, ScalarValue::new_vector(1, None));
     }
+
+    fn assert_round_trip(value: ScalarValue, ty: LogicalType) {
+        let s = format!("{value}");
+        assert_eq!(ScalarValue::parse(&s, &ty), Ok(value), "{s}");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        assert_round_trip(ScalarValue::Null, LogicalType::Null);
+        assert_round_trip(ScalarValue::Boolean(Some(true)), LogicalType::Boolean);
+        assert_round_trip(ScalarValue::Int8(Some(i8::MIN)), LogicalType::Int8);
+        assert_round_trip(ScalarValue::Int16(Some(i16::MAX)), LogicalType::Int16);
+        assert_round_trip(ScalarValue::Int32(Some(-42)), LogicalType::Int32);
+        assert_round_trip(ScalarValue::Int64(Some(i64::MIN)), LogicalType::Int64);
+        assert_round_trip(ScalarValue::UInt8(Some(u8::MAX)), LogicalType::UInt8);
+        assert_round_trip(ScalarValue::UInt16(Some(0)), LogicalType::UInt16);
+        assert_round_trip(ScalarValue::UInt32(Some(u32::MAX)), LogicalType::UInt32);
+        assert_round_trip(ScalarValue::UInt64(Some(u64::MAX)), LogicalType::UInt64);
+        assert_round_trip(ScalarValue::String(Some("abc".into())), LogicalType::String);
+        assert_round_trip(
+            ScalarValue::String(Some(String::new())),
+            LogicalType::String,
+        );
+        let vector = VectorValue::new(vec![OrderedFloat(0.1), OrderedFloat(-2.5)], 2).unwrap();
+        assert_round_trip(
+            ScalarValue::new_vector(2, Some(vector)),
+            LogicalType::Vector(2),
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip_nulls() {
+        for ty in [
+            LogicalType::Boolean,
+            LogicalType::Int8,
+            LogicalType::Int16,
+            LogicalType::Int32,
+            LogicalType::Int64,
+            LogicalType::UInt8,
+            LogicalType::UInt16,
+            LogicalType::UInt32,
+            LogicalType::UInt64,
+            LogicalType::Float32,
+            LogicalType::Float64,
+            LogicalType::String,
+            LogicalType::Vector(3),
+        ] {
+            let null = ScalarValue::null_of(&ty).unwrap();
+            assert!(null.is_null());
+            assert_eq!(format!("{null}"), "NULL");
+            assert_round_trip(null, ty);
+        }
+        assert_eq!(
+            ScalarValue::parse("null", &LogicalType::Int32),
+            Ok(ScalarValue::Int32(None))
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip_floats() {
+        for v in [
+            0.1,
+            -0.0,
+            1.0 / 3.0,
+            f32::MIN_POSITIVE,
+            f32::MAX,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ] {
+            assert_round_trip(
+                ScalarValue::Float32(Some(OrderedFloat(v))),
+                LogicalType::Float32,
+            );
+        }
+        for v in [
+            0.1,
+            1.0 / 3.0,
+            1e-300,
+            f64::MAX,
+            f64::EPSILON,
+            f64::NEG_INFINITY,
+        ] {
+            assert_round_trip(
+                ScalarValue::Float64(Some(OrderedFloat(v))),
+                LogicalType::Float64,
+            );
+        }
+        // `OrderedFloat` considers all NaNs equal.
+        assert_round_trip(
+            ScalarValue::Float64(Some(OrderedFloat(f64::NAN))),
+            LogicalType::Float64,
+        );
+        // The sign of zero is kept.
+        let zero = ScalarValue::parse("-0", &LogicalType::Float64).unwrap();
+        assert!(zero.get_float64().unwrap().is_sign_negative());
+    }
+
+    #[test]
+    fn test_display_round_trip_quoted_strings() {
+        let value = ScalarValue::String(Some("it's 'NULL'".into()));
+        assert_eq!(format!("{value}"), "'it''s ''NULL'''");
+        assert_round_trip(value, LogicalType::String);
+        // A quoted `NULL` is a string rather than a null.
+        assert_round_trip(
+            ScalarValue::String(Some("NULL".into())),
+            LogicalType::String,
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            ScalarValue::parse("128", &LogicalType::Int8),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            ScalarValue::parse("-1", &LogicalType::UInt32),
+            Err(ConversionError::ParseError("-1".into()))
+        );
+        assert_eq!(
+            ScalarValue::parse("1.5", &LogicalType::Int64),
+            Err(ConversionError::ParseError("1.5".into()))
+        );
+        assert_eq!(
+            ScalarValue::parse("yes", &LogicalType::Boolean),
+            Err(ConversionError::ParseError("yes".into()))
+        );
+        // Unquoted, or with an undoubled quote inside.
+        assert!(ScalarValue::parse("abc", &LogicalType::String).is_err());
+        assert!(ScalarValue::parse("'a'b'", &LogicalType::String).is_err());
+        // Wrong dimension.
+        assert!(ScalarValue::parse("[1, 2]", &LogicalType::Vector(3)).is_err());
+        assert_eq!(
+            ScalarValue::parse("1", &LogicalType::Vertex(vec![])),
+            Err(ConversionError::IncompatibleType)
+        );
+    }
+
+    fn assert_array_round_trip(ty: LogicalType, values: Vec<ScalarValue>) {
+        let mut builder = ScalarValue::to_array_builder(&ty, values.len());
+        for value in &values {
+            builder.append(value.clone()).unwrap();
+        }
+        let array = builder.finish();
+        assert!(builder.is_empty());
+        assert_eq!(array.data_type(), &ty.to_arrow_data_type());
+        let actual: Vec<_> = (0..array.len())
+            .map(|i| ScalarValue::from_array(array.as_ref(), i))
+            .collect();
+        assert_eq!(actual, values, "{ty}");
+    }
+
+    #[test]
+    fn test_array_round_trip_primitives() {
+        use ScalarValue::*;
+        assert_array_round_trip(
+            LogicalType::Boolean,
+            vec![Boolean(Some(true)), Boolean(None)],
+        );
+        assert_array_round_trip(LogicalType::Int8, vec![Int8(Some(-1)), Int8(None)]);
+        assert_array_round_trip(LogicalType::Int16, vec![Int16(None), Int16(Some(2))]);
+        assert_array_round_trip(LogicalType::Int32, vec![Int32(Some(3)), Int32(None)]);
+        assert_array_round_trip(LogicalType::Int64, vec![Int64(Some(i64::MIN)), Int64(None)]);
+        assert_array_round_trip(LogicalType::UInt8, vec![UInt8(Some(5)), UInt8(None)]);
+        assert_array_round_trip(LogicalType::UInt16, vec![UInt16(Some(6)), UInt16(None)]);
+        assert_array_round_trip(LogicalType::UInt32, vec![UInt32(Some(7)), UInt32(None)]);
+        assert_array_round_trip(
+            LogicalType::UInt64,
+            vec![UInt64(Some(u64::MAX)), UInt64(None)],
+        );
+        assert_array_round_trip(
+            LogicalType::Float32,
+            vec![Float32(Some(OrderedFloat(0.5))), Float32(None)],
+        );
+        assert_array_round_trip(
+            LogicalType::Float64,
+            vec![Float64(None), Float64(Some(OrderedFloat(-1.5)))],
+        );
+        assert_array_round_trip(
+            LogicalType::String,
+            vec![
+                String(Some("a".into())),
+                String(None),
+                String(Some("".into())),
+            ],
+        );
+        assert_array_round_trip(LogicalType::Null, vec![Null, Null]);
+        assert_array_round_trip(LogicalType::Int32, vec![]);
+    }
+
+    #[test]
+    fn test_dictionary_from_array() {
+        let array: DictionaryArray<Int32Type> = vec![Some("a"), None, Some("b"), Some("a")]
+            .into_iter()
+            .collect();
+        let actual: Vec<_> = (0..array.len())
+            .map(|i| ScalarValue::from_array(&array, i))
+            .collect();
+        assert_eq!(
+            actual,
+            vec![
+                ScalarValue::String(Some("a".into())),
+                ScalarValue::String(None),
+                ScalarValue::String(Some("b".into())),
+                ScalarValue::String(Some("a".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_array_round_trip_vectors() {
+        let vector = VectorValue::new(vec![OrderedFloat(1.0), OrderedFloat(-2.0)], 2).unwrap();
+        assert_array_round_trip(
+            LogicalType::Vector(2),
+            vec![
+                ScalarValue::new_vector(2, None),
+                ScalarValue::new_vector(2, Some(vector)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_array_round_trip_vertices_and_edges() {
+        let label = LabelId::new(1).unwrap();
+        let fields = vec![
+            DataField::new("name".into(), LogicalType::String, true),
+            DataField::new("age".into(), LogicalType::Int32, false),
+        ];
+        let vertex = VertexValue {
+            id: 42,
+            label,
+            properties: vec![
+                PropertyValue {
+                    name: "name".into(),
+                    value: ScalarValue::String(None),
+                },
+                PropertyValue {
+                    name: "age".into(),
+                    value: ScalarValue::Int32(Some(30)),
+                },
+            ],
+        };
+        assert_array_round_trip(
+            LogicalType::Vertex(fields),
+            vec![ScalarValue::Vertex(None), ScalarValue::Vertex(Some(vertex))],
+        );
+
+        let fields = vec![DataField::new("since".into(), LogicalType::Int32, true)];
+        let edge = EdgeValue {
+            id: 7,
+            src: 1,
+            dst: 2,
+            label,
+            properties: vec![PropertyValue {
+                name: "since".into(),
+                value: ScalarValue::Int32(Some(2020)),
+            }],
+        };
+        assert_array_round_trip(
+            LogicalType::Edge(fields),
+            vec![ScalarValue::Edge(Some(edge)), ScalarValue::Edge(None)],
+        );
+    }
+
+    #[test]
+    fn test_array_round_trip_lists() {
+        let ty = LogicalType::List(Box::new(LogicalType::String));
+        let values = vec![
+            vec!["a".into(), ScalarValue::String(None)].into(),
+            ScalarValue::List(Some(vec![])),
+            ScalarValue::List(None),
+            vec![ScalarValue::from("b")].into(),
+        ];
+        assert_array_round_trip(ty.clone(), values);
+        let mut builder = ScalarValue::to_array_builder(&ty, 1);
+        assert_eq!(
+            builder.append(vec![ScalarValue::from(1i32)].into()),
+            Err(ConversionError::IncompatibleType)
+        );
+        let list = ScalarValue::from(vec!["a".into(), ScalarValue::String(None)]);
+        assert_eq!(format!("{list}"), "['a', NULL]");
+    }
+
+    #[test]
+    fn test_array_round_trip_records() {
+        let fields = vec![
+            DataField::new("name".into(), LogicalType::String, false),
+            DataField::new("age".into(), LogicalType::Int32, true),
+        ];
+        let record = |name: &str, age: Option<i32>| {
+            ScalarValue::from(vec![
+                PropertyValue::new("name".into(), name.into()),
+                PropertyValue::new("age".into(), age.into()),
+            ])
+        };
+        let values = vec![
+            record("alice", Some(30)),
+            ScalarValue::Record(None),
+            record("bob", None),
+        ];
+        assert_array_round_trip(LogicalType::Record(fields), values);
+        let empty = vec![ScalarValue::Record(Some(vec![])), ScalarValue::Record(None)];
+        assert_array_round_trip(LogicalType::Record(vec![]), empty);
+        assert_eq!(
+            format!("{}", record("alice", None)),
+            "record { name: 'alice', age: NULL }"
+        );
+    }
+
+    #[test]
+    fn test_array_round_trip_paths() {
+        let vertex_fields = vec![DataField::new("name".into(), LogicalType::String, false)];
+        let label = LabelId::new(1).unwrap();
+        let vertex = |id: u64| {
+            let name = PropertyValue::new("name".into(), format!("v{id}").into());
+            VertexValue::new(id, label, vec![name])
+        };
+        let edge = |id: u64, src: u64, dst: u64| EdgeValue::new(id, src, dst, label, vec![]);
+        let two_hops = PathValue::new(
+            vec![vertex(1), vertex(2), vertex(3)],
+            vec![edge(10, 1, 2), edge(11, 2, 3)],
+        );
+        assert_eq!(two_hops.length(), 2);
+        let values = vec![
+            two_hops.into(),
+            ScalarValue::Path(None),
+            PathValue::new(vec![vertex(4)], vec![]).into(),
+        ];
+        assert_array_round_trip(LogicalType::Path(Box::new((vertex_fields, vec![]))), values);
+        let one_hop = PathValue::new(vec![vertex(1), vertex(2)], vec![edge(10, 1, 2)]);
+        assert_eq!(
+            format!("{}", ScalarValue::from(one_hop)),
+            "path [vertex { id: 1, label: 1, name: 'v1' }, edge { id: 10, src: 1, dst: 2, label: 1 \
+             }, vertex { id: 2, label: 1, name: 'v2' }]"
+        );
+    }
+
+    #[test]
+    fn test_nested_to_scalar_array() {
+        let label = LabelId::new(1).unwrap();
+        let name = |name: &str| PropertyValue::new("name".into(), name.into());
+        let vertex = |id: u64| VertexValue::new(id, label, vec![name(&format!("v{id}"))]);
+        let edge = EdgeValue::new(10, 1, 2, label, vec![name("e")]);
+        let values = [
+            ScalarValue::Vertex(Some(vertex(1))),
+            ScalarValue::Vertex(None),
+            ScalarValue::Edge(Some(edge.clone())),
+            vec![ScalarValue::Int32(None), 1i32.into()].into(),
+            ScalarValue::List(Some(vec![])),
+            vec![
+                name("alice"),
+                PropertyValue::new("age".into(), 30i32.into()),
+            ]
+            .into(),
+            ScalarValue::Record(None),
+            PathValue::new(vec![vertex(1), vertex(2)], vec![edge]).into(),
+        ];
+        for value in values {
+            let array = value.to_scalar_array();
+            assert_eq!(array.len(), 1);
+            assert_eq!(ScalarValue::from_array(array.as_ref(), 0), value);
+        }
+    }
+
+    #[test]
+    fn test_array_builder_type_check() {
+        let mut builder = ScalarValue::to_array_builder(&LogicalType::Int32, 2);
+        // Untyped nulls are accepted, and read back as typed nulls.
+        builder.append(ScalarValue::Null).unwrap();
+        assert_eq!(
+            builder.append(ScalarValue::Int64(Some(1))),
+            Err(ConversionError::IncompatibleType)
+        );
+        let array = builder.finish();
+        assert_eq!(array.len(), 1);
+        assert_eq!(
+            ScalarValue::from_array(array.as_ref(), 0),
+            ScalarValue::Int32(None)
+        );
+
+        let mut builder = ScalarValue::to_array_builder(&LogicalType::Vector(3), 1);
+        assert_eq!(
+            builder.append(ScalarValue::new_vector(2, None)),
+            Err(ConversionError::IncompatibleType)
+        );
+
+        // A null for a non-nullable property.
+        let ty = LogicalType::Vertex(vec![DataField::new(
+            "age".into(),
+            LogicalType::Int32,
+            false,
+        )]);
+        let vertex = VertexValue {
+            id: 1,
+            label: LabelId::new(1).unwrap(),
+            properties: vec![PropertyValue {
+                name: "age".into(),
+                value: ScalarValue::Int32(None),
+            }],
+        };
+        let mut builder = ScalarValue::to_array_builder(&ty, 1);
+        assert_eq!(
+            builder.append(ScalarValue::Vertex(Some(vertex))),
+            Err(ConversionError::IncompatibleType)
+        );
+    }
 }
@@ -1,15 +1,95 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use minigu_catalog::memory::MemoryCatalog;
+use minigu_common::arithmetic::{DivisionByZeroPolicy, OverflowPolicy};
+use minigu_storage::tp::checkpoint::CheckpointManagerConfig;
+use minigu_storage::wal::graph_wal::{Durability, WalManagerConfig};
+use minigu_transaction::IsolationLevel;
 use rayon::ThreadPool;
 
+use crate::function::FunctionRegistry;
+
 #[derive(Debug)]
 pub struct DatabaseContext {
     catalog: MemoryCatalog,
     runtime: ThreadPool,
+    scan_runtime: Option<Arc<ThreadPool>>,
+    durability: Durability,
+    wal_dir: Option<PathBuf>,
+    checkpoint_dir: Option<PathBuf>,
+    overflow_policy: OverflowPolicy,
+    division_by_zero_policy: DivisionByZeroPolicy,
+    default_isolation: IsolationLevel,
+    aggregate_memory_limit: Option<usize>,
+    functions: FunctionRegistry,
 }
 
 impl DatabaseContext {
     pub fn new(catalog: MemoryCatalog, runtime: ThreadPool) -> Self {
-        Self { catalog, runtime }
+        Self {
+            catalog,
+            runtime,
+            scan_runtime: None,
+            durability: Durability::default(),
+            wal_dir: None,
+            checkpoint_dir: None,
+            overflow_policy: OverflowPolicy::default(),
+            division_by_zero_policy: DivisionByZeroPolicy::default(),
+            default_isolation: IsolationLevel::Serializable,
+            aggregate_memory_limit: None,
+            functions: FunctionRegistry::default(),
+        }
+    }
+
+    /// Runs scans in parallel on `scan_runtime`, with one partition per thread.
+    pub fn with_scan_runtime(mut self, scan_runtime: ThreadPool) -> Self {
+        self.scan_runtime = Some(Arc::new(scan_runtime));
+        self
+    }
+
+    /// Sets the durability of the graphs created in the database.
+    pub fn with_durability(mut self, durability: Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    /// Sets the directory under which the graphs created in the database keep their WALs, instead
+    /// of the default one.
+    pub fn with_wal_dir(mut self, wal_dir: PathBuf) -> Self {
+        self.wal_dir = Some(wal_dir);
+        self
+    }
+
+    /// Sets the directory under which the graphs created in the database keep their checkpoints,
+    /// instead of the default one.
+    pub fn with_checkpoint_dir(mut self, checkpoint_dir: PathBuf) -> Self {
+        self.checkpoint_dir = Some(checkpoint_dir);
+        self
+    }
+
+    /// Sets the default overflow policy of the integer arithmetic of the sessions.
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Sets the default policy of the integer divisions by zero of the sessions.
+    pub fn with_division_by_zero_policy(mut self, policy: DivisionByZeroPolicy) -> Self {
+        self.division_by_zero_policy = policy;
+        self
+    }
+
+    /// Sets the default isolation level of the transactions of the sessions.
+    pub fn with_default_isolation(mut self, isolation: IsolationLevel) -> Self {
+        self.default_isolation = isolation;
+        self
+    }
+
+    /// Sets the default memory limit of the grouped aggregations of the sessions, in bytes.
+    pub fn with_aggregate_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.aggregate_memory_limit = Some(memory_limit);
+        self
     }
 
     #[inline]
@@ -21,4 +101,72 @@ pub fn catalog(&self) -> &MemoryCatalog {
     pub fn runtime(&self) -> &ThreadPool {
         &self.runtime
     }
+
+    /// Returns the user-defined scalar functions of the database.
+    #[inline]
+    pub fn functions(&self) -> &FunctionRegistry {
+        &self.functions
+    }
+
+    /// Returns the thread pool that scans are run on, or `None` if scans are serial.
+    #[inline]
+    pub fn scan_runtime(&self) -> Option<&Arc<ThreadPool>> {
+        self.scan_runtime.as_ref()
+    }
+
+    /// Returns the default overflow policy of the integer arithmetic of the sessions.
+    #[inline]
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    /// Returns the default policy of the integer divisions by zero of the sessions.
+    #[inline]
+    pub fn division_by_zero_policy(&self) -> DivisionByZeroPolicy {
+        self.division_by_zero_policy
+    }
+
+    /// Returns the default isolation level of the transactions of the sessions.
+    #[inline]
+    pub fn default_isolation(&self) -> IsolationLevel {
+        self.default_isolation
+    }
+
+    /// Returns the default memory limit of the grouped aggregations of the sessions, in bytes, or
+    /// `None` if they are not limited.
+    #[inline]
+    pub fn aggregate_memory_limit(&self) -> Option<usize> {
+        self.aggregate_memory_limit
+    }
+
+    /// Returns the WAL configuration of the graph named `graph_name`, which logs its transactions
+    /// to a WAL of its own, so that it never recovers the transactions of other graphs.
+    pub fn wal_config(&self, graph_name: &str) -> WalManagerConfig {
+        let config = WalManagerConfig {
+            durability: self.durability,
+            ..Default::default()
+        };
+        let wal_path = self
+            .wal_dir
+            .as_ref()
+            .unwrap_or(&config.wal_path)
+            .join(format!("{graph_name}.wal"));
+        WalManagerConfig { wal_path, ..config }
+    }
+
+    /// Returns the checkpoint configuration of the graph named `graph_name`, whose checkpoints are
+    /// kept in a directory of their own, so that they are not pruned along with those of other
+    /// graphs.
+    pub fn checkpoint_config(&self, graph_name: &str) -> CheckpointManagerConfig {
+        let config = CheckpointManagerConfig::default();
+        let checkpoint_dir = self
+            .checkpoint_dir
+            .as_ref()
+            .unwrap_or(&config.checkpoint_dir)
+            .join(graph_name);
+        CheckpointManagerConfig {
+            checkpoint_dir,
+            ..config
+        }
+    }
 }
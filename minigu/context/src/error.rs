@@ -15,6 +15,12 @@ pub enum Error {
 
     #[error("graph not exists{0}")]
     GraphNotExists(String),
+
+    #[error("procedure already exists: {0}")]
+    ProcedureAlreadyExists(String),
+
+    #[error("procedure output does not match its schema: {0}")]
+    ProcedureOutputMismatch(String),
 }
 
 pub type SessionResult<T> = std::result::Result<T, Error>;
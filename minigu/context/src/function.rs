@@ -0,0 +1,311 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock};
+
+use minigu_common::data_type::LogicalType;
+use minigu_common::datum::DatumRef;
+use minigu_common::value::ScalarValue;
+
+pub type FunctionImpl = Box<
+    dyn Fn(&[DatumRef]) -> Result<DatumRef, Box<dyn Error + Send + Sync + 'static>> + Send + Sync,
+>;
+
+pub type FunctionRef = Arc<Function>;
+
+pub type UserAggregateRef = Arc<UserAggregate>;
+
+/// The type-erased state of a [`UserAggregate`].
+pub type UserAggregateState = Box<dyn Any + Send>;
+
+/// A user-defined scalar function, which computes a value for every row out of its arguments.
+///
+/// The arguments are evaluated over a whole chunk at a time, so each of them is either a column of
+/// the chunk or a scalar standing for every row. The returned datum must be of the declared return
+/// type.
+pub struct Function {
+    parameters: Vec<LogicalType>,
+    return_type: LogicalType,
+    inner: FunctionImpl,
+}
+
+impl Function {
+    pub fn new<F>(parameters: Vec<LogicalType>, return_type: LogicalType, inner: F) -> Self
+    where
+        F: Fn(&[DatumRef]) -> Result<DatumRef, Box<dyn Error + Send + Sync + 'static>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            parameters,
+            return_type,
+            inner: Box::new(inner),
+        }
+    }
+
+    #[inline]
+    pub fn parameters(&self) -> &[LogicalType] {
+        &self.parameters
+    }
+
+    #[inline]
+    pub fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    pub fn call(
+        &self,
+        args: &[DatumRef],
+    ) -> Result<DatumRef, Box<dyn Error + Send + Sync + 'static>> {
+        (self.inner)(args)
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Function")
+            .field("parameters", &self.parameters)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+// Functions cannot be compared by their implementations, so a function is only equal to itself.
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for Function {}
+
+impl Hash for Function {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self, state)
+    }
+}
+
+/// A user-defined aggregate function, which folds the values of each group into a state, from
+/// which the result of the group is computed.
+///
+/// Null values are skipped, like those of the built-in aggregates, so only non-null values are
+/// accumulated.
+pub trait Aggregate: Send + Sync + 'static {
+    /// The state of a group, holding what has been accumulated so far.
+    type State: Send + 'static;
+
+    /// Returns the type of the aggregated values.
+    fn input_type(&self) -> LogicalType;
+
+    /// Returns the type of the result.
+    fn return_type(&self) -> LogicalType;
+
+    /// Returns the state of a group without any value.
+    fn init(&self) -> Self::State;
+
+    /// Adds `value` to `state`.
+    fn accumulate(
+        &self,
+        state: &mut Self::State,
+        value: &ScalarValue,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>>;
+
+    /// Adds to `state` the values accumulated into `other`, e.g., values of the same group
+    /// aggregated separately.
+    fn merge(
+        &self,
+        state: &mut Self::State,
+        other: Self::State,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>>;
+
+    /// Computes the result of a group out of its state.
+    fn finalize(
+        &self,
+        state: &Self::State,
+    ) -> Result<ScalarValue, Box<dyn Error + Send + Sync + 'static>>;
+}
+
+/// [`Aggregate`] with its state type erased, so that aggregates of different states can be
+/// registered together.
+trait ErasedAggregate: Send + Sync {
+    fn init(&self) -> UserAggregateState;
+
+    fn accumulate(
+        &self,
+        state: &mut (dyn Any + Send),
+        value: &ScalarValue,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>>;
+
+    fn merge(
+        &self,
+        state: &mut (dyn Any + Send),
+        other: UserAggregateState,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>>;
+
+    fn finalize(
+        &self,
+        state: &(dyn Any + Send),
+    ) -> Result<ScalarValue, Box<dyn Error + Send + Sync + 'static>>;
+}
+
+impl<A: Aggregate> ErasedAggregate for A {
+    fn init(&self) -> UserAggregateState {
+        Box::new(Aggregate::init(self))
+    }
+
+    fn accumulate(
+        &self,
+        state: &mut (dyn Any + Send),
+        value: &ScalarValue,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        let state = state
+            .downcast_mut()
+            .expect("state should be of the aggregate");
+        Aggregate::accumulate(self, state, value)
+    }
+
+    fn merge(
+        &self,
+        state: &mut (dyn Any + Send),
+        other: UserAggregateState,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        let state = state
+            .downcast_mut()
+            .expect("state should be of the aggregate");
+        let other = other.downcast().expect("state should be of the aggregate");
+        Aggregate::merge(self, state, *other)
+    }
+
+    fn finalize(
+        &self,
+        state: &(dyn Any + Send),
+    ) -> Result<ScalarValue, Box<dyn Error + Send + Sync + 'static>> {
+        let state = state
+            .downcast_ref()
+            .expect("state should be of the aggregate");
+        Aggregate::finalize(self, state)
+    }
+}
+
+/// A registered [`Aggregate`], whose states are passed around as [`UserAggregateState`]s.
+pub struct UserAggregate {
+    input_type: LogicalType,
+    return_type: LogicalType,
+    inner: Box<dyn ErasedAggregate>,
+}
+
+impl UserAggregate {
+    pub fn new<A: Aggregate>(aggregate: A) -> Self {
+        Self {
+            input_type: aggregate.input_type(),
+            return_type: aggregate.return_type(),
+            inner: Box::new(aggregate),
+        }
+    }
+
+    #[inline]
+    pub fn input_type(&self) -> &LogicalType {
+        &self.input_type
+    }
+
+    #[inline]
+    pub fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    pub fn init(&self) -> UserAggregateState {
+        self.inner.init()
+    }
+
+    pub fn accumulate(
+        &self,
+        state: &mut (dyn Any + Send),
+        value: &ScalarValue,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        self.inner.accumulate(state, value)
+    }
+
+    pub fn merge(
+        &self,
+        state: &mut (dyn Any + Send),
+        other: UserAggregateState,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        self.inner.merge(state, other)
+    }
+
+    pub fn finalize(
+        &self,
+        state: &(dyn Any + Send),
+    ) -> Result<ScalarValue, Box<dyn Error + Send + Sync + 'static>> {
+        self.inner.finalize(state)
+    }
+}
+
+impl Debug for UserAggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserAggregate")
+            .field("input_type", &self.input_type)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+/// The user-defined scalar and aggregate functions of a database, resolved by name.
+#[derive(Debug, Default)]
+pub struct FunctionRegistry {
+    functions: RwLock<HashMap<String, FunctionRef>>,
+    aggregates: RwLock<HashMap<String, UserAggregateRef>>,
+}
+
+impl FunctionRegistry {
+    /// Registers `function` as `name`, returning `false` if a function of that name already exists.
+    pub fn register(&self, name: String, function: FunctionRef) -> bool {
+        let mut functions = self
+            .functions
+            .write()
+            .expect("the write lock should be acquired successfully");
+        match functions.entry(name) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(e) => {
+                e.insert(function);
+                true
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<FunctionRef> {
+        self.functions
+            .read()
+            .expect("the read lock should be acquired successfully")
+            .get(name)
+            .cloned()
+    }
+
+    /// Registers `aggregate` as `name`, returning `false` if an aggregate of that name already
+    /// exists.
+    pub fn register_aggregate(&self, name: String, aggregate: UserAggregateRef) -> bool {
+        let mut aggregates = self
+            .aggregates
+            .write()
+            .expect("the write lock should be acquired successfully");
+        match aggregates.entry(name) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(e) => {
+                e.insert(aggregate);
+                true
+            }
+        }
+    }
+
+    pub fn get_aggregate(&self, name: &str) -> Option<UserAggregateRef> {
+        self.aggregates
+            .read()
+            .expect("the read lock should be acquired successfully")
+            .get(name)
+            .cloned()
+    }
+}
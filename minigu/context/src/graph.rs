@@ -1,15 +1,22 @@
 use std::any::Any;
 use std::fmt::{self, Debug};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use minigu_catalog::memory::graph_type::MemoryGraphTypeCatalog;
-use minigu_catalog::provider::{GraphProvider, GraphTypeRef};
-use minigu_common::types::{LabelId, VertexIdArray};
-use minigu_storage::error::StorageResult;
-use minigu_storage::tp::MemoryGraph;
-use minigu_storage::tp::transaction::IsolationLevel;
+use minigu_catalog::provider::{GraphProvider, GraphTypeProvider, GraphTypeRef};
+use minigu_common::types::{LabelId, PropertyId, PropertyIndexKey, VertexId, VertexIdArray};
+use minigu_common::value::ScalarValue;
+use minigu_storage::common::{PropertyRecord, Vertex};
+use minigu_storage::error::{StorageError, StorageResult, TransactionError};
+use minigu_storage::tp::iterators::ScanOrder;
+use minigu_storage::tp::transaction::{CommitValidator, IsolationLevel, VertexPredicate};
+use minigu_storage::tp::{MemTransaction, MemoryGraph};
 use minigu_transaction::manager::GraphTxnManager;
+use minigu_transaction::{Timestamp, Transaction};
 
+#[derive(Clone)]
 pub enum GraphStorage {
     Memory(Arc<MemoryGraph>),
 }
@@ -17,13 +24,31 @@ pub enum GraphStorage {
 pub struct GraphContainer {
     graph_type: Arc<MemoryGraphTypeCatalog>,
     graph_storage: GraphStorage,
+    /// The timestamp the graph is read as of, or `None` to read its latest versions.
+    read_ts: Option<Timestamp>,
+    /// The isolation level of the transactions reading and modifying the graph.
+    isolation: IsolationLevel,
+    /// The transaction the graph is read and modified within, or `None` to begin a new one for
+    /// each read and each writer.
+    txn: Option<Arc<MemTransaction>>,
 }
 
 impl GraphContainer {
+    /// Creates a container of `graph_storage`, building the property indexes declared by
+    /// `graph_type` which the storage does not have yet.
     pub fn new(graph_type: Arc<MemoryGraphTypeCatalog>, graph_storage: GraphStorage) -> Self {
+        let GraphStorage::Memory(graph) = &graph_storage;
+        for name in graph_type.property_index_names() {
+            if let Ok(Some(key)) = graph_type.get_property_index(&name) {
+                graph.build_property_index(key);
+            }
+        }
         Self {
             graph_type,
             graph_storage,
+            read_ts: None,
+            isolation: IsolationLevel::Serializable,
+            txn: None,
         }
     }
 
@@ -31,59 +56,419 @@ pub fn new(graph_type: Arc<MemoryGraphTypeCatalog>, graph_storage: GraphStorage)
     pub fn graph_storage(&self) -> &GraphStorage {
         &self.graph_storage
     }
-}
 
-// TODO: Remove and use a checker.
-fn vertex_has_all_labels(
-    _mem: &Arc<MemoryGraph>,
-    _txn: &Arc<minigu_storage::tp::transaction::MemTransaction>,
-    _vid: u64,
-    _label_ids: &[LabelId],
-) -> StorageResult<bool> {
-    for label_id in _label_ids {
-        if _mem.get_vertex(_txn, _vid)?.label_id != *label_id {
-            return Ok(false);
+    /// Returns a view of the graph as of `ts`, i.e., which only sees the modifications committed
+    /// at or before `ts`. The view cannot modify the graph.
+    pub fn as_of(&self, ts: Timestamp) -> Self {
+        Self {
+            graph_type: self.graph_type.clone(),
+            graph_storage: self.graph_storage.clone(),
+            read_ts: Some(ts),
+            isolation: IsolationLevel::Snapshot,
+            txn: None,
+        }
+    }
+
+    /// Returns a view of the graph which reads and modifies it in transactions of the given
+    /// isolation level.
+    pub fn with_isolation(&self, isolation: IsolationLevel) -> Self {
+        Self {
+            graph_type: self.graph_type.clone(),
+            graph_storage: self.graph_storage.clone(),
+            read_ts: self.read_ts,
+            isolation,
+            txn: self.txn.clone(),
+        }
+    }
+
+    /// Returns a view of the graph which reads and modifies it within the transaction of
+    /// `writer`, so that the reads see the modifications made so far, e.g., by the previous
+    /// statements of an explicit transaction.
+    ///
+    /// The writers of the view share the transaction, so committing or aborting them does
+    /// nothing: the transaction ends when `writer` is committed or aborted.
+    pub fn within(&self, writer: &GraphWriter) -> Self {
+        Self {
+            graph_type: self.graph_type.clone(),
+            graph_storage: self.graph_storage.clone(),
+            read_ts: None,
+            isolation: self.isolation,
+            txn: Some(writer.txn.clone()),
         }
     }
-    Ok(true)
+
+    /// Returns the commit timestamp of the latest modification of the graph.
+    pub fn latest_commit_ts(&self) -> Timestamp {
+        let GraphStorage::Memory(graph) = self.graph_storage();
+        graph.txn_manager().latest_commit_ts()
+    }
+
+    /// Calls `f` with the graph and the transaction of the view if any, or a new transaction
+    /// reading it, as of the timestamp of the view if any.
+    fn read<R>(
+        &self,
+        f: impl FnOnce(&Arc<MemoryGraph>, &Arc<MemTransaction>) -> StorageResult<R>,
+    ) -> StorageResult<R> {
+        let GraphStorage::Memory(graph) = self.graph_storage();
+        if let Some(txn) = &self.txn {
+            return f(graph, txn);
+        }
+        let txn = match self.read_ts {
+            Some(ts) => graph
+                .txn_manager()
+                .begin_transaction_as_of(ts, self.isolation)?,
+            None => graph.txn_manager().begin_transaction(self.isolation)?,
+        };
+        let result = f(graph, &txn);
+        // Finish the transaction right away, so that it is no longer active, e.g., holding back
+        // the garbage collection of the versions it reads or the checkpoints waiting for it.
+        txn.abort()?;
+        result
+    }
+}
+
+/// Returns whether a vertex labeled `label_id` bears every label of the conjunction `label_ids`.
+///
+/// Vertices carry a single label in storage, so a conjunction of distinct labels never matches.
+/// An empty conjunction matches any vertex.
+fn has_all_labels(label_id: LabelId, label_ids: &[LabelId]) -> bool {
+    label_ids.iter().all(|l| *l == label_id)
 }
 
 impl GraphContainer {
+    /// Returns the ids of the vertices matching any of the label sets in `labels`, each of which
+    /// is a conjunction, in batches of `batch_size`.
+    ///
+    /// The graph is scanned once, however many label sets there are, so a vertex matching several
+    /// of them is returned once.
     pub fn vertex_source(
         &self,
-        label_ids: &[LabelId],
+        labels: &[Vec<LabelId>],
+        batch_size: usize,
+    ) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
+        self.vertex_source_in_range(labels, 0..=VertexId::MAX, batch_size)
+    }
+
+    /// Like [`vertex_source`](Self::vertex_source), but only returns the vertices whose ids are in
+    /// `range`.
+    pub fn vertex_source_in_range(
+        &self,
+        labels: &[Vec<LabelId>],
+        range: RangeInclusive<VertexId>,
+        batch_size: usize,
+    ) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
+        self.scan_in_range(labels, range, ScanOrder::Unordered, batch_size)
+    }
+
+    /// Like [`vertex_source_in_range`](Self::vertex_source_in_range), but returns the vertices in
+    /// ascending order of id.
+    pub fn sorted_vertex_source_in_range(
+        &self,
+        labels: &[Vec<LabelId>],
+        range: RangeInclusive<VertexId>,
+        batch_size: usize,
+    ) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
+        self.scan_in_range(labels, range, ScanOrder::Ascending, batch_size)
+    }
+
+    fn scan_in_range(
+        &self,
+        labels: &[Vec<LabelId>],
+        range: RangeInclusive<VertexId>,
+        order: ScanOrder,
         batch_size: usize,
     ) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
-        let mem = match self.graph_storage() {
+        self.read(|_, txn| scan_vertices(txn, labels, range, order, batch_size))
+    }
+
+    /// Returns the ids of the vertices whose property `key.property_id` is `value` among the
+    /// vertices labeled `key.label_id`, looked up in the property index of `key`, in batches of
+    /// `batch_size`.
+    pub fn vertex_source_by_property(
+        &self,
+        key: PropertyIndexKey,
+        value: &ScalarValue,
+        batch_size: usize,
+    ) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
+        let ids = self.read(|graph, txn| graph.lookup_property_index(txn, key, value))?;
+        let batches = ids
+            .chunks(batch_size)
+            .map(|ids| Arc::new(VertexIdArray::from_iter_values(ids.iter().copied())))
+            .collect::<Vec<_>>();
+        Ok(Box::new(batches.into_iter()))
+    }
+
+    /// Returns the vertices of `vids`, with `None` for null ids.
+    pub fn vertices(&self, vids: &VertexIdArray) -> StorageResult<Vec<Option<Vertex>>> {
+        self.read(|graph, txn| {
+            vids.iter()
+                .map(|vid| vid.map(|vid| graph.get_vertex(txn, vid)).transpose())
+                .collect()
+        })
+    }
+
+    /// Splits the ids of the vertices of the graph into at most `partitions` disjoint ranges of
+    /// similar widths, e.g., to scan each range on a different thread.
+    pub fn vertex_id_ranges(&self, partitions: usize) -> Vec<RangeInclusive<VertexId>> {
+        assert_ne!(partitions, 0, "there should be at least one partition");
+        let bounds = match self.graph_storage() {
+            GraphStorage::Memory(m) => m.vertex_id_bounds(),
+        };
+        let Some((min, max)) = bounds else {
+            return vec![];
+        };
+        split_range(min, max, partitions)
+    }
+}
+
+/// Returns the ids of the vertices visible to `txn` whose ids are in `range` and which bear every
+/// label of any of the label sets in `labels`, in batches of `batch_size`, visited in the given
+/// order.
+fn scan_vertices(
+    txn: &MemTransaction,
+    labels: &[Vec<LabelId>],
+    range: RangeInclusive<VertexId>,
+    order: ScanOrder,
+    batch_size: usize,
+) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
+    // The scan is recorded as a predicate, so that a serializable transaction fails to commit if
+    // a vertex it would now return was committed meanwhile.
+    let predicate: VertexPredicate = {
+        let (labels, range) = (labels.to_vec(), range.clone());
+        Arc::new(move |v| {
+            range.contains(&v.vid())
+                && labels
+                    .iter()
+                    .any(|label_ids| has_all_labels(v.label_id, label_ids))
+        })
+    };
+    let mut ids: Vec<u64> = Vec::new();
+    for v in txn.iter_vertices_in_range_where(order, range, predicate) {
+        ids.push(v?.vid());
+    }
+
+    let mut pos = 0usize;
+    let iter = std::iter::from_fn(move || {
+        if pos >= ids.len() {
+            return None;
+        }
+        let end = (pos + batch_size).min(ids.len());
+        let slice = &ids[pos..end];
+        pos = end;
+        Some(Arc::new(VertexIdArray::from_iter(slice.iter().copied())))
+    });
+
+    Ok(Box::new(iter))
+}
+
+/// Splits `min..=max` into at most `partitions` consecutive ranges whose widths differ by at most
+/// one.
+fn split_range(min: VertexId, max: VertexId, partitions: usize) -> Vec<RangeInclusive<VertexId>> {
+    let len = (max - min) as u128 + 1;
+    let partitions = (partitions as u128).min(len);
+    let (width, remainder) = (len / partitions, len % partitions);
+    let mut start = min as u128;
+    (0..partitions)
+        .map(|i| {
+            let end = start + width + u128::from(i < remainder);
+            let range = start as VertexId..=(end - 1) as VertexId;
+            start = end;
+            range
+        })
+        .collect()
+}
+
+impl GraphContainer {
+    /// Returns a writer that modifies the graph within a new transaction, or within the transaction
+    /// of the view if any (see [`within`](Self::within)).
+    ///
+    /// Fails if the graph is read as of a past timestamp.
+    pub fn writer(&self) -> StorageResult<GraphWriter> {
+        if let Some(ts) = self.read_ts {
+            return Err(StorageError::Transaction(TransactionError::InvalidState(
+                format!("cannot modify the graph read as of timestamp {}", ts.raw()),
+            )));
+        }
+        let graph = match self.graph_storage() {
             GraphStorage::Memory(m) => Arc::clone(m),
         };
-        let txn = mem
-            .txn_manager()
-            .begin_transaction(IsolationLevel::Serializable)?;
-        let mut ids: Vec<u64> = Vec::new();
-        {
-            let it = mem.iter_vertices(&txn)?;
-            for v in it {
-                let v = v?;
-                let vid = v.vid();
-                if label_ids.is_empty() || vertex_has_all_labels(&mem, &txn, vid, label_ids)? {
-                    ids.push(vid);
+        if let Some(txn) = &self.txn {
+            return Ok(GraphWriter {
+                graph,
+                txn: txn.clone(),
+                shared: true,
+            });
+        }
+        let txn = graph.txn_manager().begin_transaction(self.isolation)?;
+        Ok(GraphWriter {
+            graph,
+            txn,
+            shared: false,
+        })
+    }
+
+    /// Like [`writer`](Self::writer), but within a new read-only transaction, which reads a
+    /// snapshot of the graph as of its start, whatever the isolation level of the view, and whose
+    /// modifications fail.
+    pub fn read_only_writer(&self) -> StorageResult<GraphWriter> {
+        if self.read_ts.is_some() || self.txn.is_some() {
+            return self.writer();
+        }
+        let GraphStorage::Memory(graph) = self.graph_storage();
+        let txn = graph.txn_manager().begin_read_only_transaction()?;
+        Ok(GraphWriter {
+            graph: graph.clone(),
+            txn,
+            shared: false,
+        })
+    }
+
+    /// Deletes the vertices which have expired at `now`, according to the
+    /// [`Ttl`](minigu_catalog::ttl::Ttl)s of their vertex types, along with their edges, within
+    /// a single transaction. Returns the number of deleted vertices.
+    pub fn sweep_expired_vertices(&self, now: SystemTime) -> StorageResult<usize> {
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX));
+        let writer = self.writer()?;
+        let mut num_deleted = 0;
+        let result = self
+            .graph_type
+            .vertex_type_keys()
+            .into_iter()
+            .try_for_each(|label_set| {
+                let Ok(Some(vertex_type)) = self.graph_type.get_vertex_type(&label_set) else {
+                    return Ok(());
+                };
+                let Some(ttl) = vertex_type.ttl() else {
+                    return Ok(());
+                };
+                let label_ids: Vec<_> = label_set.iter().collect();
+                for vids in writer.vertex_source(&label_ids, 1024)? {
+                    for &vid in vids.values() {
+                        let vertex = writer.graph.get_vertex(&writer.txn, vid)?;
+                        let timestamp = vertex.properties().get(ttl.property_id() as usize);
+                        if let Some(ScalarValue::Int64(Some(timestamp))) = timestamp {
+                            if ttl.is_expired(*timestamp, now) {
+                                writer.delete_vertex(vid)?;
+                                num_deleted += 1;
+                            }
+                        }
+                    }
                 }
+                Ok(())
+            });
+        match result {
+            Ok(()) => writer.commit()?,
+            Err(e) => {
+                writer.abort()?;
+                return Err(e);
             }
         }
+        Ok(num_deleted)
+    }
+}
 
-        let mut pos = 0usize;
-        let iter = std::iter::from_fn(move || {
-            if pos >= ids.len() {
-                return None;
-            }
-            let end = (pos + batch_size).min(ids.len());
-            let slice = &ids[pos..end];
-            pos = end;
-            Some(Arc::new(VertexIdArray::from_iter(slice.iter().copied())))
-        });
+/// Modifies a graph within a single transaction.
+///
+/// None of the modifications are visible to other transactions until [`commit`](Self::commit) is
+/// called.
+pub struct GraphWriter {
+    graph: Arc<MemoryGraph>,
+    txn: Arc<MemTransaction>,
+    /// Whether the transaction is the one of a [`GraphContainer::within`] view, which is committed
+    /// or aborted by the writer it was created from instead.
+    shared: bool,
+}
+
+impl GraphWriter {
+    /// Sets the properties at `indices` of vertex `vid` to `values`.
+    pub fn set_vertex_properties(
+        &self,
+        vid: VertexId,
+        indices: Vec<usize>,
+        values: Vec<ScalarValue>,
+    ) -> StorageResult<()> {
+        self.graph
+            .set_vertex_property(&self.txn, vid, indices, values)
+    }
+
+    /// Inserts `vertex` into the graph, returning its id.
+    pub fn create_vertex(&self, vertex: Vertex) -> StorageResult<VertexId> {
+        self.graph.create_vertex(&self.txn, vertex)
+    }
+
+    /// Inserts a vertex with label `label_id` and `properties` into the graph, returning its id,
+    /// which is allocated by the graph, so that concurrent insertions never get the same id.
+    pub fn insert_vertex(
+        &self,
+        label_id: LabelId,
+        properties: Vec<ScalarValue>,
+    ) -> StorageResult<VertexId> {
+        let vid = self.graph.allocate_vertex_id();
+        self.create_vertex(Vertex::new(vid, label_id, PropertyRecord::new(properties)))
+    }
+
+    /// Like [`GraphContainer::vertex_source`], but scans the vertices bearing every label of
+    /// `label_ids` within the transaction of the writer, so that its own uncommitted modifications
+    /// are visible.
+    pub fn vertex_source(
+        &self,
+        label_ids: &[LabelId],
+        batch_size: usize,
+    ) -> StorageResult<Box<dyn Iterator<Item = Arc<VertexIdArray>> + Send + 'static>> {
+        scan_vertices(
+            &self.txn,
+            &[label_ids.to_vec()],
+            0..=VertexId::MAX,
+            ScanOrder::Unordered,
+            batch_size,
+        )
+    }
+
+    /// Returns whether vertex `vid` has any incoming or outgoing edge.
+    pub fn has_edges(&self, vid: VertexId) -> StorageResult<bool> {
+        let edge = self
+            .graph
+            .iter_adjacency(&self.txn, vid)?
+            .next()
+            .transpose()?;
+        Ok(edge.is_some())
+    }
 
-        Ok(Box::new(iter))
+    /// Deletes vertex `vid`, together with all of its edges.
+    pub fn delete_vertex(&self, vid: VertexId) -> StorageResult<()> {
+        self.graph.delete_vertex(&self.txn, vid)
+    }
+
+    /// Adds `validator` to the checks run on the modifications of the writer when it commits.
+    ///
+    /// See [`MemTransaction::add_commit_validator`].
+    pub fn add_commit_validator(&self, validator: CommitValidator) {
+        self.txn.add_commit_validator(validator);
+    }
+
+    pub fn commit(&self) -> StorageResult<()> {
+        if !self.shared {
+            self.txn.commit()?;
+        }
+        Ok(())
+    }
+
+    /// Discards every modification made by the writer.
+    pub fn abort(&self) -> StorageResult<()> {
+        if self.shared {
+            return Ok(());
+        }
+        self.txn.abort()
+    }
+}
+
+impl Debug for GraphWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphWriter")
+            .field("txn_id", &self.txn.txn_id())
+            .finish_non_exhaustive()
     }
 }
 
@@ -105,4 +490,202 @@ fn graph_type(&self) -> GraphTypeRef {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn vertex_property_range(
+        &self,
+        label_id: LabelId,
+        property_id: PropertyId,
+    ) -> Option<(ScalarValue, ScalarValue)> {
+        let GraphStorage::Memory(graph) = self.graph_storage();
+        let range = graph.statistics().get(label_id, property_id as usize)?;
+        Some((range.min, range.max))
+    }
+
+    fn estimated_vertex_count(&self, label_id: Option<LabelId>) -> Option<u64> {
+        let GraphStorage::Memory(graph) = self.graph_storage();
+        let counts = graph.vertex_counts()?;
+        Some(label_id.map_or(counts.total(), |label_id| counts.get(label_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use minigu_catalog::label_set::LabelSet;
+    use minigu_catalog::memory::graph_type::MemoryVertexTypeCatalog;
+    use minigu_catalog::property::Property;
+    use minigu_catalog::ttl::Ttl;
+    use minigu_common::data_type::LogicalType;
+    use minigu_storage::common::PropertyRecord;
+    use minigu_storage::tp::checkpoint::CheckpointManagerConfig;
+    use minigu_storage::wal::graph_wal::{Durability, WalManagerConfig};
+
+    use super::*;
+
+    const PERSON: LabelId = LabelId::new(1).unwrap();
+    const COMPANY: LabelId = LabelId::new(2).unwrap();
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(split_range(0, 9, 3), [0..=3, 4..=6, 7..=9]);
+        assert_eq!(split_range(5, 6, 4), [5..=5, 6..=6]);
+        assert_eq!(split_range(7, 7, 1), [7..=7]);
+        assert_eq!(
+            split_range(0, VertexId::MAX, 2),
+            [0..=VertexId::MAX / 2, VertexId::MAX / 2 + 1..=VertexId::MAX]
+        );
+    }
+
+    #[test]
+    fn test_has_all_labels() {
+        assert!(has_all_labels(PERSON, &[]));
+        assert!(has_all_labels(PERSON, &[PERSON]));
+        assert!(!has_all_labels(COMPANY, &[PERSON]));
+        // `:Person&Person`
+        assert!(has_all_labels(PERSON, &[PERSON, PERSON]));
+        // `:Person&Company`
+        assert!(!has_all_labels(PERSON, &[PERSON, COMPANY]));
+        assert!(!has_all_labels(COMPANY, &[PERSON, COMPANY]));
+    }
+
+    #[test]
+    fn test_scan_label_disjunction() {
+        let wal_config = WalManagerConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        let graph = MemoryGraph::with_config_fresh(Default::default(), wal_config);
+        let graph_type = Arc::new(MemoryGraphTypeCatalog::new());
+        let container = GraphContainer::new(graph_type, GraphStorage::Memory(graph));
+        let writer = container.writer().unwrap();
+        for (vid, label_id) in [(0, PERSON), (1, COMPANY), (2, PERSON)] {
+            let vertex = Vertex::new(vid, label_id, PropertyRecord::new(vec![]));
+            writer.create_vertex(vertex).unwrap();
+        }
+        writer.commit().unwrap();
+
+        let scan = |labels: &[Vec<LabelId>]| -> Vec<VertexId> {
+            container
+                .sorted_vertex_source_in_range(labels, 0..=VertexId::MAX, 2)
+                .unwrap()
+                .flat_map(|vids| vids.values().to_vec())
+                .collect()
+        };
+        assert_eq!(scan(&[vec![COMPANY]]), [1]);
+        // `:Person|Company|Person` returns every vertex once.
+        let labels = [vec![PERSON], vec![COMPANY], vec![PERSON]];
+        assert_eq!(scan(&labels), [0, 1, 2]);
+        // `:Person|Person&Company`
+        assert_eq!(scan(&[vec![PERSON], vec![PERSON, COMPANY]]), [0, 2]);
+    }
+
+    #[test]
+    fn test_sweep_expired_vertices() {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let event = graph_type.add_label("Event".into()).unwrap();
+        let label_set = LabelSet::from_iter([event]);
+        let properties = vec![Property::new(
+            "inserted_at".into(),
+            LogicalType::Int64,
+            true,
+        )];
+        let ttl = Ttl::new(0, Duration::from_secs(1));
+        let vertex_type = MemoryVertexTypeCatalog::new(label_set.clone(), properties).with_ttl(ttl);
+        graph_type.add_vertex_type(label_set, Arc::new(vertex_type));
+        let wal_config = WalManagerConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        let graph = MemoryGraph::with_config_fresh(Default::default(), wal_config);
+        let container = GraphContainer::new(Arc::new(graph_type), GraphStorage::Memory(graph));
+
+        let inserted_at = UNIX_EPOCH + Duration::from_secs(1_000);
+        let writer = container.writer().unwrap();
+        let timestamps = [Some(1_000_000), Some(1_005_000), None];
+        for (vid, timestamp) in timestamps.into_iter().enumerate() {
+            let properties = PropertyRecord::new(vec![ScalarValue::Int64(timestamp)]);
+            let vertex = Vertex::new(vid as VertexId, event, properties);
+            writer.create_vertex(vertex).unwrap();
+        }
+        writer.commit().unwrap();
+
+        let scan = || -> Vec<VertexId> {
+            let mut ids: Vec<_> = container
+                .vertex_source(&[vec![event]], 1024)
+                .unwrap()
+                .flat_map(|vids| vids.values().to_vec())
+                .collect();
+            ids.sort();
+            ids
+        };
+        // Not expired yet.
+        let now = inserted_at + Duration::from_millis(500);
+        assert_eq!(container.sweep_expired_vertices(now).unwrap(), 0);
+        assert_eq!(scan(), [0, 1, 2]);
+        // Only the first vertex has expired, and vertices without timestamps never do.
+        let now = inserted_at + Duration::from_secs(2);
+        assert_eq!(container.sweep_expired_vertices(now).unwrap(), 1);
+        assert_eq!(scan(), [1, 2]);
+        let now = inserted_at + Duration::from_secs(3_600);
+        assert_eq!(container.sweep_expired_vertices(now).unwrap(), 1);
+        assert_eq!(scan(), [2]);
+    }
+
+    #[test]
+    fn test_scan_prevents_phantoms() {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let person = graph_type.add_label("Person".into()).unwrap();
+        let wal_config = WalManagerConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        // Checkpoints would wait for the scanning transaction to finish.
+        let checkpoint_config = CheckpointManagerConfig {
+            auto_checkpoint_interval_secs: 0,
+            ..Default::default()
+        };
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config, wal_config);
+        let container = GraphContainer::new(Arc::new(graph_type), GraphStorage::Memory(graph));
+        let person_vertex = |vid| Vertex::new(vid, person, PropertyRecord::new(vec![]));
+
+        // Finds no person.
+        let writer = container.writer().unwrap();
+        assert_eq!(writer.vertex_source(&[person], 1024).unwrap().count(), 0);
+        writer.create_vertex(person_vertex(1)).unwrap();
+
+        // Meanwhile, a concurrent transaction commits a person, which the scan would now find.
+        let other = container.writer().unwrap();
+        other.create_vertex(person_vertex(2)).unwrap();
+        other.commit().unwrap();
+
+        assert!(matches!(
+            writer.commit(),
+            Err(StorageError::Transaction(
+                TransactionError::ReadWriteConflict(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_read_finishes_its_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal_config = WalManagerConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        // Checkpoints fail right away if a transaction is still active.
+        let checkpoint_config = CheckpointManagerConfig {
+            checkpoint_dir: dir.path().to_path_buf(),
+            auto_checkpoint_interval_secs: 0,
+            transaction_timeout_secs: 0,
+            ..Default::default()
+        };
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config, wal_config);
+        let graph_type = Arc::new(MemoryGraphTypeCatalog::new());
+        let container = GraphContainer::new(graph_type, GraphStorage::Memory(graph.clone()));
+
+        assert_eq!(container.vertex_source(&[vec![]], 1024).unwrap().count(), 0);
+        graph.create_managed_checkpoint(None).unwrap();
+    }
 }
@@ -1,5 +1,8 @@
 pub mod database;
 pub mod error;
+pub mod function;
 pub mod graph;
 pub mod procedure;
+pub mod progress;
 pub mod session;
+pub mod view;
@@ -4,9 +4,10 @@
 
 use minigu_catalog::provider::ProcedureProvider;
 use minigu_common::data_chunk::DataChunk;
-use minigu_common::data_type::{DataSchemaRef, LogicalType};
+use minigu_common::data_type::{DataSchema, DataSchemaRef, LogicalType};
 use minigu_common::value::ScalarValue;
 
+use crate::error::Error as SessionError;
 use crate::session::SessionContext;
 
 pub type ProcedureImpl = Box<
@@ -51,6 +52,63 @@ pub fn call(
     }
 }
 
+impl Procedure {
+    /// Creates a procedure from a [`TableProcedure`], checking that every chunk it returns matches
+    /// its declared schema.
+    pub fn from_table<P: TableProcedure>(procedure: P) -> Self {
+        let parameters = procedure.parameters();
+        let schema = procedure.schema();
+        Self::new(parameters, Some(schema.clone()), move |context, args| {
+            let chunks = procedure.call(context, args)?;
+            for chunk in &chunks {
+                check_output(chunk, &schema)?;
+            }
+            Ok(chunks)
+        })
+    }
+}
+
+/// A procedure returning a table, whose columns are declared before it is called.
+///
+/// The declared schema is what a `CALL` statement binds its `YIELD` items against, e.g.,
+/// `CALL show_procedures() YIELD name, params`.
+pub trait TableProcedure: Send + Sync + 'static {
+    /// Returns the types of the arguments of the procedure.
+    fn parameters(&self) -> Vec<LogicalType>;
+
+    /// Returns the schema of the table returned by the procedure.
+    fn schema(&self) -> DataSchemaRef;
+
+    /// Returns the rows of the table, in chunks with a column for each field of
+    /// [`schema`](Self::schema), in the same order and of the same type.
+    fn call(
+        &self,
+        context: SessionContext,
+        args: Vec<ScalarValue>,
+    ) -> Result<Vec<DataChunk>, Box<dyn Error + Send + Sync + 'static>>;
+}
+
+fn check_output(chunk: &DataChunk, schema: &DataSchema) -> Result<(), SessionError> {
+    if chunk.columns().len() != schema.fields().len() {
+        return Err(SessionError::ProcedureOutputMismatch(format!(
+            "expected {} columns, got {}",
+            schema.fields().len(),
+            chunk.columns().len()
+        )));
+    }
+    for (column, field) in chunk.columns().iter().zip(schema.fields()) {
+        let expected = field.ty().to_arrow_data_type();
+        if column.data_type() != &expected {
+            return Err(SessionError::ProcedureOutputMismatch(format!(
+                "expected column `{}` of type {expected}, got {}",
+                field.name(),
+                column.data_type()
+            )));
+        }
+    }
+    Ok(())
+}
+
 impl Debug for Procedure {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Procedure")
@@ -76,3 +134,63 @@ fn as_any(&self) -> &dyn Any {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use minigu_catalog::memory::MemoryCatalog;
+    use minigu_catalog::memory::schema::MemorySchemaCatalog;
+    use minigu_catalog::provider::DirectoryOrSchema;
+    use minigu_common::data_chunk;
+    use minigu_common::data_type::DataField;
+    use rayon::ThreadPoolBuilder;
+
+    use super::*;
+    use crate::database::DatabaseContext;
+
+    /// Returns `output` as is, declaring a single `Int32` column.
+    struct Constant {
+        output: DataChunk,
+    }
+
+    impl TableProcedure for Constant {
+        fn parameters(&self) -> Vec<LogicalType> {
+            vec![]
+        }
+
+        fn schema(&self) -> DataSchemaRef {
+            Arc::new(DataSchema::new(vec![DataField::new(
+                "a".into(),
+                LogicalType::Int32,
+                false,
+            )]))
+        }
+
+        fn call(
+            &self,
+            _context: SessionContext,
+            _args: Vec<ScalarValue>,
+        ) -> Result<Vec<DataChunk>, Box<dyn Error + Send + Sync + 'static>> {
+            Ok(vec![self.output.clone()])
+        }
+    }
+
+    fn call(output: DataChunk) -> Result<Vec<DataChunk>, Box<dyn Error + Send + Sync + 'static>> {
+        let root = Arc::new(MemorySchemaCatalog::new(None));
+        let catalog = MemoryCatalog::new(DirectoryOrSchema::Schema(root));
+        let runtime = ThreadPoolBuilder::new().build().unwrap();
+        let context = SessionContext::new(Arc::new(DatabaseContext::new(catalog, runtime)));
+        let procedure = Procedure::from_table(Constant { output });
+        assert_eq!(procedure.schema().unwrap().fields()[0].name(), "a");
+        procedure.call(context, vec![])
+    }
+
+    #[test]
+    fn test_table_procedure() {
+        let output = call(data_chunk!((Int32, [1, 2, 3]))).unwrap();
+        assert_eq!(output, [data_chunk!((Int32, [1, 2, 3]))]);
+        assert!(call(data_chunk!((Utf8, ["a"]))).is_err());
+        assert!(call(data_chunk!((Int32, [1]), (Int32, [2]))).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// The progress of a long-running operation processing rows, e.g., an import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of rows processed so far.
+    pub rows: usize,
+    /// The time elapsed since the operation started.
+    pub elapsed: Duration,
+}
+
+/// A callback invoked every `interval` rows processed by a long-running operation.
+#[derive(Clone)]
+pub struct ProgressCallback {
+    interval: usize,
+    callback: Arc<dyn Fn(Progress) + Send + Sync>,
+}
+
+impl ProgressCallback {
+    pub fn new(interval: usize, callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        assert_ne!(interval, 0, "the interval should be at least one row");
+        Self {
+            interval,
+            callback: Arc::new(callback),
+        }
+    }
+
+    #[inline]
+    pub fn interval(&self) -> usize {
+        self.interval
+    }
+
+    /// Returns a tracker of the progress of an operation starting now.
+    pub fn start(&self) -> ProgressTracker {
+        ProgressTracker {
+            callback: self.clone(),
+            start: Instant::now(),
+            rows: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressCallback")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Counts the rows processed by an operation, reporting them to a [`ProgressCallback`].
+///
+/// Rows may be counted from several threads at once.
+#[derive(Debug)]
+pub struct ProgressTracker {
+    callback: ProgressCallback,
+    start: Instant,
+    rows: AtomicUsize,
+}
+
+impl ProgressTracker {
+    /// Counts `rows` more processed rows, invoking the callback once for every multiple of the
+    /// interval reached.
+    pub fn advance(&self, rows: usize) {
+        let interval = self.callback.interval;
+        let before = self.rows.fetch_add(rows, Ordering::Relaxed);
+        let after = before + rows;
+        for n in before / interval + 1..=after / interval {
+            (self.callback.callback)(Progress {
+                rows: n * interval,
+                elapsed: self.start.elapsed(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn test_progress_tracker() {
+        let reported = Arc::new(Mutex::new(vec![]));
+        let callback = {
+            let reported = reported.clone();
+            ProgressCallback::new(10, move |progress| {
+                reported.lock().unwrap().push(progress.rows);
+            })
+        };
+        let tracker = callback.start();
+        for _ in 0..25 {
+            tracker.advance(1);
+        }
+        // A single advance may reach several multiples of the interval.
+        tracker.advance(30);
+        tracker.advance(0);
+        assert_eq!(*reported.lock().unwrap(), [10, 20, 30, 40, 50]);
+    }
+}
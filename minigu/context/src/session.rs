@@ -4,29 +4,63 @@
 use minigu_catalog::memory::schema::MemorySchemaCatalog;
 use minigu_catalog::named_ref::NamedGraphRef;
 use minigu_catalog::provider::{CatalogProvider, SchemaProvider};
+use minigu_common::arithmetic::{DivisionByZeroPolicy, OverflowPolicy};
+use minigu_transaction::{IsolationLevel, Timestamp};
 
 use crate::database::DatabaseContext;
 use crate::error::{Error, SessionResult};
+use crate::progress::ProgressCallback;
 
 #[derive(Clone, Debug)]
 pub struct SessionContext {
     database: Arc<DatabaseContext>,
     pub home_schema: Option<Arc<MemorySchemaCatalog>>,
     pub current_schema: Option<Arc<MemorySchemaCatalog>>,
-    // currently home_graph and current_graph is same.
-    // In the future, home_graph is a default graph named default.
+    // The graph `SESSION RESET GRAPH` returns to, i.e., the default graph of the database if it
+    // has one.
     pub home_graph: Option<NamedGraphRef>,
     pub current_graph: Option<NamedGraphRef>,
+    /// The callback notified of the progress of long-running procedures, e.g., `import`.
+    pub progress: Option<ProgressCallback>,
+    /// How the integer arithmetic of the queries resolves overflows.
+    pub overflow_policy: OverflowPolicy,
+    /// How the integer divisions and remainders by zero of the queries are resolved.
+    pub division_by_zero_policy: DivisionByZeroPolicy,
+    /// The number of digits after the decimal point of the floats written by exports, e.g.,
+    /// `export`, or `None` to write them in full.
+    pub float_precision: Option<usize>,
+    /// The timestamp the queries read the graphs as of, or `None` to read their latest versions.
+    pub read_timestamp: Option<Timestamp>,
+    /// The isolation level of the transactions which do not specify one.
+    pub default_isolation: IsolationLevel,
+    /// The isolation level of the transaction in progress, or `None` outside explicit
+    /// transactions, whose queries run with the default isolation level.
+    pub transaction_isolation: Option<IsolationLevel>,
+    /// Whether the transaction in progress is read-only, i.e., its queries may not modify the
+    /// graphs.
+    pub transaction_read_only: bool,
+    /// The memory, in bytes, past which the grouped aggregations of the queries spill their groups
+    /// to disk, or `None` to keep them in memory.
+    pub aggregate_memory_limit: Option<usize>,
 }
 
 impl SessionContext {
     pub fn new(database: Arc<DatabaseContext>) -> Self {
         Self {
+            overflow_policy: database.overflow_policy(),
+            division_by_zero_policy: database.division_by_zero_policy(),
+            default_isolation: database.default_isolation(),
+            aggregate_memory_limit: database.aggregate_memory_limit(),
             database,
             home_schema: None,
             current_schema: None,
             home_graph: None,
             current_graph: None,
+            progress: None,
+            float_precision: None,
+            read_timestamp: None,
+            transaction_isolation: None,
+            transaction_read_only: false,
         }
     }
 
@@ -34,6 +68,12 @@ pub fn database(&self) -> &DatabaseContext {
         &self.database
     }
 
+    /// Returns the isolation level the queries of the session run with.
+    #[inline]
+    pub fn isolation_level(&self) -> IsolationLevel {
+        self.transaction_isolation.unwrap_or(self.default_isolation)
+    }
+
     pub fn set_current_schema(&mut self, schema: SchemaRef) -> SessionResult<()> {
         match schema {
             SchemaRef::Absolute(schema_path) => {
@@ -0,0 +1,65 @@
+//! Views, i.e., named queries stored in a schema alongside its procedures.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use gql_parser::ast::CompositeQueryStatement;
+use minigu_catalog::provider::ProcedureProvider;
+use minigu_common::data_type::{DataSchemaRef, LogicalType};
+
+use crate::error::Error;
+use crate::procedure::Procedure;
+
+/// A named query, called as a procedure without arguments, e.g., `CALL v() YIELD name`.
+///
+/// A view is not executed on its own: the binder inlines its query into the calling one, which is
+/// then planned as if the query had been written there.
+#[derive(Debug)]
+pub struct View {
+    query: CompositeQueryStatement,
+    schema: DataSchemaRef,
+}
+
+impl View {
+    /// Creates a view of `query`, whose result has the given `schema`.
+    pub fn new(query: CompositeQueryStatement, schema: DataSchemaRef) -> Self {
+        Self { query, schema }
+    }
+
+    #[inline]
+    pub fn query(&self) -> &CompositeQueryStatement {
+        &self.query
+    }
+}
+
+impl ProcedureProvider for View {
+    #[inline]
+    fn parameters(&self) -> &[LogicalType] {
+        &[]
+    }
+
+    #[inline]
+    fn schema(&self) -> Option<DataSchemaRef> {
+        Some(self.schema.clone())
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Returns a catalog-modifying procedure adding `view` to the current schema as `name`, replacing
+/// the procedure of that name if `or_replace` is set.
+pub fn build_create_procedure(name: String, view: Arc<View>, or_replace: bool) -> Procedure {
+    Procedure::new(vec![], None, move |context, _| {
+        let schema = context.current_schema.ok_or(Error::CurrentSchemaNotSet)?;
+        if or_replace {
+            schema.remove_procedure(&name);
+        }
+        if !schema.add_procedure(name.clone(), view.clone()) {
+            return Err(Error::ProcedureAlreadyExists(name.clone()).into());
+        }
+        Ok(vec![])
+    })
+}
@@ -0,0 +1,840 @@
+//! Bulk loading of vertices from CSV and NDJSON files, or from CSV streams, bypassing the query
+//! layer.
+//!
+//! Each row of a CSV file encodes a vertex as written by the `export` procedure:
+//!
+//! ```csv
+//! <vid>,<prop‑1>,<prop‑2>, ...
+//! ```
+//!
+//! where the properties are those of the vertex type of the label the vertices are loaded as, in
+//! order. As with the `import` procedure, the vertices are assigned fresh ids, following the
+//! largest id of the graph, so the ids of the file are ignored.
+//!
+//! Each line of an NDJSON (JSON Lines) file is an object encoding a vertex, whose `label` field is
+//! the label of the vertex, and whose other fields are its properties, by name:
+//!
+//! ```json
+//! {"label": "person", "name": "alice", "age": 30}
+//! ```
+//!
+//! Missing and `null` fields are nulls, and the other values must match the types of their
+//! properties, e.g., strings for `String` properties and integers for `Int32` ones. Objects are
+//! the values of `Record` properties, whose fields are looked up by name, and arrays those of
+//! `List` properties.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+
+use csv::{ReaderBuilder, StringRecord};
+use minigu_catalog::label_set::LabelSet;
+use minigu_catalog::property::Property;
+use minigu_catalog::provider::{GraphProvider, GraphTypeProvider};
+use minigu_common::data_type::LogicalType;
+use minigu_common::types::LabelId;
+use minigu_common::value::{PropertyValue, ScalarValue};
+use minigu_context::graph::GraphContainer;
+use minigu_context::progress::{ProgressCallback, ProgressTracker};
+use minigu_storage::common::{PropertyRecord, Vertex};
+use minigu_storage::error::{StorageError, StorageResult};
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::procedures::export_import::import::property_to_scalar_value;
+
+/// Options of [`Database::load_csv`], [`Database::load_ndjson`] and [`Database::copy_from`].
+///
+/// [`Database::load_csv`]: crate::database::Database::load_csv
+/// [`Database::load_ndjson`]: crate::database::Database::load_ndjson
+/// [`Database::copy_from`]: crate::database::Database::copy_from
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    /// The number of rows inserted per transaction.
+    pub batch_size: usize,
+    /// The number of threads inserting batches concurrently.
+    pub parallelism: usize,
+    /// Whether the first row of a CSV file or stream is a header, which is skipped.
+    pub has_headers: bool,
+    /// The callback notified of the rows inserted, as batches are committed.
+    pub progress: Option<ProgressCallback>,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 10_000,
+            parallelism: 1,
+            has_headers: false,
+            progress: None,
+        }
+    }
+}
+
+/// A row of a file or stream which could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// The line of the row in the file or stream, starting from 1.
+    pub line: u64,
+    pub message: String,
+}
+
+/// The outcome of [`Database::load_csv`], [`Database::load_ndjson`] and [`Database::copy_from`].
+///
+/// [`Database::load_csv`]: crate::database::Database::load_csv
+/// [`Database::load_ndjson`]: crate::database::Database::load_ndjson
+/// [`Database::copy_from`]: crate::database::Database::copy_from
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    rows_loaded: usize,
+    errors: Vec<RowError>,
+}
+
+impl LoadReport {
+    /// Returns the number of rows inserted into the graph.
+    #[inline]
+    pub fn rows_loaded(&self) -> usize {
+        self.rows_loaded
+    }
+
+    /// Returns the rows which were skipped, e.g., because of a value not matching the type of its
+    /// property.
+    #[inline]
+    pub fn errors(&self) -> &[RowError] {
+        &self.errors
+    }
+}
+
+/// The label and the properties of a vertex parsed from a row, or why the row is skipped.
+type ParsedRow = std::result::Result<(LabelId, Vec<ScalarValue>), RowError>;
+
+/// Loads the rows of the CSV file at `path` into `container`, as vertices labeled `label`.
+///
+/// See [`insert_rows`] for how the rows are inserted.
+pub(crate) fn load_csv(
+    container: &GraphContainer,
+    label: &str,
+    path: &Path,
+    options: &LoadOptions,
+) -> Result<LoadReport> {
+    copy_csv(container, label, File::open(path)?, options)
+}
+
+/// Loads the CSV rows read from `reader` into `container`, as vertices labeled `label`.
+///
+/// The rows are read as they are inserted, so that at most a few batches of them are held in
+/// memory, however long the stream.
+pub(crate) fn copy_csv(
+    container: &GraphContainer,
+    label: &str,
+    reader: impl Read,
+    options: &LoadOptions,
+) -> Result<LoadReport> {
+    let graph_type = container.graph_type();
+    let (label_id, properties) = vertex_type(graph_type.as_ref(), label)?;
+    let mut reader = ReaderBuilder::new()
+        .has_headers(options.has_headers)
+        .flexible(true)
+        .from_reader(reader);
+    let rows = reader.records().map(|record| -> Result<ParsedRow> {
+        match record {
+            Ok(record) => {
+                let line = record.position().map_or(0, |p| p.line());
+                Ok(parse_record(&record, &properties)
+                    .map(|props| (label_id, props))
+                    .map_err(|message| RowError { line, message }))
+            }
+            Err(e) if e.is_io_error() => Err(e.into()),
+            Err(e) => {
+                let line = e.position().map_or(0, |p| p.line());
+                Ok(Err(RowError {
+                    line,
+                    message: e.to_string(),
+                }))
+            }
+        }
+    });
+    insert_rows(container, rows, options)
+}
+
+/// Loads the lines of the NDJSON file at `path` into `container`, as vertices labeled by their
+/// `label` field. Blank lines are skipped.
+///
+/// See [`insert_rows`] for how the rows are inserted.
+pub(crate) fn load_ndjson(
+    container: &GraphContainer,
+    path: &Path,
+    options: &LoadOptions,
+) -> Result<LoadReport> {
+    let graph_type = container.graph_type();
+    let reader = BufReader::new(File::open(path)?);
+    // The vertex types of the labels seen so far.
+    let mut vertex_types = HashMap::new();
+    let rows = reader
+        .lines()
+        .zip(1..)
+        .filter(|(line, _)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|(line, number)| -> Result<ParsedRow> {
+            let line = line?;
+            let parsed = parse_object(&line, |label| match vertex_types.entry(label.to_string()) {
+                Entry::Occupied(entry) => Ok(&*entry.into_mut()),
+                Entry::Vacant(entry) => {
+                    Ok(&*entry.insert(vertex_type(graph_type.as_ref(), label)?))
+                }
+            });
+            Ok(match parsed {
+                Ok(row) => Ok(row),
+                Err(ObjectError::Row(message)) => Err(RowError {
+                    line: number,
+                    message,
+                }),
+                Err(ObjectError::Fatal(e)) => return Err(e),
+            })
+        });
+    insert_rows(container, rows, options)
+}
+
+/// Returns the id of `label` and the properties of its vertex type.
+fn vertex_type(
+    graph_type: &dyn GraphTypeProvider,
+    label: &str,
+) -> Result<(LabelId, Vec<Property>)> {
+    let not_found = || Error::VertexTypeNotFound(label.to_string());
+    let label_id = graph_type.get_label_id(label)?.ok_or_else(not_found)?;
+    let properties = graph_type
+        .get_vertex_type(&LabelSet::from_iter([label_id]))?
+        .ok_or_else(not_found)?
+        .properties()
+        .into_iter()
+        .map(|(_, property)| property)
+        .collect();
+    Ok((label_id, properties))
+}
+
+/// Inserts the vertices parsed from `rows` into `container`, stopping at the first error reading
+/// them.
+///
+/// The rows are parsed on the calling thread, and inserted batch by batch, each within a
+/// transaction of its own, by `options.parallelism` threads. Rows which cannot be parsed are
+/// skipped and reported, while failing to insert a batch aborts the load, leaving the batches
+/// inserted so far in the graph.
+fn insert_rows(
+    container: &GraphContainer,
+    rows: impl Iterator<Item = Result<ParsedRow>>,
+    options: &LoadOptions,
+) -> Result<LoadReport> {
+    assert_ne!(options.batch_size, 0, "batches should not be empty");
+    assert_ne!(
+        options.parallelism, 0,
+        "there should be at least one thread"
+    );
+    let mut next_vid = container
+        .vertex_id_ranges(1)
+        .last()
+        .map_or(1, |range| range.end() + 1);
+
+    let progress = options.progress.as_ref().map(ProgressCallback::start);
+    let rows_loaded = AtomicUsize::new(0);
+    let failure = Mutex::new(None);
+    let mut errors = Vec::new();
+    let (sender, receiver) = mpsc::sync_channel(options.parallelism);
+    let receiver = Mutex::new(receiver);
+    std::thread::scope(|s| -> Result<()> {
+        // Dropping the sender, including on early returns, stops the threads.
+        let sender = sender;
+        for _ in 0..options.parallelism {
+            s.spawn(|| {
+                insert_batches(
+                    container,
+                    &receiver,
+                    &rows_loaded,
+                    progress.as_ref(),
+                    &failure,
+                )
+            });
+        }
+        let mut batch = Vec::with_capacity(options.batch_size);
+        for row in rows {
+            if failure.lock().unwrap().is_some() {
+                break;
+            }
+            match row? {
+                Ok((label_id, props)) => {
+                    batch.push(Vertex::new(next_vid, label_id, PropertyRecord::new(props)));
+                    next_vid += 1;
+                }
+                Err(e) => errors.push(e),
+            }
+            if batch.len() == options.batch_size {
+                let full = std::mem::replace(&mut batch, Vec::with_capacity(options.batch_size));
+                sender.send(full).expect("the receiver should be alive");
+            }
+        }
+        if !batch.is_empty() {
+            sender.send(batch).expect("the receiver should be alive");
+        }
+        Ok(())
+    })?;
+    if let Some(e) = failure.into_inner().unwrap() {
+        return Err(e.into());
+    }
+    Ok(LoadReport {
+        rows_loaded: rows_loaded.into_inner(),
+        errors,
+    })
+}
+
+/// Parses the properties of a vertex from `record`, whose first field is the id of the vertex.
+fn parse_record(
+    record: &StringRecord,
+    properties: &[Property],
+) -> std::result::Result<Vec<ScalarValue>, String> {
+    if record.len() != properties.len() + 1 {
+        return Err(format!(
+            "expected {} fields, found {}",
+            properties.len() + 1,
+            record.len()
+        ));
+    }
+    properties
+        .iter()
+        .zip(record.iter().skip(1))
+        .map(|(property, value)| {
+            property_to_scalar_value(property, value)
+                .map_err(|e| format!("invalid value `{value}` of `{}`: {e}", property.name()))
+        })
+        .collect()
+}
+
+enum ObjectError {
+    /// The line is skipped.
+    Row(String),
+    /// The load is aborted.
+    Fatal(Error),
+}
+
+impl From<String> for ObjectError {
+    fn from(message: String) -> Self {
+        Self::Row(message)
+    }
+}
+
+/// Parses the label and the properties of a vertex from the JSON object `line`, where
+/// `vertex_type` returns the id and the properties of the vertex type of a label.
+fn parse_object<'a>(
+    line: &str,
+    vertex_type: impl FnOnce(&str) -> Result<&'a (LabelId, Vec<Property>)>,
+) -> std::result::Result<(LabelId, Vec<ScalarValue>), ObjectError> {
+    let mut object = match serde_json::from_str(line) {
+        Ok(Value::Object(object)) => object,
+        Ok(_) => return Err("expected an object".to_string().into()),
+        Err(e) => return Err(e.to_string().into()),
+    };
+    let label = match object.remove("label") {
+        Some(Value::String(label)) => label,
+        Some(_) => return Err("`label` should be a string".to_string().into()),
+        None => return Err("missing `label`".to_string().into()),
+    };
+    let (label_id, properties) = match vertex_type(&label) {
+        Ok(vertex_type) => vertex_type,
+        Err(Error::VertexTypeNotFound(label)) => {
+            return Err(format!("vertex type `{label}` not found").into());
+        }
+        Err(e) => return Err(ObjectError::Fatal(e)),
+    };
+    let props = properties
+        .iter()
+        .map(|property| {
+            let value = object.remove(property.name()).unwrap_or(Value::Null);
+            json_to_scalar_value(property, value)
+                .map_err(|e| format!("invalid value of `{}`: {e}", property.name()))
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if let Some(name) = object.keys().next() {
+        return Err(format!("unknown property `{name}` of `{label}`").into());
+    }
+    Ok((*label_id, props))
+}
+
+/// Converts a JSON value into a [`ScalarValue`] of the type of `property`.
+fn json_to_scalar_value(
+    property: &Property,
+    value: Value,
+) -> std::result::Result<ScalarValue, String> {
+    json_to_value(property.logical_type(), property.nullable(), value)
+}
+
+/// Converts a JSON value into a [`ScalarValue`] of type `ty`. Objects are converted into records,
+/// whose fields are looked up by name, and arrays into lists.
+fn json_to_value(
+    ty: &LogicalType,
+    nullable: bool,
+    value: Value,
+) -> std::result::Result<ScalarValue, String> {
+    match (value, ty) {
+        (Value::Null, _) if nullable => {
+            ScalarValue::null_of(ty).ok_or_else(|| format!("unsupported type {ty}"))
+        }
+        (Value::Null, _) => Err("null value for non-nullable property".to_string()),
+        (Value::String(s), LogicalType::String) => Ok(ScalarValue::String(Some(s))),
+        (Value::Object(mut object), LogicalType::Record(fields)) => {
+            let values = fields
+                .iter()
+                .map(|field| {
+                    let value = object.remove(field.name()).unwrap_or(Value::Null);
+                    let value = json_to_value(field.ty(), field.is_nullable(), value)
+                        .map_err(|e| format!("invalid value of field `{}`: {e}", field.name()))?;
+                    Ok(PropertyValue::new(field.name().to_string(), value))
+                })
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+            if let Some(name) = object.keys().next() {
+                return Err(format!("unknown field `{name}`"));
+            }
+            Ok(ScalarValue::Record(Some(values)))
+        }
+        (Value::Array(values), LogicalType::List(element)) => {
+            let values = values
+                .into_iter()
+                .map(|value| json_to_value(element, true, value))
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+            Ok(ScalarValue::List(Some(values)))
+        }
+        (value @ (Value::Bool(_) | Value::Number(_)), _)
+            if !matches!(
+                ty,
+                LogicalType::String | LogicalType::Record(_) | LogicalType::List(_)
+            ) =>
+        {
+            ScalarValue::parse(&value.to_string(), ty).map_err(|e| e.to_string())
+        }
+        (value, _) => Err(format!("expected a value of type {ty}, found {value}")),
+    }
+}
+
+/// Inserts the batches of `receiver` until it is disconnected, or until a batch fails to be
+/// inserted, in which case the error is recorded in `failure` and the next batches are dropped.
+fn insert_batches(
+    container: &GraphContainer,
+    receiver: &Mutex<Receiver<Vec<Vertex>>>,
+    rows_loaded: &AtomicUsize,
+    progress: Option<&ProgressTracker>,
+    failure: &Mutex<Option<StorageError>>,
+) {
+    loop {
+        let Ok(batch) = receiver.lock().unwrap().recv() else {
+            return;
+        };
+        if failure.lock().unwrap().is_some() {
+            continue;
+        }
+        match insert_batch(container, batch) {
+            Ok(num_rows) => {
+                rows_loaded.fetch_add(num_rows, Ordering::Relaxed);
+                if let Some(progress) = progress {
+                    progress.advance(num_rows);
+                }
+            }
+            Err(e) => {
+                failure.lock().unwrap().get_or_insert(e);
+            }
+        }
+    }
+}
+
+fn insert_batch(container: &GraphContainer, batch: Vec<Vertex>) -> StorageResult<usize> {
+    let writer = container.writer()?;
+    let num_rows = batch.len();
+    for vertex in batch {
+        if let Err(e) = writer.create_vertex(vertex) {
+            writer.abort()?;
+            return Err(e);
+        }
+    }
+    writer.commit()?;
+    Ok(num_rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use minigu_storage::wal::graph_wal::Durability;
+
+    use super::*;
+    use crate::database::{Database, DatabaseConfig};
+    use crate::session::Session;
+
+    const NUM_ROWS: usize = 100_000;
+
+    fn database() -> Database {
+        let config = DatabaseConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        Database::open_in_memory(&config).unwrap()
+    }
+
+    /// The manifest of the `import` procedure for a graph of `person` vertices read from `file`.
+    fn manifest(file: &str) -> String {
+        format!(
+            r#"{{
+                "vertices": [{{
+                    "label": "person",
+                    "file": {{ "path": "{file}", "format": "csv" }},
+                    "properties": [
+                        {{ "name": "name", "logical_type": "String", "nullable": false }},
+                        {{ "name": "age", "logical_type": "Int32", "nullable": true }}
+                    ]
+                }}],
+                "edges": []
+            }}"#
+        )
+    }
+
+    fn count_persons(session: &mut Session, graph_name: &str) -> usize {
+        session
+            .query_on(graph_name, "MATCH (n:person) RETURN n")
+            .unwrap()
+            .num_rows()
+    }
+
+    #[test]
+    fn test_load_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut persons = String::new();
+        for i in 0..NUM_ROWS {
+            writeln!(persons, "{i},person_{i},{}", i % 100).unwrap();
+        }
+        std::fs::write(dir.path().join("person.csv"), persons).unwrap();
+        std::fs::write(dir.path().join("empty.csv"), "").unwrap();
+        std::fs::write(dir.path().join("manifest.json"), manifest("person.csv")).unwrap();
+        std::fs::write(dir.path().join("empty.json"), manifest("empty.csv")).unwrap();
+
+        let db = database();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!(
+                "CALL import('queried', '{dir_path}', 'manifest.json')"
+            ))
+            .unwrap();
+        session
+            .query(&format!("CALL import('bulk', '{dir_path}', 'empty.json')"))
+            .unwrap();
+
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let progress = {
+            let invocations = invocations.clone();
+            ProgressCallback::new(10_000, move |_| {
+                invocations.fetch_add(1, Ordering::Relaxed);
+            })
+        };
+        let options = LoadOptions {
+            batch_size: 8192,
+            parallelism: 4,
+            progress: Some(progress),
+            ..Default::default()
+        };
+        let report = db
+            .load_csv("bulk", "person", dir.path().join("person.csv"), &options)
+            .unwrap();
+        assert_eq!(report.rows_loaded(), NUM_ROWS);
+        assert!(report.errors().is_empty());
+        assert_eq!(invocations.load(Ordering::Relaxed), NUM_ROWS / 10_000);
+        assert_eq!(count_persons(&mut session, "queried"), NUM_ROWS);
+        assert_eq!(count_persons(&mut session, "bulk"), NUM_ROWS);
+    }
+
+    #[test]
+    fn test_load_csv_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("person.csv");
+        std::fs::write(&path, "1,alice,30\n2,bob,old\n3,carol\n4,dave,\n").unwrap();
+        std::fs::write(dir.path().join("empty.csv"), "").unwrap();
+        std::fs::write(dir.path().join("empty.json"), manifest("empty.csv")).unwrap();
+
+        let db = database();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!("CALL import('bulk', '{dir_path}', 'empty.json')"))
+            .unwrap();
+        let report = db
+            .load_csv("bulk", "person", &path, &LoadOptions::default())
+            .unwrap();
+        assert_eq!(report.rows_loaded(), 2);
+        let lines: Vec<_> = report.errors().iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 3]);
+        assert_eq!(count_persons(&mut session, "bulk"), 2);
+
+        let err = db
+            .load_csv("bulk", "company", &path, &LoadOptions::default())
+            .unwrap_err();
+        assert!(matches!(err, Error::VertexTypeNotFound(label) if label == "company"));
+    }
+
+    #[test]
+    fn test_load_ndjson() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("person.ndjson");
+        std::fs::write(
+            &path,
+            r#"{"label": "person", "name": "alice", "age": 30}
+{"label": "person", "name": "bob", "age": null}
+{"label": "person", "name": "carol"}
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("empty.csv"), "").unwrap();
+        std::fs::write(dir.path().join("empty.json"), manifest("empty.csv")).unwrap();
+
+        let db = database();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!("CALL import('bulk', '{dir_path}', 'empty.json')"))
+            .unwrap();
+        let report = db
+            .load_ndjson("bulk", &path, &LoadOptions::default())
+            .unwrap();
+        assert_eq!(report.rows_loaded(), 3);
+        assert!(report.errors().is_empty());
+
+        let result = session
+            .query_on(
+                "bulk",
+                "MATCH (n:person) RETURN n.name AS name, n.age AS age",
+            )
+            .unwrap();
+        let schema = result.schema().unwrap();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.ty().clone()).collect();
+        assert_eq!(types, [LogicalType::String, LogicalType::Int32]);
+        let mut rows: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .map(|row| match (row.get(0), row.get(1)) {
+                (Some(ScalarValue::String(Some(name))), Some(ScalarValue::Int32(age))) => {
+                    (name.clone(), *age)
+                }
+                row => panic!("unexpected row {row:?}"),
+            })
+            .collect();
+        rows.sort();
+        assert_eq!(
+            rows,
+            [
+                ("alice".to_string(), Some(30)),
+                ("bob".to_string(), None),
+                ("carol".to_string(), None),
+            ]
+        );
+
+        std::fs::write(
+            &path,
+            r#"{"label": "person", "name": "dave", "age": "old"}
+
+{"label": "company", "name": "acme"}
+{"label": "person", "name": {"first": "erin"}}
+{"label": "person", "name": "frank", "age": 52}
+"#,
+        )
+        .unwrap();
+        let report = db
+            .load_ndjson("bulk", &path, &LoadOptions::default())
+            .unwrap();
+        assert_eq!(report.rows_loaded(), 1);
+        let lines: Vec<_> = report.errors().iter().map(|e| e.line).collect();
+        assert_eq!(lines, [1, 3, 4]);
+        assert_eq!(
+            report.errors()[2].message,
+            r#"invalid value of `name`: expected a value of type string, found {"first":"erin"}"#
+        );
+        assert_eq!(count_persons(&mut session, "bulk"), 4);
+    }
+
+    #[test]
+    fn test_load_nested_ndjson() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("person.ndjson");
+        std::fs::write(
+            &path,
+            r#"{"label": "person", "address": {"city": "paris", "zip": 75001}, "tags": ["a", null]}
+{"label": "person", "address": {"city": "rome"}, "tags": []}
+{"label": "person"}
+{"label": "person", "address": {"zip": 1}}
+{"label": "person", "address": {"city": "oslo", "country": "norway"}}
+{"label": "person", "tags": [1]}
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("empty.csv"), "").unwrap();
+        let manifest = r#"{
+            "vertices": [{
+                "label": "person",
+                "file": { "path": "empty.csv", "format": "csv" },
+                "properties": [
+                    {
+                        "name": "address",
+                        "logical_type": { "Record": [
+                            { "name": "city", "ty": "String", "nullable": false },
+                            { "name": "zip", "ty": "Int32", "nullable": true }
+                        ] },
+                        "nullable": true
+                    },
+                    { "name": "tags", "logical_type": { "List": "String" }, "nullable": true }
+                ]
+            }],
+            "edges": []
+        }"#;
+        std::fs::write(dir.path().join("empty.json"), manifest).unwrap();
+
+        let db = database();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!("CALL import('bulk', '{dir_path}', 'empty.json')"))
+            .unwrap();
+        let report = db
+            .load_ndjson("bulk", &path, &LoadOptions::default())
+            .unwrap();
+        assert_eq!(report.rows_loaded(), 3);
+        let errors: Vec<_> = report
+            .errors()
+            .iter()
+            .map(|e| (e.line, e.message.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (
+                    4,
+                    "invalid value of `address`: invalid value of field `city`: null value for \
+                     non-nullable property"
+                ),
+                (5, "invalid value of `address`: unknown field `country`"),
+                (
+                    6,
+                    "invalid value of `tags`: expected a value of type string, found 1"
+                ),
+            ]
+        );
+
+        let result = session
+            .query_on("bulk", "MATCH (n:person) RETURN n.address, n.tags")
+            .unwrap();
+        let mut rows: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned().into_inner()))
+            .collect();
+        rows.sort_by_cached_key(|row| format!("{}", row[0]));
+        let record = |city: &str, zip: Option<i32>| {
+            ScalarValue::Record(Some(vec![
+                PropertyValue::new("city".into(), city.into()),
+                PropertyValue::new("zip".into(), ScalarValue::Int32(zip)),
+            ]))
+        };
+        assert_eq!(
+            rows,
+            [
+                vec![ScalarValue::Record(None), ScalarValue::List(None)],
+                vec![
+                    record("paris", Some(75001)),
+                    ScalarValue::List(Some(vec!["a".into(), ScalarValue::String(None)])),
+                ],
+                vec![record("rome", None), ScalarValue::List(Some(vec![]))],
+            ]
+        );
+    }
+
+    /// A reader returning at most 7 bytes at a time, as a network connection may.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_copy_from() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("empty.csv"), "").unwrap();
+        std::fs::write(dir.path().join("empty.json"), manifest("empty.csv")).unwrap();
+        let db = database();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!("CALL import('bulk', '{dir_path}', 'empty.json')"))
+            .unwrap();
+
+        let mut persons = String::from("vid,name,age\n");
+        for i in 0..1000 {
+            writeln!(persons, "{i},person_{i},{}", i % 100).unwrap();
+        }
+        persons.push_str("1000,person_1000,old\n");
+        let options = LoadOptions {
+            batch_size: 64,
+            has_headers: true,
+            ..Default::default()
+        };
+        let report = db
+            .copy_from("bulk", "person", Trickle(persons.as_bytes()), &options)
+            .unwrap();
+        assert_eq!(report.rows_loaded(), 1000);
+        let lines: Vec<_> = report.errors().iter().map(|e| e.line).collect();
+        assert_eq!(lines, [1002]);
+        assert_eq!(count_persons(&mut session, "bulk"), 1000);
+
+        let report = db
+            .copy_from("bulk", "person", "1,alice,30\n".as_bytes(), &options)
+            .unwrap();
+        // The first row is taken as the header.
+        assert_eq!(report.rows_loaded(), 0);
+        assert_eq!(count_persons(&mut session, "bulk"), 1000);
+    }
+
+    #[test]
+    fn test_analyze_after_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut persons = String::new();
+        for i in 0..1000 {
+            writeln!(persons, "{i},person_{i},{}", i % 100).unwrap();
+        }
+        std::fs::write(dir.path().join("person.csv"), persons).unwrap();
+        std::fs::write(dir.path().join("empty.csv"), "").unwrap();
+        std::fs::write(dir.path().join("empty.json"), manifest("empty.csv")).unwrap();
+
+        let config = DatabaseConfig::builder()
+            .durability(Durability::None)
+            .auto_analyze_interval(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+        let db = Database::open_in_memory(&config).unwrap();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!("CALL import('bulk', '{dir_path}', 'empty.json')"))
+            .unwrap();
+        session.query("SESSION SET GRAPH bulk").unwrap();
+        db.load_csv(
+            "bulk",
+            "person",
+            dir.path().join("person.csv"),
+            &LoadOptions::default(),
+        )
+        .unwrap();
+        // The statistics were refreshed by the load, without any explicit analysis.
+        let plan = session.explain("MATCH (n:person) RETURN n").unwrap();
+        assert!(plan.contains("estimated rows: 1000"), "{plan}");
+    }
+}
@@ -1,32 +1,282 @@
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, SystemTime};
 
 use minigu_catalog::memory::MemoryCatalog;
 use minigu_catalog::memory::directory::MemoryDirectoryCatalog;
+use minigu_catalog::memory::graph_type::MemoryGraphTypeCatalog;
 use minigu_catalog::memory::schema::MemorySchemaCatalog;
-use minigu_catalog::provider::{CatalogProvider, DirectoryOrSchema, SchemaRef};
+use minigu_catalog::provider::{
+    CatalogProvider, DirectoryOrSchema, GraphProvider, GraphRef, SchemaProvider, SchemaRef,
+};
+use minigu_common::arithmetic::{DivisionByZeroPolicy, OverflowPolicy};
 use minigu_common::constants::DEFAULT_SCHEMA_NAME;
 use minigu_context::database::DatabaseContext;
+use minigu_context::error::Error as SessionError;
+use minigu_context::function::{Aggregate, Function, UserAggregate};
+use minigu_context::graph::{GraphContainer, GraphStorage, GraphWriter};
+use minigu_storage::common::changefeed::CommittedDeltas;
+use minigu_storage::common::statistics::VertexCounts;
+use minigu_storage::error::StorageResult;
+use minigu_storage::tp::MemoryGraph;
+use minigu_storage::wal::graph_wal::Durability;
+use minigu_transaction::IsolationLevel;
 use rayon::ThreadPoolBuilder;
 
-use crate::error::Result;
+use crate::bulk_load::{self, LoadOptions, LoadReport};
+use crate::error::{Error, Result};
 use crate::procedures::build_predefined_procedures;
 use crate::session::Session;
 
+/// The delay before the first retry of [`Database::with_retry`], doubled before each next one.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(1);
+
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
     pub num_threads: usize,
+    /// The number of threads a vertex scan is split across. Scans are serial if this is 1.
+    pub parallelism: usize,
+    /// Whether the graphs created in the database log their transactions to a WAL. Graphs created
+    /// with [`Durability::None`] cannot be recovered.
+    pub durability: Durability,
+    /// The directory under which each graph created in the database keeps its WAL, in a file named
+    /// after it, or `None` for the default one. Only meaningful with [`Durability::Wal`].
+    pub wal_dir: Option<PathBuf>,
+    /// The directory under which each graph created in the database keeps its checkpoints, in a
+    /// subdirectory named after it, or `None` for the default one.
+    pub checkpoint_dir: Option<PathBuf>,
+    /// How often a background thread deletes the expired vertices of the graphs in the default
+    /// schema, i.e., those whose vertex type has a [`Ttl`](minigu_catalog::ttl::Ttl) which has
+    /// elapsed. If `None`, expired vertices are only deleted by
+    /// [`Database::sweep_expired_vertices`].
+    pub ttl_sweep_interval: Option<Duration>,
+    /// How the integer additions, subtractions and multiplications of queries resolve overflows,
+    /// unless overridden by [`Session::set_overflow_policy`].
+    pub overflow_policy: OverflowPolicy,
+    /// How the integer divisions and remainders by zero of queries are resolved, unless
+    /// overridden by [`Session::set_division_by_zero_policy`].
+    pub division_by_zero_policy: DivisionByZeroPolicy,
+    /// The isolation level of the transactions which do not specify one, unless overridden by
+    /// [`Session::set_default_isolation`].
+    pub default_isolation: IsolationLevel,
+    /// The graph created in the default schema when the database is opened, if any. It is then
+    /// the home graph of every session, i.e., their current graph until they set another one.
+    pub default_graph: Option<DefaultGraph>,
+    /// How often a background thread refreshes the stale statistics of the graphs in the default
+    /// schema (see [`stale_statistics_tolerance`](Self::stale_statistics_tolerance)), which are
+    /// then also refreshed after each bulk load. If `None`, statistics are only collected by the
+    /// `analyze` procedure and [`Database::analyze`].
+    pub auto_analyze_interval: Option<Duration>,
+    /// The number of modifications, relative to the number of vertices, after which the
+    /// statistics of a graph are stale, e.g., 0.1 for 10% of its vertices. Only meaningful with
+    /// [`auto_analyze_interval`](Self::auto_analyze_interval).
+    pub stale_statistics_tolerance: f64,
+    /// The memory, in bytes, past which grouped aggregations spill their groups to disk, unless
+    /// overridden by [`Session::set_aggregate_memory_limit`]. If `None`, groups are kept in
+    /// memory.
+    pub aggregate_memory_limit: Option<usize>,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
-        Self { num_threads: 1 }
+        Self {
+            num_threads: 1,
+            parallelism: 1,
+            durability: Durability::default(),
+            wal_dir: None,
+            checkpoint_dir: None,
+            ttl_sweep_interval: None,
+            overflow_policy: OverflowPolicy::default(),
+            division_by_zero_policy: DivisionByZeroPolicy::default(),
+            default_isolation: IsolationLevel::Serializable,
+            default_graph: None,
+            auto_analyze_interval: None,
+            stale_statistics_tolerance: 0.1,
+            aggregate_memory_limit: None,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// Returns a builder of a configuration, starting from the default one.
+    pub fn builder() -> DatabaseConfigBuilder {
+        DatabaseConfigBuilder::default()
+    }
+}
+
+/// A graph created along with a database (see [`DatabaseConfig::default_graph`]).
+#[derive(Debug, Clone)]
+pub struct DefaultGraph {
+    pub name: String,
+    /// The type of the graph, or `None` for a graph without any vertex or edge type.
+    pub graph_type: Option<Arc<MemoryGraphTypeCatalog>>,
+}
+
+impl DefaultGraph {
+    /// Returns a graph named `name`, without any vertex or edge type.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            graph_type: None,
+        }
+    }
+
+    /// Sets the type of the graph.
+    pub fn with_graph_type(mut self, graph_type: Arc<MemoryGraphTypeCatalog>) -> Self {
+        self.graph_type = Some(graph_type);
+        self
+    }
+}
+
+/// Builds a [`DatabaseConfig`], checking that its settings are consistent with each other.
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseConfigBuilder {
+    config: DatabaseConfig,
+}
+
+impl DatabaseConfigBuilder {
+    /// Sets the number of threads queries are run on, or 0 for one per CPU.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.config.num_threads = num_threads;
+        self
+    }
+
+    /// Sets the number of threads a vertex scan is split across, which must be at least 1.
+    pub fn parallelism(mut self, parallelism: usize) -> Self {
+        self.config.parallelism = parallelism;
+        self
+    }
+
+    /// Sets whether the graphs created in the database log their transactions to a WAL.
+    pub fn durability(mut self, durability: Durability) -> Self {
+        self.config.durability = durability;
+        self
+    }
+
+    /// Sets the directory under which the graphs created in the database keep their WALs, which
+    /// requires [`Durability::Wal`].
+    pub fn wal_dir(mut self, wal_dir: impl Into<PathBuf>) -> Self {
+        self.config.wal_dir = Some(wal_dir.into());
+        self
+    }
+
+    /// Sets the directory under which the graphs created in the database keep their checkpoints.
+    pub fn checkpoint_dir(mut self, checkpoint_dir: impl Into<PathBuf>) -> Self {
+        self.config.checkpoint_dir = Some(checkpoint_dir.into());
+        self
+    }
+
+    /// Sets how often the expired vertices are deleted in the background, which must not be zero.
+    pub fn ttl_sweep_interval(mut self, interval: Duration) -> Self {
+        self.config.ttl_sweep_interval = Some(interval);
+        self
+    }
+
+    /// Sets how the integer arithmetic of queries resolves overflows.
+    pub fn overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.config.overflow_policy = policy;
+        self
+    }
+
+    /// Sets how the integer divisions and remainders by zero of queries are resolved.
+    pub fn division_by_zero_policy(mut self, policy: DivisionByZeroPolicy) -> Self {
+        self.config.division_by_zero_policy = policy;
+        self
+    }
+
+    /// Sets the isolation level of the transactions which do not specify one.
+    pub fn default_isolation(mut self, isolation: IsolationLevel) -> Self {
+        self.config.default_isolation = isolation;
+        self
+    }
+
+    /// Sets the graph created when the database is opened, whose name must not be empty.
+    pub fn default_graph(mut self, graph: DefaultGraph) -> Self {
+        self.config.default_graph = Some(graph);
+        self
+    }
+
+    /// Sets how often the stale statistics are refreshed in the background, which must not be
+    /// zero. Statistics are then also refreshed after each bulk load.
+    pub fn auto_analyze_interval(mut self, interval: Duration) -> Self {
+        self.config.auto_analyze_interval = Some(interval);
+        self
+    }
+
+    /// Sets the number of modifications, relative to the number of vertices, after which the
+    /// statistics of a graph are stale, which must be finite and non-negative.
+    pub fn stale_statistics_tolerance(mut self, tolerance: f64) -> Self {
+        self.config.stale_statistics_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the memory, in bytes, past which grouped aggregations spill their groups to disk,
+    /// which must not be zero.
+    pub fn aggregate_memory_limit(mut self, memory_limit: usize) -> Self {
+        self.config.aggregate_memory_limit = Some(memory_limit);
+        self
+    }
+
+    /// Returns the configuration, or [`Error::InvalidConfig`] if some of its settings conflict.
+    pub fn build(self) -> Result<DatabaseConfig> {
+        let config = self.config;
+        if config.parallelism == 0 {
+            return Err(Error::InvalidConfig(
+                "parallelism must be at least 1".into(),
+            ));
+        }
+        if config.ttl_sweep_interval == Some(Duration::ZERO) {
+            return Err(Error::InvalidConfig(
+                "the TTL sweep interval must not be zero".into(),
+            ));
+        }
+        if config.auto_analyze_interval == Some(Duration::ZERO) {
+            return Err(Error::InvalidConfig(
+                "the auto-analyze interval must not be zero".into(),
+            ));
+        }
+        if !(config.stale_statistics_tolerance.is_finite()
+            && config.stale_statistics_tolerance >= 0.0)
+        {
+            return Err(Error::InvalidConfig(
+                "the stale statistics tolerance must be finite and non-negative".into(),
+            ));
+        }
+        if config.aggregate_memory_limit == Some(0) {
+            return Err(Error::InvalidConfig(
+                "the aggregate memory limit must not be zero".into(),
+            ));
+        }
+        if config.wal_dir.is_some() && config.durability == Durability::None {
+            return Err(Error::InvalidConfig(
+                "a WAL directory is set, but the durability is `None`".into(),
+            ));
+        }
+        if let Some(graph) = &config.default_graph {
+            if graph.name.is_empty() {
+                return Err(Error::InvalidConfig(
+                    "the default graph must have a name".into(),
+                ));
+            }
+        }
+        Ok(config)
     }
 }
 
 pub struct Database {
     context: Arc<DatabaseContext>,
     default_schema: Arc<MemorySchemaCatalog>,
+    /// The name of the graph created when the database was opened, if any.
+    default_graph: Option<String>,
+    /// The tolerance of the statistics refreshed after bulk loads, or `None` if statistics are not
+    /// collected automatically.
+    auto_analyze_tolerance: Option<f64>,
+    /// Stops the background TTL sweeper, if any, once dropped along with the database.
+    _stop_sweeper: Option<Sender<()>>,
+    /// Stops the background statistics refresher, if any, once dropped along with the database.
+    _stop_analyzer: Option<Sender<()>>,
 }
 
 impl Database {
@@ -39,15 +289,221 @@ pub fn open_in_memory(config: &DatabaseConfig) -> Result<Self> {
         let runtime = ThreadPoolBuilder::new()
             .num_threads(config.num_threads)
             .build()?;
-        let context = Arc::new(DatabaseContext::new(catalog, runtime));
+        let mut context = DatabaseContext::new(catalog, runtime)
+            .with_durability(config.durability)
+            .with_overflow_policy(config.overflow_policy)
+            .with_division_by_zero_policy(config.division_by_zero_policy)
+            .with_default_isolation(config.default_isolation);
+        if let Some(wal_dir) = &config.wal_dir {
+            context = context.with_wal_dir(wal_dir.clone());
+        }
+        if let Some(checkpoint_dir) = &config.checkpoint_dir {
+            context = context.with_checkpoint_dir(checkpoint_dir.clone());
+        }
+        if let Some(memory_limit) = config.aggregate_memory_limit {
+            context = context.with_aggregate_memory_limit(memory_limit);
+        }
+        if config.parallelism > 1 {
+            // Scans get their own threads, so that they never wait for the queries consuming them.
+            let scan_runtime = ThreadPoolBuilder::new()
+                .num_threads(config.parallelism)
+                .thread_name(|i| format!("minigu-scan-{i}"))
+                .build()?;
+            context = context.with_scan_runtime(scan_runtime);
+        }
+        if let Some(graph) = &config.default_graph {
+            create_default_graph(&context, &default_schema, graph);
+        }
+        let context = Arc::new(context);
+        let _stop_sweeper = config
+            .ttl_sweep_interval
+            .map(|interval| spawn_ttl_sweeper(default_schema.clone(), interval));
+        let tolerance = config.stale_statistics_tolerance;
+        let _stop_analyzer = config
+            .auto_analyze_interval
+            .map(|interval| spawn_analyzer(default_schema.clone(), interval, tolerance));
         Ok(Self {
             context,
             default_schema,
+            default_graph: config
+                .default_graph
+                .as_ref()
+                .map(|graph| graph.name.clone()),
+            auto_analyze_tolerance: config.auto_analyze_interval.map(|_| tolerance),
+            _stop_sweeper,
+            _stop_analyzer,
         })
     }
 
     pub fn session(&self) -> Result<Session> {
-        Session::new(self.context.clone(), self.default_schema().clone())
+        Session::new(
+            self.context.clone(),
+            self.default_schema().clone(),
+            self.default_graph.as_deref(),
+        )
+    }
+
+    /// Registers `function` as a scalar function named `name`, which queries of every session can
+    /// then call, e.g., `RETURN name(1)`. The name must not be a reserved word, such as `double`,
+    /// which queries cannot call. Returns `false` if a function of that name already exists.
+    pub fn register_function(&self, name: impl Into<String>, function: Function) -> bool {
+        self.context
+            .functions()
+            .register(name.into(), Arc::new(function))
+    }
+
+    /// Registers `aggregate` as an aggregate function named `name`. Returns `false` if an aggregate
+    /// of that name already exists.
+    pub fn register_aggregate<A: Aggregate>(&self, name: impl Into<String>, aggregate: A) -> bool {
+        self.context
+            .functions()
+            .register_aggregate(name.into(), Arc::new(UserAggregate::new(aggregate)))
+    }
+
+    /// Runs `f` within a transaction on the graph named `graph_name` in the default schema, then
+    /// commits the transaction.
+    ///
+    /// If `f` or the commit fails with a
+    /// [serialization conflict](minigu_storage::error::StorageError::is_serialization_conflict),
+    /// the transaction is aborted and `f` is run again within a new one, after a delay growing
+    /// exponentially, for up to `max_attempts` attempts in total. `f` should thus have no effect
+    /// outside of the transaction. Other errors, and the conflict of the last attempt, are returned
+    /// right away.
+    pub fn with_retry<T, F>(&self, graph_name: &str, mut f: F, max_attempts: usize) -> Result<T>
+    where
+        F: FnMut(&GraphWriter) -> StorageResult<T>,
+    {
+        assert!(max_attempts > 0, "at least one attempt must be made");
+        let mut backoff = INITIAL_RETRY_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            let writer = self.writer(graph_name)?;
+            let result = match f(&writer) {
+                // A failed commit aborts the transaction by itself.
+                Ok(value) => writer.commit().map(|_| value),
+                Err(e) => {
+                    writer.abort()?;
+                    Err(e)
+                }
+            };
+            match result {
+                Err(e) if e.is_serialization_conflict() && attempt < max_attempts => {
+                    std::thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return Ok(result?),
+            }
+        }
+    }
+
+    /// Deletes the expired vertices of the graphs in the default schema, along with their edges.
+    /// Returns the number of deleted vertices.
+    ///
+    /// See [`GraphContainer::sweep_expired_vertices`].
+    pub fn sweep_expired_vertices(&self) -> Result<usize> {
+        sweep_expired_vertices(&self.default_schema, SystemTime::now())
+    }
+
+    /// Loads the vertices of the CSV file at `path` into the graph named `graph_name` in the
+    /// default schema, labeled `label`, bypassing the query layer.
+    ///
+    /// See [`bulk_load`](crate::bulk_load) for the format of the file.
+    pub fn load_csv<P: AsRef<Path>>(
+        &self,
+        graph_name: &str,
+        label: &str,
+        path: P,
+        options: &LoadOptions,
+    ) -> Result<LoadReport> {
+        let graph = self.graph(graph_name)?;
+        let report = bulk_load::load_csv(container(&graph), label, path.as_ref(), options)?;
+        self.analyze_after_load(&graph)?;
+        Ok(report)
+    }
+
+    /// Loads the vertices of the NDJSON file at `path` into the graph named `graph_name` in the
+    /// default schema, bypassing the query layer.
+    ///
+    /// See [`bulk_load`](crate::bulk_load) for the format of the file.
+    pub fn load_ndjson<P: AsRef<Path>>(
+        &self,
+        graph_name: &str,
+        path: P,
+        options: &LoadOptions,
+    ) -> Result<LoadReport> {
+        let graph = self.graph(graph_name)?;
+        let report = bulk_load::load_ndjson(container(&graph), path.as_ref(), options)?;
+        self.analyze_after_load(&graph)?;
+        Ok(report)
+    }
+
+    /// Loads the vertices of the CSV rows read from `reader` into the graph named `graph_name` in
+    /// the default schema, labeled `label`, bypassing the query layer.
+    ///
+    /// Unlike [`load_csv`](Self::load_csv), the rows are inserted as they are read, batch by batch,
+    /// without waiting for the end of the stream, e.g., that of a network connection. See
+    /// [`bulk_load`](crate::bulk_load) for the format of the rows.
+    pub fn copy_from<R: Read>(
+        &self,
+        graph_name: &str,
+        label: &str,
+        reader: R,
+        options: &LoadOptions,
+    ) -> Result<LoadReport> {
+        let graph = self.graph(graph_name)?;
+        let report = bulk_load::copy_csv(container(&graph), label, reader, options)?;
+        self.analyze_after_load(&graph)?;
+        Ok(report)
+    }
+
+    /// Collects the statistics of the graph named `graph_name` in the default schema, which the
+    /// optimizer then estimates the cardinality of the scans from. Returns the vertex counts.
+    pub fn analyze(&self, graph_name: &str) -> Result<VertexCounts> {
+        let graph = self.graph(graph_name)?;
+        let GraphStorage::Memory(graph) = container(&graph).graph_storage();
+        Ok(graph.analyze()?)
+    }
+
+    /// Refreshes the statistics of `graph` after a bulk load, if they are collected automatically
+    /// and have become stale.
+    fn analyze_after_load(&self, graph: &GraphRef) -> Result<()> {
+        if let Some(tolerance) = self.auto_analyze_tolerance {
+            let GraphStorage::Memory(graph) = container(graph).graph_storage();
+            analyze_if_stale(graph, tolerance)?;
+        }
+        Ok(())
+    }
+
+    /// Subscribes to the modifications committed to the graph named `graph_name` in the default
+    /// schema from now on, e.g., to mirror them downstream.
+    ///
+    /// The modifications of each transaction are received in commit order. Those of up to
+    /// `capacity` transactions are buffered until they are received, after which commits wait for
+    /// the subscriber to catch up. The subscription ends once the receiver is dropped.
+    pub fn subscribe(
+        &self,
+        graph_name: &str,
+        capacity: usize,
+    ) -> Result<Receiver<CommittedDeltas>> {
+        let graph = self.graph(graph_name)?;
+        let GraphStorage::Memory(graph) = container(&graph).graph_storage();
+        Ok(graph.subscribe(capacity))
+    }
+
+    /// Returns a writer of the graph named `graph_name` in the default schema.
+    fn writer(&self, graph_name: &str) -> Result<GraphWriter> {
+        let graph = self.graph(graph_name)?;
+        Ok(container(&graph).writer()?)
+    }
+
+    /// Returns the graph named `graph_name` in the default schema.
+    fn graph(&self, graph_name: &str) -> Result<GraphRef> {
+        let graph = self
+            .default_schema
+            .get_graph(graph_name)?
+            .ok_or_else(|| SessionError::GraphNotExists(graph_name.to_string()))?;
+        Ok(graph)
     }
 
     fn default_schema(&self) -> &Arc<MemorySchemaCatalog> {
@@ -55,6 +511,118 @@ fn default_schema(&self) -> &Arc<MemorySchemaCatalog> {
     }
 }
 
+fn container(graph: &GraphRef) -> &GraphContainer {
+    graph
+        .as_any()
+        .downcast_ref::<GraphContainer>()
+        .expect("graph should be a graph container")
+}
+
+fn sweep_expired_vertices(schema: &MemorySchemaCatalog, now: SystemTime) -> Result<usize> {
+    let mut num_deleted = 0;
+    for name in schema.graph_names() {
+        let Some(graph) = schema.get_graph(&name)? else {
+            continue;
+        };
+        if let Some(container) = graph.as_any().downcast_ref::<GraphContainer>() {
+            num_deleted += container.sweep_expired_vertices(now)?;
+        }
+    }
+    Ok(num_deleted)
+}
+
+/// Spawns a thread sweeping the expired vertices of the graphs in `schema` every `interval`, until
+/// the returned sender is dropped.
+fn spawn_ttl_sweeper(schema: Arc<MemorySchemaCatalog>, interval: Duration) -> Sender<()> {
+    let (stop, stopped) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("minigu-ttl-sweeper".into())
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                // A sweep failing, e.g., on a conflict with a concurrent transaction, is retried
+                // by the next one.
+                let _ = sweep_expired_vertices(&schema, SystemTime::now());
+            }
+        })
+        .expect("failed to spawn the TTL sweeper");
+    stop
+}
+
+/// Returns whether `graph` has never been analyzed, or has had more than `tolerance` times its
+/// number of vertices modifications committed since.
+fn has_stale_statistics(graph: &MemoryGraph, tolerance: f64) -> bool {
+    let (Some(counts), Some(modifications)) =
+        (graph.vertex_counts(), graph.modifications_since_analysis())
+    else {
+        return true;
+    };
+    modifications as f64 > tolerance * counts.total().max(1) as f64
+}
+
+/// Analyzes `graph` if its statistics are stale. Returns whether it was analyzed.
+fn analyze_if_stale(graph: &MemoryGraph, tolerance: f64) -> StorageResult<bool> {
+    if !has_stale_statistics(graph, tolerance) {
+        return Ok(false);
+    }
+    graph.analyze()?;
+    Ok(true)
+}
+
+fn analyze_stale_graphs(schema: &MemorySchemaCatalog, tolerance: f64) -> Result<usize> {
+    let mut num_analyzed = 0;
+    for name in schema.graph_names() {
+        let Some(graph) = schema.get_graph(&name)? else {
+            continue;
+        };
+        if let Some(container) = graph.as_any().downcast_ref::<GraphContainer>() {
+            let GraphStorage::Memory(graph) = container.graph_storage();
+            if analyze_if_stale(graph, tolerance)? {
+                num_analyzed += 1;
+            }
+        }
+    }
+    Ok(num_analyzed)
+}
+
+/// Spawns a thread refreshing the stale statistics of the graphs in `schema` every `interval`,
+/// until the returned sender is dropped.
+fn spawn_analyzer(
+    schema: Arc<MemorySchemaCatalog>,
+    interval: Duration,
+    tolerance: f64,
+) -> Sender<()> {
+    let (stop, stopped) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("minigu-analyzer".into())
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                // A failed analysis is retried by the next one, the statistics being still stale.
+                let _ = analyze_stale_graphs(&schema, tolerance);
+            }
+        })
+        .expect("failed to spawn the analyzer");
+    stop
+}
+
+/// Creates `graph` in `schema`, which must not hold a graph of that name yet.
+fn create_default_graph(
+    context: &DatabaseContext,
+    schema: &MemorySchemaCatalog,
+    graph: &DefaultGraph,
+) {
+    let storage = MemoryGraph::with_config_recovered(
+        context.checkpoint_config(&graph.name),
+        context.wal_config(&graph.name),
+    );
+    let graph_type = graph
+        .graph_type
+        .clone()
+        .unwrap_or_else(|| Arc::new(MemoryGraphTypeCatalog::new()));
+    let container = GraphContainer::new(graph_type, GraphStorage::Memory(storage));
+    let created = schema.add_graph(graph.name.clone(), Arc::new(container));
+    assert!(created, "graph {} should not exist yet", graph.name);
+}
+
 fn init_memory_catalog() -> Result<(MemoryCatalog, Arc<MemorySchemaCatalog>)> {
     let root = Arc::new(MemoryDirectoryCatalog::new(None));
     let parent = Arc::downgrade(&root);
@@ -69,3 +637,274 @@ fn init_memory_catalog() -> Result<(MemoryCatalog, Arc<MemorySchemaCatalog>)> {
     let catalog = MemoryCatalog::new(DirectoryOrSchema::Directory(root));
     Ok((catalog, default_schema))
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use arrow::array::{AsArray, Int64Array};
+    use arrow::compute::kernels::numeric;
+    use arrow::datatypes::Int64Type;
+    use itertools::Itertools;
+    use minigu_common::data_type::LogicalType;
+    use minigu_common::datum::DatumRef;
+    use minigu_common::types::LabelId;
+    use minigu_storage::common::{DeltaOp, PropertyRecord, SetPropsOp, Vertex};
+    use minigu_storage::error::StorageError;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Opens an in-memory database with `config`, whose graphs keep their WALs and checkpoints in
+    /// the returned temporary directory, so that they never recover the graphs of other tests.
+    pub(crate) fn open_database(config: DatabaseConfig) -> (TempDir, Database) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DatabaseConfig {
+            wal_dir: Some(dir.path().join("wal")),
+            checkpoint_dir: Some(dir.path().join("checkpoint")),
+            ..config
+        };
+        let db = Database::open_in_memory(&config).unwrap();
+        (dir, db)
+    }
+
+    fn database() -> (TempDir, Database) {
+        let config = DatabaseConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        let (dir, db) = open_database(config);
+        db.session()
+            .unwrap()
+            .query("CALL create_test_graph_data('g', 2)")
+            .unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = DatabaseConfig::builder()
+            .parallelism(4)
+            .durability(Durability::None)
+            .ttl_sweep_interval(Duration::from_secs(60))
+            .overflow_policy(OverflowPolicy::Wrap)
+            .build()
+            .unwrap();
+        assert_eq!(config.parallelism, 4);
+        assert_eq!(config.durability, Durability::None);
+        assert_eq!(config.ttl_sweep_interval, Some(Duration::from_secs(60)));
+        assert_eq!(config.overflow_policy, OverflowPolicy::Wrap);
+        assert_eq!(config.num_threads, DatabaseConfig::default().num_threads);
+        assert!(Database::open_in_memory(&config).is_ok());
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = DatabaseConfig::builder()
+            .wal_dir(dir.path().join("wal"))
+            .build()
+            .unwrap();
+        assert_eq!(config.wal_dir, Some(dir.path().join("wal")));
+    }
+
+    #[test]
+    fn test_config_builder_conflicts() {
+        let conflicts = [
+            DatabaseConfig::builder().parallelism(0),
+            DatabaseConfig::builder().ttl_sweep_interval(Duration::ZERO),
+            DatabaseConfig::builder()
+                .wal_dir("wal")
+                .durability(Durability::None),
+            DatabaseConfig::builder().default_graph(DefaultGraph::new("")),
+            DatabaseConfig::builder().auto_analyze_interval(Duration::ZERO),
+            DatabaseConfig::builder().stale_statistics_tolerance(-0.1),
+            DatabaseConfig::builder().stale_statistics_tolerance(f64::NAN),
+            DatabaseConfig::builder().aggregate_memory_limit(0),
+        ];
+        for builder in conflicts {
+            assert!(matches!(builder.build(), Err(Error::InvalidConfig(_))));
+        }
+    }
+
+    #[test]
+    fn test_default_graph() {
+        let config = DatabaseConfig::builder()
+            .durability(Durability::None)
+            .default_graph(DefaultGraph::new("default_graph"))
+            .build()
+            .unwrap();
+        let (_dir, db) = open_database(config);
+        let properties = PropertyRecord::new(vec!["first".into()]);
+        let vertex = Vertex::new(0, LabelId::new(1).unwrap(), properties);
+        db.with_retry(
+            "default_graph",
+            |writer| writer.create_vertex(vertex.clone()),
+            1,
+        )
+        .unwrap();
+        // Sessions start on the default graph, without having to set it.
+        let mut session = db.session().unwrap();
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        assert_eq!(result.iter().map(|c| c.cardinality()).sum::<usize>(), 1);
+        assert!(session.query("SESSION RESET GRAPH").is_ok());
+        assert!(session.query("MATCH (n) RETURN n").is_ok());
+    }
+
+    #[test]
+    fn test_with_retry() {
+        let (_dir, db) = database();
+        let other = db.writer("g").unwrap();
+        other
+            .set_vertex_properties(0, vec![0], vec!["other".into()])
+            .unwrap();
+        let mut attempts = 0;
+        let f = |writer: &GraphWriter| {
+            attempts += 1;
+            let result = writer.set_vertex_properties(0, vec![0], vec!["retried".into()]);
+            if attempts == 1 {
+                // The vertex is being modified by the other transaction, which then commits.
+                assert!(
+                    result
+                        .as_ref()
+                        .is_err_and(StorageError::is_serialization_conflict)
+                );
+                other.commit().unwrap();
+            }
+            result
+        };
+        db.with_retry("g", f, 3).unwrap();
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn test_with_retry_gives_up() {
+        let (_dir, db) = database();
+        let other = db.writer("g").unwrap();
+        other
+            .set_vertex_properties(0, vec![0], vec!["other".into()])
+            .unwrap();
+        let mut attempts = 0;
+        let f = |writer: &GraphWriter| {
+            attempts += 1;
+            writer.set_vertex_properties(0, vec![0], vec!["retried".into()])
+        };
+        let result = db.with_retry("g", f, 3);
+        assert!(matches!(result, Err(Error::Storage(e)) if e.is_serialization_conflict()));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_with_retry_other_error() {
+        let (_dir, db) = database();
+        let mut attempts = 0;
+        let f = |writer: &GraphWriter| {
+            attempts += 1;
+            writer.set_vertex_properties(100, vec![0], vec!["missing".into()])
+        };
+        let result = db.with_retry("g", f, 3);
+        assert!(matches!(
+            result,
+            Err(Error::Storage(StorageError::VertexNotFound(_)))
+        ));
+        assert_eq!(attempts, 1);
+        assert!(db.with_retry("missing", |_| Ok(()), 3).is_err());
+    }
+
+    #[test]
+    fn test_writer_reads_own_writes() {
+        let (_dir, db) = database();
+        let mut session = db.session().unwrap();
+        let writer = db.writer("g").unwrap();
+        let vertex = Vertex::new(
+            2,
+            LabelId::new(1).unwrap(),
+            PropertyRecord::new(vec!["new".into()]),
+        );
+        writer.create_vertex(vertex).unwrap();
+        let ids = writer
+            .vertex_source(&[], 1024)
+            .unwrap()
+            .flat_map(|ids| ids.values().to_vec())
+            .sorted()
+            .collect_vec();
+        assert_eq!(ids, [0, 1, 2]);
+        // The vertex is not visible outside of the transaction until it is committed.
+        let count = |session: &mut Session| {
+            let result = session.query_on("g", "MATCH (n) RETURN n").unwrap();
+            result.iter().map(|c| c.cardinality()).sum::<usize>()
+        };
+        assert_eq!(count(&mut session), 2);
+        writer.commit().unwrap();
+        assert_eq!(count(&mut session), 3);
+    }
+
+    #[test]
+    fn test_stale_statistics() {
+        let (_dir, db) = database();
+        let graph = db.graph("g").unwrap();
+        let GraphStorage::Memory(graph) = container(&graph).graph_storage();
+        assert!(has_stale_statistics(graph, 0.5));
+        assert_eq!(db.analyze("g").unwrap().total(), 2);
+        assert!(!has_stale_statistics(graph, 0.0));
+
+        let properties = PropertyRecord::new(vec!["third".into()]);
+        let vertex = Vertex::new(2, LabelId::new(1).unwrap(), properties);
+        db.with_retry("g", |writer| writer.create_vertex(vertex.clone()), 1)
+            .unwrap();
+        // One modification, for two vertices as of the analysis.
+        assert!(!has_stale_statistics(graph, 0.5));
+        assert!(has_stale_statistics(graph, 0.4));
+        assert!(analyze_if_stale(graph, 0.4).unwrap());
+        assert_eq!(graph.vertex_counts().unwrap().total(), 3);
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let (_dir, db) = database();
+        let subscriber = db.subscribe("g", 3).unwrap();
+        let commit = |f: &dyn Fn(&GraphWriter) -> StorageResult<()>| {
+            let writer = db.writer("g").unwrap();
+            f(&writer).unwrap();
+            writer.commit().unwrap();
+        };
+        commit(&|writer| writer.set_vertex_properties(0, vec![0], vec!["first".into()]));
+        commit(&|writer| {
+            let properties = PropertyRecord::new(vec!["second".into()]);
+            writer.create_vertex(Vertex::new(2, LabelId::new(1).unwrap(), properties))?;
+            Ok(())
+        });
+        // Transactions without modifications are skipped.
+        commit(&|_| Ok(()));
+        commit(&|writer| writer.delete_vertex(2));
+
+        let committed = subscriber.try_iter().collect_vec();
+        assert_eq!(committed.len(), 3);
+        assert!(
+            committed
+                .iter()
+                .tuple_windows()
+                .all(|(a, b)| a.commit_ts < b.commit_ts)
+        );
+        let deltas = committed.iter().map(|c| &c.deltas[..]).collect_vec();
+        assert!(matches!(
+            deltas[0],
+            [DeltaOp::SetVertexProps(0, SetPropsOp { indices, .. })] if indices == &[0]
+        ));
+        assert!(matches!(deltas[1], [DeltaOp::CreateVertex(v)] if v.vid() == 2));
+        assert!(matches!(deltas[2], [DeltaOp::DelVertex(2)]));
+
+        assert!(db.subscribe("missing", 1).is_err());
+    }
+
+    #[test]
+    fn test_register_function() {
+        let (_dir, db) = database();
+        let twice = Function::new(vec![LogicalType::Int64], LogicalType::Int64, |args| {
+            let array = numeric::mul(&args[0], &Int64Array::new_scalar(2))?;
+            Ok(DatumRef::new(array, args[0].is_scalar()))
+        });
+        assert!(db.register_function("twice", twice));
+        let mut session = db.session().unwrap();
+        let result = session.query("RETURN twice(21)").unwrap();
+        let chunk = result.iter().next().unwrap();
+        assert_eq!(chunk.columns()[0].as_primitive::<Int64Type>().value(0), 42);
+        assert!(session.query("RETURN twice('twenty-one')").is_err());
+        assert!(session.query("RETURN triple(21)").is_err());
+    }
+}
@@ -22,13 +22,40 @@ pub enum Error {
     #[error("rayon error")]
     Rayon(#[from] rayon::ThreadPoolBuildError),
 
+    #[error("storage error")]
+    Storage(#[from] minigu_storage::error::StorageError),
+
+    #[error("csv error")]
+    Csv(#[from] csv::Error),
+
+    #[error("io error")]
+    Io(#[from] std::io::Error),
+
+    #[error("vertex type `{0}` not found")]
+    VertexTypeNotFound(String),
+
     #[error("session error")]
     #[diagnostic(transparent)]
     Session(#[from] minigu_context::error::Error),
 
+    #[error("invalid database configuration: {0}")]
+    InvalidConfig(String),
+
     #[error("current session is closed")]
     SessionClosed,
 
+    #[error("query result has more than {max_rows} rows")]
+    ResultTooLarge { max_rows: usize },
+
+    #[error("query cannot be resumed from a cursor: it must scan vertices under a limit")]
+    NotResumable,
+
+    #[error("query cannot modify the graphs while they are read as of a past timestamp")]
+    ReadOnlySnapshot,
+
+    #[error("query cannot modify the graphs within a read-only transaction")]
+    ReadOnlyTransaction,
+
     #[error(transparent)]
     #[diagnostic(transparent)]
     NotImplemented(#[from] NotImplemented),
@@ -1,6 +1,7 @@
 #![feature(impl_trait_in_assoc_type)]
 #![allow(unused)]
 
+pub mod bulk_load;
 pub mod database;
 pub mod error;
 pub mod metrics;
@@ -9,3 +10,4 @@
 pub mod session;
 
 pub use minigu_common as common;
+pub use minigu_context::progress;
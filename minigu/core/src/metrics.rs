@@ -5,6 +5,8 @@ pub struct QueryMetrics {
     pub(crate) parsing_time: Duration,
     pub(crate) planning_time: Duration,
     pub(crate) execution_time: Duration,
+    pub(crate) chunks_produced: u64,
+    pub(crate) bytes_scanned: u64,
 }
 
 impl QueryMetrics {
@@ -39,4 +41,17 @@ pub fn execution_time(&self) -> Duration {
     pub fn total_time(&self) -> Duration {
         self.parsing_time + self.planning_time + self.execution_time
     }
+
+    /// Returns the number of chunks produced by the operators of the query plan, summed over all
+    /// of them.
+    #[inline]
+    pub fn chunks_produced(&self) -> u64 {
+        self.chunks_produced
+    }
+
+    /// Returns the approximate number of bytes read from the graph by the scans of the query plan.
+    #[inline]
+    pub fn bytes_scanned(&self) -> u64 {
+        self.bytes_scanned
+    }
 }
@@ -0,0 +1,91 @@
+//! call analyze(<graph>);
+//!
+//! Collect the statistics of the graph named `<graph>` in the current schema, from which the
+//! optimizer estimates the cardinality of the scans, and return a single row with its number of
+//! vertices:
+//!
+//! ```text
+//! vertex_count
+//! ```
+//!
+//! Statistics can also be collected automatically (see
+//! [`DatabaseConfig::auto_analyze_interval`]).
+//!
+//! [`DatabaseConfig::auto_analyze_interval`]: crate::database::DatabaseConfig::auto_analyze_interval
+
+use std::sync::Arc;
+
+use arrow::array::UInt64Array;
+use minigu_catalog::provider::SchemaProvider;
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+use minigu_context::graph::{GraphContainer, GraphStorage};
+use minigu_context::procedure::Procedure;
+
+pub fn build_procedure() -> Procedure {
+    let parameters = vec![LogicalType::String];
+    let schema = Arc::new(DataSchema::new(vec![DataField::new(
+        "vertex_count".into(),
+        LogicalType::UInt64,
+        false,
+    )]));
+
+    Procedure::new(parameters, Some(schema), |context, args| {
+        assert_eq!(args.len(), 1);
+        let graph_name = args[0]
+            .try_as_string()
+            .expect("graph name must be a string")
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("graph name cannot be null"))?;
+        let schema = context
+            .current_schema
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("current schema not set"))?;
+        let graph = schema
+            .get_graph(graph_name)?
+            .ok_or_else(|| anyhow::anyhow!("graph `{graph_name}` does not exist"))?;
+        let container = graph
+            .as_any()
+            .downcast_ref::<GraphContainer>()
+            .ok_or_else(|| anyhow::anyhow!("graph `{graph_name}` cannot be analyzed"))?;
+        let GraphStorage::Memory(graph) = container.graph_storage();
+        let counts = graph.analyze()?;
+        Ok(vec![DataChunk::new(vec![Arc::new(UInt64Array::from(
+            vec![counts.total()],
+        ))])])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::value::ScalarValue;
+
+    use crate::database::DatabaseConfig;
+    use crate::database::tests::open_database;
+
+    #[test]
+    fn test_analyze() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('g', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH g").unwrap();
+        let plan = session.explain("MATCH (n) RETURN n").unwrap();
+        assert!(!plan.contains("estimated rows"), "{plan}");
+
+        let result = session.query("CALL analyze('g')").unwrap();
+        let rows: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .collect();
+        let [row] = rows.as_slice() else {
+            panic!("expected a single row");
+        };
+        assert_eq!(row.get(0), Some(&ScalarValue::UInt64(Some(10))));
+        let plan = session.explain("MATCH (n) RETURN n").unwrap();
+        assert!(plan.contains("estimated rows: 10"), "{plan}");
+
+        assert!(session.query("CALL analyze('missing')").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! call benchmark(<query>, <iterations>);
+//!
+//! Run `<query>` `<iterations>` times in a session in the state of the calling one, and return a
+//! single row with the statistics of its execution times, in milliseconds, as measured by
+//! [`QueryMetrics::execution_time`](crate::metrics::QueryMetrics::execution_time):
+//!
+//! ```text
+//! iterations | min_ms | max_ms | mean_ms | p95_ms
+//! ```
+//!
+//! Changes made by the query to the session, e.g., `SESSION SET GRAPH`, do not outlive the call,
+//! but changes made to the graphs do.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use arrow::array::{Float64Array, UInt64Array};
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+use minigu_context::procedure::Procedure;
+use minigu_context::session::SessionContext;
+
+use crate::error::Result;
+use crate::session::Session;
+
+/// Runs `query` `iterations` times in a session in the state of `context`, returning the
+/// execution time of each run.
+fn run_benchmark(context: SessionContext, query: &str, iterations: usize) -> Result<Vec<Duration>> {
+    let mut session = Session::from_context(context);
+    (0..iterations)
+        .map(|_| Ok(session.query(query)?.metrics().execution_time()))
+        .collect()
+}
+
+/// Returns the `p`-th percentile of `samples`, which must be sorted, by the nearest-rank method.
+fn percentile(samples: &[Duration], p: usize) -> Duration {
+    let rank = (samples.len() * p).div_ceil(100).max(1);
+    samples[rank - 1]
+}
+
+pub fn build_procedure() -> Procedure {
+    // Query, iterations
+    let parameters = vec![LogicalType::String, LogicalType::Int8];
+    let schema = Arc::new(DataSchema::new(vec![
+        DataField::new("iterations".into(), LogicalType::UInt64, false),
+        DataField::new("min_ms".into(), LogicalType::Float64, false),
+        DataField::new("max_ms".into(), LogicalType::Float64, false),
+        DataField::new("mean_ms".into(), LogicalType::Float64, false),
+        DataField::new("p95_ms".into(), LogicalType::Float64, false),
+    ]));
+
+    Procedure::new(parameters, Some(schema), |context, args| {
+        assert_eq!(args.len(), 2);
+        let query = args[0]
+            .try_as_string()
+            .expect("query must be a string")
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("query cannot be null"))?;
+        let iterations = args[1]
+            .try_as_int8()
+            .expect("iterations must be an int")
+            .ok_or_else(|| anyhow::anyhow!("iterations cannot be null"))?;
+        if iterations <= 0 {
+            return Err(anyhow::anyhow!("iterations must be > 0").into());
+        }
+
+        let mut samples = run_benchmark(context, &query, iterations as usize)?;
+        samples.sort_unstable();
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+        let stat = |value: Duration| Arc::new(Float64Array::from(vec![ms(value)]));
+        Ok(vec![DataChunk::new(vec![
+            Arc::new(UInt64Array::from(vec![samples.len() as u64])),
+            stat(samples[0]),
+            stat(samples[samples.len() - 1]),
+            stat(mean),
+            stat(percentile(&samples, 95)),
+        ])])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::value::ScalarValue;
+
+    use super::*;
+    use crate::database::DatabaseConfig;
+    use crate::database::tests::open_database;
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<_> = (1..=20).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&samples, 95), Duration::from_millis(19));
+        assert_eq!(percentile(&samples[..1], 95), Duration::from_millis(1));
+        assert_eq!(percentile(&samples[..10], 95), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let result = session
+            .query("CALL benchmark('MATCH (n) RETURN n', 20)")
+            .unwrap();
+        let rows: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .collect();
+        let [row] = rows.as_slice() else {
+            panic!("expected a single row");
+        };
+        assert_eq!(row.get(0), Some(&ScalarValue::UInt64(Some(20))));
+        let stat = |i| match row.get(i) {
+            Some(ScalarValue::Float64(Some(value))) => value.0,
+            value => panic!("unexpected value: {value:?}"),
+        };
+        let (min, max, mean, p95) = (stat(1), stat(2), stat(3), stat(4));
+        assert!(min <= mean && mean <= max);
+        assert!(min <= p95 && p95 <= max);
+
+        assert!(
+            session
+                .query("CALL benchmark('MATCH (n) RETURN n', 0)")
+                .is_err()
+        );
+    }
+}
@@ -19,10 +19,12 @@ pub fn build_procedure() -> Procedure {
             .expect("arg must be a string")
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("graph name cannot be null"))?;
+        let checkpoint_config = context.database().checkpoint_config(graph_name);
+        let wal_config = context.database().wal_config(graph_name);
         let schema = context
             .current_schema
             .ok_or_else(|| anyhow::anyhow!("current schema not set"))?;
-        let graph = MemoryGraph::new();
+        let graph = MemoryGraph::with_config_recovered(checkpoint_config, wal_config);
         let mut graph_type = MemoryGraphTypeCatalog::new();
         let container = GraphContainer::new(Arc::new(graph_type), GraphStorage::Memory(graph));
         if !schema.add_graph(graph_name.clone(), Arc::new(container)) {
@@ -39,7 +39,10 @@ pub fn build_procedure() -> Procedure {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("current schema not set"))?;
 
-        let graph = MemoryGraph::new();
+        let graph = MemoryGraph::with_config_recovered(
+            context.database().checkpoint_config(&graph_name),
+            context.database().wal_config(&graph_name),
+        );
         let graph_type = Arc::new(MemoryGraphTypeCatalog::new());
         let container = Arc::new(GraphContainer::new(
             graph_type.clone(),
@@ -19,12 +19,14 @@
 
 use csv::{Writer, WriterBuilder};
 use minigu_catalog::provider::{GraphProvider, GraphTypeProvider, SchemaProvider};
+use minigu_common::data_chunk::display::format_float;
 use minigu_common::data_type::LogicalType;
 use minigu_common::error::not_implemented;
 use minigu_common::types::{EdgeId, LabelId, VertexId};
 use minigu_common::value::ScalarValue;
 use minigu_context::graph::{GraphContainer, GraphStorage};
 use minigu_context::procedure::Procedure;
+use minigu_context::progress::{ProgressCallback, ProgressTracker};
 use minigu_storage::common::{Edge, Vertex};
 use minigu_storage::tp::MemoryGraph;
 use minigu_transaction::{GraphTxnManager, IsolationLevel, Transaction};
@@ -32,26 +34,32 @@
 use crate::procedures::export_import::{Manifest, RecordType, Result, SchemaMetadata};
 
 /// Convert a [`ScalarValue`] back into a *CSV‑ready* string. `NULL` becomes an
-/// empty string.
-fn scalar_value_to_string(scalar_value: &ScalarValue) -> Result<String> {
+/// empty string, and floats are written with `float_precision` digits after the decimal point, if
+/// set.
+fn scalar_value_to_string(
+    scalar_value: &ScalarValue,
+    float_precision: Option<usize>,
+) -> Result<String> {
+    match (scalar_value, float_precision) {
+        (ScalarValue::Float32(Some(value)), Some(precision)) => {
+            return Ok(format_float(value.0 as f64, precision));
+        }
+        (ScalarValue::Float64(Some(value)), Some(precision)) => {
+            return Ok(format_float(value.0, precision));
+        }
+        _ => (),
+    }
     match scalar_value {
-        ScalarValue::Int8(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::Int16(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::Int32(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::Int64(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::UInt8(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::UInt16(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::UInt32(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::UInt64(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::Boolean(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::Float32(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
-        ScalarValue::Float64(value) => Ok(value.map_or(String::new(), |inner| inner.to_string())),
         ScalarValue::String(value) => Ok(value.clone().unwrap_or_default()),
-        ScalarValue::Null => Ok(String::new()),
-        _ => not_implemented(
-            "convert `ScalarValue::Vertex`/`ScalarValue::Edge` to string",
-            None,
-        ),
+        ScalarValue::Vector { .. } | ScalarValue::Vertex(_) | ScalarValue::Edge(_) => {
+            not_implemented(
+                "convert `ScalarValue::Vector`/`ScalarValue::Vertex`/`ScalarValue::Edge` to string",
+                None,
+            )
+        }
+        // All other nulls are written as `NULL` by `Display`.
+        _ if scalar_value.is_null() => Ok(String::new()),
+        _ => Ok(format!("{scalar_value}")),
     }
 }
 
@@ -70,10 +78,15 @@ fn get_graph_from_graph_container(container: Arc<dyn GraphProvider>) -> Result<A
 struct VerticesBuilder {
     records: HashMap<LabelId, BTreeMap<VertexId, RecordType>>,
     writers: HashMap<LabelId, Writer<File>>,
+    float_precision: Option<usize>,
 }
 
 impl VerticesBuilder {
-    fn new<P: AsRef<Path>>(dir: P, map: &HashMap<LabelId, String>) -> Result<Self> {
+    fn new<P: AsRef<Path>>(
+        dir: P,
+        map: &HashMap<LabelId, String>,
+        float_precision: Option<usize>,
+    ) -> Result<Self> {
         let mut writers = HashMap::with_capacity(map.len());
 
         for (&id, label) in map {
@@ -86,6 +99,7 @@ fn new<P: AsRef<Path>>(dir: P, map: &HashMap<LabelId, String>) -> Result<Self> {
         Ok(Self {
             records: HashMap::new(),
             writers,
+            float_precision,
         })
     }
 
@@ -94,7 +108,7 @@ fn add_vertex(&mut self, v: &Vertex) -> Result<()> {
         record.push(v.vid().to_string());
 
         for prop in v.properties() {
-            record.push(scalar_value_to_string(prop)?);
+            record.push(scalar_value_to_string(prop, self.float_precision)?);
         }
 
         self.records
@@ -122,10 +136,15 @@ fn dump(&mut self) -> Result<()> {
 struct EdgesBuilder {
     records: HashMap<LabelId, BTreeMap<EdgeId, RecordType>>,
     writers: HashMap<LabelId, Writer<File>>,
+    float_precision: Option<usize>,
 }
 
 impl EdgesBuilder {
-    fn new<P: AsRef<Path>>(dir: P, map: &HashMap<LabelId, String>) -> Result<Self> {
+    fn new<P: AsRef<Path>>(
+        dir: P,
+        map: &HashMap<LabelId, String>,
+        float_precision: Option<usize>,
+    ) -> Result<Self> {
         let mut writers = HashMap::with_capacity(map.len());
 
         for (&id, label) in map {
@@ -138,6 +157,7 @@ fn new<P: AsRef<Path>>(dir: P, map: &HashMap<LabelId, String>) -> Result<Self> {
         Ok(Self {
             records: HashMap::new(),
             writers,
+            float_precision,
         })
     }
 
@@ -150,7 +170,7 @@ fn add_edge(&mut self, e: &Edge) -> Result<()> {
         ]);
 
         for prop in e.properties() {
-            record.push(scalar_value_to_string(prop)?);
+            record.push(scalar_value_to_string(prop, self.float_precision)?);
         }
 
         self.records
@@ -173,11 +193,15 @@ fn dump(&mut self) -> Result<()> {
     }
 }
 
+/// Exports `graph` into `dir`, reporting every vertex and edge written to `progress`, if any.
+/// Floats are written with `float_precision` digits after the decimal point, if set.
 pub(crate) fn export<P: AsRef<Path>>(
     graph: Arc<MemoryGraph>,
     dir: P,
     manifest_rel_path: P, // relative path
     graph_type: Arc<dyn GraphTypeProvider>,
+    float_precision: Option<usize>,
+    progress: Option<&ProgressTracker>,
 ) -> Result<()> {
     let txn = graph
         .txn_manager()
@@ -189,18 +213,24 @@ pub(crate) fn export<P: AsRef<Path>>(
 
     let metadata = SchemaMetadata::from_schema(Arc::clone(&graph_type))?;
 
-    let mut vertice_builder = VerticesBuilder::new(dir, &metadata.label_map)?;
-    let mut edges_builder = EdgesBuilder::new(dir, &metadata.label_map)?;
+    let mut vertice_builder = VerticesBuilder::new(dir, &metadata.label_map, float_precision)?;
+    let mut edges_builder = EdgesBuilder::new(dir, &metadata.label_map, float_precision)?;
 
     // 2. Dump vertices
     for v in txn.iter_vertices() {
         vertice_builder.add_vertex(&v?)?;
+        if let Some(progress) = progress {
+            progress.advance(1);
+        }
     }
     vertice_builder.dump()?;
 
     // 3. Dump edge
     for e in txn.iter_edges() {
         edges_builder.add_edge(&e?)?;
+        if let Some(progress) = progress {
+            progress.advance(1);
+        }
     }
     edges_builder.dump()?;
 
@@ -251,7 +281,15 @@ pub fn build_procedure() -> Procedure {
         let graph_type = graph_container.graph_type();
         let graph = get_graph_from_graph_container(graph_container)?;
 
-        export(graph, dir_path, manifest_rel_path, graph_type)?;
+        let progress = context.progress.as_ref().map(ProgressCallback::start);
+        export(
+            graph,
+            dir_path,
+            manifest_rel_path,
+            graph_type,
+            context.float_precision,
+            progress.as_ref(),
+        )?;
 
         Ok(vec![])
     })
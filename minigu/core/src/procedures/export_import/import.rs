@@ -7,7 +7,8 @@
 //! * `<graph_name>` – Name to register the imported graph under in the current schema.
 //! * `<dir_path>` – Directory that contains the CSV files and the manifest.
 //! * `<manifest_relative_path>` – File name or relative path (inside `dir_path`) to
-//!   `manifest.json`.
+//!   `manifest.json`, or `''` to infer the manifest from the CSV files of `dir_path` (see
+//!   [`infer`](super::infer)).
 //!
 //! ## Output
 //! * Returns nothing. On success the graph is added to the current schema. Errors (missing files,
@@ -31,13 +32,17 @@
 use minigu_common::value::ScalarValue;
 use minigu_context::graph::{GraphContainer, GraphStorage};
 use minigu_context::procedure::Procedure;
+use minigu_context::progress::{ProgressCallback, ProgressTracker};
 use minigu_storage::common::{Edge, PropertyRecord, Vertex};
 use minigu_storage::tp::MemoryGraph;
+use minigu_storage::tp::checkpoint::CheckpointManagerConfig;
+use minigu_storage::wal::graph_wal::WalManagerConfig;
 use minigu_transaction::{GraphTxnManager, IsolationLevel, Transaction};
 
+use crate::procedures::export_import::infer::infer_manifest;
 use crate::procedures::export_import::{Manifest, Result};
 
-fn build_manifest<P: AsRef<Path>>(manifest_path: P) -> Result<Manifest> {
+pub(super) fn build_manifest<P: AsRef<Path>>(manifest_path: P) -> Result<Manifest> {
     let data = std::fs::read(manifest_path)?;
 
     let data_str = std::str::from_utf8(&data)?;
@@ -45,42 +50,33 @@ fn build_manifest<P: AsRef<Path>>(manifest_path: P) -> Result<Manifest> {
 }
 
 /// Convert a *string* coming from CSV into an owned [`ScalarValue`] according
-/// to a given property definition.
-fn property_to_scalar_value(property: &Property, value: &str) -> Result<ScalarValue> {
+/// to a given property definition. An empty string is a null if the property is nullable, and
+/// strings are stored unquoted.
+pub(crate) fn property_to_scalar_value(property: &Property, value: &str) -> Result<ScalarValue> {
+    let ty = property.logical_type();
     if value.is_empty() && property.nullable() {
-        return match property.logical_type() {
-            LogicalType::Int8 => Ok(ScalarValue::Int8(None)),
-            LogicalType::Int16 => Ok(ScalarValue::Int16(None)),
-            LogicalType::Int32 => Ok(ScalarValue::Int32(None)),
-            LogicalType::Int64 => Ok(ScalarValue::Int64(None)),
-            LogicalType::UInt8 => Ok(ScalarValue::UInt8(None)),
-            LogicalType::UInt16 => Ok(ScalarValue::UInt16(None)),
-            LogicalType::UInt32 => Ok(ScalarValue::UInt32(None)),
-            LogicalType::UInt64 => Ok(ScalarValue::UInt64(None)),
-            LogicalType::Boolean => Ok(ScalarValue::Boolean(None)),
-            LogicalType::Float32 => Ok(ScalarValue::Float32(None)),
-            LogicalType::Float64 => Ok(ScalarValue::Float64(None)),
-            LogicalType::String => Ok(ScalarValue::String(None)),
-            LogicalType::Null => Ok(ScalarValue::Null),
-            _ => not_implemented("", None),
+        return match ScalarValue::null_of(ty) {
+            Some(null) => Ok(null),
+            None => not_implemented("", None),
         };
     }
 
-    match property.logical_type() {
-        LogicalType::Int8 => Ok(ScalarValue::Int8(Some(value.parse()?))),
-        LogicalType::Int16 => Ok(ScalarValue::Int16(Some(value.parse()?))),
-        LogicalType::Int32 => Ok(ScalarValue::Int32(Some(value.parse()?))),
-        LogicalType::Int64 => Ok(ScalarValue::Int64(Some(value.parse()?))),
-        LogicalType::UInt8 => Ok(ScalarValue::UInt8(Some(value.parse()?))),
-        LogicalType::UInt16 => Ok(ScalarValue::UInt16(Some(value.parse()?))),
-        LogicalType::UInt32 => Ok(ScalarValue::UInt32(Some(value.parse()?))),
-        LogicalType::UInt64 => Ok(ScalarValue::UInt64(Some(value.parse()?))),
-        LogicalType::Boolean => Ok(ScalarValue::Boolean(Some(value.parse()?))),
-        LogicalType::Float32 => Ok(ScalarValue::Float32(Some(value.parse()?))),
-        LogicalType::Float64 => Ok(ScalarValue::Float64(Some(value.parse()?))),
+    match ty {
         LogicalType::String => Ok(ScalarValue::String(Some(value.to_string()))),
         LogicalType::Null => Err(anyhow::anyhow!("str isn't empty").into()),
-        _ => not_implemented("", None),
+        LogicalType::Vector(_)
+        | LogicalType::Vertex(_)
+        | LogicalType::Edge(_)
+        | LogicalType::Record(_)
+        | LogicalType::List(_)
+        | LogicalType::Path(..) => not_implemented("", None),
+        _ => {
+            let value = ScalarValue::parse(value, ty)?;
+            if value.is_null() && !property.nullable() {
+                return Err(anyhow::anyhow!("null value for non-nullable property").into());
+            }
+            Ok(value)
+        }
     }
 }
 
@@ -97,33 +93,57 @@ fn build_properties<'a>(
     Ok(props)
 }
 
+/// Imports the graph described by the manifest at `manifest_path`, reporting every vertex and
+/// edge created to `progress`, if any.
 pub(crate) fn import<P: AsRef<Path>>(
     manifest_path: P,
+    checkpoint_config: CheckpointManagerConfig,
+    wal_config: WalManagerConfig,
+    progress: Option<&ProgressTracker>,
 ) -> Result<(Arc<MemoryGraph>, Arc<MemoryGraphTypeCatalog>)> {
-    // Graph type
     let manifest = build_manifest(&manifest_path)?;
-    let graph_type = get_graph_type_from_manifest(&manifest)?;
-
-    // Graph
-    let graph = MemoryGraph::with_config_fresh(Default::default(), Default::default());
-    let txn = graph
-        .txn_manager()
-        .begin_transaction(IsolationLevel::Serializable)?;
-
     let manifest_parent_dir = manifest_path.as_ref().parent().ok_or_else(|| {
         anyhow::anyhow!(
             "manifest path has no parent directory: {}",
             manifest_path.as_ref().display()
         )
     })?;
+    import_manifest(
+        &manifest,
+        manifest_parent_dir,
+        checkpoint_config,
+        wal_config,
+        progress,
+    )
+}
+
+/// Imports the graph described by `manifest`, whose files are relative to `dir`.
+pub(super) fn import_manifest(
+    manifest: &Manifest,
+    dir: &Path,
+    checkpoint_config: CheckpointManagerConfig,
+    wal_config: WalManagerConfig,
+    progress: Option<&ProgressTracker>,
+) -> Result<(Arc<MemoryGraph>, Arc<MemoryGraphTypeCatalog>)> {
+    // Graph type
+    let graph_type = get_graph_type_from_manifest(manifest)?;
+
+    // Graph
+    let graph = MemoryGraph::with_config_fresh(checkpoint_config, wal_config);
+    let txn = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)?;
+
     // Map each original vertex ID to it's newly assigned ID.
     let mut vid_mapping = HashMap::new();
 
     // 1. Vertices
     let mut vid = 1;
     for vertex_spec in manifest.vertices.iter() {
-        let path = manifest_parent_dir.join(&vertex_spec.file.path);
-        let mut rdr = ReaderBuilder::new().has_headers(false).from_path(path)?;
+        let path = dir.join(&vertex_spec.file.path);
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(vertex_spec.file.has_headers)
+            .from_path(path)?;
 
         let label_id = graph_type
             .get_label_id(&vertex_spec.label)?
@@ -147,18 +167,23 @@ pub(crate) fn import<P: AsRef<Path>>(
             // Update vid mapping
             vid_mapping.insert(old_vid, vid);
             vid += 1;
+            if let Some(progress) = progress {
+                progress.advance(1);
+            }
         }
     }
 
     // 2. Edges
     let mut eid = 1;
     for edge_spec in manifest.edges.iter() {
-        let path = manifest_parent_dir.join(&edge_spec.file.path);
+        let path = dir.join(&edge_spec.file.path);
         let label_id = graph_type
             .get_label_id(&edge_spec.label)?
             .expect("label id not found");
 
-        let mut rdr = ReaderBuilder::new().has_headers(false).from_path(path)?;
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(edge_spec.file.has_headers)
+            .from_path(path)?;
 
         for record in rdr.records() {
             let record = record?;
@@ -180,6 +205,9 @@ pub(crate) fn import<P: AsRef<Path>>(
             let edge = Edge::new(eid, *src_id, *dst_id, label_id, PropertyRecord::new(props));
             graph.create_edge(&txn, edge)?;
             eid += 1;
+            if let Some(progress) = progress {
+                progress.advance(1);
+            }
         }
     }
 
@@ -259,12 +287,32 @@ pub fn build_procedure() -> Procedure {
             .clone()
             .expect("manifest relative path can't be empty");
 
-        let manifest_path = (dir_path.as_ref() as &Path).join(manifest_rel_path);
+        let dir_path: &Path = dir_path.as_ref();
+        let checkpoint_config = context.database().checkpoint_config(&graph_name);
+        let wal_config = context.database().wal_config(&graph_name);
         let schema = context
             .current_schema
             .ok_or_else(|| anyhow::anyhow!("current schema not set"))?;
 
-        let (graph, graph_type) = import(manifest_path)?;
+        let progress = context.progress.as_ref().map(ProgressCallback::start);
+        let (graph, graph_type) = if manifest_rel_path.is_empty() {
+            let manifest = infer_manifest(dir_path)?;
+            import_manifest(
+                &manifest,
+                dir_path,
+                checkpoint_config,
+                wal_config,
+                progress.as_ref(),
+            )?
+        } else {
+            let manifest_path = dir_path.join(manifest_rel_path);
+            import(
+                manifest_path,
+                checkpoint_config,
+                wal_config,
+                progress.as_ref(),
+            )?
+        };
 
         let container = GraphContainer::new(
             Arc::clone(&graph_type),
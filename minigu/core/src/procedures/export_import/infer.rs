@@ -0,0 +1,292 @@
+//! call infer_manifest(<dir_path>, <manifest_relative_path>);
+//!
+//! Infer the manifest of a directory of CSV files, e.g., to import a graph which was not exported
+//! by `export`, and write it to `<manifest_relative_path>` (inside `<dir_path>`), where it can be
+//! edited before calling `import`, e.g., to override the inferred types. `import` also infers the
+//! manifest on the fly when given `''` as manifest path.
+//!
+//! ## Files
+//! Each `<label>.csv` file of the directory holds the vertices or the edges labeled `<label>`, and
+//! starts with a header naming its columns:
+//!
+//! ```csv
+//! id,<prop-1>,<prop-2>, ...
+//! id,src:<src-label>,dst:<dst-label>,<prop-1>,<prop-2>, ...
+//! ```
+//!
+//! for vertices and edges respectively. The type of each property is inferred from the first
+//! [`SAMPLE_SIZE`] rows of the file, as the narrowest of `Boolean`, `Int64`, `Float64` and
+//! `String` which all of its values can be parsed as. A property with empty values is nullable.
+
+use std::path::Path;
+
+use csv::{ReaderBuilder, StringRecord};
+use minigu_catalog::property::Property;
+use minigu_common::data_type::LogicalType;
+use minigu_context::procedure::Procedure;
+
+use crate::procedures::export_import::{EdgeSpec, FileSpec, Manifest, Result, VertexSpec};
+
+/// The number of rows of each file the types of the properties are inferred from.
+pub(crate) const SAMPLE_SIZE: usize = 1000;
+
+const SRC_PREFIX: &str = "src:";
+const DST_PREFIX: &str = "dst:";
+
+/// Infers the manifest of the CSV files in `dir`, in the order of their names.
+pub(super) fn infer_manifest<P: AsRef<Path>>(dir: P) -> Result<Manifest> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "csv") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut manifest = Manifest::default();
+    for path in paths {
+        let file_name = path
+            .file_name()
+            .expect("files should have a name")
+            .to_string_lossy()
+            .into_owned();
+        let label = path
+            .file_stem()
+            .expect("files should have a name")
+            .to_string_lossy()
+            .into_owned();
+        let mut reader = ReaderBuilder::new().has_headers(true).from_path(&path)?;
+        let header = reader.headers()?.clone();
+        if header.get(0) != Some("id") {
+            return Err(anyhow::anyhow!("the first column of {file_name} should be `id`").into());
+        }
+        let endpoints = header
+            .get(1)
+            .and_then(|src| src.strip_prefix(SRC_PREFIX))
+            .zip(header.get(2).and_then(|dst| dst.strip_prefix(DST_PREFIX)));
+        let offset = if endpoints.is_some() { 3 } else { 1 };
+
+        let mut columns = vec![ColumnType::default(); header.len() - offset];
+        for record in reader.records().take(SAMPLE_SIZE) {
+            let record: StringRecord = record?;
+            for (column, value) in columns.iter_mut().zip(record.iter().skip(offset)) {
+                column.widen(value);
+            }
+        }
+        let properties = header
+            .iter()
+            .skip(offset)
+            .zip(columns)
+            .map(|(name, column)| column.into_property(name.to_string()))
+            .collect();
+
+        let file = FileSpec {
+            has_headers: true,
+            ..FileSpec::new(file_name, "csv".to_string())
+        };
+        match endpoints {
+            Some((src_label, dst_label)) => manifest.edges.push(EdgeSpec::new(
+                label,
+                src_label.to_string(),
+                dst_label.to_string(),
+                file,
+                properties,
+            )),
+            None => manifest
+                .vertices
+                .push(VertexSpec::new(label, file, properties)),
+        }
+    }
+
+    for edge_spec in &manifest.edges {
+        for label in [edge_spec.src_label(), edge_spec.dst_label()] {
+            if !manifest.vertices.iter().any(|v| v.label_name() == label) {
+                return Err(anyhow::anyhow!(
+                    "vertex label {label} of edge label {} not found",
+                    edge_spec.label_name()
+                )
+                .into());
+            }
+        }
+    }
+    Ok(manifest)
+}
+
+/// The type of a column inferred from the values seen so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum InferredType {
+    /// Only empty values were seen.
+    #[default]
+    Unknown,
+    Boolean,
+    Int64,
+    Float64,
+    String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ColumnType {
+    ty: InferredType,
+    nullable: bool,
+}
+
+impl ColumnType {
+    /// Widens the type of the column, if needed, so that `value` can be parsed as it.
+    fn widen(&mut self, value: &str) {
+        if value.is_empty() {
+            self.nullable = true;
+            return;
+        }
+        let parses_as = |ty| match ty {
+            InferredType::Unknown => false,
+            InferredType::Boolean => {
+                value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+            }
+            InferredType::Int64 => value.parse::<i64>().is_ok(),
+            InferredType::Float64 => value.parse::<f64>().is_ok(),
+            InferredType::String => true,
+        };
+        if !parses_as(self.ty) {
+            self.ty = [
+                InferredType::Boolean,
+                InferredType::Int64,
+                InferredType::Float64,
+                InferredType::String,
+            ]
+            .into_iter()
+            .filter(|&ty| ty > self.ty)
+            .find(|&ty| parses_as(ty))
+            .expect("any value can be parsed as a string");
+        }
+    }
+
+    fn into_property(self, name: String) -> Property {
+        let ty = match self.ty {
+            InferredType::Unknown | InferredType::String => LogicalType::String,
+            InferredType::Boolean => LogicalType::Boolean,
+            InferredType::Int64 => LogicalType::Int64,
+            InferredType::Float64 => LogicalType::Float64,
+        };
+        Property::new(name, ty, self.nullable)
+    }
+}
+
+pub fn build_procedure() -> Procedure {
+    // Directory path, manifest relative path
+    let parameters = vec![LogicalType::String, LogicalType::String];
+
+    Procedure::new(parameters, None, |_context, args| {
+        assert_eq!(args.len(), 2);
+        let dir_path = args[0]
+            .try_as_string()
+            .expect("directory path must be a string")
+            .clone()
+            .expect("directory path can't be empty");
+        let manifest_rel_path = args[1]
+            .try_as_string()
+            .expect("manifest relative path must be a string")
+            .clone()
+            .expect("manifest relative path can't be empty");
+
+        let dir_path: &Path = dir_path.as_ref();
+        let manifest = infer_manifest(dir_path)?;
+        std::fs::write(
+            dir_path.join(manifest_rel_path),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+        Ok(vec![])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{Database, DatabaseConfig};
+
+    fn properties(properties: &[Property]) -> Vec<(&str, LogicalType, bool)> {
+        properties
+            .iter()
+            .map(|p| (p.name(), p.logical_type().clone(), p.nullable()))
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("person.csv"),
+            "id,name,age,height,member,note\n\
+             1,alice,30,1.70,true,\n\
+             2,bob,,1.8,FALSE,\n\
+             3,42,27,2,true,\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("knows.csv"),
+            "id,src:person,dst:person,since\n1,1,2,2020\n2,2,3,2021\n",
+        )
+        .unwrap();
+
+        let manifest = infer_manifest(dir.path()).unwrap();
+        let [person] = manifest.vertices_spec().as_slice() else {
+            panic!("expected a single vertex label");
+        };
+        assert_eq!(person.label_name(), "person");
+        assert_eq!(
+            properties(person.properties()),
+            [
+                ("name", LogicalType::String, false),
+                ("age", LogicalType::Int64, true),
+                ("height", LogicalType::Float64, false),
+                ("member", LogicalType::Boolean, false),
+                ("note", LogicalType::String, true),
+            ]
+        );
+        let [knows] = manifest.edges_spec().as_slice() else {
+            panic!("expected a single edge label");
+        };
+        assert_eq!(knows.label_name(), "knows");
+        assert_eq!(knows.src_label(), "person");
+        assert_eq!(knows.dst_label(), "person");
+        assert_eq!(
+            properties(knows.properties()),
+            [("since", LogicalType::Int64, false)]
+        );
+
+        let db = Database::open_in_memory(&DatabaseConfig::default()).unwrap();
+        let mut session = db.session().unwrap();
+        let dir_path = dir.path().display();
+        session
+            .query(&format!("CALL import('inferred', '{dir_path}', '')"))
+            .unwrap();
+        let result = session
+            .query_on("inferred", "MATCH (n:person) RETURN n")
+            .unwrap();
+        assert_eq!(result.num_rows(), 3);
+
+        // The written manifest can be edited to override the inferred types.
+        session
+            .query(&format!(
+                "CALL infer_manifest('{dir_path}', 'manifest.json')"
+            ))
+            .unwrap();
+        let manifest_path = dir.path().join("manifest.json");
+        let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+        assert_eq!(manifest.matches(r#""logical_type": "Int64""#).count(), 2);
+        std::fs::write(
+            &manifest_path,
+            manifest.replace(r#""logical_type": "Int64""#, r#""logical_type": "Int32""#),
+        )
+        .unwrap();
+        session
+            .query(&format!(
+                "CALL import('overridden', '{dir_path}', 'manifest.json')"
+            ))
+            .unwrap();
+        let result = session
+            .query_on("overridden", "MATCH (n:person) RETURN n")
+            .unwrap();
+        assert_eq!(result.num_rows(), 3);
+    }
+}
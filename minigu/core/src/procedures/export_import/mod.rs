@@ -34,6 +34,8 @@
 
 pub mod export;
 pub mod import;
+pub mod infer;
+pub mod validate;
 
 type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync + 'static>>;
 type RecordType = Vec<String>;
@@ -100,11 +102,18 @@ fn from_schema(graph_type: Arc<dyn GraphTypeProvider>) -> Result<Self> {
 struct FileSpec {
     path: String,   // relative path
     format: String, // currently always "csv"
+    /// Whether the first row of the file is a header, which is skipped.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    has_headers: bool,
 }
 
 impl FileSpec {
     pub fn new(path: String, format: String) -> Self {
-        Self { path, format }
+        Self {
+            path,
+            format,
+            has_headers: false,
+        }
     }
 }
 
@@ -262,6 +271,7 @@ fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
 mod tests {
     use std::collections::BTreeMap;
     use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     use minigu_catalog::memory::graph_type::{
         MemoryEdgeTypeCatalog, MemoryGraphTypeCatalog, MemoryVertexTypeCatalog,
@@ -269,6 +279,7 @@ mod tests {
     use minigu_common::data_type::LogicalType;
     use minigu_common::types::{EdgeId, VertexId};
     use minigu_common::value::ScalarValue;
+    use minigu_context::progress::ProgressCallback;
     use minigu_storage::common::{Edge, PropertyRecord, Vertex};
     use minigu_storage::tp::MemoryGraph;
     use minigu_storage::tp::checkpoint::CheckpointManagerConfig;
@@ -322,7 +333,10 @@ fn mock_wal_config() -> WalManagerConfig {
         let filename = format!("wal_{}.log", chrono::Utc::now().format("%Y%m%d%H%M"));
         let wal_path = dir.as_ref().join(filename);
 
-        WalManagerConfig { wal_path }
+        WalManagerConfig {
+            wal_path,
+            ..Default::default()
+        }
     }
 
     fn mock_graph() -> Arc<MemoryGraph> {
@@ -517,23 +531,69 @@ fn test_export_and_import() {
                 export_dir1,
                 manifest_rel_path.as_ref(),
                 Arc::clone(&graph_type),
+                None,
+                None,
             )
             .unwrap();
         }
 
         {
             let manifest_path = export_dir1.join(manifest_rel_path);
-            let (graph, graph_type) = import(manifest_path).unwrap();
+            let (graph, graph_type) = import(
+                manifest_path,
+                mock_checkpoint_config(),
+                mock_wal_config(),
+                None,
+            )
+            .unwrap();
 
             export(
                 graph,
                 export_dir2,
                 manifest_rel_path.as_ref(),
                 graph_type.clone(),
+                None,
+                None,
             )
             .unwrap();
         }
 
         assert!(export_dirs_equal_semantically(export_dir1, export_dir2));
     }
+
+    #[test]
+    fn test_export_and_import_progress() {
+        let export_dir = tempfile::tempdir().unwrap();
+        let export_dir = export_dir.path();
+        let manifest_rel_path = "manifest.json";
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let callback = {
+            let invocations = invocations.clone();
+            ProgressCallback::new(2, move |_| {
+                invocations.fetch_add(1, Ordering::Relaxed);
+            })
+        };
+
+        // The mock graph has 4 vertices and 4 edges, i.e., 8 rows reported every 2 rows.
+        export(
+            mock_graph(),
+            export_dir,
+            manifest_rel_path.as_ref(),
+            Arc::new(mock_graph_type()),
+            None,
+            Some(&callback.start()),
+        )
+        .unwrap();
+        assert_eq!(invocations.load(Ordering::Relaxed), 4);
+
+        let manifest_path = export_dir.join(manifest_rel_path);
+        import(
+            manifest_path,
+            mock_checkpoint_config(),
+            mock_wal_config(),
+            Some(&callback.start()),
+        )
+        .unwrap();
+        assert_eq!(invocations.load(Ordering::Relaxed), 8);
+    }
 }
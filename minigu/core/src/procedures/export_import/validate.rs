@@ -0,0 +1,325 @@
+//! call validate_import(<dir_path>, <manifest_relative_path>);
+//!
+//! Validate a graph to be imported with `import`, i.e., its manifest and the CSV files it refers
+//! to, without building the graph.
+//!
+//! ## Inputs
+//! * `<dir_path>` – Directory that contains the CSV files and the manifest.
+//! * `<manifest_relative_path>` – File name or relative path (inside `dir_path`) to
+//!   `manifest.json`.
+//!
+//! ## Output
+//! * A row `(file, line, message)` for each mismatch between the files and the manifest: missing
+//!   files, rows with missing or extra columns, values whose type is incompatible with their
+//!   property, and edges between unknown vertices. No rows means the import should succeed. A
+//!   manifest which cannot be parsed is an error.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::{StringArray, UInt64Array};
+use csv::{ReaderBuilder, StringRecord};
+use minigu_catalog::property::Property;
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::data_type::{DataField, DataSchema, DataSchemaRef, LogicalType};
+use minigu_common::types::VertexId;
+use minigu_common::value::ScalarValue;
+use minigu_context::procedure::{Procedure, TableProcedure};
+use minigu_context::session::SessionContext;
+
+use crate::procedures::export_import::Result;
+use crate::procedures::export_import::import::{build_manifest, property_to_scalar_value};
+
+/// A mismatch between a file to be imported and the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ImportMismatch {
+    /// The path of the file, as written in the manifest.
+    pub file: String,
+    /// The line of the mismatching row, starting from 1, or 0 if the whole file mismatches.
+    pub line: u64,
+    pub message: String,
+}
+
+/// Checks the files referred to by the manifest at `manifest_path` against the properties it
+/// declares, returning the mismatches found.
+pub(crate) fn validate<P: AsRef<Path>>(manifest_path: P) -> Result<Vec<ImportMismatch>> {
+    let manifest = build_manifest(&manifest_path)?;
+    let manifest_parent_dir = manifest_path.as_ref().parent().ok_or_else(|| {
+        anyhow::anyhow!(
+            "manifest path has no parent directory: {}",
+            manifest_path.as_ref().display()
+        )
+    })?;
+    let mut mismatches = Vec::new();
+
+    // 1. Vertices, whose ids are collected per label to check the endpoints of the edges.
+    let mut vertex_ids: HashMap<&str, HashSet<VertexId>> = HashMap::new();
+    for vertex_spec in manifest.vertices_spec() {
+        let ids = vertex_ids
+            .entry(vertex_spec.label_name().as_str())
+            .or_default();
+        let file = &vertex_spec.file.path;
+        let mut report = |line: u64, message: String| {
+            mismatches.push(ImportMismatch {
+                file: file.clone(),
+                line,
+                message,
+            })
+        };
+        let path = manifest_parent_dir.join(file);
+        let has_headers = vertex_spec.file.has_headers;
+        for_each_record(&path, has_headers, &mut report, |record, report| {
+            let Some(vid) = parse_id(record, 0, "vertex id", report) else {
+                return;
+            };
+            ids.insert(vid);
+            check_properties(record, 1, vertex_spec.properties(), report);
+        });
+    }
+
+    // 2. Edges
+    for edge_spec in manifest.edges_spec() {
+        let file = &edge_spec.file.path;
+        let mut report = |line: u64, message: String| {
+            mismatches.push(ImportMismatch {
+                file: file.clone(),
+                line,
+                message,
+            })
+        };
+        let path = manifest_parent_dir.join(file);
+        let endpoints = [
+            (1, "source", edge_spec.src_label()),
+            (2, "destination", edge_spec.dst_label()),
+        ];
+        let has_headers = edge_spec.file.has_headers;
+        for_each_record(&path, has_headers, &mut report, |record, report| {
+            parse_id(record, 0, "edge id", report);
+            for (index, endpoint, label) in endpoints {
+                let Some(vid) = parse_id(record, index, endpoint, report) else {
+                    continue;
+                };
+                if !vertex_ids
+                    .get(label.as_str())
+                    .is_some_and(|ids| ids.contains(&vid))
+                {
+                    report(format!(
+                        "{endpoint} vertex {vid} not found in `{label}` vertices"
+                    ));
+                }
+            }
+            check_properties(record, 3, edge_spec.properties(), report);
+        });
+    }
+
+    Ok(mismatches)
+}
+
+/// Calls `f` with each record of the CSV file at `path`, along with a function reporting a
+/// mismatch at the line of the record. Unreadable files and records are reported as is.
+fn for_each_record(
+    path: &Path,
+    has_headers: bool,
+    report: &mut dyn FnMut(u64, String),
+    mut f: impl FnMut(&StringRecord, &mut dyn FnMut(String)),
+) {
+    let mut reader = match ReaderBuilder::new()
+        .has_headers(has_headers)
+        .flexible(true)
+        .from_path(path)
+    {
+        Ok(reader) => reader,
+        Err(e) => return report(0, format!("cannot open file: {e}")),
+    };
+    for record in reader.records() {
+        match record {
+            Ok(record) => {
+                let line = record.position().map_or(0, |p| p.line());
+                f(&record, &mut |message| report(line, message));
+            }
+            Err(e) => {
+                let line = e.position().map_or(0, |p| p.line());
+                report(line, e.to_string());
+                if e.is_io_error() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn parse_id(
+    record: &StringRecord,
+    index: usize,
+    name: &str,
+    report: &mut dyn FnMut(String),
+) -> Option<VertexId> {
+    let value = record.get(index)?;
+    match value.parse() {
+        Ok(id) => Some(id),
+        Err(e) => {
+            report(format!("invalid {name} `{value}`: {e}"));
+            None
+        }
+    }
+}
+
+/// Checks the properties of `record`, which start at `offset`, against `properties`.
+fn check_properties(
+    record: &StringRecord,
+    offset: usize,
+    properties: &[Property],
+    report: &mut dyn FnMut(String),
+) {
+    let expected = offset + properties.len();
+    if record.len() != expected {
+        report(format!(
+            "expected {expected} columns, found {}",
+            record.len()
+        ));
+        return;
+    }
+    for (property, value) in properties.iter().zip(record.iter().skip(offset)) {
+        if let Err(e) = property_to_scalar_value(property, value) {
+            report(format!(
+                "invalid value `{value}` of `{}` ({}): {e}",
+                property.name(),
+                property.logical_type()
+            ));
+        }
+    }
+}
+
+pub fn build_procedure() -> Procedure {
+    Procedure::from_table(ValidateImport)
+}
+
+struct ValidateImport;
+
+impl TableProcedure for ValidateImport {
+    fn parameters(&self) -> Vec<LogicalType> {
+        // Directory path, manifest relative path
+        vec![LogicalType::String, LogicalType::String]
+    }
+
+    fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::new(vec![
+            DataField::new("file".into(), LogicalType::String, false),
+            DataField::new("line".into(), LogicalType::UInt64, false),
+            DataField::new("message".into(), LogicalType::String, false),
+        ]))
+    }
+
+    fn call(
+        &self,
+        _context: SessionContext,
+        args: Vec<ScalarValue>,
+    ) -> std::result::Result<Vec<DataChunk>, Box<dyn Error + Send + Sync + 'static>> {
+        assert_eq!(args.len(), 2);
+        let dir_path = args[0]
+            .try_as_string()
+            .expect("directory path must be a string")
+            .clone()
+            .expect("directory path can't be empty");
+        let manifest_rel_path = args[1]
+            .try_as_string()
+            .expect("manifest relative path must be a string")
+            .clone()
+            .expect("manifest relative path can't be empty");
+
+        let manifest_path = (dir_path.as_ref() as &Path).join(manifest_rel_path);
+        let mismatches = validate(manifest_path)?;
+        let files = StringArray::from_iter_values(mismatches.iter().map(|m| &m.file));
+        let lines = UInt64Array::from_iter_values(mismatches.iter().map(|m| m.line));
+        let messages = StringArray::from_iter_values(mismatches.iter().map(|m| &m.message));
+        Ok(vec![DataChunk::new(vec![
+            Arc::new(files),
+            Arc::new(lines),
+            Arc::new(messages),
+        ])])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::AsArray;
+    use arrow::datatypes::UInt64Type;
+    use minigu_catalog::provider::SchemaProvider;
+
+    use super::*;
+    use crate::database::{Database, DatabaseConfig};
+
+    const MANIFEST: &str = r#"{
+        "vertices": [{
+            "label": "person",
+            "file": { "path": "person.csv", "format": "csv" },
+            "properties": [
+                { "name": "name", "logical_type": "String", "nullable": false },
+                { "name": "age", "logical_type": "Int32", "nullable": true }
+            ]
+        }],
+        "edges": [{
+            "label": "knows",
+            "src_label": "person",
+            "dst_label": "person",
+            "file": { "path": "knows.csv", "format": "csv" },
+            "properties": []
+        }]
+    }"#;
+
+    #[test]
+    fn test_validate_import() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("manifest.json"), MANIFEST).unwrap();
+        std::fs::write(
+            dir.path().join("person.csv"),
+            "1,alice,30\n2,bob,old\n3,carol\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("knows.csv"), "1,1,2\n2,1,4\n").unwrap();
+
+        let mismatches = validate(dir.path().join("manifest.json")).unwrap();
+        let found: Vec<_> = mismatches
+            .iter()
+            .map(|m| (m.file.as_str(), m.line))
+            .collect();
+        assert_eq!(
+            found,
+            [("person.csv", 2), ("person.csv", 3), ("knows.csv", 2)]
+        );
+        assert!(
+            mismatches[0]
+                .message
+                .starts_with("invalid value `old` of `age` (int32)")
+        );
+        assert_eq!(mismatches[1].message, "expected 3 columns, found 2");
+        assert_eq!(
+            mismatches[2].message,
+            "destination vertex 4 not found in `person` vertices"
+        );
+
+        let db = Database::open_in_memory(&DatabaseConfig::default()).unwrap();
+        let mut session = db.session().unwrap();
+        let graph_names = session.current_schema().unwrap().graph_names();
+        let result = session
+            .query(&format!(
+                "CALL validate_import('{}', 'manifest.json')",
+                dir.path().display()
+            ))
+            .unwrap();
+        let lines: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| {
+                chunk.columns()[1]
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        assert_eq!(lines, [2, 3, 2]);
+        // Nothing was imported.
+        assert_eq!(session.current_schema().unwrap().graph_names(), graph_names);
+    }
+}
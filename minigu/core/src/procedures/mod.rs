@@ -1,7 +1,9 @@
+mod analyze;
+mod benchmark;
 mod create_test_graph;
 mod create_test_graph_data;
 mod echo;
-mod export_import;
+pub(crate) mod export_import;
 mod show_graph;
 mod show_procedures;
 
@@ -24,10 +26,20 @@ pub fn build_predefined_procedures() -> Vec<(String, Procedure)> {
         ),
         // Show graph in current schema.
         ("show_graph".to_string(), show_graph::build_procedure()),
+        ("benchmark".to_string(), benchmark::build_procedure()),
+        ("analyze".to_string(), analyze::build_procedure()),
         (
             "import".to_string(),
             export_import::import::build_procedure(),
         ),
+        (
+            "infer_manifest".to_string(),
+            export_import::infer::build_procedure(),
+        ),
+        (
+            "validate_import".to_string(),
+            export_import::validate::build_procedure(),
+        ),
         (
             "export".to_string(),
             export_import::export::build_procedure(),
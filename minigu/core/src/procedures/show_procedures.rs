@@ -1,20 +1,39 @@
+use std::error::Error;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, StringArray};
+use arrow::array::StringArray;
 use itertools::Itertools;
 use minigu_catalog::provider::SchemaProvider;
-use minigu_common::data_chunk;
 use minigu_common::data_chunk::DataChunk;
-use minigu_common::data_type::{DataField, DataSchema, LogicalType};
-use minigu_context::procedure::Procedure;
+use minigu_common::data_type::{DataField, DataSchema, DataSchemaRef, LogicalType};
+use minigu_common::value::ScalarValue;
+use minigu_context::procedure::{Procedure, TableProcedure};
+use minigu_context::session::SessionContext;
 
 /// Show all procedures in current schema.
 pub fn build_procedure() -> Procedure {
-    let schema = Arc::new(DataSchema::new(vec![
-        DataField::new("name".into(), LogicalType::String, false),
-        DataField::new("params".into(), LogicalType::String, false),
-    ]));
-    Procedure::new(vec![], Some(schema.clone()), move |context, args| {
+    Procedure::from_table(ShowProcedures)
+}
+
+struct ShowProcedures;
+
+impl TableProcedure for ShowProcedures {
+    fn parameters(&self) -> Vec<LogicalType> {
+        vec![]
+    }
+
+    fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::new(vec![
+            DataField::new("name".into(), LogicalType::String, false),
+            DataField::new("params".into(), LogicalType::String, false),
+        ]))
+    }
+
+    fn call(
+        &self,
+        context: SessionContext,
+        args: Vec<ScalarValue>,
+    ) -> Result<Vec<DataChunk>, Box<dyn Error + Send + Sync + 'static>> {
         assert!(args.is_empty());
         let chunk = if let Some(current_schema) = context.current_schema {
             let names = current_schema.procedure_names();
@@ -33,8 +52,79 @@ pub fn build_procedure() -> Procedure {
             let parameters = Arc::new(StringArray::from_iter_values(parameters));
             DataChunk::new(vec![names, parameters])
         } else {
-            DataChunk::new_empty(&schema)
+            DataChunk::new_empty(&self.schema())
         };
         Ok(vec![chunk])
-    })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::AsArray;
+
+    use super::*;
+    use crate::database::{Database, DatabaseConfig};
+
+    #[test]
+    fn test_show_procedures_yield() {
+        let db = Database::open_in_memory(&DatabaseConfig::default()).unwrap();
+        let mut session = db.session().unwrap();
+        let result = session
+            .query("CALL show_procedures() YIELD name AS procedure_name, params")
+            .unwrap();
+        let schema = result.schema().expect("result should have a schema");
+        let fields: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name(), f.ty().clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("procedure_name", LogicalType::String),
+                ("params", LogicalType::String)
+            ]
+        );
+        let names: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| {
+                chunk.columns()[0]
+                    .as_string::<i32>()
+                    .iter()
+                    .map(|name| name.unwrap().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert!(names.iter().any(|name| name == "echo"));
+
+        let result = session
+            .query("CALL show_procedures() YIELD params, name")
+            .unwrap();
+        let schema = result.schema().expect("result should have a schema");
+        assert_eq!(schema.fields()[0].name(), "params");
+        assert!(
+            session
+                .query("CALL show_procedures() YIELD missing")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_show_procedures_filter() {
+        let db = Database::open_in_memory(&DatabaseConfig::default()).unwrap();
+        let mut session = db.session().unwrap();
+        let result = session
+            .query("CALL show_procedures() YIELD name, params FILTER name = 'echo' RETURN name")
+            .unwrap();
+        // The filtered out rows may still be in the chunks, so the rows are read through the
+        // filters of the chunks.
+        let rows: Vec<_> = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .collect();
+        let [row] = rows.as_slice() else {
+            panic!("expected a single row");
+        };
+        assert_eq!(row.get(0), Some(&"echo".into()));
+    }
 }
@@ -3,8 +3,9 @@
 use arrow::array::RecordBatch;
 use minigu_common::data_chunk::DataChunk;
 use minigu_common::data_type::{DataSchema, DataSchemaRef};
+use minigu_common::types::VertexId;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::metrics::QueryMetrics;
 
 #[derive(Debug, Default)]
@@ -12,6 +13,7 @@ pub struct QueryResult {
     pub(crate) schema: Option<DataSchemaRef>,
     pub(crate) metrics: QueryMetrics,
     pub(crate) chunks: Vec<DataChunk>,
+    pub(crate) cursor: Option<Cursor>,
 }
 
 impl QueryResult {
@@ -25,10 +27,79 @@ pub fn metrics(&self) -> &QueryMetrics {
         &self.metrics
     }
 
+    /// Returns the cursor from which [`Session::query_after`] fetches the next page of the result,
+    /// if the query is paginated and the page is full.
+    ///
+    /// A query is paginated if it scans the vertices of a single variable under a `LIMIT`, and
+    /// returns that variable, e.g., `MATCH (n) RETURN n LIMIT 100`.
+    ///
+    /// [`Session::query_after`]: crate::session::Session::query_after
+    #[inline]
+    pub fn cursor(&self) -> Option<Cursor> {
+        self.cursor
+    }
+
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &DataChunk> {
         self.chunks.iter()
     }
+
+    /// Converts the chunks of the result into arrow record batches of its schema, or returns
+    /// `None` if the result has no schema, e.g., for a `SESSION SET` statement.
+    pub fn to_record_batches(&self) -> Option<Vec<RecordBatch>> {
+        let schema = self.schema.as_ref()?;
+        Some(
+            self.chunks
+                .iter()
+                .map(|chunk| chunk.to_arrow_record_batch(schema))
+                .collect(),
+        )
+    }
+
+    /// Returns the number of rows of the result.
+    pub fn num_rows(&self) -> usize {
+        self.chunks.iter().map(DataChunk::cardinality).sum()
+    }
+
+    /// Collects the chunks of the result, failing with [`Error::ResultTooLarge`] if it has more
+    /// than `max_rows` rows.
+    ///
+    /// This guards callers that keep the whole result around (e.g., to render it) against
+    /// unexpectedly large results.
+    pub fn collect_capped(self, max_rows: usize) -> Result<Vec<DataChunk>> {
+        let mut num_rows = 0;
+        let mut chunks = Vec::with_capacity(self.chunks.len());
+        for chunk in self.chunks {
+            num_rows += chunk.cardinality();
+            if num_rows > max_rows {
+                return Err(Error::ResultTooLarge { max_rows });
+            }
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+}
+
+/// The position of a paginated query after the last row of a page, i.e., the id of the last vertex
+/// scanned for it.
+///
+/// The scans of paginated queries visit vertices in ascending order of id, so the next page is
+/// fetched by resuming the scan right after that vertex, instead of rescanning the previous pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    last_vertex_id: VertexId,
+}
+
+impl Cursor {
+    #[inline]
+    pub fn new(last_vertex_id: VertexId) -> Self {
+        Self { last_vertex_id }
+    }
+
+    #[inline]
+    pub fn last_vertex_id(&self) -> VertexId {
+        self.last_vertex_id
+    }
 }
 
 impl IntoIterator for QueryResult {
@@ -41,3 +112,34 @@ fn into_iter(self) -> Self::IntoIter {
         self.chunks.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::data_chunk;
+
+    use super::*;
+
+    fn result() -> QueryResult {
+        QueryResult {
+            chunks: vec![
+                data_chunk!((Int32, [1, 2, 3])),
+                data_chunk!({ true, false }, (Int32, [4, 5])),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collect_capped() {
+        assert_eq!(result().num_rows(), 4);
+        let chunks = result().collect_capped(4).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(DataChunk::cardinality).sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn test_collect_capped_exceeded() {
+        let err = result().collect_capped(3).unwrap_err();
+        assert!(matches!(err, Error::ResultTooLarge { max_rows: 3 }));
+    }
+}
@@ -1,68 +1,284 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
+use arrow::array::AsArray;
+use arrow::datatypes::UInt64Type;
 use gql_parser::ast::{
-    GraphExpr, Procedure, ProgramActivity, SessionActivity, SessionResetArgs, SessionSet,
-    TransactionActivity,
+    EndTransaction, GraphExpr, GraphRef, Procedure, Program, ProgramActivity, SessionActivity,
+    SessionResetArgs, SessionSet, StartTransaction, TransactionActivity, TransactionMode,
 };
-use gql_parser::parse_gql;
+use gql_parser::{ast, parse_gql};
 use itertools::Itertools;
 use minigu_catalog::memory::schema::MemorySchemaCatalog;
+use minigu_catalog::named_ref::NamedGraphRef;
+use minigu_common::arithmetic::{DivisionByZeroPolicy, OverflowPolicy};
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::data_type::{DataSchema, LogicalType};
 use minigu_common::error::not_implemented;
+use minigu_common::types::VertexId;
 use minigu_context::database::DatabaseContext;
+use minigu_context::graph::{GraphContainer, GraphWriter};
+use minigu_context::progress::ProgressCallback;
 use minigu_context::session::SessionContext;
 use minigu_execution::builder::ExecutorBuilder;
+use minigu_execution::error::ExecutionResult;
 use minigu_execution::executor::Executor;
+use minigu_execution::executor::metered::ExecutionMetrics;
 use minigu_planner::Planner;
-use minigu_planner::plan::PlanData;
+use minigu_planner::plan::{PlanData, PlanNode};
+use minigu_transaction::{IsolationLevel, Timestamp};
 
 use crate::error::{Error, Result};
 use crate::metrics::QueryMetrics;
-use crate::result::QueryResult;
+use crate::result::{Cursor, QueryResult};
 
+/// A session on a [`Database`](crate::database::Database).
+///
+/// A session is [`Send`], so it can be handed over to another thread (e.g., kept in a connection
+/// pool), but it must not be used by several threads at the same time. Open one session per
+/// concurrent user instead.
 pub struct Session {
     context: SessionContext,
     closed: bool,
+    cancelled: Arc<AtomicBool>,
+    cursor: Option<Cursor>,
+    #[cfg(feature = "morsel")]
+    morsels: bool,
 }
 
 impl Session {
     pub(crate) fn new(
         database: Arc<DatabaseContext>,
         default_schema: Arc<MemorySchemaCatalog>,
+        default_graph: Option<&str>,
     ) -> Result<Self> {
         let mut context = SessionContext::new(database);
         context.home_schema = Some(default_schema.clone());
         context.current_schema = Some(default_schema);
-        Ok(Self {
+        if let Some(graph_name) = default_graph {
+            context.set_current_graph(graph_name.to_string())?;
+            context.home_graph = context.current_graph.clone();
+        }
+        Ok(Self::from_context(context))
+    }
+
+    /// Creates a session in the state of `context`, e.g., to run queries from a procedure.
+    pub(crate) fn from_context(context: SessionContext) -> Self {
+        Self {
             context,
             closed: false,
-        })
+            cancelled: Arc::new(AtomicBool::new(false)),
+            cursor: None,
+            #[cfg(feature = "morsel")]
+            morsels: false,
+        }
+    }
+
+    /// Returns a flag which cancels the running query of the session once set, e.g., from another
+    /// thread.
+    ///
+    /// The query then fails with [`ExecutionError::Cancelled`], and the flag is cleared for the
+    /// next query.
+    ///
+    /// [`ExecutionError::Cancelled`]: minigu_execution::error::ExecutionError::Cancelled
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+
+    /// Returns the name of the current graph of the session, as set by `SESSION SET GRAPH`.
+    pub fn current_graph(&self) -> Option<&str> {
+        self.context
+            .current_graph
+            .as_ref()
+            .map(|graph| graph.name().as_str())
+    }
+
+    /// Returns the current schema of the session, as set by `SESSION SET SCHEMA`.
+    pub fn current_schema(&self) -> Option<&Arc<MemorySchemaCatalog>> {
+        self.context.current_schema.as_ref()
+    }
+
+    /// Sets the callback notified of the progress of the long-running procedures called in the
+    /// session, i.e., `import` and `export`, or unsets it with `None`.
+    pub fn set_progress_callback(&mut self, progress: Option<ProgressCallback>) {
+        self.context.progress = progress;
+    }
+
+    /// Sets how the integer additions, subtractions and multiplications of the queries of the
+    /// session resolve overflows, overriding the policy of the database.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.context.overflow_policy = policy;
     }
 
+    /// Sets how the integer divisions and remainders by zero of the queries of the session are
+    /// resolved, overriding the policy of the database.
+    pub fn set_division_by_zero_policy(&mut self, policy: DivisionByZeroPolicy) {
+        self.context.division_by_zero_policy = policy;
+    }
+
+    /// Sets the number of digits after the decimal point of the floats written by the exports of
+    /// the session, or `None` to write them in full.
+    pub fn set_float_precision(&mut self, float_precision: Option<usize>) {
+        self.context.float_precision = float_precision;
+    }
+
+    /// Sets the isolation level of the transactions of the session which do not specify one,
+    /// overriding the default isolation level of the database.
+    pub fn set_default_isolation(&mut self, isolation: IsolationLevel) {
+        self.context.default_isolation = isolation;
+    }
+
+    /// Sets the memory, in bytes, past which the grouped aggregations of the queries of the session
+    /// spill their groups to disk, or keeps them in memory with `None`, overriding the limit of the
+    /// database. The limit must not be zero.
+    pub fn set_aggregate_memory_limit(&mut self, memory_limit: Option<usize>) {
+        assert_ne!(memory_limit, Some(0), "memory limit must be positive");
+        self.context.aggregate_memory_limit = memory_limit;
+    }
+
+    /// Makes the queries of the session read the graphs as of `ts`, i.e., only see the
+    /// modifications committed at or before it, or read their latest versions again with `None`.
+    ///
+    /// Queries modifying the graphs fail with [`Error::ReadOnlySnapshot`] while it is set, and the
+    /// ones reading them fail if the versions as of `ts` were already garbage collected.
+    pub fn set_read_timestamp(&mut self, ts: Option<Timestamp>) {
+        self.context.read_timestamp = ts;
+    }
+
+    /// Returns the commit timestamp of the latest modification of the current graph, e.g., to read
+    /// it as of that point later with [`set_read_timestamp`](Self::set_read_timestamp).
+    pub fn latest_commit_timestamp(&self) -> Option<Timestamp> {
+        let graph = self.context.current_graph.as_ref()?.object();
+        let container = graph.as_any().downcast_ref::<GraphContainer>()?;
+        Some(container.latest_commit_ts())
+    }
+
+    /// Executes `query`, returning the result set of its last statement, e.g., of `RETURN 2` in
+    /// `RETURN 1 NEXT RETURN 2`. Use [`query_all`](Self::query_all) to get those of every
+    /// statement.
     pub fn query(&mut self, query: &str) -> Result<QueryResult> {
+        Ok(self.query_all(query)?.pop().unwrap_or_default())
+    }
+
+    /// Executes `query`, returning a result set for each of its statements, in order, e.g., two
+    /// for `MATCH (n) RETURN n NEXT MATCH ()-[e]->() RETURN e`.
+    ///
+    /// The statements following `NEXT` run one after the other, as queries of their own: passing
+    /// the result of a statement to the next one with `YIELD` is not supported yet. The parsing
+    /// time is reported in the metrics of the last result set.
+    pub fn query_all(&mut self, query: &str) -> Result<Vec<QueryResult>> {
         if self.closed {
             return Err(Error::SessionClosed);
         }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("query").entered();
         let start = Instant::now();
-        let program = parse_gql(query)?;
+        let program = {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("parse").entered();
+            parse_gql(query)?
+        };
         let parsing_time = start.elapsed();
-        let mut result = program
-            .value()
+        let mut results = self.execute_program_all(program.value())?;
+        if let Some(result) = results.last_mut() {
+            result.metrics.parsing_time = parsing_time;
+        }
+        Ok(results)
+    }
+
+    /// Executes `program`, e.g., parsed once by [`parse_gql`] and executed many times, binding,
+    /// planning and executing it as [`query`](Self::query) does, but without parsing it again.
+    ///
+    /// The parsing time of the returned metrics is zero.
+    pub fn execute_program(&mut self, program: &Program) -> Result<QueryResult> {
+        Ok(self.execute_program_all(program)?.pop().unwrap_or_default())
+    }
+
+    /// Executes `program` as [`query_all`](Self::query_all) does, but without parsing it again.
+    pub fn execute_program_all(&mut self, program: &Program) -> Result<Vec<QueryResult>> {
+        if self.closed {
+            return Err(Error::SessionClosed);
+        }
+        let results = program
             .activity
             .as_ref()
             .map(|activity| match activity.value() {
-                ProgramActivity::Session(activity) => self.handle_session_activity(activity),
+                ProgramActivity::Session(activity) => {
+                    Ok(vec![self.handle_session_activity(activity)?])
+                }
                 ProgramActivity::Transaction(activity) => {
                     self.handle_transaction_activity(activity)
                 }
             })
             .transpose()?
             .unwrap_or_default();
-        result.metrics.parsing_time = parsing_time;
-        if program.value().session_close {
+        if program.session_close {
             self.closed = true;
         }
-        Ok(result)
+        Ok(results)
+    }
+
+    /// Returns the physical plan of `query` in its textual form (see [`PlanNode::explain`]),
+    /// without executing it.
+    pub fn explain(&self, query: &str) -> Result<String> {
+        if self.closed {
+            return Err(Error::SessionClosed);
+        }
+        let program = parse_gql(query)?;
+        let activity = program.value().activity.as_ref().map(|a| a.value());
+        let Some(ProgramActivity::Transaction(activity)) = activity else {
+            return not_implemented("explaining a session activity", None);
+        };
+        let (None, None, Some(procedure)) = (&activity.start, &activity.end, &activity.procedure)
+        else {
+            return not_implemented("explaining a transaction", None);
+        };
+        let plan = Planner::new(self.context.clone()).plan_query(procedure.value())?;
+        Ok(plan.explain())
+    }
+
+    /// Executes `query` against the graph named `graph_name` in the current schema, as if it were
+    /// the current graph.
+    ///
+    /// The override only applies to this call: the session's current graph is left untouched, so
+    /// callers working with several graphs do not need to issue `SESSION SET GRAPH` before every
+    /// statement.
+    pub fn query_on(&mut self, graph_name: &str, query: &str) -> Result<QueryResult> {
+        if self.closed {
+            return Err(Error::SessionClosed);
+        }
+        let previous = self.context.current_graph.clone();
+        self.context.set_current_graph(graph_name.to_string())?;
+        let result = self.query(query);
+        self.context.current_graph = previous;
+        result
+    }
+
+    /// Executes the paginated `query` from `cursor`, i.e., returns the page of its result following
+    /// the one `cursor` was returned with by [`QueryResult::cursor`].
+    ///
+    /// `query` should be the query of the previous page. It fails with [`Error::NotResumable`] if
+    /// it is not paginated.
+    pub fn query_after(&mut self, query: &str, cursor: Cursor) -> Result<QueryResult> {
+        self.cursor = Some(cursor);
+        let result = self.query(query);
+        self.cursor = None;
+        result
+    }
+
+    /// Executes `query`, running its scans, along with the filters and projections right above
+    /// them, as morsel-driven pipelines: the scanned vertices are cut into fixed-size morsels,
+    /// which the scan threads process in parallel.
+    ///
+    /// The rows are returned in no particular order. The query is executed as usual by
+    /// [`query`](Self::query) if the database is configured with a `parallelism` of 1.
+    #[cfg(feature = "morsel")]
+    pub fn query_with_morsels(&mut self, query: &str) -> Result<QueryResult> {
+        self.morsels = true;
+        let result = self.query(query);
+        self.morsels = false;
+        result
     }
 
     fn handle_session_activity(&mut self, activity: &SessionActivity) -> Result<QueryResult> {
@@ -73,8 +289,9 @@ fn handle_session_activity(&mut self, activity: &SessionActivity) -> Result<Quer
                     self.context.set_current_schema(sp_ref.value().clone())?;
                 }
                 SessionSet::Graph(sp_ref) => match sp_ref.value() {
-                    GraphExpr::Name(graph_name) => {
-                        self.context.set_current_graph(graph_name.to_string());
+                    // Delimited names, e.g., `my-graph`, are parsed into graph references.
+                    GraphExpr::Name(graph_name) | GraphExpr::Ref(GraphRef::Name(graph_name)) => {
+                        self.context.set_current_graph(graph_name.to_string())?;
                     }
                     _ => {
                         return not_implemented("not allowed there", None);
@@ -105,20 +322,90 @@ fn handle_session_activity(&mut self, activity: &SessionActivity) -> Result<Quer
         Ok(QueryResult::default())
     }
 
-    fn handle_transaction_activity(&self, activity: &TransactionActivity) -> Result<QueryResult> {
-        if activity.start.is_some() {
-            return not_implemented("start transaction", None);
+    fn handle_transaction_activity(
+        &mut self,
+        activity: &TransactionActivity,
+    ) -> Result<Vec<QueryResult>> {
+        if let Some(start) = &activity.start {
+            return self.handle_explicit_transaction(start.value(), activity);
         }
         if activity.end.is_some() {
             return not_implemented("end transaction", None);
         }
-        let result = activity
+        let results = activity
             .procedure
             .as_ref()
-            .map(|procedure| self.handle_procedure(procedure.value()))
+            .map(|procedure| self.handle_statements(procedure.value()))
             .transpose()?
             .unwrap_or_default();
-        Ok(result)
+        Ok(results)
+    }
+
+    /// Runs the procedure of a transaction started explicitly, with the isolation level it
+    /// specifies, or the default one of the session. The queries of a read-only transaction fail
+    /// with [`Error::ReadOnlyTransaction`] if they modify the graph.
+    ///
+    /// Transactions spanning several activities are not supported yet, so the procedure must
+    /// follow the start of the transaction, and may only be followed by a commit.
+    fn handle_explicit_transaction(
+        &mut self,
+        start: &StartTransaction,
+        activity: &TransactionActivity,
+    ) -> Result<Vec<QueryResult>> {
+        let Some(procedure) = &activity.procedure else {
+            return not_implemented("start transaction without a procedure", None);
+        };
+        if let Some(EndTransaction::Rollback) = activity.end.as_ref().map(|end| end.value()) {
+            return not_implemented("rollback", None);
+        }
+        let isolation = transaction_isolation(start, self.context.default_isolation)?;
+        self.context.transaction_isolation = Some(isolation);
+        self.context.transaction_read_only = is_read_only(start);
+        // The statements read and modify the current graph within the same transaction, so each
+        // of them sees the modifications of the previous ones.
+        let results = match begin_transaction(&self.context) {
+            Ok(Some((context, writer))) => {
+                let previous = std::mem::replace(&mut self.context, context);
+                let results = self.handle_statements(procedure.value());
+                self.context = previous;
+                end_transaction(Some(writer), results)
+            }
+            Ok(None) => self.handle_statements(procedure.value()),
+            Err(e) => Err(e),
+        };
+        self.context.transaction_isolation = None;
+        self.context.transaction_read_only = false;
+        results
+    }
+
+    /// Runs the statement of `procedure` and the ones following it with `NEXT`, one after the
+    /// other, returning the result set of each.
+    ///
+    /// Each statement is planned and executed as a procedure of its own, so it cannot refer to the
+    /// results of the previous ones, i.e., next statements with a `YIELD` clause are not supported.
+    fn handle_statements(&self, procedure: &Procedure) -> Result<Vec<QueryResult>> {
+        if procedure.next_statements.is_empty() {
+            return Ok(vec![self.handle_procedure(procedure)?]);
+        }
+        let mut statements = vec![procedure.statement.clone()];
+        for next in &procedure.next_statements {
+            let next = next.value();
+            if next.yield_clause.is_some() {
+                return not_implemented("yield clause of next statement", None);
+            }
+            statements.push(next.statement.clone());
+        }
+        statements
+            .into_iter()
+            .map(|statement| {
+                self.handle_procedure(&Procedure {
+                    at: procedure.at.clone(),
+                    binding_variable_defs: procedure.binding_variable_defs.clone(),
+                    statement,
+                    next_statements: Vec::new(),
+                })
+            })
+            .collect()
     }
 
     fn handle_procedure(&self, procedure: &Procedure) -> Result<QueryResult> {
@@ -129,18 +416,1536 @@ fn handle_procedure(&self, procedure: &Procedure) -> Result<QueryResult> {
         let physical_plan = planner.plan_query(procedure)?;
         metrics.planning_time = start.elapsed();
 
+        if self.context.read_timestamp.is_some() && modifies_graph(&physical_plan) {
+            return Err(Error::ReadOnlySnapshot);
+        }
+        if self.context.transaction_read_only && modifies_graph(&physical_plan) {
+            return Err(Error::ReadOnlyTransaction);
+        }
+
         let schema = physical_plan.schema().cloned();
+        let pagination = paginated_scan(&physical_plan);
+        if self.cursor.is_some() && pagination.is_none() {
+            return Err(Error::NotResumable);
+        }
+        // Paginated queries scan vertices in order, so that the next page resumes the scan after
+        // the last vertex of the previous one.
+        let sorted_scan = pagination.map(|_| {
+            let start = self
+                .cursor
+                .map_or(0, |cursor| cursor.last_vertex_id().saturating_add(1));
+            start..=VertexId::MAX
+        });
+        // Outside of explicit transactions, a statement modifying the graph runs within a
+        // transaction of its own, which its reads share with its writes.
+        let (context, writer) =
+            if self.context.transaction_isolation.is_none() && modifies_graph(&physical_plan) {
+                match begin_transaction(&self.context)? {
+                    Some((context, writer)) => (context, Some(writer)),
+                    None => (self.context.clone(), None),
+                }
+            } else {
+                (self.context.clone(), None)
+            };
+        let execution_metrics = Arc::new(ExecutionMetrics::default());
         let start = Instant::now();
-        let chunks: Vec<_> = self.context.database().runtime().scope(|_| {
-            let mut executor = ExecutorBuilder::new(self.context.clone()).build(&physical_plan);
+        let execute = || -> ExecutionResult<Vec<DataChunk>> {
+            let builder = ExecutorBuilder::new(context.clone())
+                .with_cancellation(self.cancelled.clone())
+                .with_metrics(execution_metrics.clone());
+            let builder = match &sorted_scan {
+                Some(range) => builder.with_sorted_scan(range.clone()),
+                None => builder,
+            };
+            #[cfg(feature = "morsel")]
+            let builder = builder.with_morsels(self.morsels);
+            let mut executor = builder.build(&physical_plan);
             executor.into_iter().try_collect()
-        })?;
+        };
+        // The query is executed on a thread of the runtime, which must report to the subscriber of
+        // the calling thread, under the span of the query.
+        #[cfg(feature = "tracing")]
+        let execute = {
+            let dispatch = tracing::dispatcher::get_default(Clone::clone);
+            let span = tracing::info_span!("execute", rows = tracing::field::Empty);
+            move || {
+                tracing::dispatcher::with_default(&dispatch, || {
+                    span.in_scope(|| -> ExecutionResult<_> {
+                        let chunks = execute()?;
+                        let rows: usize = chunks.iter().map(DataChunk::cardinality).sum();
+                        span.record("rows", rows);
+                        Ok(chunks)
+                    })
+                })
+            }
+        };
+        let chunks = self.context.database().runtime().scope(|_| execute());
+        self.cancelled.store(false, Ordering::Relaxed);
+        let chunks = end_transaction(writer, chunks.map_err(Error::from))?;
         metrics.execution_time = start.elapsed();
+        metrics.chunks_produced = execution_metrics.chunks_produced();
+        metrics.bytes_scanned = execution_metrics.bytes_scanned();
+        let cursor = pagination
+            .and_then(|(var, limit)| page_cursor(schema.as_deref()?, &chunks, var, limit));
 
         Ok(QueryResult {
             schema,
             metrics,
             chunks,
+            cursor,
         })
     }
 }
+
+/// Begins a transaction on the current graph of `context`, with the isolation level of the
+/// session. Returns a copy of `context` whose current graph is read and modified within the
+/// transaction, along with the writer ending it.
+///
+/// Returns `None` if there is no current graph, or if it is read as of a past timestamp, in which
+/// case it cannot be modified anyway.
+fn begin_transaction(context: &SessionContext) -> Result<Option<(SessionContext, GraphWriter)>> {
+    if context.read_timestamp.is_some() {
+        return Ok(None);
+    }
+    let Some(graph) = &context.current_graph else {
+        return Ok(None);
+    };
+    let Some(container) = graph.as_any().downcast_ref::<GraphContainer>() else {
+        return Ok(None);
+    };
+    let container = container.with_isolation(context.isolation_level());
+    let writer = if context.transaction_read_only {
+        container.read_only_writer()?
+    } else {
+        container.writer()?
+    };
+    let mut context = context.clone();
+    let view = Arc::new(container.within(&writer));
+    context.current_graph = Some(NamedGraphRef::new(graph.name().clone(), view));
+    Ok(Some((context, writer)))
+}
+
+/// Commits the transaction of `writer` if `result` is successful, or aborts it otherwise.
+fn end_transaction<T>(writer: Option<GraphWriter>, result: Result<T>) -> Result<T> {
+    let Some(writer) = writer else {
+        return result;
+    };
+    match result {
+        Ok(value) => {
+            writer.commit()?;
+            Ok(value)
+        }
+        Err(e) => {
+            writer.abort()?;
+            Err(e)
+        }
+    }
+}
+
+/// Returns the isolation level of the transaction started by `start`, i.e., the one it specifies
+/// if any, or `default`.
+///
+/// Read committed transactions run with snapshot isolation, the weakest level supported by the
+/// storage, which also prevents the anomalies read committed does.
+fn transaction_isolation(
+    start: &StartTransaction,
+    default: IsolationLevel,
+) -> Result<IsolationLevel> {
+    let mut isolation = default;
+    for mode in &start.0 {
+        match mode.value() {
+            TransactionMode::ReadWrite | TransactionMode::ReadOnly => {}
+            TransactionMode::IsolationLevel(level) => {
+                isolation = match level {
+                    ast::IsolationLevel::ReadCommitted | ast::IsolationLevel::Snapshot => {
+                        IsolationLevel::Snapshot
+                    }
+                    ast::IsolationLevel::Serializable => IsolationLevel::Serializable,
+                };
+            }
+        }
+    }
+    Ok(isolation)
+}
+
+/// Returns whether the transaction started by `start` is read-only, i.e., specifies `READ ONLY`.
+fn is_read_only(start: &StartTransaction) -> bool {
+    start
+        .0
+        .iter()
+        .any(|mode| matches!(mode.value(), TransactionMode::ReadOnly))
+}
+
+/// Returns the scanned variable and the limit of `plan` if it is paginated, i.e., if it limits
+/// filters and projections of a scan of the vertices of a single label set.
+fn paginated_scan(plan: &PlanNode) -> Option<(&str, usize)> {
+    let mut limit = None;
+    let mut node = plan;
+    loop {
+        match node {
+            PlanNode::PhysicalLimit(l) if limit.is_none() => limit = Some(l.limit),
+            PlanNode::PhysicalProject(_) | PlanNode::PhysicalVertexPropertyScan(_) => {}
+            // Filters above the limit would make pages look partial.
+            PlanNode::PhysicalFilter(_) if limit.is_some() => {}
+            PlanNode::PhysicalNodeScan(scan) if scan.labels.len() == 1 => {
+                return limit.map(|limit| (scan.var.as_str(), limit));
+            }
+            _ => return None,
+        }
+        node = &node.children()[0];
+    }
+}
+
+/// Returns whether `plan` modifies the graph, i.e., inserts elements, sets properties or deletes
+/// elements.
+fn modifies_graph(plan: &PlanNode) -> bool {
+    matches!(
+        plan,
+        PlanNode::PhysicalInsert(_)
+            | PlanNode::PhysicalSetProperties(_)
+            | PlanNode::PhysicalDelete(_)
+    ) || plan.children().iter().any(modifies_graph)
+}
+
+/// Returns the cursor after the page of `chunks` if it is full, i.e., if it has `limit` rows, and
+/// the vertices of `var` are among the columns of `schema`.
+fn page_cursor(
+    schema: &DataSchema,
+    chunks: &[DataChunk],
+    var: &str,
+    limit: usize,
+) -> Option<Cursor> {
+    if chunks.iter().map(DataChunk::cardinality).sum::<usize>() < limit {
+        return None;
+    }
+    let index = schema
+        .fields()
+        .iter()
+        .position(|f| f.name() == var && matches!(f.ty(), LogicalType::Vertex(_)))?;
+    // Vertices are scanned in ascending order of id, so the last one has the largest id.
+    chunks
+        .iter()
+        .filter_map(|chunk| {
+            let mut chunk = chunk.clone();
+            chunk.compact();
+            // Vertices are returned as structs whose first field is the id.
+            let vertices = chunk.columns()[index].as_struct();
+            arrow::compute::max(vertices.column(0).as_primitive::<UInt64Type>())
+        })
+        .max()
+        .map(Cursor::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::AsArray;
+    use arrow::datatypes::{Int32Type, UInt64Type};
+    use gql_parser::{quote_identifier, quote_string};
+    use minigu_catalog::label_set::LabelSet;
+    use minigu_catalog::memory::graph_type::{MemoryGraphTypeCatalog, MemoryVertexTypeCatalog};
+    use minigu_catalog::property::Property;
+    use minigu_common::data_type::{DataField, LogicalType};
+    use minigu_common::types::{LabelId, PropertyIndexKey};
+    use minigu_common::value::{PropertyValue, ScalarValue};
+    use minigu_context::graph::{GraphContainer, GraphStorage};
+    use minigu_execution::error::ExecutionError;
+    use minigu_planner::binder::error::BindError;
+    use minigu_planner::error::PlanError;
+    use minigu_storage::common::{PropertyRecord, Vertex};
+    use minigu_storage::tp::MemoryGraph;
+    use minigu_storage::wal::graph_wal::Durability;
+
+    use super::*;
+    use crate::database::tests::open_database;
+    use crate::database::{Database, DatabaseConfig};
+
+    fn assert_send<T: Send>() {}
+
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn test_session_is_send() {
+        assert_send::<Session>();
+        assert_send::<SessionContext>();
+        assert_send::<Database>();
+        assert_sync::<Database>();
+    }
+
+    #[test]
+    fn test_sessions_across_threads() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let sessions: Vec<_> = (0..4).map(|_| db.session().unwrap()).collect();
+        std::thread::scope(|s| {
+            for (i, mut session) in sessions.into_iter().enumerate() {
+                s.spawn(move || {
+                    let graph_name = format!("graph_{i}");
+                    session
+                        .query(&format!("CALL create_test_graph('{graph_name}')"))
+                        .unwrap();
+                    let result = session.query_on(&graph_name, "MATCH (n) RETURN n").unwrap();
+                    assert_eq!(
+                        result
+                            .iter()
+                            .map(|chunk| chunk.cardinality())
+                            .sum::<usize>(),
+                        0
+                    );
+                });
+            }
+        });
+        // Every thread should have registered its graph in the shared default schema.
+        let mut session = db.session().unwrap();
+        for i in 0..4 {
+            assert!(
+                session
+                    .query(&format!("CALL create_test_graph('graph_{i}')"))
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_current_graph() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        assert_eq!(session.current_graph(), None);
+        assert!(session.current_schema().is_some());
+        session.query("CALL create_test_graph('g')").unwrap();
+        session.query("SESSION SET GRAPH g").unwrap();
+        assert_eq!(session.current_graph(), Some("g"));
+
+        assert!(session.query("SESSION SET GRAPH missing").is_err());
+        assert_eq!(session.current_graph(), Some("g"));
+
+        session.query("SESSION RESET GRAPH").unwrap();
+        assert_eq!(session.current_graph(), None);
+    }
+
+    #[test]
+    fn test_no_current_graph() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        for query in ["MATCH (n) RETURN n", "INSERT (:Person {name: 'alice'})"] {
+            assert!(matches!(
+                session.query(query),
+                Err(Error::Plan(PlanError::Bind(
+                    BindError::CurrentGraphNotSpecified
+                )))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_delimited_graph_name() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        for name in ["my-graph", "my graph", "my`graph"] {
+            session
+                .query(&format!(
+                    "CALL create_test_graph_data({}, 3)",
+                    quote_string(name)
+                ))
+                .unwrap();
+            session.query("SESSION RESET GRAPH").unwrap();
+            session
+                .query(&format!("SESSION SET GRAPH {}", quote_identifier(name)))
+                .unwrap();
+            assert_eq!(session.current_graph(), Some(name));
+            let result = session.query("MATCH (n) RETURN n").unwrap();
+            assert_eq!(result.iter().map(|c| c.cardinality()).sum::<usize>(), 3);
+        }
+        session.query("SESSION SET GRAPH `my-graph`").unwrap();
+        assert_eq!(session.current_graph(), Some("my-graph"));
+    }
+
+    #[test]
+    fn test_query_on_keeps_current_graph() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('g1', 3)")
+            .unwrap();
+        session
+            .query("CALL create_test_graph_data('g2', 5)")
+            .unwrap();
+        session.query("SESSION SET GRAPH g1").unwrap();
+        assert_eq!(session.current_graph(), Some("g1"));
+        let num_vertices = |result: QueryResult| {
+            result
+                .iter()
+                .map(|chunk| chunk.cardinality())
+                .sum::<usize>()
+        };
+
+        let result = session.query_on("g2", "MATCH (n) RETURN n").unwrap();
+        assert_eq!(num_vertices(result), 5);
+        assert_eq!(session.current_graph(), Some("g1"));
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        assert_eq!(num_vertices(result), 3);
+
+        assert!(session.query_on("g3", "MATCH (n) RETURN n").is_err());
+        assert_eq!(session.current_graph(), Some("g1"));
+    }
+
+    /// Returns the ids of the vertices of a test graph of 100 vertices, scanned by a database with
+    /// the given parallelism.
+    fn scan_test_graph(parallelism: usize) -> Vec<u64> {
+        let config = DatabaseConfig {
+            parallelism,
+            ..Default::default()
+        };
+        let (_dir, db) = open_database(config);
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 100)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        result
+            .iter()
+            .flat_map(|chunk| {
+                // Vertices are returned as structs whose first field is the id.
+                chunk.columns()[0]
+                    .as_struct()
+                    .column(0)
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .sorted()
+            .collect()
+    }
+
+    #[cfg(feature = "morsel")]
+    #[test]
+    fn test_morsel_scan() {
+        let config = DatabaseConfig {
+            parallelism: 4,
+            ..Default::default()
+        };
+        let (_dir, db) = open_database(config);
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 100)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let result = session.query_with_morsels("MATCH (n) RETURN n").unwrap();
+        let ids = result
+            .iter()
+            .flat_map(|chunk| {
+                // Vertices are returned as structs whose first field is the id.
+                chunk.columns()[0]
+                    .as_struct()
+                    .column(0)
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .sorted()
+            .collect_vec();
+        assert_eq!(ids, scan_test_graph(1));
+    }
+
+    #[test]
+    fn test_no_durability() {
+        let config = DatabaseConfig {
+            durability: Durability::None,
+            ..Default::default()
+        };
+        let (_dir, db) = open_database(config);
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let graph = session.context.current_graph.as_ref().unwrap().object();
+        let container = graph.as_any().downcast_ref::<GraphContainer>().unwrap();
+        let GraphStorage::Memory(graph) = container.graph_storage();
+        assert_eq!(graph.durability(), Durability::None);
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        assert_eq!(
+            result
+                .iter()
+                .map(|chunk| chunk.cardinality())
+                .sum::<usize>(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_execution_metrics() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        assert!(result.metrics().chunks_produced() > 0);
+        assert!(result.metrics().bytes_scanned() > 0);
+    }
+
+    #[test]
+    fn test_paginate_with_cursor() {
+        let config = DatabaseConfig {
+            parallelism: 4,
+            ..Default::default()
+        };
+        let (_dir, db) = open_database(config);
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 100)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let query = "MATCH (n) RETURN n LIMIT 30";
+        let mut ids = vec![];
+        let mut pages = 0;
+        let mut result = session.query(query).unwrap();
+        loop {
+            pages += 1;
+            ids.extend(result.iter().flat_map(|chunk| {
+                chunk.columns()[0]
+                    .as_struct()
+                    .column(0)
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            }));
+            let Some(cursor) = result.cursor() else {
+                break;
+            };
+            assert_eq!(cursor.last_vertex_id(), *ids.last().unwrap());
+            result = session.query_after(query, cursor).unwrap();
+        }
+        assert_eq!(pages, 4);
+        // Every vertex is returned exactly once, in ascending order of id.
+        assert_eq!(ids, scan_test_graph(1));
+
+        let cursor = Cursor::new(0);
+        let err = session
+            .query_after("MATCH (n) RETURN n", cursor)
+            .unwrap_err();
+        assert!(matches!(err, Error::NotResumable));
+    }
+
+    #[test]
+    fn test_union_with_typed_null() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        let result = session
+            .query("RETURN 100000 AS x UNION ALL RETURN NULL AS y")
+            .unwrap();
+        let schema = result.schema().unwrap();
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.fields()[0].name(), "x");
+        assert_eq!(schema.fields()[0].ty(), &LogicalType::Int32);
+        assert!(schema.fields()[0].is_nullable());
+        let values = result
+            .iter()
+            .flat_map(|chunk| chunk.columns()[0].as_primitive::<Int32Type>().iter())
+            .collect_vec();
+        assert_eq!(values, [Some(100000), None]);
+    }
+
+    #[test]
+    fn test_multiple_result_sets() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        let query = "RETURN 1 AS a NEXT RETURN 2 AS b, 3 AS c";
+        let results = session.query_all(query).unwrap();
+        assert_eq!(results.len(), 2);
+        let names = |result: &QueryResult| {
+            result
+                .schema()
+                .unwrap()
+                .fields()
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect_vec()
+        };
+        assert_eq!(names(&results[0]), ["a"]);
+        assert_eq!(names(&results[1]), ["b", "c"]);
+        assert_eq!(results[0].num_rows(), 1);
+        assert_eq!(results[1].num_rows(), 1);
+
+        // `query` returns the result set of the last statement.
+        let result = session.query(query).unwrap();
+        assert_eq!(names(&result), ["b", "c"]);
+
+        // Passing the result of a statement to the next one is not supported.
+        assert!(
+            session
+                .query_all("RETURN 1 AS a NEXT YIELD a RETURN a")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parallel_scan() {
+        let parallel = scan_test_graph(4);
+        assert_eq!(parallel, (0..100).collect_vec());
+        assert_eq!(parallel, scan_test_graph(1));
+    }
+
+    #[test]
+    fn test_cancel_query() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        session.cancel_flag().store(true, Ordering::Relaxed);
+        let err = session.query("MATCH (n) RETURN n").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Execution(minigu_execution::error::ExecutionError::Cancelled)
+        ));
+        // The flag is cleared once the cancelled query is done.
+        let result = session.query("MATCH (n) RETURN n").unwrap();
+        assert_eq!(
+            result
+                .iter()
+                .map(|chunk| chunk.cardinality())
+                .sum::<usize>(),
+            10
+        );
+    }
+
+    /// Sets the current graph of `session` to a graph `test` with a single `Person` vertex 42,
+    /// named `alice`, returning the id of the `Person` label.
+    fn set_person_graph(session: &mut Session) -> LabelId {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let person = graph_type.add_label("Person".into()).unwrap();
+        let label_set = LabelSet::from_iter([person]);
+        let properties = vec![Property::new("name".into(), LogicalType::String, false)];
+        let vertex_type = MemoryVertexTypeCatalog::new(label_set.clone(), properties);
+        graph_type.add_vertex_type(label_set, Arc::new(vertex_type));
+        let database = session.context.database();
+        let graph = MemoryGraph::with_config_fresh(
+            database.checkpoint_config("test"),
+            database.wal_config("test"),
+        );
+        let container = GraphContainer::new(Arc::new(graph_type), GraphStorage::Memory(graph));
+        let writer = container.writer().unwrap();
+        let vertex = Vertex::new(42, person, PropertyRecord::new(vec!["alice".into()]));
+        writer.create_vertex(vertex).unwrap();
+        writer.commit().unwrap();
+        session
+            .current_schema()
+            .unwrap()
+            .add_graph("test".into(), Arc::new(container));
+        session.query("SESSION SET GRAPH test").unwrap();
+        person
+    }
+
+    /// Sets a graph of people, some of whom have no age, as the current graph of `session`. The
+    /// people are indexed by name by the property index `person_name`.
+    fn set_people_graph(session: &mut Session) {
+        let mut graph_type = MemoryGraphTypeCatalog::new();
+        let person = graph_type.add_label("Person".into()).unwrap();
+        let label_set = LabelSet::from_iter([person]);
+        let properties = vec![
+            Property::new("name".into(), LogicalType::String, false),
+            Property::new("age".into(), LogicalType::Int32, true),
+        ];
+        let vertex_type = MemoryVertexTypeCatalog::new(label_set.clone(), properties);
+        graph_type.add_vertex_type(label_set, Arc::new(vertex_type));
+        graph_type.add_property_index("person_name".into(), PropertyIndexKey::new(person, 0));
+        let database = session.context.database();
+        let graph = MemoryGraph::with_config_fresh(
+            database.checkpoint_config("test"),
+            database.wal_config("test"),
+        );
+        let container = GraphContainer::new(Arc::new(graph_type), GraphStorage::Memory(graph));
+        let writer = container.writer().unwrap();
+        let people = [("alice", Some(30)), ("bob", None), ("carol", Some(25))];
+        for (vid, (name, age)) in (1..).zip(people) {
+            let properties = PropertyRecord::new(vec![name.into(), ScalarValue::Int32(age)]);
+            writer
+                .create_vertex(Vertex::new(vid, person, properties))
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        session
+            .current_schema()
+            .unwrap()
+            .add_graph("test".into(), Arc::new(container));
+        session.query("SESSION SET GRAPH test").unwrap();
+    }
+
+    /// Returns the values of the first column of the result of `query`, in the order of their
+    /// string representations.
+    fn query_column(session: &mut Session, query: &str) -> Vec<ScalarValue> {
+        let result = session.query(query).unwrap();
+        result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .map(|row| row.get(0).unwrap().clone())
+            .sorted_by_cached_key(|value| format!("{value}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_by_property() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age IS NOT NULL RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("alice"), "carol".into()]);
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age IS NULL RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("bob")]);
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age > 26 RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("alice")]);
+        let ages = query_column(&mut session, "MATCH (n:Person) RETURN n.age");
+        assert_eq!(
+            ages,
+            [
+                ScalarValue::Int32(Some(25)),
+                ScalarValue::Int32(Some(30)),
+                ScalarValue::Int32(None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fused_filters_skip_filtered_rows() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        // The filters are fused, and carol must not reach the division by zero.
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) FILTER n.age <> 25 FILTER 10 / (n.age - 25) > 1 RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("alice")]);
+    }
+
+    #[test]
+    fn test_set_property() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        session
+            .query("MATCH (n:Person) WHERE n.name = 'bob' SET n.age = 40")
+            .unwrap();
+        let ages = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.name = 'bob' RETURN n.age",
+        );
+        assert_eq!(ages, [ScalarValue::Int32(Some(40))]);
+
+        // Several properties are set at once, and setting a property to null unsets it.
+        session
+            .query("MATCH (n:Person) WHERE n.age < 26 SET n.name = 'caroline', n.age = null")
+            .unwrap();
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age IS NULL RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("caroline")]);
+
+        // Within an explicit transaction, the statements see the modifications of the previous
+        // ones before they are committed.
+        let names = query_column(
+            &mut session,
+            "START TRANSACTION \
+             MATCH (n:Person) WHERE n.name = 'alice' SET n.age = 31 \
+             NEXT MATCH (n:Person) WHERE n.age = 31 RETURN n.name \
+             COMMIT",
+        );
+        assert_eq!(names, [ScalarValue::from("alice")]);
+        // The modifications of a failed transaction are discarded.
+        let err = session
+            .query(
+                "START TRANSACTION \
+                 MATCH (n:Person) SET n.age = 1 \
+                 NEXT RETURN 1 / 0 \
+                 COMMIT",
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Execution(ExecutionError::DivisionByZero)
+        ));
+        let ages = query_column(&mut session, "MATCH (n:Person) RETURN n.age");
+        assert_eq!(
+            ages,
+            [
+                ScalarValue::Int32(Some(31)),
+                ScalarValue::Int32(Some(40)),
+                ScalarValue::Int32(None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_coerces_property_values() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        let names = query_column(
+            &mut session,
+            "INSERT (n:Person {name: 'dave', age: '25'}) RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("dave")]);
+        session.query("INSERT (:Person {name: 'erin'})").unwrap();
+        let ages = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.name = 'dave' OR n.name = 'erin' RETURN n.age",
+        );
+        assert_eq!(
+            ages,
+            [ScalarValue::Int32(Some(25)), ScalarValue::Int32(None)]
+        );
+
+        // A string that is not a number of the property type is rejected, as is a missing value
+        // for a non-nullable property, and nothing is inserted.
+        let err = session
+            .query("INSERT (:Person {name: 'frank', age: 'old'})")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Plan(PlanError::Bind(BindError::PropertyTypeMismatch { property, .. }))
+                if property == "age"
+        ));
+        let err = session.query("INSERT (:Person {age: 40})").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Plan(PlanError::Bind(BindError::NullPropertyValue(property))) if property == "name"
+        ));
+        let names = query_column(&mut session, "MATCH (n:Person) RETURN n.name");
+        assert_eq!(names.len(), 5);
+
+        // Set items are coerced in the same way.
+        session
+            .query("MATCH (n:Person) WHERE n.name = 'bob' SET n.age = '40'")
+            .unwrap();
+        let ages = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.name = 'bob' RETURN n.age",
+        );
+        assert_eq!(ages, [ScalarValue::Int32(Some(40))]);
+        let err = session
+            .query("MATCH (n:Person) WHERE n.name = 'bob' SET n.age = 'old'")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Plan(PlanError::Bind(BindError::PropertyTypeMismatch { property, .. }))
+                if property == "age"
+        ));
+    }
+
+    #[test]
+    fn test_insert_then_match_in_transaction() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        // The inserted vertex is matched by the next statement, before the transaction commits.
+        let names = query_column(
+            &mut session,
+            "START TRANSACTION \
+             INSERT (:Person {name: 'dave', age: 40}) \
+             NEXT MATCH (n:Person) WHERE n.age = 40 RETURN n.name \
+             COMMIT",
+        );
+        assert_eq!(names, [ScalarValue::from("dave")]);
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age = 40 RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("dave")]);
+
+        // The inserted vertex is matched before a later statement fails, and is then discarded.
+        let err = session
+            .query(
+                "START TRANSACTION \
+                 INSERT (:Person {name: 'erin', age: 50}) \
+                 NEXT MATCH (n:Person) WHERE n.age = 50 RETURN 1 / 0 \
+                 COMMIT",
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Execution(ExecutionError::DivisionByZero)
+        ));
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age = 50 RETURN n.name",
+        );
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn test_scan_only_returned_properties() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        let query = "MATCH (n:Person) WHERE n.age > 26 RETURN n.name";
+        let plan = session.explain(query).unwrap();
+        // The age is only used by the filter, so the name alone is scanned for the output.
+        assert!(plan.contains("PhysicalVertexPropertyScan: n, properties: [n.name]\n"));
+        assert_eq!(
+            query_column(&mut session, query),
+            [ScalarValue::from("alice")]
+        );
+    }
+
+    #[test]
+    fn test_index_hint() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        let query = "MATCH (n:Person USING INDEX person_name {name: 'alice'}) RETURN n.age";
+        let plan = session.explain(query).unwrap();
+        assert!(plan.contains(
+            "PhysicalPropertyIndexScan: n, index: person_name = String(Some(\"alice\"))"
+        ));
+        assert!(!plan.contains("PhysicalNodeScan"));
+        assert_eq!(
+            query_column(&mut session, query),
+            [ScalarValue::Int32(Some(30))]
+        );
+
+        // The index is maintained by the modifications of the graph.
+        session
+            .query("MATCH (n:Person) WHERE n.name = 'alice' SET n.name = 'alicia'")
+            .unwrap();
+        session
+            .query("INSERT (:Person {name: 'alice', age: 40})")
+            .unwrap();
+        assert_eq!(
+            query_column(&mut session, query),
+            [ScalarValue::Int32(Some(40))]
+        );
+        let ages = query_column(
+            &mut session,
+            "MATCH (n:Person USING INDEX person_name {name: 'alicia'}) RETURN n.age",
+        );
+        assert_eq!(ages, [ScalarValue::Int32(Some(30))]);
+
+        let err = session
+            .query("MATCH (n:Person USING INDEX person_age {age: 30}) RETURN n")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Plan(PlanError::Bind(BindError::IndexNotFound(index))) if index == "person_age"
+        ));
+        // The indexed property must be given a literal value.
+        let err = session
+            .query("MATCH (n:Person USING INDEX person_name WHERE n.age = 30) RETURN n")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Plan(PlanError::Bind(BindError::InapplicableIndex { index, .. }))
+                if index == "person_name"
+        ));
+    }
+
+    #[test]
+    fn test_return_vertex() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        let person = set_person_graph(&mut session);
+        let result = session.query("MATCH (n:Person) RETURN n").unwrap();
+        let fields = vec![DataField::new("name".into(), LogicalType::String, false)];
+        assert_eq!(
+            result.schema().unwrap().fields()[0].ty(),
+            &LogicalType::Vertex(fields)
+        );
+        let rows = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .collect_vec();
+        assert_eq!(rows.len(), 1);
+        let vertex = rows[0].get(0).unwrap().get_vertex().unwrap();
+        assert_eq!(vertex.id(), 42);
+        assert_eq!(vertex.label(), person);
+        assert_eq!(
+            vertex.properties(),
+            [PropertyValue::new("name".into(), "alice".into())]
+        );
+    }
+
+    #[test]
+    fn test_element_functions() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_person_graph(&mut session);
+        let result = session
+            .query("MATCH (n:Person) RETURN id(n), labels(n), properties(n)")
+            .unwrap();
+        let rows = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .collect_vec();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(0).unwrap(), &ScalarValue::UInt64(Some(42)));
+        let labels = rows[0]
+            .get(1)
+            .unwrap()
+            .clone()
+            .into_list()
+            .unwrap()
+            .unwrap();
+        assert!(labels.contains(&"Person".into()));
+        let chunk = result.iter().next().unwrap();
+        let properties = chunk.columns()[2].as_struct();
+        assert_eq!(properties.fields()[0].name(), "name");
+        assert_eq!(properties.column(0).as_string::<i32>().value(0), "alice");
+
+        assert!(session.query("RETURN id(1)").is_err());
+        // Path functions only take paths.
+        assert!(session.query("MATCH (n:Person) RETURN nodes(n)").is_err());
+
+        // The ids of vertices are returned without looking them up.
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let result = session.query("MATCH (n) RETURN id(n)").unwrap();
+        let ids = result
+            .iter()
+            .flat_map(|chunk| {
+                chunk.columns()[0]
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .sorted()
+            .collect_vec();
+        assert_eq!(ids, (0..10).collect_vec());
+    }
+
+    #[test]
+    fn test_binary_expression() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        let value = |session: &mut Session, query: &str| {
+            let result = session.query(query)?;
+            let chunk = result.iter().next().expect("there should be a chunk");
+            let row = chunk
+                .rows()
+                .next()
+                .expect("there should be a row")
+                .into_owned();
+            Ok::<_, Error>(row.get(0).unwrap().clone())
+        };
+        let int8 = |value| ScalarValue::Int8(value);
+
+        assert_eq!(value(&mut session, "RETURN 7 % 3").unwrap(), int8(Some(1)));
+        assert_eq!(value(&mut session, "RETURN 6 & 3").unwrap(), int8(Some(2)));
+        assert_eq!(value(&mut session, "RETURN 6 | 3").unwrap(), int8(Some(7)));
+        assert_eq!(value(&mut session, "RETURN 6 ^ 3").unwrap(), int8(Some(5)));
+        assert_eq!(value(&mut session, "RETURN 1 << 3").unwrap(), int8(Some(8)));
+        assert_eq!(
+            value(&mut session, "RETURN 1 < 2").unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+        // The literal `1` is coerced to the type of `1000`.
+        assert_eq!(
+            value(&mut session, "RETURN 1000 + 1").unwrap(),
+            ScalarValue::Int16(Some(1001))
+        );
+        assert!(matches!(
+            session.query("RETURN 1 + 'a'"),
+            Err(Error::Plan(PlanError::Bind(
+                BindError::BinaryTypeMismatch { .. }
+            )))
+        ));
+
+        // Literals are 8-bit integers if they fit, so `127 + 1` overflows.
+        assert!(value(&mut session, "RETURN 127 + 1").is_err());
+        session.set_overflow_policy(OverflowPolicy::Wrap);
+        assert_eq!(
+            value(&mut session, "RETURN 127 + 1").unwrap(),
+            int8(Some(-128))
+        );
+        session.set_overflow_policy(OverflowPolicy::Saturate);
+        assert_eq!(
+            value(&mut session, "RETURN 127 + 1").unwrap(),
+            int8(Some(127))
+        );
+        session.set_overflow_policy(OverflowPolicy::Null);
+        assert_eq!(value(&mut session, "RETURN 127 + 1").unwrap(), int8(None));
+
+        assert!(matches!(
+            value(&mut session, "RETURN 1 / 0"),
+            Err(Error::Execution(ExecutionError::DivisionByZero))
+        ));
+        assert!(value(&mut session, "RETURN 1 % 0").is_err());
+        session.set_division_by_zero_policy(DivisionByZeroPolicy::Null);
+        assert_eq!(value(&mut session, "RETURN 1 / 0").unwrap(), int8(None));
+        assert_eq!(value(&mut session, "RETURN 1 % 0").unwrap(), int8(None));
+    }
+
+    #[test]
+    fn test_with_statement() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let rows = |session: &mut Session, query: &str| {
+            let result = session.query(query).unwrap();
+            result
+                .iter()
+                .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+                .collect_vec()
+        };
+
+        // Each vertex is its own group.
+        let query = "MATCH (n) WITH n, count(*) AS c WHERE c > 1 RETURN n";
+        assert!(rows(&mut session, query).is_empty());
+        let query = "MATCH (n) WITH n, count(*) AS c WHERE c = 1 RETURN n";
+        let ids = rows(&mut session, query)
+            .iter()
+            .map(|row| row.get(0).unwrap().get_vertex().unwrap().id())
+            .sorted()
+            .collect_vec();
+        assert_eq!(ids, (0..10).collect_vec());
+
+        let query =
+            "MATCH (n) WITH id(n) % 2 AS parity, count(*) AS c WHERE c > 1 RETURN parity, c";
+        let groups = rows(&mut session, query);
+        assert_eq!(groups.len(), 2);
+        assert!(
+            groups
+                .iter()
+                .all(|row| row.get(1) == Some(&ScalarValue::Int64(Some(5))))
+        );
+
+        // Aggregates must be whole items.
+        assert!(
+            session
+                .query("MATCH (n) WITH count(*) + 1 AS c RETURN c")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_aggregate_memory_limit() {
+        // Every group but the first one is spilled to disk.
+        let config = DatabaseConfig::builder()
+            .aggregate_memory_limit(1)
+            .build()
+            .unwrap();
+        let (_dir, db) = open_database(config);
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        let query = "MATCH (n:Person) WITH n.name AS name, count(*) AS c WHERE c = 1 RETURN name";
+        let expected = [ScalarValue::from("alice"), "bob".into(), "carol".into()];
+        assert_eq!(query_column(&mut session, query), expected);
+        session.set_aggregate_memory_limit(None);
+        assert_eq!(query_column(&mut session, query), expected);
+    }
+
+    #[test]
+    fn test_match_after_with() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        // Without common variables, the matched rows are joined with all the rows so far.
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WITH n.name AS a MATCH (m:Person) RETURN a",
+        );
+        assert_eq!(names.len(), 9);
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WITH n.age AS a MATCH (m:Person) WHERE m.age = a RETURN m.name",
+        );
+        assert_eq!(names, [ScalarValue::from("alice"), "carol".into()]);
+        // Common variables are bound to the same vertices on both sides.
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age > 26 WITH n MATCH (n:Person) RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("alice")]);
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WITH n, count(*) AS c MATCH (n:Person) WHERE n.age IS NULL \
+             RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("bob")]);
+    }
+
+    #[test]
+    fn test_read_timestamp() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_person_graph(&mut session);
+        let name = |session: &mut Session| {
+            let result = session.query("MATCH (n:Person) RETURN n.name").unwrap();
+            let chunk = result.iter().next().unwrap();
+            chunk.columns()[0].as_string::<i32>().value(0).to_string()
+        };
+
+        let before = session.latest_commit_timestamp().unwrap();
+        session
+            .query("MATCH (n:Person) SET n.name = 'bob' RETURN n")
+            .unwrap();
+        assert_eq!(name(&mut session), "bob");
+
+        // The query as of the timestamp before the update sees the previous value.
+        session.set_read_timestamp(Some(before));
+        assert_eq!(name(&mut session), "alice");
+        let err = session
+            .query("MATCH (n:Person) SET n.name = 'carol' RETURN n")
+            .unwrap_err();
+        assert!(matches!(err, Error::ReadOnlySnapshot));
+
+        session.set_read_timestamp(None);
+        assert_eq!(name(&mut session), "bob");
+    }
+
+    #[test]
+    fn test_transaction_isolation() {
+        use IsolationLevel::{Serializable, Snapshot};
+
+        let isolation = |query: &str, default| {
+            let program = parse_gql(query).unwrap();
+            let activity = program.value().activity.as_ref().unwrap();
+            let ProgramActivity::Transaction(activity) = activity.value() else {
+                panic!("expected a transaction activity");
+            };
+            transaction_isolation(activity.start.as_ref().unwrap().value(), default).unwrap()
+        };
+        // The default isolation level applies unless the transaction specifies one.
+        assert_eq!(isolation("START TRANSACTION", Snapshot), Snapshot);
+        assert_eq!(
+            isolation("START TRANSACTION READ WRITE", Serializable),
+            Serializable
+        );
+        assert_eq!(
+            isolation("START TRANSACTION ISOLATION LEVEL SERIALIZABLE", Snapshot),
+            Serializable
+        );
+        assert_eq!(
+            isolation(
+                "START TRANSACTION ISOLATION LEVEL READ COMMITTED",
+                Serializable
+            ),
+            Snapshot
+        );
+        assert_eq!(isolation("START TRANSACTION READ ONLY", Snapshot), Snapshot);
+    }
+
+    #[test]
+    fn test_read_only_transaction() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_people_graph(&mut session);
+        let names = query_column(
+            &mut session,
+            "START TRANSACTION READ ONLY \
+             MATCH (n:Person) WHERE n.age > 26 RETURN n.name \
+             NEXT MATCH (n:Person) WHERE n.age < 26 RETURN n.name \
+             COMMIT",
+        );
+        assert_eq!(names, [ScalarValue::from("carol")]);
+
+        let err = session
+            .query(
+                "START TRANSACTION READ ONLY \
+                 INSERT (:Person {name: 'dave', age: 40}) \
+                 COMMIT",
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::ReadOnlyTransaction));
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age = 40 RETURN n.name",
+        );
+        assert!(names.is_empty());
+
+        // The following transactions may modify the graph again.
+        session
+            .query("START TRANSACTION INSERT (:Person {name: 'dave', age: 40}) COMMIT")
+            .unwrap();
+        let names = query_column(
+            &mut session,
+            "MATCH (n:Person) WHERE n.age = 40 RETURN n.name",
+        );
+        assert_eq!(names, [ScalarValue::from("dave")]);
+    }
+
+    #[test]
+    fn test_default_isolation() {
+        let config = DatabaseConfig {
+            default_isolation: IsolationLevel::Snapshot,
+            ..Default::default()
+        };
+        let (_dir, db) = open_database(config);
+        let mut session = db.session().unwrap();
+        set_person_graph(&mut session);
+        assert_eq!(session.context.isolation_level(), IsolationLevel::Snapshot);
+        session.set_default_isolation(IsolationLevel::Serializable);
+        assert_eq!(
+            session.context.isolation_level(),
+            IsolationLevel::Serializable
+        );
+
+        let result = session
+            .query(
+                "START TRANSACTION ISOLATION LEVEL READ COMMITTED MATCH (n:Person) RETURN n COMMIT",
+            )
+            .unwrap();
+        assert_eq!(result.iter().map(DataChunk::cardinality).sum::<usize>(), 1);
+        // The isolation level of the transaction does not outlive it.
+        assert_eq!(
+            session.context.isolation_level(),
+            IsolationLevel::Serializable
+        );
+
+        assert!(session.query("START TRANSACTION").is_err());
+        assert!(
+            session
+                .query("START TRANSACTION MATCH (n:Person) RETURN n ROLLBACK")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_record_field_access() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        set_person_graph(&mut session);
+        let result = session
+            .query("MATCH (n:Person) RETURN properties(n).name, properties(n)['name']")
+            .unwrap();
+        let rows = result
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned()))
+            .collect_vec();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get(0).unwrap(), &"alice".into());
+        assert_eq!(rows[0].get(1).unwrap(), &"alice".into());
+
+        assert!(
+            session
+                .query("MATCH (n:Person) RETURN properties(n).age")
+                .is_err()
+        );
+        assert!(
+            session
+                .query("MATCH (n:Person) RETURN n.name.first")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_empty_query() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        for query in ["", "  \n\t ", "-- nothing to see here\n/* nor here */"] {
+            let result = session.query(query).unwrap();
+            assert!(result.schema().is_none());
+            assert_eq!(result.iter().count(), 0);
+        }
+        // The session is still usable afterwards.
+        assert!(session.query("RETURN 1").is_ok());
+    }
+
+    #[test]
+    fn test_execute_program() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 5)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let program = parse_gql("MATCH (n) RETURN id(n)").unwrap();
+        for _ in 0..2 {
+            let result = session.execute_program(program.value()).unwrap();
+            let ids = result
+                .iter()
+                .flat_map(|chunk| {
+                    chunk.columns()[0]
+                        .as_primitive::<UInt64Type>()
+                        .values()
+                        .to_vec()
+                })
+                .sorted()
+                .collect_vec();
+            assert_eq!(ids, [0, 1, 2, 3, 4]);
+        }
+
+        let program = parse_gql("SESSION CLOSE").unwrap();
+        session.execute_program(program.value()).unwrap();
+        assert!(matches!(
+            session.execute_program(program.value()),
+            Err(Error::SessionClosed)
+        ));
+    }
+
+    #[test]
+    fn test_explain() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let plan = session.explain("MATCH (n) RETURN n").unwrap();
+        // Every vertex is scanned, whatever its label.
+        assert!(plan.ends_with("PhysicalNodeScan: n, labels: [[]]\n"));
+        assert!(session.explain("SESSION SET GRAPH test").is_err());
+    }
+
+    #[test]
+    fn test_view() {
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        session
+            .query("CALL create_test_graph_data('test', 10)")
+            .unwrap();
+        session.query("SESSION SET GRAPH test").unwrap();
+        let ids = |session: &mut Session, query: &str| {
+            let result = session.query(query).unwrap();
+            result
+                .iter()
+                .flat_map(|chunk| {
+                    chunk.columns()[0]
+                        .as_primitive::<UInt64Type>()
+                        .values()
+                        .to_vec()
+                })
+                .sorted()
+                .collect_vec()
+        };
+        session
+            .query("CREATE VIEW ids AS MATCH (n) RETURN id(n) AS id")
+            .unwrap();
+        let inlined = ids(&mut session, "MATCH (n) RETURN id(n) AS id");
+        assert_eq!(inlined, (0..10).collect_vec());
+        assert_eq!(ids(&mut session, "CALL ids() YIELD id RETURN id"), inlined);
+        assert_eq!(
+            ids(&mut session, "CALL ids() YIELD id AS x RETURN x"),
+            inlined
+        );
+
+        assert!(session.query("CALL ids(1) YIELD id RETURN id").is_err());
+        // Views only replace views.
+        assert!(
+            session
+                .query("CREATE VIEW ids AS MATCH (n) RETURN n")
+                .is_err()
+        );
+        assert!(
+            session
+                .query("CREATE OR REPLACE VIEW benchmark AS MATCH (n) RETURN n")
+                .is_err()
+        );
+
+        let recursion = |session: &mut Session, query: &str| match session.query(query).unwrap_err()
+        {
+            Error::Plan(PlanError::Bind(BindError::RecursiveView(cycle))) => cycle,
+            err => panic!("expected a recursive view, got {err:?}"),
+        };
+        assert_eq!(
+            recursion(
+                &mut session,
+                "CREATE VIEW looping AS CALL looping() YIELD id RETURN id"
+            ),
+            ["looping", "looping"]
+        );
+        session
+            .query("CREATE VIEW ids_of_ids AS CALL ids() YIELD id RETURN id")
+            .unwrap();
+        assert_eq!(
+            recursion(
+                &mut session,
+                "CREATE OR REPLACE VIEW ids AS CALL ids_of_ids() YIELD id RETURN id"
+            ),
+            ["ids", "ids_of_ids", "ids"]
+        );
+
+        // Replacing a view changes the views calling it.
+        session
+            .query("CREATE OR REPLACE VIEW ids AS MATCH (n) RETURN id(n) AS id LIMIT 3")
+            .unwrap();
+        assert_eq!(
+            ids(&mut session, "CALL ids_of_ids() YIELD id RETURN id").len(),
+            3
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_query_spans() {
+        use std::sync::Mutex;
+        use std::sync::atomic::AtomicU64;
+
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records the name of every span, along with its `node` field if any.
+        #[derive(Default)]
+        struct Recorder {
+            spans: Arc<Mutex<Vec<String>>>,
+            next_id: AtomicU64,
+        }
+
+        struct SpanName(String);
+
+        impl Visit for SpanName {
+            fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "node" {
+                    self.0 = format!("{}:{value}", self.0);
+                }
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut name = SpanName(span.metadata().name().to_string());
+                span.record(&mut name);
+                self.spans.lock().unwrap().push(name.0);
+                Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, _: &Event<'_>) {}
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let (_dir, db) = open_database(DatabaseConfig::default());
+        let mut session = db.session().unwrap();
+        let recorder = Recorder::default();
+        let spans = recorder.spans.clone();
+        tracing::subscriber::with_default(recorder, || session.query("RETURN 1 AS x").unwrap());
+        let spans = spans.lock().unwrap();
+        assert_eq!(
+            spans[..5],
+            ["query", "parse", "plan", "optimize", "execute"]
+        );
+        assert!(spans[5..].contains(&"next_chunk:PhysicalProject".to_string()));
+        assert!(spans[5..].contains(&"next_chunk:PhysicalOneRow".to_string()));
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use arrow::array::AsArray;
+use arrow::datatypes::Int64Type;
+use divan::Bencher;
+use divan::counter::ItemsCount;
+use minigu_common::types::VertexIdArray;
+use minigu_execution::evaluator::column_ref::ColumnRef;
+use minigu_execution::evaluator::constant::Constant;
+use minigu_execution::evaluator::{BoxedEvaluator, Evaluator};
+use minigu_execution::executor::aggregate::AggregateSpec;
+use minigu_execution::executor::morsel::{MorselPipelineBuilder, MorselStage};
+use minigu_execution::executor::{Executor, IntoExecutor};
+use minigu_execution::source::VertexSource;
+use rayon::ThreadPoolBuilder;
+
+fn main() {
+    divan::main();
+}
+
+const NUM_VERTICES: u64 = 1 << 22;
+
+const CHUNK_SIZE: usize = 2048;
+
+const NUM_THREADS: &[usize] = &[1, 2, 4, 8];
+
+fn vertices() -> Arc<VertexIdArray> {
+    Arc::new(VertexIdArray::from_iter_values(0..NUM_VERTICES))
+}
+
+fn predicate() -> BoxedEvaluator {
+    let predicate = ColumnRef::new(0)
+        .rem(Constant::new(3u64.into()))
+        .eq(Constant::new(0u64.into()));
+    Box::new(predicate)
+}
+
+fn projection() -> BoxedEvaluator {
+    Box::new(ColumnRef::new(0).mul(Constant::new(2u64.into())))
+}
+
+/// Counts the rows of the output of `executor`.
+fn count(executor: impl Executor) -> i64 {
+    let chunk = executor
+        .aggregate(vec![AggregateSpec::count()], vec![], vec![])
+        .next_chunk()
+        .unwrap()
+        .unwrap();
+    chunk.columns()[0].as_primitive::<Int64Type>().value(0)
+}
+
+#[divan::bench]
+fn serial_scan(bencher: Bencher) {
+    let vertices = vertices();
+    bencher
+        .counter(ItemsCount::new(NUM_VERTICES))
+        .bench_local(|| {
+            let source = (0..vertices.len()).step_by(CHUNK_SIZE).map(|offset| {
+                let length = CHUNK_SIZE.min(vertices.len() - offset);
+                Ok(Arc::new(vertices.slice(offset, length)))
+            });
+            let predicate = predicate();
+            let executor = source
+                .scan_vertex()
+                .filter(move |c| {
+                    predicate
+                        .evaluate(c)
+                        .map(|a| a.into_array().as_boolean().clone())
+                })
+                .project(vec![projection()]);
+            count(executor)
+        });
+}
+
+#[divan::bench(args = NUM_THREADS)]
+fn morsel_scan(bencher: Bencher, num_threads: usize) {
+    let vertices = vertices();
+    let pool = Arc::new(
+        ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap(),
+    );
+    let stages = || {
+        vec![
+            MorselStage::Filter(predicate()),
+            MorselStage::Project(vec![projection()]),
+        ]
+    };
+    bencher
+        .counter(ItemsCount::new(NUM_VERTICES))
+        .bench_local(|| {
+            let pipeline =
+                MorselPipelineBuilder::new(vertices.clone(), stages, pool.clone(), CHUNK_SIZE);
+            count(pipeline.into_executor())
+        });
+}
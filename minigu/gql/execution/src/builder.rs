@@ -1,41 +1,146 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
-use arrow::array::{AsArray, Int32Array};
-use minigu_catalog::provider::{GraphProvider, SchemaProvider};
+use arrow::array::{ArrayRef, AsArray, Int32Array};
+use arrow::datatypes::UInt64Type;
+use minigu_catalog::provider::{GraphProvider, GraphRef};
 use minigu_common::data_chunk::DataChunk;
 use minigu_common::data_type::{DataSchema, LogicalType};
-use minigu_common::types::VertexIdArray;
+use minigu_common::types::{LabelId, VertexId, VertexIdArray};
+use minigu_common::value::{PropertyValue, ScalarValue, VertexValue};
 use minigu_context::graph::GraphContainer;
 use minigu_context::session::SessionContext;
-use minigu_planner::bound::{BoundExpr, BoundExprKind};
+use minigu_planner::bound::{
+    BoundAggregate, BoundAggregateFunction, BoundBinaryOp, BoundElementFunction, BoundExpr,
+    BoundExprKind, BoundPathFunction, BoundUnaryOp,
+};
+use minigu_planner::plan::scan::{PhysicalNodeScan, PhysicalVertexPropertyScan};
 use minigu_planner::plan::{PlanData, PlanNode};
+use minigu_storage::common::Vertex;
+use rayon::ThreadPool;
 
+use crate::error::{ExecutionError, ExecutionResult};
 use crate::evaluator::BoxedEvaluator;
+use crate::evaluator::binary::{Binary, BinaryOp};
+use crate::evaluator::cast::Cast;
 use crate::evaluator::column_ref::ColumnRef;
 use crate::evaluator::constant::Constant;
+use crate::evaluator::datum::DatumRef;
+use crate::evaluator::element_function::{ElementFunction, ElementFunctionEvaluator};
+use crate::evaluator::field_access::FieldAccess;
+use crate::evaluator::path_length::PathLength;
+use crate::evaluator::scalar_function::ScalarFunction;
+use crate::evaluator::unary::{Unary, UnaryOp};
 use crate::evaluator::vector_distance::VectorDistanceEvaluator;
+use crate::executor::aggregate::{AggregateBuilder, AggregateSpec};
+use crate::executor::insert::InsertVertexSpec;
+use crate::executor::join::JoinCond;
+use crate::executor::merge::MergeBuilder;
+use crate::executor::metered::ExecutionMetrics;
+#[cfg(feature = "morsel")]
+use crate::executor::morsel::{MorselPipelineBuilder, MorselStage};
 use crate::executor::procedure_call::ProcedureCallBuilder;
+use crate::executor::project::ProjectBuilder;
+use crate::executor::set_properties::SetPropertiesSpec;
 use crate::executor::sort::SortSpec;
+use crate::executor::utils::gen_try;
 use crate::executor::vector_index_scan::VectorIndexScanBuilder;
 use crate::executor::{BoxedExecutor, Executor, IntoExecutor};
-use crate::source::VertexSource;
+use crate::source::{VertexPropertySource, VertexSource};
 
 const DEFAULT_CHUNK_SIZE: usize = 2048;
 
+/// The number of chunks a parallel scan buffers ahead of its consumer.
+const PARALLEL_SCAN_BUFFER_SIZE: usize = 16;
+
 pub struct ExecutorBuilder {
     session: SessionContext,
+    cancelled: Option<Arc<AtomicBool>>,
+    metrics: Option<Arc<ExecutionMetrics>>,
+    sorted_scan: Option<RangeInclusive<VertexId>>,
+    #[cfg(feature = "morsel")]
+    morsels: bool,
 }
 
 impl ExecutorBuilder {
     pub fn new(session: SessionContext) -> Self {
-        Self { session }
+        Self {
+            session,
+            cancelled: None,
+            metrics: None,
+            sorted_scan: None,
+            #[cfg(feature = "morsel")]
+            morsels: false,
+        }
+    }
+
+    /// Makes the built executor stop with [`ExecutionError::Cancelled`] once `cancelled` is set.
+    ///
+    /// [`ExecutionError::Cancelled`]: crate::error::ExecutionError::Cancelled
+    pub fn with_cancellation(mut self, cancelled: Arc<AtomicBool>) -> Self {
+        self.cancelled = Some(cancelled);
+        self
+    }
+
+    /// Counts the chunks produced by every executor, and the bytes scanned, into `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<ExecutionMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Scans the vertices of each label set of node scans in ascending order of id, on a single
+    /// thread, and only those whose ids are in `range`, e.g., to resume a paginated query after the
+    /// last vertex of the previous page.
+    pub fn with_sorted_scan(mut self, range: RangeInclusive<VertexId>) -> Self {
+        self.sorted_scan = Some(range);
+        self
+    }
+
+    /// Runs node scans, along with the filters and projections right above them, as morsel-driven
+    /// pipelines on the scan thread pool of the database.
+    ///
+    /// This has no effect if the database has no scan thread pool.
+    #[cfg(feature = "morsel")]
+    pub fn with_morsels(mut self, morsels: bool) -> Self {
+        self.morsels = morsels;
+        self
     }
 
     pub fn build(self, physical_plan: &PlanNode) -> BoxedExecutor {
-        self.build_executor(physical_plan)
+        let executor = self.build_executor(physical_plan);
+        match self.cancelled {
+            Some(cancelled) => Box::new(executor.cancellable(cancelled)),
+            None => executor,
+        }
     }
 
     fn build_executor(&self, physical_plan: &PlanNode) -> BoxedExecutor {
+        let executor = self.build_node_executor(physical_plan);
+        #[cfg(feature = "tracing")]
+        let executor: BoxedExecutor = Box::new(executor.traced(physical_plan.into()));
+        match &self.metrics {
+            Some(metrics) => {
+                let scan = matches!(
+                    physical_plan,
+                    PlanNode::PhysicalNodeScan(_)
+                        | PlanNode::PhysicalPropertyIndexScan(_)
+                        | PlanNode::PhysicalVectorIndexScan(_)
+                );
+                Box::new(executor.metered(metrics.clone(), scan))
+            }
+            None => executor,
+        }
+    }
+
+    fn build_node_executor(&self, physical_plan: &PlanNode) -> BoxedExecutor {
+        #[cfg(feature = "morsel")]
+        if self.morsels && self.sorted_scan.is_none() {
+            if let Some(pipeline) = self.build_morsel_pipeline(physical_plan) {
+                return pipeline;
+            }
+        }
         let children = physical_plan.children();
         match physical_plan {
             PlanNode::PhysicalFilter(filter) => {
@@ -48,38 +153,75 @@ fn build_executor(&self, physical_plan: &PlanNode) -> BoxedExecutor {
                         .map(|a| a.into_array().as_boolean().clone())
                 }))
             }
-            PlanNode::PhysicalNodeScan(_node_scan) => {
-                // NodeScan provide graph id and label, Handle in next pr.
+            PlanNode::PhysicalNodeScan(node_scan) => {
+                // TODO: Scan the graph identified by the graph id of the node scan.
                 assert_eq!(children.len(), 0);
-                let cur_schema = self
-                    .session
-                    .home_schema
-                    .as_ref()
-                    .expect("there should be a home schema");
-                let cur_graph = cur_schema
-                    .get_graph("test".to_string().as_str())
-                    .expect("there should be a test graph")
-                    .unwrap();
-                let provider: &dyn GraphProvider = cur_graph.as_ref();
-                let container = provider
-                    .as_any()
-                    .downcast_ref::<GraphContainer>()
-                    .expect("current graph must be GraphContainer");
-                let batches = container
-                    .vertex_source(&[], 1024)
-                    .expect("failed to create vertex source");
-                let source = batches.map(|arr: Arc<VertexIdArray>| Ok(arr));
-                Box::new(source.scan_vertex())
+                if let Some(pool) = self.session.database().scan_runtime() {
+                    if self.sorted_scan.is_none() {
+                        return self.build_parallel_node_scan(node_scan, pool.clone());
+                    }
+                }
+                // Each label set is a conjunction, which only matches vertices bearing all of its
+                // labels. A disjunction of label sets is matched in a single scan of the graph.
+                let source = self.with_current_graph(|container| {
+                    match &self.sorted_scan {
+                        Some(range) => container.sorted_vertex_source_in_range(
+                            &node_scan.labels,
+                            range.clone(),
+                            1024,
+                        ),
+                        None => container.vertex_source(&node_scan.labels, 1024),
+                    }
+                    .expect("failed to create vertex source")
+                });
+                Box::new(source.map(|arr: Arc<VertexIdArray>| Ok(arr)).scan_vertex())
+            }
+            PlanNode::PhysicalPropertyIndexScan(index_scan) => {
+                assert_eq!(children.len(), 0);
+                let lookup = &index_scan.lookup;
+                let source = self.with_current_graph(|container| {
+                    container
+                        .vertex_source_by_property(lookup.key, &lookup.value, 1024)
+                        .expect("failed to create vertex source")
+                });
+                Box::new(source.map(|arr: Arc<VertexIdArray>| Ok(arr)).scan_vertex())
+            }
+            PlanNode::PhysicalVertexPropertyScan(property_scan) => {
+                assert_eq!(children.len(), 1);
+                let schema = children[0].schema().expect("child should have a schema");
+                let index = schema
+                    .get_field_index_by_name(&property_scan.var)
+                    .expect("variable should be present in the schema");
+                let (properties, source) = self.vertex_property_source(property_scan);
+                Box::new(
+                    self.build_executor(&children[0])
+                        .scan_vertex_property(index, properties, source),
+                )
             }
             PlanNode::PhysicalProject(project) => {
                 assert_eq!(children.len(), 1);
                 let schema = children[0].schema().expect("child should have a schema");
+                // Subexpressions shared by several outputs are evaluated once, into columns
+                // appended after those of the child.
+                let common = common_subexpressions(&project.exprs);
+                let common_evaluators = common
+                    .iter()
+                    .map(|e| self.build_evaluator(e, schema))
+                    .collect();
+                let reused = common
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| (*e, schema.size() + i))
+                    .collect();
                 let evaluators = project
                     .exprs
                     .iter()
-                    .map(|e| self.build_evaluator(e, schema))
+                    .map(|e| self.build_materialized_evaluator(e, schema, &reused))
                     .collect();
-                Box::new(self.build_executor(&children[0]).project(evaluators))
+                let child = self.build_executor(&children[0]);
+                let project = ProjectBuilder::new(child, evaluators)
+                    .with_common_evaluators(common_evaluators);
+                Box::new(project.into_executor())
             }
             PlanNode::PhysicalCall(call) => {
                 assert!(children.is_empty());
@@ -101,6 +243,10 @@ fn build_executor(&self, physical_plan: &PlanNode) -> BoxedExecutor {
                 let chunk = DataChunk::new(columns);
                 Box::new([Ok(chunk)].into_executor())
             }
+            PlanNode::PhysicalEmpty(_) => {
+                assert!(children.is_empty());
+                Box::new(std::iter::empty::<ExecutionResult<DataChunk>>().into_executor())
+            }
             PlanNode::PhysicalSort(sort) => {
                 assert_eq!(children.len(), 1);
                 let schema = children[0].schema().expect("child should have a schema");
@@ -117,9 +263,162 @@ fn build_executor(&self, physical_plan: &PlanNode) -> BoxedExecutor {
                         .sort(specs, DEFAULT_CHUNK_SIZE),
                 )
             }
+            PlanNode::PhysicalDistinct(_) => {
+                assert_eq!(children.len(), 1);
+                Box::new(self.build_executor(&children[0]).distinct())
+            }
+            PlanNode::PhysicalAggregate(aggregate)
+            | PlanNode::PhysicalSortedAggregate(aggregate) => {
+                assert_eq!(children.len(), 1);
+                let schema = children[0].schema().expect("child should have a schema");
+                let group_by = aggregate
+                    .group_by
+                    .iter()
+                    .map(|key| self.build_evaluator(key, schema))
+                    .collect();
+                let specs = aggregate
+                    .aggregates
+                    .iter()
+                    .map(|aggregate| self.build_aggregate_spec(aggregate, schema))
+                    .collect();
+                let child = self.build_executor(&children[0]);
+                if matches!(physical_plan, PlanNode::PhysicalSortedAggregate(_)) {
+                    Box::new(child.sorted_aggregate(specs, group_by, vec![], DEFAULT_CHUNK_SIZE))
+                } else {
+                    let aggregate = AggregateBuilder::new(child, specs, group_by, vec![]);
+                    match self.session.aggregate_memory_limit {
+                        Some(memory_limit) => {
+                            Box::new(aggregate.with_memory_limit(memory_limit).into_executor())
+                        }
+                        None => Box::new(aggregate.into_executor()),
+                    }
+                }
+            }
+            PlanNode::PhysicalUnion(_) => {
+                let children: Vec<_> = children.iter().map(|c| self.build_executor(c)).collect();
+                Box::new(
+                    children
+                        .into_iter()
+                        .flat_map(|child| child.into_iter())
+                        .into_executor(),
+                )
+            }
+            PlanNode::PhysicalJoin(join) => {
+                assert_eq!(children.len(), 2);
+                let left_schema = children[0]
+                    .schema()
+                    .expect("left child should have a schema");
+                let right_schema = children[1]
+                    .schema()
+                    .expect("right child should have a schema");
+                let conds = join
+                    .keys
+                    .iter()
+                    .map(|key| {
+                        JoinCond::new(
+                            join_key_evaluator(key, left_schema),
+                            join_key_evaluator(key, right_schema),
+                        )
+                    })
+                    .collect();
+                // The keys are output once, from the left child.
+                let outputs = (0..left_schema.size())
+                    .chain(
+                        right_schema
+                            .fields()
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, f)| !join.keys.iter().any(|key| key == f.name()))
+                            .map(|(i, _)| left_schema.size() + i),
+                    )
+                    .map(|i| Box::new(ColumnRef::new(i)) as BoxedEvaluator)
+                    .collect();
+                let left = self.build_executor(&children[0]);
+                let right = self.build_executor(&children[1]);
+                Box::new(left.join(right, conds).project(outputs))
+            }
             PlanNode::PhysicalLimit(limit) => {
                 assert_eq!(children.len(), 1);
-                Box::new(self.build_executor(&children[0]).limit(limit.limit))
+                let child = self.build_executor(&children[0]);
+                if limit.approximate {
+                    Box::new(child.approximate_limit(limit.limit))
+                } else {
+                    Box::new(child.limit(limit.limit))
+                }
+            }
+            PlanNode::PhysicalSetProperties(set) => {
+                assert_eq!(children.len(), 1);
+                let schema = children[0].schema().expect("child should have a schema");
+                // Items are grouped by their variable, preserving the order of first appearance.
+                let mut groups: Vec<(&str, Vec<usize>, Vec<BoxedEvaluator>)> = Vec::new();
+                for item in &set.items {
+                    let value = self.build_evaluator(&item.value, schema);
+                    let property = item.property_id as usize;
+                    match groups.iter_mut().find(|(var, ..)| *var == item.var) {
+                        Some((_, properties, values)) => {
+                            properties.push(property);
+                            values.push(value);
+                        }
+                        None => groups.push((&item.var, vec![property], vec![value])),
+                    }
+                }
+                let specs = groups
+                    .into_iter()
+                    .map(|(var, properties, values)| {
+                        let index = schema
+                            .get_field_index_by_name(var)
+                            .expect("variable should be present in the schema");
+                        SetPropertiesSpec::new(index, properties, values)
+                    })
+                    .collect();
+                // TODO: Modify the graph bound to the variables instead of the current graph.
+                let writer = self.with_current_graph(|container| {
+                    container.writer().expect("failed to begin a transaction")
+                });
+                Box::new(
+                    self.build_executor(&children[0])
+                        .set_properties(specs, writer),
+                )
+            }
+            PlanNode::PhysicalInsert(insert) => {
+                assert_eq!(children.len(), 1);
+                let schema = children[0].schema().expect("child should have a schema");
+                let specs = insert
+                    .vertices
+                    .iter()
+                    .map(|vertex| {
+                        let properties = vertex
+                            .properties
+                            .iter()
+                            .map(|value| self.build_evaluator(value, schema))
+                            .collect();
+                        InsertVertexSpec::new(vertex.label_id, properties)
+                    })
+                    .collect();
+                let writer = self.with_current_graph(|container| {
+                    container.writer().expect("failed to begin a transaction")
+                });
+                Box::new(self.build_executor(&children[0]).insert(specs, writer))
+            }
+            PlanNode::PhysicalDelete(delete) => {
+                assert_eq!(children.len(), 1);
+                let schema = children[0].schema().expect("child should have a schema");
+                let indices = delete
+                    .vars
+                    .iter()
+                    .map(|var| {
+                        schema
+                            .get_field_index_by_name(var)
+                            .expect("variable should be present in the schema")
+                    })
+                    .collect();
+                let writer = self.with_current_graph(|container| {
+                    container.writer().expect("failed to begin a transaction")
+                });
+                Box::new(
+                    self.build_executor(&children[0])
+                        .delete(indices, delete.detach, writer),
+                )
             }
             PlanNode::PhysicalVectorIndexScan(vector_scan) => {
                 assert!(children.is_empty());
@@ -130,8 +429,236 @@ fn build_executor(&self, physical_plan: &PlanNode) -> BoxedExecutor {
         }
     }
 
-    #[allow(clippy::only_used_in_recursion)]
+    /// Scans the vertices of `node_scan` in disjoint ranges of ids, one per thread of `pool`.
+    fn build_parallel_node_scan(
+        &self,
+        node_scan: &PhysicalNodeScan,
+        pool: Arc<ThreadPool>,
+    ) -> BoxedExecutor {
+        let graph = self.current_graph();
+        let ranges = self
+            .with_current_graph(|container| container.vertex_id_ranges(pool.current_num_threads()));
+        let partitions = ranges
+            .into_iter()
+            .map(|range| scan_vertex_range(graph.clone(), node_scan.labels.clone(), range))
+            .collect();
+        Box::new(MergeBuilder::new(partitions, pool, PARALLEL_SCAN_BUFFER_SIZE).into_executor())
+    }
+
+    /// Builds a morsel-driven pipeline for `plan`, if it only consists of filters, projections and
+    /// property scans over a node scan.
+    #[cfg(feature = "morsel")]
+    fn build_morsel_pipeline(&self, plan: &PlanNode) -> Option<BoxedExecutor> {
+        let pool = self.session.database().scan_runtime()?.clone();
+        let mut stages = vec![];
+        let mut plan = plan;
+        let node_scan = loop {
+            match plan {
+                PlanNode::PhysicalFilter(_)
+                | PlanNode::PhysicalProject(_)
+                | PlanNode::PhysicalVertexPropertyScan(_) => {
+                    stages.push(plan.clone());
+                    plan = &plan.children()[0];
+                }
+                PlanNode::PhysicalNodeScan(node_scan) => break node_scan,
+                _ => return None,
+            }
+        };
+        // The stages have been collected from the top of the pipeline down.
+        stages.reverse();
+        let vertices = self.with_current_graph(|container| {
+            let ids = container
+                .vertex_source(&node_scan.labels, DEFAULT_CHUNK_SIZE)
+                .expect("failed to create vertex source")
+                .flat_map(|arr| arr.values().to_vec());
+            VertexIdArray::from_iter_values(ids)
+        });
+        // The vertices are all scanned up front, rather than by the pipeline.
+        if let Some(metrics) = &self.metrics {
+            metrics.add_bytes_scanned(arrow::array::Array::get_array_memory_size(&vertices));
+        }
+        let builder = ExecutorBuilder::new(self.session.clone());
+        let build_stages = move || {
+            stages
+                .iter()
+                .map(|stage| builder.build_morsel_stage(stage))
+                .collect::<Vec<_>>()
+        };
+        let pipeline =
+            MorselPipelineBuilder::new(Arc::new(vertices), build_stages, pool, DEFAULT_CHUNK_SIZE);
+        Some(Box::new(pipeline.into_executor()))
+    }
+
+    #[cfg(feature = "morsel")]
+    fn build_morsel_stage(&self, plan: &PlanNode) -> MorselStage {
+        let schema = plan.children()[0]
+            .schema()
+            .expect("child should have a schema");
+        match plan {
+            PlanNode::PhysicalFilter(filter) => {
+                MorselStage::Filter(self.build_evaluator(&filter.predicate, schema))
+            }
+            PlanNode::PhysicalProject(project) => MorselStage::Project(
+                project
+                    .exprs
+                    .iter()
+                    .map(|e| self.build_materialized_evaluator(e, schema, &HashMap::new()))
+                    .collect(),
+            ),
+            PlanNode::PhysicalVertexPropertyScan(property_scan) => {
+                // The columns of the child are kept, followed by the scanned properties.
+                let index = schema
+                    .get_field_index_by_name(&property_scan.var)
+                    .expect("variable should be present in the schema");
+                let (properties, source) = self.vertex_property_source(property_scan);
+                let source = Arc::new(source);
+                let columns =
+                    (0..schema.size()).map(|i| Box::new(ColumnRef::new(i)) as BoxedEvaluator);
+                let scanned = properties.into_iter().map(|property| {
+                    let source = source.clone();
+                    let func = move |args: Vec<DatumRef>| -> ExecutionResult<DatumRef> {
+                        let [ids] = args
+                            .try_into()
+                            .expect("there should be exactly one argument");
+                        let is_scalar = ids.is_scalar();
+                        let ids = ids.as_array().as_primitive::<UInt64Type>();
+                        let [column] = source
+                            .scan_vertex_properties(ids, &[property])?
+                            .try_into()
+                            .expect("there should be exactly one column");
+                        Ok(DatumRef::new(column, is_scalar))
+                    };
+                    let ids: BoxedEvaluator = Box::new(ColumnRef::new(index));
+                    Box::new(ScalarFunction::new(func, vec![ids])) as BoxedEvaluator
+                });
+                MorselStage::Project(columns.chain(scanned).collect())
+            }
+            _ => unreachable!("only filters, projections and property scans are morsel stages"),
+        }
+    }
+
+    /// Returns the properties scanned by `property_scan`, along with a source reading them from the
+    /// current graph.
+    fn vertex_property_source(
+        &self,
+        property_scan: &PhysicalVertexPropertyScan,
+    ) -> (Vec<usize>, GraphVertexPropertySource) {
+        let (properties, types): (Vec<_>, Vec<_>) = property_scan
+            .properties
+            .iter()
+            .map(|p| {
+                let BoundExprKind::Property { property_id, .. } = &p.kind else {
+                    unreachable!("only properties should be scanned")
+                };
+                (*property_id as usize, p.logical_type.clone())
+            })
+            .unzip();
+        let source = GraphVertexPropertySource {
+            graph: self.current_graph(),
+            types: properties.iter().copied().zip(types).collect(),
+        };
+        (properties, source)
+    }
+
+    /// Returns the graph of the current session, read and modified with the isolation level of the
+    /// session, or as of its read timestamp if any.
+    fn current_graph(&self) -> GraphRef {
+        let graph = self
+            .session
+            .current_graph
+            .as_ref()
+            .expect("the binder should have checked that there is a current graph");
+        let container = graph
+            .as_any()
+            .downcast_ref::<GraphContainer>()
+            .expect("current graph must be GraphContainer");
+        match self.session.read_timestamp {
+            Some(ts) => Arc::new(container.as_of(ts)),
+            None => Arc::new(container.with_isolation(self.session.isolation_level())),
+        }
+    }
+
+    /// Calls `f` with the graph of the current session.
+    fn with_current_graph<R>(&self, f: impl FnOnce(&GraphContainer) -> R) -> R {
+        let cur_graph = self.current_graph();
+        let provider: &dyn GraphProvider = cur_graph.as_ref();
+        let container = provider
+            .as_any()
+            .downcast_ref::<GraphContainer>()
+            .expect("current graph must be GraphContainer");
+        f(container)
+    }
+
+    fn build_aggregate_spec(
+        &self,
+        aggregate: &BoundAggregate,
+        schema: &DataSchema,
+    ) -> AggregateSpec {
+        let Some(arg) = &aggregate.arg else {
+            return AggregateSpec::count();
+        };
+        let arg = self.build_evaluator(arg, schema);
+        let spec = match aggregate.function {
+            BoundAggregateFunction::Count => {
+                AggregateSpec::count_expression(arg, aggregate.distinct)
+            }
+            BoundAggregateFunction::Sum => AggregateSpec::sum(arg, aggregate.distinct),
+            BoundAggregateFunction::Avg => AggregateSpec::avg(arg, aggregate.distinct),
+            BoundAggregateFunction::Min => AggregateSpec::min(arg),
+            BoundAggregateFunction::Max => AggregateSpec::max(arg),
+        };
+        spec.with_output_type(aggregate.logical_type.to_arrow_data_type())
+    }
+
     fn build_evaluator(&self, expr: &BoundExpr, schema: &DataSchema) -> BoxedEvaluator {
+        self.build_evaluator_reusing(expr, schema, &HashMap::new())
+    }
+
+    /// Like [`build_evaluator_reusing`](Self::build_evaluator_reusing), but evaluates vertex
+    /// variables to whole vertices, e.g., for the outputs of projections.
+    ///
+    /// Vertex variables are bound to columns of vertex ids by scans, so the vertices are looked up
+    /// to get their labels and properties.
+    fn build_materialized_evaluator(
+        &self,
+        expr: &BoundExpr,
+        schema: &DataSchema,
+        reused: &HashMap<&BoundExpr, usize>,
+    ) -> BoxedEvaluator {
+        let evaluator = self.build_evaluator_reusing(expr, schema, reused);
+        let BoundExprKind::Variable(variable) = &expr.kind else {
+            return evaluator;
+        };
+        let field = schema
+            .get_field_by_name(variable)
+            .expect("variable should be present in the schema");
+        if !matches!(expr.logical_type, LogicalType::Vertex(_))
+            || matches!(field.ty(), LogicalType::Vertex(_))
+        {
+            return evaluator;
+        }
+        let graph = self.current_graph();
+        let ty = expr.logical_type.clone();
+        let func = move |args: Vec<DatumRef>| -> ExecutionResult<DatumRef> {
+            let [ids] = args
+                .try_into()
+                .expect("there should be exactly one argument");
+            materialize_vertices(&graph, ids, &ty)
+        };
+        Box::new(ScalarFunction::new(func, vec![evaluator]))
+    }
+
+    /// Builds an evaluator of `expr`, which reads the subexpressions in `reused` from the columns
+    /// they are mapped to instead of evaluating them.
+    fn build_evaluator_reusing(
+        &self,
+        expr: &BoundExpr,
+        schema: &DataSchema,
+        reused: &HashMap<&BoundExpr, usize>,
+    ) -> BoxedEvaluator {
+        if let Some(&index) = reused.get(expr) {
+            return Box::new(ColumnRef::new(index));
+        }
         match &expr.kind {
             BoundExprKind::Value(value) => Box::new(Constant::new(value.clone())),
             BoundExprKind::Variable(variable) => {
@@ -140,16 +667,408 @@ fn build_evaluator(&self, expr: &BoundExpr, schema: &DataSchema) -> BoxedEvaluat
                     .expect("variable should be present in the schema");
                 Box::new(ColumnRef::new(index))
             }
+            BoundExprKind::Property {
+                var, property_id, ..
+            } => {
+                let index = schema
+                    .get_field_index_by_name(var)
+                    .expect("variable should be present in the schema");
+                let vertices = Box::new(ColumnRef::new(index));
+                // Materialized vertices are laid out as structs of their ids, their labels and
+                // then their properties.
+                if matches!(schema.fields()[index].ty(), LogicalType::Vertex(_)) {
+                    return Box::new(FieldAccess::new(vertices, *property_id as usize + 2));
+                }
+                let graph = self.current_graph();
+                let property_id = *property_id as usize;
+                let ty = expr.logical_type.clone();
+                let func = move |args: Vec<DatumRef>| -> ExecutionResult<DatumRef> {
+                    let [ids] = args
+                        .try_into()
+                        .expect("there should be exactly one argument");
+                    lookup_properties(&graph, ids, property_id, &ty)
+                };
+                Box::new(ScalarFunction::new(func, vec![vertices]))
+            }
+            BoundExprKind::Unary { op, child } => {
+                let child = self.build_evaluator_reusing(child.as_ref(), schema, reused);
+                let op = match op {
+                    BoundUnaryOp::Plus => return child,
+                    BoundUnaryOp::Minus => UnaryOp::Neg,
+                    BoundUnaryOp::Not => UnaryOp::Not,
+                    BoundUnaryOp::IsNull => UnaryOp::IsNull,
+                    BoundUnaryOp::IsNotNull => UnaryOp::IsNotNull,
+                };
+                Box::new(Unary::new(op, child))
+            }
+            BoundExprKind::Binary { op, lhs, rhs } => {
+                let lhs = self.build_evaluator_reusing(lhs.as_ref(), schema, reused);
+                let rhs = self.build_evaluator_reusing(rhs.as_ref(), schema, reused);
+                let op = match op {
+                    BoundBinaryOp::Add => BinaryOp::Add,
+                    BoundBinaryOp::Sub => BinaryOp::Sub,
+                    BoundBinaryOp::Mul => BinaryOp::Mul,
+                    BoundBinaryOp::Div => BinaryOp::Div,
+                    BoundBinaryOp::Rem => BinaryOp::Rem,
+                    BoundBinaryOp::BitAnd => BinaryOp::BitAnd,
+                    BoundBinaryOp::BitOr => BinaryOp::BitOr,
+                    BoundBinaryOp::BitXor => BinaryOp::BitXor,
+                    BoundBinaryOp::ShiftLeft => BinaryOp::ShiftLeft,
+                    BoundBinaryOp::ShiftRight => BinaryOp::ShiftRight,
+                    BoundBinaryOp::And => BinaryOp::And,
+                    BoundBinaryOp::Or => BinaryOp::Or,
+                    BoundBinaryOp::Eq => BinaryOp::Eq,
+                    BoundBinaryOp::Ne => BinaryOp::Ne,
+                    BoundBinaryOp::Gt => BinaryOp::Gt,
+                    BoundBinaryOp::Ge => BinaryOp::Ge,
+                    BoundBinaryOp::Lt => BinaryOp::Lt,
+                    BoundBinaryOp::Le => BinaryOp::Le,
+                    BoundBinaryOp::Concat | BoundBinaryOp::Xor => {
+                        unimplemented!("binary operator {op} is not supported yet")
+                    }
+                };
+                Box::new(
+                    Binary::new(op, lhs, rhs)
+                        .with_overflow_policy(self.session.overflow_policy)
+                        .with_division_by_zero_policy(self.session.division_by_zero_policy),
+                )
+            }
             BoundExprKind::VectorDistance {
                 lhs,
                 rhs,
                 metric,
                 dimension,
             } => {
-                let lhs = self.build_evaluator(lhs.as_ref(), schema);
-                let rhs = self.build_evaluator(rhs.as_ref(), schema);
+                let lhs = self.build_evaluator_reusing(lhs.as_ref(), schema, reused);
+                let rhs = self.build_evaluator_reusing(rhs.as_ref(), schema, reused);
                 Box::new(VectorDistanceEvaluator::new(lhs, rhs, *metric, *dimension))
             }
+            BoundExprKind::Function { function, args, .. } => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.build_evaluator_reusing(arg, schema, reused))
+                    .collect();
+                let function = function.clone();
+                let func = move |args: Vec<DatumRef>| -> ExecutionResult<DatumRef> {
+                    Ok(function.call(&args)?)
+                };
+                Box::new(ScalarFunction::new(func, args))
+            }
+            BoundExprKind::Field { record, index, .. } => {
+                let record = self.build_evaluator_reusing(record, schema, reused);
+                Box::new(FieldAccess::new(record, *index))
+            }
+            BoundExprKind::ElementFunction { function, element } => {
+                let (function, element) = match function {
+                    // Ids are known without looking the elements up.
+                    BoundElementFunction::Id => (
+                        ElementFunction::Id,
+                        self.build_evaluator_reusing(element, schema, reused),
+                    ),
+                    BoundElementFunction::Labels => (
+                        ElementFunction::Labels(self.label_names()),
+                        self.build_materialized_evaluator(element, schema, reused),
+                    ),
+                    BoundElementFunction::Properties => (
+                        ElementFunction::Properties,
+                        self.build_materialized_evaluator(element, schema, reused),
+                    ),
+                };
+                Box::new(ElementFunctionEvaluator::new(function, element))
+            }
+            BoundExprKind::PathFunction { function, path } => {
+                let path = self.build_evaluator_reusing(path, schema, reused);
+                match function {
+                    BoundPathFunction::Length => Box::new(PathLength::new(path)),
+                    // Paths are laid out as structs of their vertices and of their edges.
+                    BoundPathFunction::Nodes => Box::new(FieldAccess::new(path, 0)),
+                    BoundPathFunction::Relationships => Box::new(FieldAccess::new(path, 1)),
+                }
+            }
+            BoundExprKind::Cast { child, target } => {
+                let child = self.build_evaluator_reusing(child, schema, reused);
+                Box::new(Cast::new(child, target.to_arrow_data_type()))
+            }
         }
     }
+
+    /// Returns the names of the labels of the current graph, keyed by their ids.
+    fn label_names(&self) -> HashMap<LabelId, String> {
+        let graph_type = self.current_graph().graph_type();
+        graph_type
+            .label_names()
+            .into_iter()
+            .filter_map(|name| {
+                let label = graph_type
+                    .get_label_id(&name)
+                    .expect("labels of the graph should be resolved")?;
+                Some((label, name))
+            })
+            .collect()
+    }
+}
+
+/// Builds an evaluator of the join key `key`, a column of `schema`. Vertices are joined on their
+/// ids, whether they are materialized or not.
+fn join_key_evaluator(key: &str, schema: &DataSchema) -> BoxedEvaluator {
+    let index = schema
+        .get_field_index_by_name(key)
+        .expect("join key should be present in the schema");
+    let column = Box::new(ColumnRef::new(index));
+    if matches!(schema.fields()[index].ty(), LogicalType::Vertex(_)) {
+        Box::new(FieldAccess::new(column, 0))
+    } else {
+        column
+    }
+}
+
+/// Looks up the vertices of `ids`, returning them as a column of `ty`, a [`LogicalType::Vertex`]
+/// whose fields are the properties of the vertices in the order of their ids.
+fn materialize_vertices(
+    graph: &GraphRef,
+    ids: DatumRef,
+    ty: &LogicalType,
+) -> ExecutionResult<DatumRef> {
+    let LogicalType::Vertex(fields) = ty else {
+        unreachable!("vertices should be of a vertex type")
+    };
+    let container = graph
+        .as_any()
+        .downcast_ref::<GraphContainer>()
+        .expect("current graph must be GraphContainer");
+    let is_scalar = ids.is_scalar();
+    let vertices = container.vertices(ids.as_array().as_primitive::<UInt64Type>())?;
+    let mut builder = ScalarValue::to_array_builder(ty, vertices.len());
+    for vertex in vertices {
+        let value = vertex.map(|vertex| {
+            let properties = fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let value = vertex.properties.get(i).cloned();
+                    PropertyValue::new(field.name().to_string(), value.unwrap_or(ScalarValue::Null))
+                })
+                .collect();
+            VertexValue::new(vertex.vid, vertex.label_id, properties)
+        });
+        builder
+            .append(ScalarValue::Vertex(value))
+            .map_err(|e| ExecutionError::Custom(Box::new(e)))?;
+    }
+    Ok(DatumRef::new(builder.finish(), is_scalar))
+}
+
+/// Looks up the vertices of `ids`, returning their property `property_id` as a column of `ty`.
+fn lookup_properties(
+    graph: &GraphRef,
+    ids: DatumRef,
+    property_id: usize,
+    ty: &LogicalType,
+) -> ExecutionResult<DatumRef> {
+    let container = graph
+        .as_any()
+        .downcast_ref::<GraphContainer>()
+        .expect("current graph must be GraphContainer");
+    let is_scalar = ids.is_scalar();
+    let vertices = container.vertices(ids.as_array().as_primitive::<UInt64Type>())?;
+    let column = property_column(&vertices, property_id, ty)?;
+    Ok(DatumRef::new(column, is_scalar))
+}
+
+/// Returns the property `property_id` of `vertices` as a column of `ty`.
+///
+/// The property is null for the vertices that do not have it, or that are not found.
+fn property_column(
+    vertices: &[Option<Vertex>],
+    property_id: usize,
+    ty: &LogicalType,
+) -> ExecutionResult<ArrayRef> {
+    let mut builder = ScalarValue::to_array_builder(ty, vertices.len());
+    for vertex in vertices {
+        let value = vertex
+            .as_ref()
+            .and_then(|vertex| vertex.properties.get(property_id).cloned())
+            .unwrap_or(ScalarValue::Null);
+        builder
+            .append(value)
+            .map_err(|e| ExecutionError::Custom(Box::new(e)))?;
+    }
+    Ok(builder.finish())
+}
+
+/// Scans the properties of the vertices of a graph, reading each property as a column of its type
+/// in `types`.
+struct GraphVertexPropertySource {
+    graph: GraphRef,
+    types: HashMap<usize, LogicalType>,
+}
+
+impl VertexPropertySource for GraphVertexPropertySource {
+    fn scan_vertex_properties(
+        &self,
+        vertices: &VertexIdArray,
+        properties: &[usize],
+    ) -> ExecutionResult<Vec<ArrayRef>> {
+        let container = self
+            .graph
+            .as_any()
+            .downcast_ref::<GraphContainer>()
+            .expect("current graph must be GraphContainer");
+        let vertices = container.vertices(vertices)?;
+        properties
+            .iter()
+            .map(|property| {
+                let ty = self
+                    .types
+                    .get(property)
+                    .expect("scanned properties should have a type");
+                property_column(&vertices, *property, ty)
+            })
+            .collect()
+    }
+}
+
+/// Returns the largest subexpressions occurring more than once in `exprs`, in the order they are
+/// first found.
+///
+/// Only subexpressions computed out of some variable are returned. The others are constant, so
+/// they are cheap to evaluate and do not evaluate to columns.
+fn common_subexpressions(exprs: &[BoundExpr]) -> Vec<&BoundExpr> {
+    let mut counts = HashMap::new();
+    for expr in exprs {
+        count_subexpressions(expr, &mut counts);
+    }
+    let mut common = vec![];
+    let mut found = HashSet::new();
+    for expr in exprs {
+        collect_common_subexpressions(expr, &counts, &mut found, &mut common);
+    }
+    common
+}
+
+/// Counts the occurrences of the non-constant subexpressions of `expr`, other than variables.
+///
+/// Returns whether `expr` refers to a variable.
+fn count_subexpressions<'a>(
+    expr: &'a BoundExpr,
+    counts: &mut HashMap<&'a BoundExpr, usize>,
+) -> bool {
+    let children = subexpressions(expr);
+    let mut refers_to_variable = false;
+    for child in &children {
+        refers_to_variable |= count_subexpressions(child, counts);
+    }
+    match &expr.kind {
+        BoundExprKind::Variable(_) | BoundExprKind::Property { .. } => true,
+        _ if children.is_empty() => false,
+        _ => {
+            if refers_to_variable {
+                *counts.entry(expr).or_default() += 1;
+            }
+            refers_to_variable
+        }
+    }
+}
+
+fn collect_common_subexpressions<'a>(
+    expr: &'a BoundExpr,
+    counts: &HashMap<&BoundExpr, usize>,
+    found: &mut HashSet<&'a BoundExpr>,
+    common: &mut Vec<&'a BoundExpr>,
+) {
+    if counts.get(expr).is_some_and(|count| *count > 1) {
+        // The subexpressions of a common subexpression are only evaluated along with it.
+        if found.insert(expr) {
+            common.push(expr);
+        }
+        return;
+    }
+    for child in subexpressions(expr) {
+        collect_common_subexpressions(child, counts, found, common);
+    }
+}
+
+fn subexpressions(expr: &BoundExpr) -> Vec<&BoundExpr> {
+    match &expr.kind {
+        BoundExprKind::Unary { child, .. } => vec![child.as_ref()],
+        BoundExprKind::Binary { lhs, rhs, .. } | BoundExprKind::VectorDistance { lhs, rhs, .. } => {
+            vec![lhs.as_ref(), rhs.as_ref()]
+        }
+        BoundExprKind::Function { args, .. } => args.iter().collect(),
+        BoundExprKind::Field { record, .. } => vec![record.as_ref()],
+        BoundExprKind::ElementFunction { element, .. } => vec![element.as_ref()],
+        BoundExprKind::PathFunction { path, .. } => vec![path.as_ref()],
+        BoundExprKind::Cast { child, .. } => vec![child.as_ref()],
+        BoundExprKind::Value(_) | BoundExprKind::Variable(_) | BoundExprKind::Property { .. } => {
+            vec![]
+        }
+    }
+}
+
+/// Scans the vertices of `graph` whose ids are in `range`, and that match any of the label sets in
+/// `labels`.
+fn scan_vertex_range(
+    graph: GraphRef,
+    labels: Vec<Vec<LabelId>>,
+    range: RangeInclusive<VertexId>,
+) -> impl Executor + Send + 'static {
+    gen move {
+        let source = {
+            let container = graph
+                .as_any()
+                .downcast_ref::<GraphContainer>()
+                .expect("current graph must be GraphContainer");
+            container.vertex_source_in_range(&labels, range, 1024)
+        };
+        let mut scan = gen_try!(source)
+            .map(|arr: Arc<VertexIdArray>| Ok(arr))
+            .scan_vertex();
+        while let Some(chunk) = scan.next_chunk() {
+            yield chunk;
+        }
+    }
+    .into_executor()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable(name: &str) -> BoundExpr {
+        BoundExpr::variable(name.to_string(), LogicalType::Int32, false)
+    }
+
+    fn one() -> BoundExpr {
+        BoundExpr::value(1i32.into(), LogicalType::Int32, false)
+    }
+
+    fn add(lhs: BoundExpr, rhs: BoundExpr) -> BoundExpr {
+        BoundExpr::binary(BoundBinaryOp::Add, lhs, rhs)
+    }
+
+    fn mul(lhs: BoundExpr, rhs: BoundExpr) -> BoundExpr {
+        BoundExpr::binary(BoundBinaryOp::Mul, lhs, rhs)
+    }
+
+    #[test]
+    fn test_common_subexpressions() {
+        let a_plus_b = add(variable("a"), variable("b"));
+        let exprs = [
+            mul(a_plus_b.clone(), variable("c")),
+            add(a_plus_b.clone(), one()),
+            // Constant subexpressions are not shared.
+            add(one(), one()),
+            add(one(), one()),
+            variable("c"),
+        ];
+        assert_eq!(common_subexpressions(&exprs), [&a_plus_b]);
+    }
+
+    #[test]
+    fn test_largest_common_subexpressions() {
+        let product = mul(add(variable("a"), variable("b")), variable("c"));
+        let exprs = [add(product.clone(), one()), product.clone()];
+        assert_eq!(common_subexpressions(&exprs), [&product]);
+        assert!(common_subexpressions(&[product]).is_empty());
+    }
 }
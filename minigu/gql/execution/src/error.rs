@@ -2,6 +2,7 @@
 
 use miette::Diagnostic;
 use minigu_common::error::NotImplemented;
+use minigu_common::types::VertexId;
 use minigu_storage::error::StorageError;
 use thiserror::Error;
 
@@ -17,8 +18,27 @@ pub enum ExecutionError {
     #[diagnostic(transparent)]
     NotImplemented(#[from] NotImplemented),
 
+    #[error("io error")]
+    Io(#[from] std::io::Error),
+
     #[error("storage error")]
     Storage(#[from] StorageError),
+
+    #[error("division by zero")]
+    #[diagnostic(help(
+        "use the `Null` division by zero policy to divide integers by zero into null instead"
+    ))]
+    DivisionByZero,
+
+    #[error("invalid cast: {0}")]
+    InvalidCast(String),
+
+    #[error("query was cancelled")]
+    Cancelled,
+
+    #[error("cannot delete vertex {0} because it still has edges")]
+    #[diagnostic(help("use DETACH DELETE to delete its edges as well"))]
+    VertexHasEdges(VertexId),
 }
 
 pub type ExecutionResult<T> = Result<T, ExecutionError>;
@@ -1,11 +1,21 @@
 use std::sync::Arc;
 
-use arrow::array::AsArray;
-use arrow::compute::kernels::{boolean, cmp, numeric};
+use arrow::array::{
+    Array, ArrayRef, ArrowPrimitiveType, AsArray, BooleanArray, Int8Array, PrimitiveArray, Scalar,
+    new_null_array,
+};
+use arrow::compute::cast;
+use arrow::compute::kernels::{boolean, cmp, nullif, numeric};
+use arrow::datatypes::{
+    DataType, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type, UInt32Type,
+    UInt64Type,
+};
+use arrow::error::ArrowError;
+use minigu_common::arithmetic::{DivisionByZeroPolicy, OverflowPolicy};
 use minigu_common::data_chunk::DataChunk;
 
 use super::{DatumRef, Evaluator};
-use crate::error::ExecutionResult;
+use crate::error::{ExecutionError, ExecutionResult};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BinaryOp {
@@ -14,6 +24,15 @@ pub enum BinaryOp {
     Mul,
     Div,
     Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    /// Shifts the bits of the left operand to the left by the right operand, modulo the bit width
+    /// of the type.
+    ShiftLeft,
+    /// Shifts the bits of the left operand to the right by the right operand, modulo the bit width
+    /// of the type. The sign bit is kept for signed integers.
+    ShiftRight,
     And,
     Or,
     Eq,
@@ -22,6 +41,8 @@ pub enum BinaryOp {
     Ge,
     Lt,
     Le,
+    /// Null-safe equality: null equals null, and differs from any other value.
+    IsNotDistinctFrom,
 }
 
 #[derive(Debug)]
@@ -29,24 +50,54 @@ pub struct Binary<L, R> {
     op: BinaryOp,
     left: L,
     right: R,
+    overflow_policy: OverflowPolicy,
+    division_by_zero_policy: DivisionByZeroPolicy,
 }
 
 impl<L, R> Binary<L, R> {
     pub fn new(op: BinaryOp, left: L, right: R) -> Self {
-        Self { op, left, right }
+        Self {
+            op,
+            left,
+            right,
+            overflow_policy: OverflowPolicy::default(),
+            division_by_zero_policy: DivisionByZeroPolicy::default(),
+        }
+    }
+
+    /// Resolves the integer overflows of additions, subtractions and multiplications with
+    /// `policy`.
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+
+    /// Resolves the integer divisions and remainders by zero with `policy`.
+    pub fn with_division_by_zero_policy(mut self, policy: DivisionByZeroPolicy) -> Self {
+        self.division_by_zero_policy = policy;
+        self
     }
 }
 
 impl<L: Evaluator, R: Evaluator> Evaluator for Binary<L, R> {
     fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
         let left = self.left.evaluate(chunk)?;
-        let right = self.right.evaluate(chunk)?;
+        let right = match self.op {
+            BinaryOp::And | BinaryOp::Or => self.evaluate_undecided(&left, chunk)?,
+            _ => self.right.evaluate(chunk)?,
+        };
         let array = match self.op {
-            BinaryOp::Add => numeric::add(&left, &right)?,
-            BinaryOp::Sub => numeric::sub(&left, &right)?,
-            BinaryOp::Mul => numeric::mul(&left, &right)?,
-            BinaryOp::Div => numeric::div(&left, &right)?,
-            BinaryOp::Rem => numeric::rem(&left, &right)?,
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => {
+                arithmetic(self.op, &left, &right, self.overflow_policy)?
+            }
+            BinaryOp::Div | BinaryOp::Rem => {
+                division(self.op, &left, &right, self.division_by_zero_policy)?
+            }
+            BinaryOp::BitAnd
+            | BinaryOp::BitOr
+            | BinaryOp::BitXor
+            | BinaryOp::ShiftLeft
+            | BinaryOp::ShiftRight => bitwise(self.op, &left, &right)?,
             BinaryOp::And | BinaryOp::Or => {
                 let left = left.as_array().as_boolean();
                 let right = right.as_array().as_boolean();
@@ -62,14 +113,249 @@ fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
             BinaryOp::Ge => Arc::new(cmp::gt_eq(&left, &right)?),
             BinaryOp::Lt => Arc::new(cmp::lt(&left, &right)?),
             BinaryOp::Le => Arc::new(cmp::lt_eq(&left, &right)?),
+            BinaryOp::IsNotDistinctFrom => Arc::new(cmp::not_distinct(&left, &right)?),
         };
         Ok(DatumRef::new(array, left.is_scalar() && right.is_scalar()))
     }
 }
 
+impl<L, R: Evaluator> Binary<L, R> {
+    /// Evaluates the right operand of a conjunction or disjunction only on the rows whose result
+    /// is not already decided by `left`, i.e., where `left` is not false or not true respectively,
+    /// so that, e.g., `x <> 0 AND 10 / x > 1` never divides by zero. The right operand is null on
+    /// the other rows.
+    fn evaluate_undecided(&self, left: &DatumRef, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
+        let decided = self.op == BinaryOp::Or;
+        let undecided: BooleanArray = left
+            .as_array()
+            .as_boolean()
+            .iter()
+            .map(|value| Some(value != Some(decided)))
+            .collect();
+        match undecided.true_count() {
+            0 => Ok(DatumRef::new(
+                new_null_array(&DataType::Boolean, undecided.len()),
+                left.is_scalar(),
+            )),
+            count if count == undecided.len() => self.right.evaluate(chunk),
+            _ => {
+                let mut undecided_chunk =
+                    DataChunk::new(chunk.columns().to_vec()).with_filter(undecided.clone());
+                undecided_chunk.compact();
+                let right = self.right.evaluate(&undecided_chunk)?;
+                let is_scalar = right.is_scalar();
+                let right = right.as_array().as_boolean();
+                let mut index = 0;
+                let right: BooleanArray = undecided
+                    .values()
+                    .iter()
+                    .map(|undecided| {
+                        if !undecided {
+                            return None;
+                        }
+                        let value = right.is_valid(index).then(|| right.value(index));
+                        if !is_scalar {
+                            index += 1;
+                        }
+                        value
+                    })
+                    .collect();
+                Ok(DatumRef::new(Arc::new(right), false))
+            }
+        }
+    }
+}
+
+/// Evaluates the addition, subtraction or multiplication `op`, resolving integer overflows with
+/// `policy`.
+fn arithmetic(
+    op: BinaryOp,
+    left: &DatumRef,
+    right: &DatumRef,
+    policy: OverflowPolicy,
+) -> ExecutionResult<ArrayRef> {
+    let result = match (op, policy) {
+        (BinaryOp::Add, OverflowPolicy::Wrap) => numeric::add_wrapping(left, right),
+        (BinaryOp::Sub, OverflowPolicy::Wrap) => numeric::sub_wrapping(left, right),
+        (BinaryOp::Mul, OverflowPolicy::Wrap) => numeric::mul_wrapping(left, right),
+        (BinaryOp::Add, _) => numeric::add(left, right),
+        (BinaryOp::Sub, _) => numeric::sub(left, right),
+        (BinaryOp::Mul, _) => numeric::mul(left, right),
+        _ => unreachable!("{op:?} is not an overflowing operator"),
+    };
+    match (result, policy) {
+        // Overflows are rare, so values are only resolved one by one once the kernel failed.
+        (
+            Err(ArrowError::ArithmeticOverflow(_)),
+            OverflowPolicy::Saturate | OverflowPolicy::Null,
+        ) => {
+            let saturate = policy == OverflowPolicy::Saturate;
+            macro_rules! resolve {
+                ($ty:ty, $native:ty) => {
+                    match op {
+                        BinaryOp::Add => resolve_overflows::<$ty>(
+                            left,
+                            right,
+                            <$native>::checked_add,
+                            saturate.then_some(<$native>::saturating_add as fn(_, _) -> _),
+                        ),
+                        BinaryOp::Sub => resolve_overflows::<$ty>(
+                            left,
+                            right,
+                            <$native>::checked_sub,
+                            saturate.then_some(<$native>::saturating_sub as fn(_, _) -> _),
+                        ),
+                        _ => resolve_overflows::<$ty>(
+                            left,
+                            right,
+                            <$native>::checked_mul,
+                            saturate.then_some(<$native>::saturating_mul as fn(_, _) -> _),
+                        ),
+                    }
+                };
+            }
+            let array = match left.as_array().data_type() {
+                DataType::Int8 => resolve!(Int8Type, i8),
+                DataType::Int16 => resolve!(Int16Type, i16),
+                DataType::Int32 => resolve!(Int32Type, i32),
+                DataType::Int64 => resolve!(Int64Type, i64),
+                DataType::UInt8 => resolve!(UInt8Type, u8),
+                DataType::UInt16 => resolve!(UInt16Type, u16),
+                DataType::UInt32 => resolve!(UInt32Type, u32),
+                DataType::UInt64 => resolve!(UInt64Type, u64),
+                ty => unreachable!("only integers overflow, not {ty}"),
+            };
+            Ok(array)
+        }
+        (result, _) => Ok(result?),
+    }
+}
+
+/// Evaluates the division or remainder `op`, resolving integer divisions by zero with `policy`.
+/// Floating-point divisions by zero follow IEEE 754.
+fn division(
+    op: BinaryOp,
+    left: &DatumRef,
+    right: &DatumRef,
+    policy: DivisionByZeroPolicy,
+) -> ExecutionResult<ArrayRef> {
+    let kernel = match op {
+        BinaryOp::Div => numeric::div,
+        BinaryOp::Rem => numeric::rem,
+        _ => unreachable!("{op:?} is not a division"),
+    };
+    match (kernel(left, right), policy) {
+        (Err(ArrowError::DivideByZero), DivisionByZeroPolicy::Error) => {
+            Err(ExecutionError::DivisionByZero)
+        }
+        (Err(ArrowError::DivideByZero), DivisionByZeroPolicy::Null) => {
+            // Zero divisors are nulled out, so that their quotients are null too.
+            let divisor = right.as_array();
+            let zero = cast(&Int8Array::from(vec![0]), divisor.data_type())?;
+            let is_zero = cmp::eq(divisor, &Scalar::new(zero))?;
+            let divisor = DatumRef::new(nullif::nullif(divisor, &is_zero)?, right.is_scalar());
+            Ok(kernel(left, &divisor)?)
+        }
+        (result, _) => Ok(result?),
+    }
+}
+
+/// Evaluates the bitwise operator `op` on integers.
+pub(super) fn bitwise(
+    op: BinaryOp,
+    left: &DatumRef,
+    right: &DatumRef,
+) -> ExecutionResult<ArrayRef> {
+    macro_rules! apply {
+        ($ty:ty) => {
+            match op {
+                BinaryOp::BitAnd => map_values::<$ty>(left, right, |l, r| Some(l & r)),
+                BinaryOp::BitOr => map_values::<$ty>(left, right, |l, r| Some(l | r)),
+                BinaryOp::BitXor => map_values::<$ty>(left, right, |l, r| Some(l ^ r)),
+                BinaryOp::ShiftLeft => {
+                    map_values::<$ty>(left, right, |l, r| Some(l.wrapping_shl(r as u32)))
+                }
+                BinaryOp::ShiftRight => {
+                    map_values::<$ty>(left, right, |l, r| Some(l.wrapping_shr(r as u32)))
+                }
+                _ => unreachable!("{op:?} is not a bitwise operator"),
+            }
+        };
+    }
+    let (lhs_type, rhs_type) = (left.as_array().data_type(), right.as_array().data_type());
+    if lhs_type != rhs_type {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "bitwise operands must have the same type, got {lhs_type} and {rhs_type}"
+        ))
+        .into());
+    }
+    let array = match lhs_type {
+        DataType::Int8 => apply!(Int8Type),
+        DataType::Int16 => apply!(Int16Type),
+        DataType::Int32 => apply!(Int32Type),
+        DataType::Int64 => apply!(Int64Type),
+        DataType::UInt8 => apply!(UInt8Type),
+        DataType::UInt16 => apply!(UInt16Type),
+        DataType::UInt32 => apply!(UInt32Type),
+        DataType::UInt64 => apply!(UInt64Type),
+        ty => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "bitwise operands must be integers, got {ty}"
+            ))
+            .into());
+        }
+    };
+    Ok(array)
+}
+
+/// A binary operator on native values, such as `i32::saturating_add`.
+type NativeOp<N> = fn(N, N) -> N;
+
+/// Applies `checked` to the values of `left` and `right`, resolving its overflows with
+/// `saturating`, or to null if `None`.
+fn resolve_overflows<T: ArrowPrimitiveType>(
+    left: &DatumRef,
+    right: &DatumRef,
+    checked: fn(T::Native, T::Native) -> Option<T::Native>,
+    saturating: Option<NativeOp<T::Native>>,
+) -> ArrayRef {
+    map_values::<T>(left, right, |l, r| {
+        checked(l, r).or_else(|| saturating.map(|saturating| saturating(l, r)))
+    })
+}
+
+/// Applies `f` to the values of `left` and `right`, which are broadcast if scalar. The result is
+/// null if either value is, or if `f` returns `None`.
+fn map_values<T: ArrowPrimitiveType>(
+    left: &DatumRef,
+    right: &DatumRef,
+    f: impl Fn(T::Native, T::Native) -> Option<T::Native>,
+) -> ArrayRef {
+    let lhs = left.as_array().as_primitive::<T>();
+    let rhs = right.as_array().as_primitive::<T>();
+    let len = if left.is_scalar() {
+        rhs.len()
+    } else {
+        lhs.len()
+    };
+    let value = |array: &PrimitiveArray<T>, is_scalar: bool, index: usize| {
+        let index = if is_scalar { 0 } else { index };
+        array.is_valid(index).then(|| array.value(index))
+    };
+    let result: PrimitiveArray<T> = (0..len)
+        .map(|i| {
+            let l = value(lhs, left.is_scalar(), i)?;
+            let r = value(rhs, right.is_scalar(), i)?;
+            f(l, r)
+        })
+        .collect();
+    Arc::new(result)
+}
+
 #[cfg(test)]
 mod tests {
-    use arrow::array::{ArrayRef, create_array};
+    use arrow::array::{ArrayRef, Int32Array, Int64Array, create_array};
+    use arrow::datatypes::Float64Type;
     use minigu_common::data_chunk;
 
     use super::*;
@@ -126,13 +412,10 @@ fn test_binary_5() {
         let chunk = data_chunk!(
             (Boolean, [Some(true), None, Some(false), None, None]),
             (Boolean, [Some(true), None, None, Some(true), Some(false)]),
-            (Boolean, [
-                Some(false),
-                Some(true),
-                None,
-                Some(false),
-                Some(false)
-            ])
+            (
+                Boolean,
+                [Some(false), Some(true), None, Some(false), Some(false)]
+            )
         );
         // c0 AND c1 OR c2
         let c0_and_c1_or_c2 = ColumnRef::new(0)
@@ -144,6 +427,31 @@ fn test_binary_5() {
         assert_eq!(result.as_array(), &expected);
     }
 
+    #[test]
+    fn test_binary_short_circuit() {
+        let chunk = data_chunk!((Int32, [Some(0), Some(2), None, Some(20)]));
+        // c0 <> 0 AND 10 / c0 > 1
+        let c0_ne_0 = || ColumnRef::new(0).ne(Constant::new(0i32.into()));
+        let ten_div_c0_gt_1 = || {
+            Constant::new(10i32.into())
+                .div(ColumnRef::new(0))
+                .gt(Constant::new(1i32.into()))
+        };
+        let result = c0_ne_0().and(ten_div_c0_gt_1()).evaluate(&chunk).unwrap();
+        let expected: ArrayRef =
+            create_array!(Boolean, [Some(false), Some(true), None, Some(false)]);
+        assert_eq!(result.as_array(), &expected);
+        // c0 = 0 OR 10 / c0 > 1
+        let c0_eq_0 = ColumnRef::new(0).eq(Constant::new(0i32.into()));
+        let result = c0_eq_0.or(ten_div_c0_gt_1()).evaluate(&chunk).unwrap();
+        let expected: ArrayRef =
+            create_array!(Boolean, [Some(true), Some(true), None, Some(false)]);
+        assert_eq!(result.as_array(), &expected);
+        // The right operand is not evaluated at all if the left one decides every row
+        let always_false = Constant::new(false.into());
+        assert!(always_false.and(ten_div_c0_gt_1()).evaluate(&chunk).is_ok());
+    }
+
     #[test]
     fn test_binary_6() {
         let chunk = data_chunk!((Int32, [Some(1), Some(2), None]));
@@ -155,4 +463,131 @@ fn test_binary_6() {
         let expected: ArrayRef = create_array!(Int32, [Some(5), Some(8), None]);
         assert_eq!(result.as_array(), &expected);
     }
+
+    #[test]
+    fn test_binary_overflow_policy() {
+        let chunk = data_chunk!((Int32, [Some(i32::MAX), Some(1), None]));
+        let add_1 = |policy| {
+            let c0_add_1 = ColumnRef::new(0)
+                .add(Constant::new(1i32.into()))
+                .with_overflow_policy(policy);
+            c0_add_1.evaluate(&chunk).map(DatumRef::into_array)
+        };
+        assert!(add_1(OverflowPolicy::Error).is_err());
+        let expected: ArrayRef = create_array!(Int32, [Some(i32::MIN), Some(2), None]);
+        assert_eq!(&add_1(OverflowPolicy::Wrap).unwrap(), &expected);
+        let expected: ArrayRef = create_array!(Int32, [Some(i32::MAX), Some(2), None]);
+        assert_eq!(&add_1(OverflowPolicy::Saturate).unwrap(), &expected);
+        let expected: ArrayRef = create_array!(Int32, [None, Some(2), None]);
+        assert_eq!(&add_1(OverflowPolicy::Null).unwrap(), &expected);
+
+        // Saturated products take the sign of the exact result.
+        let chunk = data_chunk!((Int64, [i64::MIN, i64::MAX]), (Int64, [2, -2]));
+        let c0_mul_c1 = ColumnRef::new(0)
+            .mul(ColumnRef::new(1))
+            .with_overflow_policy(OverflowPolicy::Saturate);
+        let result = c0_mul_c1.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Int64, [i64::MIN, i64::MIN]);
+        assert_eq!(result.as_array(), &expected);
+    }
+
+    #[test]
+    fn test_binary_division_by_zero() {
+        let chunk = data_chunk!((Int32, [Some(7), Some(7), None]), (Int32, [2, 0, 0]));
+        let c0_div_c1 = ColumnRef::new(0).div(ColumnRef::new(1));
+        assert!(matches!(
+            c0_div_c1.evaluate(&chunk),
+            Err(ExecutionError::DivisionByZero)
+        ));
+        let c0_rem_c1 = ColumnRef::new(0).rem(ColumnRef::new(1));
+        assert!(matches!(
+            c0_rem_c1.evaluate(&chunk),
+            Err(ExecutionError::DivisionByZero)
+        ));
+
+        let c0_div_c1 = ColumnRef::new(0)
+            .div(ColumnRef::new(1))
+            .with_division_by_zero_policy(DivisionByZeroPolicy::Null);
+        let result = c0_div_c1.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Int32, [Some(3), None, None]);
+        assert_eq!(result.as_array(), &expected);
+        let c0_rem_0 = ColumnRef::new(0)
+            .rem(Constant::new(0i32.into()))
+            .with_division_by_zero_policy(DivisionByZeroPolicy::Null);
+        let result = c0_rem_0.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = Arc::new(Int32Array::new_null(3));
+        assert_eq!(result.as_array(), &expected);
+    }
+
+    #[test]
+    fn test_binary_float_division_by_zero() {
+        let chunk = data_chunk!((Float64, [1.0, -1.0, 0.0]), (Float64, [0.0, 0.0, 0.0]));
+        let c0_div_c1 = ColumnRef::new(0).div(ColumnRef::new(1));
+        let result = c0_div_c1.evaluate(&chunk).unwrap();
+        let result = result.as_array().as_primitive::<Float64Type>();
+        assert_eq!(result.value(0), f64::INFINITY);
+        assert_eq!(result.value(1), f64::NEG_INFINITY);
+        assert!(result.value(2).is_nan());
+
+        let c0_rem_c1 = ColumnRef::new(0).rem(ColumnRef::new(1));
+        let result = c0_rem_c1.evaluate(&chunk).unwrap();
+        let result = result.as_array().as_primitive::<Float64Type>();
+        assert!(result.values().iter().all(|value| value.is_nan()));
+    }
+
+    #[test]
+    fn test_binary_modulo_and_bitwise() {
+        let chunk = data_chunk!(
+            (Int64, [Some(12), Some(-7), Some(5), None]),
+            (Int64, [Some(10), Some(3), Some(1), Some(1)])
+        );
+        let evaluate = |op| {
+            Binary::new(op, ColumnRef::new(0), ColumnRef::new(1))
+                .evaluate(&chunk)
+                .unwrap()
+                .into_array()
+        };
+        let cases = [
+            (BinaryOp::Rem, [Some(2), Some(-1), Some(0), None]),
+            (BinaryOp::BitAnd, [Some(8), Some(1), Some(1), None]),
+            (BinaryOp::BitOr, [Some(14), Some(-5), Some(5), None]),
+            (BinaryOp::BitXor, [Some(6), Some(-6), Some(4), None]),
+            (
+                BinaryOp::ShiftLeft,
+                [Some(12288), Some(-56), Some(10), None],
+            ),
+            (BinaryOp::ShiftRight, [Some(0), Some(-1), Some(2), None]),
+        ];
+        for (op, expected) in cases {
+            let expected: ArrayRef = Arc::new(Int64Array::from(expected.to_vec()));
+            assert_eq!(&evaluate(op), &expected, "{op:?}");
+        }
+
+        // Scalars are broadcast.
+        let c0_and_1 = ColumnRef::new(0).bit_and(Constant::new(1i64.into()));
+        let result = c0_and_1.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Int64, [Some(0), Some(1), Some(1), None]);
+        assert_eq!(result.as_array(), &expected);
+
+        let chunk = data_chunk!((Float64, [1.0]));
+        let c0_or_c0 = ColumnRef::new(0).bit_or(ColumnRef::new(0));
+        assert!(c0_or_c0.evaluate(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_binary_is_not_distinct_from() {
+        let chunk = data_chunk!(
+            (Int32, [Some(1), Some(1), None, None]),
+            (Int32, [Some(1), None, Some(2), None])
+        );
+        let c0_eq_c1 = ColumnRef::new(0).eq(ColumnRef::new(1));
+        let result = c0_eq_c1.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Boolean, [Some(true), None, None, None]);
+        assert_eq!(result.as_array(), &expected);
+
+        let c0_not_distinct_c1 = ColumnRef::new(0).is_not_distinct_from(ColumnRef::new(1));
+        let result = c0_not_distinct_c1.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Boolean, [true, false, false, true]);
+        assert_eq!(result.as_array(), &expected);
+    }
 }
@@ -0,0 +1,87 @@
+use arrow::compute::{CastOptions, cast_with_options};
+use arrow::datatypes::DataType;
+use arrow::error::ArrowError;
+use minigu_common::data_chunk::DataChunk;
+
+use super::{BoxedEvaluator, DatumRef, Evaluator};
+use crate::error::{ExecutionError, ExecutionResult};
+
+/// Evaluates the conversion of the values of `operand` to `to`.
+///
+/// Values which cannot be converted, e.g., `'abc'` to an integer or `300` to an 8-bit integer, make
+/// the evaluation fail rather than being converted into null.
+#[derive(Debug)]
+pub struct Cast {
+    operand: BoxedEvaluator,
+    to: DataType,
+}
+
+impl Cast {
+    pub fn new(operand: BoxedEvaluator, to: DataType) -> Self {
+        Self { operand, to }
+    }
+}
+
+impl Evaluator for Cast {
+    fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
+        let operand = self.operand.evaluate(chunk)?;
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let array =
+            cast_with_options(operand.as_array(), &self.to, &options).map_err(|e| match e {
+                ArrowError::CastError(message)
+                | ArrowError::ParseError(message)
+                | ArrowError::ComputeError(message) => ExecutionError::InvalidCast(message),
+                e => e.into(),
+            })?;
+        Ok(DatumRef::new(array, operand.is_scalar()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{AsArray, Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{Float64Type, Int32Type};
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+
+    fn cast(chunk: DataChunk, to: DataType) -> ExecutionResult<DatumRef> {
+        Cast::new(Box::new(ColumnRef::new(0)), to).evaluate(&chunk)
+    }
+
+    #[test]
+    fn test_cast_string_to_int() {
+        let chunk = DataChunk::new(vec![Arc::new(StringArray::from(vec![
+            Some("42"),
+            None,
+            Some("-7"),
+        ]))]);
+        let result = cast(chunk, DataType::Int32).unwrap().into_array();
+        let expected = Int32Array::from(vec![Some(42), None, Some(-7)]);
+        assert_eq!(result.as_primitive::<Int32Type>(), &expected);
+    }
+
+    #[test]
+    fn test_cast_invalid_string_to_int() {
+        let chunk = DataChunk::new(vec![Arc::new(StringArray::from(vec!["42", "abc"]))]);
+        let err = cast(chunk, DataType::Int32).unwrap_err();
+        assert!(matches!(err, ExecutionError::InvalidCast(_)));
+    }
+
+    #[test]
+    fn test_cast_int_to_float() {
+        let chunk = DataChunk::new(vec![Arc::new(Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(-3),
+        ]))]);
+        let result = cast(chunk, DataType::Float64).unwrap().into_array();
+        let expected = Float64Array::from(vec![Some(1.0), None, Some(-3.0)]);
+        assert_eq!(result.as_primitive::<Float64Type>(), &expected);
+    }
+}
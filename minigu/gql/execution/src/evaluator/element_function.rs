@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, ListBuilder, StringBuilder, StructArray, UInt64Array,
+};
+use arrow::compute;
+use arrow::datatypes::{DataType, UInt32Type, UInt64Type};
+use minigu_common::constants::EID_FIELD_NAME;
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::types::LabelId;
+
+use super::{BoxedEvaluator, DatumRef, Evaluator};
+use crate::error::ExecutionResult;
+
+/// A built-in function on graph elements.
+#[derive(Debug, Clone)]
+pub enum ElementFunction {
+    /// Returns the ids of the elements.
+    Id,
+    /// Returns the names of the labels of the elements, as lists of strings, given the names of
+    /// the labels of the graph.
+    Labels(HashMap<LabelId, String>),
+    /// Returns the properties of the elements, as records of their property fields.
+    Properties,
+}
+
+/// Evaluates an [`ElementFunction`] over a column of vertices or edges.
+///
+/// The elements are read from struct arrays, laid out as in
+/// [`LogicalType::Vertex`](minigu_common::data_type::LogicalType::Vertex) and
+/// [`LogicalType::Edge`](minigu_common::data_type::LogicalType::Edge). [`ElementFunction::Id`] also
+/// accepts a column of ids, e.g., the vertices output by a scan, which are returned as is.
+#[derive(Debug)]
+pub struct ElementFunctionEvaluator {
+    function: ElementFunction,
+    element: BoxedEvaluator,
+}
+
+impl ElementFunctionEvaluator {
+    pub fn new(function: ElementFunction, element: BoxedEvaluator) -> Self {
+        Self { function, element }
+    }
+}
+
+impl Evaluator for ElementFunctionEvaluator {
+    fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
+        let element = self.element.evaluate(chunk)?;
+        let is_scalar = element.is_scalar();
+        let array = element.as_array();
+        let Some(elements) = array.as_struct_opt() else {
+            assert!(
+                matches!(self.function, ElementFunction::Id),
+                "elements should be structs"
+            );
+            let ids = compute::cast(array, &DataType::UInt64)?;
+            return Ok(DatumRef::new(ids, is_scalar));
+        };
+        let result: ArrayRef = match &self.function {
+            ElementFunction::Id => {
+                // The ids of null elements are masked by the struct, so they are masked here too.
+                let ids = elements.column(0).as_primitive::<UInt64Type>().values();
+                Arc::new(UInt64Array::new(ids.clone(), elements.nulls().cloned()))
+            }
+            ElementFunction::Labels(names) => {
+                let labels = elements.column(1).as_primitive::<UInt32Type>();
+                let mut builder = ListBuilder::new(StringBuilder::new());
+                for i in 0..elements.len() {
+                    if elements.is_null(i) {
+                        builder.append_null();
+                        continue;
+                    }
+                    let name = LabelId::new(labels.value(i)).and_then(|label| names.get(&label));
+                    builder.values().append_option(name);
+                    builder.append(true);
+                }
+                Arc::new(builder.finish())
+            }
+            ElementFunction::Properties => {
+                // Vertices have an id and a label before their properties, and edges also have
+                // their source and destination.
+                let offset = match elements.fields().first() {
+                    Some(field) if field.name() == EID_FIELD_NAME => 4,
+                    _ => 2,
+                };
+                let fields = elements.fields()[offset..].iter().cloned().collect();
+                let columns = elements.columns()[offset..].to_vec();
+                let nulls = elements.nulls().cloned();
+                let properties = if columns.is_empty() {
+                    StructArray::new_empty_fields(elements.len(), nulls)
+                } else {
+                    StructArray::new(fields, columns, nulls)
+                };
+                Arc::new(properties)
+            }
+        };
+        Ok(DatumRef::new(result, is_scalar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::ListArray;
+    use minigu_common::data_type::{DataField, LogicalType};
+    use minigu_common::value::{PropertyValue, ScalarValue, VertexValue};
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+
+    fn vertices() -> DataChunk {
+        let ty = LogicalType::Vertex(vec![DataField::new(
+            "name".into(),
+            LogicalType::String,
+            true,
+        )]);
+        let label = LabelId::new(1).unwrap();
+        let mut builder = ScalarValue::to_array_builder(&ty, 2);
+        let properties = vec![PropertyValue::new("name".into(), "alice".into())];
+        let vertex = VertexValue::new(7, label, properties);
+        builder.append(ScalarValue::Vertex(Some(vertex))).unwrap();
+        builder.append(ScalarValue::Vertex(None)).unwrap();
+        DataChunk::new(vec![builder.finish()])
+    }
+
+    fn evaluate(function: ElementFunction, chunk: &DataChunk) -> DatumRef {
+        ElementFunctionEvaluator::new(function, Box::new(ColumnRef::new(0)))
+            .evaluate(chunk)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_id() {
+        let ids = evaluate(ElementFunction::Id, &vertices()).into_array();
+        let expected = UInt64Array::from(vec![Some(7), None]);
+        assert_eq!(ids.as_primitive::<UInt64Type>(), &expected);
+        let chunk = DataChunk::new(vec![Arc::new(UInt64Array::from(vec![1, 2]))]);
+        let ids = evaluate(ElementFunction::Id, &chunk).into_array();
+        assert_eq!(
+            ids.as_primitive::<UInt64Type>().values().to_vec(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let names = HashMap::from([(LabelId::new(1).unwrap(), "Person".to_string())]);
+        let labels = evaluate(ElementFunction::Labels(names), &vertices()).into_array();
+        let labels: &ListArray = labels.as_list();
+        assert_eq!(
+            ScalarValue::from_array(labels, 0),
+            vec![ScalarValue::from("Person")].into()
+        );
+        assert!(labels.is_null(1));
+    }
+
+    #[test]
+    fn test_properties() {
+        let properties = evaluate(ElementFunction::Properties, &vertices()).into_array();
+        let properties = properties.as_struct();
+        assert_eq!(properties.fields().len(), 1);
+        assert_eq!(properties.fields()[0].name(), "name");
+        assert_eq!(properties.column(0).as_string::<i32>().value(0), "alice");
+        assert!(properties.is_null(1));
+    }
+}
@@ -3,13 +3,14 @@
 
 use arrow::array::{Array, ArrayRef, AsArray};
 use arrow::compute::kernels::{boolean, cmp, numeric};
+use arrow::compute::{is_not_null, is_null};
 use minigu_common::data_chunk::DataChunk;
 use minigu_common::result_set::{DataPos, ResultSet};
 use minigu_common::value::ScalarValue;
 
 use crate::error::ExecutionResult;
 use crate::evaluator::DatumRef;
-use crate::evaluator::binary::BinaryOp;
+use crate::evaluator::binary::{BinaryOp, bitwise};
 use crate::evaluator::unary::UnaryOp;
 
 pub trait FactorizedEvaluator: Debug {
@@ -159,6 +160,8 @@ fn evaluate(&self, result_set: &ResultSet) -> ExecutionResult<DataChunk> {
                 let operand = operand_col.as_boolean();
                 Arc::new(boolean::not(operand)?)
             }
+            UnaryOp::IsNull => Arc::new(is_null(operand_col)?),
+            UnaryOp::IsNotNull => Arc::new(is_not_null(operand_col)?),
         };
 
         let mut result_chunk = DataChunk::new(vec![result_array]);
@@ -272,6 +275,11 @@ fn apply_op(&self, left: &DatumRef, right: &DatumRef) -> ExecutionResult<ArrayRe
             BinaryOp::Mul => numeric::mul(left, right)?,
             BinaryOp::Div => numeric::div(left, right)?,
             BinaryOp::Rem => numeric::rem(left, right)?,
+            BinaryOp::BitAnd
+            | BinaryOp::BitOr
+            | BinaryOp::BitXor
+            | BinaryOp::ShiftLeft
+            | BinaryOp::ShiftRight => bitwise(self.op, left, right)?,
             BinaryOp::And | BinaryOp::Or => {
                 let left_arr = left.as_array().as_boolean();
                 let right_arr = right.as_array().as_boolean();
@@ -287,6 +295,7 @@ fn apply_op(&self, left: &DatumRef, right: &DatumRef) -> ExecutionResult<ArrayRe
             BinaryOp::Ge => Arc::new(cmp::gt_eq(left, right)?),
             BinaryOp::Lt => Arc::new(cmp::lt(left, right)?),
             BinaryOp::Le => Arc::new(cmp::lt_eq(left, right)?),
+            BinaryOp::IsNotDistinctFrom => Arc::new(cmp::not_distinct(left, right)?),
         };
         Ok(result)
     }
@@ -410,6 +419,26 @@ fn test_unflat_in_different_chunks() {
         assert_eq!(result.columns()[0].as_ref(), expected.as_ref());
     }
 
+    #[test]
+    fn test_flat_shift_left_unflat() {
+        let chunk1 = data_chunk!((Int64, [1, 2, 3]));
+        let mut chunk2 = data_chunk!((Int64, [0, 1, 4]));
+        chunk2.set_unflat();
+        let result_set = result_set!(chunk1, chunk2);
+
+        // flat << unflat: 1 << [0, 1, 4] = [1, 2, 16]
+        let evaluator = FactorizedBinary::new(
+            BinaryOp::ShiftLeft,
+            FactorizedDataRef::new(data_pos!(0, 0)),
+            FactorizedDataRef::new(data_pos!(1, 0)),
+        );
+
+        let result = evaluator.evaluate(&result_set).unwrap();
+        assert!(result.is_unflat());
+        let expected = create_array!(Int64, [1, 2, 16]);
+        assert_eq!(result.columns()[0].as_ref(), expected.as_ref());
+    }
+
     #[test]
     fn test_unary_neg() {
         let mut chunk = data_chunk!((Int32, [1, 2, 3]));
@@ -0,0 +1,81 @@
+use arrow::array::{Array, AsArray, make_array};
+use arrow::buffer::NullBuffer;
+use minigu_common::data_chunk::DataChunk;
+
+use super::{BoxedEvaluator, DatumRef, Evaluator};
+use crate::error::ExecutionResult;
+
+/// Evaluates the field at `index` of a column of records, laid out as struct arrays.
+///
+/// The field of a null record is null, even if the struct array holds a value for it.
+#[derive(Debug)]
+pub struct FieldAccess {
+    record: BoxedEvaluator,
+    index: usize,
+}
+
+impl FieldAccess {
+    pub fn new(record: BoxedEvaluator, index: usize) -> Self {
+        Self { record, index }
+    }
+}
+
+impl Evaluator for FieldAccess {
+    fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
+        let record = self.record.evaluate(chunk)?;
+        let is_scalar = record.is_scalar();
+        let records = record.as_array().as_struct();
+        let field = records.column(self.index);
+        let nulls = NullBuffer::union(records.nulls(), field.nulls());
+        let field = if nulls.as_ref() == field.nulls() {
+            field.clone()
+        } else {
+            make_array(field.to_data().into_builder().nulls(nulls).build()?)
+        };
+        Ok(DatumRef::new(field, is_scalar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Int64Array, StringArray};
+    use minigu_common::data_type::{DataField, LogicalType};
+    use minigu_common::value::{PropertyValue, ScalarValue};
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+
+    fn records() -> DataChunk {
+        let ty = LogicalType::Record(vec![
+            DataField::new("name".into(), LogicalType::String, true),
+            DataField::new("age".into(), LogicalType::Int64, true),
+        ]);
+        let mut builder = ScalarValue::to_array_builder(&ty, 3);
+        let record = |name: &str, age: Option<i64>| {
+            ScalarValue::from(vec![
+                PropertyValue::new("name".into(), name.into()),
+                PropertyValue::new("age".into(), age.into()),
+            ])
+        };
+        builder.append(record("alice", Some(30))).unwrap();
+        builder.append(record("bob", None)).unwrap();
+        builder.append(ScalarValue::Record(None)).unwrap();
+        DataChunk::new(vec![builder.finish()])
+    }
+
+    fn evaluate(index: usize) -> DatumRef {
+        FieldAccess::new(Box::new(ColumnRef::new(0)), index)
+            .evaluate(&records())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_field_access() {
+        let names = evaluate(0).into_array();
+        let expected = StringArray::from(vec![Some("alice"), Some("bob"), None]);
+        assert_eq!(names.as_string::<i32>(), &expected);
+        let ages = evaluate(1).into_array();
+        let expected = Int64Array::from(vec![Some(30), None, None]);
+        assert_eq!(ages.as_primitive(), &expected);
+    }
+}
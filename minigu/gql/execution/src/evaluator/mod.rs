@@ -1,8 +1,12 @@
 pub mod binary;
+pub mod cast;
 pub mod column_ref;
 pub mod constant;
 pub mod datum;
+pub mod element_function;
 pub mod factorized_evaluator;
+pub mod field_access;
+pub mod path_length;
 pub mod scalar_function;
 pub mod unary;
 pub mod vector_distance;
@@ -68,6 +72,46 @@ fn rem<E>(self, other: E) -> Binary<Self, E>
         Binary::new(BinaryOp::Rem, self, other)
     }
 
+    fn bit_and<E>(self, other: E) -> Binary<Self, E>
+    where
+        Self: Sized,
+        E: Evaluator,
+    {
+        Binary::new(BinaryOp::BitAnd, self, other)
+    }
+
+    fn bit_or<E>(self, other: E) -> Binary<Self, E>
+    where
+        Self: Sized,
+        E: Evaluator,
+    {
+        Binary::new(BinaryOp::BitOr, self, other)
+    }
+
+    fn bit_xor<E>(self, other: E) -> Binary<Self, E>
+    where
+        Self: Sized,
+        E: Evaluator,
+    {
+        Binary::new(BinaryOp::BitXor, self, other)
+    }
+
+    fn shift_left<E>(self, other: E) -> Binary<Self, E>
+    where
+        Self: Sized,
+        E: Evaluator,
+    {
+        Binary::new(BinaryOp::ShiftLeft, self, other)
+    }
+
+    fn shift_right<E>(self, other: E) -> Binary<Self, E>
+    where
+        Self: Sized,
+        E: Evaluator,
+    {
+        Binary::new(BinaryOp::ShiftRight, self, other)
+    }
+
     fn neg(self) -> Unary<Self>
     where
         Self: Sized,
@@ -82,6 +126,22 @@ fn not(self) -> Unary<Self>
         Unary::new(UnaryOp::Not, self)
     }
 
+    #[allow(clippy::wrong_self_convention)]
+    fn is_null(self) -> Unary<Self>
+    where
+        Self: Sized,
+    {
+        Unary::new(UnaryOp::IsNull, self)
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn is_not_null(self) -> Unary<Self>
+    where
+        Self: Sized,
+    {
+        Unary::new(UnaryOp::IsNotNull, self)
+    }
+
     fn and<E>(self, other: E) -> Binary<Self, E>
     where
         Self: Sized,
@@ -145,6 +205,17 @@ fn le<E>(self, other: E) -> Binary<Self, E>
     {
         Binary::new(BinaryOp::Le, self, other)
     }
+
+    /// Compares `self` and `other` like [`eq`](Self::eq), except that the result is never null:
+    /// two nulls are equal, and a null differs from any other value.
+    #[allow(clippy::wrong_self_convention)]
+    fn is_not_distinct_from<E>(self, other: E) -> Binary<Self, E>
+    where
+        Self: Sized,
+        E: Evaluator,
+    {
+        Binary::new(BinaryOp::IsNotDistinctFrom, self, other)
+    }
 }
 
 impl<E> Evaluator for Box<E>
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use arrow::array::{Array, AsArray, Int64Array};
+use minigu_common::data_chunk::DataChunk;
+
+use super::{BoxedEvaluator, DatumRef, Evaluator};
+use crate::error::ExecutionResult;
+
+/// Evaluates the lengths, i.e., the numbers of edges, of a column of paths, laid out as in
+/// [`LogicalType::Path`](minigu_common::data_type::LogicalType::Path).
+#[derive(Debug)]
+pub struct PathLength {
+    path: BoxedEvaluator,
+}
+
+impl PathLength {
+    pub fn new(path: BoxedEvaluator) -> Self {
+        Self { path }
+    }
+}
+
+impl Evaluator for PathLength {
+    fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
+        let path = self.path.evaluate(chunk)?;
+        let is_scalar = path.is_scalar();
+        let paths = path.as_array().as_struct();
+        let edges = paths.column(1).as_list::<i32>();
+        let lengths = edges.offsets().lengths().map(|l| l as i64).collect();
+        let lengths = Int64Array::new(lengths, paths.nulls().cloned());
+        Ok(DatumRef::new(Arc::new(lengths), is_scalar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::ListArray;
+    use arrow::datatypes::UInt64Type;
+    use minigu_common::constants::{EID_FIELD_NAME, VID_FIELD_NAME};
+    use minigu_common::data_type::LogicalType;
+    use minigu_common::types::LabelId;
+    use minigu_common::value::{EdgeValue, PathValue, ScalarValue, VertexValue};
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+    use crate::evaluator::field_access::FieldAccess;
+
+    /// A 2-hop path from vertex 1 to vertex 3 through vertex 2, and a null path.
+    fn paths() -> DataChunk {
+        let label = LabelId::new(1).unwrap();
+        let vertex = |id| VertexValue::new(id, label, vec![]);
+        let path = PathValue::new(
+            vec![vertex(1), vertex(2), vertex(3)],
+            vec![
+                EdgeValue::new(10, 1, 2, label, vec![]),
+                EdgeValue::new(11, 2, 3, label, vec![]),
+            ],
+        );
+        let ty = LogicalType::Path(Box::default());
+        let mut builder = ScalarValue::to_array_builder(&ty, 2);
+        builder.append(path.into()).unwrap();
+        builder.append(ScalarValue::Path(None)).unwrap();
+        DataChunk::new(vec![builder.finish()])
+    }
+
+    /// Returns the ids of the elements of the list at `index` of `lists`, read from `id_field`.
+    fn ids(lists: &ListArray, index: usize, id_field: &str) -> Vec<u64> {
+        let elements = lists.value(index);
+        let elements = elements.as_struct();
+        let ids = elements.column_by_name(id_field).unwrap();
+        ids.as_primitive::<UInt64Type>().values().to_vec()
+    }
+
+    #[test]
+    fn test_path_length() {
+        let lengths = PathLength::new(Box::new(ColumnRef::new(0)))
+            .evaluate(&paths())
+            .unwrap()
+            .into_array();
+        let expected = Int64Array::from(vec![Some(2), None]);
+        assert_eq!(lengths.as_primitive(), &expected);
+    }
+
+    #[test]
+    fn test_path_elements() {
+        let chunk = paths();
+        let nodes = FieldAccess::new(Box::new(ColumnRef::new(0)), 0)
+            .evaluate(&chunk)
+            .unwrap()
+            .into_array();
+        let nodes = nodes.as_list();
+        assert_eq!(ids(nodes, 0, VID_FIELD_NAME), vec![1, 2, 3]);
+        assert!(nodes.is_null(1));
+        let relationships = FieldAccess::new(Box::new(ColumnRef::new(0)), 1)
+            .evaluate(&chunk)
+            .unwrap()
+            .into_array();
+        let relationships = relationships.as_list();
+        assert_eq!(ids(relationships, 0, EID_FIELD_NAME), vec![10, 11]);
+        assert!(relationships.is_null(1));
+    }
+}
@@ -3,6 +3,7 @@
 use arrow::array::AsArray;
 use arrow::compute::kernels::{boolean, numeric};
 use minigu_common::data_chunk::DataChunk;
+use minigu_common::nulls;
 
 use super::{DatumRef, Evaluator};
 use crate::error::ExecutionResult;
@@ -11,6 +12,8 @@
 pub enum UnaryOp {
     Neg,
     Not,
+    IsNull,
+    IsNotNull,
 }
 
 #[derive(Debug)]
@@ -34,6 +37,9 @@ fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
                 let operand = operand.as_array().as_boolean();
                 Arc::new(boolean::not(operand)?)
             }
+            // Only the null bitmap of the operand is consulted, so these work for any data type.
+            UnaryOp::IsNull => Arc::new(nulls::is_null_mask(operand.as_array())),
+            UnaryOp::IsNotNull => Arc::new(nulls::is_not_null_mask(operand.as_array())),
         };
         Ok(DatumRef::new(array, operand.is_scalar()))
     }
@@ -64,4 +70,22 @@ fn test_unary_not() {
         let expected: ArrayRef = create_array!(Boolean, [None, Some(false), Some(true)]);
         assert_eq!(result.as_array(), &expected);
     }
+
+    #[test]
+    fn test_unary_is_null() {
+        let chunk = data_chunk!((Utf8, [Some("a"), None, Some("c")]));
+        let e = ColumnRef::new(0).is_null();
+        let result = e.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Boolean, [false, true, false]);
+        assert_eq!(result.as_array(), &expected);
+    }
+
+    #[test]
+    fn test_unary_is_not_null() {
+        let chunk = data_chunk!((Int32, [Some(1), None, Some(3)]));
+        let e = ColumnRef::new(0).is_not_null();
+        let result = e.evaluate(&chunk).unwrap();
+        let expected: ArrayRef = create_array!(Boolean, [true, false, true]);
+        assert_eq!(result.as_array(), &expected);
+    }
 }
@@ -1,16 +1,22 @@
 use std::collections::HashMap;
+use std::fs::File;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::mem;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, Float32Array, Float64Array, Int64Array, StringArray};
+use arrow::array::{ArrayRef, Float32Array, Float64Array, Int64Array, StringArray, new_null_array};
+use arrow::datatypes::DataType;
 use minigu_common::data_chunk::DataChunk;
 use minigu_common::value::{ScalarValue, ScalarValueAccessor};
+use minigu_context::function::{UserAggregateRef, UserAggregateState};
 
 use super::utils::gen_try;
 use super::{Executor, IntoExecutor};
 use crate::error::ExecutionResult;
 use crate::evaluator::BoxedEvaluator;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub enum AggregateFunction {
     /// COUNT(*)
     Count,
@@ -24,14 +30,18 @@ pub enum AggregateFunction {
     Min,
     /// MAX(expr)
     Max,
+    /// A user-defined aggregate over expr
+    User(UserAggregateRef),
 }
 
 /// Aggregate specification, defines the aggregate function and its parameters
 #[derive(Debug)]
 pub struct AggregateSpec {
-    function: AggregateFunction,
-    expression: Option<BoxedEvaluator>,
-    distinct: bool,
+    pub(super) function: AggregateFunction,
+    pub(super) expression: Option<BoxedEvaluator>,
+    pub(super) distinct: bool,
+    /// The type of the result, if known.
+    pub(super) output_type: Option<DataType>,
 }
 
 impl AggregateSpec {
@@ -41,6 +51,7 @@ pub fn count() -> Self {
             function: AggregateFunction::Count,
             expression: None,
             distinct: false,
+            output_type: None,
         }
     }
 
@@ -50,6 +61,7 @@ pub fn count_expression(expr: BoxedEvaluator, distinct: bool) -> Self {
             function: AggregateFunction::CountExpression,
             expression: Some(expr),
             distinct,
+            output_type: None,
         }
     }
 
@@ -59,6 +71,7 @@ pub fn sum(expr: BoxedEvaluator, distinct: bool) -> Self {
             function: AggregateFunction::Sum,
             expression: Some(expr),
             distinct,
+            output_type: None,
         }
     }
 
@@ -68,6 +81,7 @@ pub fn avg(expr: BoxedEvaluator, distinct: bool) -> Self {
             function: AggregateFunction::Avg,
             expression: Some(expr),
             distinct,
+            output_type: None,
         }
     }
 
@@ -77,6 +91,7 @@ pub fn min(expr: BoxedEvaluator) -> Self {
             function: AggregateFunction::Min,
             expression: Some(expr),
             distinct: false,
+            output_type: None,
         }
     }
 
@@ -86,8 +101,25 @@ pub fn max(expr: BoxedEvaluator) -> Self {
             function: AggregateFunction::Max,
             expression: Some(expr),
             distinct: false,
+            output_type: None,
         }
     }
+
+    /// Create a user-defined aggregate specification over expr
+    pub fn user(aggregate: UserAggregateRef, expr: BoxedEvaluator) -> Self {
+        Self {
+            function: AggregateFunction::User(aggregate),
+            expression: Some(expr),
+            distinct: false,
+            output_type: None,
+        }
+    }
+
+    /// Sets the type of the result, which is that of the NULL returned over no rows.
+    pub fn with_output_type(mut self, output_type: DataType) -> Self {
+        self.output_type = Some(output_type);
+        self
+    }
 }
 
 /// Aggregate state for storing intermediate results during aggregation
@@ -120,6 +152,10 @@ pub enum AggregateState {
         max_f64: Option<f64>,
         max_string: Option<String>,
     },
+    User {
+        aggregate: UserAggregateRef,
+        state: UserAggregateState,
+    },
 }
 
 impl AggregateState {
@@ -151,6 +187,10 @@ pub fn new(func: &AggregateFunction, distinct: bool) -> Self {
                 max_f64: None,
                 max_string: None,
             },
+            AggregateFunction::User(aggregate) => Self::User {
+                aggregate: aggregate.clone(),
+                state: aggregate.init(),
+            },
         }
     }
 
@@ -217,6 +257,13 @@ pub fn update(&mut self, value: Option<ScalarValue>) -> ExecutionResult<()> {
                     }
                 }
             }
+            AggregateState::User { aggregate, state } => {
+                if let Some(val) = value {
+                    if !is_null_value(&val) {
+                        aggregate.accumulate(state.as_mut(), &val)?;
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -668,6 +715,8 @@ pub fn finalize(&self) -> ExecutionResult<ScalarValue> {
                 }
                 Ok(ScalarValue::Null)
             }
+
+            AggregateState::User { aggregate, state } => Ok(aggregate.finalize(state.as_ref())?),
         }
     }
 }
@@ -691,6 +740,9 @@ pub fn is_null_value(value: &ScalarValue) -> bool {
             | ScalarValue::String(None)
             | ScalarValue::Vertex(None)
             | ScalarValue::Edge(None)
+            | ScalarValue::List(None)
+            | ScalarValue::Record(None)
+            | ScalarValue::Path(None)
     )
 }
 
@@ -772,6 +824,27 @@ macro_rules! handle_scalar_types {
     )
 }
 
+/// Returns the result of `spec` over no rows: 0 for `COUNT`, the result of the initial state for
+/// user-defined aggregates, and NULL otherwise.
+fn empty_aggregate_result(spec: &AggregateSpec) -> ExecutionResult<ArrayRef> {
+    let result: ArrayRef = match &spec.function {
+        AggregateFunction::Count | AggregateFunction::CountExpression => {
+            Arc::new(Int64Array::from(vec![Some(0i64)]))
+        }
+        // AVG always returns a Float64.
+        AggregateFunction::Avg => Arc::new(Float64Array::from(vec![None::<f64>])),
+        AggregateFunction::User(aggregate) => {
+            let state = aggregate.init();
+            aggregate.finalize(state.as_ref())?.to_scalar_array()
+        }
+        _ => match &spec.output_type {
+            Some(output_type) => new_null_array(output_type, 1),
+            None => Arc::new(Int64Array::from(vec![None::<i64>])),
+        },
+    };
+    Ok(result)
+}
+
 /// Aggregate operator builder
 #[derive(Debug)]
 pub struct AggregateBuilder<E> {
@@ -779,6 +852,7 @@ pub struct AggregateBuilder<E> {
     aggregate_specs: Vec<AggregateSpec>,
     group_by_expressions: Vec<BoxedEvaluator>,
     output_expressions: Vec<BoxedEvaluator>, // Expressions like `1 + COUNT(*)`
+    memory_limit: Option<usize>,
 }
 
 impl<E> AggregateBuilder<E> {
@@ -798,8 +872,22 @@ pub fn new(
             aggregate_specs,
             group_by_expressions,
             output_expressions,
+            memory_limit: None,
         }
     }
+
+    /// Limits the memory used by the groups of grouped aggregation to about `memory_limit` bytes.
+    ///
+    /// The memory of a group is estimated as that of its key, as a row of a [`DataChunk`] (see
+    /// [`DataChunk::num_bytes`]), plus that of its aggregate states. Once the groups held in
+    /// memory reach the limit, rows of new groups are spilled to temporary files, partitioned by
+    /// the hash of their group keys. The partitions are aggregated one by one after the input is
+    /// exhausted, spilling again if needed.
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self {
+        assert_ne!(memory_limit, 0, "memory limit must be positive");
+        self.memory_limit = Some(memory_limit);
+        self
+    }
 }
 
 impl<E> IntoExecutor for AggregateBuilder<E>
@@ -815,6 +903,7 @@ fn into_executor(self) -> Self::IntoExecutor {
                 aggregate_specs,
                 group_by_expressions,
                 output_expressions,
+                memory_limit,
             } = self;
 
             // If there is no grouping expression, perform simple aggregation
@@ -834,10 +923,9 @@ fn into_executor(self) -> Self::IntoExecutor {
                         continue;
                     }
 
-                    has_data = true;
-
                     // Process each row of the current chunk directly
                     for row in chunk.rows() {
+                        has_data = true;
                         for (i, spec) in aggregate_specs.iter().enumerate() {
                             // If there is an expression, evaluate it for the current row
                             let value = if let Some(ref expr) = spec.expression {
@@ -861,31 +949,16 @@ fn into_executor(self) -> Self::IntoExecutor {
                     }
                 }
 
-                // If there is no data, return the default aggregate result
-                if !has_data {
-                    let mut result_columns = Vec::new();
-                    for spec in &aggregate_specs {
-                        let default_value = match spec.function {
-                            AggregateFunction::Count | AggregateFunction::CountExpression => {
-                                // For COUNT(*) and COUNT(expr), return 0 if there is no data
-                                Arc::new(Int64Array::from(vec![Some(0i64)])) as ArrayRef
-                            }
-                            // For other aggregate functions, return NULL if there is no data
-                            _ => Arc::new(Int64Array::from(vec![None::<i64>])) as ArrayRef,
-                        };
-                        result_columns.push(default_value);
-                    }
-                    if !result_columns.is_empty() {
-                        yield Ok(DataChunk::new(result_columns));
-                    }
-                    return;
-                }
-
-                // Generate the final result
+                // Generate the final result. Without any input row, this is the identity row of
+                // the aggregate functions.
                 let mut result_columns = Vec::new();
-                for (i, _spec) in aggregate_specs.iter().enumerate() {
-                    let final_value = gen_try!(states[i].finalize());
-                    result_columns.push(final_value.to_scalar_array());
+                for (spec, state) in aggregate_specs.iter().zip(&states) {
+                    let column = if has_data {
+                        gen_try!(state.finalize()).to_scalar_array()
+                    } else {
+                        gen_try!(empty_aggregate_result(spec))
+                    };
+                    result_columns.push(column);
                 }
 
                 // Apply output expressions if any
@@ -904,8 +977,7 @@ fn into_executor(self) -> Self::IntoExecutor {
                 yield Ok(DataChunk::new(result_columns));
             } else {
                 // Grouped aggregation
-                let mut groups: HashMap<Vec<ScalarValue>, Vec<AggregateState>> = HashMap::new();
-                let mut has_data = false;
+                let mut table = GroupTable::new(&aggregate_specs, memory_limit, 0);
 
                 // Stream processing each chunk to avoid performance overhead of concat
                 for chunk in child.into_iter() {
@@ -914,102 +986,59 @@ fn into_executor(self) -> Self::IntoExecutor {
                         continue;
                     }
 
-                    has_data = true;
-
                     for row in chunk.rows() {
+                        // Create a single row data chunk for the current row
+                        let row_columns: Vec<ArrayRef> = chunk
+                            .columns()
+                            .iter()
+                            .map(|col| col.slice(row.row_index(), 1))
+                            .collect();
+                        let row_chunk = DataChunk::new(row_columns);
+
                         // Calculate the group key using original ScalarValue
                         let mut group_key = Vec::new();
                         for group_expr in &group_by_expressions {
-                            // Create a single row data chunk for the current row
-                            let row_columns: Vec<ArrayRef> = chunk
-                                .columns()
-                                .iter()
-                                .map(|col| col.slice(row.row_index(), 1))
-                                .collect();
-                            let row_chunk = DataChunk::new(row_columns);
                             let result = gen_try!(group_expr.evaluate(&row_chunk));
-                            let scalar_value = result.as_array().as_ref().index(0);
-                            // Push the original ScalarValue to the group key
-                            group_key.push(scalar_value);
+                            group_key.push(result.as_array().as_ref().index(0));
                         }
 
-                        // Get or create the state for this group
-                        let states = groups.entry(group_key).or_insert_with(|| {
-                            aggregate_specs
-                                .iter()
-                                .map(|spec| AggregateState::new(&spec.function, spec.distinct))
-                                .collect()
-                        });
-
-                        // Update the aggregate state for the current row
-                        for (i, spec) in aggregate_specs.iter().enumerate() {
+                        // Calculate the input of each aggregate function
+                        let mut values = Vec::new();
+                        for spec in &aggregate_specs {
                             let value = if let Some(ref expr) = spec.expression {
-                                // Create a single row data chunk for the current row
-                                let row_columns: Vec<ArrayRef> = chunk
-                                    .columns()
-                                    .iter()
-                                    .map(|col| col.slice(row.row_index(), 1))
-                                    .collect();
-                                let row_chunk = DataChunk::new(row_columns);
                                 let result = gen_try!(expr.evaluate(&row_chunk));
-                                let scalar_value = result.as_array().as_ref().index(0);
-                                Some(scalar_value)
+                                result.as_array().as_ref().index(0)
                             } else {
-                                Some(ScalarValue::Int64(Some(1))) // COUNT(*)
+                                ScalarValue::Int64(Some(1)) // COUNT(*)
                             };
-
-                            gen_try!(states[i].update(value));
+                            values.push(value);
                         }
+
+                        gen_try!(table.insert(group_key, values));
                     }
                 }
 
-                // Generate the final result
-                if has_data && !groups.is_empty() {
-                    // [0, group_by_expressions.len() - 1] is group by columns like `id`, `name`
-                    // [group_by_expressions.len(), group_by_expressions.len() +
-                    // aggregate_specs.len() - 1] is aggregate columns like `SUM(expr)`, `AVG(expr)`
-                    let mut result_columns: Vec<Vec<ScalarValue>> =
-                        vec![Vec::new(); group_by_expressions.len() + aggregate_specs.len()];
-
-                    for (group_key, states) in groups {
-                        // Add the original group key values directly
-                        for (i, scalar_value) in group_key.into_iter().enumerate() {
-                            result_columns[i].push(scalar_value);
-                        }
-
-                        // Add aggregate results
-                        for (i, _spec) in aggregate_specs.iter().enumerate() {
-                            let final_value = gen_try!(states[i].finalize());
-                            result_columns[group_by_expressions.len() + i].push(final_value);
-                        }
+                // Groups are emitted partition by partition, so that only the groups of one
+                // partition are held in memory at a time.
+                let mut pending = Vec::new();
+                loop {
+                    let (groups, partitions) = table.into_parts();
+                    pending.extend(partitions);
+                    if let Some(chunk) = gen_try!(finish_groups(
+                        groups,
+                        group_by_expressions.len(),
+                        &output_expressions
+                    )) {
+                        yield Ok(chunk);
                     }
-
-                    // Convert to ArrayRef
-                    let mut arrays: Vec<ArrayRef> = result_columns
-                        .into_iter()
-                        .map(|col| {
-                            if col.is_empty() {
-                                Arc::new(Int64Array::from(Vec::<Option<i64>>::new())) as ArrayRef
-                            } else {
-                                scalar_values_to_array(col)
-                            }
-                        })
-                        .collect();
-
-                    // Apply output expressions if any
-                    if !output_expressions.is_empty() {
-                        let mut output_arrays: Vec<ArrayRef> = Vec::new();
-                        for expr in output_expressions {
-                            // Create a data chunk with the aggregate results
-                            let agg_chunk = DataChunk::new(arrays.clone());
-                            // Evaluate the output expression
-                            let result = gen_try!(expr.evaluate(&agg_chunk));
-                            output_arrays.push(result.as_array().clone());
-                        }
-                        arrays = output_arrays;
+                    let Some((partition, depth)) = pending.pop() else {
+                        break;
+                    };
+                    table = GroupTable::new(&aggregate_specs, memory_limit, depth);
+                    for row in gen_try!(partition.into_rows()) {
+                        let (group_key, values) = gen_try!(row);
+                        gen_try!(table.insert(group_key, values));
                     }
-
-                    yield Ok(DataChunk::new(arrays));
                 }
             }
         }
@@ -1017,12 +1046,206 @@ fn into_executor(self) -> Self::IntoExecutor {
     }
 }
 
+/// The number of partitions rows are spilled into.
+const NUM_SPILL_PARTITIONS: u64 = 8;
+
+/// The maximum number of times a group can be spilled. Groups of partitions at this depth are
+/// aggregated in memory regardless of the budget, so that skewed partitions are not spilled
+/// forever.
+const MAX_SPILL_DEPTH: usize = 3;
+
+/// Group keys and aggregate inputs of a row.
+type GroupRow = (Vec<ScalarValue>, Vec<ScalarValue>);
+
+/// The aggregate states of each group, by group keys.
+type Groups = HashMap<Vec<ScalarValue>, Vec<AggregateState>>;
+
+/// The hash table of grouped aggregation, spilling rows of new groups once it is full.
+struct GroupTable<'a> {
+    specs: &'a [AggregateSpec],
+    groups: Groups,
+    memory_limit: Option<usize>,
+    /// The estimated memory used by `groups`, in bytes.
+    num_bytes: usize,
+    depth: usize,
+    partitions: Vec<SpillFile>,
+}
+
+impl<'a> GroupTable<'a> {
+    fn new(specs: &'a [AggregateSpec], memory_limit: Option<usize>, depth: usize) -> Self {
+        Self {
+            specs,
+            groups: HashMap::new(),
+            memory_limit,
+            num_bytes: 0,
+            depth,
+            partitions: Vec::new(),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        group_key: Vec<ScalarValue>,
+        values: Vec<ScalarValue>,
+    ) -> ExecutionResult<()> {
+        if !self.groups.contains_key(&group_key) {
+            if self.is_full() {
+                return self.spill(group_key, values);
+            }
+            self.num_bytes += group_num_bytes(&group_key, self.specs.len());
+        }
+        let specs = self.specs;
+        let states = self.groups.entry(group_key).or_insert_with(|| {
+            specs
+                .iter()
+                .map(|spec| AggregateState::new(&spec.function, spec.distinct))
+                .collect()
+        });
+        for (state, value) in states.iter_mut().zip(values) {
+            state.update(Some(value))?;
+        }
+        Ok(())
+    }
+
+    fn is_full(&self) -> bool {
+        self.depth < MAX_SPILL_DEPTH
+            && self
+                .memory_limit
+                .is_some_and(|memory_limit| self.num_bytes >= memory_limit)
+    }
+
+    fn spill(
+        &mut self,
+        group_key: Vec<ScalarValue>,
+        values: Vec<ScalarValue>,
+    ) -> ExecutionResult<()> {
+        if self.partitions.is_empty() {
+            self.partitions = (0..NUM_SPILL_PARTITIONS)
+                .map(|_| SpillFile::new())
+                .collect::<io::Result<_>>()?;
+        }
+        // Salting the hash with the depth splits the groups of a spilled partition differently.
+        let mut hasher = DefaultHasher::new();
+        (self.depth, &group_key).hash(&mut hasher);
+        let partition = (hasher.finish() % NUM_SPILL_PARTITIONS) as usize;
+        self.partitions[partition].write(&(group_key, values))
+    }
+
+    /// Returns the groups held in memory, and the non-empty spilled partitions with the depth to
+    /// aggregate them at.
+    fn into_parts(self) -> (Groups, Vec<(SpillFile, usize)>) {
+        let depth = self.depth + 1;
+        let partitions = self
+            .partitions
+            .into_iter()
+            .filter(|p| p.num_rows > 0)
+            .map(|p| (p, depth))
+            .collect();
+        (self.groups, partitions)
+    }
+}
+
+/// Returns the estimated memory used by a group of key `group_key` with `num_states` aggregate
+/// states, in bytes.
+fn group_num_bytes(group_key: &[ScalarValue], num_states: usize) -> usize {
+    let key = DataChunk::new(group_key.iter().map(ScalarValue::to_scalar_array).collect());
+    key.num_bytes() + num_states * mem::size_of::<AggregateState>()
+}
+
+/// A temporary file of spilled rows, deleted once dropped.
+struct SpillFile {
+    writer: BufWriter<File>,
+    num_rows: usize,
+}
+
+impl SpillFile {
+    fn new() -> io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(tempfile::tempfile()?),
+            num_rows: 0,
+        })
+    }
+
+    fn write(&mut self, row: &GroupRow) -> ExecutionResult<()> {
+        let bytes = postcard::to_allocvec(row).map_err(|e| io::Error::other(e.to_string()))?;
+        self.writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        self.writer.write_all(&bytes)?;
+        self.num_rows += 1;
+        Ok(())
+    }
+
+    fn into_rows(self) -> ExecutionResult<impl Iterator<Item = ExecutionResult<GroupRow>>> {
+        let mut file = self.writer.into_inner().map_err(|e| e.into_error())?;
+        file.seek(SeekFrom::Start(0))?;
+        let mut reader = BufReader::new(file);
+        let rows = (0..self.num_rows).map(move |_| {
+            let mut len = [0; 8];
+            reader.read_exact(&mut len)?;
+            let mut bytes = vec![0; u64::from_le_bytes(len) as usize];
+            reader.read_exact(&mut bytes)?;
+            let row = postcard::from_bytes(&bytes).map_err(|e| io::Error::other(e.to_string()))?;
+            Ok(row)
+        });
+        Ok(rows)
+    }
+}
+
+/// Builds a chunk of the group keys and aggregate results of `groups`, followed by the output
+/// expressions if any. Returns `None` if there are no groups.
+fn finish_groups(
+    groups: Groups,
+    num_keys: usize,
+    output_expressions: &[BoxedEvaluator],
+) -> ExecutionResult<Option<DataChunk>> {
+    let Some(num_states) = groups.values().next().map(Vec::len) else {
+        return Ok(None);
+    };
+    // [0, num_keys - 1] is group by columns like `id`, `name`
+    // [num_keys, num_keys + num_states - 1] is aggregate columns like `SUM(expr)`, `AVG(expr)`
+    let mut result_columns: Vec<Vec<ScalarValue>> = vec![Vec::new(); num_keys + num_states];
+
+    for (group_key, states) in groups {
+        // Add the original group key values directly
+        for (i, scalar_value) in group_key.into_iter().enumerate() {
+            result_columns[i].push(scalar_value);
+        }
+
+        // Add aggregate results
+        for (i, state) in states.iter().enumerate() {
+            result_columns[num_keys + i].push(state.finalize()?);
+        }
+    }
+
+    // Convert to ArrayRef
+    let mut arrays: Vec<ArrayRef> = result_columns
+        .into_iter()
+        .map(scalar_values_to_array)
+        .collect();
+
+    // Apply output expressions if any
+    if !output_expressions.is_empty() {
+        let mut output_arrays: Vec<ArrayRef> = Vec::new();
+        for expr in output_expressions {
+            // Create a data chunk with the aggregate results
+            let agg_chunk = DataChunk::new(arrays.clone());
+            // Evaluate the output expression
+            let result = expr.evaluate(&agg_chunk)?;
+            output_arrays.push(result.as_array().clone());
+        }
+        arrays = output_arrays;
+    }
+
+    Ok(Some(DataChunk::new(arrays)))
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
     use minigu_common::data_chunk;
     use minigu_common::data_chunk::DataChunk;
+    use minigu_common::data_type::LogicalType;
     use minigu_common::value::F64;
+    use minigu_context::function::{Aggregate, FunctionRegistry, UserAggregate};
 
     use super::*;
     use crate::evaluator::Evaluator;
@@ -1330,6 +1553,139 @@ fn test_group_by_aggregate() {
         }
     }
 
+    /// The product of integers, as a user-defined aggregate.
+    struct Product;
+
+    impl Aggregate for Product {
+        type State = i64;
+
+        fn input_type(&self) -> LogicalType {
+            LogicalType::Int64
+        }
+
+        fn return_type(&self) -> LogicalType {
+            LogicalType::Int64
+        }
+
+        fn init(&self) -> i64 {
+            1
+        }
+
+        fn accumulate(
+            &self,
+            state: &mut i64,
+            value: &ScalarValue,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            *state *= value.to_i64()?;
+            Ok(())
+        }
+
+        fn merge(
+            &self,
+            state: &mut i64,
+            other: i64,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            *state *= other;
+            Ok(())
+        }
+
+        fn finalize(
+            &self,
+            state: &i64,
+        ) -> Result<ScalarValue, Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Ok(ScalarValue::Int64(Some(*state)))
+        }
+    }
+
+    #[test]
+    fn test_group_by_user_aggregate() {
+        let registry = FunctionRegistry::default();
+        assert!(
+            registry.register_aggregate("product".into(), Arc::new(UserAggregate::new(Product)))
+        );
+        let product = registry.get_aggregate("product").unwrap();
+
+        // The null value is skipped.
+        let chunk = data_chunk!(
+            (Int32, [1, 1, 2, 2, 1]),
+            (Int64, [Some(2), Some(3), Some(4), None, Some(5)])
+        );
+        let result: DataChunk = [Ok(chunk)]
+            .into_executor()
+            .aggregate(
+                vec![AggregateSpec::user(
+                    product.clone(),
+                    Box::new(ColumnRef::new(1)),
+                )],
+                vec![Box::new(ColumnRef::new(0))],
+                vec![],
+            )
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let groups: Vec<_> = result
+            .rows()
+            .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+            .sorted_by_key(|(key, _)| key.to_i64().unwrap())
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                (ScalarValue::Int32(Some(1)), ScalarValue::Int64(Some(30))),
+                (ScalarValue::Int32(Some(2)), ScalarValue::Int64(Some(4))),
+            ]
+        );
+
+        // States of the same group aggregated separately are merged.
+        let mut state = product.init();
+        product
+            .accumulate(state.as_mut(), &ScalarValue::Int64(Some(2)))
+            .unwrap();
+        let mut other = product.init();
+        product
+            .accumulate(other.as_mut(), &ScalarValue::Int64(Some(7)))
+            .unwrap();
+        product.merge(state.as_mut(), other).unwrap();
+        assert_eq!(
+            product.finalize(state.as_ref()).unwrap(),
+            ScalarValue::Int64(Some(14))
+        );
+    }
+
+    #[test]
+    fn test_group_by_dictionary_key() {
+        let mut chunk = data_chunk!(
+            (
+                Utf8,
+                [Some("person"), Some("city"), Some("person"), None, None]
+            ),
+            (Int32, [1, 2, 3, 4, 5])
+        );
+        chunk.dictionary_encode(0);
+
+        let result: DataChunk = [Ok(chunk)]
+            .into_executor()
+            .aggregate(
+                vec![AggregateSpec::sum(Box::new(ColumnRef::new(1)), false)],
+                vec![Box::new(ColumnRef::new(0))],
+                vec![],
+            )
+            .into_iter()
+            .try_collect()
+            .unwrap();
+
+        let groups: HashMap<_, _> = result
+            .rows()
+            .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+            .collect();
+        let expected: HashMap<ScalarValue, ScalarValue> = HashMap::from([
+            (ScalarValue::String(Some("person".into())), 4i64.into()),
+            (ScalarValue::String(Some("city".into())), 2i64.into()),
+            (ScalarValue::String(None), 9i64.into()),
+        ]);
+        assert_eq!(groups, expected);
+    }
+
     #[test]
     fn test_group_by_multiple_keys() {
         // Create test data: department, position, salary
@@ -1706,4 +2062,129 @@ fn test_avg_unified_f64_precision() {
         assert_eq!(result_columns.len(), 1);
         assert!(result_columns[0].as_any().is::<Float64Array>());
     }
+
+    #[test]
+    fn test_group_by_aggregate_with_spilling() {
+        // 100 groups of 3 rows each, spread over 3 chunks.
+        let input = (0..3).map(|i| {
+            let keys = arrow::array::Int32Array::from_iter_values(0..100);
+            let values = Int64Array::from_iter_values((0..100).map(|k| k * 10 + i % 2));
+            Ok(DataChunk::new(vec![Arc::new(keys), Arc::new(values)]))
+        });
+        let chunks: Vec<DataChunk> = AggregateBuilder::new(
+            input.into_executor(),
+            vec![
+                AggregateSpec::count(),                                 // COUNT(*)
+                AggregateSpec::sum(Box::new(ColumnRef::new(1)), false), // SUM(value)
+                AggregateSpec::count_expression(Box::new(ColumnRef::new(1)), true), // COUNT(DISTINCT value)
+            ],
+            vec![Box::new(ColumnRef::new(0))],
+            vec![],
+        )
+        .with_memory_limit(1024)
+        .into_executor()
+        .into_iter()
+        .try_collect()
+        .unwrap();
+
+        // Without spilling, all groups would be emitted in a single chunk.
+        assert!(chunks.len() > 1);
+        let rows = chunks
+            .iter()
+            .flat_map(|chunk| chunk.rows().map(|row| row.into_owned().into_inner()))
+            .sorted_by_key(|row| row[0].to_i64().unwrap())
+            .collect_vec();
+        assert_eq!(rows.len(), 100);
+        for (k, row) in (0..100).zip(rows) {
+            let expected = vec![
+                ScalarValue::Int32(Some(k as i32)),
+                ScalarValue::Int64(Some(3)),
+                ScalarValue::Int64(Some(k * 30 + 1)),
+                ScalarValue::Int64(Some(2)),
+            ];
+            assert_eq!(row, expected);
+        }
+    }
+
+    fn aggregate_empty_input(
+        aggregate_specs: Vec<AggregateSpec>,
+        group_by_expressions: Vec<BoxedEvaluator>,
+        output_expressions: Vec<BoxedEvaluator>,
+    ) -> Vec<DataChunk> {
+        // All rows of the only chunk are filtered out.
+        let chunk = data_chunk!({ false, false }, (Int32, [1, 2]));
+        [Ok(chunk)]
+            .into_executor()
+            .aggregate(aggregate_specs, group_by_expressions, output_expressions)
+            .into_iter()
+            .try_collect()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_count_empty_input() {
+        let result = aggregate_empty_input(
+            vec![
+                AggregateSpec::count(),
+                AggregateSpec::count_expression(Box::new(ColumnRef::new(0)), false),
+            ],
+            vec![],
+            vec![],
+        );
+        assert_eq!(result, [data_chunk!((Int64, [0]), (Int64, [0]))]);
+
+        let result: Vec<DataChunk> = std::iter::empty::<ExecutionResult<DataChunk>>()
+            .into_executor()
+            .aggregate(vec![AggregateSpec::count()], vec![], vec![])
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(result, [data_chunk!((Int64, [0]))]);
+    }
+
+    #[test]
+    fn test_sum_avg_empty_input() {
+        let result = aggregate_empty_input(
+            vec![
+                AggregateSpec::sum(Box::new(ColumnRef::new(0)), false),
+                AggregateSpec::avg(Box::new(ColumnRef::new(0)), false),
+                AggregateSpec::max(Box::new(ColumnRef::new(0))),
+            ],
+            vec![],
+            vec![],
+        );
+        let expected = data_chunk!((Int64, [None]), (Float64, [None]), (Int64, [None]));
+        assert_eq!(result, [expected]);
+    }
+
+    #[test]
+    fn test_min_empty_input_with_output_type() {
+        let result = aggregate_empty_input(
+            vec![AggregateSpec::min(Box::new(ColumnRef::new(0))).with_output_type(DataType::Utf8)],
+            vec![],
+            vec![],
+        );
+        assert_eq!(result, [data_chunk!((Utf8, [None::<&str>]))]);
+    }
+
+    #[test]
+    fn test_output_expressions_empty_input() {
+        let add_ten = ColumnRef::new(0).add(Constant::new(ScalarValue::Int64(Some(10))));
+        let result = aggregate_empty_input(
+            vec![AggregateSpec::count()], // COUNT(*)
+            vec![],
+            vec![Box::new(add_ten)], // Output: COUNT(*) + 10
+        );
+        assert_eq!(result, [data_chunk!((Int64, [10]))]);
+    }
+
+    #[test]
+    fn test_group_by_empty_input() {
+        let result = aggregate_empty_input(
+            vec![AggregateSpec::count()],
+            vec![Box::new(ColumnRef::new(0))],
+            vec![],
+        );
+        assert!(result.is_empty());
+    }
 }
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use minigu_common::data_chunk::DataChunk;
+
+use super::Executor;
+use crate::error::{ExecutionError, ExecutionResult};
+
+/// Wraps an executor so that it stops once `cancelled` is set, returning
+/// [`ExecutionError::Cancelled`].
+///
+/// The flag is checked before pulling each chunk from the child, so wrapping the root of a plan is
+/// enough to cancel the whole query: the operators below only run while their parent pulls from
+/// them. A chunk that is being computed when the flag is set is still computed, but not returned.
+#[derive(Debug)]
+pub struct Cancellable<E> {
+    child: E,
+    cancelled: Arc<AtomicBool>,
+    done: bool,
+}
+
+impl<E> Cancellable<E> {
+    pub fn new(child: E, cancelled: Arc<AtomicBool>) -> Self {
+        Self {
+            child,
+            cancelled,
+            done: false,
+        }
+    }
+}
+
+impl<E: Executor> Executor for Cancellable<E> {
+    fn next_chunk(&mut self) -> Option<ExecutionResult<DataChunk>> {
+        if self.done {
+            return None;
+        }
+        if self.cancelled.load(Ordering::Relaxed) {
+            self.done = true;
+            return Some(Err(ExecutionError::Cancelled));
+        }
+        let chunk = self.child.next_chunk();
+        if !matches!(chunk, Some(Ok(_))) {
+            self.done = true;
+        }
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::data_chunk;
+
+    use super::*;
+    use crate::executor::IntoExecutor;
+
+    #[test]
+    fn test_cancellable() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut pulled = 0;
+        let child = std::iter::from_fn(|| {
+            pulled += 1;
+            Some(Ok(data_chunk!((Int32, [1, 2, 3]))))
+        })
+        .into_executor();
+        let mut executor = child.cancellable(cancelled.clone());
+        assert!(matches!(executor.next_chunk(), Some(Ok(_))));
+        assert!(matches!(executor.next_chunk(), Some(Ok(_))));
+        cancelled.store(true, Ordering::Relaxed);
+        assert!(matches!(
+            executor.next_chunk(),
+            Some(Err(ExecutionError::Cancelled))
+        ));
+        assert!(executor.next_chunk().is_none());
+        drop(executor);
+        // The child is not pulled from once the query is cancelled.
+        assert_eq!(pulled, 2);
+    }
+}
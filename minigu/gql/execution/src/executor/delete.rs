@@ -0,0 +1,161 @@
+use arrow::array::AsArray;
+use arrow::datatypes::UInt64Type;
+
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+use crate::error::ExecutionError;
+use crate::sink::GraphSink;
+
+/// Deletes the vertices in the given columns of each row of its input, and then outputs the input
+/// unchanged.
+///
+/// Without `detach`, deleting a vertex that still has edges fails, and nothing is committed. Like
+/// [`SetPropertiesBuilder`](super::set_properties::SetPropertiesBuilder), all input is consumed and
+/// the sink is committed before any chunk is output.
+#[derive(Debug)]
+pub struct DeleteBuilder<E, S> {
+    child: E,
+    input_column_indices: Vec<usize>,
+    detach: bool,
+    sink: S,
+}
+
+impl<E, S> DeleteBuilder<E, S> {
+    pub fn new(child: E, input_column_indices: Vec<usize>, detach: bool, sink: S) -> Self {
+        assert!(
+            !input_column_indices.is_empty(),
+            "at least one column to delete is required"
+        );
+        Self {
+            child,
+            input_column_indices,
+            detach,
+            sink,
+        }
+    }
+}
+
+impl<E, S> IntoExecutor for DeleteBuilder<E, S>
+where
+    E: Executor,
+    S: GraphSink,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let DeleteBuilder {
+                child,
+                input_column_indices,
+                detach,
+                sink,
+            } = self;
+            let mut chunks = Vec::new();
+            for chunk in child.into_iter() {
+                let mut chunk = gen_try!(chunk);
+                chunk.compact();
+                if chunk.is_empty() {
+                    continue;
+                }
+                for &index in &input_column_indices {
+                    let vertices = chunk.columns()[index].as_primitive::<UInt64Type>();
+                    // A null vertex is not bound, so there is nothing to delete.
+                    for vertex in vertices.iter().flatten() {
+                        if !detach && gen_try!(sink.has_edges(vertex)) {
+                            yield Err(ExecutionError::VertexHasEdges(vertex));
+                            return;
+                        }
+                        gen_try!(sink.delete_vertex(vertex));
+                    }
+                }
+                chunks.push(chunk);
+            }
+            gen_try!(sink.commit());
+            for chunk in chunks {
+                yield Ok(chunk);
+            }
+        }
+        .into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk;
+    use minigu_common::data_chunk::DataChunk;
+
+    use super::*;
+    use crate::error::ExecutionResult;
+    use crate::sink::mock::MockGraphSink;
+
+    /// Builds a sink with vertices 1 to 4 and edges 1->2 and 2->3.
+    fn build_test_sink() -> MockGraphSink {
+        let mut sink = MockGraphSink::new();
+        for vertex in 1..=4 {
+            sink.add_vertex(vertex, vec![]);
+        }
+        sink.add_edge(1, 2);
+        sink.add_edge(2, 3);
+        sink
+    }
+
+    fn delete(
+        chunk: DataChunk,
+        detach: bool,
+        sink: &MockGraphSink,
+    ) -> ExecutionResult<Vec<DataChunk>> {
+        [Ok(chunk)]
+            .into_executor()
+            .delete(vec![0], detach, sink)
+            .into_iter()
+            .try_collect()
+    }
+
+    #[test]
+    fn test_delete() {
+        let sink = build_test_sink();
+        let chunks = delete(data_chunk!({ false, true }, (UInt64, [3, 4])), false, &sink).unwrap();
+        assert_eq!(chunks, [data_chunk!((UInt64, [4]))]);
+        assert!(sink.is_committed());
+        assert!(!sink.contains_vertex(4));
+        // Filtered out, so not deleted.
+        assert!(sink.contains_vertex(3));
+        assert_eq!(sink.num_edges(), 2);
+    }
+
+    #[test]
+    fn test_detach_delete() {
+        let sink = build_test_sink();
+        let chunks = delete(data_chunk!((UInt64, [2])), true, &sink).unwrap();
+        assert_eq!(chunks, [data_chunk!((UInt64, [2]))]);
+        assert!(sink.is_committed());
+        assert!(!sink.contains_vertex(2));
+        assert_eq!(sink.num_edges(), 0);
+        assert!(sink.contains_vertex(1));
+        assert!(sink.contains_vertex(3));
+    }
+
+    #[test]
+    fn test_delete_vertex_with_edges() {
+        let sink = build_test_sink();
+        let result = delete(data_chunk!((UInt64, [4, 1])), false, &sink);
+        assert!(matches!(result, Err(ExecutionError::VertexHasEdges(1))));
+        assert!(!sink.is_committed());
+        assert!(sink.contains_vertex(1));
+        assert_eq!(sink.num_edges(), 2);
+    }
+
+    #[test]
+    fn test_delete_empty_input() {
+        let sink = build_test_sink();
+        let chunks: Vec<DataChunk> = std::iter::empty::<ExecutionResult<DataChunk>>()
+            .into_executor()
+            .delete(vec![0], false, &sink)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert!(chunks.is_empty());
+        assert!(sink.is_committed());
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use arrow::array::BooleanArray;
+
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+
+/// Builds an executor returning the rows of its child with duplicates removed.
+///
+/// The first occurrence of each row is kept, so the order of the child is preserved. Output chunks
+/// are compacted, so that downstream operators (e.g., limits) can count rows by their length.
+#[derive(Debug)]
+pub struct DistinctBuilder<E> {
+    child: E,
+}
+
+impl<E> DistinctBuilder<E> {
+    pub fn new(child: E) -> Self {
+        Self { child }
+    }
+}
+
+impl<E> IntoExecutor for DistinctBuilder<E>
+where
+    E: Executor,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let mut seen = HashSet::new();
+            for chunk in self.child.into_iter() {
+                let chunk = gen_try!(chunk);
+                let mut first = vec![false; chunk.len()];
+                for row in chunk.rows() {
+                    let index = row.row_index();
+                    first[index] = seen.insert(row.into_owned().into_inner());
+                }
+                let filter = BooleanArray::from(first);
+                match filter.true_count() {
+                    0 => (),
+                    true_count if true_count == chunk.len() => yield Ok(chunk.unfiltered()),
+                    _ => {
+                        let mut chunk = chunk.with_filter(filter);
+                        chunk.compact();
+                        yield Ok(chunk);
+                    }
+                }
+            }
+        }
+        .into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk;
+    use minigu_common::data_chunk::DataChunk;
+
+    use super::*;
+
+    #[test]
+    fn test_distinct() {
+        let chunk1 = data_chunk!(
+            (Int32, [1, 2, 1, 3]),
+            (Utf8, [Some("a"), Some("b"), Some("a"), None])
+        );
+        let chunk2 = data_chunk!((Int32, [3, 1, 4]), (Utf8, [None, Some("b"), Some("d")]));
+        let chunks: Vec<DataChunk> = [Ok(chunk1), Ok(chunk2)]
+            .into_executor()
+            .distinct()
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let expected = [
+            data_chunk!((Int32, [1, 2, 3]), (Utf8, [Some("a"), Some("b"), None])),
+            data_chunk!((Int32, [1, 4]), (Utf8, [Some("b"), Some("d")])),
+        ];
+        assert_eq!(chunks, expected);
+    }
+
+    #[test]
+    fn test_distinct_before_limit() {
+        let chunk = data_chunk!((Int32, [1, 1, 1, 2, 2, 3]));
+        let result: DataChunk = [Ok(chunk)]
+            .into_executor()
+            .distinct()
+            .limit(2)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(result, data_chunk!((Int32, [1, 2])));
+    }
+
+    #[test]
+    fn test_distinct_filtered_input() {
+        // Rows removed by the filter are neither returned nor remembered.
+        let chunk = data_chunk!({ false, true, true }, (Int32, [1, 1, 2]));
+        let result: DataChunk = [Ok(chunk)]
+            .into_executor()
+            .distinct()
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(result, data_chunk!((Int32, [1, 2])));
+    }
+}
@@ -34,14 +34,9 @@ fn into_executor(self) -> Self::IntoExecutor {
             } = self;
             for chunk in child.into_iter() {
                 let chunk = gen_try!(chunk);
-                let mut filter = gen_try!(predicate(&chunk));
-                if let Some(old_filter) = chunk.filter() {
-                    filter = gen_try!(boolean::and(old_filter, &filter));
-                }
-                match filter.true_count() {
-                    0 => (),
-                    true_count if true_count == chunk.len() => yield Ok(chunk.unfiltered()),
-                    _ => yield Ok(chunk.with_filter(filter)),
+                let filter = gen_try!(predicate(&chunk));
+                if let Some(chunk) = gen_try!(filter_chunk(chunk, filter)) {
+                    yield Ok(chunk);
                 }
             }
         }
@@ -49,11 +44,34 @@ fn into_executor(self) -> Self::IntoExecutor {
     }
 }
 
+/// Keeps the rows of `chunk` for which `filter` is true, on top of its existing filter.
+///
+/// Returns `None` if no row is kept.
+pub(crate) fn filter_chunk(
+    chunk: DataChunk,
+    mut filter: BooleanArray,
+) -> ExecutionResult<Option<DataChunk>> {
+    if let Some(old_filter) = chunk.filter() {
+        filter = boolean::and(old_filter, &filter)?;
+    }
+    let chunk = match filter.true_count() {
+        0 => None,
+        true_count if true_count == chunk.len() => Some(chunk.unfiltered()),
+        _ => Some(chunk.with_filter(filter)),
+    };
+    Ok(chunk)
+}
+
 #[cfg(test)]
 mod tests {
+    use arrow::array::{ArrayRef, AsArray, create_array};
     use minigu_common::data_chunk;
+    use minigu_common::value::ScalarValue;
 
     use super::*;
+    use crate::evaluator::Evaluator;
+    use crate::evaluator::column_ref::ColumnRef;
+    use crate::evaluator::constant::Constant;
 
     #[test]
     fn test_filter() {
@@ -77,4 +95,77 @@ fn test_filter() {
         let expected = data_chunk!((Int32, [Some(1), None]));
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_filter_by_property_presence() {
+        // Vertex ids along with a nullable property column, as produced by a property scan.
+        let chunk = data_chunk!(
+            (UInt64, [1, 2, 3, 4]),
+            (Utf8, [Some("alice"), None, Some("carol"), None])
+        );
+
+        let present = ColumnRef::new(1).is_not_null();
+        let mut result = [Ok(chunk.clone())]
+            .into_executor()
+            .filter(move |c| {
+                present
+                    .evaluate(c)
+                    .map(|a| a.into_array().as_boolean().clone())
+            })
+            .next_chunk()
+            .unwrap()
+            .unwrap();
+        result.compact();
+        let expected = data_chunk!((UInt64, [1, 3]), (Utf8, ["alice", "carol"]));
+        assert_eq!(result, expected);
+
+        let absent = ColumnRef::new(1).is_null();
+        let mut result = [Ok(chunk)]
+            .into_executor()
+            .filter(move |c| {
+                absent
+                    .evaluate(c)
+                    .map(|a| a.into_array().as_boolean().clone())
+            })
+            .next_chunk()
+            .unwrap()
+            .unwrap();
+        result.compact();
+        let expected = data_chunk!((UInt64, [2, 4]), (Utf8, [None::<&str>, None]));
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_filter_dictionary_column() {
+        let mut chunk = data_chunk!(
+            (UInt64, [1, 2, 3, 4]),
+            (Utf8, [Some("person"), Some("city"), None, Some("person")])
+        );
+        chunk.dictionary_encode(1);
+
+        let is_person = ColumnRef::new(1).eq(Constant::new("person".into()));
+        let mut result = [Ok(chunk)]
+            .into_executor()
+            .filter(move |c| {
+                is_person
+                    .evaluate(c)
+                    .map(|a| a.into_array().as_boolean().clone())
+            })
+            .next_chunk()
+            .unwrap()
+            .unwrap();
+        result.compact();
+        assert_eq!(
+            &result.columns()[0],
+            &(create_array!(UInt64, [1, 4]) as ArrayRef)
+        );
+        // The filtered column stays encoded.
+        let labels = result.columns()[1].as_any_dictionary();
+        assert_eq!(labels.keys().len(), 2);
+        let labels: Vec<_> = result.rows().map(|row| row.get(1).unwrap()).collect();
+        assert_eq!(
+            labels,
+            [ScalarValue::from("person"), ScalarValue::from("person")]
+        );
+    }
 }
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use arrow::array::UInt64Array;
+use minigu_common::types::LabelId;
+
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+use crate::evaluator::BoxedEvaluator;
+use crate::sink::GraphSink;
+
+/// A vertex to insert for each row of the input.
+#[derive(Debug)]
+pub struct InsertVertexSpec {
+    label: LabelId,
+    properties: Vec<BoxedEvaluator>,
+}
+
+impl InsertVertexSpec {
+    /// Creates a spec inserting vertices with `label`, whose property columns are evaluated by
+    /// `properties`, in order.
+    pub fn new(label: LabelId, properties: Vec<BoxedEvaluator>) -> Self {
+        Self { label, properties }
+    }
+}
+
+/// Inserts a vertex per spec for each row of its input, and then outputs the input with a column
+/// of the ids of the inserted vertices appended per spec.
+///
+/// Like [`SetPropertiesBuilder`](super::set_properties::SetPropertiesBuilder), all input is
+/// consumed and the sink is committed before any chunk is output.
+#[derive(Debug)]
+pub struct InsertBuilder<E, S> {
+    child: E,
+    specs: Vec<InsertVertexSpec>,
+    sink: S,
+}
+
+impl<E, S> InsertBuilder<E, S> {
+    pub fn new(child: E, specs: Vec<InsertVertexSpec>, sink: S) -> Self {
+        assert!(
+            !specs.is_empty(),
+            "at least one vertex to insert is required"
+        );
+        Self { child, specs, sink }
+    }
+}
+
+impl<E, S> IntoExecutor for InsertBuilder<E, S>
+where
+    E: Executor,
+    S: GraphSink,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let InsertBuilder { child, specs, sink } = self;
+            let mut chunks = Vec::new();
+            for chunk in child.into_iter() {
+                let mut chunk = gen_try!(chunk);
+                chunk.compact();
+                if chunk.is_empty() {
+                    continue;
+                }
+                let mut columns = Vec::with_capacity(specs.len());
+                for spec in &specs {
+                    let mut values = Vec::with_capacity(spec.properties.len());
+                    for property in &spec.properties {
+                        values.push(gen_try!(property.evaluate(&chunk)));
+                    }
+                    let mut vertices = Vec::with_capacity(chunk.len());
+                    for index in 0..chunk.len() {
+                        let properties = values.iter().map(|v| v.value_at(index)).collect();
+                        vertices.push(gen_try!(sink.create_vertex(spec.label, properties)));
+                    }
+                    columns.push(Arc::new(UInt64Array::from(vertices)) as _);
+                }
+                chunk.append_columns(columns);
+                chunks.push(chunk);
+            }
+            gen_try!(sink.commit());
+            for chunk in chunks {
+                yield Ok(chunk);
+            }
+        }
+        .into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk;
+    use minigu_common::data_chunk::DataChunk;
+    use minigu_common::value::ScalarValue;
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+    use crate::evaluator::constant::Constant;
+    use crate::sink::mock::MockGraphSink;
+
+    #[test]
+    fn test_insert() {
+        let mut sink = MockGraphSink::new();
+        sink.add_vertex(1, vec![]);
+        let label = LabelId::new(1).unwrap();
+        let chunk = data_chunk!({ true, false, true }, (Int32, [10, 20, 30]));
+        // INSERT (:Person {name: NULL, age: <column 0>})
+        let spec = InsertVertexSpec::new(
+            label,
+            vec![
+                Box::new(Constant::new(ScalarValue::String(None))),
+                Box::new(ColumnRef::new(0)),
+            ],
+        );
+        let chunks: Vec<DataChunk> = [Ok(chunk)]
+            .into_executor()
+            .insert(vec![spec], &sink)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(chunks, [data_chunk!((Int32, [10, 30]), (UInt64, [2, 3]))]);
+        assert!(sink.is_committed());
+        assert_eq!(
+            sink.vertex_properties(2).unwrap(),
+            [ScalarValue::String(None), ScalarValue::Int32(Some(10))]
+        );
+        assert_eq!(
+            sink.vertex_properties(3).unwrap(),
+            [ScalarValue::String(None), ScalarValue::Int32(Some(30))]
+        );
+        assert!(!sink.contains_vertex(4));
+    }
+}
@@ -2,7 +2,7 @@
 use std::hash::Hash;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, UInt32Array};
+use arrow::array::{Array, ArrayRef, UInt32Array};
 use itertools::Itertools;
 use minigu_common::data_chunk::DataChunk;
 use minigu_common::value::{ScalarValue, ScalarValueAccessor};
@@ -23,6 +23,7 @@ pub struct JoinBuilder<L, R> {
 pub struct JoinCond {
     left_key: BoxedEvaluator,
     right_key: BoxedEvaluator,
+    null_safe: bool,
 }
 
 // TODO(ColinLee): Replace per-row join key construction with a batched approach
@@ -31,19 +32,36 @@ pub struct JoinCond {
 #[derive(Debug, PartialEq, Hash, Eq)]
 struct JoinKey(Vec<ScalarValue>);
 
-fn make_join_key(arrs: &[ArrayRef], row: usize) -> JoinKey {
+/// Returns the join key of `row`, or `None` if the row cannot match any other row, i.e., if one of
+/// its keys is null and is not compared in a null-safe way.
+fn make_join_key(arrs: &[ArrayRef], null_safe: &[bool], row: usize) -> Option<JoinKey> {
     let mut keys = Vec::with_capacity(arrs.len());
-    for arr in arrs {
+    for (arr, null_safe) in arrs.iter().zip(null_safe) {
+        if !null_safe && arr.is_null(row) {
+            return None;
+        }
         keys.push(arr.as_ref().index(row));
     }
-    JoinKey(keys)
+    Some(JoinKey(keys))
 }
 
 impl JoinCond {
+    /// Joins rows whose keys are equal. A null key matches no key, not even another null one.
     pub fn new(left_key: BoxedEvaluator, right_key: BoxedEvaluator) -> Self {
         Self {
             left_key,
             right_key,
+            null_safe: false,
+        }
+    }
+
+    /// Joins rows whose keys are not distinct (`IS NOT DISTINCT FROM`), so that a null key
+    /// matches the null keys of the other side.
+    pub fn null_safe(left_key: BoxedEvaluator, right_key: BoxedEvaluator) -> Self {
+        Self {
+            left_key,
+            right_key,
+            null_safe: true,
         }
     }
 }
@@ -64,6 +82,7 @@ impl<L, R> IntoExecutor for JoinBuilder<L, R>
     fn into_executor(self) -> Self::IntoExecutor {
         gen move {
             let JoinBuilder { left, right, conds } = self;
+            let null_safe: Vec<_> = conds.iter().map(|c| c.null_safe).collect();
             let (left_eval, right_eval): (Vec<_>, Vec<_>) =
                 conds.into_iter().map(|c| (c.left_key, c.right_key)).unzip();
 
@@ -71,8 +90,11 @@ fn into_executor(self) -> Self::IntoExecutor {
             let mut hash_table: HashMap<JoinKey, Vec<(u32, u32)>> = HashMap::new();
             let mut data_chunk_vec = vec![];
 
+            // Rows are addressed by their positions, so filtered chunks are compacted first.
             for chunk in left.into_iter() {
-                let chunk = Arc::new(gen_try!(chunk));
+                let mut chunk = gen_try!(chunk);
+                chunk.compact();
+                let chunk = Arc::new(chunk);
                 let key_cols: Vec<_> = gen_try!(
                     left_eval
                         .iter()
@@ -82,14 +104,17 @@ fn into_executor(self) -> Self::IntoExecutor {
                 let chunk_id: u32 = data_chunk_vec.len().try_into().expect("chunk num overflow");
                 for row in 0..chunk.len() {
                     let row_id: u32 = row.try_into().expect("row_id overflow");
-                    let key = make_join_key(&key_cols, row);
+                    let Some(key) = make_join_key(&key_cols, &null_safe, row) else {
+                        continue;
+                    };
                     hash_table.entry(key).or_default().push((chunk_id, row_id));
                 }
                 data_chunk_vec.push(chunk.clone());
             }
             // probe
             for chunk in right.into_iter() {
-                let chunk: DataChunk = gen_try!(chunk);
+                let mut chunk: DataChunk = gen_try!(chunk);
+                chunk.compact();
                 let key_cols: Vec<_> = gen_try!(
                     right_eval
                         .iter()
@@ -99,7 +124,9 @@ fn into_executor(self) -> Self::IntoExecutor {
                 let mut triples = vec![]; // (chunk_id, left_row, right_row)
                 for row in 0..chunk.len() {
                     let row_id: u32 = row.try_into().expect("row_id overflow");
-                    let key = make_join_key(&key_cols, row);
+                    let Some(key) = make_join_key(&key_cols, &null_safe, row) else {
+                        continue;
+                    };
                     if let Some(match_rows) = hash_table.get(&key) {
                         for (left_chunk, left_index) in match_rows {
                             triples.push((*left_chunk, *left_index, row_id));
@@ -291,4 +318,72 @@ fn test_hash_join_many_chunks_with_duplicates() {
         let all_rows = results.iter().map(|c| c.len()).sum::<usize>();
         assert_eq!(all_rows, 20); // (2 + 2) * 5 = 20
     }
+
+    #[test]
+    fn test_hash_join_null_keys() {
+        let left_chunk = data_chunk!((Int32, [Some(1), None, Some(2)]));
+        let right_chunk = data_chunk!((Int32, [None, Some(1)]));
+
+        let conds = vec![JoinCond::new(
+            Box::new(ColumnRef::new(0)),
+            Box::new(ColumnRef::new(0)),
+        )];
+        let left_executor = [Ok(left_chunk.clone())].into_executor();
+        let right_executor = [Ok(right_chunk.clone())].into_executor();
+        let results: Vec<DataChunk> = left_executor
+            .join(right_executor, conds)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let expected = data_chunk!((Int32, [1]), (Int32, [1]));
+        assert_eq!(results, vec![expected]);
+
+        let conds = vec![JoinCond::null_safe(
+            Box::new(ColumnRef::new(0)),
+            Box::new(ColumnRef::new(0)),
+        )];
+        let left_executor = [Ok(left_chunk)].into_executor();
+        let right_executor = [Ok(right_chunk)].into_executor();
+        let results: Vec<DataChunk> = left_executor
+            .join(right_executor, conds)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let expected = data_chunk!((Int32, [None, Some(1)]), (Int32, [None, Some(1)]));
+        assert_eq!(results, vec![expected]);
+    }
+
+    #[test]
+    fn test_hash_join_filtered_chunks() {
+        let left_chunk = data_chunk!({ true, false, true }, (Int32, [1, 2, 3]));
+        let right_chunk = data_chunk!({ false, true, true }, (Int32, [1, 2, 3]));
+        let conds = vec![JoinCond::new(
+            Box::new(ColumnRef::new(0)),
+            Box::new(ColumnRef::new(0)),
+        )];
+        let left_executor = [Ok(left_chunk)].into_executor();
+        let right_executor = [Ok(right_chunk)].into_executor();
+        let results: Vec<DataChunk> = left_executor
+            .join(right_executor, conds)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let expected = data_chunk!((Int32, [3]), (Int32, [3]));
+        assert_eq!(results, vec![expected]);
+    }
+
+    #[test]
+    fn test_hash_join_without_conds() {
+        let left_chunk = data_chunk!((Int32, [1, 2]));
+        let right_chunk = data_chunk!((Utf8, ["a", "b", "c"]));
+        let left_executor = [Ok(left_chunk)].into_executor();
+        let right_executor = [Ok(right_chunk)].into_executor();
+        let results: Vec<DataChunk> = left_executor
+            .join(right_executor, vec![])
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let rows = results.iter().map(|c| c.len()).sum::<usize>();
+        assert_eq!(rows, 6);
+    }
 }
@@ -1,15 +1,26 @@
 use super::utils::gen_try;
 use super::{Executor, IntoExecutor};
 
+/// Builds an executor returning at most `limit` rows of its child.
+///
+/// If the limit is *approximate*, it is enforced at chunk granularity: whole chunks are passed
+/// through until at least `limit` rows have been returned, so the last chunk may contain extra
+/// rows beyond the limit. This avoids slicing the last chunk and stops pulling from the child as
+/// early as the exact limit does, so it never returns fewer rows than the exact limit.
 #[derive(Debug)]
 pub struct LimitBuilder<E> {
     child: E,
     limit: usize,
+    approximate: bool,
 }
 
 impl<E> LimitBuilder<E> {
-    pub fn new(child: E, limit: usize) -> Self {
-        Self { child, limit }
+    pub fn new(child: E, limit: usize, approximate: bool) -> Self {
+        Self {
+            child,
+            limit,
+            approximate,
+        }
     }
 }
 
@@ -21,7 +32,11 @@ impl<E> IntoExecutor for LimitBuilder<E>
 
     fn into_executor(self) -> Self::IntoExecutor {
         gen move {
-            let LimitBuilder { child, limit } = self;
+            let LimitBuilder {
+                child,
+                limit,
+                approximate,
+            } = self;
             let mut count = 0;
 
             for chunk in child.into_iter() {
@@ -31,9 +46,9 @@ fn into_executor(self) -> Self::IntoExecutor {
                 }
 
                 let remaining = limit - count;
-                if chunk.len() <= remaining {
-                    // If the current chunk has fewer rows than the remaining limit, output the
-                    // entire chunk.
+                if chunk.len() <= remaining || approximate {
+                    // If the current chunk has fewer rows than the remaining limit (or the limit
+                    // is approximate), output the entire chunk.
                     count += chunk.len();
                     yield Ok(chunk);
                 } else {
@@ -88,4 +103,48 @@ fn test_limit_larger_than_input() {
         let expected = data_chunk!((Int32, [1, 2, 3]));
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_approximate_limit() {
+        let chunks = || {
+            [
+                Ok(data_chunk!((Int32, [1, 2, 3]))),
+                Ok(data_chunk!((Int32, [4, 5, 6]))),
+                Ok(data_chunk!((Int32, [7, 8, 9]))),
+            ]
+        };
+
+        let exact: DataChunk = chunks()
+            .into_executor()
+            .limit(5)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let approximate: DataChunk = chunks()
+            .into_executor()
+            .approximate_limit(5)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        // The approximate limit returns the whole chunk reaching the limit, but no further chunks.
+        assert_eq!(exact, data_chunk!((Int32, [1, 2, 3, 4, 5])));
+        assert_eq!(approximate, data_chunk!((Int32, [1, 2, 3, 4, 5, 6])));
+        assert_eq!(approximate.slice(0, exact.len()), exact);
+    }
+
+    #[test]
+    fn test_approximate_limit_on_chunk_boundary() {
+        let result: DataChunk = [
+            Ok(data_chunk!((Int32, [1, 2, 3]))),
+            Ok(data_chunk!((Int32, [4, 5, 6]))),
+        ]
+        .into_executor()
+        .approximate_limit(3)
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+        assert_eq!(result, data_chunk!((Int32, [1, 2, 3])));
+    }
 }
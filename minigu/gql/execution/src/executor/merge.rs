@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use std::sync::mpsc::sync_channel;
+
+use rayon::ThreadPool;
+
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+
+/// Runs each of its children on a thread pool, and outputs their chunks in the order they are
+/// produced.
+///
+/// Up to `buffer_size` chunks are buffered ahead of the consumer, after which the children block
+/// until the consumer catches up. Once the executor is dropped, e.g., by a limit, every child stops
+/// before producing its next chunk.
+///
+/// The consumer should not run on `pool`, since it blocks while waiting for the children, which
+/// could then never be scheduled.
+#[derive(Debug)]
+pub struct MergeBuilder<E> {
+    children: Vec<E>,
+    pool: Arc<ThreadPool>,
+    buffer_size: usize,
+}
+
+impl<E> MergeBuilder<E> {
+    pub fn new(children: Vec<E>, pool: Arc<ThreadPool>, buffer_size: usize) -> Self {
+        Self {
+            children,
+            pool,
+            buffer_size,
+        }
+    }
+}
+
+impl<E> IntoExecutor for MergeBuilder<E>
+where
+    E: Executor + Send + 'static,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let MergeBuilder {
+                children,
+                pool,
+                buffer_size,
+            } = self;
+            let (sender, receiver) = sync_channel(buffer_size);
+            for child in children {
+                let sender = sender.clone();
+                pool.spawn(move || {
+                    for chunk in child.into_iter() {
+                        let failed = chunk.is_err();
+                        // Sending fails once the receiver is dropped.
+                        if sender.send(chunk).is_err() || failed {
+                            return;
+                        }
+                    }
+                });
+            }
+            // The receiver is exhausted once every child is done and has dropped its sender.
+            drop(sender);
+            for chunk in receiver {
+                yield Ok(gen_try!(chunk));
+            }
+        }
+        .into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::{Barrier, Mutex};
+    use std::thread::ThreadId;
+
+    use arrow::array::AsArray;
+    use arrow::datatypes::Int32Type;
+    use itertools::Itertools;
+    use minigu_common::data_chunk;
+    use minigu_common::data_chunk::DataChunk;
+    use rayon::ThreadPoolBuilder;
+
+    use super::*;
+    use crate::error::{ExecutionError, ExecutionResult};
+
+    fn pool(num_threads: usize) -> Arc<ThreadPool> {
+        Arc::new(
+            ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn partition(start: i32) -> Vec<ExecutionResult<DataChunk>> {
+        (start..start + 3)
+            .map(|i| Ok(data_chunk!((Int32, [i * 10, i * 10 + 1]))))
+            .collect()
+    }
+
+    fn sorted_rows<'a>(chunks: impl IntoIterator<Item = &'a DataChunk>) -> Vec<i32> {
+        chunks
+            .into_iter()
+            .flat_map(|c| c.columns()[0].as_primitive::<Int32Type>().values().to_vec())
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_matches_serial() {
+        let serial: Vec<DataChunk> = (0..4).flat_map(|i| partition(i * 3)).try_collect().unwrap();
+        let num_threads = 4;
+        let barrier = Arc::new(Barrier::new(num_threads));
+        let threads = Arc::new(Mutex::new(HashSet::<ThreadId>::new()));
+        let children = (0..4)
+            .map(|i| {
+                let barrier = barrier.clone();
+                let threads = threads.clone();
+                gen move {
+                    // Every child waits for the others, so they must run on distinct threads.
+                    barrier.wait();
+                    threads.lock().unwrap().insert(std::thread::current().id());
+                    for chunk in partition(i * 3) {
+                        yield chunk;
+                    }
+                }
+                .into_executor()
+            })
+            .collect();
+        let merged: Vec<DataChunk> = MergeBuilder::new(children, pool(num_threads), 1)
+            .into_executor()
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(merged.len(), serial.len());
+        assert_eq!(sorted_rows(&merged), sorted_rows(&serial));
+        assert_eq!(threads.lock().unwrap().len(), num_threads);
+    }
+
+    #[test]
+    fn test_merge_error() {
+        let failing = [Err(ExecutionError::Custom("failed".into()))]
+            .into_iter()
+            .chain(partition(0))
+            .collect_vec()
+            .into_executor();
+        let result: ExecutionResult<Vec<DataChunk>> = MergeBuilder::new(vec![failing], pool(1), 1)
+            .into_executor()
+            .into_iter()
+            .try_collect();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_empty_child() {
+        let child = std::iter::empty::<ExecutionResult<DataChunk>>().into_executor();
+        let mut merged = MergeBuilder::new(vec![child], pool(1), 1).into_executor();
+        assert!(merged.next_chunk().is_none());
+    }
+}
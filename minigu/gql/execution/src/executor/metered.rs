@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use minigu_common::data_chunk::DataChunk;
+
+use super::Executor;
+use crate::error::ExecutionResult;
+
+/// Counters of the work done by the executors of a query, shared by all of them.
+#[derive(Debug, Default)]
+pub struct ExecutionMetrics {
+    chunks_produced: AtomicU64,
+    bytes_scanned: AtomicU64,
+}
+
+impl ExecutionMetrics {
+    /// Returns the number of chunks produced by the executors, summed over all of them.
+    #[inline]
+    pub fn chunks_produced(&self) -> u64 {
+        self.chunks_produced.load(Ordering::Relaxed)
+    }
+
+    /// Returns the approximate number of bytes read from the graph, i.e., the memory size of the
+    /// columns output by scans.
+    #[inline]
+    pub fn bytes_scanned(&self) -> u64 {
+        self.bytes_scanned.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn add_bytes_scanned(&self, bytes: usize) {
+        self.bytes_scanned
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// Wraps an executor so that the chunks it returns are counted into `metrics`, along with their
+/// size if the executor is a scan.
+#[derive(Debug)]
+pub struct Metered<E> {
+    child: E,
+    metrics: Arc<ExecutionMetrics>,
+    scan: bool,
+}
+
+impl<E> Metered<E> {
+    pub fn new(child: E, metrics: Arc<ExecutionMetrics>, scan: bool) -> Self {
+        Self {
+            child,
+            metrics,
+            scan,
+        }
+    }
+}
+
+impl<E: Executor> Executor for Metered<E> {
+    fn next_chunk(&mut self) -> Option<ExecutionResult<DataChunk>> {
+        let chunk = self.child.next_chunk();
+        if let Some(Ok(chunk)) = &chunk {
+            self.metrics.chunks_produced.fetch_add(1, Ordering::Relaxed);
+            if self.scan {
+                self.metrics.add_bytes_scanned(chunk.num_bytes());
+            }
+        }
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::data_chunk;
+
+    use super::*;
+    use crate::executor::IntoExecutor;
+
+    #[test]
+    fn test_metered() {
+        let metrics = Arc::new(ExecutionMetrics::default());
+        let chunks = vec![
+            Ok(data_chunk!((Int64, [1, 2, 3]))),
+            Ok(data_chunk!((Int64, [4]))),
+        ];
+        let scan = chunks
+            .into_executor()
+            .metered(metrics.clone(), true)
+            .into_iter()
+            .count();
+        assert_eq!(scan, 2);
+        assert_eq!(metrics.chunks_produced(), 2);
+        // Each column holds at least its 8-byte values.
+        assert!(metrics.bytes_scanned() >= 4 * 8);
+
+        let bytes_scanned = metrics.bytes_scanned();
+        let chunks = vec![Ok(data_chunk!((Int64, [1])))];
+        chunks
+            .into_executor()
+            .metered(metrics.clone(), false)
+            .into_iter()
+            .for_each(drop);
+        assert_eq!(metrics.chunks_produced(), 3);
+        assert_eq!(metrics.bytes_scanned(), bytes_scanned);
+    }
+}
@@ -1,8 +1,12 @@
 pub mod aggregate;
+pub mod cancellable;
+pub mod delete;
+pub mod distinct;
 pub mod expand;
 pub mod factorized_filter;
 pub mod filter;
 pub mod flatten;
+pub mod insert;
 pub mod procedure_call;
 
 // TODO: Implement join executor.
@@ -14,24 +18,43 @@
 // TODO: Implement limit executor.
 pub mod limit;
 
+pub mod merge;
+pub mod metered;
+#[cfg(feature = "morsel")]
+pub mod morsel;
 pub mod project;
+pub mod set_properties;
 pub mod sort;
+pub mod sorted_aggregate;
+#[cfg(feature = "tracing")]
+pub mod traced;
 pub mod utils;
 pub mod vector_index_scan;
 pub mod vertex_property_scan;
 pub mod vertex_scan;
 
 use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
 use aggregate::{AggregateBuilder, AggregateSpec};
 use arrow::array::{BooleanArray, ListArray};
+use cancellable::Cancellable;
+use delete::DeleteBuilder;
+use distinct::DistinctBuilder;
 use expand::ExpandBuilder;
 use factorized_filter::FactorizedFilterBuilder;
 use filter::FilterBuilder;
 use flatten::FlattenBuilder;
+use insert::{InsertBuilder, InsertVertexSpec};
+use metered::{ExecutionMetrics, Metered};
 use minigu_common::data_chunk::DataChunk;
 use project::ProjectBuilder;
+use set_properties::{SetPropertiesBuilder, SetPropertiesSpec};
 use sort::{SortBuilder, SortSpec};
+use sorted_aggregate::SortedAggregateBuilder;
+#[cfg(feature = "tracing")]
+use traced::Traced;
 use vertex_property_scan::VertexPropertyScanBuilder;
 
 use crate::error::ExecutionResult;
@@ -39,6 +62,7 @@
 use crate::executor::join::{JoinBuilder, JoinCond};
 use crate::executor::limit::LimitBuilder;
 use crate::executor::vertex_scan::VertexScanBuilder;
+use crate::sink::GraphSink;
 use crate::source::{ExpandSource, VertexPropertySource, VertexSource};
 
 pub type BoxedExecutor = Box<dyn Executor>;
@@ -97,12 +121,17 @@ fn expand<S>(self, input_column_index: usize, source: S) -> impl Executor
         ExpandBuilder::new(self, input_column_index, source).into_executor()
     }
 
-    fn scan_vertex_property<S>(self, input_column_index: usize, source: S) -> impl Executor
+    fn scan_vertex_property<S>(
+        self,
+        input_column_index: usize,
+        properties: Vec<usize>,
+        source: S,
+    ) -> impl Executor
     where
         Self: Sized,
         S: VertexPropertySource,
     {
-        VertexPropertyScanBuilder::new(self, input_column_index, source).into_executor()
+        VertexPropertyScanBuilder::new(self, input_column_index, properties, source).into_executor()
     }
 
     fn scan_vertex<S>(self, source: S) -> impl Executor
@@ -160,11 +189,95 @@ fn aggregate(
         .into_executor()
     }
 
+    /// Like [`Executor::aggregate`], but the input must be sorted on the group by expressions, so
+    /// that each group is emitted as soon as its rows are consumed.
+    fn sorted_aggregate(
+        self,
+        aggregate_specs: Vec<AggregateSpec>,
+        group_by_expressions: Vec<BoxedEvaluator>,
+        output_expressions: Vec<BoxedEvaluator>,
+        max_chunk_size: usize,
+    ) -> impl Executor
+    where
+        Self: Sized,
+    {
+        SortedAggregateBuilder::new(
+            self,
+            aggregate_specs,
+            group_by_expressions,
+            output_expressions,
+            max_chunk_size,
+        )
+        .into_executor()
+    }
+
+    fn distinct(self) -> impl Executor
+    where
+        Self: Sized,
+    {
+        DistinctBuilder::new(self).into_executor()
+    }
+
     fn limit(self, limit: usize) -> impl Executor
     where
         Self: Sized,
     {
-        LimitBuilder::new(self, limit).into_executor()
+        LimitBuilder::new(self, limit, false).into_executor()
+    }
+
+    /// Like [`Executor::limit`], but the limit is only enforced at chunk granularity, i.e., the
+    /// last chunk may contain more rows than requested.
+    fn approximate_limit(self, limit: usize) -> impl Executor
+    where
+        Self: Sized,
+    {
+        LimitBuilder::new(self, limit, true).into_executor()
+    }
+
+    fn set_properties<S>(self, specs: Vec<SetPropertiesSpec>, sink: S) -> impl Executor
+    where
+        Self: Sized,
+        S: GraphSink,
+    {
+        SetPropertiesBuilder::new(self, specs, sink).into_executor()
+    }
+
+    fn insert<S>(self, specs: Vec<InsertVertexSpec>, sink: S) -> impl Executor
+    where
+        Self: Sized,
+        S: GraphSink,
+    {
+        InsertBuilder::new(self, specs, sink).into_executor()
+    }
+
+    fn delete<S>(self, input_column_indices: Vec<usize>, detach: bool, sink: S) -> impl Executor
+    where
+        Self: Sized,
+        S: GraphSink,
+    {
+        DeleteBuilder::new(self, input_column_indices, detach, sink).into_executor()
+    }
+
+    fn cancellable(self, cancelled: Arc<AtomicBool>) -> Cancellable<Self>
+    where
+        Self: Sized,
+    {
+        Cancellable::new(self, cancelled)
+    }
+
+    fn metered(self, metrics: Arc<ExecutionMetrics>, scan: bool) -> Metered<Self>
+    where
+        Self: Sized,
+    {
+        Metered::new(self, metrics, scan)
+    }
+
+    #[cfg(feature = "tracing")]
+    fn traced(self, node: &'static str) -> Traced<Self>
+    where
+        Self: Sized,
+    {
+        Traced::new(self, node)
     }
 
     /// Convert this Executor into a FactorizedExecutor.
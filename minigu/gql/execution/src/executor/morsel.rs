@@ -0,0 +1,224 @@
+//! Morsel-driven execution of pipelines over vertex scans.
+//!
+//! Rather than pulling chunks through a tree of executors, the scanned vertices are cut into
+//! morsels of a fixed number of rows. Worker threads repeatedly grab the next morsel and push it
+//! through every stage of the pipeline, so a worker that is done early simply takes more morsels
+//! and the load stays balanced without partitioning the input up front.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::sync_channel;
+
+use arrow::array::AsArray;
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::types::VertexIdArray;
+use rayon::ThreadPool;
+
+use super::filter::filter_chunk;
+use super::project::project_chunk;
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+use crate::error::ExecutionResult;
+use crate::evaluator::{BoxedEvaluator, Evaluator};
+
+/// A stage of a morsel-driven pipeline, applied to every morsel on its own.
+#[derive(Debug)]
+pub enum MorselStage {
+    /// Keeps the rows for which the predicate is true.
+    Filter(BoxedEvaluator),
+    /// Replaces the columns with the results of the evaluators.
+    Project(Vec<BoxedEvaluator>),
+}
+
+impl MorselStage {
+    /// Applies the stage to `chunk`, returning `None` if no row is left.
+    fn apply(&self, chunk: DataChunk) -> ExecutionResult<Option<DataChunk>> {
+        match self {
+            MorselStage::Filter(predicate) => {
+                let filter = predicate.evaluate(&chunk)?.into_array();
+                filter_chunk(chunk, filter.as_boolean().clone())
+            }
+            MorselStage::Project(evaluators) => project_chunk(&chunk, evaluators).map(Some),
+        }
+    }
+}
+
+/// Runs a pipeline of [`MorselStage`]s over `vertices`, cut into morsels of `morsel_size` rows,
+/// on every thread of `pool`.
+///
+/// Evaluators cannot be shared between threads, so each worker builds its own stages by calling
+/// `stages`. Chunks are output in the order they are produced, which does not preserve the order
+/// of `vertices`.
+///
+/// The pipeline is itself an [`Executor`], so operators breaking it (e.g., aggregations) can be
+/// applied to its output as usual. They run on the consumer thread, which should not be a thread
+/// of `pool`, since it blocks while waiting for the workers.
+#[derive(Debug)]
+pub struct MorselPipelineBuilder<F> {
+    vertices: Arc<VertexIdArray>,
+    stages: F,
+    pool: Arc<ThreadPool>,
+    morsel_size: usize,
+}
+
+impl<F> MorselPipelineBuilder<F> {
+    pub fn new(
+        vertices: Arc<VertexIdArray>,
+        stages: F,
+        pool: Arc<ThreadPool>,
+        morsel_size: usize,
+    ) -> Self {
+        assert_ne!(morsel_size, 0, "morsel size must be positive");
+        Self {
+            vertices,
+            stages,
+            pool,
+            morsel_size,
+        }
+    }
+}
+
+impl<F> IntoExecutor for MorselPipelineBuilder<F>
+where
+    F: Fn() -> Vec<MorselStage> + Send + Sync + 'static,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let MorselPipelineBuilder {
+                vertices,
+                stages,
+                pool,
+                morsel_size,
+            } = self;
+            let num_workers = pool.current_num_threads();
+            let stages = Arc::new(stages);
+            // The offset of the next morsel to be processed.
+            let next = Arc::new(AtomicUsize::new(0));
+            let (sender, receiver) = sync_channel(num_workers);
+            for _ in 0..num_workers {
+                let vertices = vertices.clone();
+                let stages = stages.clone();
+                let next = next.clone();
+                let sender = sender.clone();
+                pool.spawn(move || {
+                    let stages = (*stages)();
+                    loop {
+                        let offset = next.fetch_add(morsel_size, Ordering::Relaxed);
+                        if offset >= vertices.len() {
+                            return;
+                        }
+                        let length = morsel_size.min(vertices.len() - offset);
+                        let morsel = DataChunk::new(vec![Arc::new(vertices.slice(offset, length))]);
+                        let output = stages.iter().try_fold(Some(morsel), |chunk, stage| {
+                            chunk.map_or(Ok(None), |chunk| stage.apply(chunk))
+                        });
+                        let Some(output) = output.transpose() else {
+                            continue;
+                        };
+                        let failed = output.is_err();
+                        // Sending fails once the receiver is dropped.
+                        if sender.send(output).is_err() || failed {
+                            return;
+                        }
+                    }
+                });
+            }
+            // The receiver is exhausted once every worker is done and has dropped its sender.
+            drop(sender);
+            for chunk in receiver {
+                yield Ok(gen_try!(chunk));
+            }
+        }
+        .into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::{Int64Type, UInt64Type};
+    use itertools::Itertools;
+    use rayon::ThreadPoolBuilder;
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+    use crate::evaluator::constant::Constant;
+    use crate::executor::aggregate::AggregateSpec;
+    use crate::source::VertexSource;
+
+    const NUM_VERTICES: u64 = 10_000;
+
+    fn vertices() -> Arc<VertexIdArray> {
+        Arc::new(VertexIdArray::from_iter_values(0..NUM_VERTICES))
+    }
+
+    /// Keeps the multiples of 3.
+    fn predicate() -> BoxedEvaluator {
+        let predicate = ColumnRef::new(0)
+            .rem(Constant::new(3u64.into()))
+            .eq(Constant::new(0u64.into()));
+        Box::new(predicate)
+    }
+
+    fn increment() -> BoxedEvaluator {
+        Box::new(ColumnRef::new(0).add(Constant::new(1u64.into())))
+    }
+
+    fn pipeline(num_threads: usize) -> impl Executor {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let stages = || {
+            vec![
+                MorselStage::Filter(predicate()),
+                MorselStage::Project(vec![increment()]),
+            ]
+        };
+        MorselPipelineBuilder::new(vertices(), stages, Arc::new(pool), 128).into_executor()
+    }
+
+    fn sorted_rows(executor: impl Executor) -> Vec<u64> {
+        executor
+            .into_iter()
+            .flat_map(|chunk| {
+                let mut chunk = chunk.unwrap();
+                chunk.compact();
+                chunk.columns()[0]
+                    .as_primitive::<UInt64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn test_morsel_pipeline_matches_serial() {
+        let predicate = predicate();
+        let serial = [Ok(vertices())]
+            .into_iter()
+            .scan_vertex()
+            .filter(move |c| {
+                predicate
+                    .evaluate(c)
+                    .map(|a| a.into_array().as_boolean().clone())
+            })
+            .project(vec![increment()]);
+        let expected = (0..NUM_VERTICES).step_by(3).map(|i| i + 1).collect_vec();
+        assert_eq!(sorted_rows(serial), expected);
+        assert_eq!(sorted_rows(pipeline(4)), expected);
+    }
+
+    #[test]
+    fn test_morsel_pipeline_aggregate() {
+        let chunk = pipeline(4)
+            .aggregate(vec![AggregateSpec::count()], vec![], vec![])
+            .next_chunk()
+            .unwrap()
+            .unwrap();
+        let count = chunk.columns()[0].as_primitive::<Int64Type>().value(0);
+        assert_eq!(count, NUM_VERTICES.div_ceil(3) as i64);
+    }
+}
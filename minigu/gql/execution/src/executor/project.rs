@@ -3,17 +3,34 @@
 
 use super::utils::gen_try;
 use super::{Executor, IntoExecutor};
+use crate::error::ExecutionResult;
 use crate::evaluator::BoxedEvaluator;
 
 #[derive(Debug)]
 pub struct ProjectBuilder<E> {
     child: E,
     evaluators: Vec<BoxedEvaluator>,
+    common_evaluators: Vec<BoxedEvaluator>,
 }
 
 impl<E> ProjectBuilder<E> {
     pub fn new(child: E, evaluators: Vec<BoxedEvaluator>) -> Self {
-        Self { child, evaluators }
+        Self {
+            child,
+            evaluators,
+            common_evaluators: vec![],
+        }
+    }
+
+    /// Evaluates `common_evaluators` once per chunk, before the output evaluators.
+    ///
+    /// Their results are appended to the columns of the chunk, in order, so that subexpressions
+    /// shared by several output evaluators can be read with a
+    /// [`ColumnRef`](crate::evaluator::column_ref::ColumnRef) rather than evaluated repeatedly.
+    /// Each of them must evaluate to a column, not to a scalar.
+    pub fn with_common_evaluators(mut self, common_evaluators: Vec<BoxedEvaluator>) -> Self {
+        self.common_evaluators = common_evaluators;
+        self
     }
 }
 
@@ -25,34 +42,55 @@ impl<E> IntoExecutor for ProjectBuilder<E>
 
     fn into_executor(self) -> Self::IntoExecutor {
         gen move {
-            let ProjectBuilder { child, evaluators } = self;
+            let ProjectBuilder {
+                child,
+                evaluators,
+                common_evaluators,
+            } = self;
             for chunk in child.into_iter() {
-                let chunk = gen_try!(chunk);
-                let columns = gen_try!(
-                    evaluators
+                let mut chunk = gen_try!(chunk);
+                let common: Vec<_> = gen_try!(
+                    common_evaluators
                         .iter()
                         .map(|e| e.evaluate(&chunk).map(|d| d.into_array()))
                         .try_collect()
                 );
-                let mut new_chunk = DataChunk::new(columns);
-                if let Some(filter) = chunk.filter() {
-                    new_chunk = new_chunk.with_filter(filter.clone());
-                }
-                yield Ok(new_chunk);
+                chunk.append_columns(common);
+                yield project_chunk(&chunk, &evaluators);
             }
         }
         .into_executor()
     }
 }
 
+/// Evaluates `evaluators` over `chunk`, keeping its filter.
+pub(crate) fn project_chunk(
+    chunk: &DataChunk,
+    evaluators: &[BoxedEvaluator],
+) -> ExecutionResult<DataChunk> {
+    let columns: Vec<_> = evaluators
+        .iter()
+        .map(|e| e.evaluate(chunk).map(|d| d.into_array()))
+        .try_collect()?;
+    let mut new_chunk = DataChunk::new(columns);
+    if let Some(filter) = chunk.filter() {
+        new_chunk = new_chunk.with_filter(filter.clone());
+    }
+    Ok(new_chunk)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
     use minigu_common::data_chunk;
 
     use super::*;
     use crate::evaluator::Evaluator;
     use crate::evaluator::column_ref::ColumnRef;
     use crate::evaluator::constant::Constant;
+    use crate::evaluator::datum::DatumRef;
 
     #[test]
     fn test_project() {
@@ -75,4 +113,43 @@ fn test_project() {
         let expected = data_chunk!((Int32, [4, 12]), (Utf8, ["a", "c"]), (Int32, [2, 4]));
         assert_eq!(chunk, expected);
     }
+
+    /// Counts how many times its inner evaluator is evaluated.
+    #[derive(Debug)]
+    struct Counting<E> {
+        inner: E,
+        count: Rc<Cell<usize>>,
+    }
+
+    impl<E: Evaluator> Evaluator for Counting<E> {
+        fn evaluate(&self, chunk: &DataChunk) -> ExecutionResult<DatumRef> {
+            self.count.set(self.count.get() + 1);
+            self.inner.evaluate(chunk)
+        }
+    }
+
+    #[test]
+    fn test_project_common_subexpression() {
+        let chunk = data_chunk!((Int32, [1, 2, 3]), (Int32, [10, 20, 30]));
+        let count = Rc::new(Cell::new(0));
+        let shared = Counting {
+            inner: ColumnRef::new(0).add(ColumnRef::new(1)),
+            count: count.clone(),
+        };
+        // Both outputs read the shared `c0 + c1`, appended as column 2.
+        let e1 = ColumnRef::new(2).mul(Constant::new(2i32.into()));
+        let e2 = ColumnRef::new(2).add(Constant::new(1i32.into()));
+        let chunk: DataChunk = ProjectBuilder::new(
+            [Ok(chunk)].into_executor(),
+            vec![Box::new(e1), Box::new(e2)],
+        )
+        .with_common_evaluators(vec![Box::new(shared)])
+        .into_executor()
+        .into_iter()
+        .try_collect()
+        .unwrap();
+        let expected = data_chunk!((Int32, [22, 44, 66]), (Int32, [12, 23, 34]));
+        assert_eq!(chunk, expected);
+        assert_eq!(count.get(), 1);
+    }
 }
@@ -0,0 +1,214 @@
+use arrow::array::AsArray;
+use arrow::datatypes::UInt64Type;
+
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+use crate::evaluator::BoxedEvaluator;
+use crate::sink::GraphSink;
+
+/// The properties to set on the vertices of an input column.
+#[derive(Debug)]
+pub struct SetPropertiesSpec {
+    input_column_index: usize,
+    properties: Vec<usize>,
+    values: Vec<BoxedEvaluator>,
+}
+
+impl SetPropertiesSpec {
+    /// Creates a spec setting the property column `properties[i]` of each vertex in
+    /// `input_column_index` to `values[i]`.
+    pub fn new(
+        input_column_index: usize,
+        properties: Vec<usize>,
+        values: Vec<BoxedEvaluator>,
+    ) -> Self {
+        assert_eq!(
+            properties.len(),
+            values.len(),
+            "every property should have a value"
+        );
+        Self {
+            input_column_index,
+            properties,
+            values,
+        }
+    }
+}
+
+/// Sets the properties of the vertices in each row of its input, and then outputs the input
+/// unchanged.
+///
+/// All input is consumed and the sink is committed before any chunk is output, so that the
+/// modifications do not depend on how much of the output is pulled (e.g., by a limit).
+#[derive(Debug)]
+pub struct SetPropertiesBuilder<E, S> {
+    child: E,
+    specs: Vec<SetPropertiesSpec>,
+    sink: S,
+}
+
+impl<E, S> SetPropertiesBuilder<E, S> {
+    pub fn new(child: E, specs: Vec<SetPropertiesSpec>, sink: S) -> Self {
+        Self { child, specs, sink }
+    }
+}
+
+impl<E, S> IntoExecutor for SetPropertiesBuilder<E, S>
+where
+    E: Executor,
+    S: GraphSink,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let SetPropertiesBuilder { child, specs, sink } = self;
+            let mut chunks = Vec::new();
+            for chunk in child.into_iter() {
+                let mut chunk = gen_try!(chunk);
+                chunk.compact();
+                if chunk.is_empty() {
+                    continue;
+                }
+                for spec in &specs {
+                    let mut values = Vec::with_capacity(spec.values.len());
+                    for value in &spec.values {
+                        values.push(gen_try!(value.evaluate(&chunk)));
+                    }
+                    let vertices =
+                        chunk.columns()[spec.input_column_index].as_primitive::<UInt64Type>();
+                    for (index, vertex) in vertices.iter().enumerate() {
+                        // A null vertex is not bound, e.g., by an optional match.
+                        let Some(vertex) = vertex else {
+                            continue;
+                        };
+                        let row = values.iter().map(|v| v.value_at(index)).collect();
+                        gen_try!(sink.set_vertex_properties(vertex, &spec.properties, row));
+                    }
+                }
+                chunks.push(chunk);
+            }
+            gen_try!(sink.commit());
+            for chunk in chunks {
+                yield Ok(chunk);
+            }
+        }
+        .into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk;
+    use minigu_common::data_chunk::DataChunk;
+    use minigu_common::value::ScalarValue;
+
+    use super::*;
+    use crate::error::ExecutionResult;
+    use crate::evaluator::column_ref::ColumnRef;
+    use crate::evaluator::constant::Constant;
+    use crate::sink::mock::MockGraphSink;
+
+    fn build_test_sink() -> MockGraphSink {
+        let mut sink = MockGraphSink::new();
+        for vertex in 1..=3 {
+            let properties = vec![
+                ScalarValue::String(Some(format!("v{vertex}"))),
+                ScalarValue::Int32(Some(vertex as i32)),
+            ];
+            sink.add_vertex(vertex, properties);
+        }
+        sink
+    }
+
+    #[test]
+    fn test_set_properties() {
+        let sink = build_test_sink();
+        let chunk = data_chunk!(
+            { true, false, true },
+            (UInt64, [1, 2, 3]),
+            (Int32, [10, 20, 30])
+        );
+        // SET n.name = NULL, n.age = <column 1>
+        let spec = SetPropertiesSpec::new(
+            0,
+            vec![0, 1],
+            vec![
+                Box::new(Constant::new(ScalarValue::String(None))),
+                Box::new(ColumnRef::new(1)),
+            ],
+        );
+        let chunks: Vec<DataChunk> = [Ok(chunk)]
+            .into_executor()
+            .set_properties(vec![spec], &sink)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(chunks, [data_chunk!((UInt64, [1, 3]), (Int32, [10, 30]))]);
+        assert!(sink.is_committed());
+        assert_eq!(
+            sink.vertex_properties(1).unwrap(),
+            [ScalarValue::String(None), ScalarValue::Int32(Some(10))]
+        );
+        // Filtered out, so left unchanged.
+        assert_eq!(
+            sink.vertex_properties(2).unwrap(),
+            [
+                ScalarValue::String(Some("v2".into())),
+                ScalarValue::Int32(Some(2))
+            ]
+        );
+        assert_eq!(
+            sink.vertex_properties(3).unwrap(),
+            [ScalarValue::String(None), ScalarValue::Int32(Some(30))]
+        );
+    }
+
+    #[test]
+    fn test_set_properties_before_output() {
+        let sink = build_test_sink();
+        let chunks = [
+            Ok(data_chunk!((UInt64, [1]))),
+            Ok(data_chunk!((UInt64, [2, 3]))),
+        ];
+        let spec = SetPropertiesSpec::new(
+            0,
+            vec![1],
+            vec![Box::new(Constant::new(ScalarValue::Int32(Some(0))))],
+        );
+        // Only the first chunk is pulled, but every vertex is updated.
+        let first = chunks
+            .into_executor()
+            .set_properties(vec![spec], &sink)
+            .next_chunk()
+            .unwrap()
+            .unwrap();
+        assert_eq!(first, data_chunk!((UInt64, [1])));
+        assert!(sink.is_committed());
+        for vertex in 1..=3 {
+            assert_eq!(
+                sink.vertex_properties(vertex).unwrap()[1],
+                ScalarValue::Int32(Some(0))
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_properties_empty_input() {
+        let sink = build_test_sink();
+        let spec = SetPropertiesSpec::new(
+            0,
+            vec![1],
+            vec![Box::new(Constant::new(ScalarValue::Int32(Some(0))))],
+        );
+        let chunks: Vec<DataChunk> = std::iter::empty::<ExecutionResult<DataChunk>>()
+            .into_executor()
+            .set_properties(vec![spec], &sink)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert!(chunks.is_empty());
+        assert!(sink.is_committed());
+    }
+}
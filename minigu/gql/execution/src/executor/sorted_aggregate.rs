@@ -0,0 +1,241 @@
+use arrow::array::ArrayRef;
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::value::ScalarValue;
+
+use super::aggregate::{AggregateSpec, AggregateState, scalar_values_to_array};
+use super::utils::gen_try;
+use super::{Executor, IntoExecutor};
+use crate::error::ExecutionResult;
+use crate::evaluator::BoxedEvaluator;
+
+/// Grouped aggregation over input that is sorted on the group keys.
+///
+/// Since all rows of a group are adjacent, a group is finalized as soon as its key changes, so only
+/// the states of the current group are kept in memory, instead of a hash table of all groups. The
+/// output is identical to the grouped [`AggregateBuilder`](super::aggregate::AggregateBuilder),
+/// with groups emitted in input order and at most `max_chunk_size` groups per chunk.
+#[derive(Debug)]
+pub struct SortedAggregateBuilder<E> {
+    child: E,
+    aggregate_specs: Vec<AggregateSpec>,
+    group_by_expressions: Vec<BoxedEvaluator>,
+    output_expressions: Vec<BoxedEvaluator>,
+    max_chunk_size: usize,
+}
+
+impl<E> SortedAggregateBuilder<E> {
+    pub fn new(
+        child: E,
+        aggregate_specs: Vec<AggregateSpec>,
+        group_by_expressions: Vec<BoxedEvaluator>,
+        output_expressions: Vec<BoxedEvaluator>,
+        max_chunk_size: usize,
+    ) -> Self {
+        assert!(
+            !aggregate_specs.is_empty(),
+            "At least one aggregate function is required"
+        );
+        assert!(
+            !group_by_expressions.is_empty(),
+            "At least one group by expression is required"
+        );
+        assert_ne!(max_chunk_size, 0, "max chunk size must be positive");
+        Self {
+            child,
+            aggregate_specs,
+            group_by_expressions,
+            output_expressions,
+            max_chunk_size,
+        }
+    }
+}
+
+impl<E> IntoExecutor for SortedAggregateBuilder<E>
+where
+    E: Executor,
+{
+    type IntoExecutor = impl Executor;
+
+    fn into_executor(self) -> Self::IntoExecutor {
+        gen move {
+            let SortedAggregateBuilder {
+                child,
+                aggregate_specs,
+                group_by_expressions,
+                output_expressions,
+                max_chunk_size,
+            } = self;
+            let num_columns = group_by_expressions.len() + aggregate_specs.len();
+            // The key and states of the group being aggregated.
+            let mut current: Option<(Vec<ScalarValue>, Vec<AggregateState>)> = None;
+            // Finalized groups that have not been emitted yet, column by column.
+            let mut finished: Vec<Vec<ScalarValue>> = vec![Vec::new(); num_columns];
+
+            for chunk in child.into_iter() {
+                let chunk = gen_try!(chunk);
+                if chunk.is_empty() {
+                    continue;
+                }
+                let mut keys = Vec::with_capacity(group_by_expressions.len());
+                for expr in &group_by_expressions {
+                    keys.push(gen_try!(expr.evaluate(&chunk)));
+                }
+                let mut args = Vec::with_capacity(aggregate_specs.len());
+                for spec in &aggregate_specs {
+                    let arg = match &spec.expression {
+                        Some(expr) => Some(gen_try!(expr.evaluate(&chunk))),
+                        None => None,
+                    };
+                    args.push(arg);
+                }
+
+                // The rows of the chunk are not borrowed across yields.
+                let indices: Vec<_> = chunk.rows().map(|row| row.row_index()).collect();
+                for index in indices {
+                    let key: Vec<_> = keys.iter().map(|k| k.value_at(index)).collect();
+                    if current.as_ref().is_none_or(|(k, _)| k != &key) {
+                        if let Some((key, states)) = current.take() {
+                            gen_try!(finish_group(key, &states, &mut finished));
+                        }
+                        if finished[0].len() == max_chunk_size {
+                            let arrays = to_arrays(&mut finished);
+                            yield apply_output_expressions(arrays, &output_expressions);
+                        }
+                        let states = aggregate_specs
+                            .iter()
+                            .map(|spec| AggregateState::new(&spec.function, spec.distinct))
+                            .collect();
+                        current = Some((key, states));
+                    }
+                    let (_, states) = current.as_mut().expect("current group should exist");
+                    for (state, arg) in states.iter_mut().zip(&args) {
+                        let value = match arg {
+                            Some(arg) => arg.value_at(index),
+                            // COUNT(*)
+                            None => ScalarValue::Int64(Some(1)),
+                        };
+                        gen_try!(state.update(Some(value)));
+                    }
+                }
+            }
+
+            if let Some((key, states)) = current.take() {
+                gen_try!(finish_group(key, &states, &mut finished));
+            }
+            if !finished[0].is_empty() {
+                let arrays = to_arrays(&mut finished);
+                yield apply_output_expressions(arrays, &output_expressions);
+            }
+        }
+        .into_executor()
+    }
+}
+
+fn finish_group(
+    key: Vec<ScalarValue>,
+    states: &[AggregateState],
+    finished: &mut [Vec<ScalarValue>],
+) -> ExecutionResult<()> {
+    let num_keys = key.len();
+    for (i, value) in key.into_iter().enumerate() {
+        finished[i].push(value);
+    }
+    for (i, state) in states.iter().enumerate() {
+        finished[num_keys + i].push(state.finalize()?);
+    }
+    Ok(())
+}
+
+fn to_arrays(finished: &mut [Vec<ScalarValue>]) -> Vec<ArrayRef> {
+    finished
+        .iter_mut()
+        .map(|column| scalar_values_to_array(std::mem::take(column)))
+        .collect()
+}
+
+fn apply_output_expressions(
+    arrays: Vec<ArrayRef>,
+    output_expressions: &[BoxedEvaluator],
+) -> ExecutionResult<DataChunk> {
+    let chunk = DataChunk::new(arrays);
+    if output_expressions.is_empty() {
+        return Ok(chunk);
+    }
+    let columns = output_expressions
+        .iter()
+        .map(|expr| Ok(expr.evaluate(&chunk)?.into_array()))
+        .collect::<ExecutionResult<_>>()?;
+    Ok(DataChunk::new(columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk;
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+
+    fn specs() -> Vec<AggregateSpec> {
+        vec![
+            AggregateSpec::count(),
+            AggregateSpec::sum(Box::new(ColumnRef::new(1)), false),
+            AggregateSpec::max(Box::new(ColumnRef::new(1))),
+        ]
+    }
+
+    fn sorted_rows(chunks: Vec<DataChunk>) -> Vec<Vec<ScalarValue>> {
+        chunks
+            .iter()
+            .flat_map(|c| c.rows().map(|r| r.into_owned().into_inner()))
+            .sorted_by_key(|r| format!("{r:?}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_sorted_aggregate_matches_hash_aggregate() {
+        // Sorted on the group key, with groups spanning chunks.
+        let input = || {
+            [
+                Ok(data_chunk!((Int32, [1, 1, 2]), (Int64, [10, 20, 30]))),
+                Ok(data_chunk!((Int32, [2, 2, 3]), (Int64, [40, 50, 60]))),
+                Ok(data_chunk!((Int32, [4]), (Int64, [70]))),
+            ]
+        };
+        let sorted: Vec<DataChunk> = input()
+            .into_executor()
+            .sorted_aggregate(specs(), vec![Box::new(ColumnRef::new(0))], vec![], 2)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let hashed: Vec<DataChunk> = input()
+            .into_executor()
+            .aggregate(specs(), vec![Box::new(ColumnRef::new(0))], vec![])
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        // Groups are emitted in input order, at most 2 per chunk.
+        assert_eq!(sorted.len(), 2);
+        assert_eq!(
+            sorted[0],
+            data_chunk!(
+                (Int32, [1, 2]),
+                (Int64, [2, 3]),
+                (Int64, [30, 120]),
+                (Int64, [20, 50])
+            )
+        );
+        assert_eq!(sorted_rows(sorted), sorted_rows(hashed));
+    }
+
+    #[test]
+    fn test_sorted_aggregate_empty_input() {
+        let chunks: Vec<DataChunk> = std::iter::empty::<ExecutionResult<DataChunk>>()
+            .into_executor()
+            .sorted_aggregate(specs(), vec![Box::new(ColumnRef::new(0))], vec![], 2)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert!(chunks.is_empty());
+    }
+}
@@ -0,0 +1,35 @@
+use minigu_common::data_chunk::DataChunk;
+use tracing::field::Empty;
+
+use super::Executor;
+use crate::error::ExecutionResult;
+
+/// Wraps an executor so that each call to `next_chunk` runs in a `next_chunk` span, with the type
+/// of the plan node the executor was built from as `node` and the number of rows of the returned
+/// chunk as `rows`.
+///
+/// The spans of the children are nested in the span of their parent, since a parent pulls from
+/// its children while computing its own chunk.
+#[derive(Debug)]
+pub struct Traced<E> {
+    child: E,
+    node: &'static str,
+}
+
+impl<E> Traced<E> {
+    pub fn new(child: E, node: &'static str) -> Self {
+        Self { child, node }
+    }
+}
+
+impl<E: Executor> Executor for Traced<E> {
+    fn next_chunk(&mut self) -> Option<ExecutionResult<DataChunk>> {
+        let span = tracing::debug_span!("next_chunk", node = self.node, rows = Empty);
+        let _entered = span.enter();
+        let chunk = self.child.next_chunk();
+        if let Some(Ok(chunk)) = &chunk {
+            span.record("rows", chunk.cardinality());
+        }
+        chunk
+    }
+}
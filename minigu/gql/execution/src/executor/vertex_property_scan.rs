@@ -8,14 +8,17 @@
 pub struct VertexPropertyScanBuilder<E, S> {
     child: E,
     input_column_index: usize,
+    /// The property columns of `source` to scan. Other columns are never materialized.
+    properties: Vec<usize>,
     source: S,
 }
 
 impl<E, S> VertexPropertyScanBuilder<E, S> {
-    pub fn new(child: E, input_column_index: usize, source: S) -> Self {
+    pub fn new(child: E, input_column_index: usize, properties: Vec<usize>, source: S) -> Self {
         Self {
             child,
             input_column_index,
+            properties,
             source,
         }
     }
@@ -33,6 +36,7 @@ fn into_executor(self) -> Self::IntoExecutor {
             let VertexPropertyScanBuilder {
                 child,
                 input_column_index,
+                properties,
                 source,
             } = self;
             for chunk in child.into_iter() {
@@ -47,7 +51,7 @@ fn into_executor(self) -> Self::IntoExecutor {
                     .get(input_column_index)
                     .expect("column with `input_column_index` should exist");
                 let input_column = input_column.as_primitive();
-                let properties = gen_try!(source.scan_vertex_properties(input_column));
+                let properties = gen_try!(source.scan_vertex_properties(input_column, &properties));
                 chunk.append_columns(properties);
                 yield Ok(chunk);
             }
@@ -66,10 +70,12 @@ mod tests {
     use crate::source::mock::MockVertexPropertySource;
 
     fn build_test_source() -> MockVertexPropertySource {
-        let mut source = MockVertexPropertySource::new();
-        source.add_vertex_property(1, "v1".to_string());
-        source.add_vertex_property(2, "v2".to_string());
-        source.add_vertex_property(3, "v3".to_string());
+        let mut source = MockVertexPropertySource::new(3);
+        for column in 0..3 {
+            for vertex in 1..=3 {
+                source.add_vertex_property(column, vertex, format!("v{vertex}_{column}"));
+            }
+        }
         source
     }
 
@@ -82,15 +88,31 @@ fn test_vertex_property_scan() {
         );
         let chunk: DataChunk = [Ok(chunk)]
             .into_executor()
-            .scan_vertex_property(0, build_test_source())
+            .scan_vertex_property(0, vec![0], build_test_source())
             .into_iter()
             .try_collect()
             .unwrap();
         let expected = data_chunk!(
             (UInt64, [1, 3, 4, 5]),
             (Utf8, ["abc", "ghi", "jkl", "mno"]),
-            (Utf8, [Some("v1"), Some("v3"), None, None])
+            (Utf8, [Some("v1_0"), Some("v3_0"), None, None])
         );
         assert_eq!(chunk, expected);
     }
+
+    #[test]
+    fn test_vertex_property_scan_projection() {
+        let chunk = data_chunk!((UInt64, [1, 2]));
+        let source = build_test_source();
+        let chunk: DataChunk = [Ok(chunk)]
+            .into_executor()
+            .scan_vertex_property(0, vec![2], &source)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        let expected = data_chunk!((UInt64, [1, 2]), (Utf8, ["v1_2", "v2_2"]));
+        assert_eq!(chunk, expected);
+        // Only the selected property column is materialized.
+        assert_eq!(source.scanned_columns(), vec![2]);
+    }
 }
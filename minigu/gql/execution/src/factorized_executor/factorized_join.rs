@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use arrow::array::Array;
+use minigu_common::result_set::{DataPos, ResultSet};
+use minigu_common::value::{ScalarValue, ScalarValueAccessor};
+
+use super::{FactorizedExecutor, IntoFactorizedExecutor};
+use crate::executor::utils::gen_try;
+
+/// Joins two streams of result sets on the equality of a key column of each side, without
+/// flattening either of them.
+///
+/// Keys must be columns of flat chunks, so that each result set has a single key; unflat key
+/// columns have to be flattened first. Each pair of matching result sets is output as their
+/// [cross product](ResultSet::cross_product), holding the chunks of the left result set followed by
+/// those of the right one. The unflat chunks of both sides thus stay unflat, and the output
+/// represents as many tuples as the flat join of the two inputs. A null key matches no key.
+///
+/// The left side is built into a hash table, while the right side is streamed.
+#[derive(Debug)]
+pub struct FactorizedJoinBuilder<L, R> {
+    left: L,
+    right: R,
+    left_key: DataPos,
+    right_key: DataPos,
+}
+
+impl<L, R> FactorizedJoinBuilder<L, R> {
+    pub fn new(left: L, right: R, left_key: DataPos, right_key: DataPos) -> Self {
+        Self {
+            left,
+            right,
+            left_key,
+            right_key,
+        }
+    }
+}
+
+impl<L, R> IntoFactorizedExecutor for FactorizedJoinBuilder<L, R>
+where
+    L: FactorizedExecutor,
+    R: FactorizedExecutor,
+{
+    type IntoFactorizedExecutor = impl FactorizedExecutor;
+
+    fn into_factorized_executor(self) -> Self::IntoFactorizedExecutor {
+        gen move {
+            let FactorizedJoinBuilder {
+                left,
+                right,
+                left_key,
+                right_key,
+            } = self;
+
+            let mut hash_table: HashMap<ScalarValue, Vec<ResultSet>> = HashMap::new();
+            for result_set in left.into_iter() {
+                let result_set = gen_try!(result_set);
+                if let Some(key) = join_key(&result_set, &left_key) {
+                    hash_table.entry(key).or_default().push(result_set);
+                }
+            }
+
+            for result_set in right.into_iter() {
+                let result_set = gen_try!(result_set);
+                let Some(key) = join_key(&result_set, &right_key) else {
+                    continue;
+                };
+                // The hash table is not borrowed across yields.
+                let joined: Vec<_> = hash_table
+                    .get(&key)
+                    .into_iter()
+                    .flatten()
+                    .map(|left| left.cross_product(&result_set))
+                    .collect();
+                for result_set in joined {
+                    yield Ok(result_set);
+                }
+            }
+        }
+        .into_factorized_executor()
+    }
+}
+
+/// Returns the value of the column at `key` in the current row of its flat chunk, or `None` if it
+/// is null.
+fn join_key(result_set: &ResultSet, key: &DataPos) -> Option<ScalarValue> {
+    let chunk = result_set
+        .get_data_chunk(key.data_chunk_pos)
+        .expect("key chunk should exist");
+    let row = chunk
+        .cur_idx()
+        .expect("join keys should be columns of flat chunks");
+    let column = &chunk.columns()[key.column_pos];
+    (!column.is_null(row)).then(|| column.index(row))
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk::DataChunk;
+    use minigu_common::data_chunk::row::OwnedRow;
+    use minigu_common::result_set::DataChunkPos;
+    use minigu_common::{data_chunk, data_pos, result_set};
+
+    use super::*;
+    use crate::evaluator::column_ref::ColumnRef;
+    use crate::executor::join::JoinCond;
+    use crate::executor::{Executor, IntoExecutor};
+
+    /// A result set made of a flat chunk of keys, at row `cur_idx`, and an unflat chunk.
+    fn factorized(mut keys: DataChunk, cur_idx: usize, mut values: DataChunk) -> ResultSet {
+        keys.set_cur_idx(Some(cur_idx));
+        values.set_unflat();
+        result_set!(keys, values)
+    }
+
+    fn left() -> Vec<ResultSet> {
+        vec![
+            factorized(
+                data_chunk!((Int32, [1, 2])),
+                0,
+                data_chunk!((Utf8, ["a", "b"])),
+            ),
+            factorized(data_chunk!((Int32, [1, 2])), 1, data_chunk!((Utf8, ["c"]))),
+            factorized(
+                data_chunk!((Int32, [Some(3), None])),
+                1,
+                data_chunk!((Utf8, ["d"])),
+            ),
+        ]
+    }
+
+    fn right() -> Vec<ResultSet> {
+        vec![
+            factorized(
+                data_chunk!((Int32, [1])),
+                0,
+                data_chunk!((Int64, [10, 20, 30])),
+            ),
+            factorized(data_chunk!((Int32, [2, 1])), 1, data_chunk!((Int64, [40]))),
+            factorized(data_chunk!((Int32, [3])), 0, data_chunk!((Int64, [50]))),
+            factorized(
+                data_chunk!((Int32, [Some(3), None])),
+                1,
+                data_chunk!((Int64, [60])),
+            ),
+        ]
+    }
+
+    fn sorted(rows: impl IntoIterator<Item = OwnedRow>) -> Vec<OwnedRow> {
+        rows.into_iter()
+            .sorted_by_key(|r| format!("{r:?}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_factorized_join() {
+        let results: Vec<ResultSet> = left()
+            .into_iter()
+            .map(Ok)
+            .into_factorized_executor()
+            .factorized_join(
+                right().into_iter().map(Ok).into_factorized_executor(),
+                data_pos!(0, 0),
+                data_pos!(0, 0),
+            )
+            .into_iter()
+            .try_collect()
+            .unwrap();
+
+        // The first left result set matches the first two right ones.
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.num_data_chunks(), 4);
+            // The unflat chunks of both sides are kept unflat.
+            assert_eq!(
+                result.get_unflat_chunks(),
+                [DataChunkPos(1), DataChunkPos(3)]
+            );
+        }
+        let rows = results.iter().flat_map(|r| r.rows()).collect_vec();
+        assert_eq!(rows.len(), 2 * 3 + 2);
+
+        // The same join over the flattened inputs.
+        let flatten = |result_sets: Vec<ResultSet>| {
+            result_sets
+                .iter()
+                .map(|r| Ok(r.to_data_chunk().unwrap()))
+                .collect_vec()
+                .into_executor()
+        };
+        let cond = JoinCond::new(Box::new(ColumnRef::new(0)), Box::new(ColumnRef::new(0)));
+        let expected = flatten(left())
+            .join(flatten(right()), vec![cond])
+            .into_iter()
+            .map(|chunk| chunk.unwrap())
+            .flat_map(|chunk| chunk.rows().map(OwnedRow::from).collect_vec())
+            .collect_vec();
+        assert_eq!(sorted(rows), sorted(expected));
+    }
+}
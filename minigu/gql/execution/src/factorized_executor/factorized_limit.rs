@@ -0,0 +1,201 @@
+use arrow::array::BooleanArray;
+use minigu_common::data_chunk::DataChunk;
+use minigu_common::result_set::ResultSet;
+
+use super::{FactorizedExecutor, IntoFactorizedExecutor};
+use crate::executor::utils::gen_try;
+
+/// Outputs at most `limit` tuples of its child, counting the tuples represented by each result set
+/// (i.e., its factor times the product of the cardinalities of its unflat chunks) rather than the
+/// result sets themselves.
+///
+/// Result sets are output as is while they fit within the limit. The one crossing the limit is
+/// split into result sets holding exactly the missing tuples, by lowering its factor, narrowing
+/// its unflat chunks with filters and fixing some of their rows, so that only the chunks that must
+/// be cut are flattened. The tuples kept from that result set are not necessarily the first ones in
+/// the order of [`ResultSet::rows`]. No result set is pulled from the child once the limit is
+/// reached.
+#[derive(Debug)]
+pub struct FactorizedLimitBuilder<E> {
+    child: E,
+    limit: u64,
+}
+
+impl<E> FactorizedLimitBuilder<E> {
+    pub fn new(child: E, limit: u64) -> Self {
+        Self { child, limit }
+    }
+}
+
+impl<E> IntoFactorizedExecutor for FactorizedLimitBuilder<E>
+where
+    E: FactorizedExecutor,
+{
+    type IntoFactorizedExecutor = impl FactorizedExecutor;
+
+    fn into_factorized_executor(self) -> Self::IntoFactorizedExecutor {
+        gen move {
+            let FactorizedLimitBuilder { mut child, limit } = self;
+            let mut remaining = limit;
+            while remaining > 0 {
+                let Some(result_set) = child.next_resultset() else {
+                    break;
+                };
+                let result_set = gen_try!(result_set);
+                let num_tuples = result_set.num_tuples();
+                if num_tuples <= remaining {
+                    remaining -= num_tuples;
+                    yield Ok(result_set);
+                } else {
+                    for result_set in truncate(&result_set, remaining) {
+                        yield Ok(result_set);
+                    }
+                    remaining = 0;
+                }
+            }
+        }
+        .into_factorized_executor()
+    }
+}
+
+/// Splits `n` tuples of `result_set`, which has more than `n` of them, into result sets.
+fn truncate(result_set: &ResultSet, mut n: u64) -> Vec<ResultSet> {
+    let mut chunks: Vec<DataChunk> = result_set.iter().map(|c| c.as_ref().clone()).collect();
+    let mut output = vec![];
+    // The number of tuples sharing the rows fixed so far, starting with a single copy of the
+    // result set.
+    let mut size = result_set.num_tuples() / result_set.factor;
+    if n >= size {
+        output.push(rebuild(result_set, chunks.clone(), n / size));
+        n %= size;
+    }
+    for pos in 0..chunks.len() {
+        if n == 0 {
+            break;
+        }
+        if !chunks[pos].is_unflat() {
+            continue;
+        }
+        size /= chunks[pos].cardinality() as u64;
+        // The number of rows of the chunk whose tuples are all kept.
+        let rows = (n / size) as usize;
+        if rows > 0 {
+            let mut kept = chunks.clone();
+            kept[pos] = keep_first(&chunks[pos], rows);
+            output.push(rebuild(result_set, kept, 1));
+            n %= size;
+        }
+        if n > 0 {
+            // Some tuples of the next row are still missing, so it is fixed.
+            let row = chunks[pos]
+                .rows()
+                .nth(rows)
+                .expect("row should exist")
+                .row_index();
+            chunks[pos].set_cur_idx(Some(row));
+        }
+    }
+    output
+}
+
+/// Builds a result set from the chunks of `result_set`, modified or not, keeping its schema.
+fn rebuild(result_set: &ResultSet, chunks: Vec<DataChunk>, factor: u64) -> ResultSet {
+    let mut output: ResultSet = chunks.into_iter().collect();
+    output.factor = factor;
+    match result_set.schema() {
+        Some(schema) => output.with_schema(schema.clone()),
+        None => output,
+    }
+}
+
+/// Filters out every row of `chunk` but its first `n` visible ones.
+fn keep_first(chunk: &DataChunk, n: usize) -> DataChunk {
+    let mut kept = 0;
+    let filter: BooleanArray = (0..chunk.len())
+        .map(|i| {
+            let keep = kept < n && chunk.filter().is_none_or(|f| f.value(i));
+            kept += keep as usize;
+            Some(keep)
+        })
+        .collect();
+    chunk.clone().with_filter(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use minigu_common::data_chunk::row::OwnedRow;
+    use minigu_common::{data_chunk, result_set};
+
+    use super::*;
+
+    /// A result set with a flat chunk and two unflat chunks, the first of which is filtered,
+    /// repeated 3 times: it has 3 * 3 * 2 = 18 tuples.
+    fn factorized() -> ResultSet {
+        let mut flat = data_chunk!((Int32, [1, 2]));
+        flat.set_cur_idx(Some(0));
+        let mut unflat1 = data_chunk!({ true, false, true, true }, (Int32, [10, 20, 30, 40]));
+        unflat1.set_unflat();
+        let mut unflat2 = data_chunk!((Utf8, ["a", "b"]));
+        unflat2.set_unflat();
+        let mut result_set = result_set!(flat, unflat1, unflat2);
+        result_set.factor = 3;
+        result_set
+    }
+
+    fn limit(inputs: Vec<ResultSet>, limit: u64) -> Vec<ResultSet> {
+        inputs
+            .into_iter()
+            .map(Ok)
+            .into_factorized_executor()
+            .factorized_limit(limit)
+            .into_iter()
+            .try_collect()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_factorized_limit() {
+        let counts = |rows: Vec<OwnedRow>| rows.iter().map(|r| format!("{r:?}")).counts();
+        let all = counts(factorized().rows().collect());
+        for n in 0..=20 {
+            let outputs = limit(vec![factorized()], n);
+            let rows = outputs.iter().flat_map(|r| r.rows()).collect_vec();
+            assert_eq!(rows.len() as u64, n.min(18), "limit {n}");
+            let total: u64 = outputs.iter().map(ResultSet::num_tuples).sum();
+            assert_eq!(total, n.min(18));
+            // Every tuple is one of the input, kept at most as many times.
+            for (row, count) in counts(rows) {
+                assert!(count <= all[&row], "limit {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_factorized_limit_keeps_unflat_chunks() {
+        // A whole copy, and the tuples of the first two rows of the first unflat chunk.
+        let outputs = limit(vec![factorized()], 6 + 4);
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].factor, 1);
+        assert_eq!(outputs[0].get_unflat_chunks().len(), 2);
+        assert_eq!(outputs[1].num_tuples(), 4);
+        assert_eq!(outputs[1].get_unflat_chunks().len(), 2);
+    }
+
+    #[test]
+    fn test_factorized_limit_stops_early() {
+        let mut pulled = 0;
+        let inputs = std::iter::from_fn(|| {
+            pulled += 1;
+            Some(Ok(factorized()))
+        });
+        let outputs: Vec<ResultSet> = inputs
+            .into_factorized_executor()
+            .factorized_limit(36)
+            .into_iter()
+            .try_collect()
+            .unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(pulled, 2);
+    }
+}
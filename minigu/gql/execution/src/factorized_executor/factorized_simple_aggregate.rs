@@ -187,7 +187,8 @@ fn into_factorized_executor(self) -> Self::IntoFactorizedExecutor {
                         | AggregateFunction::Sum
                         | AggregateFunction::Avg
                         | AggregateFunction::Min
-                        | AggregateFunction::Max => {
+                        | AggregateFunction::Max
+                        | AggregateFunction::User(_) => {
                             // Use the expression from spec (which handles both column and
                             // expression cases)
                             gen_try!(process_aggregate(
@@ -1,14 +1,18 @@
 pub mod factorized_expand;
 pub mod factorized_flatten;
+pub mod factorized_join;
+pub mod factorized_limit;
 pub mod factorized_project;
 pub mod factorized_simple_aggregate;
 pub mod factorized_transfer;
 
 use factorized_expand::FactorizedExpandBuilder;
 use factorized_flatten::FactorizedFlattenBuilder;
+use factorized_join::FactorizedJoinBuilder;
+use factorized_limit::FactorizedLimitBuilder;
 use factorized_project::FactorizedProjectBuilder;
 use factorized_simple_aggregate::{FactorizedAggregateBuilder, SimpleAggregateSpec};
-use minigu_common::result_set::ResultSet;
+use minigu_common::result_set::{DataPos, ResultSet};
 
 use crate::error::ExecutionResult;
 use crate::evaluator::factorized_evaluator::BoxedFactorizedEvaluator;
@@ -65,6 +69,26 @@ fn factorized_flatten(
     {
         FactorizedFlattenBuilder::new(self, target_chunk_pos).into_factorized_executor()
     }
+
+    fn factorized_join<R>(
+        self,
+        right: R,
+        left_key: DataPos,
+        right_key: DataPos,
+    ) -> impl FactorizedExecutor
+    where
+        Self: Sized,
+        R: FactorizedExecutor,
+    {
+        FactorizedJoinBuilder::new(self, right, left_key, right_key).into_factorized_executor()
+    }
+
+    fn factorized_limit(self, limit: u64) -> impl FactorizedExecutor
+    where
+        Self: Sized,
+    {
+        FactorizedLimitBuilder::new(self, limit).into_factorized_executor()
+    }
 }
 
 /// A bridge between `Iterator` and [`FactorizedExecutor`].
@@ -5,4 +5,5 @@
 pub mod evaluator;
 pub mod executor;
 pub mod factorized_executor;
+pub mod sink;
 pub mod source;
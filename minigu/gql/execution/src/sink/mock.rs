@@ -0,0 +1,102 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use minigu_common::types::{LabelId, VertexId};
+use minigu_common::value::ScalarValue;
+
+use super::GraphSink;
+use crate::error::ExecutionResult;
+
+/// A mock graph sink that keeps the properties of each vertex, and the endpoints of each edge, in
+/// memory.
+///
+/// Modifications are applied immediately, and whether the sink has been committed is recorded. This
+/// should be used for testing purposes only.
+#[derive(Debug, Default)]
+pub struct MockGraphSink {
+    vertices: RefCell<HashMap<VertexId, Vec<ScalarValue>>>,
+    edges: RefCell<Vec<(VertexId, VertexId)>>,
+    committed: Cell<bool>,
+}
+
+impl MockGraphSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_vertex(&mut self, vertex: VertexId, properties: Vec<ScalarValue>) {
+        self.vertices.get_mut().insert(vertex, properties);
+    }
+
+    pub fn add_edge(&mut self, src: VertexId, dst: VertexId) {
+        self.edges.get_mut().push((src, dst));
+    }
+
+    pub fn contains_vertex(&self, vertex: VertexId) -> bool {
+        self.vertices.borrow().contains_key(&vertex)
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.borrow().len()
+    }
+
+    pub fn vertex_properties(&self, vertex: VertexId) -> Option<Vec<ScalarValue>> {
+        self.vertices.borrow().get(&vertex).cloned()
+    }
+
+    pub fn is_committed(&self) -> bool {
+        self.committed.get()
+    }
+}
+
+impl GraphSink for MockGraphSink {
+    fn create_vertex(
+        &self,
+        _label: LabelId,
+        properties: Vec<ScalarValue>,
+    ) -> ExecutionResult<VertexId> {
+        let mut vertices = self.vertices.borrow_mut();
+        let vertex = vertices.keys().max().map_or(1, |max| max + 1);
+        vertices.insert(vertex, properties);
+        Ok(vertex)
+    }
+
+    fn set_vertex_properties(
+        &self,
+        vertex: VertexId,
+        properties: &[usize],
+        values: Vec<ScalarValue>,
+    ) -> ExecutionResult<()> {
+        let mut vertices = self.vertices.borrow_mut();
+        let record = vertices
+            .get_mut(&vertex)
+            .unwrap_or_else(|| panic!("vertex {vertex} should exist"));
+        for (&property, value) in properties.iter().zip(values) {
+            record[property] = value;
+        }
+        Ok(())
+    }
+
+    fn has_edges(&self, vertex: VertexId) -> ExecutionResult<bool> {
+        let edges = self.edges.borrow();
+        Ok(edges
+            .iter()
+            .any(|&(src, dst)| src == vertex || dst == vertex))
+    }
+
+    fn delete_vertex(&self, vertex: VertexId) -> ExecutionResult<()> {
+        self.vertices.borrow_mut().remove(&vertex);
+        self.edges
+            .borrow_mut()
+            .retain(|&(src, dst)| src != vertex && dst != vertex);
+        Ok(())
+    }
+
+    fn commit(&self) -> ExecutionResult<()> {
+        assert!(
+            !self.committed.replace(true),
+            "sink should be committed once"
+        );
+        Ok(())
+    }
+}
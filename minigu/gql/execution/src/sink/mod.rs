@@ -0,0 +1,75 @@
+#[cfg(test)]
+pub(crate) mod mock;
+
+use auto_impl::auto_impl;
+use minigu_common::types::{LabelId, VertexId};
+use minigu_common::value::ScalarValue;
+use minigu_context::graph::GraphWriter;
+
+use crate::error::ExecutionResult;
+
+/// A trait for sinks that modify a graph.
+///
+/// Modifications may be buffered, e.g., in a transaction, and are only guaranteed to take effect
+/// once [`commit`](GraphSink::commit) returns.
+#[auto_impl(&, Box, Arc)]
+pub trait GraphSink {
+    /// Inserts a vertex with `label` and the property columns `properties`, returning its id.
+    fn create_vertex(
+        &self,
+        label: LabelId,
+        properties: Vec<ScalarValue>,
+    ) -> ExecutionResult<VertexId>;
+
+    /// Sets the property columns at `properties` of `vertex` to `values`, in the order of
+    /// `properties`.
+    fn set_vertex_properties(
+        &self,
+        vertex: VertexId,
+        properties: &[usize],
+        values: Vec<ScalarValue>,
+    ) -> ExecutionResult<()>;
+
+    /// Returns whether `vertex` has any incoming or outgoing edge.
+    fn has_edges(&self, vertex: VertexId) -> ExecutionResult<bool>;
+
+    /// Deletes `vertex`, together with all of its edges.
+    fn delete_vertex(&self, vertex: VertexId) -> ExecutionResult<()>;
+
+    /// Makes all modifications take effect. This is called once, after all modifications.
+    fn commit(&self) -> ExecutionResult<()>;
+}
+
+impl GraphSink for GraphWriter {
+    fn create_vertex(
+        &self,
+        label: LabelId,
+        properties: Vec<ScalarValue>,
+    ) -> ExecutionResult<VertexId> {
+        Ok(GraphWriter::insert_vertex(self, label, properties)?)
+    }
+
+    fn set_vertex_properties(
+        &self,
+        vertex: VertexId,
+        properties: &[usize],
+        values: Vec<ScalarValue>,
+    ) -> ExecutionResult<()> {
+        GraphWriter::set_vertex_properties(self, vertex, properties.to_vec(), values)?;
+        Ok(())
+    }
+
+    fn has_edges(&self, vertex: VertexId) -> ExecutionResult<bool> {
+        Ok(GraphWriter::has_edges(self, vertex)?)
+    }
+
+    fn delete_vertex(&self, vertex: VertexId) -> ExecutionResult<()> {
+        GraphWriter::delete_vertex(self, vertex)?;
+        Ok(())
+    }
+
+    fn commit(&self) -> ExecutionResult<()> {
+        GraphWriter::commit(self)?;
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -99,32 +100,53 @@ fn expand_from_vertex(&self, vertex: VertexId) -> Option<Self::ExpandIter> {
     }
 }
 
+/// A mock vertex property source with String-typed property columns.
+///
+/// The indices of the columns materialized by scans are recorded, so that tests can check that
+/// unused columns are not scanned. This should be used for testing purposes only.
 #[derive(Debug, Clone)]
 pub struct MockVertexPropertySource {
-    vertex_properties: HashMap<VertexId, String>,
+    columns: Vec<HashMap<VertexId, String>>,
+    scanned_columns: RefCell<Vec<usize>>,
 }
 
 impl MockVertexPropertySource {
-    pub fn new() -> Self {
+    pub fn new(num_columns: usize) -> Self {
         Self {
-            vertex_properties: HashMap::new(),
+            columns: vec![HashMap::new(); num_columns],
+            scanned_columns: RefCell::new(vec![]),
         }
     }
 
-    pub fn add_vertex_property(&mut self, vertex: VertexId, property: String) {
-        self.vertex_properties.insert(vertex, property);
+    pub fn add_vertex_property(&mut self, column: usize, vertex: VertexId, property: String) {
+        self.columns[column].insert(vertex, property);
+    }
+
+    pub fn scanned_columns(&self) -> Vec<usize> {
+        self.scanned_columns.borrow().clone()
     }
 }
 
 impl VertexPropertySource for MockVertexPropertySource {
-    fn scan_vertex_properties(&self, vertices: &VertexIdArray) -> ExecutionResult<Vec<ArrayRef>> {
+    fn scan_vertex_properties(
+        &self,
+        vertices: &VertexIdArray,
+        properties: &[usize],
+    ) -> ExecutionResult<Vec<ArrayRef>> {
         assert!(!vertices.is_nullable());
-        let properties = StringArray::from_iter(
-            vertices
-                .values()
-                .iter()
-                .map(|v| self.vertex_properties.get(v)),
-        );
-        Ok(vec![Arc::new(properties)])
+        let columns = properties
+            .iter()
+            .map(|&column| {
+                self.scanned_columns.borrow_mut().push(column);
+                let properties = StringArray::from_iter(
+                    vertices
+                        .values()
+                        .iter()
+                        .map(|v| self.columns[column].get(v)),
+                );
+                Arc::new(properties) as ArrayRef
+            })
+            .collect();
+        Ok(columns)
     }
 }
@@ -19,9 +19,9 @@
 /// This has been automatically implemented for all types of `IntoIterator<Item =
 /// VertexSourceOutput>`.
 pub trait VertexSource: Iterator<Item = VertexSourceOutput> {
-    fn scan_vertex(self) -> impl Executor
+    fn scan_vertex(self) -> impl Executor + Send
     where
-        Self: Sized,
+        Self: Sized + Send,
     {
         VertexScanBuilder::new(self).into_executor()
     }
@@ -32,7 +32,14 @@ impl<I> VertexSource for I where I: Iterator<Item = VertexSourceOutput> {}
 /// A trait for sources that map vertex IDs to (multiple) property value columns.
 #[auto_impl(&, Box, Arc)]
 pub trait VertexPropertySource {
-    fn scan_vertex_properties(&self, vertices: &VertexIdArray) -> ExecutionResult<Vec<ArrayRef>>;
+    /// Returns the property columns at `properties` for `vertices`, in the order of `properties`.
+    ///
+    /// Columns not listed in `properties` should not be materialized.
+    fn scan_vertex_properties(
+        &self,
+        vertices: &VertexIdArray,
+        properties: &[usize],
+    ) -> ExecutionResult<Vec<ArrayRef>>;
 }
 
 /// A trait for sources that map a vertex to its neighbors and (possibly) properties of the
@@ -1,7 +1,8 @@
 //! AST definitions for *catalog-modifying statements*.
 
 use super::{
-    CallProcedureStatement, CatalogObjectRef, GraphElementType, GraphExpr, GraphTypeRef, SchemaPath,
+    CallProcedureStatement, CatalogObjectRef, CompositeQueryStatement, GraphElementType, GraphExpr,
+    GraphTypeRef, Ident, SchemaPath,
 };
 use crate::macros::base;
 use crate::span::{OptSpanned, Spanned, VecSpanned};
@@ -17,6 +18,7 @@ pub enum CatalogModifyingStatement {
     DropGraph(DropGraphStatement),
     CreateGraphType(CreateGraphTypeStatement),
     DropGraphType(DropGraphTypeStatement),
+    CreateView(CreateViewStatement),
 }
 
 #[apply(base)]
@@ -79,3 +81,12 @@ pub enum GraphTypeSource {
     Like(Spanned<GraphExpr>),
     Nested(VecSpanned<GraphElementType>),
 }
+
+/// `CREATE [OR REPLACE] VIEW <name> AS <query>`, naming a query to be called later as
+/// `CALL <name>()`.
+#[apply(base)]
+pub struct CreateViewStatement {
+    pub name: Spanned<Ident>,
+    pub or_replace: bool,
+    pub query: Spanned<CompositeQueryStatement>,
+}
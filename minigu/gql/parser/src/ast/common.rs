@@ -138,6 +138,8 @@ pub enum EdgePatternKind {
 pub struct ElementPatternFiller {
     pub variable: OptSpanned<Ident>,
     pub label: OptSpanned<LabelExpr>,
+    /// The index named by `USING INDEX <name>`, which the element should be looked up with.
+    pub index_hint: OptSpanned<Ident>,
     pub predicate: OptSpanned<ElementPatternPredicate>,
 }
 
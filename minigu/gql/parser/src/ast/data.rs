@@ -1,6 +1,54 @@
 //! AST definitions for *data-modifying statements*.
 
+use super::{ElementPattern, Expr, Ident, ResultStatement, SimpleQueryStatement};
 use crate::macros::base;
+use crate::span::{OptSpanned, Spanned, VecSpanned};
 
 #[apply(base)]
-pub struct LinearDataModifyingStatement {}
+pub struct LinearDataModifyingStatement {
+    pub statements: VecSpanned<SimpleDataAccessingStatement>,
+    pub result: OptSpanned<ResultStatement>,
+}
+
+#[apply(base)]
+pub enum SimpleDataAccessingStatement {
+    Query(SimpleQueryStatement),
+    Modifying(SimpleDataModifyingStatement),
+}
+
+#[apply(base)]
+pub enum SimpleDataModifyingStatement {
+    Insert(InsertPatternList),
+    Set(SetItemList),
+    // Remove,
+    Delete(DeleteStatement),
+}
+
+/// The node patterns of `INSERT (n:Label {key: value, ...}), ...`, each of which describes a vertex
+/// to insert.
+pub type InsertPatternList = VecSpanned<ElementPattern>;
+
+pub type SetItemList = VecSpanned<SetItem>;
+
+#[apply(base)]
+pub enum SetItem {
+    /// `SET n.name = value`
+    Property {
+        element: Spanned<Ident>,
+        property: Spanned<Ident>,
+        value: Spanned<Expr>,
+    },
+    // AllProperties,
+    // Label,
+}
+
+/// `[DETACH | NODETACH] DELETE items`
+#[apply(base)]
+pub struct DeleteStatement {
+    /// Whether the edges incident to deleted vertices are deleted as well. If not, deleting a
+    /// vertex that still has edges is an error.
+    pub detach: bool,
+    pub items: DeleteItemList,
+}
+
+pub type DeleteItemList = VecSpanned<Expr>;
@@ -58,9 +58,19 @@ pub enum SimpleQueryStatement {
     Match(MatchStatement),
     // Let,
     // For,
-    // Filter,
+    Filter(Spanned<Expr>),
     Call(CallProcedureStatement),
     OrderByAndPage(OrderByAndPageStatement),
+    With(WithStatement),
+}
+
+/// `WITH [DISTINCT] <items> [WHERE <condition>]`, which passes the projected rows of the preceding
+/// statements to the following ones.
+#[apply(base)]
+pub struct WithStatement {
+    pub quantifier: OptSpanned<SetQuantifier>,
+    pub items: Spanned<Return>,
+    pub where_clause: OptSpanned<Expr>,
 }
 
 #[apply(base)]
@@ -16,4 +16,12 @@ pub enum EndTransaction {
 pub enum TransactionMode {
     ReadOnly,
     ReadWrite,
+    IsolationLevel(IsolationLevel),
+}
+
+#[apply(base)]
+pub enum IsolationLevel {
+    ReadCommitted,
+    Snapshot,
+    Serializable,
 }
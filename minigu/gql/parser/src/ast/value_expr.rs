@@ -1,6 +1,8 @@
 //! AST definitions for *Value expressions and specifications*.
 
-use super::{BooleanLiteral, GraphExpr, Ident, ListTypeName, Literal, UnsignedInteger};
+use super::{
+    BooleanLiteral, GraphExpr, Ident, ListTypeName, Literal, Predicate, UnsignedInteger, ValueType,
+};
 use crate::imports::Box;
 use crate::macros::base;
 use crate::span::{BoxSpanned, OptSpanned, Spanned, VecSpanned};
@@ -39,6 +41,12 @@ pub enum Expr {
         trailing_names: VecSpanned<Ident>,
     },
     Graph(Box<GraphExpr>),
+    Predicate(Predicate),
+    /// A cast specification, e.g., `CAST(a AS INT32)`.
+    Cast {
+        expr: BoxSpanned<Expr>,
+        value_type: BoxSpanned<ValueType>,
+    },
 }
 
 /// Binary operators.
@@ -52,6 +60,18 @@ pub enum BinaryOp {
     Mul,
     /// Division, e.g., `a / b`.
     Div,
+    /// Remainder, e.g., `a % b`.
+    Rem,
+    /// Bitwise AND, e.g., `a & b`.
+    BitAnd,
+    /// Bitwise OR, e.g., `a | b`.
+    BitOr,
+    /// Bitwise XOR, e.g., `a ^ b`.
+    BitXor,
+    /// Left shift, e.g., `a << b`.
+    ShiftLeft,
+    /// Right shift, e.g., `a >> b`.
+    ShiftRight,
     /// Concatenation, e.g., `a || b`.
     Concat,
     /// OR, e.g., `a OR b`.
@@ -8,6 +8,7 @@
 use winnow::combinator::todo;
 
 use crate::imports::Arc;
+use crate::span::offset_to_line_column;
 
 /// A lightweight error type for tokenizing.
 ///
@@ -151,6 +152,24 @@ pub struct UnexpectedError {
     position: (usize, usize),
 }
 
+impl UnexpectedError {
+    #[inline]
+    pub fn input(&self) -> &Arc<str> {
+        &self.input
+    }
+
+    #[inline]
+    pub fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    /// Returns the 1-based `(line, column)` of the unexpected token.
+    #[inline]
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+}
+
 impl Display for UnexpectedError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let (line, column) = self.position;
@@ -164,6 +183,16 @@ fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
 impl core::error::Error for UnexpectedError {}
 
 impl Error {
+    /// Returns the 1-based `(line, column)` where the error occurred, or `None` if the error is
+    /// not associated with a position in the input (e.g., unexpected end of input).
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::UnexpectedEof => None,
+            Error::InvalidToken(e) | Error::IncompleteComment(e) => Some(e.position()),
+            Error::Unexpected(e) => Some(e.position()),
+        }
+    }
+
     pub fn unexpected(input: &str, span: Range<usize>) -> Self {
         let offset = span.start;
         Error::Unexpected(UnexpectedError {
@@ -194,28 +223,9 @@ pub fn from_tokenize_error<'a>(input: &'a str, err: TokenizeError<'a>) -> Self {
     }
 }
 
+#[inline]
 fn translate_offset_to_line_column(input: &str, offset: usize) -> (usize, usize) {
-    assert!(!input.is_empty(), "`input` should not be empty");
-    assert!(
-        input.is_char_boundary(offset),
-        "`offset` must be a valid character boundary"
-    );
-    for (line_idx, line) in input.lines().enumerate() {
-        // SAFETY: `line` and `input` are both derived from the same allocated string.
-        let start_offset = unsafe { line.as_ptr().byte_offset_from(input.as_ptr()) as usize };
-        let end_offset = start_offset + line.len();
-        if offset <= end_offset {
-            let column_idx = line
-                .char_indices()
-                .enumerate()
-                .find_map(|(column_idx, (byte_idx, _))| {
-                    (byte_idx == offset - start_offset).then_some(column_idx)
-                })
-                .expect("`column_idx` should be found successfully");
-            return (line_idx + 1, column_idx + 1);
-        }
-    }
-    unreachable!("`offset` should be within the range of `input`");
+    offset_to_line_column(input, offset)
 }
 
 #[cfg(test)]
@@ -554,6 +554,8 @@ pub enum TokenKind<'a> {
     Binding,
     #[token("bindings", ignore(case))]
     Bindings,
+    #[token("committed", ignore(case))]
+    Committed,
     #[token("connecting", ignore(case))]
     Connecting,
     #[token("destination", ignore(case))]
@@ -576,6 +578,10 @@ pub enum TokenKind<'a> {
     Graph,
     #[token("groups", ignore(case))]
     Groups,
+    #[token("index", ignore(case))]
+    Index,
+    #[token("isolation", ignore(case))]
+    Isolation,
     #[token("keep", ignore(case))]
     Keep,
     #[token("label", ignore(case))]
@@ -586,6 +592,8 @@ pub enum TokenKind<'a> {
     Labels,
     #[token("last", ignore(case))]
     Last,
+    #[token("level", ignore(case))]
+    Level,
     #[token("nfc", ignore(case))]
     Nfc,
     #[token("nfd", ignore(case))]
@@ -614,10 +622,14 @@ pub enum TokenKind<'a> {
     Relationships,
     #[token("repeatable", ignore(case))]
     Repeatable,
+    #[token("serializable", ignore(case))]
+    Serializable,
     #[token("shortest", ignore(case))]
     Shortest,
     #[token("simple", ignore(case))]
     Simple,
+    #[token("snapshot", ignore(case))]
+    Snapshot,
     #[token("source", ignore(case))]
     Source,
     #[token("table", ignore(case))]
@@ -632,8 +644,12 @@ pub enum TokenKind<'a> {
     Type,
     #[token("undirected", ignore(case))]
     Undirected,
+    #[token("using", ignore(case))]
+    Using,
     #[token("vertex", ignore(case))]
     Vertex,
+    #[token("view", ignore(case))]
+    View,
     #[token("walk", ignore(case))]
     Walk,
     #[token("without", ignore(case))]
@@ -652,8 +668,12 @@ pub enum TokenKind<'a> {
     Concatenation,
     #[token("::")]
     DoubleColon,
+    #[token("<<")]
+    DoubleLeftAngleBracket,
     #[token("..")]
     DoublePeriod,
+    #[token(">>")]
+    DoubleRightAngleBracket,
     #[token(">=")]
     GreaterThanOrEquals,
     #[token("<-")]
@@ -708,6 +728,9 @@ pub enum TokenKind<'a> {
     Ampersand,
     #[token("*")]
     Asterisk,
+    // Not a GQL special character, but used by the bitwise XOR operator.
+    #[token("^")]
+    Circumflex,
     #[token(":")]
     Colon,
     #[token(",")]
@@ -1172,6 +1195,7 @@ pub fn is_non_reserved_word(&self) -> bool {
             Self::Acyclic
                 | Self::Binding
                 | Self::Bindings
+                | Self::Committed
                 | Self::Connecting
                 | Self::Destination
                 | Self::Different
@@ -1183,11 +1207,14 @@ pub fn is_non_reserved_word(&self) -> bool {
                 | Self::First
                 | Self::Graph
                 | Self::Groups
+                | Self::Index
+                | Self::Isolation
                 | Self::Keep
                 | Self::Label
                 | Self::Labeled
                 | Self::Labels
                 | Self::Last
+                | Self::Level
                 | Self::Nfc
                 | Self::Nfd
                 | Self::Nfkc
@@ -1202,8 +1229,10 @@ pub fn is_non_reserved_word(&self) -> bool {
                 | Self::Relationship
                 | Self::Relationships
                 | Self::Repeatable
+                | Self::Serializable
                 | Self::Shortest
                 | Self::Simple
+                | Self::Snapshot
                 | Self::Source
                 | Self::Table
                 | Self::To
@@ -1211,7 +1240,9 @@ pub fn is_non_reserved_word(&self) -> bool {
                 | Self::Transaction
                 | Self::Type
                 | Self::Undirected
+                | Self::Using
                 | Self::Vertex
+                | Self::View
                 | Self::Walk
                 | Self::Without
                 | Self::Write
@@ -1268,6 +1299,7 @@ pub fn is_prefix_of_predefined_type(&self) -> bool {
                 | Self::Vector
         ) || self.is_prefix_of_signed_exact_numeric_type()
             || self.is_prefix_of_unsigned_exact_numeric_type()
+            || self.is_prefix_of_approximate_numeric_type()
             || self.is_prefix_of_temporal_type()
     }
 
@@ -1305,6 +1337,21 @@ pub fn is_prefix_of_unsigned_exact_numeric_type(&self) -> bool {
         )
     }
 
+    #[inline]
+    pub fn is_prefix_of_approximate_numeric_type(&self) -> bool {
+        matches!(
+            self,
+            Self::Float16
+                | Self::Float32
+                | Self::Float64
+                | Self::Float128
+                | Self::Float256
+                | Self::Float
+                | Self::Real
+                | Self::Double
+        )
+    }
+
     #[inline]
     pub fn is_prefix_of_verbose_exact_numeric_type(&self) -> bool {
         matches!(
@@ -1346,6 +1393,7 @@ pub fn is_prefix_of_ambient_linear_query_statement(&self) -> bool {
                 | Self::Limit
                 | Self::Offset
                 | Self::Skip
+                | Self::With
         )
     }
 
@@ -1393,6 +1441,15 @@ pub fn is_prefix_of_simple_query_statement(&self) -> bool {
                 | Self::Offset
                 | Self::Skip
                 | Self::Call
+                | Self::With
+        )
+    }
+
+    #[inline]
+    pub fn is_prefix_of_simple_data_modifying_statement(&self) -> bool {
+        matches!(
+            self,
+            Self::Insert | Self::Set | Self::Delete | Self::Detach | Self::Nodetach
         )
     }
 
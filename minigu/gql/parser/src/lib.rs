@@ -19,7 +19,11 @@
 mod unescape;
 
 pub use lexer::TokenKind;
-pub use parser::{ParseOptions, Token, parse_gql, tokenize, tokenize_full};
+pub use parser::{
+    LosslessToken, ParseOptions, Token, Trivia, TriviaKind, parse_gql, parse_gql_recover,
+    parse_gql_script, tokenize, tokenize_full, tokenize_lossless,
+};
+pub use unescape::{quote_identifier, quote_string};
 
 #[cfg(not(feature = "std"))]
 mod imports {
@@ -1,9 +1,11 @@
 use winnow::combinator::{alt, dispatch, fail, opt, peek, preceded, repeat, seq};
 use winnow::{ModalResult, Parser};
 
+use super::lexical::identifier;
 use super::object_expr::graph_expression;
 use super::object_ref::*;
 use super::procedure_call::call_procedure_statement;
+use super::query::composite_query_statement;
 use super::type_element::{nested_graph_type_specification, typed};
 use crate::ast::*;
 use crate::lexer::TokenKind;
@@ -41,12 +43,19 @@ pub fn primitive_catalog_modifying_statement(
         (TokenKind::Drop, TokenKind::Schema) => {
             drop_schema_statement.map_inner(CatalogModifyingStatement::DropSchema)
         },
-        (TokenKind::Create, TokenKind::Property | TokenKind::Graph | TokenKind::Or) => {
+        (TokenKind::Create, TokenKind::Property | TokenKind::Graph) => {
             alt((
                 create_graph_type_statement.map_inner(CatalogModifyingStatement::CreateGraphType),
                 create_graph_statement.map_inner(CatalogModifyingStatement::CreateGraph),
             ))
         },
+        (TokenKind::Create, TokenKind::Or | TokenKind::View) => {
+            alt((
+                create_view_statement.map_inner(CatalogModifyingStatement::CreateView),
+                create_graph_type_statement.map_inner(CatalogModifyingStatement::CreateGraphType),
+                create_graph_statement.map_inner(CatalogModifyingStatement::CreateGraph),
+            ))
+        },
         (TokenKind::Drop, TokenKind::Property | TokenKind::Graph) => {
             alt((
                 drop_graph_type_statement.map_inner(CatalogModifyingStatement::DropGraphType),
@@ -271,6 +280,19 @@ pub fn drop_graph_type_statement(
     .parse_next(input)
 }
 
+pub fn create_view_statement(input: &mut TokenStream) -> ModalResult<Spanned<CreateViewStatement>> {
+    seq! {CreateViewStatement {
+        _: TokenKind::Create,
+        or_replace: opt((TokenKind::Or, TokenKind::Replace)).map(|o| o.is_some()),
+        _: TokenKind::View,
+        name: identifier,
+        _: TokenKind::As,
+        query: composite_query_statement,
+    }}
+    .spanned()
+    .parse_next(input)
+}
+
 #[cfg(all(test, feature = "serde"))]
 mod tests {
     use insta::assert_yaml_snapshot;
@@ -355,4 +377,12 @@ fn test_simple_catalog_modifying_statement() {
         );
         assert_yaml_snapshot!(parsed);
     }
+    #[test]
+    fn test_create_view_statement() {
+        let parsed = parse!(
+            create_view_statement,
+            "create or replace view v as match (n) return n"
+        );
+        assert_yaml_snapshot!(parsed);
+    }
 }
@@ -6,8 +6,8 @@
 use winnow::{ModalResult, Parser};
 
 use super::lexical::{
-    binding_variable, edge_synonym, edges_synonym, element_variable, field_name, label_name,
-    path_variable, property_name, subpath_variable, unsigned_integer,
+    binding_variable, edge_synonym, edges_synonym, element_variable, field_name, identifier,
+    label_name, path_variable, property_name, subpath_variable, unsigned_integer,
 };
 use super::object_expr::graph_expression;
 use super::object_ref::schema_reference;
@@ -157,6 +157,7 @@ pub fn edge_pattern(input: &mut TokenStream) -> ModalResult<Spanned<ElementPatte
                     filler: ElementPatternFiller {
                         variable: None,
                         label: None,
+                        index_hint: None,
                         predicate: None,
                     },
                 })
@@ -219,12 +220,17 @@ pub fn element_pattern_filler(
     seq! {ElementPatternFiller {
         variable: opt(element_variable_declaration),
         label: opt(is_label_expression),
+        index_hint: opt(index_hint),
         predicate: opt(element_pattern_predicate),
     }}
     .spanned()
     .parse_next(input)
 }
 
+pub fn index_hint(input: &mut TokenStream) -> ModalResult<Spanned<Ident>> {
+    preceded((TokenKind::Using, TokenKind::Index), identifier).parse_next(input)
+}
+
 pub fn element_pattern_predicate(
     input: &mut TokenStream,
 ) -> ModalResult<Spanned<ElementPatternPredicate>> {
@@ -731,6 +737,12 @@ fn test_node_pattern_2() {
         assert_yaml_snapshot!(parsed);
     }
 
+    #[test]
+    fn test_node_pattern_3() {
+        let parsed = parse!(node_pattern, "(n:Person USING INDEX person_id {id: 42})");
+        assert_yaml_snapshot!(parsed);
+    }
+
     #[test]
     fn test_edge_pattern_1() {
         let parsed = parse!(edge_pattern, "-[e: Knows {since: 2025}]->");
@@ -1,23 +1,223 @@
-use winnow::combinator::{delimited, fail, opt, preceded, separated, seq};
+use winnow::combinator::{alt, dispatch, fail, opt, peek, preceded, repeat, separated, seq};
 use winnow::{ModalResult, Parser};
 
-use super::lexical::{binding_variable, field_name};
-use crate::ast::{Ident, LinearDataModifyingStatement, Procedure, SchemaRef, Yield, YieldItem};
+use super::common::node_pattern;
+use super::lexical::property_name;
+use super::query::{primitive_result_statement, simple_query_statement};
+use super::value_expr::{binding_variable_reference, value_expression};
+use crate::ast::{
+    DeleteStatement, Expr, InsertPatternList, LinearDataModifyingStatement, SetItem, SetItemList,
+    SimpleDataAccessingStatement, SimpleDataModifyingStatement,
+};
 use crate::lexer::TokenKind;
-use crate::parser::token::TokenStream;
-use crate::parser::utils::{SpannedParserExt, ToSpanned, def_parser_alias};
-use crate::span::Spanned;
+use crate::parser::token::{TokenStream, any};
+use crate::parser::utils::{SpannedParserExt, ToSpanned};
+use crate::span::{Spanned, VecSpanned};
 
 pub fn linear_data_modifying_statement(
     input: &mut TokenStream,
 ) -> ModalResult<Spanned<LinearDataModifyingStatement>> {
-    fail(input)
+    seq! {LinearDataModifyingStatement {
+        statements: repeat(1.., simple_data_accessing_statement),
+        result: opt(primitive_result_statement),
+    }}
+    .spanned()
+    .parse_next(input)
+}
+
+pub fn simple_data_accessing_statement(
+    input: &mut TokenStream,
+) -> ModalResult<Spanned<SimpleDataAccessingStatement>> {
+    dispatch! {peek(any);
+        kind if kind.is_prefix_of_simple_data_modifying_statement() => {
+            simple_data_modifying_statement.map_inner(SimpleDataAccessingStatement::Modifying)
+        },
+        _ => simple_query_statement.map_inner(SimpleDataAccessingStatement::Query),
+    }
+    .parse_next(input)
+}
+
+pub fn simple_data_modifying_statement(
+    input: &mut TokenStream,
+) -> ModalResult<Spanned<SimpleDataModifyingStatement>> {
+    dispatch! {peek(any);
+        TokenKind::Insert => insert_statement.map(SimpleDataModifyingStatement::Insert),
+        TokenKind::Set => set_statement.map(SimpleDataModifyingStatement::Set),
+        TokenKind::Delete | TokenKind::Detach | TokenKind::Nodetach => {
+            delete_statement.map(SimpleDataModifyingStatement::Delete)
+        },
+        _ => fail
+    }
+    .spanned()
+    .parse_next(input)
+}
+
+pub fn insert_statement(input: &mut TokenStream) -> ModalResult<InsertPatternList> {
+    preceded(
+        TokenKind::Insert,
+        separated(1.., node_pattern, TokenKind::Comma),
+    )
+    .parse_next(input)
+}
+
+pub fn set_statement(input: &mut TokenStream) -> ModalResult<SetItemList> {
+    preceded(TokenKind::Set, set_item_list).parse_next(input)
+}
+
+pub fn set_item_list(input: &mut TokenStream) -> ModalResult<VecSpanned<SetItem>> {
+    separated(1.., set_item, TokenKind::Comma).parse_next(input)
+}
+
+pub fn set_item(input: &mut TokenStream) -> ModalResult<Spanned<SetItem>> {
+    set_property_item.parse_next(input)
+}
+
+pub fn set_property_item(input: &mut TokenStream) -> ModalResult<Spanned<SetItem>> {
+    seq! {SetItem::Property {
+        element: binding_variable_reference,
+        _: TokenKind::Period,
+        property: property_name,
+        _: TokenKind::Equals,
+        value: value_expression,
+    }}
+    .spanned()
+    .parse_next(input)
+}
+
+pub fn delete_statement(input: &mut TokenStream) -> ModalResult<DeleteStatement> {
+    seq! {DeleteStatement {
+        detach: opt(alt((
+            TokenKind::Detach.value(true),
+            TokenKind::Nodetach.value(false),
+        )))
+        .map(|detach| detach.unwrap_or(false)),
+        _: TokenKind::Delete,
+        items: delete_item_list,
+    }}
+    .parse_next(input)
+}
+
+pub fn delete_item_list(input: &mut TokenStream) -> ModalResult<VecSpanned<Expr>> {
+    separated(1.., value_expression, TokenKind::Comma).parse_next(input)
 }
 
 #[cfg(all(test, feature = "serde"))]
 mod tests {
-    use insta::assert_yaml_snapshot;
-
     use super::*;
+    use crate::ast::{
+        ElementPattern, ElementPatternPredicate, Expr, Literal, ResultStatement,
+        SimpleQueryStatement, Value,
+    };
     use crate::parser::utils::parse;
+
+    #[test]
+    fn test_insert_statement() {
+        let statement = parse!(
+            linear_data_modifying_statement,
+            "INSERT (n:Person {name: 'alice'}), (:Person) RETURN n"
+        )
+        .unwrap();
+        let statement = statement.value();
+        let [insert] = statement.statements.as_slice() else {
+            panic!("expected one statement");
+        };
+        let SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Insert(patterns)) =
+            insert.value()
+        else {
+            panic!("expected an insert statement");
+        };
+        let [alice, anonymous] = patterns.as_slice() else {
+            panic!("expected two node patterns");
+        };
+        let ElementPattern::Node(filler) = alice.value() else {
+            panic!("expected a node pattern");
+        };
+        assert_eq!(filler.variable.as_ref().unwrap().value().as_str(), "n");
+        assert!(matches!(
+            filler.predicate.as_ref().map(|p| p.value()),
+            Some(ElementPatternPredicate::Property(_))
+        ));
+        let ElementPattern::Node(filler) = anonymous.value() else {
+            panic!("expected a node pattern");
+        };
+        assert!(filler.variable.is_none());
+        assert!(parse!(insert_statement, "INSERT").is_none());
+    }
+
+    #[test]
+    fn test_set_statement() {
+        let statement = parse!(
+            linear_data_modifying_statement,
+            "MATCH (n) SET n.age = 30, n.name = NULL RETURN n"
+        )
+        .unwrap();
+        let statement = statement.value();
+        let [query, set] = statement.statements.as_slice() else {
+            panic!("expected two statements");
+        };
+        assert!(matches!(
+            query.value(),
+            SimpleDataAccessingStatement::Query(SimpleQueryStatement::Match(_))
+        ));
+        let SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Set(items)) =
+            set.value()
+        else {
+            panic!("expected a set statement");
+        };
+        let [age, name] = items.as_slice() else {
+            panic!("expected two set items");
+        };
+        let SetItem::Property {
+            element,
+            property,
+            value,
+        } = age.value();
+        assert_eq!(element.value().as_str(), "n");
+        assert_eq!(property.value().as_str(), "age");
+        assert!(matches!(value.value(), Expr::Value(_)));
+        let SetItem::Property {
+            property, value, ..
+        } = name.value();
+        assert_eq!(property.value().as_str(), "name");
+        assert!(matches!(
+            value.value(),
+            Expr::Value(Value::Literal(Literal::Null))
+        ));
+        assert!(matches!(
+            statement.result.as_ref().map(|r| r.value()),
+            Some(ResultStatement::Return { .. })
+        ));
+    }
+
+    #[test]
+    fn test_set_statement_requires_property() {
+        assert!(parse!(set_statement, "SET n = 1").is_none());
+        assert!(parse!(set_statement, "SET").is_none());
+    }
+
+    #[test]
+    fn test_delete_statement() {
+        let statement = parse!(linear_data_modifying_statement, "MATCH (n) DELETE n, m").unwrap();
+        let [_, delete] = statement.value().statements.as_slice() else {
+            panic!("expected two statements");
+        };
+        let SimpleDataAccessingStatement::Modifying(SimpleDataModifyingStatement::Delete(delete)) =
+            delete.value()
+        else {
+            panic!("expected a delete statement");
+        };
+        assert!(!delete.detach);
+        assert_eq!(delete.items.len(), 2);
+        assert!(matches!(delete.items[0].value(), Expr::Variable(_)));
+    }
+
+    #[test]
+    fn test_detach_delete_statement() {
+        let delete = parse!(delete_statement, "DETACH DELETE n").unwrap();
+        assert!(delete.detach);
+        let delete = parse!(delete_statement, "NODETACH DELETE n").unwrap();
+        assert!(!delete.detach);
+        assert!(parse!(delete_statement, "DETACH n").is_none());
+        assert!(parse!(delete_statement, "DELETE").is_none());
+    }
 }
@@ -1,6 +1,33 @@
-use super::value_expr::boolean_value_expression;
-use crate::ast::Expr;
-use crate::parser::utils::def_parser_alias;
+use winnow::combinator::{delimited, dispatch, fail, separated_pair};
+use winnow::{ModalResult, Parser};
+
+use super::lexical::property_name;
+use super::value_expr::{binding_variable_reference, boolean_value_expression};
+use crate::ast::{Expr, Predicate};
+use crate::lexer::TokenKind;
+use crate::parser::token::{TokenStream, any};
+use crate::parser::utils::{ToSpanned, def_parser_alias};
 use crate::span::Spanned;
 
 def_parser_alias!(search_condition, boolean_value_expression, Spanned<Expr>);
+
+/// Parses a property exists predicate, i.e., `PROPERTY_EXISTS(n, name)`. The shorthand
+/// `EXISTS(n.name)` is accepted as well.
+pub fn property_exists_predicate(input: &mut TokenStream) -> ModalResult<Spanned<Predicate>> {
+    dispatch! {any;
+        TokenKind::PropertyExists => delimited(
+            TokenKind::LeftParen,
+            separated_pair(binding_variable_reference, TokenKind::Comma, property_name),
+            TokenKind::RightParen,
+        ),
+        TokenKind::Exists => delimited(
+            TokenKind::LeftParen,
+            separated_pair(binding_variable_reference, TokenKind::Period, property_name),
+            TokenKind::RightParen,
+        ),
+        _ => fail,
+    }
+    .map(|(element, property)| Predicate::PropertyExists { element, property })
+    .spanned()
+    .parse_next(input)
+}
@@ -8,6 +8,7 @@
     graph_pattern_binding_table, limit_clause, offset_clause, order_by_clause, use_graph_clause,
 };
 use super::lexical::identifier;
+use super::predicate::search_condition;
 use super::procedure_call::call_procedure_statement;
 use super::procedure_spec::nested_query_specification;
 use super::value_expr::{aggregating_value_expression, binding_variable_reference, set_quantifier};
@@ -209,20 +210,24 @@ pub fn return_statement(input: &mut TokenStream) -> ModalResult<Spanned<ReturnSt
 }
 
 pub fn return_statement_body(input: &mut TokenStream) -> ModalResult<Spanned<ReturnStatement>> {
-    let mut items = dispatch! {peek(any);
-        TokenKind::Asterisk => TokenKind::Asterisk.value(Return::All),
-        _ => return_item_list.map(Return::Items)
-    }
-    .spanned();
     seq! {ReturnStatement {
         quantifier: opt(set_quantifier),
-        items: items,
+        items: return_items,
         group_by: opt(group_by_clause)
     }}
     .spanned()
     .parse_next(input)
 }
 
+pub fn return_items(input: &mut TokenStream) -> ModalResult<Spanned<Return>> {
+    dispatch! {peek(any);
+        TokenKind::Asterisk => TokenKind::Asterisk.value(Return::All),
+        _ => return_item_list.map(Return::Items)
+    }
+    .spanned()
+    .parse_next(input)
+}
+
 pub fn return_item_list(input: &mut TokenStream) -> ModalResult<VecSpanned<ReturnItem>> {
     separated(1.., return_item, TokenKind::Comma).parse_next(input)
 }
@@ -266,13 +271,14 @@ pub fn simple_query_statement(
         | (TokenKind::Optional, TokenKind::LeftParen) => match_statement.map_inner(SimpleQueryStatement::Match),
         (TokenKind::Let, _) => fail,
         (TokenKind::For, _) => fail,
-        (TokenKind::Filter, _) => fail,
+        (TokenKind::Filter, _) => filter_statement.map(SimpleQueryStatement::Filter).spanned(),
         (TokenKind::Order, TokenKind::By)
         | (TokenKind::Offset, _)
         | (TokenKind::Limit, _)
         | (TokenKind::Skip, _) => order_by_and_page_statement.map_inner(SimpleQueryStatement::OrderByAndPage),
         (TokenKind::Call, _)
         | (TokenKind::Optional, TokenKind::Call) => call_query_statement.map_inner(SimpleQueryStatement::Call),
+        (TokenKind::With, _) => with_statement.map_inner(SimpleQueryStatement::With),
         _ => fail
     }
     .parse_next(input)
@@ -284,6 +290,21 @@ pub fn simple_query_statement(
     Spanned<CallProcedureStatement>
 );
 
+pub fn filter_statement(input: &mut TokenStream) -> ModalResult<Spanned<Expr>> {
+    preceded((TokenKind::Filter, opt(TokenKind::Where)), search_condition).parse_next(input)
+}
+
+pub fn with_statement(input: &mut TokenStream) -> ModalResult<Spanned<WithStatement>> {
+    seq! {WithStatement {
+        _: TokenKind::With,
+        quantifier: opt(set_quantifier),
+        items: return_items,
+        where_clause: opt(preceded(TokenKind::Where, search_condition))
+    }}
+    .spanned()
+    .parse_next(input)
+}
+
 pub fn match_statement(input: &mut TokenStream) -> ModalResult<Spanned<MatchStatement>> {
     dispatch! {peek(any);
         TokenKind::Match => simple_match_statement.map(MatchStatement::Simple),
@@ -364,6 +385,78 @@ fn test_ambient_linear_query_statement_2() {
         assert_yaml_snapshot!(query);
     }
 
+    #[test]
+    fn test_filter_statement() {
+        let statements = [
+            parse!(
+                ambient_linear_query_statement,
+                "CALL PROC() YIELD a FILTER a > 1 RETURN a"
+            ),
+            parse!(
+                ambient_linear_query_statement,
+                "CALL PROC() YIELD a FILTER WHERE a > 1 RETURN a"
+            ),
+        ];
+        for statement in statements {
+            let statement = statement.unwrap();
+            let AmbientLinearQueryStatement::Parts { parts, .. } = statement.value() else {
+                panic!("expected parts");
+            };
+            let [call, filter] = parts.as_slice() else {
+                panic!("expected two statements");
+            };
+            assert!(matches!(call.value(), SimpleQueryStatement::Call(_)));
+            let SimpleQueryStatement::Filter(condition) = filter.value() else {
+                panic!("expected a filter statement");
+            };
+            assert!(matches!(condition.value(), Expr::Binary { .. }));
+        }
+    }
+
+    #[test]
+    fn test_with_statement() {
+        let statement = parse!(
+            ambient_linear_query_statement,
+            "MATCH (n) WITH DISTINCT n, count(*) AS c WHERE c > 1 RETURN n"
+        )
+        .unwrap();
+        let AmbientLinearQueryStatement::Parts { parts, .. } = statement.value() else {
+            panic!("expected parts");
+        };
+        let [r#match, with] = parts.as_slice() else {
+            panic!("expected two statements");
+        };
+        assert!(matches!(r#match.value(), SimpleQueryStatement::Match(_)));
+        let SimpleQueryStatement::With(with) = with.value() else {
+            panic!("expected a with statement");
+        };
+        assert!(with.quantifier.is_some());
+        let Return::Items(items) = with.items.value() else {
+            panic!("expected return items");
+        };
+        let [n, c] = items.as_slice() else {
+            panic!("expected two items");
+        };
+        assert!(n.value().alias.is_none());
+        assert!(matches!(c.value().value.value(), Expr::Aggregate(_)));
+        assert_eq!(c.value().alias.as_ref().unwrap().value().as_str(), "c");
+        assert!(matches!(
+            with.where_clause.as_ref().unwrap().value(),
+            Expr::Binary { .. }
+        ));
+
+        let statement =
+            parse!(ambient_linear_query_statement, "MATCH (n) WITH * RETURN n").unwrap();
+        let AmbientLinearQueryStatement::Parts { parts, .. } = statement.value() else {
+            panic!("expected parts");
+        };
+        let SimpleQueryStatement::With(with) = parts[1].value() else {
+            panic!("expected a with statement");
+        };
+        assert_eq!(with.items.value(), &Return::All);
+        assert!(with.where_clause.is_none());
+    }
+
     #[test]
     fn test_ambient_linear_query_statement_limit_approximate_vector_distance() {
         let query = parse!(
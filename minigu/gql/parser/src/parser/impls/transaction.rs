@@ -1,10 +1,10 @@
-use winnow::combinator::{dispatch, empty, fail, preceded, separated};
+use winnow::combinator::{dispatch, empty, fail, peek, preceded, separated};
 use winnow::{ModalResult, Parser};
 
-use crate::ast::{StartTransaction, TransactionMode};
+use crate::ast::{IsolationLevel, StartTransaction, TransactionMode};
 use crate::lexer::TokenKind;
 use crate::parser::token::{TokenStream, any};
-use crate::parser::utils::ToSpanned;
+use crate::parser::utils::{SpannedParserExt, ToSpanned};
 use crate::span::Spanned;
 
 pub fn start_transaction_command(
@@ -12,13 +12,21 @@ pub fn start_transaction_command(
 ) -> ModalResult<Spanned<StartTransaction>> {
     preceded(
         (TokenKind::Start, TokenKind::Transaction),
-        separated(0.., transaction_access_mode, TokenKind::Comma),
+        separated(0.., transaction_mode, TokenKind::Comma),
     )
     .map(StartTransaction)
     .spanned()
     .parse_next(input)
 }
 
+pub fn transaction_mode(input: &mut TokenStream) -> ModalResult<Spanned<TransactionMode>> {
+    dispatch! {peek(any);
+        TokenKind::Isolation => isolation_level.map_inner(TransactionMode::IsolationLevel),
+        _ => transaction_access_mode,
+    }
+    .parse_next(input)
+}
+
 pub fn transaction_access_mode(input: &mut TokenStream) -> ModalResult<Spanned<TransactionMode>> {
     dispatch! {(any, any);
         (TokenKind::Read, TokenKind::Only) => empty.value(TransactionMode::ReadOnly),
@@ -29,6 +37,20 @@ pub fn transaction_access_mode(input: &mut TokenStream) -> ModalResult<Spanned<T
     .parse_next(input)
 }
 
+pub fn isolation_level(input: &mut TokenStream) -> ModalResult<Spanned<IsolationLevel>> {
+    preceded(
+        (TokenKind::Isolation, TokenKind::Level),
+        dispatch! {any;
+            TokenKind::Read => TokenKind::Committed.value(IsolationLevel::ReadCommitted),
+            TokenKind::Snapshot => empty.value(IsolationLevel::Snapshot),
+            TokenKind::Serializable => empty.value(IsolationLevel::Serializable),
+            _ => fail
+        },
+    )
+    .spanned()
+    .parse_next(input)
+}
+
 #[cfg(all(test, feature = "serde"))]
 mod tests {
     use insta::assert_yaml_snapshot;
@@ -50,4 +72,19 @@ fn test_start_transaction_command_2() {
         );
         assert_yaml_snapshot!(parsed);
     }
+
+    #[test]
+    fn test_start_transaction_command_3() {
+        let parsed = parse!(
+            start_transaction_command,
+            "start transaction isolation level read committed, read only"
+        );
+        assert_yaml_snapshot!(parsed);
+    }
+
+    #[test]
+    fn test_isolation_level() {
+        let parsed = parse!(isolation_level, "isolation level serializable");
+        assert_yaml_snapshot!(parsed);
+    }
 }
@@ -502,6 +502,7 @@ pub fn predefined_type(input: &mut TokenStream) -> ModalResult<Spanned<ValueType
         TokenKind::Vector => vector_type,
         kind if kind.is_prefix_of_signed_exact_numeric_type() => signed_binary_exact_numeric_type,
         kind if kind.is_prefix_of_unsigned_exact_numeric_type() => unsigned_binary_exact_numeric_type,
+        kind if kind.is_prefix_of_approximate_numeric_type() => approximate_numeric_type,
         kind if kind.is_prefix_of_temporal_type() => temporal_type,
         _ => fail
     }
@@ -709,6 +710,46 @@ pub fn unsigned_binary_exact_numeric_type(
     .parse_next(input)
 }
 
+pub fn approximate_numeric_type(input: &mut TokenStream) -> ModalResult<Spanned<ValueType>> {
+    seq! {ValueType::Float {
+        kind: approximate_numeric_type_kind,
+        not_null: opt(not_null).map(|not_null| not_null.is_some()),
+    }}
+    .spanned()
+    .parse_next(input)
+}
+
+pub fn approximate_numeric_type_kind(
+    input: &mut TokenStream,
+) -> ModalResult<Spanned<FloatTypeKind>> {
+    dispatch! {any;
+        TokenKind::Float16 => empty.value(FloatTypeKind::Float16),
+        TokenKind::Float32 => empty.value(FloatTypeKind::Float32),
+        TokenKind::Float64 => empty.value(FloatTypeKind::Float64),
+        TokenKind::Float128 => empty.value(FloatTypeKind::Float128),
+        TokenKind::Float256 => empty.value(FloatTypeKind::Float256),
+        TokenKind::Real => empty.value(FloatTypeKind::Real),
+        TokenKind::Double => opt(TokenKind::Precision).value(FloatTypeKind::Double),
+        TokenKind::Float => {
+            opt(delimited(
+                TokenKind::LeftParen,
+                (precision, opt(preceded(TokenKind::Comma, scale))),
+                TokenKind::RightParen,
+            ))
+            .map(|precision_scale| {
+                let (precision, scale) = precision_scale.unzip();
+                FloatTypeKind::Float {
+                    precision: precision.map(Box::new),
+                    scale: scale.flatten().map(Box::new),
+                }
+            })
+        },
+        _ => fail,
+    }
+    .spanned()
+    .parse_next(input)
+}
+
 pub fn decimal_numeric_type(input: &mut TokenStream) -> ModalResult<Spanned<ValueType>> {
     preceded(
         one_of((TokenKind::Decimal, TokenKind::Dec)),
@@ -1019,6 +1060,18 @@ fn test_exact_numeric_type_5() {
         assert_yaml_snapshot!(parsed);
     }
 
+    #[test]
+    fn test_approximate_numeric_type_1() {
+        let parsed = parse!(value_type, "double precision not null");
+        assert_yaml_snapshot!(parsed);
+    }
+
+    #[test]
+    fn test_approximate_numeric_type_2() {
+        let parsed = parse!(value_type, "float(10)");
+        assert_yaml_snapshot!(parsed);
+    }
+
     #[test]
     fn test_vector_type_1() {
         let parsed = parse!(value_type, "vector(128)");
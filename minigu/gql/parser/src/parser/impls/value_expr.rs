@@ -5,9 +5,11 @@
 use winnow::{ModalResult, Parser};
 
 use super::lexical::{
-    boolean_literal, general_parameter_reference, property_name, regular_identifier,
-    unsigned_integer, unsigned_literal, unsigned_numeric_literal,
+    boolean_literal, character_string_literal, general_parameter_reference, property_name,
+    regular_identifier, unsigned_integer, unsigned_literal, unsigned_numeric_literal,
 };
+use super::predicate::property_exists_predicate;
+use super::type_element::value_type;
 use crate::ast::*;
 use crate::imports::{Box, Vec};
 use crate::lexer::TokenKind;
@@ -36,10 +38,11 @@ macro_rules! predefined_value_function {
 const PREC_IS: Precedence = 3;
 const PREC_NOT: Precedence = 4;
 const PREC_CMP: Precedence = 5;
-const PREC_CONCAT: Precedence = 6;
-const PREC_ADD_SUB: Precedence = 7;
-const PREC_MUL_DIV: Precedence = 8;
-const PREC_PLUS_MINUS: Precedence = 9;
+const PREC_BITWISE: Precedence = 6;
+const PREC_CONCAT: Precedence = 7;
+const PREC_ADD_SUB: Precedence = 8;
+const PREC_MUL_DIV: Precedence = 9;
+const PREC_PLUS_MINUS: Precedence = 10;
 
 fn value_expression_prefix(input: &mut TokenStream) -> ModalResult<(Precedence, Spanned<UnaryOp>)> {
     dispatch! {any;
@@ -56,14 +59,22 @@ fn value_expression_prefix(input: &mut TokenStream) -> ModalResult<(Precedence,
 enum SuffixOp {
     Is(Spanned<BooleanLiteral>),
     IsNot(Spanned<BooleanLiteral>),
+    /// `IS [NOT] NULL`. The span covers the whole suffix.
+    Null(Spanned<bool>),
 }
 
 fn value_expression_suffix(input: &mut TokenStream) -> ModalResult<(Precedence, SuffixOp)> {
-    dispatch! {peek((any, any));
-        (TokenKind::Is, TokenKind::Not) => {
+    dispatch! {peek((any, any, opt(any)));
+        (TokenKind::Is, TokenKind::Null, _) => {
+            (TokenKind::Is, TokenKind::Null).value(false).spanned().map(|not| (PREC_IS, SuffixOp::Null(not)))
+        },
+        (TokenKind::Is, TokenKind::Not, Some(TokenKind::Null)) => {
+            (TokenKind::Is, TokenKind::Not, TokenKind::Null).value(true).spanned().map(|not| (PREC_IS, SuffixOp::Null(not)))
+        },
+        (TokenKind::Is, TokenKind::Not, _) => {
             preceded((TokenKind::Is, TokenKind::Not), boolean_literal).map(|truth| (PREC_IS, SuffixOp::IsNot(truth)))
         },
-        (TokenKind::Is, _) => {
+        (TokenKind::Is, _, _) => {
             preceded(TokenKind::Is, boolean_literal).map(|truth| (PREC_IS, SuffixOp::Is(truth)))
         },
         _ => fail,
@@ -84,11 +95,17 @@ fn value_expression_infix(
         TokenKind::GreaterThanOrEquals => empty.value((Assoc::Left, PREC_CMP, BinaryOp::Ge)),
         TokenKind::Equals => empty.value((Assoc::Left, PREC_CMP, BinaryOp::Eq)),
         TokenKind::NotEquals => empty.value((Assoc::Left, PREC_CMP, BinaryOp::Ne)),
+        TokenKind::Ampersand => empty.value((Assoc::Left, PREC_BITWISE, BinaryOp::BitAnd)),
+        TokenKind::VerticalBar => empty.value((Assoc::Left, PREC_BITWISE, BinaryOp::BitOr)),
+        TokenKind::Circumflex => empty.value((Assoc::Left, PREC_BITWISE, BinaryOp::BitXor)),
+        TokenKind::DoubleLeftAngleBracket => empty.value((Assoc::Left, PREC_BITWISE, BinaryOp::ShiftLeft)),
+        TokenKind::DoubleRightAngleBracket => empty.value((Assoc::Left, PREC_BITWISE, BinaryOp::ShiftRight)),
         TokenKind::Concatenation => empty.value((Assoc::Left, PREC_CONCAT, BinaryOp::Concat)),
         TokenKind::Plus => empty.value((Assoc::Left, PREC_ADD_SUB, BinaryOp::Add)),
         TokenKind::Minus => empty.value((Assoc::Left, PREC_ADD_SUB, BinaryOp::Sub)),
         TokenKind::Asterisk => empty.value((Assoc::Left, PREC_MUL_DIV, BinaryOp::Mul)),
         TokenKind::Solidus => empty.value((Assoc::Left, PREC_MUL_DIV, BinaryOp::Div)),
+        TokenKind::Percent => empty.value((Assoc::Left, PREC_MUL_DIV, BinaryOp::Rem)),
         _ => fail,
     }
     .spanned()
@@ -134,6 +151,16 @@ pub fn value_expression(input: &mut TokenStream) -> ModalResult<Spanned<Expr>> {
                     span,
                 ))
             }
+            SuffixOp::Null(Spanned(not, suffix)) => {
+                let span = a.1.start..suffix.end;
+                Ok(Spanned(
+                    Expr::Predicate(Predicate::Null {
+                        expr: Box::new(a),
+                        not,
+                    }),
+                    span,
+                ))
+            }
         },
         |a, op, b| {
             let span = a.1.start..b.1.end;
@@ -155,7 +182,9 @@ fn value_expression_operand(input: &mut TokenStream) -> ModalResult<Spanned<Expr
         (kind, Some(TokenKind::LeftParen))
             if kind.is_prefix_of_value_function() =>
         {
-            value_function.map_inner(Expr::Function)
+            (value_function.map_inner(Expr::Function), repeat(0.., trailing_name))
+                .map(property_access)
+                .spanned()
         },
         _ => {
             value_expression_primary
@@ -167,7 +196,9 @@ fn value_expression_operand(input: &mut TokenStream) -> ModalResult<Spanned<Expr
 pub fn value_expression_primary_inner(input: &mut TokenStream) -> ModalResult<Spanned<Expr>> {
     dispatch! {peek(any);
         TokenKind::Path => path_value_constructor.map_inner(Expr::Path),
+        TokenKind::Cast => cast_specification,
         TokenKind::Case | TokenKind::Coalesce | TokenKind::Nullif => case_expression,
+        TokenKind::PropertyExists | TokenKind::Exists => property_exists_predicate.map_inner(Expr::Predicate),
         kind if kind.is_prefix_of_aggregate_function() => aggregate_function.map_inner(Expr::Aggregate),
         _ => unsigned_value_specification.map_inner(Expr::Value),
     }
@@ -180,24 +211,38 @@ pub fn value_expression_primary(input: &mut TokenStream) -> ModalResult<Spanned<
         kind if kind.is_prefix_of_regular_identifier() => binding_variable_reference.map_inner(Expr::Variable),
         _ => value_expression_primary_inner,
     };
-    (
-        base,
-        repeat(0.., preceded(TokenKind::Period, property_name)),
-    )
-        .map(|(source, trailing_names): (_, Vec<_>)| {
-            if trailing_names.is_empty() {
-                source.0
-            } else {
-                Expr::Property {
-                    source: Box::new(source),
-                    trailing_names,
-                }
-            }
-        })
+    (base, repeat(0.., trailing_name))
+        .map(property_access)
         .spanned()
         .parse_next(input)
 }
 
+/// Returns the access to the trailing names of `source`, if any, or `source` itself otherwise.
+fn property_access((source, trailing_names): (Spanned<Expr>, Vec<Spanned<Ident>>)) -> Expr {
+    if trailing_names.is_empty() {
+        source.0
+    } else {
+        Expr::Property {
+            source: Box::new(source),
+            trailing_names,
+        }
+    }
+}
+
+/// Parses the name of a property or a field following a value, either as `.name` or as
+/// `['name']`.
+pub fn trailing_name(input: &mut TokenStream) -> ModalResult<Spanned<Ident>> {
+    alt((
+        preceded(TokenKind::Period, property_name),
+        delimited(
+            TokenKind::LeftBracket,
+            character_string_literal.map_inner(|literal| literal.literal),
+            TokenKind::RightBracket,
+        ),
+    ))
+    .parse_next(input)
+}
+
 pub fn parenthesized_value_expression(input: &mut TokenStream) -> ModalResult<Spanned<Expr>> {
     delimited(
         TokenKind::LeftParen,
@@ -226,6 +271,19 @@ pub fn non_parenthesized_value_expression_primary_special_case(
     fail(input)
 }
 
+pub fn cast_specification(input: &mut TokenStream) -> ModalResult<Spanned<Expr>> {
+    seq! {Expr::Cast {
+        _: TokenKind::Cast,
+        _: TokenKind::LeftParen,
+        expr: value_expression.map(Box::new),
+        _: TokenKind::As,
+        value_type: value_type.map(Box::new),
+        _: TokenKind::RightParen,
+    }}
+    .spanned()
+    .parse_next(input)
+}
+
 pub fn case_expression(input: &mut TokenStream) -> ModalResult<Spanned<Expr>> {
     dispatch! {peek(any);
         TokenKind::Nullif | TokenKind::Coalesce => {
@@ -631,6 +689,30 @@ fn test_value_expression_3() {
         assert_yaml_snapshot!(parsed);
     }
 
+    #[test]
+    fn test_value_expression_4() {
+        let parsed = parse!(value_expression, "a | b & 1 << c % 2");
+        assert_yaml_snapshot!(parsed);
+    }
+
+    #[test]
+    fn test_cast_specification() {
+        let parsed = parse!(value_expression, "cast(a as int32)");
+        assert_yaml_snapshot!(parsed);
+    }
+
+    #[test]
+    fn test_value_expression_primary_trailing_names() {
+        let parsed = parse!(value_expression_primary, "m['key'].b");
+        assert_yaml_snapshot!(parsed);
+    }
+
+    #[test]
+    fn test_value_expression_function_trailing_names() {
+        let parsed = parse!(value_expression, "properties(n)['name'].first");
+        assert_yaml_snapshot!(parsed);
+    }
+
     #[test]
     fn test_parenthesized_value_expression() {
         let parsed = parse!(parenthesized_value_expression, "(1 + 1)");
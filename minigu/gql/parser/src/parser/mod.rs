@@ -1,8 +1,11 @@
 pub use options::ParseOptions;
-pub use token::{Token, tokenize, tokenize_full};
+pub use token::{
+    LosslessToken, Token, Trivia, TriviaKind, tokenize, tokenize_full, tokenize_lossless,
+};
 
 use crate::ast::Program;
 use crate::error::Error;
+use crate::imports::Vec;
 use crate::span::Spanned;
 
 mod impls;
@@ -31,3 +34,28 @@
 pub fn parse_gql(gql: &str) -> Result<Spanned<Program>, Error> {
     ParseOptions::new().parse(gql)
 }
+
+/// Parses a script of semicolon-separated GQL statements with default options.
+///
+/// See [`ParseOptions::parse_script`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// # use gql_parser::parse_gql_script;
+/// let input = "SESSION CLOSE; COMMIT";
+/// let programs = parse_gql_script(input).unwrap();
+/// assert_eq!(programs.len(), 2);
+/// assert_eq!(programs[1].span(), 15..21);
+/// ```
+pub fn parse_gql_script(gql: &str) -> Result<Vec<Spanned<Program>>, Error> {
+    ParseOptions::new().parse_script(gql)
+}
+
+/// Parses a script of semicolon-separated GQL statements with default options, collecting all
+/// errors instead of stopping at the first one.
+///
+/// See [`ParseOptions::parse_recover`] for more information.
+pub fn parse_gql_recover(gql: &str) -> (Vec<Spanned<Program>>, Vec<Error>) {
+    ParseOptions::new().parse_recover(gql)
+}
@@ -2,9 +2,10 @@
 use winnow::Parser;
 
 use super::impls::gql_program;
-use super::token::{Token, build_token_stream, tokenize};
+use super::token::{Token, build_token_stream, tokenize, tokenize_full};
 use crate::ast::Program;
-use crate::error::Error;
+use crate::error::{Error, TokenErrorKind, TokenizeError};
+use crate::imports::Vec;
 use crate::span::Spanned;
 
 /// Options which can be used to configure the behavior of the parser.
@@ -64,6 +65,9 @@ pub fn unescape(&mut self, unescape: bool) -> &mut Self {
     /// Parses a GQL query `gql` into a spanned abstract syntax tree with the options specified by
     /// `self`.
     ///
+    /// A query made of whitespace and comments only is parsed into an empty program, i.e., one
+    /// without any activity which does not close the session, spanning the whole query.
+    ///
     /// # Errors
     ///
     /// This function will return an error if `gql` is not a valid GQL query. The error will carry
@@ -82,9 +86,82 @@ pub fn unescape(&mut self, unescape: bool) -> &mut Self {
     /// ```
     pub fn parse(&self, gql: &str) -> Result<Spanned<Program>, Error> {
         let tokens = tokenize(gql).map_err(|e| Error::from_tokenize_error(gql, e))?;
+        if tokens.is_empty() {
+            let program = Program {
+                activity: None,
+                session_close: false,
+            };
+            return Ok(Spanned(program, 0..gql.len()));
+        }
         self.parse_tokens(gql, &tokens)
     }
 
+    /// Parses a script consisting of multiple GQL statements separated by semicolons, with the
+    /// options specified by `self`.
+    ///
+    /// Each statement is parsed into its own spanned abstract syntax tree. Spans are relative to
+    /// the whole script, so `&gql[program.span()]` is the source text of a statement. Empty
+    /// statements are skipped, and the trailing semicolon is optional.
+    ///
+    /// Unlike naive string splitting, semicolons inside quoted sequences and comments do not
+    /// terminate a statement.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the error of the first statement that is not a valid GQL query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gql_parser::ParseOptions;
+    /// let input = "SESSION CLOSE; COMMIT;";
+    /// let programs = ParseOptions::new().parse_script(input).unwrap();
+    /// assert_eq!(programs.len(), 2);
+    /// assert_eq!(&input[programs[1].span()], "COMMIT");
+    /// ```
+    pub fn parse_script(&self, gql: &str) -> Result<Vec<Spanned<Program>>, Error> {
+        split_statements(gql)
+            .into_iter()
+            .map(|statement| {
+                let tokens = statement.map_err(|e| Error::from_tokenize_error(gql, e))?;
+                self.parse_tokens(gql, &tokens)
+            })
+            .collect()
+    }
+
+    /// Parses a script like [`ParseOptions::parse_script`], but does not stop at the first error.
+    ///
+    /// When a statement fails to parse, its error is recorded and parsing resumes at the next
+    /// statement boundary (i.e., after the next semicolon). This is useful for editors and linters
+    /// that want to report every problem in a script at once.
+    ///
+    /// Returns the successfully parsed statements (a partial AST of the script) along with the
+    /// errors of the statements that could not be parsed, both in source order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gql_parser::ParseOptions;
+    /// let input = "COMMIT; MATCH (n RETURN n; ROLLBACK";
+    /// let (programs, errors) = ParseOptions::new().parse_recover(input);
+    /// assert_eq!(programs.len(), 2);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn parse_recover(&self, gql: &str) -> (Vec<Spanned<Program>>, Vec<Error>) {
+        let mut programs = Vec::new();
+        let mut errors = Vec::new();
+        for statement in split_statements(gql) {
+            let result = statement
+                .map_err(|e| Error::from_tokenize_error(gql, e))
+                .and_then(|tokens| self.parse_tokens(gql, &tokens));
+            match result {
+                Ok(program) => programs.push(program),
+                Err(e) => errors.push(e),
+            }
+        }
+        (programs, errors)
+    }
+
     /// Parses the tokens into a spanned abstract syntax tree with the options specified by
     /// `self`.
     ///
@@ -116,6 +193,39 @@ pub fn parse_tokens(&self, gql: &str, tokens: &[Token]) -> Result<Spanned<Progra
     }
 }
 
+/// Splits the tokens of `gql` into statements separated by semicolons, skipping empty ones.
+///
+/// A statement containing an invalid token is reported as the first such error, and the rest of it
+/// (up to the next semicolon) is skipped.
+fn split_statements(gql: &str) -> Vec<Result<Vec<Token<'_>>, TokenizeError<'_>>> {
+    let mut statements = Vec::new();
+    let mut current: Result<Vec<Token>, TokenizeError> = Ok(Vec::new());
+    for token in tokenize_full(gql) {
+        match token {
+            Err(e) if *e.kind() == TokenErrorKind::InvalidToken && e.slice() == ";" => {
+                let statement = core::mem::replace(&mut current, Ok(Vec::new()));
+                if !matches!(&statement, Ok(tokens) if tokens.is_empty()) {
+                    statements.push(statement);
+                }
+            }
+            Ok(token) => {
+                if let Ok(tokens) = &mut current {
+                    tokens.push(token);
+                }
+            }
+            Err(e) => {
+                if current.is_ok() {
+                    current = Err(e);
+                }
+            }
+        }
+    }
+    if !matches!(&current, Ok(tokens) if tokens.is_empty()) {
+        statements.push(current);
+    }
+    statements
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct ParseOptionsInner {
     unescape: bool,
@@ -132,3 +242,69 @@ pub(super) fn unescape(&self) -> bool {
         self.unescape
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty() {
+        for input in [
+            "",
+            " \n\t",
+            "// comment\n-- another one\n/* and a bracketed one */",
+        ] {
+            let program = ParseOptions::new().parse(input).unwrap();
+            assert_eq!(program.span(), 0..input.len());
+            assert!(program.value().activity.is_none());
+            assert!(!program.value().session_close);
+        }
+        // Unterminated comments are still errors.
+        assert!(ParseOptions::new().parse("/* comment").is_err());
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let input =
+            "SESSION SET SCHEMA /default;\nMATCH (n) WHERE n.name = 'a;b' RETURN n;\n;\nCOMMIT";
+        let programs = ParseOptions::new().parse_script(input).unwrap();
+        let statements: Vec<_> = programs.iter().map(|p| &input[p.span()]).collect();
+        assert_eq!(
+            statements,
+            vec![
+                "SESSION SET SCHEMA /default",
+                "MATCH (n) WHERE n.name = 'a;b' RETURN n",
+                "COMMIT"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_recover() {
+        let input = "MATCH (n RETURN n;\nCOMMIT;\nSESSION SET GRAPH;\nROLLBACK";
+        let (programs, errors) = ParseOptions::new().parse_recover(input);
+        let statements: Vec<_> = programs.iter().map(|p| &input[p.span()]).collect();
+        assert_eq!(statements, vec!["COMMIT", "ROLLBACK"]);
+        assert_eq!(errors.len(), 2);
+        // Each error points into the line of its statement, not past the end of the input.
+        assert_eq!(errors[0].position().map(|(line, _)| line), Some(1));
+        assert_eq!(errors[1].position().map(|(line, _)| line), Some(3));
+    }
+
+    #[test]
+    fn test_parse_recover_invalid_token() {
+        let input = "COMMIT #; ROLLBACK";
+        let (programs, errors) = ParseOptions::new().parse_recover(input);
+        assert_eq!(programs.len(), 1);
+        assert!(matches!(errors.as_slice(), [Error::InvalidToken(_)]));
+    }
+
+    #[test]
+    fn test_parse_script_error() {
+        let input = "COMMIT; MATCH (n RETURN n; COMMIT;";
+        assert!(matches!(
+            ParseOptions::new().parse_script(input),
+            Err(Error::Unexpected(_))
+        ));
+    }
+}
@@ -118,10 +118,13 @@ pub(super) fn unescape(&self) -> bool {
 /// # use gql_parser::{tokenize_full, Token, TokenKind};
 /// # use gql_parser::error::{TokenizeError, TokenErrorKind};
 /// let tokens = tokenize_full("COMMIT;");
-/// assert_eq!(tokens, vec![
-///     Ok(Token::new(TokenKind::Commit, "COMMIT", 0..6)),
-///     Err(TokenizeError::new(TokenErrorKind::InvalidToken, ";", 6..7))
-/// ]);
+/// assert_eq!(
+///     tokens,
+///     vec![
+///         Ok(Token::new(TokenKind::Commit, "COMMIT", 0..6)),
+///         Err(TokenizeError::new(TokenErrorKind::InvalidToken, ";", 6..7))
+///     ]
+/// );
 /// ```
 pub fn tokenize_full(input: &str) -> Vec<Result<Token<'_>, TokenizeError<'_>>> {
     let mut lexer = TokenKind::lexer(input).spanned();
@@ -173,6 +176,134 @@ pub fn tokenize(input: &str) -> Result<Vec<Token<'_>>, TokenizeError<'_>> {
     Ok(tokens)
 }
 
+/// The kind of a [`Trivia`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    Comment,
+}
+
+/// A piece of source text which is ignored by the parser, i.e., whitespaces or a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia<'a> {
+    kind: TriviaKind,
+    slice: &'a str,
+    span: Range<usize>,
+}
+
+impl<'a> Trivia<'a> {
+    #[inline]
+    pub fn new(kind: TriviaKind, slice: &'a str, span: Range<usize>) -> Self {
+        Self { kind, slice, span }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> TriviaKind {
+        self.kind
+    }
+
+    #[inline]
+    pub fn slice(&self) -> &'a str {
+        self.slice
+    }
+
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// An element of the stream returned by [`tokenize_lossless`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LosslessToken<'a> {
+    Token(Token<'a>),
+    Trivia(Trivia<'a>),
+    Error(TokenizeError<'a>),
+}
+
+impl<'a> LosslessToken<'a> {
+    #[inline]
+    pub fn slice(&self) -> &'a str {
+        match self {
+            LosslessToken::Token(token) => token.slice(),
+            LosslessToken::Trivia(trivia) => trivia.slice(),
+            LosslessToken::Error(e) => e.slice(),
+        }
+    }
+
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            LosslessToken::Token(token) => token.span(),
+            LosslessToken::Trivia(trivia) => trivia.span(),
+            LosslessToken::Error(e) => e.span().clone(),
+        }
+    }
+}
+
+/// Tokenizes the input string, preserving whitespaces and comments as [`Trivia`].
+///
+/// Unlike [`tokenize_full`], the spans of the returned elements cover the whole input without gaps,
+/// so concatenating their slices reconstructs the input verbatim. This is intended for tools like
+/// formatters. Since locating trivia takes an extra pass over the skipped text, prefer
+/// [`tokenize`] or [`tokenize_full`] when trivia is not needed.
+///
+/// # Examples
+///
+/// ```
+/// # use gql_parser::{tokenize_lossless, LosslessToken, Token, TokenKind, Trivia, TriviaKind};
+/// let tokens = tokenize_lossless("COMMIT // done");
+/// assert_eq!(tokens, vec![
+///     LosslessToken::Token(Token::new(TokenKind::Commit, "COMMIT", 0..6)),
+///     LosslessToken::Trivia(Trivia::new(TriviaKind::Whitespace, " ", 6..7)),
+///     LosslessToken::Trivia(Trivia::new(TriviaKind::Comment, "// done", 7..14)),
+/// ]);
+/// ```
+pub fn tokenize_lossless(input: &str) -> Vec<LosslessToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    for token in tokenize_full(input) {
+        let token = match token {
+            Ok(token) => LosslessToken::Token(token),
+            Err(e) => LosslessToken::Error(e),
+        };
+        let span = token.span();
+        push_trivia(input, offset..span.start, &mut tokens);
+        offset = span.end;
+        tokens.push(token);
+    }
+    push_trivia(input, offset..input.len(), &mut tokens);
+    tokens
+}
+
+/// Splits the text skipped by the lexer in `range` into whitespaces and comments.
+fn push_trivia<'a>(input: &'a str, range: Range<usize>, tokens: &mut Vec<LosslessToken<'a>>) {
+    let mut start = range.start;
+    while start < range.end {
+        let rest = &input[start..range.end];
+        let (kind, len) = if rest.starts_with("//") || rest.starts_with("--") {
+            let len = rest.find(['\r', '\n']).unwrap_or(rest.len());
+            (TriviaKind::Comment, len)
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            let len = body.find("*/").map_or(rest.len(), |len| len + 4);
+            (TriviaKind::Comment, len)
+        } else {
+            let len = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            debug_assert!(len > 0, "skipped text should be whitespaces or comments");
+            (TriviaKind::Whitespace, len.max(1))
+        };
+        let span = start..start + len;
+        tokens.push(LosslessToken::Trivia(Trivia::new(
+            kind,
+            &input[span.clone()],
+            span,
+        )));
+        start += len;
+    }
+}
+
 pub(super) fn build_token_stream<'a, 'b>(
     input: &'b [Token<'a>],
     options: ParseOptionsInner,
@@ -186,3 +317,67 @@ pub(super) fn build_token_stream<'a, 'b>(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reconstruct(input: &str) -> String {
+        tokenize_lossless(input)
+            .iter()
+            .map(LosslessToken::slice)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_lossless_reconstructs_input() {
+        let inputs = [
+            "",
+            "   ",
+            "MATCH (n:Person) RETURN n",
+            "  MATCH (n)\r\n\t-- find all\nWHERE n.name = 'a -- b' /* inline */ RETURN n // end\n",
+            "COMMIT;; ROLLBACK ^ /* unterminated",
+            "/**//*/ x */--\n//",
+        ];
+        for input in inputs {
+            assert_eq!(reconstruct(input), input);
+        }
+    }
+
+    #[test]
+    fn test_tokenize_lossless_trivia_kinds() {
+        let input = "RETURN 1 /* a */\n-- b\n";
+        let trivia: Vec<_> = tokenize_lossless(input)
+            .into_iter()
+            .filter_map(|token| match token {
+                LosslessToken::Trivia(trivia) => Some((trivia.kind(), trivia.slice())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            trivia,
+            vec![
+                (TriviaKind::Whitespace, " "),
+                (TriviaKind::Whitespace, " "),
+                (TriviaKind::Comment, "/* a */"),
+                (TriviaKind::Whitespace, "\n"),
+                (TriviaKind::Comment, "-- b"),
+                (TriviaKind::Whitespace, "\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_lossless_matches_tokenize_full() {
+        let input = "MATCH (n) -- comment\nRETURN n;";
+        let tokens: Vec<_> = tokenize_lossless(input)
+            .into_iter()
+            .filter_map(|token| match token {
+                LosslessToken::Token(token) => Some(Ok(token)),
+                LosslessToken::Error(e) => Some(Err(e)),
+                LosslessToken::Trivia(_) => None,
+            })
+            .collect();
+        assert_eq!(tokens, tokenize_full(input));
+    }
+}
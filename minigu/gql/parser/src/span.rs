@@ -44,3 +44,78 @@ pub fn map<F, O>(self, f: F) -> Spanned<O>
 
 /// Type alias for optional spanned values.
 pub type OptSpanned<T> = Option<Spanned<T>>;
+
+/// Translates a byte offset in `input` into a 1-based `(line, column)` pair.
+///
+/// Lines are separated by `\n` (so `\r\n` works as well), and columns are counted in characters
+/// rather than bytes. An offset equal to `input.len()` is allowed and refers to the position right
+/// after the last character.
+///
+/// # Panics
+///
+/// Panics if `offset` is out of bounds or not on a character boundary.
+///
+/// # Examples
+///
+/// ```
+/// # use gql_parser::span::offset_to_line_column;
+/// let input = "MATCH (n)\nRETURN m";
+/// assert_eq!(offset_to_line_column(input, 17), (2, 8));
+/// ```
+pub fn offset_to_line_column(input: &str, offset: usize) -> (usize, usize) {
+    assert!(
+        input.is_char_boundary(offset),
+        "`offset` must be a valid character boundary"
+    );
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Translates the start of `span` in `input` into a 1-based `(line, column)` pair.
+///
+/// See [`offset_to_line_column`] for details.
+///
+/// # Examples
+///
+/// ```
+/// # use gql_parser::span::span_to_line_column;
+/// let input = "MATCH (n)\nRETURN m";
+/// assert_eq!(span_to_line_column(input, &(17..18)), (2, 8));
+/// ```
+#[inline]
+pub fn span_to_line_column(input: &str, span: &Range<usize>) -> (usize, usize) {
+    offset_to_line_column(input, span.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_to_line_column_multi_line() {
+        let input = "MATCH (n:Person)\nWHERE n.age > 18\r\nRETURN n.name,\n       n.age";
+        let span = |token: &str| {
+            let start = input.find(token).unwrap();
+            start..start + token.len()
+        };
+        assert_eq!(span_to_line_column(input, &span("MATCH")), (1, 1));
+        assert_eq!(span_to_line_column(input, &span("Person")), (1, 10));
+        assert_eq!(span_to_line_column(input, &span("18")), (2, 15));
+        assert_eq!(span_to_line_column(input, &span("RETURN")), (3, 1));
+        assert_eq!(span_to_line_column(input, &span("n.age")), (2, 7));
+        assert_eq!(
+            span_to_line_column(input, &(input.len()..input.len())),
+            (4, 13)
+        );
+    }
+
+    #[test]
+    fn test_span_to_line_column_trailing_newline() {
+        let input = "COMMIT\n";
+        assert_eq!(offset_to_line_column(input, 6), (1, 7));
+        assert_eq!(offset_to_line_column(input, 7), (2, 1));
+    }
+}
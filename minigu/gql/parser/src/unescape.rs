@@ -57,9 +57,41 @@ pub fn unescape<const Q: char, const NO_ESCAPE: bool>(mut input: &str) -> Option
     Some(builder.finish())
 }
 
+/// Returns `name` as a delimited identifier, i.e., between backticks, which is parsed back into
+/// `name` whatever characters it contains, e.g., to refer to a graph named `my-graph`.
+pub fn quote_identifier(name: &str) -> SmolStr {
+    quote::<'`'>(name)
+}
+
+/// Returns `value` as a character string literal, i.e., between single quotes, which is parsed
+/// back into `value` whatever characters it contains.
+pub fn quote_string(value: &str) -> SmolStr {
+    quote::<'\''>(value)
+}
+
+/// Returns `input` between `Q`s, doubling the `Q`s and escaping the backslashes it contains.
+fn quote<const Q: char>(input: &str) -> SmolStr {
+    let mut builder = SmolStrBuilder::new();
+    builder.push(Q);
+    for c in input.chars() {
+        match c {
+            '\\' => builder.push_str("\\\\"),
+            c if c == Q => {
+                builder.push(Q);
+                builder.push(Q);
+            }
+            c => builder.push(c),
+        }
+    }
+    builder.push(Q);
+    builder.finish()
+}
+
 #[cfg(all(test, feature = "serde", feature = "std"))]
 mod tests {
-    use super::unescape;
+    use super::{quote_identifier, quote_string, unescape};
+    use crate::TokenKind;
+    use crate::parser::tokenize;
 
     #[test]
     fn test_unescape_1() {
@@ -84,4 +116,22 @@ fn test_unescape_4() {
         let unescaped = unescape::<'\'', false>(r#"''这是一个UTF8字符串\n''"#).unwrap();
         assert_eq!(unescaped, "'这是一个UTF8字符串\n'");
     }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote_identifier("my-graph"), "`my-graph`");
+        assert_eq!(quote_identifier("a`b\\c"), "`a``b\\\\c`");
+        assert_eq!(quote_string("it's"), "'it''s'");
+        for name in ["my-graph", "a`b\\c", "match", "'\"\n"] {
+            let quoted = quote_identifier(name);
+            let tokens = tokenize(&quoted).unwrap();
+            let [token] = tokens.as_slice() else {
+                panic!("expected a single token");
+            };
+            let TokenKind::AccentQuoted(quoted) = token.kind() else {
+                panic!("expected a delimited identifier, got {:?}", token.kind());
+            };
+            assert_eq!(quoted.unescape().unwrap(), name);
+        }
+    }
 }
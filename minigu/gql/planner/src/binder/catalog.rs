@@ -1,15 +1,19 @@
+use std::sync::Arc;
+
 use gql_parser::ast::{
     CatalogModifyingStatement, CreateGraphStatement, CreateGraphTypeStatement,
-    CreateSchemaStatement, DropGraphStatement, DropGraphTypeStatement, DropSchemaStatement,
+    CreateSchemaStatement, CreateViewStatement, DropGraphStatement, DropGraphTypeStatement,
+    DropSchemaStatement,
 };
 use minigu_common::error::not_implemented;
+use minigu_context::view::View;
 
 use super::Binder;
 use super::error::{BindError, BindResult};
 use crate::bound::{
     BoundCatalogModifyingStatement, BoundCreateGraphStatement, BoundCreateGraphTypeStatement,
-    BoundCreateSchemaStatement, BoundDropGraphStatement, BoundDropGraphTypeStatement,
-    BoundDropSchemaStatement,
+    BoundCreateSchemaStatement, BoundCreateViewStatement, BoundDropGraphStatement,
+    BoundDropGraphTypeStatement, BoundDropSchemaStatement,
 };
 
 impl Binder<'_> {
@@ -46,6 +50,9 @@ pub fn bind_catalog_modifying_statement(
             CatalogModifyingStatement::DropGraphType(statement) => self
                 .bind_drop_graph_type_statement(statement)
                 .map(BoundCatalogModifyingStatement::DropGraphType),
+            CatalogModifyingStatement::CreateView(statement) => self
+                .bind_create_view_statement(statement)
+                .map(BoundCatalogModifyingStatement::CreateView),
         }
     }
 
@@ -90,4 +97,37 @@ pub fn bind_drop_graph_type_statement(
     ) -> BindResult<BoundDropGraphTypeStatement> {
         not_implemented("drop graph type statement", None)
     }
+
+    pub fn bind_create_view_statement(
+        &mut self,
+        statement: &CreateViewStatement,
+    ) -> BindResult<BoundCreateViewStatement> {
+        let name = statement.name.value().clone();
+        let schema = self
+            .current_schema
+            .as_ref()
+            .ok_or(BindError::CurrentSchemaNotSpecified)?;
+        if let Some(procedure) = schema.get_procedure(&name)? {
+            if !statement.or_replace {
+                return Err(BindError::ProcedureAlreadyExists(name));
+            }
+            if !procedure.as_any().is::<View>() {
+                return Err(BindError::NotView(name));
+            }
+        }
+        // The query is bound as if the view were already created, so that referring to it from
+        // the query is reported as a recursion.
+        let query = statement.query.value();
+        let bound = self
+            .view_binder(name.clone())
+            .bind_composite_query_statement(query)?;
+        let schema = bound
+            .schema()
+            .ok_or_else(|| BindError::DataSchemaNotProvided(name.clone()))?;
+        Ok(BoundCreateViewStatement {
+            name,
+            or_replace: statement.or_replace,
+            view: Arc::new(View::new(query.clone(), schema)),
+        })
+    }
 }
@@ -0,0 +1,266 @@
+//! Implicit coercion of literals to declared property types.
+//!
+//! A literal written in a query (e.g., `{age: '25'}`) is checked against the property type recorded
+//! in the graph type. Conversions are only applied when they are unambiguous and lossless enough to
+//! be unsurprising; everything else is rejected at bind time.
+
+use minigu_common::data_type::LogicalType;
+use minigu_common::value::{F32, F64, ScalarValue};
+use smol_str::SmolStr;
+
+use super::error::{BindError, BindResult};
+use crate::bound::{BoundExpr, BoundExprKind};
+
+/// Coerces `expr` so that it can be stored in (or compared with) the property `property` of type
+/// `target`.
+///
+/// The following conversions are applied to literals:
+/// - integers to any integer type that can hold the value, or to a floating-point type;
+/// - `FLOAT64` to `FLOAT32`, if the value is in range;
+/// - numeric strings (e.g., `'25'`) to numeric types, if the whole string parses as the target;
+/// - `NULL` to a typed null, if the property is nullable.
+///
+/// Non-literal expressions must already have the target type.
+pub fn coerce_to_property_type(
+    expr: BoundExpr,
+    property: &str,
+    target: &LogicalType,
+    nullable: bool,
+) -> BindResult<BoundExpr> {
+    let mismatch = |actual: &LogicalType| BindError::PropertyTypeMismatch {
+        property: SmolStr::new(property),
+        expected: target.clone(),
+        actual: actual.clone(),
+    };
+    if &expr.logical_type == target {
+        if expr.nullable && !nullable && matches!(expr.kind, BoundExprKind::Value(_)) {
+            return Err(BindError::NullPropertyValue(SmolStr::new(property)));
+        }
+        return Ok(expr);
+    }
+    let BoundExprKind::Value(value) = &expr.kind else {
+        return Err(mismatch(&expr.logical_type));
+    };
+    if !is_untyped_null(&expr) {
+        let coerced = coerce_scalar(value, &expr.logical_type, target)
+            .ok_or_else(|| mismatch(&expr.logical_type))?;
+        return Ok(BoundExpr::value(coerced, target.clone(), expr.nullable));
+    }
+    if !nullable {
+        return Err(BindError::NullPropertyValue(SmolStr::new(property)));
+    }
+    let null = infer_null_type(expr, target);
+    if &null.logical_type != target {
+        return Err(mismatch(&null.logical_type));
+    }
+    Ok(null)
+}
+
+/// Gives `expr` the type `target` if it is an untyped `NULL` literal, i.e., turns it into the null
+/// value of `target`. Any other expression, or a `NULL` for which `target` has no null value (e.g.,
+/// vertices), is returned unchanged.
+pub fn infer_null_type(expr: BoundExpr, target: &LogicalType) -> BoundExpr {
+    if !is_untyped_null(&expr) {
+        return expr;
+    }
+    match ScalarValue::null_of(target) {
+        Some(null) => BoundExpr::value(null, target.clone(), true),
+        None => expr,
+    }
+}
+
+/// Gives `expr` the type `target`, e.g., for it to be passed as an argument of that type. Literals
+/// are converted as by [`coerce_to_property_type`], with `NULL` always allowed. Returns `None` if
+/// `expr` cannot have the type `target`.
+pub fn coerce_to_type(expr: BoundExpr, target: &LogicalType) -> Option<BoundExpr> {
+    if &expr.logical_type == target {
+        return Some(expr);
+    }
+    if is_untyped_null(&expr) {
+        let null = infer_null_type(expr, target);
+        return (&null.logical_type == target).then_some(null);
+    }
+    let BoundExprKind::Value(value) = &expr.kind else {
+        return None;
+    };
+    let coerced = coerce_scalar(value, &expr.logical_type, target)?;
+    Some(BoundExpr::value(coerced, target.clone(), expr.nullable))
+}
+
+/// Returns whether `expr` is a `NULL` literal whose type is not known yet.
+#[inline]
+pub fn is_untyped_null(expr: &BoundExpr) -> bool {
+    matches!(expr.kind, BoundExprKind::Value(ScalarValue::Null))
+}
+
+/// Returns whether values of type `source` can be explicitly cast to `target`, i.e., if the types
+/// are both numeric, a string and a numeric or boolean type, or the same type. Whether a given
+/// value can be cast, e.g., `'abc'` to `INT32`, is only known when the cast is evaluated.
+pub fn is_castable(source: &LogicalType, target: &LogicalType) -> bool {
+    let is_numeric = |ty| is_integer(ty) || is_float(ty);
+    let is_string_convertible = |ty| is_numeric(ty) || ty == &LogicalType::Boolean;
+    source == target
+        || source == &LogicalType::Null
+        || (is_numeric(source) && is_numeric(target))
+        || (source == &LogicalType::String && is_string_convertible(target))
+        || (is_string_convertible(source) && target == &LogicalType::String)
+}
+
+fn coerce_scalar(
+    value: &ScalarValue,
+    source: &LogicalType,
+    target: &LogicalType,
+) -> Option<ScalarValue> {
+    let allowed = match source {
+        _ if is_integer(source) => is_integer(target) || is_float(target),
+        LogicalType::Float32 | LogicalType::Float64 => is_float(target),
+        LogicalType::String => is_integer(target) || is_float(target),
+        _ => false,
+    };
+    if !allowed {
+        return None;
+    }
+    let coerced = match target {
+        LogicalType::Int8 => value.to_i8().ok()?.into(),
+        LogicalType::Int16 => value.to_i16().ok()?.into(),
+        LogicalType::Int32 => value.to_i32().ok()?.into(),
+        LogicalType::Int64 => value.to_i64().ok()?.into(),
+        LogicalType::UInt8 => value.to_u8().ok()?.into(),
+        LogicalType::UInt16 => value.to_u16().ok()?.into(),
+        LogicalType::UInt32 => value.to_u32().ok()?.into(),
+        LogicalType::UInt64 => value.to_u64().ok()?.into(),
+        LogicalType::Float32 => F32::from(value.to_f32().ok()?).into(),
+        LogicalType::Float64 => F64::from(value.to_f64().ok()?).into(),
+        _ => return None,
+    };
+    Some(coerced)
+}
+
+#[inline]
+pub fn is_integer(ty: &LogicalType) -> bool {
+    matches!(
+        ty,
+        LogicalType::Int8
+            | LogicalType::Int16
+            | LogicalType::Int32
+            | LogicalType::Int64
+            | LogicalType::UInt8
+            | LogicalType::UInt16
+            | LogicalType::UInt32
+            | LogicalType::UInt64
+    )
+}
+
+#[inline]
+pub fn is_float(ty: &LogicalType) -> bool {
+    matches!(ty, LogicalType::Float32 | LogicalType::Float64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(value: ScalarValue, ty: LogicalType) -> BoundExpr {
+        BoundExpr::value(value, ty, false)
+    }
+
+    #[test]
+    fn test_is_castable() {
+        assert!(is_castable(&LogicalType::String, &LogicalType::Int32));
+        assert!(is_castable(&LogicalType::Int64, &LogicalType::Float32));
+        assert!(is_castable(&LogicalType::Boolean, &LogicalType::String));
+        assert!(!is_castable(&LogicalType::Boolean, &LogicalType::Int32));
+        assert!(!is_castable(
+            &LogicalType::Vertex(vec![]),
+            &LogicalType::String
+        ));
+    }
+
+    #[test]
+    fn test_coerce_numeric_string() {
+        let expr = literal("25".into(), LogicalType::String);
+        let coerced = coerce_to_property_type(expr, "age", &LogicalType::Int32, false).unwrap();
+        assert_eq!(coerced.logical_type, LogicalType::Int32);
+        assert_eq!(
+            coerced.evaluate_scalar(),
+            Some(ScalarValue::Int32(Some(25)))
+        );
+    }
+
+    #[test]
+    fn test_coerce_integer_widening() {
+        let expr = literal(7i8.into(), LogicalType::Int8);
+        let coerced = coerce_to_property_type(expr, "score", &LogicalType::Float64, false).unwrap();
+        assert_eq!(
+            coerced.evaluate_scalar(),
+            Some(ScalarValue::Float64(Some(F64::from(7.0))))
+        );
+    }
+
+    #[test]
+    fn test_coerce_non_numeric_string() {
+        let expr = literal("twenty-five".into(), LogicalType::String);
+        let err = coerce_to_property_type(expr, "age", &LogicalType::Int32, false).unwrap_err();
+        assert!(matches!(
+            err,
+            BindError::PropertyTypeMismatch {
+                expected: LogicalType::Int32,
+                actual: LogicalType::String,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_coerce_out_of_range() {
+        let expr = literal(300i16.into(), LogicalType::Int16);
+        assert!(coerce_to_property_type(expr, "age", &LogicalType::Int8, false).is_err());
+    }
+
+    #[test]
+    fn test_coerce_null() {
+        let null = BoundExpr::value(ScalarValue::Null, LogicalType::Null, true);
+        let coerced =
+            coerce_to_property_type(null.clone(), "name", &LogicalType::String, true).unwrap();
+        assert_eq!(coerced.evaluate_scalar(), Some(ScalarValue::String(None)));
+        let err = coerce_to_property_type(null, "name", &LogicalType::String, false).unwrap_err();
+        assert!(matches!(err, BindError::NullPropertyValue(_)));
+    }
+
+    #[test]
+    fn test_coerce_null_to_nullable_int32() {
+        let null = BoundExpr::value(ScalarValue::Null, LogicalType::Null, true);
+        let coerced = coerce_to_property_type(null, "age", &LogicalType::Int32, true).unwrap();
+        assert_eq!(coerced.logical_type, LogicalType::Int32);
+        assert!(coerced.nullable);
+        assert_eq!(coerced.evaluate_scalar(), Some(ScalarValue::Int32(None)));
+    }
+
+    #[test]
+    fn test_infer_null_type() {
+        let null = BoundExpr::value(ScalarValue::Null, LogicalType::Null, true);
+        let typed = infer_null_type(null.clone(), &LogicalType::Int32);
+        assert_eq!(typed.logical_type, LogicalType::Int32);
+        assert!(!is_untyped_null(&typed));
+        // Vertices have no null value, so the literal stays untyped.
+        let vertex = LogicalType::Vertex(vec![]);
+        assert!(is_untyped_null(&infer_null_type(null, &vertex)));
+        // Other expressions are left alone.
+        let one = literal(1i8.into(), LogicalType::Int8);
+        let one = infer_null_type(one, &LogicalType::Int32);
+        assert_eq!(one.logical_type, LogicalType::Int8);
+    }
+
+    #[test]
+    fn test_coerce_to_type() {
+        let expr = literal(21i8.into(), LogicalType::Int8);
+        let coerced = coerce_to_type(expr, &LogicalType::Int64).unwrap();
+        assert_eq!(
+            coerced.evaluate_scalar(),
+            Some(ScalarValue::Int64(Some(21)))
+        );
+        // Only literals are converted.
+        let var = BoundExpr::variable("x".into(), LogicalType::Int8, false);
+        assert!(coerce_to_type(var, &LogicalType::Int64).is_none());
+    }
+}
@@ -1,18 +1,26 @@
 use std::sync::Arc;
 
 use gql_parser::ast::{
-    ElementPattern, ElementPatternFiller, GraphPattern, GraphPatternBindingTable, LabelExpr,
-    MatchMode, PathMode, PathPattern, PathPatternExpr, PathPatternPrefix,
+    ElementPattern, ElementPatternFiller, ElementPatternPredicate, FieldOrProperty, GraphPattern,
+    GraphPatternBindingTable, Ident, LabelExpr, MatchMode, PathMode, PathPattern, PathPatternExpr,
+    PathPatternPrefix,
 };
+use gql_parser::span::Spanned;
+use minigu_catalog::label_set::LabelSet;
+use minigu_catalog::provider::VertexTypeRef;
 use minigu_common::data_type::{DataField, DataSchema, LogicalType};
 use minigu_common::error::not_implemented;
+use minigu_common::types::{LabelId, PropertyId};
+use minigu_common::value::ScalarValue;
+use smol_str::SmolStr;
 
+use super::coercion::coerce_to_property_type;
 use super::error::{BindError, BindResult};
 use crate::binder::Binder;
 use crate::bound::{
-    BoundElementPattern, BoundExpr, BoundGraphPattern, BoundGraphPatternBindingTable,
-    BoundLabelExpr, BoundMatchMode, BoundPathMode, BoundPathPattern, BoundPathPatternExpr,
-    BoundVertexPattern,
+    BoundBinaryOp, BoundElementPattern, BoundExpr, BoundExprKind, BoundGraphPattern,
+    BoundGraphPatternBindingTable, BoundIndexLookup, BoundLabelExpr, BoundMatchMode, BoundPathMode,
+    BoundPathPattern, BoundPathPatternExpr, BoundVertexPattern,
 };
 
 impl Binder<'_> {
@@ -61,6 +69,9 @@ pub fn bind_graph_pattern(&mut self, pattern: &GraphPattern) -> BindResult<Bound
         if pattern.keep.is_some() {
             return not_implemented("keep clause in graph pattern", None);
         }
+        if self.current_graph.is_none() {
+            return Err(BindError::CurrentGraphNotSpecified);
+        }
         let match_mode = pattern
             .match_mode
             .as_ref()
@@ -156,7 +167,7 @@ pub fn bind_label_expr(&mut self, expr: &LabelExpr) -> BindResult<BoundLabelExpr
                 let graph = self
                     .current_graph
                     .as_ref()
-                    .ok_or_else(|| BindError::Unexpected)?;
+                    .ok_or(BindError::CurrentGraphNotSpecified)?;
                 // To handle.
                 let id = graph.graph_type().get_label_id(name)?.unwrap();
                 Ok(BoundLabelExpr::Label(id))
@@ -192,25 +203,160 @@ fn bind_vertex_filler(&mut self, f: &ElementPatternFiller) -> BindResult<BoundVe
             }
         };
 
-        let vertex_ty =
-            LogicalType::Vertex(vec![DataField::new("id".into(), LogicalType::Int64, false)]);
-        self.register_variable(var.as_str(), vertex_ty, false)?;
-
         let label = match &f.label {
             Some(sp) => Some(self.bind_label_expr(sp.value())?),
             None => None,
         };
+        let vertex_type = self.resolve_vertex_type(label.as_ref())?;
+        // The properties of the vertices are only known if their type is, so vertices of
+        // several types are only typed by their ids and labels.
+        let fields = vertex_type
+            .iter()
+            .flat_map(|vertex_type| vertex_type.properties())
+            .map(|(_, p)| {
+                DataField::new(p.name().to_string(), p.logical_type().clone(), p.nullable())
+            })
+            .collect();
+        self.register_variable(var.as_str(), LogicalType::Vertex(fields), false)?;
+        if let Some(vertex_type) = &vertex_type {
+            self.vertex_types.insert(var.clone(), vertex_type.clone());
+        }
         let predicate = match &f.predicate {
             None => None,
-            Some(sp) => None,
+            Some(sp) => match sp.value() {
+                ElementPatternPredicate::Where(expr) => {
+                    Some(self.bind_value_expression(expr.value())?)
+                }
+                ElementPatternPredicate::Property(properties) => {
+                    self.bind_property_specification(&var, vertex_type.as_ref(), properties)?
+                }
+            },
+        };
+        let index_lookup = match &f.index_hint {
+            Some(index) => Some(self.bind_index_hint(
+                index.value(),
+                &var,
+                label.as_ref(),
+                predicate.as_ref(),
+            )?),
+            None => None,
         };
         Ok(BoundVertexPattern {
             var,
             label,
             predicate,
+            index_lookup,
         })
     }
 
+    /// Binds the index hint of the pattern of `var` to a lookup in the property index it names.
+    /// The index must be on the label of the pattern, and the predicate of the pattern must
+    /// require the indexed property to be equal to a literal.
+    fn bind_index_hint(
+        &self,
+        index: &Ident,
+        var: &str,
+        label: Option<&BoundLabelExpr>,
+        predicate: Option<&BoundExpr>,
+    ) -> BindResult<BoundIndexLookup> {
+        let graph = self
+            .current_graph
+            .as_ref()
+            .ok_or(BindError::CurrentGraphNotSpecified)?;
+        let key = graph
+            .graph_type()
+            .get_property_index(index)?
+            .ok_or_else(|| BindError::IndexNotFound(index.clone()))?;
+        let inapplicable = |reason| BindError::InapplicableIndex {
+            index: index.clone(),
+            reason,
+        };
+        if !matches!(label, Some(BoundLabelExpr::Label(id)) if *id == key.label_id) {
+            return Err(inapplicable(
+                "the label of the pattern is not the indexed label",
+            ));
+        }
+        let value = predicate
+            .and_then(|predicate| indexed_value(predicate, var, key.property_id))
+            .ok_or_else(|| inapplicable("no literal value is given for the indexed property"))?;
+        Ok(BoundIndexLookup {
+            name: index.to_string(),
+            key,
+            value,
+        })
+    }
+
+    /// Resolves the vertex type selected by a label expression. Only a label or a conjunction of
+    /// labels determines a single vertex type; `None` is returned for all other expressions.
+    pub(super) fn resolve_vertex_type(
+        &self,
+        label: Option<&BoundLabelExpr>,
+    ) -> BindResult<Option<VertexTypeRef>> {
+        fn collect_labels(expr: &BoundLabelExpr, labels: &mut Vec<LabelId>) -> bool {
+            match expr {
+                BoundLabelExpr::Label(id) => {
+                    labels.push(*id);
+                    true
+                }
+                BoundLabelExpr::Conjunction(lhs, rhs) => {
+                    collect_labels(lhs, labels) && collect_labels(rhs, labels)
+                }
+                _ => false,
+            }
+        }
+
+        let Some(label) = label else {
+            return Ok(None);
+        };
+        let mut labels = Vec::new();
+        if !collect_labels(label, &mut labels) {
+            return Ok(None);
+        }
+        let graph = self
+            .current_graph
+            .as_ref()
+            .ok_or(BindError::CurrentGraphNotSpecified)?;
+        let key: LabelSet = labels.into_iter().collect();
+        Ok(graph.graph_type().get_vertex_type(&key)?)
+    }
+
+    /// Binds a property specification, e.g., `{name: 'Alice', age: 25}`, into a conjunction of
+    /// equality predicates. Each value is coerced to the type declared by the vertex type.
+    fn bind_property_specification(
+        &self,
+        var: &str,
+        vertex_type: Option<&VertexTypeRef>,
+        properties: &[Spanned<FieldOrProperty>],
+    ) -> BindResult<Option<BoundExpr>> {
+        let Some(vertex_type) = vertex_type else {
+            return not_implemented("property specification without a known vertex type", None);
+        };
+        let mut predicate: Option<BoundExpr> = None;
+        for property in properties {
+            let property = property.value();
+            let name = property.name.value().as_str();
+            let (property_id, declared) = vertex_type
+                .get_property(name)?
+                .ok_or_else(|| BindError::PropertyNotFound(SmolStr::new(name)))?;
+            let value = self.bind_value_expression(property.value.value())?;
+            let value =
+                coerce_to_property_type(value, name, declared.logical_type(), declared.nullable())?;
+            let lhs = BoundExpr::property(
+                var.to_string(),
+                property_id,
+                name.to_string(),
+                declared.logical_type().clone(),
+                declared.nullable(),
+            );
+            let eq = BoundExpr::binary(BoundBinaryOp::Eq, lhs, value);
+            predicate = Some(match predicate {
+                Some(acc) => BoundExpr::binary(BoundBinaryOp::And, acc, eq),
+                None => eq,
+            });
+        }
+        Ok(predicate)
+    }
+
     pub fn register_variable(
         &mut self,
         name: &str,
@@ -264,3 +410,35 @@ pub fn bind_match_mode(mode: &MatchMode) -> BoundMatchMode {
         MatchMode::Different => BoundMatchMode::Different,
     }
 }
+
+/// Returns the value that `predicate` requires the property `property_id` of `var` to be equal
+/// to, if the predicate is a conjunction with an equality of the property and a non-null literal
+/// of the property type.
+fn indexed_value(predicate: &BoundExpr, var: &str, property_id: PropertyId) -> Option<ScalarValue> {
+    let BoundExprKind::Binary { op, lhs, rhs } = &predicate.kind else {
+        return None;
+    };
+    match op {
+        BoundBinaryOp::And => {
+            indexed_value(lhs, var, property_id).or_else(|| indexed_value(rhs, var, property_id))
+        }
+        BoundBinaryOp::Eq => match (&lhs.kind, &rhs.kind) {
+            (
+                BoundExprKind::Property {
+                    var: v,
+                    property_id: id,
+                    ..
+                },
+                BoundExprKind::Value(value),
+            ) if v == var
+                && *id == property_id
+                && lhs.logical_type == rhs.logical_type
+                && !value.is_null() =>
+            {
+                Some(value.clone())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,224 @@
+use gql_parser::ast::{
+    DeleteStatement, ElementPattern, ElementPatternPredicate, Expr, InsertPatternList,
+    LinearDataModifyingStatement, SetItem, SimpleDataAccessingStatement,
+    SimpleDataModifyingStatement,
+};
+use itertools::Itertools;
+use minigu_common::data_type::{DataField, LogicalType};
+use minigu_common::error::not_implemented;
+use minigu_common::value::ScalarValue;
+
+use super::Binder;
+use super::coercion::coerce_to_property_type;
+use super::error::{BindError, BindResult};
+use crate::bound::{
+    BoundDeleteStatement, BoundExpr, BoundExprKind, BoundInsertVertex, BoundLabelExpr,
+    BoundLinearDataModifyingStatement, BoundSetItem, BoundSimpleDataAccessingStatement,
+    BoundSimpleDataModifyingStatement,
+};
+
+impl Binder<'_> {
+    pub fn bind_linear_data_modifying_statement(
+        &mut self,
+        statement: &LinearDataModifyingStatement,
+    ) -> BindResult<BoundLinearDataModifyingStatement> {
+        let statements = statement
+            .statements
+            .iter()
+            .map(|s| self.bind_simple_data_accessing_statement(s.value()))
+            .try_collect()?;
+        let result = statement
+            .result
+            .as_ref()
+            .map(|r| self.bind_result_statement(r.value()))
+            .transpose()?;
+        Ok(BoundLinearDataModifyingStatement { statements, result })
+    }
+
+    pub fn bind_simple_data_accessing_statement(
+        &mut self,
+        statement: &SimpleDataAccessingStatement,
+    ) -> BindResult<BoundSimpleDataAccessingStatement> {
+        match statement {
+            SimpleDataAccessingStatement::Query(statement) => self
+                .bind_simple_query_statement(statement)
+                .map(BoundSimpleDataAccessingStatement::Query),
+            SimpleDataAccessingStatement::Modifying(statement) => self
+                .bind_simple_data_modifying_statement(statement)
+                .map(BoundSimpleDataAccessingStatement::Modifying),
+        }
+    }
+
+    pub fn bind_simple_data_modifying_statement(
+        &mut self,
+        statement: &SimpleDataModifyingStatement,
+    ) -> BindResult<BoundSimpleDataModifyingStatement> {
+        match statement {
+            SimpleDataModifyingStatement::Insert(patterns) => self
+                .bind_insert_statement(patterns)
+                .map(BoundSimpleDataModifyingStatement::Insert),
+            SimpleDataModifyingStatement::Set(items) => items
+                .iter()
+                .map(|item| self.bind_set_item(item.value()))
+                .try_collect()
+                .map(BoundSimpleDataModifyingStatement::Set),
+            SimpleDataModifyingStatement::Delete(statement) => self
+                .bind_delete_statement(statement)
+                .map(BoundSimpleDataModifyingStatement::Delete),
+        }
+    }
+
+    /// Binds the vertices of an insert statement, each of which is bound to a new variable.
+    pub fn bind_insert_statement(
+        &mut self,
+        patterns: &InsertPatternList,
+    ) -> BindResult<Vec<BoundInsertVertex>> {
+        patterns
+            .iter()
+            .map(|pattern| self.bind_insert_vertex(pattern.value()))
+            .try_collect()
+    }
+
+    /// Binds a vertex to insert against the vertex type of its label. Like set items, the given
+    /// property values are coerced to the declared property types, and the properties that are not
+    /// given are null, so that they must be given for non-nullable properties.
+    fn bind_insert_vertex(&mut self, pattern: &ElementPattern) -> BindResult<BoundInsertVertex> {
+        let ElementPattern::Node(filler) = pattern else {
+            return not_implemented("inserting edges", None);
+        };
+        if filler.index_hint.is_some() {
+            return not_implemented("index hint in an insert statement", None);
+        }
+        let Some(label) = &filler.label else {
+            return not_implemented("inserting a vertex without a label", None);
+        };
+        let label = self.bind_label_expr(label.value())?;
+        let BoundLabelExpr::Label(label_id) = label else {
+            return not_implemented("inserting a vertex with a label expression", None);
+        };
+        let Some(vertex_type) = self.resolve_vertex_type(Some(&label))? else {
+            return not_implemented("inserting a vertex without a vertex type", None);
+        };
+        let given = match filler.predicate.as_ref().map(|p| p.value()) {
+            None => &[][..],
+            Some(ElementPatternPredicate::Property(properties)) => properties.as_slice(),
+            Some(ElementPatternPredicate::Where(_)) => {
+                return not_implemented("where clause in an insert statement", None);
+            }
+        };
+        for property in given {
+            let name = property.value().name.value();
+            if vertex_type.get_property(name.as_str())?.is_none() {
+                return Err(BindError::PropertyNotFound(name.clone()));
+            }
+        }
+        let properties = vertex_type
+            .properties()
+            .into_iter()
+            .map(|(_, declared)| {
+                let value = match given
+                    .iter()
+                    .find(|p| p.value().name.value().as_str() == declared.name())
+                {
+                    Some(property) => self.bind_value_expression(property.value().value.value())?,
+                    None => BoundExpr::value(ScalarValue::Null, LogicalType::Null, true),
+                };
+                coerce_to_property_type(
+                    value,
+                    declared.name(),
+                    declared.logical_type(),
+                    declared.nullable(),
+                )
+            })
+            .try_collect()?;
+
+        let var = match &filler.variable {
+            Some(var) => var.value().to_string(),
+            None => {
+                let idx = self
+                    .active_data_schema
+                    .as_ref()
+                    .map(|s| s.size())
+                    .unwrap_or(0);
+                format!("__n{idx}")
+            }
+        };
+        if self
+            .active_data_schema
+            .as_ref()
+            .is_some_and(|s| s.get_field_by_name(&var).is_some())
+        {
+            return not_implemented("inserting a vertex bound to an existing variable", None);
+        }
+        let fields = vertex_type
+            .properties()
+            .into_iter()
+            .map(|(_, p)| {
+                DataField::new(p.name().to_string(), p.logical_type().clone(), p.nullable())
+            })
+            .collect();
+        self.register_variable(&var, LogicalType::Vertex(fields), false)?;
+        self.vertex_types.insert(var.clone(), vertex_type);
+        Ok(BoundInsertVertex {
+            var,
+            label_id,
+            properties,
+        })
+    }
+
+    /// Binds a delete statement, whose items must be variables bound to vertices.
+    pub fn bind_delete_statement(
+        &self,
+        statement: &DeleteStatement,
+    ) -> BindResult<BoundDeleteStatement> {
+        let vars = statement
+            .items
+            .iter()
+            .map(|item| {
+                let Expr::Variable(var) = item.value() else {
+                    return not_implemented("deleting a non-variable expression", None);
+                };
+                match self.bind_value_expression(item.value())?.logical_type {
+                    LogicalType::Vertex(_) => Ok(var.to_string()),
+                    LogicalType::Edge(_) => not_implemented("deleting edges", None),
+                    _ => Err(BindError::NotGraphElement(var.clone())),
+                }
+            })
+            .try_collect()?;
+        Ok(BoundDeleteStatement {
+            detach: statement.detach,
+            vars,
+        })
+    }
+
+    /// Binds a set item against the vertex type of its variable. The new value is coerced to the
+    /// declared property type, so that `SET n.prop = NULL` is only accepted for nullable
+    /// properties.
+    pub fn bind_set_item(&self, item: &SetItem) -> BindResult<BoundSetItem> {
+        match item {
+            SetItem::Property {
+                element,
+                property,
+                value,
+            } => {
+                let var = element.value();
+                let name = property.value();
+                let target = self
+                    .lookup_property(var, name)?
+                    .ok_or_else(|| BindError::PropertyNotFound(name.clone()))?;
+                let BoundExprKind::Property { property_id, .. } = target.kind else {
+                    unreachable!("a property reference should be bound to a property");
+                };
+                let value = self.bind_value_expression(value.value())?;
+                let value =
+                    coerce_to_property_type(value, name, &target.logical_type, target.nullable)?;
+                Ok(BoundSetItem {
+                    var: var.to_string(),
+                    property_id,
+                    name: name.to_string(),
+                    value,
+                })
+            }
+        }
+    }
+}
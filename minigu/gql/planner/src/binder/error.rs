@@ -6,6 +6,8 @@
 use smol_str::SmolStr;
 use thiserror::Error;
 
+use crate::bound::{BoundAggregateFunction, BoundBinaryOp};
+
 #[derive(Debug, Error, Diagnostic)]
 pub enum BindError {
     #[error("catalog error")]
@@ -64,14 +66,46 @@ pub enum BindError {
         actual: Vec<LogicalType>,
     },
 
+    #[error("function not found: {0}")]
+    FunctionNotFound(SmolStr),
+
+    #[error(
+        "incorrect number or types of arguments for function {function}: expected [{}], got [{}]",
+        expected.iter().map(|t| t.to_string()).join(", "),
+        actual.iter().map(|t| t.to_string()).join(", "),
+    )]
+    IncorrectFunctionArguments {
+        function: SmolStr,
+        expected: Vec<LogicalType>,
+        actual: Vec<LogicalType>,
+    },
+
+    #[error(
+        "function {function} expects a vertex or an edge, got [{}]",
+        actual.iter().map(|t| t.to_string()).join(", "),
+    )]
+    InvalidElementFunctionArguments {
+        function: SmolStr,
+        actual: Vec<LogicalType>,
+    },
+
+    #[error(
+        "function {function} expects a path, got [{}]",
+        actual.iter().map(|t| t.to_string()).join(", "),
+    )]
+    InvalidPathFunctionArguments {
+        function: SmolStr,
+        actual: Vec<LogicalType>,
+    },
+
     #[error("yield clause not allowed for procedure without data schema: {0}")]
     YieldAfterSchemalessProcedure(SmolStr),
 
     #[error("data schema not provided for procedure: {0}")]
     DataSchemaNotProvided(SmolStr),
 
-    #[error("incorrect number of yield items: expected {expected}, got {actual}")]
-    IncorrectNumberOfYieldItems { expected: usize, actual: usize },
+    #[error("filter condition must be a boolean, but found {0}")]
+    NonBooleanFilter(LogicalType),
 
     #[error("no column can be returned in the return statement")]
     NoColumnInReturnStatement,
@@ -100,6 +134,82 @@ pub enum BindError {
     #[error("invalid float literal: {0}")]
     InvalidFloatLiteral(String),
 
+    #[error("property not found: {0}")]
+    PropertyNotFound(SmolStr),
+
+    #[error("field not found: {0}")]
+    FieldNotFound(SmolStr),
+
+    #[error("field {field} of a value of type {ty}, which is not a record")]
+    NotRecord { field: SmolStr, ty: LogicalType },
+
+    #[error("type mismatch for property {property}: expected {expected}, got {actual}")]
+    #[diagnostic(help(
+        "string literals are only accepted for numeric properties if they hold a number of the property type"
+    ))]
+    PropertyTypeMismatch {
+        property: SmolStr,
+        expected: LogicalType,
+        actual: LogicalType,
+    },
+
+    #[error("operands of {op} have incompatible types: {left} and {right}")]
+    BinaryTypeMismatch {
+        op: BoundBinaryOp,
+        left: LogicalType,
+        right: LogicalType,
+    },
+
+    #[error("invalid argument of {function}: {ty}")]
+    InvalidAggregateArgument {
+        function: BoundAggregateFunction,
+        ty: LogicalType,
+    },
+
+    #[error("cannot cast {from} to {to}")]
+    InvalidCast { from: LogicalType, to: LogicalType },
+
+    #[error("null value for non-nullable property: {0}")]
+    NullPropertyValue(SmolStr),
+
+    #[error("not a vertex or an edge: {0}")]
+    NotGraphElement(SmolStr),
+
+    #[error("queries of a union return {left} and {right} columns")]
+    UnionColumnCountMismatch { left: usize, right: usize },
+
+    #[error("type mismatch for column {column} of a union: {left} and {right}")]
+    UnionTypeMismatch {
+        column: SmolStr,
+        left: LogicalType,
+        right: LogicalType,
+    },
+
+    #[error("query without result in a union")]
+    UnionWithoutResult,
+
+    #[error("procedure already exists: {0}")]
+    #[diagnostic(help("use \"create or replace view\" to replace an existing view"))]
+    ProcedureAlreadyExists(SmolStr),
+
+    #[error("not a view: {0}")]
+    NotView(SmolStr),
+
+    #[error("recursive view: {}", .0.iter().join(" -> "))]
+    RecursiveView(Vec<SmolStr>),
+
+    #[error("index not found: {0}")]
+    IndexNotFound(SmolStr),
+
+    #[error("index {index} cannot be used: {reason}")]
+    #[diagnostic(help(
+        "an index hint requires the label of the index and a literal value of the indexed property"
+    ))]
+    InapplicableIndex {
+        index: SmolStr,
+        reason: &'static str,
+    },
+
     // TODO: Remove this error variant
     #[error("unexpected bind error")]
     Unexpected,
@@ -1,7 +1,9 @@
 #![allow(unused)]
 
 mod catalog;
+mod coercion;
 mod common;
+mod data;
 pub mod error;
 mod object_expr;
 mod object_ref;
@@ -10,10 +12,14 @@
 mod query;
 mod value_expr;
 
+use std::collections::HashMap;
+
 use gql_parser::ast::Procedure;
 use minigu_catalog::named_ref::NamedGraphRef;
-use minigu_catalog::provider::{CatalogProvider, SchemaRef};
+use minigu_catalog::provider::{CatalogProvider, SchemaRef, VertexTypeRef};
 use minigu_common::data_type::DataSchema;
+use minigu_context::function::FunctionRegistry;
+use smol_str::SmolStr;
 
 use crate::binder::error::BindResult;
 use crate::bound::BoundProcedure;
@@ -21,6 +27,8 @@
 #[derive(Debug)]
 pub struct Binder<'a> {
     catalog: &'a dyn CatalogProvider,
+    /// User-defined scalar functions, resolved by name in function calls.
+    functions: &'a FunctionRegistry,
 
     current_schema: Option<SchemaRef>,
     home_schema: Option<SchemaRef>,
@@ -28,11 +36,18 @@ pub struct Binder<'a> {
     home_graph: Option<NamedGraphRef>,
 
     active_data_schema: Option<DataSchema>,
+    /// Vertex types of the vertex variables whose labels determine a single vertex type. These are
+    /// used to resolve property references such as `n.name`.
+    vertex_types: HashMap<String, VertexTypeRef>,
+    /// Names of the views whose queries are being bound, outermost first. A view referring to one
+    /// of these is recursive.
+    views: Vec<SmolStr>,
 }
 
 impl<'a> Binder<'a> {
     pub fn new(
         catalog: &'a dyn CatalogProvider,
+        functions: &'a FunctionRegistry,
         current_schema: Option<SchemaRef>,
         home_schema: Option<SchemaRef>,
         current_graph: Option<NamedGraphRef>,
@@ -40,11 +55,32 @@ pub fn new(
     ) -> Self {
         Binder {
             catalog,
+            functions,
             current_schema,
             home_schema,
             current_graph,
             home_graph,
             active_data_schema: None,
+            vertex_types: HashMap::new(),
+            views: Vec::new(),
+        }
+    }
+
+    /// Returns a binder for the query of the view `name`, in the same session state but without
+    /// any variable in scope.
+    fn view_binder(&self, name: SmolStr) -> Binder<'a> {
+        let mut views = self.views.clone();
+        views.push(name);
+        Binder {
+            catalog: self.catalog,
+            functions: self.functions,
+            current_schema: self.current_schema.clone(),
+            home_schema: self.home_schema.clone(),
+            current_graph: self.current_graph.clone(),
+            home_graph: self.home_graph.clone(),
+            active_data_schema: None,
+            vertex_types: HashMap::new(),
+            views,
         }
     }
 
@@ -1,13 +1,22 @@
 use std::sync::Arc;
 
-use gql_parser::ast::{CallProcedureStatement, NamedProcedureCall, ProcedureCall};
+use gql_parser::ast::{
+    CallProcedureStatement, NamedProcedureCall, ProcedureCall, ProcedureRef as AstProcedureRef,
+    Yield,
+};
 use itertools::Itertools;
-use minigu_common::data_type::{DataField, DataSchema};
+use minigu_catalog::named_ref::NamedProcedureRef;
+use minigu_common::data_type::{DataField, DataSchema, DataSchemaRef};
 use minigu_common::error::not_implemented;
+use minigu_context::view::View;
+use smol_str::SmolStr;
 
 use super::Binder;
 use super::error::{BindError, BindResult};
-use crate::bound::{BoundCallProcedureStatement, BoundNamedProcedureCall, BoundProcedureCall};
+use crate::bound::{
+    BoundCallProcedureStatement, BoundExpr, BoundNamedProcedureCall, BoundProcedureCall,
+    BoundViewCall,
+};
 
 impl Binder<'_> {
     pub fn bind_call_procedure_statement(
@@ -24,18 +33,87 @@ pub fn bind_call_procedure_statement(
 
     pub fn bind_procedure_call(&self, call: &ProcedureCall) -> BindResult<BoundProcedureCall> {
         match call {
-            ProcedureCall::Named(call) => self
-                .bind_named_procedure_call(call)
-                .map(BoundProcedureCall::Named),
+            ProcedureCall::Named(call) => {
+                self.check_recursive_view(call.name.value())?;
+                let procedure_ref = self.bind_procedure_ref(call.name.value())?;
+                if procedure_ref.as_any().is::<View>() {
+                    self.bind_view_call(procedure_ref, call)
+                        .map(BoundProcedureCall::View)
+                } else {
+                    self.bind_named_procedure_call(procedure_ref, call)
+                        .map(BoundProcedureCall::Named)
+                }
+            }
             _ => not_implemented("inline procedure call".to_string(), None),
         }
     }
 
     pub fn bind_named_procedure_call(
         &self,
+        procedure_ref: NamedProcedureRef,
         call: &NamedProcedureCall,
     ) -> BindResult<BoundNamedProcedureCall> {
-        let procedure_ref = self.bind_procedure_ref(call.name.value())?;
+        let args = self.bind_procedure_args(&procedure_ref, call)?;
+        let (yield_items, schema) = match call.yield_clause.as_ref() {
+            Some(yield_clause) => {
+                let Some(original_schema) = procedure_ref.schema() else {
+                    return Err(BindError::YieldAfterSchemalessProcedure(
+                        procedure_ref.name().clone(),
+                    ));
+                };
+                let (items, schema) = bind_yield_clause(yield_clause.value(), &original_schema)?;
+                (Some(items), Some(schema))
+            }
+            None => (None, procedure_ref.schema().clone()),
+        };
+        Ok(BoundNamedProcedureCall {
+            procedure_ref,
+            args,
+            yield_items,
+            schema,
+        })
+    }
+
+    /// Binds a call of a view by binding the query of the view in place of the call.
+    pub fn bind_view_call(
+        &self,
+        view_ref: NamedProcedureRef,
+        call: &NamedProcedureCall,
+    ) -> BindResult<BoundViewCall> {
+        self.bind_procedure_args(&view_ref, call)?;
+        let view = view_ref
+            .as_any()
+            .downcast_ref::<View>()
+            .expect("procedure should be a view");
+        let name = view_ref.name().clone();
+        let query = self
+            .view_binder(name.clone())
+            .bind_composite_query_statement(view.query())?;
+        // The graphs may have changed since the view was created, so the columns are those of the
+        // query as bound now.
+        let query_schema = query
+            .schema()
+            .ok_or_else(|| BindError::DataSchemaNotProvided(name.clone()))?;
+        let (yield_items, schema) = match call.yield_clause.as_ref() {
+            Some(yield_clause) => {
+                let (items, schema) = bind_yield_clause(yield_clause.value(), &query_schema)?;
+                (Some(items), schema)
+            }
+            None => (None, query_schema),
+        };
+        Ok(BoundViewCall {
+            name,
+            query: Box::new(query),
+            yield_items,
+            schema,
+        })
+    }
+
+    fn bind_procedure_args(
+        &self,
+        procedure_ref: &NamedProcedureRef,
+        call: &NamedProcedureCall,
+    ) -> BindResult<Vec<BoundExpr>> {
         let parameters = procedure_ref.parameters();
         let args: Vec<_> = call
             .args
@@ -50,43 +128,51 @@ pub fn bind_named_procedure_call(
                 actual: args_types,
             });
         }
-        let schema = if let Some(yield_clause) = call.yield_clause.as_ref() {
-            let original_schema = procedure_ref.schema();
-            let yield_clause = yield_clause.value();
-            if let Some(original_schema) = original_schema {
-                if yield_clause.len() != original_schema.fields().len() {
-                    return Err(BindError::IncorrectNumberOfYieldItems {
-                        expected: original_schema.fields().len(),
-                        actual: yield_clause.len(),
-                    });
-                }
-                let mut fields = Vec::with_capacity(yield_clause.len());
-                for (item, field) in yield_clause.iter().zip(original_schema.fields()) {
-                    let item = item.value();
-                    let item_name = item.name.value();
-                    if item_name != field.name() {
-                        return Err(BindError::YieldItemNotFound(item_name.clone()));
-                    }
-                    let name = item.alias.as_ref().map(|a| a.value()).unwrap_or(item_name);
-                    fields.push(DataField::new(
-                        name.to_string(),
-                        field.ty().clone(),
-                        field.is_nullable(),
-                    ));
-                }
-                Some(Arc::new(DataSchema::new(fields)))
-            } else {
-                return Err(BindError::YieldAfterSchemalessProcedure(
-                    procedure_ref.name().clone(),
-                ));
-            }
-        } else {
-            procedure_ref.schema().clone()
+        Ok(args)
+    }
+
+    /// Fails if `procedure` refers to one of the views whose queries are being bound.
+    fn check_recursive_view(&self, procedure: &AstProcedureRef) -> BindResult<()> {
+        let AstProcedureRef::Ref(procedure) = procedure else {
+            return Ok(());
         };
-        Ok(BoundNamedProcedureCall {
-            procedure_ref,
-            args,
-            schema,
-        })
+        let [name] = procedure.objects.as_slice() else {
+            return Ok(());
+        };
+        if let Some(position) = self.views.iter().position(|view| view == name.value()) {
+            let mut cycle = self.views[position..].to_vec();
+            cycle.push(name.value().clone());
+            return Err(BindError::RecursiveView(cycle));
+        }
+        Ok(())
+    }
+}
+
+/// Binds the items of `yield_clause` against the columns of `schema`, returning the yielded
+/// columns and the schema they make up.
+fn bind_yield_clause(
+    yield_clause: &Yield,
+    schema: &DataSchema,
+) -> BindResult<(Vec<BoundExpr>, DataSchemaRef)> {
+    let mut items = Vec::with_capacity(yield_clause.len());
+    let mut fields = Vec::with_capacity(yield_clause.len());
+    for item in yield_clause {
+        let item = item.value();
+        let item_name = item.name.value();
+        let field = schema
+            .get_field_by_name(item_name)
+            .ok_or_else(|| BindError::YieldItemNotFound(item_name.clone()))?;
+        let name = item.alias.as_ref().map(|a| a.value()).unwrap_or(item_name);
+        items.push(BoundExpr::variable(
+            field.name().to_string(),
+            field.ty().clone(),
+            field.is_nullable(),
+        ));
+        fields.push(DataField::new(
+            name.to_string(),
+            field.ty().clone(),
+            field.is_nullable(),
+        ));
     }
+    Ok((items, Arc::new(DataSchema::new(fields))))
 }
@@ -1,10 +1,13 @@
-use gql_parser::ast::{Procedure, Statement};
+use gql_parser::ast::{CallProcedureStatement, CatalogModifyingStatement, Procedure, Statement};
 use itertools::Itertools;
 use minigu_common::error::not_implemented;
 
 use super::Binder;
 use super::error::BindResult;
-use crate::bound::{BoundProcedure, BoundStatement};
+use crate::bound::{
+    BoundCompositeQueryStatement, BoundLinearQueryStatement, BoundProcedure, BoundResultStatement,
+    BoundReturnStatement, BoundSimpleQueryStatement, BoundStatement,
+};
 
 impl Binder<'_> {
     pub fn bind_procedure(&mut self, procedure: &Procedure) -> BindResult<BoundProcedure> {
@@ -27,15 +30,56 @@ pub fn bind_procedure(&mut self, procedure: &Procedure) -> BindResult<BoundProce
 
     pub fn bind_statement(&mut self, statement: &Statement) -> BindResult<BoundStatement> {
         match statement {
-            Statement::Catalog(statements) => statements
-                .iter()
-                .map(|s| self.bind_catalog_modifying_statement(s.value()))
-                .try_collect()
-                .map(BoundStatement::Catalog),
+            Statement::Catalog(statements) => {
+                if let [statement] = statements.as_slice() {
+                    if let CatalogModifyingStatement::Call(statement) = statement.value() {
+                        if let Some(query) = self.bind_table_procedure_call(statement)? {
+                            return Ok(BoundStatement::Query(query));
+                        }
+                    }
+                }
+                statements
+                    .iter()
+                    .map(|s| self.bind_catalog_modifying_statement(s.value()))
+                    .try_collect()
+                    .map(BoundStatement::Catalog)
+            }
             Statement::Query(statement) => self
                 .bind_composite_query_statement(statement)
                 .map(BoundStatement::Query),
-            Statement::Data(_) => not_implemented("data-modifying statement".to_string(), None),
+            Statement::Data(statement) => self
+                .bind_linear_data_modifying_statement(statement)
+                .map(BoundStatement::Data),
         }
     }
+
+    /// Binds a call standing alone as a query returning the table of the procedure, as if it were
+    /// followed by `RETURN *`, or returns `None` if the procedure returns no table.
+    fn bind_table_procedure_call(
+        &mut self,
+        statement: &CallProcedureStatement,
+    ) -> BindResult<Option<BoundCompositeQueryStatement>> {
+        let statement = self.bind_call_procedure_statement(statement)?;
+        let Some(schema) = statement.schema().cloned() else {
+            return Ok(None);
+        };
+        if statement.optional {
+            return not_implemented("optional procedure calls returning a table", None);
+        }
+        self.active_data_schema = Some(schema.as_ref().clone());
+        let result = BoundResultStatement::Return {
+            statement: BoundReturnStatement {
+                quantifier: None,
+                items: None,
+                schema,
+            },
+            order_by_and_page: None,
+        };
+        Ok(Some(BoundCompositeQueryStatement::Primary(
+            BoundLinearQueryStatement::Query {
+                statements: vec![BoundSimpleQueryStatement::Call(statement)],
+                result,
+            },
+        )))
+    }
 }
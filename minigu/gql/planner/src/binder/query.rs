@@ -1,25 +1,28 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use gql_parser::ast::{
-    AmbientLinearQueryStatement, CompositeQueryStatement, FocusedLinearQueryStatement,
-    FocusedLinearQueryStatementPart, LinearQueryStatement, MatchStatement,
-    NullOrdering as AstNullOrdering, OrderByAndPageStatement, Ordering, QueryConjunction,
-    ResultStatement, Return, ReturnStatement, SetOp, SetOpKind, SetQuantifier,
-    SimpleQueryStatement, SortSpec,
+    AggregateFunction, AmbientLinearQueryStatement, CompositeQueryStatement, Expr,
+    FocusedLinearQueryStatement, FocusedLinearQueryStatementPart, GeneralSetFunctionKind,
+    LinearQueryStatement, MatchStatement, NullOrdering as AstNullOrdering, OrderByAndPageStatement,
+    Ordering, QueryConjunction, ResultStatement, Return, ReturnStatement, SetOp, SetOpKind,
+    SetQuantifier, SimpleQueryStatement, SortSpec, WithStatement,
 };
 use itertools::Itertools;
-use minigu_common::data_type::{DataField, DataSchema, DataSchemaRef};
+use minigu_common::data_type::{DataField, DataSchema, DataSchemaRef, LogicalType};
 use minigu_common::error::not_implemented;
 use minigu_common::ordering::{NullOrdering, SortOrdering};
 use minigu_common::types::{VectorIndexKey, VectorMetric};
 
 use super::Binder;
+use super::coercion::{infer_null_type, is_float, is_integer, is_untyped_null};
 use super::error::{BindError, BindResult};
 use crate::bound::{
-    BoundCompositeQueryStatement, BoundExpr, BoundLimitClause, BoundLinearQueryStatement,
-    BoundMatchStatement, BoundOrderByAndPageStatement, BoundQueryConjunction, BoundResultStatement,
-    BoundReturnStatement, BoundSetOp, BoundSetOpKind, BoundSetQuantifier,
-    BoundSimpleQueryStatement, BoundSortSpec, BoundVectorIndexScan,
+    BoundAggregate, BoundAggregateFunction, BoundCompositeQueryStatement, BoundExpr, BoundExprKind,
+    BoundLimitClause, BoundLinearQueryStatement, BoundMatchStatement, BoundOrderByAndPageStatement,
+    BoundQueryConjunction, BoundResultStatement, BoundReturnStatement, BoundSetOp, BoundSetOpKind,
+    BoundSetQuantifier, BoundSimpleQueryStatement, BoundSortSpec, BoundVectorIndexScan,
+    BoundWithItem, BoundWithStatement,
 };
 
 impl Binder<'_> {
@@ -28,8 +31,35 @@ pub fn bind_composite_query_statement(
         statement: &CompositeQueryStatement,
     ) -> BindResult<BoundCompositeQueryStatement> {
         match statement {
-            CompositeQueryStatement::Conjunction { .. } => {
-                not_implemented("query conjunction", None)
+            CompositeQueryStatement::Conjunction {
+                conjunction,
+                left,
+                right,
+            } => {
+                let conjunction = bind_query_conjunction(conjunction.value())?;
+                let BoundQueryConjunction::SetOp(BoundSetOp {
+                    kind: BoundSetOpKind::Union,
+                    ..
+                }) = &conjunction
+                else {
+                    return not_implemented("query conjunction other than UNION", None);
+                };
+                // Each side is bound against the same input, without the variables of the other.
+                let active_data_schema = self.active_data_schema.clone();
+                let vertex_types = self.vertex_types.clone();
+                let mut left = self.bind_composite_query_statement(left.value())?;
+                self.active_data_schema = active_data_schema;
+                self.vertex_types = vertex_types.clone();
+                let mut right = self.bind_composite_query_statement(right.value())?;
+                let schema = union_schema(&mut left, &mut right)?;
+                self.active_data_schema = Some(schema.as_ref().clone());
+                self.vertex_types = vertex_types;
+                Ok(BoundCompositeQueryStatement::Conjunction {
+                    conjunction,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    schema,
+                })
             }
             CompositeQueryStatement::Primary(statement) => {
                 let statement = self.bind_linear_query_statement(statement)?;
@@ -141,10 +171,194 @@ pub fn bind_simple_query_statement(
                 }
                 Ok(BoundSimpleQueryStatement::Call(statement))
             }
+            SimpleQueryStatement::Filter(condition) => {
+                let condition = self.bind_value_expression(condition.value())?;
+                if condition.logical_type != LogicalType::Boolean {
+                    return Err(BindError::NonBooleanFilter(condition.logical_type));
+                }
+                Ok(BoundSimpleQueryStatement::Filter(condition))
+            }
             SimpleQueryStatement::OrderByAndPage(_) => {
                 not_implemented("standalone order by and page statement", None)
             }
+            SimpleQueryStatement::With(statement) => {
+                let statement = self.bind_with_statement(statement)?;
+                Ok(BoundSimpleQueryStatement::With(statement))
+            }
+        }
+    }
+
+    /// Binds a `WITH` statement, after which only its items are visible, under their aliases. The
+    /// condition of its `WHERE` clause is bound against these items.
+    pub fn bind_with_statement(
+        &mut self,
+        statement: &WithStatement,
+    ) -> BindResult<BoundWithStatement> {
+        let quantifier = statement
+            .quantifier
+            .as_ref()
+            .map(|q| bind_set_quantifier(q.value()));
+        let mut items = Vec::new();
+        let mut fields = Vec::new();
+        match statement.items.value() {
+            Return::Items(return_items) => {
+                for item in return_items {
+                    let item = item.value();
+                    let bound = match item.value.value() {
+                        Expr::Aggregate(aggregate) => {
+                            BoundWithItem::Aggregate(self.bind_aggregate(aggregate)?)
+                        }
+                        expr => BoundWithItem::Expr(self.bind_value_expression(expr)?),
+                    };
+                    let name = if let Some(alias) = &item.alias {
+                        alias.value().to_string()
+                    } else {
+                        bound.to_string()
+                    };
+                    fields.push(DataField::new(
+                        name,
+                        bound.logical_type().clone(),
+                        bound.nullable(),
+                    ));
+                    items.push(bound);
+                }
+            }
+            Return::All => {
+                let schema = self
+                    .active_data_schema
+                    .as_ref()
+                    .ok_or(BindError::NoColumnInReturnStatement)?;
+                for field in schema.fields() {
+                    items.push(BoundWithItem::Expr(BoundExpr::variable(
+                        field.name().to_string(),
+                        field.ty().clone(),
+                        field.is_nullable(),
+                    )));
+                    fields.push(field.clone());
+                }
+            }
+        }
+        // Variables holding vertices keep their vertex types, under their new names.
+        let vertex_types = items
+            .iter()
+            .zip(&fields)
+            .filter_map(|(item, field)| {
+                let BoundWithItem::Expr(BoundExpr {
+                    kind: BoundExprKind::Variable(var),
+                    ..
+                }) = item
+                else {
+                    return None;
+                };
+                let vertex_type = self.vertex_types.get(var)?;
+                Some((field.name().to_string(), vertex_type.clone()))
+            })
+            .collect::<HashMap<_, _>>();
+        let schema = Arc::new(DataSchema::new(fields));
+        self.active_data_schema = Some(schema.as_ref().clone());
+        self.vertex_types = vertex_types;
+        let predicate = statement
+            .where_clause
+            .as_ref()
+            .map(|condition| {
+                let condition = self.bind_value_expression(condition.value())?;
+                if condition.logical_type != LogicalType::Boolean {
+                    return Err(BindError::NonBooleanFilter(condition.logical_type));
+                }
+                Ok(condition)
+            })
+            .transpose()?;
+        Ok(BoundWithStatement {
+            quantifier,
+            items,
+            schema,
+            predicate,
+        })
+    }
+
+    /// Binds a call to an aggregate function. `COUNT` yields a 64-bit integer, `AVG` a 64-bit
+    /// float, and `SUM`, `MIN` and `MAX` a value of the widest integer or floating-point type of
+    /// their argument, or a string for `MIN` and `MAX` of strings.
+    pub fn bind_aggregate(&self, aggregate: &AggregateFunction) -> BindResult<BoundAggregate> {
+        let function = match aggregate {
+            AggregateFunction::Count => {
+                return Ok(BoundAggregate {
+                    function: BoundAggregateFunction::Count,
+                    arg: None,
+                    distinct: false,
+                    logical_type: LogicalType::Int64,
+                    nullable: false,
+                });
+            }
+            AggregateFunction::General(function) => function,
+            AggregateFunction::Binary(_) => {
+                return not_implemented("binary set function", None);
+            }
+        };
+        let kind = match function.kind.value() {
+            GeneralSetFunctionKind::Count => BoundAggregateFunction::Count,
+            GeneralSetFunctionKind::Sum => BoundAggregateFunction::Sum,
+            GeneralSetFunctionKind::Avg => BoundAggregateFunction::Avg,
+            GeneralSetFunctionKind::Min => BoundAggregateFunction::Min,
+            GeneralSetFunctionKind::Max => BoundAggregateFunction::Max,
+            GeneralSetFunctionKind::CollectList => {
+                return not_implemented("collect_list function", None);
+            }
+            GeneralSetFunctionKind::StddevSamp | GeneralSetFunctionKind::StddevPop => {
+                return not_implemented("standard deviation function", None);
+            }
+        };
+        let distinct = matches!(
+            function.quantifier.as_ref().map(|q| q.value()),
+            Some(SetQuantifier::Distinct)
+        );
+        if distinct
+            && matches!(
+                kind,
+                BoundAggregateFunction::Sum | BoundAggregateFunction::Avg
+            )
+        {
+            return not_implemented("distinct sum or average", None);
         }
+        let arg = self.bind_value_expression(function.expr.value())?;
+        let ty = &arg.logical_type;
+        let logical_type = match kind {
+            BoundAggregateFunction::Count => LogicalType::Int64,
+            BoundAggregateFunction::Avg if is_integer(ty) || is_float(ty) => LogicalType::Float64,
+            BoundAggregateFunction::Sum
+            | BoundAggregateFunction::Min
+            | BoundAggregateFunction::Max
+                if is_integer(ty) =>
+            {
+                LogicalType::Int64
+            }
+            BoundAggregateFunction::Sum
+            | BoundAggregateFunction::Min
+            | BoundAggregateFunction::Max
+                if is_float(ty) =>
+            {
+                LogicalType::Float64
+            }
+            BoundAggregateFunction::Min | BoundAggregateFunction::Max
+                if *ty == LogicalType::String =>
+            {
+                LogicalType::String
+            }
+            _ => {
+                return Err(BindError::InvalidAggregateArgument {
+                    function: kind,
+                    ty: ty.clone(),
+                });
+            }
+        };
+        Ok(BoundAggregate {
+            function: kind,
+            arg: Some(arg),
+            distinct,
+            logical_type,
+            // Aggregates over empty groups, or over nulls only, are null, except for counts.
+            nullable: kind != BoundAggregateFunction::Count,
+        })
     }
 
     // NOTE: `bind_vector_index_scan` is currently only invoked via placeholder wiring so executor
@@ -326,6 +540,106 @@ pub fn bind_null_ordering(null_ordering: &AstNullOrdering) -> NullOrdering {
     }
 }
 
+/// Computes the output schema of the union of `left` and `right`.
+///
+/// Columns are matched by position and named after `left`. A column returning an untyped `NULL` on
+/// one side takes the type of the other side, and the `NULL` is typed accordingly.
+fn union_schema(
+    left: &mut BoundCompositeQueryStatement,
+    right: &mut BoundCompositeQueryStatement,
+) -> BindResult<DataSchemaRef> {
+    let (Some(left_schema), Some(right_schema)) = (left.schema(), right.schema()) else {
+        return Err(BindError::UnionWithoutResult);
+    };
+    if left_schema.size() != right_schema.size() {
+        return Err(BindError::UnionColumnCountMismatch {
+            left: left_schema.size(),
+            right: right_schema.size(),
+        });
+    }
+    let mut fields = Vec::with_capacity(left_schema.size());
+    for (index, (l, r)) in left_schema
+        .fields()
+        .iter()
+        .zip(right_schema.fields())
+        .enumerate()
+    {
+        let ty = match (l.ty(), r.ty()) {
+            (lt, rt) if lt == rt => lt.clone(),
+            (LogicalType::Null, ty) if type_null_column(left, index, ty) => ty.clone(),
+            (ty, LogicalType::Null) if type_null_column(right, index, ty) => ty.clone(),
+            (lt, rt) => {
+                return Err(BindError::UnionTypeMismatch {
+                    column: l.name().into(),
+                    left: lt.clone(),
+                    right: rt.clone(),
+                });
+            }
+        };
+        let nullable = l.is_nullable() || r.is_nullable();
+        fields.push(DataField::new(l.name().to_string(), ty, nullable));
+    }
+    Ok(Arc::new(DataSchema::new(fields)))
+}
+
+/// Gives the type `ty` to the untyped `NULL` returned at `index` by `statement`.
+///
+/// Returns `false` if the column is not a `NULL` literal of a RETURN statement, in which case
+/// `statement` may be left partially typed.
+fn type_null_column(
+    statement: &mut BoundCompositeQueryStatement,
+    index: usize,
+    ty: &LogicalType,
+) -> bool {
+    match statement {
+        BoundCompositeQueryStatement::Conjunction {
+            left,
+            right,
+            schema,
+            ..
+        } => {
+            // The column of a nested union is only untyped if it is untyped on both sides.
+            if !type_null_column(left, index, ty) || !type_null_column(right, index, ty) {
+                return false;
+            }
+            *schema = with_field_type(schema, index, ty);
+            true
+        }
+        BoundCompositeQueryStatement::Primary(BoundLinearQueryStatement::Query {
+            result: BoundResultStatement::Return { statement, .. },
+            ..
+        }) => {
+            let Some(item) = statement.items.as_mut().and_then(|i| i.get_mut(index)) else {
+                return false;
+            };
+            let typed = infer_null_type(item.clone(), ty);
+            if is_untyped_null(&typed) {
+                return false;
+            }
+            *item = typed;
+            statement.schema = with_field_type(&statement.schema, index, ty);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn with_field_type(schema: &DataSchema, index: usize, ty: &LogicalType) -> DataSchemaRef {
+    let fields = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            if i == index {
+                DataField::new(f.name().to_string(), ty.clone(), f.is_nullable())
+            } else {
+                f.clone()
+            }
+        })
+        .collect();
+    Arc::new(DataSchema::new(fields))
+}
+
 pub fn bind_query_conjunction(conjunction: &QueryConjunction) -> BindResult<BoundQueryConjunction> {
     match conjunction {
         QueryConjunction::SetOp(set_op) => Ok(BoundQueryConjunction::SetOp(bind_set_op(set_op))),
@@ -1,10 +1,13 @@
 use std::str::FromStr;
 
 use gql_parser::ast::{
-    BinaryOp, BooleanLiteral, Expr, Function, Literal, NonNegativeInteger, StringLiteral,
-    StringLiteralKind, UnaryOp, UnsignedInteger, UnsignedIntegerKind, UnsignedNumericLiteral,
-    Value, VectorDistance, VectorLiteral,
+    BinaryOp, BooleanLiteral, Expr, FloatTypeKind, Function, GenericFunction, Ident, Literal,
+    NonNegativeInteger, NumericTypeKind, Predicate, StringLiteral, StringLiteralKind, UnaryOp,
+    UnsignedInteger, UnsignedIntegerKind, UnsignedNumericLiteral, Value, ValueType, VectorDistance,
+    VectorLiteral,
 };
+use gql_parser::span::Spanned;
+use itertools::Itertools;
 use minigu_common::constants::SESSION_USER;
 use minigu_common::data_type::LogicalType;
 use minigu_common::error::not_implemented;
@@ -12,13 +15,19 @@
 use minigu_common::value::{F32, F64, ScalarValue, VectorValue};
 
 use super::Binder;
+use super::coercion::{coerce_to_type, infer_null_type, is_castable};
 use super::error::{BindError, BindResult};
-use crate::bound::{BoundBinaryOp, BoundExpr, BoundUnsignedInteger};
+use crate::bound::{
+    BoundBinaryOp, BoundElementFunction, BoundExpr, BoundPathFunction, BoundUnaryOp,
+    BoundUnsignedInteger,
+};
 
 impl Binder<'_> {
     pub fn bind_value_expression(&self, expr: &Expr) -> BindResult<BoundExpr> {
         match expr {
-            Expr::Binary { .. } => not_implemented("binary expression", None),
+            Expr::Binary { op, left, right } => {
+                self.bind_binary(op.value(), left.value(), right.value())
+            }
             Expr::Unary { .. } => not_implemented("unary expression", None),
             Expr::DurationBetween { .. } => not_implemented("duration between expression", None),
             Expr::Is { .. } => not_implemented("is expression", None),
@@ -40,20 +49,197 @@ pub fn bind_value_expression(&self, expr: &Expr) -> BindResult<BoundExpr> {
             }
             Expr::Value(value) => bind_value(value),
             Expr::Path(_) => not_implemented("path expression", None),
-            Expr::Property { .. } => not_implemented("property expression", None),
+            Expr::Property {
+                source,
+                trailing_names,
+            } => self.bind_property_reference(source.value(), trailing_names),
             Expr::Graph(_) => not_implemented("graph expression", None),
+            Expr::Predicate(predicate) => self.bind_predicate(predicate),
+            Expr::Cast { expr, value_type } => self.bind_cast(expr.value(), value_type.value()),
+        }
+    }
+
+    fn bind_cast(&self, expr: &Expr, value_type: &ValueType) -> BindResult<BoundExpr> {
+        let target = bind_value_type(value_type)?;
+        let child = infer_null_type(self.bind_value_expression(expr)?, &target);
+        if child.logical_type == target {
+            return Ok(child);
+        }
+        if !is_castable(&child.logical_type, &target) {
+            return Err(BindError::InvalidCast {
+                from: child.logical_type,
+                to: target,
+            });
+        }
+        Ok(BoundExpr::cast(child, target))
+    }
+
+    /// Binds a binary operation. Operands of different types are given a common type by coercing
+    /// the right one to the type of the left one, or else the left one to the type of the right
+    /// one, e.g., in `c > 1`, the literal `1` gets the type of `c`.
+    fn bind_binary(&self, op: &BinaryOp, left: &Expr, right: &Expr) -> BindResult<BoundExpr> {
+        let op = bind_binary_op(op);
+        let lhs = self.bind_value_expression(left)?;
+        let rhs = self.bind_value_expression(right)?;
+        if lhs.logical_type == rhs.logical_type {
+            return Ok(BoundExpr::binary(op, lhs, rhs));
+        }
+        let (left_type, right_type) = (lhs.logical_type.clone(), rhs.logical_type.clone());
+        if let Some(rhs) = coerce_to_type(rhs.clone(), &left_type) {
+            return Ok(BoundExpr::binary(op, lhs, rhs));
+        }
+        if let Some(lhs) = coerce_to_type(lhs, &right_type) {
+            return Ok(BoundExpr::binary(op, lhs, rhs));
+        }
+        Err(BindError::BinaryTypeMismatch {
+            op,
+            left: left_type,
+            right: right_type,
+        })
+    }
+
+    /// Binds a reference to a property of a vertex, or to a field of a record, followed by
+    /// references to fields of the records they hold.
+    fn bind_property_reference(
+        &self,
+        source: &Expr,
+        trailing_names: &[Spanned<Ident>],
+    ) -> BindResult<BoundExpr> {
+        let source_expr = self.bind_value_expression(source)?;
+        let (mut expr, names) = match (&source_expr.logical_type, source) {
+            (LogicalType::Vertex(_) | LogicalType::Edge(_), Expr::Variable(var)) => {
+                let (name, names) = trailing_names
+                    .split_first()
+                    .expect("property reference should have a name");
+                let name = name.value();
+                let property = self
+                    .lookup_property(var, name)?
+                    .ok_or_else(|| BindError::PropertyNotFound(name.clone()))?;
+                (property, names)
+            }
+            (LogicalType::Vertex(_) | LogicalType::Edge(_), _) => {
+                return not_implemented("property reference on a non-variable expression", None);
+            }
+            _ => (source_expr, trailing_names),
+        };
+        for name in names {
+            let name = name.value();
+            let LogicalType::Record(fields) = &expr.logical_type else {
+                return Err(BindError::NotRecord {
+                    field: name.clone(),
+                    ty: expr.logical_type,
+                });
+            };
+            let index = fields
+                .iter()
+                .position(|f| f.name() == name.as_str())
+                .ok_or_else(|| BindError::FieldNotFound(name.clone()))?;
+            expr = BoundExpr::field(expr, index);
+        }
+        Ok(expr)
+    }
+
+    /// Resolves property `name` of the vertex bound to `var`, returning `None` if the vertex type
+    /// does not declare such a property.
+    pub(super) fn lookup_property(&self, var: &Ident, name: &str) -> BindResult<Option<BoundExpr>> {
+        self.active_data_schema
+            .as_ref()
+            .and_then(|schema| schema.get_field_by_name(var))
+            .ok_or_else(|| BindError::VariableNotFound(var.clone()))?;
+        let Some(vertex_type) = self.vertex_types.get(var.as_str()) else {
+            return not_implemented(
+                "property reference on a vertex without a known vertex type",
+                None,
+            );
+        };
+        let property = vertex_type.get_property(name)?.map(|(id, property)| {
+            BoundExpr::property(
+                var.to_string(),
+                id,
+                name.to_string(),
+                property.logical_type().clone(),
+                property.nullable(),
+            )
+        });
+        Ok(property)
+    }
+
+    fn bind_predicate(&self, predicate: &Predicate) -> BindResult<BoundExpr> {
+        match predicate {
+            Predicate::Null { expr, not } => {
+                let child = self.bind_value_expression(expr.value())?;
+                let op = if *not {
+                    BoundUnaryOp::IsNotNull
+                } else {
+                    BoundUnaryOp::IsNull
+                };
+                Ok(BoundExpr::unary(op, child))
+            }
+            Predicate::PropertyExists { element, property } => {
+                match self.lookup_property(element.value(), property.value())? {
+                    Some(property) => Ok(BoundExpr::unary(BoundUnaryOp::IsNotNull, property)),
+                    // A property not declared by the vertex type can never be present.
+                    None => Ok(BoundExpr::value(false.into(), LogicalType::Boolean, false)),
+                }
+            }
+            Predicate::Exists(_) => not_implemented("exists predicate", None),
+            Predicate::Typed { .. } => not_implemented("typed predicate", None),
+            Predicate::Directed { .. } => not_implemented("directed predicate", None),
+            Predicate::Labeled { .. } => not_implemented("labeled predicate", None),
+            Predicate::SrcOf { .. } | Predicate::DstOf { .. } => {
+                not_implemented("source/destination predicate", None)
+            }
+            Predicate::AllDifferent(_) => not_implemented("all_different predicate", None),
+            Predicate::Same(_) => not_implemented("same predicate", None),
         }
     }
 
     fn bind_function_expression(&self, function: &Function) -> BindResult<BoundExpr> {
         match function {
             Function::Vector(vector) => self.bind_vector_distance(vector),
-            Function::Generic(_) => not_implemented("generic function expression", None),
+            Function::Generic(function) => self.bind_generic_function(function),
             Function::Numeric(_) => not_implemented("numeric function expression", None),
             Function::Case(_) => not_implemented("case function expression", None),
         }
     }
 
+    /// Binds a call to a built-in function on graph elements or paths, or else to a user-defined
+    /// scalar function. Literal arguments of user-defined functions are coerced to the types of
+    /// the parameters, which the other arguments must have exactly.
+    fn bind_generic_function(&self, function: &GenericFunction) -> BindResult<BoundExpr> {
+        let name = function.name.value();
+        let args: Vec<_> = function
+            .args
+            .iter()
+            .map(|arg| self.bind_value_expression(arg.value()))
+            .try_collect()?;
+        if let Some(element_function) = BoundElementFunction::from_name(name) {
+            return bind_element_function(name, element_function, args);
+        }
+        if let Some(path_function) = BoundPathFunction::from_name(name) {
+            return bind_path_function(name, path_function, args);
+        }
+        let Some(function_ref) = self.functions.get(name) else {
+            return Err(BindError::FunctionNotFound(name.clone()));
+        };
+        let parameters = function_ref.parameters();
+        let args_types = args.iter().map(|a| a.logical_type.clone()).collect_vec();
+        let mismatch = || BindError::IncorrectFunctionArguments {
+            function: name.clone(),
+            expected: parameters.to_vec(),
+            actual: args_types.clone(),
+        };
+        if args.len() != parameters.len() {
+            return Err(mismatch());
+        }
+        let args = args
+            .into_iter()
+            .zip(parameters)
+            .map(|(arg, ty)| coerce_to_type(arg, ty).ok_or_else(mismatch))
+            .try_collect()?;
+        Ok(BoundExpr::function(name.to_string(), function_ref, args))
+    }
+
     fn bind_vector_distance(&self, function: &VectorDistance) -> BindResult<BoundExpr> {
         let lhs = self.bind_value_expression(function.lhs.as_ref().value())?;
         let rhs = self.bind_value_expression(function.rhs.as_ref().value())?;
@@ -104,12 +290,60 @@ pub fn bind_non_negative_integer(
     }
 }
 
+/// Binds a call to a built-in function on graph elements, whose only argument must be a vertex or
+/// an edge.
+fn bind_element_function(
+    name: &Ident,
+    function: BoundElementFunction,
+    args: Vec<BoundExpr>,
+) -> BindResult<BoundExpr> {
+    let actual = args.iter().map(|a| a.logical_type.clone()).collect_vec();
+    match <[BoundExpr; 1]>::try_from(args) {
+        Ok([element])
+            if matches!(
+                element.logical_type,
+                LogicalType::Vertex(_) | LogicalType::Edge(_)
+            ) =>
+        {
+            Ok(BoundExpr::element_function(function, element))
+        }
+        _ => Err(BindError::InvalidElementFunctionArguments {
+            function: name.clone(),
+            actual,
+        }),
+    }
+}
+
+/// Binds a call to a built-in function on paths, whose only argument must be a path.
+fn bind_path_function(
+    name: &Ident,
+    function: BoundPathFunction,
+    args: Vec<BoundExpr>,
+) -> BindResult<BoundExpr> {
+    let actual = args.iter().map(|a| a.logical_type.clone()).collect_vec();
+    match <[BoundExpr; 1]>::try_from(args) {
+        Ok([path]) if matches!(path.logical_type, LogicalType::Path(..)) => {
+            Ok(BoundExpr::path_function(function, path))
+        }
+        _ => Err(BindError::InvalidPathFunctionArguments {
+            function: name.clone(),
+            actual,
+        }),
+    }
+}
+
 pub fn bind_binary_op(op: &BinaryOp) -> BoundBinaryOp {
     match op {
         BinaryOp::Add => BoundBinaryOp::Add,
         BinaryOp::Sub => BoundBinaryOp::Sub,
         BinaryOp::Mul => BoundBinaryOp::Mul,
         BinaryOp::Div => BoundBinaryOp::Div,
+        BinaryOp::Rem => BoundBinaryOp::Rem,
+        BinaryOp::BitAnd => BoundBinaryOp::BitAnd,
+        BinaryOp::BitOr => BoundBinaryOp::BitOr,
+        BinaryOp::BitXor => BoundBinaryOp::BitXor,
+        BinaryOp::ShiftLeft => BoundBinaryOp::ShiftLeft,
+        BinaryOp::ShiftRight => BoundBinaryOp::ShiftRight,
         BinaryOp::Concat => BoundBinaryOp::Concat,
         BinaryOp::Or => BoundBinaryOp::Or,
         BinaryOp::Xor => BoundBinaryOp::Xor,
@@ -123,6 +357,42 @@ pub fn bind_binary_op(op: &BinaryOp) -> BoundBinaryOp {
     }
 }
 
+/// Binds the type of a cast. Only the types of the values which can be cast are supported.
+pub fn bind_value_type(value_type: &ValueType) -> BindResult<LogicalType> {
+    let ty = match value_type {
+        ValueType::Bool { .. } => LogicalType::Boolean,
+        ValueType::Char { .. } | ValueType::Varchar { .. } | ValueType::String { .. } => {
+            LogicalType::String
+        }
+        ValueType::SignedNumeric { kind, .. } => match kind.value() {
+            NumericTypeKind::Int8 => LogicalType::Int8,
+            NumericTypeKind::Int16 | NumericTypeKind::Small => LogicalType::Int16,
+            NumericTypeKind::Int32 | NumericTypeKind::Int(None) => LogicalType::Int32,
+            NumericTypeKind::Int64 | NumericTypeKind::Big => LogicalType::Int64,
+            _ => return not_implemented("cast to this numeric type", None),
+        },
+        ValueType::UnsignedNumeric { kind, .. } => match kind.value() {
+            NumericTypeKind::Int8 => LogicalType::UInt8,
+            NumericTypeKind::Int16 | NumericTypeKind::Small => LogicalType::UInt16,
+            NumericTypeKind::Int32 | NumericTypeKind::Int(None) => LogicalType::UInt32,
+            NumericTypeKind::Int64 | NumericTypeKind::Big => LogicalType::UInt64,
+            _ => return not_implemented("cast to this numeric type", None),
+        },
+        ValueType::Float { kind, .. } => match kind.value() {
+            FloatTypeKind::Float32 | FloatTypeKind::Real => LogicalType::Float32,
+            FloatTypeKind::Float64
+            | FloatTypeKind::Double
+            | FloatTypeKind::Float {
+                precision: None,
+                scale: None,
+            } => LogicalType::Float64,
+            _ => return not_implemented("cast to this numeric type", None),
+        },
+        _ => return not_implemented("cast to this type", None),
+    };
+    Ok(ty)
+}
+
 pub fn bind_value(value: &Value) -> BindResult<BoundExpr> {
     match value {
         Value::SessionUser => Ok(BoundExpr::value(
@@ -1,4 +1,7 @@
+use std::sync::Arc;
+
 use minigu_catalog::named_ref::NamedGraphRef;
+use minigu_context::view::View;
 use serde::Serialize;
 use smol_str::SmolStr;
 
@@ -14,6 +17,7 @@ pub enum BoundCatalogModifyingStatement {
     DropGraph(BoundDropGraphStatement),
     CreateGraphType(BoundCreateGraphTypeStatement),
     DropGraphType(BoundDropGraphTypeStatement),
+    CreateView(BoundCreateViewStatement),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -65,3 +69,11 @@ pub struct BoundDropGraphTypeStatement {
     pub name: SmolStr,
     pub if_exists: bool,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundCreateViewStatement {
+    pub name: SmolStr,
+    pub or_replace: bool,
+    #[serde(skip)]
+    pub view: Arc<View>,
+}
@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use minigu_common::data_type::DataSchema;
-use minigu_common::types::LabelId;
+use minigu_common::types::{LabelId, PropertyIndexKey};
+use minigu_common::value::ScalarValue;
 use serde::Serialize;
 
 use crate::bound::BoundExpr;
@@ -81,6 +82,17 @@ pub struct BoundVertexPattern {
     pub var: String,
     pub label: Option<BoundLabelExpr>,
     pub predicate: Option<BoundExpr>,
+    /// The lookup forced by the index hint of the pattern, if any.
+    pub index_lookup: Option<BoundIndexLookup>,
+}
+
+/// A lookup of the vertices whose indexed property is `value` in the property index `name`, e.g.,
+/// `(n:Person USING INDEX person_name {name: 'Alice'})`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundIndexLookup {
+    pub name: String,
+    pub key: PropertyIndexKey,
+    pub value: ScalarValue,
 }
 
 #[derive(Debug, Clone, Serialize)]
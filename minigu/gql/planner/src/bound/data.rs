@@ -0,0 +1,49 @@
+use minigu_common::types::{LabelId, PropertyId};
+use serde::Serialize;
+
+use crate::bound::{BoundExpr, BoundResultStatement, BoundSimpleQueryStatement};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundLinearDataModifyingStatement {
+    pub statements: Vec<BoundSimpleDataAccessingStatement>,
+    pub result: Option<BoundResultStatement>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum BoundSimpleDataAccessingStatement {
+    Query(BoundSimpleQueryStatement),
+    Modifying(BoundSimpleDataModifyingStatement),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum BoundSimpleDataModifyingStatement {
+    Insert(Vec<BoundInsertVertex>),
+    Set(Vec<BoundSetItem>),
+    Delete(BoundDeleteStatement),
+}
+
+/// A vertex of `INSERT`, bound to `var`. `properties` holds a value per property of the vertex
+/// type, in the order of the property ids, where each value has already been coerced to the
+/// property type.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundInsertVertex {
+    pub var: String,
+    pub label_id: LabelId,
+    pub properties: Vec<BoundExpr>,
+}
+
+/// `SET var.name = value`, where `value` has already been coerced to the property type.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundSetItem {
+    pub var: String,
+    pub property_id: PropertyId,
+    pub name: String,
+    pub value: BoundExpr,
+}
+
+/// `[DETACH] DELETE vars`, where each of `vars` is bound to a vertex.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundDeleteStatement {
+    pub detach: bool,
+    pub vars: Vec<String>,
+}
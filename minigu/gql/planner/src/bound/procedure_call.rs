@@ -3,6 +3,7 @@
 use serde::Serialize;
 use smol_str::SmolStr;
 
+use super::query::BoundCompositeQueryStatement;
 use super::value_expr::BoundExpr;
 
 #[derive(Debug, Clone, Serialize)]
@@ -15,6 +16,7 @@ impl BoundCallProcedureStatement {
     pub fn name(&self) -> SmolStr {
         match &self.procedure {
             BoundProcedureCall::Named(call) => call.procedure_ref.name().clone(),
+            BoundProcedureCall::View(call) => call.name.clone(),
             _ => todo!(),
         }
     }
@@ -29,6 +31,7 @@ pub fn schema(&self) -> Option<&DataSchemaRef> {
 pub enum BoundProcedureCall {
     Inline(BoundInlineProcedureCall),
     Named(BoundNamedProcedureCall),
+    View(BoundViewCall),
 }
 
 impl BoundProcedureCall {
@@ -36,6 +39,7 @@ impl BoundProcedureCall {
     pub fn schema(&self) -> Option<&DataSchemaRef> {
         match self {
             BoundProcedureCall::Named(call) => call.schema.as_ref(),
+            BoundProcedureCall::View(call) => Some(&call.schema),
             _ => todo!(),
         }
     }
@@ -51,7 +55,23 @@ pub struct BoundNamedProcedureCall {
     pub procedure_ref: NamedProcedureRef,
     /// The arguments of the procedure call.
     pub args: Vec<BoundExpr>,
+    /// The columns of the output of the procedure selected by the yield clause, in the order of
+    /// the clause, or `None` if there is no yield clause.
+    pub yield_items: Option<Vec<BoundExpr>>,
     /// The actual schema of the procedure call (possibly after a yield clause). This is only
     /// available for query procedures.
     pub schema: Option<DataSchemaRef>,
 }
+
+/// A call of a view, whose query is inlined in place of the call.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundViewCall {
+    pub name: SmolStr,
+    /// The query of the view, bound in the state of the calling session.
+    pub query: Box<BoundCompositeQueryStatement>,
+    /// The columns of the query selected by the yield clause, in the order of the clause, or
+    /// `None` if there is no yield clause.
+    pub yield_items: Option<Vec<BoundExpr>>,
+    /// The schema of the call (possibly after a yield clause).
+    pub schema: DataSchemaRef,
+}
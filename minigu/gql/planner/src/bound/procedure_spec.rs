@@ -2,6 +2,7 @@
 use serde::Serialize;
 
 use super::catalog::BoundCatalogModifyingStatement;
+use super::data::BoundLinearDataModifyingStatement;
 use super::query::BoundCompositeQueryStatement;
 
 #[derive(Debug, Clone, Serialize)]
@@ -20,7 +21,7 @@ pub fn schema(&self) -> Option<DataSchemaRef> {
 pub enum BoundStatement {
     Catalog(Vec<BoundCatalogModifyingStatement>),
     Query(BoundCompositeQueryStatement),
-    // Data(BoundLinearDataModifyingStatement),
+    Data(BoundLinearDataModifyingStatement),
 }
 
 #[derive(Debug, Clone, Serialize)]
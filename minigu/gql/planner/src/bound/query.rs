@@ -1,4 +1,6 @@
-use minigu_common::data_type::DataSchemaRef;
+use std::fmt::Display;
+
+use minigu_common::data_type::{DataSchemaRef, LogicalType};
 use minigu_common::ordering::{NullOrdering, SortOrdering};
 use minigu_common::types::{VectorIndexKey, VectorMetric};
 use serde::Serialize;
@@ -14,13 +16,18 @@ pub enum BoundCompositeQueryStatement {
         conjunction: BoundQueryConjunction,
         left: Box<BoundCompositeQueryStatement>,
         right: Box<BoundCompositeQueryStatement>,
+        /// The output schema, named after `left`, with the types shared by both sides.
+        schema: DataSchemaRef,
     },
     Primary(BoundLinearQueryStatement),
 }
 
 impl BoundCompositeQueryStatement {
-    pub fn schema(&self) -> DataSchemaRef {
-        todo!()
+    pub fn schema(&self) -> Option<DataSchemaRef> {
+        match self {
+            BoundCompositeQueryStatement::Conjunction { schema, .. } => Some(schema.clone()),
+            BoundCompositeQueryStatement::Primary(statement) => statement.schema(),
+        }
     }
 }
 
@@ -72,6 +79,102 @@ pub struct BoundReturnStatement {
     pub schema: DataSchemaRef,
 }
 
+/// A `WITH` statement, which projects the rows of the preceding statements, possibly aggregating
+/// them, before passing them to the following ones.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundWithStatement {
+    pub quantifier: Option<BoundSetQuantifier>,
+    pub items: Vec<BoundWithItem>,
+    /// The output schema of the statement, i.e., the variables visible to the following ones.
+    pub schema: DataSchemaRef,
+    /// The condition of the `WHERE` clause, over the output variables.
+    pub predicate: Option<BoundExpr>,
+}
+
+impl BoundWithStatement {
+    /// Returns `true` if the statement aggregates the rows, grouping them by the non-aggregate
+    /// items.
+    pub fn is_aggregate(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| matches!(item, BoundWithItem::Aggregate(_)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum BoundWithItem {
+    Expr(BoundExpr),
+    Aggregate(BoundAggregate),
+}
+
+impl BoundWithItem {
+    pub fn logical_type(&self) -> &LogicalType {
+        match self {
+            BoundWithItem::Expr(expr) => &expr.logical_type,
+            BoundWithItem::Aggregate(aggregate) => &aggregate.logical_type,
+        }
+    }
+
+    pub fn nullable(&self) -> bool {
+        match self {
+            BoundWithItem::Expr(expr) => expr.nullable,
+            BoundWithItem::Aggregate(aggregate) => aggregate.nullable,
+        }
+    }
+}
+
+impl Display for BoundWithItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundWithItem::Expr(expr) => write!(f, "{expr}"),
+            BoundWithItem::Aggregate(aggregate) => write!(f, "{aggregate}"),
+        }
+    }
+}
+
+/// A call to an aggregate function, over the rows of a group.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundAggregate {
+    pub function: BoundAggregateFunction,
+    /// The aggregated expression, which is `None` for `COUNT(*)`.
+    pub arg: Option<BoundExpr>,
+    pub distinct: bool,
+    pub logical_type: LogicalType,
+    pub nullable: bool,
+}
+
+impl Display for BoundAggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let distinct = if self.distinct { "DISTINCT " } else { "" };
+        match &self.arg {
+            Some(arg) => write!(f, "{}({distinct}{arg})", self.function),
+            None => write!(f, "{}(*)", self.function),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BoundAggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Display for BoundAggregateFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Count => "COUNT",
+            Self::Sum => "SUM",
+            Self::Avg => "AVG",
+            Self::Min => "MIN",
+            Self::Max => "MAX",
+        };
+        write!(f, "{name}")
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct BoundLimitClause {
     pub count: usize,
@@ -108,6 +211,9 @@ pub struct BoundVectorIndexScan {
 pub enum BoundSimpleQueryStatement {
     Call(BoundCallProcedureStatement),
     Match(BoundMatchStatement),
+    /// Keeps the rows of the preceding statements for which the condition is true.
+    Filter(BoundExpr),
+    With(BoundWithStatement),
     // TODO(minigu-vector-search): once MATCH binding lands, retain the MATCH-produced
     // candidate set (or bitmap) as input and append a VectorIndexScan to perform the
     // ANN/precise search.
@@ -1,20 +1,64 @@
 use std::fmt::Display;
 
+use itertools::Itertools;
 use minigu_common::data_type::LogicalType;
-use minigu_common::types::VectorMetric;
+use minigu_common::types::{PropertyId, VectorMetric};
 use minigu_common::value::ScalarValue;
+use minigu_context::function::FunctionRef;
 use serde::Serialize;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum BoundExprKind {
     Value(ScalarValue),
     Variable(String),
+    Property {
+        var: String,
+        property_id: PropertyId,
+        name: String,
+    },
+    Unary {
+        op: BoundUnaryOp,
+        child: Box<BoundExpr>,
+    },
+    Binary {
+        op: BoundBinaryOp,
+        lhs: Box<BoundExpr>,
+        rhs: Box<BoundExpr>,
+    },
     VectorDistance {
         lhs: Box<BoundExpr>,
         rhs: Box<BoundExpr>,
         metric: VectorMetric,
         dimension: usize,
     },
+    /// A call to the user-defined scalar function registered as `name`.
+    Function {
+        name: String,
+        #[serde(skip)]
+        function: FunctionRef,
+        args: Vec<BoundExpr>,
+    },
+    /// The field at `index` of a record, named `name`.
+    Field {
+        record: Box<BoundExpr>,
+        index: usize,
+        name: String,
+    },
+    /// A call to a built-in function on a vertex or an edge.
+    ElementFunction {
+        function: BoundElementFunction,
+        element: Box<BoundExpr>,
+    },
+    /// A call to a built-in function on a path.
+    PathFunction {
+        function: BoundPathFunction,
+        path: Box<BoundExpr>,
+    },
+    /// A conversion of `child` to `target`, the type of the expression.
+    Cast {
+        child: Box<BoundExpr>,
+        target: LogicalType,
+    },
 }
 
 impl Display for BoundExprKind {
@@ -23,16 +67,34 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             // TODO: Use `Display` rather than `Debug` representation for `value`.
             BoundExprKind::Value(value) => write!(f, "{value:?}"),
             BoundExprKind::Variable(variable) => write!(f, "{variable}"),
+            BoundExprKind::Property { var, name, .. } => write!(f, "{var}.{name}"),
+            BoundExprKind::Unary { op, child } => match op {
+                BoundUnaryOp::Plus => write!(f, "+{child}"),
+                BoundUnaryOp::Minus => write!(f, "-{child}"),
+                BoundUnaryOp::Not => write!(f, "NOT {child}"),
+                BoundUnaryOp::IsNull => write!(f, "{child} IS NULL"),
+                BoundUnaryOp::IsNotNull => write!(f, "{child} IS NOT NULL"),
+            },
+            BoundExprKind::Binary { op, lhs, rhs } => write!(f, "({lhs} {op} {rhs})"),
             BoundExprKind::VectorDistance {
                 lhs, rhs, metric, ..
             } => {
                 write!(f, "VECTOR_DISTANCE({}, {}, {})", lhs, rhs, metric)
             }
+            BoundExprKind::Function { name, args, .. } => {
+                write!(f, "{name}({})", args.iter().join(", "))
+            }
+            BoundExprKind::Field { record, name, .. } => write!(f, "{record}.{name}"),
+            BoundExprKind::ElementFunction { function, element } => {
+                write!(f, "{function}({element})")
+            }
+            BoundExprKind::PathFunction { function, path } => write!(f, "{function}({path})"),
+            BoundExprKind::Cast { child, target } => write!(f, "CAST({child} AS {target})"),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct BoundExpr {
     pub kind: BoundExprKind,
     pub logical_type: LogicalType,
@@ -56,6 +118,60 @@ pub fn variable(name: String, logical_type: LogicalType, nullable: bool) -> Self
         }
     }
 
+    pub fn property(
+        var: String,
+        property_id: PropertyId,
+        name: String,
+        logical_type: LogicalType,
+        nullable: bool,
+    ) -> Self {
+        Self {
+            kind: BoundExprKind::Property {
+                var,
+                property_id,
+                name,
+            },
+            logical_type,
+            nullable,
+        }
+    }
+
+    pub fn unary(op: BoundUnaryOp, child: BoundExpr) -> Self {
+        let (logical_type, nullable) = match op {
+            BoundUnaryOp::Plus | BoundUnaryOp::Minus => {
+                (child.logical_type.clone(), child.nullable)
+            }
+            BoundUnaryOp::Not => (LogicalType::Boolean, child.nullable),
+            BoundUnaryOp::IsNull | BoundUnaryOp::IsNotNull => (LogicalType::Boolean, false),
+        };
+        Self {
+            kind: BoundExprKind::Unary {
+                op,
+                child: Box::new(child),
+            },
+            logical_type,
+            nullable,
+        }
+    }
+
+    pub fn binary(op: BoundBinaryOp, lhs: BoundExpr, rhs: BoundExpr) -> Self {
+        let nullable = lhs.nullable || rhs.nullable;
+        let logical_type = if op.is_predicate() {
+            LogicalType::Boolean
+        } else {
+            lhs.logical_type.clone()
+        };
+        Self {
+            kind: BoundExprKind::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            },
+            logical_type,
+            nullable,
+        }
+    }
+
     pub fn vector_distance(
         lhs: BoundExpr,
         rhs: BoundExpr,
@@ -75,6 +191,103 @@ pub fn vector_distance(
         }
     }
 
+    /// The result of a function may be null whatever its arguments, so it is always nullable.
+    pub fn function(name: String, function: FunctionRef, args: Vec<BoundExpr>) -> Self {
+        let logical_type = function.return_type().clone();
+        Self {
+            kind: BoundExprKind::Function {
+                name,
+                function,
+                args,
+            },
+            logical_type,
+            nullable: true,
+        }
+    }
+
+    /// The field is null if the record is.
+    pub fn field(record: BoundExpr, index: usize) -> Self {
+        let LogicalType::Record(fields) = &record.logical_type else {
+            unreachable!("fields should only be taken from records")
+        };
+        let field = &fields[index];
+        let name = field.name().to_string();
+        let logical_type = field.ty().clone();
+        let nullable = record.nullable || field.is_nullable();
+        Self {
+            kind: BoundExprKind::Field {
+                record: Box::new(record),
+                index,
+                name,
+            },
+            logical_type,
+            nullable,
+        }
+    }
+
+    /// The result is null if the element is, e.g., an unmatched vertex of an optional match.
+    pub fn element_function(function: BoundElementFunction, element: BoundExpr) -> Self {
+        let logical_type = match (function, &element.logical_type) {
+            (BoundElementFunction::Id, _) => LogicalType::UInt64,
+            (BoundElementFunction::Labels, _) => LogicalType::List(Box::new(LogicalType::String)),
+            (
+                BoundElementFunction::Properties,
+                LogicalType::Vertex(fields) | LogicalType::Edge(fields),
+            ) => LogicalType::Record(fields.clone()),
+            (BoundElementFunction::Properties, ty) => {
+                unreachable!("{ty} is not the type of a vertex or an edge")
+            }
+        };
+        let nullable = element.nullable;
+        Self {
+            kind: BoundExprKind::ElementFunction {
+                function,
+                element: Box::new(element),
+            },
+            logical_type,
+            nullable,
+        }
+    }
+
+    /// The result is null if the path is.
+    pub fn path_function(function: BoundPathFunction, path: BoundExpr) -> Self {
+        let LogicalType::Path(path_fields) = &path.logical_type else {
+            unreachable!("{} is not the type of a path", path.logical_type)
+        };
+        let (vertex_fields, edge_fields) = &**path_fields;
+        let logical_type = match function {
+            BoundPathFunction::Length => LogicalType::Int64,
+            BoundPathFunction::Nodes => {
+                LogicalType::List(Box::new(LogicalType::Vertex(vertex_fields.clone())))
+            }
+            BoundPathFunction::Relationships => {
+                LogicalType::List(Box::new(LogicalType::Edge(edge_fields.clone())))
+            }
+        };
+        let nullable = path.nullable;
+        Self {
+            kind: BoundExprKind::PathFunction {
+                function,
+                path: Box::new(path),
+            },
+            logical_type,
+            nullable,
+        }
+    }
+
+    /// The result is null if `child` is.
+    pub fn cast(child: BoundExpr, target: LogicalType) -> Self {
+        let nullable = child.nullable;
+        Self {
+            kind: BoundExprKind::Cast {
+                child: Box::new(child),
+                target: target.clone(),
+            },
+            logical_type: target,
+            nullable,
+        }
+    }
+
     pub fn evaluate_scalar(self) -> Option<ScalarValue> {
         match self.kind {
             BoundExprKind::Value(value) => Some(value),
@@ -89,12 +302,18 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum BoundBinaryOp {
     Add,
     Sub,
     Mul,
     Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     Concat,
     Or,
     Xor,
@@ -107,11 +326,134 @@ pub enum BoundBinaryOp {
     Ne,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl BoundBinaryOp {
+    /// Returns `true` if the operator yields a boolean, i.e., it is a comparison or a logical
+    /// connective.
+    pub fn is_predicate(&self) -> bool {
+        matches!(
+            self,
+            Self::Or
+                | Self::Xor
+                | Self::And
+                | Self::Lt
+                | Self::Le
+                | Self::Gt
+                | Self::Ge
+                | Self::Eq
+                | Self::Ne
+        )
+    }
+}
+
+impl Display for BoundBinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::BitXor => "^",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::Concat => "||",
+            Self::Or => "OR",
+            Self::Xor => "XOR",
+            Self::And => "AND",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "=",
+            Self::Ne => "<>",
+        };
+        write!(f, "{op}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum BoundUnaryOp {
     Plus,
     Minus,
     Not,
+    IsNull,
+    IsNotNull,
+}
+
+/// A built-in function on graph elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum BoundElementFunction {
+    /// `id(e)`: the id of the element.
+    Id,
+    /// `labels(e)`: the names of the labels of the element.
+    Labels,
+    /// `properties(e)`: the properties of the element, as a record of its property fields.
+    Properties,
+}
+
+impl BoundElementFunction {
+    /// Returns the function named `name`, case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("id") {
+            Some(Self::Id)
+        } else if name.eq_ignore_ascii_case("labels") {
+            Some(Self::Labels)
+        } else if name.eq_ignore_ascii_case("properties") {
+            Some(Self::Properties)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for BoundElementFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Id => "id",
+            Self::Labels => "labels",
+            Self::Properties => "properties",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A built-in function on paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum BoundPathFunction {
+    /// `length(p)`: the number of edges of the path.
+    Length,
+    /// `nodes(p)`: the vertices of the path, in order.
+    Nodes,
+    /// `relationships(p)`: the edges of the path, in order.
+    Relationships,
+}
+
+impl BoundPathFunction {
+    /// Returns the function named `name`, case-insensitively.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("length") {
+            Some(Self::Length)
+        } else if name.eq_ignore_ascii_case("nodes") {
+            Some(Self::Nodes)
+        } else if name.eq_ignore_ascii_case("relationships") {
+            Some(Self::Relationships)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for BoundPathFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Length => "length",
+            Self::Nodes => "nodes",
+            Self::Relationships => "relationships",
+        };
+        write!(f, "{name}")
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
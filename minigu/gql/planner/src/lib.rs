@@ -26,13 +26,24 @@ pub fn new(context: SessionContext) -> Self {
     pub fn plan_query(&self, query: &Procedure) -> PlanResult<PlanNode> {
         let binder = Binder::new(
             self.context.database().catalog(),
+            self.context.database().functions(),
             self.context.current_schema.clone().map(|s| s as _),
             self.context.home_schema.clone().map(|s| s as _),
             self.context.current_graph.clone(),
             self.context.home_graph.clone(),
         );
-        let bound = binder.bind(query)?;
-        let logical_plan = LogicalPlanner::new().create_logical_plan(bound)?;
-        Optimizer::new().create_physical_plan(&logical_plan)
+        let logical_plan = {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!("plan").entered();
+            let bound = binder.bind(query)?;
+            LogicalPlanner::new().create_logical_plan(bound)?
+        };
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("optimize").entered();
+        let mut optimizer = Optimizer::new();
+        if let Some(graph) = &self.context.current_graph {
+            optimizer = optimizer.with_graph(graph.object().clone());
+        }
+        optimizer.create_physical_plan(&logical_plan)
     }
 }
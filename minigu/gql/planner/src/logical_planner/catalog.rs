@@ -1,7 +1,13 @@
-use crate::bound::BoundCatalogModifyingStatement;
+use std::sync::Arc;
+
+use minigu_catalog::named_ref::NamedProcedureRef;
+use minigu_context::view::build_create_procedure;
+
+use crate::bound::{BoundCatalogModifyingStatement, BoundCreateViewStatement};
 use crate::error::PlanResult;
 use crate::logical_planner::LogicalPlanner;
 use crate::plan::PlanNode;
+use crate::plan::call::Call;
 
 impl LogicalPlanner {
     pub fn plan_catalog_modifying_statement(
@@ -10,7 +16,26 @@ pub fn plan_catalog_modifying_statement(
     ) -> PlanResult<PlanNode> {
         match statement {
             BoundCatalogModifyingStatement::Call(call) => self.plan_call_procedure_statement(call),
+            BoundCatalogModifyingStatement::CreateView(statement) => {
+                self.plan_create_view_statement(statement)
+            }
             _ => todo!(),
         }
     }
+
+    /// Plans the creation of a view as a call of a procedure adding it to the current schema.
+    pub fn plan_create_view_statement(
+        &self,
+        statement: BoundCreateViewStatement,
+    ) -> PlanResult<PlanNode> {
+        let BoundCreateViewStatement {
+            name,
+            or_replace,
+            view,
+        } = statement;
+        let procedure = build_create_procedure(name.to_string(), view, or_replace);
+        let procedure = NamedProcedureRef::new("create_view".into(), Arc::new(procedure));
+        let call = Call::new(procedure, vec![], None);
+        Ok(PlanNode::LogicalCall(Arc::new(call)))
+    }
 }
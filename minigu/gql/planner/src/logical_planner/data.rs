@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use minigu_common::error::not_implemented;
+
+use crate::bound::{
+    BoundLinearDataModifyingStatement, BoundSimpleDataAccessingStatement,
+    BoundSimpleDataModifyingStatement, BoundSimpleQueryStatement,
+};
+use crate::error::PlanResult;
+use crate::logical_planner::LogicalPlanner;
+use crate::plan::PlanNode;
+use crate::plan::delete::Delete;
+use crate::plan::filter::Filter;
+use crate::plan::insert::Insert;
+use crate::plan::one_row::OneRow;
+use crate::plan::set_properties::SetProperties;
+
+impl LogicalPlanner {
+    /// Plans the statements of `statement` on top of each other. Without a result statement, the
+    /// rows bound by the last statement are returned.
+    pub fn plan_linear_data_modifying_statement(
+        &self,
+        statement: BoundLinearDataModifyingStatement,
+    ) -> PlanResult<PlanNode> {
+        let mut plan = None;
+        for statement in statement.statements {
+            plan = Some(match (plan, statement) {
+                // A filter applies to the rows bound by the preceding statements.
+                (
+                    Some(child),
+                    BoundSimpleDataAccessingStatement::Query(BoundSimpleQueryStatement::Filter(
+                        predicate,
+                    )),
+                ) => PlanNode::LogicalFilter(Arc::new(Filter::new(child, predicate))),
+                (None, BoundSimpleDataAccessingStatement::Query(statement)) => {
+                    self.plan_simple_query_statement(statement)?
+                }
+                (Some(_), BoundSimpleDataAccessingStatement::Query(_)) => {
+                    return not_implemented("query statement after other statements", None);
+                }
+                (plan, BoundSimpleDataAccessingStatement::Modifying(statement)) => {
+                    let child =
+                        plan.unwrap_or_else(|| PlanNode::LogicalOneRow(Arc::new(OneRow::new())));
+                    self.plan_simple_data_modifying_statement(statement, child)?
+                }
+            });
+        }
+        let plan = plan.expect("at least one statement should be present");
+        match statement.result {
+            Some(result) => self.plan_result_statement(result, plan),
+            None => Ok(plan),
+        }
+    }
+
+    pub fn plan_simple_data_modifying_statement(
+        &self,
+        statement: BoundSimpleDataModifyingStatement,
+        child: PlanNode,
+    ) -> PlanResult<PlanNode> {
+        match statement {
+            BoundSimpleDataModifyingStatement::Insert(vertices) => {
+                let insert = Insert::new(child, vertices);
+                Ok(PlanNode::LogicalInsert(Arc::new(insert)))
+            }
+            BoundSimpleDataModifyingStatement::Set(items) => {
+                let set = SetProperties::new(child, items);
+                Ok(PlanNode::LogicalSetProperties(Arc::new(set)))
+            }
+            BoundSimpleDataModifyingStatement::Delete(statement) => {
+                let delete = Delete::new(child, statement.detach, statement.vars);
+                Ok(PlanNode::LogicalDelete(Arc::new(delete)))
+            }
+        }
+    }
+}
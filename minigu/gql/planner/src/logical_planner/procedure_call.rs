@@ -2,11 +2,14 @@
 
 use minigu_common::error::not_implemented;
 
-use crate::bound::{BoundCallProcedureStatement, BoundNamedProcedureCall, BoundProcedureCall};
+use crate::bound::{
+    BoundCallProcedureStatement, BoundNamedProcedureCall, BoundProcedureCall, BoundViewCall,
+};
 use crate::error::PlanResult;
 use crate::logical_planner::LogicalPlanner;
 use crate::plan::PlanNode;
 use crate::plan::call::Call;
+use crate::plan::project::Project;
 
 impl LogicalPlanner {
     pub fn plan_call_procedure_statement(
@@ -23,18 +26,54 @@ pub fn plan_procedure_call(&self, statement: BoundProcedureCall) -> PlanResult<P
         match statement {
             BoundProcedureCall::Inline(_) => not_implemented("inline procedure call", None),
             BoundProcedureCall::Named(call) => self.plan_named_procedure_call(call),
+            BoundProcedureCall::View(call) => self.plan_view_call(call),
         }
     }
 
     pub fn plan_named_procedure_call(&self, call: BoundNamedProcedureCall) -> PlanResult<PlanNode> {
+        let BoundNamedProcedureCall {
+            procedure_ref,
+            args,
+            yield_items,
+            schema,
+        } = call;
         // The binder guarantees that the arguments are evaluable.
-        let args = call
-            .args
+        let args = args
             .into_iter()
             .map(|arg| arg.evaluate_scalar().expect("arguments must be evaluable"))
             .collect();
-        let schema = call.schema.clone();
-        let call = Call::new(call.procedure_ref, args, schema);
-        Ok(PlanNode::LogicalCall(Arc::new(call)))
+        match yield_items {
+            // The procedure outputs all its columns, which are then projected onto the yielded
+            // ones.
+            Some(items) => {
+                let output_schema = procedure_ref.schema();
+                let call = Call::new(procedure_ref, args, output_schema);
+                let call = PlanNode::LogicalCall(Arc::new(call));
+                let schema = schema.expect("yield clause should have a schema");
+                let project = Project::new(call, items, schema);
+                Ok(PlanNode::LogicalProject(Arc::new(project)))
+            }
+            None => {
+                let call = Call::new(procedure_ref, args, schema);
+                Ok(PlanNode::LogicalCall(Arc::new(call)))
+            }
+        }
+    }
+
+    pub fn plan_view_call(&self, call: BoundViewCall) -> PlanResult<PlanNode> {
+        let BoundViewCall {
+            query,
+            yield_items,
+            schema,
+            ..
+        } = call;
+        let plan = self.plan_composite_query_statement(*query)?;
+        match yield_items {
+            Some(items) => {
+                let project = Project::new(plan, items, schema);
+                Ok(PlanNode::LogicalProject(Arc::new(project)))
+            }
+            None => Ok(plan),
+        }
     }
 }
@@ -27,6 +27,7 @@ pub fn plan_statement(&self, statement: BoundStatement) -> PlanResult<PlanNode>
                 self.plan_catalog_modifying_statement(statement)
             }
             BoundStatement::Query(statement) => self.plan_composite_query_statement(statement),
+            BoundStatement::Data(statement) => self.plan_linear_data_modifying_statement(statement),
         }
     }
 }
@@ -1,20 +1,28 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use minigu_common::data_type::{DataSchema, DataSchemaRef};
 use minigu_common::error::not_implemented;
 
 use crate::bound::{
-    BoundCompositeQueryStatement, BoundLinearQueryStatement, BoundMatchStatement,
-    BoundOrderByAndPageStatement, BoundResultStatement, BoundReturnStatement,
-    BoundSimpleQueryStatement, BoundVectorIndexScan,
+    BoundCompositeQueryStatement, BoundElementPattern, BoundExpr, BoundLinearQueryStatement,
+    BoundMatchStatement, BoundOrderByAndPageStatement, BoundPathPatternExpr, BoundQueryConjunction,
+    BoundResultStatement, BoundReturnStatement, BoundSetOp, BoundSetOpKind, BoundSetQuantifier,
+    BoundSimpleQueryStatement, BoundVectorIndexScan, BoundWithItem, BoundWithStatement,
 };
 use crate::error::PlanResult;
 use crate::logical_planner::LogicalPlanner;
 use crate::plan::PlanNode;
+use crate::plan::aggregate::Aggregate;
+use crate::plan::distinct::Distinct;
+use crate::plan::filter::Filter;
+use crate::plan::join::Join;
 use crate::plan::limit::Limit;
 use crate::plan::logical_match::{LogicalMatch, MatchKind};
 use crate::plan::one_row::OneRow;
 use crate::plan::project::Project;
 use crate::plan::sort::Sort;
+use crate::plan::union::Union;
 use crate::plan::vector_index_scan::VectorIndexScan;
 
 impl LogicalPlanner {
@@ -23,8 +31,28 @@ pub fn plan_composite_query_statement(
         statement: BoundCompositeQueryStatement,
     ) -> PlanResult<PlanNode> {
         match statement {
-            BoundCompositeQueryStatement::Conjunction { .. } => {
-                not_implemented("query conjunction", None)
+            BoundCompositeQueryStatement::Conjunction {
+                conjunction,
+                left,
+                right,
+                schema,
+            } => {
+                let BoundQueryConjunction::SetOp(BoundSetOp {
+                    kind: BoundSetOpKind::Union,
+                    quantifier,
+                }) = conjunction
+                else {
+                    return not_implemented("query conjunction other than UNION", None);
+                };
+                let left = self.plan_composite_query_statement(*left)?;
+                let right = self.plan_composite_query_statement(*right)?;
+                let union = Union::new(vec![left, right], schema);
+                let plan = PlanNode::LogicalUnion(Arc::new(union));
+                // UNION without a quantifier is UNION DISTINCT.
+                match quantifier {
+                    Some(BoundSetQuantifier::All) => Ok(plan),
+                    _ => Ok(PlanNode::LogicalDistinct(Arc::new(Distinct::new(plan)))),
+                }
             }
             BoundCompositeQueryStatement::Primary(statement) => {
                 self.plan_linear_query_statement(statement)
@@ -37,21 +65,25 @@ pub fn plan_linear_query_statement(
         statement: BoundLinearQueryStatement,
     ) -> PlanResult<PlanNode> {
         match statement {
-            BoundLinearQueryStatement::Query {
-                mut statements,
-                result,
-            } => {
-                if statements.len() > 1 {
-                    return not_implemented("multiple statements", None);
+            BoundLinearQueryStatement::Query { statements, result } => {
+                let mut plan = None;
+                for statement in statements {
+                    plan = Some(match (plan, statement) {
+                        // A filter applies to the rows bound by the preceding statements.
+                        (Some(child), BoundSimpleQueryStatement::Filter(predicate)) => {
+                            PlanNode::LogicalFilter(Arc::new(Filter::new(child, predicate)))
+                        }
+                        (Some(child), BoundSimpleQueryStatement::With(statement)) => {
+                            self.plan_with_statement(statement, child)?
+                        }
+                        (Some(child), BoundSimpleQueryStatement::Match(statement)) => {
+                            self.plan_subsequent_match_statement(statement, child)?
+                        }
+                        (None, statement) => self.plan_simple_query_statement(statement)?,
+                        (Some(_), _) => return not_implemented("multiple statements", None),
+                    });
                 }
-                let plan = if statements.is_empty() {
-                    PlanNode::LogicalOneRow(Arc::new(OneRow::new()))
-                } else {
-                    let statement = statements
-                        .pop()
-                        .expect("at least one statement should be present");
-                    self.plan_simple_query_statement(statement)?
-                };
+                let plan = plan.unwrap_or_else(|| PlanNode::LogicalOneRow(Arc::new(OneRow::new())));
                 self.plan_result_statement(result, plan)
             }
             BoundLinearQueryStatement::Nested(_) => not_implemented("nested query", None),
@@ -68,6 +100,15 @@ pub fn plan_simple_query_statement(
                 self.plan_call_procedure_statement(statement)
             }
             BoundSimpleQueryStatement::Match(statement) => self.plan_match_statement(statement),
+            BoundSimpleQueryStatement::Filter(predicate) => {
+                let one_row = PlanNode::LogicalOneRow(Arc::new(OneRow::new()));
+                let filter = Filter::new(one_row, predicate);
+                Ok(PlanNode::LogicalFilter(Arc::new(filter)))
+            }
+            BoundSimpleQueryStatement::With(statement) => {
+                let one_row = PlanNode::LogicalOneRow(Arc::new(OneRow::new()));
+                self.plan_with_statement(statement, one_row)
+            }
 
             BoundSimpleQueryStatement::VectorIndexScan(statement) => {
                 self.plan_vector_index_scan_statement(statement)
@@ -90,6 +131,51 @@ pub fn plan_match_statement(&self, statement: BoundMatchStatement) -> PlanResult
         }
     }
 
+    /// Plans `statement`, which follows the statements planned as `plan`, by joining the rows it
+    /// matches with the rows of `plan` binding the same values to their common variables.
+    ///
+    /// The `WHERE` clause of the graph pattern may refer to the variables bound by `plan`, so it
+    /// filters the joined rows.
+    fn plan_subsequent_match_statement(
+        &self,
+        statement: BoundMatchStatement,
+        plan: PlanNode,
+    ) -> PlanResult<PlanNode> {
+        let BoundMatchStatement::Simple(mut binding) = statement else {
+            return not_implemented("match statement optional", None);
+        };
+        let predicate = binding.pattern.predicate.take();
+        // The output schema of the binding table holds all the variables bound so far, whereas
+        // the match only outputs the variables of its pattern.
+        let mut variables = HashSet::new();
+        for path in &binding.pattern.paths {
+            collect_pattern_variables(&path.expr, &mut variables);
+        }
+        let fields: Vec<_> = binding
+            .output_schema
+            .fields()
+            .iter()
+            .filter(|f| variables.contains(f.name()))
+            .cloned()
+            .collect();
+        let yield_clause = fields
+            .iter()
+            .map(|f| BoundExpr::variable(f.name().to_string(), f.ty().clone(), f.is_nullable()))
+            .collect();
+        let node = LogicalMatch::new(
+            MatchKind::Simple,
+            binding.pattern,
+            yield_clause,
+            DataSchema::new(fields),
+        );
+        let matched = PlanNode::LogicalMatch(Arc::new(node));
+        let mut plan = PlanNode::LogicalJoin(Arc::new(Join::new(plan, matched)));
+        if let Some(predicate) = predicate {
+            plan = PlanNode::LogicalFilter(Arc::new(Filter::new(plan, predicate)));
+        }
+        Ok(plan)
+    }
+
     fn plan_vector_index_scan_statement(
         &self,
         statement: BoundVectorIndexScan,
@@ -132,13 +218,44 @@ pub fn plan_return_statement(
         statement: BoundReturnStatement,
         mut plan: PlanNode,
     ) -> PlanResult<PlanNode> {
-        if statement.quantifier.is_some() {
-            return not_implemented("set quantifier in return statement", None);
-        }
         if let Some(items) = statement.items {
             let project = Project::new(plan, items, statement.schema);
             plan = PlanNode::LogicalProject(Arc::new(project));
         }
+        // ORDER BY and LIMIT are planned above, so they apply to the distinct rows.
+        if let Some(BoundSetQuantifier::Distinct) = statement.quantifier {
+            plan = PlanNode::LogicalDistinct(Arc::new(Distinct::new(plan)));
+        }
+        Ok(plan)
+    }
+
+    /// Plans `statement` on top of `plan`. The rows are aggregated if any item is an aggregate,
+    /// grouping them by the other items, and then filtered by the `WHERE` clause.
+    pub fn plan_with_statement(
+        &self,
+        statement: BoundWithStatement,
+        mut plan: PlanNode,
+    ) -> PlanResult<PlanNode> {
+        if statement.is_aggregate() {
+            plan = plan_aggregate(plan, statement.items, statement.schema);
+        } else {
+            let exprs = statement
+                .items
+                .into_iter()
+                .map(|item| match item {
+                    BoundWithItem::Expr(expr) => expr,
+                    BoundWithItem::Aggregate(_) => unreachable!(),
+                })
+                .collect();
+            let project = Project::new(plan, exprs, statement.schema);
+            plan = PlanNode::LogicalProject(Arc::new(project));
+        }
+        if let Some(BoundSetQuantifier::Distinct) = statement.quantifier {
+            plan = PlanNode::LogicalDistinct(Arc::new(Distinct::new(plan)));
+        }
+        if let Some(predicate) = statement.predicate {
+            plan = PlanNode::LogicalFilter(Arc::new(Filter::new(plan, predicate)));
+        }
         Ok(plan)
     }
 
@@ -162,3 +279,114 @@ pub fn plan_order_by_and_page_statement(
         Ok(plan)
     }
 }
+
+/// Plans the aggregation of the rows of `plan` into `items`, grouped by those which are not
+/// aggregates. The aggregate outputs its keys first, so the items are reordered by a projection if
+/// they are not.
+fn plan_aggregate(plan: PlanNode, items: Vec<BoundWithItem>, schema: DataSchemaRef) -> PlanNode {
+    let keys_first = items.is_sorted_by_key(|item| matches!(item, BoundWithItem::Aggregate(_)));
+    let mut group_by = Vec::new();
+    let mut aggregates = Vec::new();
+    let mut key_fields = Vec::new();
+    let mut aggregate_fields = Vec::new();
+    for (item, field) in items.into_iter().zip(schema.fields()) {
+        match item {
+            BoundWithItem::Expr(expr) => {
+                group_by.push(expr);
+                key_fields.push(field.clone());
+            }
+            BoundWithItem::Aggregate(aggregate) => {
+                aggregates.push(aggregate);
+                aggregate_fields.push(field.clone());
+            }
+        }
+    }
+    key_fields.extend(aggregate_fields);
+    let aggregate_schema = Arc::new(DataSchema::new(key_fields));
+    let aggregate = Aggregate::new(plan, group_by, aggregates, aggregate_schema);
+    let plan = PlanNode::LogicalAggregate(Arc::new(aggregate));
+    if keys_first {
+        return plan;
+    }
+    let exprs = schema
+        .fields()
+        .iter()
+        .map(|f| BoundExpr::variable(f.name().to_string(), f.ty().clone(), f.is_nullable()))
+        .collect();
+    PlanNode::LogicalProject(Arc::new(Project::new(plan, exprs, schema)))
+}
+
+/// Collects the names of the variables bound by the element patterns of `expr`.
+fn collect_pattern_variables(expr: &BoundPathPatternExpr, variables: &mut HashSet<String>) {
+    match expr {
+        BoundPathPatternExpr::Union(exprs)
+        | BoundPathPatternExpr::Alternation(exprs)
+        | BoundPathPatternExpr::Concat(exprs) => {
+            for expr in exprs {
+                collect_pattern_variables(expr, variables);
+            }
+        }
+        BoundPathPatternExpr::Quantified { path, .. } | BoundPathPatternExpr::Optional(path) => {
+            collect_pattern_variables(path, variables)
+        }
+        BoundPathPatternExpr::Subpath(subpath) => {
+            collect_pattern_variables(&subpath.expr, variables)
+        }
+        BoundPathPatternExpr::Pattern(BoundElementPattern::Vertex(vertex)) => {
+            variables.insert(vertex.var.clone());
+        }
+        BoundPathPatternExpr::Pattern(BoundElementPattern::Edge(edge)) => {
+            if let Some(var) = &edge.var {
+                variables.insert(var.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+
+    use super::*;
+    use crate::bound::{BoundExpr, BoundLimitClause};
+    use crate::plan::PlanData;
+
+    #[test]
+    fn test_return_distinct_below_limit() {
+        let field = DataField::new("x".into(), LogicalType::Int32, false);
+        let statement = BoundResultStatement::Return {
+            statement: BoundReturnStatement {
+                quantifier: Some(BoundSetQuantifier::Distinct),
+                items: Some(vec![BoundExpr::variable(
+                    "one_row".into(),
+                    LogicalType::Int32,
+                    false,
+                )]),
+                schema: Arc::new(DataSchema::new(vec![field])),
+            },
+            order_by_and_page: Some(BoundOrderByAndPageStatement {
+                order_by: vec![],
+                offset: None,
+                limit: Some(BoundLimitClause {
+                    count: 1,
+                    approximate: false,
+                }),
+            }),
+        };
+        let input = PlanNode::LogicalOneRow(Arc::new(OneRow::new()));
+        let plan = LogicalPlanner::new()
+            .plan_result_statement(statement, input)
+            .unwrap();
+        // The limit applies to the distinct rows.
+        let PlanNode::LogicalLimit(limit) = &plan else {
+            panic!("expected a limit, got {plan:?}");
+        };
+        let PlanNode::LogicalDistinct(distinct) = &limit.children()[0] else {
+            panic!("expected a distinct, got {:?}", limit.children()[0]);
+        };
+        assert!(matches!(
+            distinct.children()[0],
+            PlanNode::LogicalProject(_)
+        ));
+    }
+}
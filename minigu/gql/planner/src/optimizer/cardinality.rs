@@ -0,0 +1,31 @@
+//! Estimation of the number of rows produced by scans, according to the vertex counts of the
+//! latest analysis of the graph.
+
+use std::collections::BTreeSet;
+
+use minigu_catalog::provider::GraphProvider;
+use minigu_common::types::LabelId;
+
+/// Returns the estimated number of vertices matching `labels`, a disjunction of conjunctions of
+/// labels, or `None` if the graph has not been analyzed.
+///
+/// Vertices carry a single label, so a conjunction of distinct labels matches none of them.
+pub fn estimate_scan_rows(labels: &[Vec<LabelId>], graph: &dyn GraphProvider) -> Option<u64> {
+    let total = graph.estimated_vertex_count(None)?;
+    let mut matched = BTreeSet::new();
+    for conjunction in labels {
+        let mut label_ids = conjunction.iter().copied();
+        let Some(label_id) = label_ids.next() else {
+            // An empty conjunction matches every vertex.
+            return Some(total);
+        };
+        if label_ids.all(|other| other == label_id) {
+            matched.insert(label_id);
+        }
+    }
+    let rows = matched
+        .into_iter()
+        .map(|label_id| graph.estimated_vertex_count(Some(label_id)).unwrap_or(0))
+        .sum::<u64>();
+    Some(rows.min(total))
+}
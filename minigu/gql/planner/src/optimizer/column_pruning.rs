@@ -0,0 +1,348 @@
+//! Removal of columns that are never used by downstream operators.
+//!
+//! Liveness is computed top-down: every operator requires the columns it outputs to its parent,
+//! plus the columns referenced by its own expressions. Projections then drop the expressions whose
+//! outputs are dead, and narrowing projections are inserted below operators that would otherwise
+//! materialize dead columns (e.g., sorts).
+//!
+//! The properties of scanned vertices are read by property scans inserted below the projections
+//! using them, so that only the properties of live outputs are read from the graph.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use minigu_common::data_type::{DataSchema, DataSchemaRef, LogicalType};
+
+use crate::bound::{BoundExpr, BoundExprKind};
+use crate::plan::aggregate::Aggregate;
+use crate::plan::delete::Delete;
+use crate::plan::distinct::Distinct;
+use crate::plan::filter::Filter;
+use crate::plan::insert::Insert;
+use crate::plan::join::Join;
+use crate::plan::limit::Limit;
+use crate::plan::project::Project;
+use crate::plan::scan::PhysicalVertexPropertyScan;
+use crate::plan::set_properties::SetProperties;
+use crate::plan::sort::Sort;
+use crate::plan::union::Union;
+use crate::plan::{PlanData, PlanNode};
+
+/// Removes the columns of `plan` that do not contribute to its output.
+pub fn prune_columns(plan: PlanNode) -> PlanNode {
+    let Some(schema) = plan.schema() else {
+        return plan;
+    };
+    let required = schema
+        .fields()
+        .iter()
+        .map(|f| f.name().to_string())
+        .collect();
+    prune(&plan, &required)
+}
+
+fn prune(plan: &PlanNode, required: &HashSet<String>) -> PlanNode {
+    match plan {
+        PlanNode::PhysicalProject(project) => {
+            let schema = project.schema().expect("project should have a schema");
+            let (exprs, fields): (Vec<_>, Vec<_>) = project
+                .exprs
+                .iter()
+                .zip(schema.fields())
+                .filter(|(_, f)| required.contains(f.name()))
+                .map(|(e, f)| (e.clone(), f.clone()))
+                .unzip();
+            // A projection must output at least one column.
+            if exprs.is_empty() {
+                return plan.clone();
+            }
+            let mut child_required = HashSet::new();
+            exprs
+                .iter()
+                .for_each(|e| collect_columns(e, &mut child_required));
+            let child = prune(&project.children()[0], &child_required);
+            let (child, exprs) = scan_properties(child, exprs);
+            let schema = if fields.len() == schema.fields().len() {
+                schema.clone()
+            } else {
+                Arc::new(DataSchema::new(fields))
+            };
+            PlanNode::PhysicalProject(Arc::new(Project::new(child, exprs, schema)))
+        }
+        PlanNode::PhysicalFilter(filter) => {
+            let mut child_required = required.clone();
+            collect_columns(&filter.predicate, &mut child_required);
+            let child = prune(&filter.children()[0], &child_required);
+            PlanNode::PhysicalFilter(Arc::new(Filter::new(child, filter.predicate.clone())))
+        }
+        PlanNode::PhysicalSort(sort) => {
+            let mut child_required = required.clone();
+            sort.specs
+                .iter()
+                .for_each(|s| collect_columns(&s.key, &mut child_required));
+            let child = prune(&sort.children()[0], &child_required);
+            // Sorting materializes all of its input, so dead columns are dropped beforehand.
+            let child = narrow(child, &child_required);
+            PlanNode::PhysicalSort(Arc::new(Sort::new(child, sort.specs.clone())))
+        }
+        PlanNode::PhysicalDistinct(distinct) => {
+            // Rows are compared on all of their columns, so none of them can be dropped.
+            let child = &distinct.children()[0];
+            let child_required = child
+                .schema()
+                .map(|schema| {
+                    schema
+                        .fields()
+                        .iter()
+                        .map(|f| f.name().to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let child = prune(child, &child_required);
+            PlanNode::PhysicalDistinct(Arc::new(Distinct::new(child)))
+        }
+        PlanNode::PhysicalSetProperties(set) => {
+            let mut child_required = required.clone();
+            for item in &set.items {
+                child_required.insert(item.var.clone());
+                collect_columns(&item.value, &mut child_required);
+            }
+            let child = prune(&set.children()[0], &child_required);
+            PlanNode::PhysicalSetProperties(Arc::new(SetProperties::new(child, set.items.clone())))
+        }
+        PlanNode::PhysicalInsert(insert) => {
+            let mut child_required = required.clone();
+            insert
+                .vertices
+                .iter()
+                .flat_map(|vertex| &vertex.properties)
+                .for_each(|e| collect_columns(e, &mut child_required));
+            let child = prune(&insert.children()[0], &child_required);
+            PlanNode::PhysicalInsert(Arc::new(Insert::new(child, insert.vertices.clone())))
+        }
+        PlanNode::PhysicalDelete(delete) => {
+            let mut child_required = required.clone();
+            child_required.extend(delete.vars.iter().cloned());
+            let child = prune(&delete.children()[0], &child_required);
+            PlanNode::PhysicalDelete(Arc::new(Delete::new(
+                child,
+                delete.detach,
+                delete.vars.clone(),
+            )))
+        }
+        PlanNode::PhysicalLimit(limit) => {
+            let child = prune(&limit.children()[0], required);
+            PlanNode::PhysicalLimit(Arc::new(Limit::new(child, limit.limit, limit.approximate)))
+        }
+        PlanNode::PhysicalAggregate(aggregate) | PlanNode::PhysicalSortedAggregate(aggregate) => {
+            // Dropping a key would merge groups, so all the outputs are kept.
+            let mut child_required = HashSet::new();
+            aggregate
+                .group_by
+                .iter()
+                .chain(aggregate.aggregates.iter().filter_map(|a| a.arg.as_ref()))
+                .for_each(|e| collect_columns(e, &mut child_required));
+            let child = prune(&aggregate.children()[0], &child_required);
+            let schema = aggregate.schema().expect("aggregate should have a schema");
+            let pruned = Arc::new(Aggregate::new(
+                child,
+                aggregate.group_by.clone(),
+                aggregate.aggregates.clone(),
+                schema.clone(),
+            ));
+            // Projections and narrowing projections preserve the order of rows, so sorted input
+            // stays sorted.
+            if matches!(plan, PlanNode::PhysicalSortedAggregate(_)) {
+                PlanNode::PhysicalSortedAggregate(pruned)
+            } else {
+                PlanNode::PhysicalAggregate(pruned)
+            }
+        }
+        PlanNode::PhysicalJoin(join) => {
+            // The keys are compared on both sides, whether they are output or not.
+            let mut child_required = required.clone();
+            child_required.extend(join.keys.iter().cloned());
+            let left = prune(&join.children()[0], &child_required);
+            let right = prune(&join.children()[1], &child_required);
+            PlanNode::PhysicalJoin(Arc::new(Join::new(left, right)))
+        }
+        PlanNode::PhysicalUnion(union) => {
+            // Columns are matched by position, so every child keeps all of its columns, which
+            // only allows pruning within the children.
+            let children = union
+                .children()
+                .iter()
+                .cloned()
+                .map(prune_columns)
+                .collect();
+            let schema = union.schema().expect("union should have a schema");
+            PlanNode::PhysicalUnion(Arc::new(Union::new(children, schema.clone())))
+        }
+        _ => plan.clone(),
+    }
+}
+
+/// Wraps `plan` in a projection keeping only the `required` columns, if any other column is
+/// present.
+fn narrow(plan: PlanNode, required: &HashSet<String>) -> PlanNode {
+    let Some(schema) = plan.schema() else {
+        return plan;
+    };
+    let fields: Vec<_> = schema
+        .fields()
+        .iter()
+        .filter(|f| required.contains(f.name()))
+        .cloned()
+        .collect();
+    if fields.is_empty() || fields.len() == schema.fields().len() {
+        return plan;
+    }
+    let exprs = fields
+        .iter()
+        .map(|f| BoundExpr::variable(f.name().to_string(), f.ty().clone(), f.is_nullable()))
+        .collect();
+    let schema: DataSchemaRef = Arc::new(DataSchema::new(fields));
+    PlanNode::PhysicalProject(Arc::new(Project::new(plan, exprs, schema)))
+}
+
+/// Scans the properties of vertices used by `exprs` below them, and rewrites `exprs` to read the
+/// scanned columns of `child`.
+///
+/// Only the vertices bound to ids are scanned. Materialized vertices already hold their properties.
+fn scan_properties(mut child: PlanNode, exprs: Vec<BoundExpr>) -> (PlanNode, Vec<BoundExpr>) {
+    let schema = child.schema().expect("child should have a schema").clone();
+    let mut properties = vec![];
+    let exprs: Vec<_> = exprs
+        .iter()
+        .map(|e| read_scanned_properties(e, &schema, &mut properties))
+        .collect();
+    let mut vars: Vec<&str> = vec![];
+    for property in &properties {
+        let BoundExprKind::Property { var, .. } = &property.kind else {
+            unreachable!("only properties should be scanned")
+        };
+        if vars.contains(&var.as_str()) {
+            continue;
+        }
+        vars.push(var);
+        let scanned = properties
+            .iter()
+            .filter(|p| matches!(&p.kind, BoundExprKind::Property { var: v, .. } if v == var))
+            .cloned()
+            .collect();
+        let scan = PhysicalVertexPropertyScan::new(child, var, scanned);
+        child = PlanNode::PhysicalVertexPropertyScan(Arc::new(scan));
+    }
+    (child, exprs)
+}
+
+/// Rewrites the properties of the vertex ids of `schema` in `expr` to the columns they are scanned
+/// into, collecting them into `properties`.
+fn read_scanned_properties(
+    expr: &BoundExpr,
+    schema: &DataSchema,
+    properties: &mut Vec<BoundExpr>,
+) -> BoundExpr {
+    if let BoundExprKind::Property { var, .. } = &expr.kind {
+        let column = expr.to_string();
+        let is_id = schema
+            .get_field_by_name(var)
+            .is_some_and(|f| matches!(f.ty(), LogicalType::Int64));
+        // A column of the same name would be ambiguous, so the property is then looked up.
+        if !is_id || schema.get_field_by_name(&column).is_some() {
+            return expr.clone();
+        }
+        if !properties.iter().any(|p| p.to_string() == column) {
+            properties.push(expr.clone());
+        }
+        return BoundExpr::variable(column, expr.logical_type.clone(), expr.nullable);
+    }
+    let mut read = |e: &BoundExpr| Box::new(read_scanned_properties(e, schema, properties));
+    let kind = match &expr.kind {
+        BoundExprKind::Value(_) | BoundExprKind::Variable(_) | BoundExprKind::Property { .. } => {
+            expr.kind.clone()
+        }
+        BoundExprKind::Unary { op, child } => BoundExprKind::Unary {
+            op: op.clone(),
+            child: read(child),
+        },
+        BoundExprKind::Binary { op, lhs, rhs } => BoundExprKind::Binary {
+            op: op.clone(),
+            lhs: read(lhs),
+            rhs: read(rhs),
+        },
+        BoundExprKind::VectorDistance {
+            lhs,
+            rhs,
+            metric,
+            dimension,
+        } => BoundExprKind::VectorDistance {
+            lhs: read(lhs),
+            rhs: read(rhs),
+            metric: *metric,
+            dimension: *dimension,
+        },
+        BoundExprKind::Function {
+            name,
+            function,
+            args,
+        } => BoundExprKind::Function {
+            name: name.clone(),
+            function: function.clone(),
+            args: args.iter().map(|arg| *read(arg)).collect(),
+        },
+        BoundExprKind::Field {
+            record,
+            index,
+            name,
+        } => BoundExprKind::Field {
+            record: read(record),
+            index: *index,
+            name: name.clone(),
+        },
+        BoundExprKind::ElementFunction { function, element } => BoundExprKind::ElementFunction {
+            function: *function,
+            element: read(element),
+        },
+        BoundExprKind::PathFunction { function, path } => BoundExprKind::PathFunction {
+            function: *function,
+            path: read(path),
+        },
+        BoundExprKind::Cast { child, target } => BoundExprKind::Cast {
+            child: read(child),
+            target: target.clone(),
+        },
+    };
+    BoundExpr {
+        kind,
+        logical_type: expr.logical_type.clone(),
+        nullable: expr.nullable,
+    }
+}
+
+/// Collects the names of the columns referenced by `expr`.
+fn collect_columns(expr: &BoundExpr, columns: &mut HashSet<String>) {
+    match &expr.kind {
+        BoundExprKind::Value(_) => (),
+        BoundExprKind::Variable(name) => {
+            columns.insert(name.clone());
+        }
+        BoundExprKind::Property { var, .. } => {
+            columns.insert(var.clone());
+        }
+        BoundExprKind::Unary { child, .. } => collect_columns(child, columns),
+        BoundExprKind::Binary { lhs, rhs, .. } | BoundExprKind::VectorDistance { lhs, rhs, .. } => {
+            collect_columns(lhs, columns);
+            collect_columns(rhs, columns);
+        }
+        BoundExprKind::Function { args, .. } => {
+            for arg in args {
+                collect_columns(arg, columns);
+            }
+        }
+        BoundExprKind::Field { record, .. } => collect_columns(record, columns),
+        BoundExprKind::ElementFunction { element, .. } => collect_columns(element, columns),
+        BoundExprKind::PathFunction { path, .. } => collect_columns(path, columns),
+        BoundExprKind::Cast { child, .. } => collect_columns(child, columns),
+    }
+}
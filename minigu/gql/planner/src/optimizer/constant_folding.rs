@@ -0,0 +1,295 @@
+//! Evaluation of constant subexpressions at plan time.
+//!
+//! A subexpression is folded only if all of its operands are literals and the result is exactly
+//! what the executor would compute for it. Anything else (e.g., integer overflow, division by zero,
+//! or operands of different types) is left untouched, so that the executor reports the error.
+
+use std::cmp::Ordering;
+
+use minigu_common::data_type::LogicalType;
+use minigu_common::value::{F32, F64, ScalarValue};
+
+use crate::bound::{BoundBinaryOp, BoundExpr, BoundExprKind, BoundUnaryOp};
+
+/// Replaces constant subexpressions of `expr` with their values.
+pub fn fold_constants(expr: BoundExpr) -> BoundExpr {
+    let BoundExpr {
+        kind,
+        logical_type,
+        nullable,
+    } = expr;
+    let kind = match kind {
+        BoundExprKind::Unary { op, child } => {
+            let child = fold_constants(*child);
+            if let BoundExprKind::Value(value) = &child.kind {
+                if let Some(folded) = fold_unary(&op, value) {
+                    return literal(folded, logical_type);
+                }
+            }
+            BoundExprKind::Unary {
+                op,
+                child: Box::new(child),
+            }
+        }
+        BoundExprKind::Binary { op, lhs, rhs } => {
+            let lhs = fold_constants(*lhs);
+            let rhs = fold_constants(*rhs);
+            if let (BoundExprKind::Value(l), BoundExprKind::Value(r)) = (&lhs.kind, &rhs.kind) {
+                if let Some(folded) = fold_binary(&op, l, r, &lhs.logical_type, &rhs.logical_type) {
+                    return literal(folded, logical_type);
+                }
+            }
+            BoundExprKind::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            }
+        }
+        BoundExprKind::VectorDistance {
+            lhs,
+            rhs,
+            metric,
+            dimension,
+        } => BoundExprKind::VectorDistance {
+            lhs: Box::new(fold_constants(*lhs)),
+            rhs: Box::new(fold_constants(*rhs)),
+            metric,
+            dimension,
+        },
+        // User-defined functions are only called by the executor, so only their arguments are
+        // folded.
+        BoundExprKind::Function {
+            name,
+            function,
+            args,
+        } => BoundExprKind::Function {
+            name,
+            function,
+            args: args.into_iter().map(fold_constants).collect(),
+        },
+        // Casts are only performed by the executor, so that invalid values are reported as query
+        // errors, and only their operand is folded.
+        BoundExprKind::Cast { child, target } => BoundExprKind::Cast {
+            child: Box::new(fold_constants(*child)),
+            target,
+        },
+        kind => kind,
+    };
+    BoundExpr {
+        kind,
+        logical_type,
+        nullable,
+    }
+}
+
+/// Returns the value of `expr` if it is a boolean literal, with `NULL` mapped to `None`.
+pub fn constant_predicate(expr: &BoundExpr) -> Option<Option<bool>> {
+    match &expr.kind {
+        BoundExprKind::Value(ScalarValue::Boolean(value)) => Some(*value),
+        BoundExprKind::Value(ScalarValue::Null) => Some(None),
+        _ => None,
+    }
+}
+
+fn literal(value: ScalarValue, logical_type: LogicalType) -> BoundExpr {
+    let nullable = is_null(&value);
+    BoundExpr::value(value, logical_type, nullable)
+}
+
+fn fold_unary(op: &BoundUnaryOp, value: &ScalarValue) -> Option<ScalarValue> {
+    match op {
+        BoundUnaryOp::Plus => Some(value.clone()),
+        BoundUnaryOp::Minus => negate(value),
+        BoundUnaryOp::Not => match value {
+            ScalarValue::Boolean(value) => Some(ScalarValue::Boolean(value.map(|v| !v))),
+            _ => None,
+        },
+        BoundUnaryOp::IsNull => Some(is_null(value).into()),
+        BoundUnaryOp::IsNotNull => Some((!is_null(value)).into()),
+    }
+}
+
+fn fold_binary(
+    op: &BoundBinaryOp,
+    lhs: &ScalarValue,
+    rhs: &ScalarValue,
+    lhs_type: &LogicalType,
+    rhs_type: &LogicalType,
+) -> Option<ScalarValue> {
+    match op {
+        BoundBinaryOp::And | BoundBinaryOp::Or => {
+            let (ScalarValue::Boolean(l), ScalarValue::Boolean(r)) = (lhs, rhs) else {
+                return None;
+            };
+            // Three-valued logic, as implemented by the executor.
+            let result = match (op, l, r) {
+                (BoundBinaryOp::And, Some(false), _) | (BoundBinaryOp::And, _, Some(false)) => {
+                    Some(false)
+                }
+                (BoundBinaryOp::And, Some(true), Some(true)) => Some(true),
+                (BoundBinaryOp::Or, Some(true), _) | (BoundBinaryOp::Or, _, Some(true)) => {
+                    Some(true)
+                }
+                (BoundBinaryOp::Or, Some(false), Some(false)) => Some(false),
+                _ => None,
+            };
+            Some(ScalarValue::Boolean(result))
+        }
+        // The executor does not support these operators.
+        BoundBinaryOp::Xor | BoundBinaryOp::Concat => None,
+        // Bitwise operators are rare in constant expressions, so they are left to the executor.
+        BoundBinaryOp::BitAnd
+        | BoundBinaryOp::BitOr
+        | BoundBinaryOp::BitXor
+        | BoundBinaryOp::ShiftLeft
+        | BoundBinaryOp::ShiftRight => None,
+        _ if lhs_type != rhs_type || is_null(lhs) || is_null(rhs) => None,
+        BoundBinaryOp::Add
+        | BoundBinaryOp::Sub
+        | BoundBinaryOp::Mul
+        | BoundBinaryOp::Div
+        | BoundBinaryOp::Rem => arithmetic(op, lhs, rhs, lhs_type),
+        BoundBinaryOp::Eq
+        | BoundBinaryOp::Ne
+        | BoundBinaryOp::Lt
+        | BoundBinaryOp::Le
+        | BoundBinaryOp::Gt
+        | BoundBinaryOp::Ge => {
+            let ordering = compare(lhs, rhs, lhs_type)?;
+            let result = match op {
+                BoundBinaryOp::Eq => ordering.is_eq(),
+                BoundBinaryOp::Ne => ordering.is_ne(),
+                BoundBinaryOp::Lt => ordering.is_lt(),
+                BoundBinaryOp::Le => ordering.is_le(),
+                BoundBinaryOp::Gt => ordering.is_gt(),
+                BoundBinaryOp::Ge => ordering.is_ge(),
+                _ => unreachable!(),
+            };
+            Some(result.into())
+        }
+    }
+}
+
+fn arithmetic(
+    op: &BoundBinaryOp,
+    lhs: &ScalarValue,
+    rhs: &ScalarValue,
+    ty: &LogicalType,
+) -> Option<ScalarValue> {
+    if is_integer(ty) {
+        let (l, r) = (lhs.to_i64().ok()?, rhs.to_i64().ok()?);
+        let result = match op {
+            BoundBinaryOp::Add => l.checked_add(r),
+            BoundBinaryOp::Sub => l.checked_sub(r),
+            BoundBinaryOp::Mul => l.checked_mul(r),
+            BoundBinaryOp::Div => l.checked_div(r),
+            BoundBinaryOp::Rem => {
+                // `MIN % -1` overflows in the type of the operands, as `MIN / -1` does.
+                integer_of_type(l.checked_div(r)?, ty)?;
+                l.checked_rem(r)
+            }
+            _ => unreachable!(),
+        }?;
+        integer_of_type(result, ty)
+    } else if is_float(ty) {
+        let (l, r) = (lhs.to_f64().ok()?, rhs.to_f64().ok()?);
+        let result = match op {
+            BoundBinaryOp::Add => l + r,
+            BoundBinaryOp::Sub => l - r,
+            BoundBinaryOp::Mul => l * r,
+            BoundBinaryOp::Div => l / r,
+            BoundBinaryOp::Rem => l % r,
+            _ => unreachable!(),
+        };
+        // Rounding the exact `f64` result of an `f32` operation yields the `f32` result.
+        match ty {
+            LogicalType::Float32 => Some(F32::from(result as f32).into()),
+            _ => Some(F64::from(result).into()),
+        }
+    } else {
+        None
+    }
+}
+
+/// Compares two non-null values of type `ty`, as the executor would.
+pub fn compare(lhs: &ScalarValue, rhs: &ScalarValue, ty: &LogicalType) -> Option<Ordering> {
+    match (lhs, rhs) {
+        _ if is_integer(ty) => Some(lhs.to_i64().ok()?.cmp(&rhs.to_i64().ok()?)),
+        _ if is_float(ty) => lhs.to_f64().ok()?.partial_cmp(&rhs.to_f64().ok()?),
+        (ScalarValue::Boolean(Some(l)), ScalarValue::Boolean(Some(r))) => Some(l.cmp(r)),
+        (ScalarValue::String(Some(l)), ScalarValue::String(Some(r))) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+fn negate(value: &ScalarValue) -> Option<ScalarValue> {
+    match value {
+        ScalarValue::Int8(Some(v)) => v.checked_neg().map(Into::into),
+        ScalarValue::Int16(Some(v)) => v.checked_neg().map(Into::into),
+        ScalarValue::Int32(Some(v)) => v.checked_neg().map(Into::into),
+        ScalarValue::Int64(Some(v)) => v.checked_neg().map(Into::into),
+        ScalarValue::Float32(Some(v)) => Some((-*v).into()),
+        ScalarValue::Float64(Some(v)) => Some((-*v).into()),
+        _ => None,
+    }
+}
+
+fn integer_of_type(value: i64, ty: &LogicalType) -> Option<ScalarValue> {
+    let value = ScalarValue::Int64(Some(value));
+    let converted = match ty {
+        LogicalType::Int8 => value.to_i8().ok()?.into(),
+        LogicalType::Int16 => value.to_i16().ok()?.into(),
+        LogicalType::Int32 => value.to_i32().ok()?.into(),
+        LogicalType::Int64 => value,
+        LogicalType::UInt8 => value.to_u8().ok()?.into(),
+        LogicalType::UInt16 => value.to_u16().ok()?.into(),
+        LogicalType::UInt32 => value.to_u32().ok()?.into(),
+        LogicalType::UInt64 => value.to_u64().ok()?.into(),
+        _ => return None,
+    };
+    Some(converted)
+}
+
+fn is_null(value: &ScalarValue) -> bool {
+    match value {
+        ScalarValue::Null => true,
+        ScalarValue::Boolean(v) => v.is_none(),
+        ScalarValue::Int8(v) => v.is_none(),
+        ScalarValue::Int16(v) => v.is_none(),
+        ScalarValue::Int32(v) => v.is_none(),
+        ScalarValue::Int64(v) => v.is_none(),
+        ScalarValue::UInt8(v) => v.is_none(),
+        ScalarValue::UInt16(v) => v.is_none(),
+        ScalarValue::UInt32(v) => v.is_none(),
+        ScalarValue::UInt64(v) => v.is_none(),
+        ScalarValue::Float32(v) => v.is_none(),
+        ScalarValue::Float64(v) => v.is_none(),
+        ScalarValue::String(v) => v.is_none(),
+        ScalarValue::Vector { value, .. } => value.is_none(),
+        ScalarValue::Vertex(v) => v.is_none(),
+        ScalarValue::Edge(v) => v.is_none(),
+        ScalarValue::List(v) => v.is_none(),
+        ScalarValue::Record(v) => v.is_none(),
+        ScalarValue::Path(v) => v.is_none(),
+    }
+}
+
+#[inline]
+fn is_integer(ty: &LogicalType) -> bool {
+    matches!(
+        ty,
+        LogicalType::Int8
+            | LogicalType::Int16
+            | LogicalType::Int32
+            | LogicalType::Int64
+            | LogicalType::UInt8
+            | LogicalType::UInt16
+            | LogicalType::UInt32
+            | LogicalType::UInt64
+    )
+}
+
+#[inline]
+fn is_float(ty: &LogicalType) -> bool {
+    matches!(ty, LogicalType::Float32 | LogicalType::Float64)
+}
@@ -1,43 +1,74 @@
+mod cardinality;
+mod column_pruning;
+mod constant_folding;
+mod ordering;
+mod predicate_pushdown;
+mod scan_pruning;
+
 use std::sync::Arc;
 
 use itertools::Itertools;
+use minigu_catalog::provider::{GraphProvider, GraphRef};
+use minigu_common::data_type::{DataField, DataSchema};
 use minigu_common::error::not_implemented;
 use minigu_common::types::LabelId;
 
-use crate::bound::{BoundElementPattern, BoundGraphPattern, BoundLabelExpr, BoundPathPatternExpr};
+use self::cardinality::estimate_scan_rows;
+use self::column_pruning::prune_columns;
+use self::constant_folding::{constant_predicate, fold_constants};
+use self::ordering::is_sorted_on;
+use self::predicate_pushdown::push_down_filter;
+use self::scan_pruning::prune_scan;
+use crate::bound::{
+    BoundAggregate, BoundBinaryOp, BoundElementPattern, BoundExpr, BoundExprKind,
+    BoundGraphPattern, BoundInsertVertex, BoundLabelExpr, BoundPathPatternExpr, BoundSetItem,
+    BoundSortSpec, BoundVertexPattern,
+};
 use crate::error::PlanResult;
+use crate::plan::aggregate::Aggregate;
+use crate::plan::delete::Delete;
+use crate::plan::distinct::Distinct;
+use crate::plan::empty::Empty;
 use crate::plan::filter::Filter;
+use crate::plan::insert::Insert;
+use crate::plan::join::Join;
 use crate::plan::limit::Limit;
 use crate::plan::project::Project;
-use crate::plan::scan::PhysicalNodeScan;
+use crate::plan::scan::{PhysicalNodeScan, PhysicalPropertyIndexScan};
+use crate::plan::set_properties::SetProperties;
 use crate::plan::sort::Sort;
+use crate::plan::union::Union;
 use crate::plan::{PlanData, PlanNode};
 
 #[derive(Debug, Default)]
-pub struct Optimizer {}
+pub struct Optimizer {
+    graph: Option<GraphRef>,
+}
 
 impl Optimizer {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Uses the statistics of `graph` to remove scans that cannot produce any row.
+    pub fn with_graph(mut self, graph: GraphRef) -> Self {
+        self.graph = Some(graph);
+        self
     }
 
     pub fn create_physical_plan(self, logical_plan: &PlanNode) -> PlanResult<PlanNode> {
-        create_physical_plan_impl(logical_plan)
+        let plan = create_physical_plan_impl(logical_plan, self.graph.as_deref())?;
+        Ok(prune_columns(plan))
     }
 }
 
 fn extract_single_vertex_from_graph_pattern(
     g: &BoundGraphPattern,
-) -> PlanResult<(String, Vec<Vec<LabelId>>, i64)> {
-    if g.predicate.is_some() {
-        return not_implemented("MATCH with predicate (WHERE) is not supported yet", Some(1));
-    }
+) -> PlanResult<&BoundVertexPattern> {
     if g.paths.len() != 1 {
         return not_implemented("multiple paths in MATCH are not supported yet", Some(1));
     }
-    let graph_id = 1;
-
-    extract_single_vertex_from_path(&g.paths[0].expr, graph_id)
+    extract_single_vertex_from_path(&g.paths[0].expr)
 }
 
 fn lower_label_expr_to_specs(expr: &BoundLabelExpr) -> PlanResult<Vec<Vec<LabelId>>> {
@@ -75,30 +106,20 @@ fn lower_label_expr_to_specs(expr: &BoundLabelExpr) -> PlanResult<Vec<Vec<LabelI
     }
 }
 
-fn extract_single_vertex_from_path(
-    expr: &BoundPathPatternExpr,
-    graph_id: i64,
-) -> PlanResult<(String, Vec<Vec<LabelId>>, i64)> {
+fn extract_single_vertex_from_path(expr: &BoundPathPatternExpr) -> PlanResult<&BoundVertexPattern> {
     use BoundPathPatternExpr::*;
     match expr {
-        Pattern(BoundElementPattern::Vertex(v)) => {
-            let var = v.var.clone();
-            let label_specs: Vec<Vec<LabelId>> = match &v.label {
-                None => vec![vec![]],
-                Some(lbl) => lower_label_expr_to_specs(lbl)?,
-            };
-            Ok((var, label_specs, graph_id))
-        }
+        Pattern(BoundElementPattern::Vertex(v)) => Ok(v),
         Concat(parts) => match parts.len() {
             0 => not_implemented("empty concat in path pattern", None),
-            1 => extract_single_vertex_from_path(&parts[0], graph_id),
+            1 => extract_single_vertex_from_path(&parts[0]),
             _ => not_implemented(
                 "concat with edges/nodes (length > 1) is not supported yet",
                 None,
             ),
         },
 
-        Subpath(sp) => extract_single_vertex_from_path(&sp.expr, graph_id),
+        Subpath(sp) => extract_single_vertex_from_path(&sp.expr),
         Alternation(_) => not_implemented(
             "alternation (A|B) in path pattern is not supported yet",
             None,
@@ -115,32 +136,80 @@ fn extract_single_vertex_from_path(
     }
 }
 
-fn create_physical_plan_impl(logical_plan: &PlanNode) -> PlanResult<PlanNode> {
+fn create_physical_plan_impl(
+    logical_plan: &PlanNode,
+    graph: Option<&dyn GraphProvider>,
+) -> PlanResult<PlanNode> {
     let children: Vec<_> = logical_plan
         .children()
         .iter()
-        .map(create_physical_plan_impl)
+        .map(|child| create_physical_plan_impl(child, graph))
         .try_collect()?;
+    let prune = |plan| match graph {
+        Some(graph) => prune_scan(plan, graph),
+        None => plan,
+    };
     match logical_plan {
         PlanNode::LogicalMatch(m) => {
             assert!(children.is_empty());
-            let (var, labels, graph_id) = extract_single_vertex_from_graph_pattern(&m.pattern)?;
-            let node = PhysicalNodeScan::new(var.as_str(), labels, graph_id);
-            Ok(PlanNode::PhysicalNodeScan(Arc::new(node)))
+            let vertex = extract_single_vertex_from_graph_pattern(&m.pattern)?;
+            let scan = match &vertex.index_lookup {
+                Some(lookup) => {
+                    let scan = PhysicalPropertyIndexScan::new(&vertex.var, lookup.clone());
+                    PlanNode::PhysicalPropertyIndexScan(Arc::new(scan))
+                }
+                None => {
+                    let labels = match &vertex.label {
+                        None => vec![vec![]],
+                        Some(label) => lower_label_expr_to_specs(label)?,
+                    };
+                    let graph_id = 1;
+                    let mut node = PhysicalNodeScan::new(&vertex.var, labels, graph_id);
+                    if let Some(rows) =
+                        graph.and_then(|graph| estimate_scan_rows(&node.labels, graph))
+                    {
+                        node = node.with_estimated_rows(rows);
+                    }
+                    PlanNode::PhysicalNodeScan(Arc::new(node))
+                }
+            };
+            // The predicate of the vertex pattern and the WHERE clause of the graph pattern filter
+            // the matched vertices.
+            let predicate = [&vertex.predicate, &m.pattern.predicate]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .reduce(|lhs, rhs| BoundExpr::binary(BoundBinaryOp::And, lhs, rhs));
+            match predicate {
+                Some(predicate) => {
+                    let predicate = fold_constants(predicate);
+                    let filter = Filter::new(scan, predicate);
+                    Ok(prune(PlanNode::PhysicalFilter(Arc::new(filter))))
+                }
+                None => Ok(scan),
+            }
         }
         PlanNode::LogicalFilter(filter) => {
             let [child] = children
                 .try_into()
                 .expect("filter should have exactly one child");
-            let predicate = filter.predicate.clone();
-            let filter = Filter::new(child, predicate);
-            Ok(PlanNode::PhysicalFilter(Arc::new(filter)))
+            let predicate = fold_constants(filter.predicate.clone());
+            match constant_predicate(&predicate) {
+                // An always-true filter is a no-op.
+                Some(Some(true)) => Ok(child),
+                // Neither `FALSE` nor `NULL` lets any row through.
+                Some(_) => {
+                    let empty = Empty::new(child.schema().cloned());
+                    Ok(PlanNode::PhysicalEmpty(Arc::new(empty)))
+                }
+                None => Ok(prune(push_down_filter(child, predicate))),
+            }
         }
         PlanNode::LogicalProject(project) => {
             let [child] = children
                 .try_into()
                 .expect("project should have exactly one child");
-            let exprs = project.exprs.clone();
+            let exprs = project.exprs.iter().cloned().map(fold_constants).collect();
             let schema = project.schema().expect("project should have a schema");
             let project = Project::new(child, exprs, schema.clone());
             Ok(PlanNode::PhysicalProject(Arc::new(project)))
@@ -154,7 +223,14 @@ fn create_physical_plan_impl(logical_plan: &PlanNode) -> PlanResult<PlanNode> {
             let [child] = children
                 .try_into()
                 .expect("sort should have exactly one child");
-            let specs = sort.specs.clone();
+            let specs = sort
+                .specs
+                .iter()
+                .map(|spec| BoundSortSpec {
+                    key: fold_constants(spec.key.clone()),
+                    ..spec.clone()
+                })
+                .collect();
             let sort = Sort::new(child, specs);
             Ok(PlanNode::PhysicalSort(Arc::new(sort)))
         }
@@ -165,6 +241,113 @@ fn create_physical_plan_impl(logical_plan: &PlanNode) -> PlanResult<PlanNode> {
             let limit = Limit::new(child, limit.limit, limit.approximate);
             Ok(PlanNode::PhysicalLimit(Arc::new(limit)))
         }
+        PlanNode::LogicalDistinct(_) => {
+            let [child] = children
+                .try_into()
+                .expect("distinct should have exactly one child");
+            Ok(PlanNode::PhysicalDistinct(Arc::new(Distinct::new(child))))
+        }
+        PlanNode::LogicalSetProperties(set) => {
+            let [child] = children
+                .try_into()
+                .expect("set properties should have exactly one child");
+            let items = set
+                .items
+                .iter()
+                .map(|item| BoundSetItem {
+                    value: fold_constants(item.value.clone()),
+                    ..item.clone()
+                })
+                .collect();
+            let set = SetProperties::new(child, items);
+            Ok(PlanNode::PhysicalSetProperties(Arc::new(set)))
+        }
+        PlanNode::LogicalInsert(insert) => {
+            let [child] = children
+                .try_into()
+                .expect("insert should have exactly one child");
+            let vertices = insert
+                .vertices
+                .iter()
+                .map(|vertex| BoundInsertVertex {
+                    properties: vertex
+                        .properties
+                        .iter()
+                        .cloned()
+                        .map(fold_constants)
+                        .collect(),
+                    ..vertex.clone()
+                })
+                .collect();
+            let insert = Insert::new(child, vertices);
+            Ok(PlanNode::PhysicalInsert(Arc::new(insert)))
+        }
+        PlanNode::LogicalDelete(delete) => {
+            let [child] = children
+                .try_into()
+                .expect("delete should have exactly one child");
+            let delete = Delete::new(child, delete.detach, delete.vars.clone());
+            Ok(PlanNode::PhysicalDelete(Arc::new(delete)))
+        }
+        PlanNode::LogicalAggregate(aggregate) => {
+            let [child] = children
+                .try_into()
+                .expect("aggregate should have exactly one child");
+            let child_schema = child.schema().expect("child should have a schema");
+            // Variables are grouped by the values of the columns they are bound to, e.g., by the
+            // ids of the vertices bound by scans, which are only materialized by the
+            // operators above.
+            let group_by: Vec<_> = aggregate
+                .group_by
+                .iter()
+                .map(|key| match &key.kind {
+                    BoundExprKind::Variable(name) => {
+                        let field = child_schema
+                            .get_field_by_name(name)
+                            .expect("variable should be present in the schema");
+                        BoundExpr::variable(name.clone(), field.ty().clone(), field.is_nullable())
+                    }
+                    _ => fold_constants(key.clone()),
+                })
+                .collect();
+            let aggregates: Vec<_> = aggregate
+                .aggregates
+                .iter()
+                .map(|aggregate| BoundAggregate {
+                    arg: aggregate.arg.clone().map(fold_constants),
+                    ..aggregate.clone()
+                })
+                .collect();
+            let schema = aggregate.schema().expect("aggregate should have a schema");
+            let fields = group_by
+                .iter()
+                .map(|key| &key.logical_type)
+                .chain(aggregates.iter().map(|aggregate| &aggregate.logical_type))
+                .zip(schema.fields())
+                .map(|(ty, f)| DataField::new(f.name().to_string(), ty.clone(), f.is_nullable()))
+                .collect();
+            let schema = Arc::new(DataSchema::new(fields));
+            // Input sorted on the keys is aggregated a group at a time, instead of into a hash
+            // table of all the groups.
+            let sorted = is_sorted_on(&child, &group_by);
+            let aggregate = Arc::new(Aggregate::new(child, group_by, aggregates, schema));
+            if sorted {
+                Ok(PlanNode::PhysicalSortedAggregate(aggregate))
+            } else {
+                Ok(PlanNode::PhysicalAggregate(aggregate))
+            }
+        }
+        PlanNode::LogicalUnion(union) => {
+            let schema = union.schema().expect("union should have a schema");
+            let union = Union::new(children, schema.clone());
+            Ok(PlanNode::PhysicalUnion(Arc::new(union)))
+        }
+        PlanNode::LogicalJoin(_) => {
+            let [left, right] = children
+                .try_into()
+                .expect("join should have exactly two children");
+            Ok(PlanNode::PhysicalJoin(Arc::new(Join::new(left, right))))
+        }
         PlanNode::LogicalVectorIndexScan(vector_scan) => {
             assert!(children.is_empty());
             Ok(PlanNode::PhysicalVectorIndexScan(vector_scan.clone()))
@@ -172,3 +355,499 @@ fn create_physical_plan_impl(logical_plan: &PlanNode) -> PlanResult<PlanNode> {
         _ => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use minigu_catalog::memory::graph_type::MemoryGraphTypeCatalog;
+    use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+    use minigu_common::ordering::{NullOrdering, SortOrdering};
+    use minigu_common::types::PropertyIndexKey;
+    use minigu_common::value::ScalarValue;
+
+    use super::*;
+    use crate::bound::{
+        BoundAggregateFunction, BoundBinaryOp, BoundExpr, BoundExprKind, BoundIndexLookup,
+        BoundPathPattern, BoundUnaryOp, BoundVertexPattern,
+    };
+    use crate::plan::logical_match::{LogicalMatch, MatchKind};
+    use crate::plan::one_row::OneRow;
+
+    fn one_row() -> PlanNode {
+        PlanNode::LogicalOneRow(Arc::new(OneRow::new()))
+    }
+
+    fn int8(value: i8) -> BoundExpr {
+        BoundExpr::value(value.into(), LogicalType::Int8, false)
+    }
+
+    fn filter(predicate: BoundExpr) -> PlanNode {
+        PlanNode::LogicalFilter(Arc::new(Filter::new(one_row(), predicate)))
+    }
+
+    #[test]
+    fn test_fold_always_false_filter() {
+        let plan = filter(BoundExpr::binary(BoundBinaryOp::Eq, int8(1), int8(0)));
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        assert!(matches!(plan, PlanNode::PhysicalEmpty(_)));
+        assert_eq!(plan.schema(), one_row().schema());
+    }
+
+    #[test]
+    fn test_fold_always_true_filter() {
+        let plan = filter(BoundExpr::binary(BoundBinaryOp::Eq, int8(1), int8(1)));
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        assert!(matches!(plan, PlanNode::PhysicalOneRow(_)));
+    }
+
+    #[test]
+    fn test_fold_project() {
+        let expr = BoundExpr::binary(BoundBinaryOp::Add, int8(2), int8(3));
+        let schema = DataSchema::new(vec![DataField::new(
+            expr.to_string(),
+            LogicalType::Int8,
+            false,
+        )]);
+        let project = Project::new(one_row(), vec![expr], Arc::new(schema));
+        let plan = PlanNode::LogicalProject(Arc::new(project));
+        let PlanNode::PhysicalProject(project) =
+            Optimizer::new().create_physical_plan(&plan).unwrap()
+        else {
+            panic!("expected a physical project");
+        };
+        assert!(matches!(
+            project.exprs[0].kind,
+            BoundExprKind::Value(ScalarValue::Int8(Some(5)))
+        ));
+    }
+
+    fn project_one_row(expr: BoundExpr, name: &str) -> PlanNode {
+        let schema = DataSchema::new(vec![DataField::new(
+            name.into(),
+            expr.logical_type.clone(),
+            expr.nullable,
+        )]);
+        let project = Project::new(one_row(), vec![expr], Arc::new(schema));
+        PlanNode::LogicalProject(Arc::new(project))
+    }
+
+    fn positive(name: &str) -> BoundExpr {
+        let var = BoundExpr::variable(name.into(), LogicalType::Int32, false);
+        let zero = BoundExpr::value(0i32.into(), LogicalType::Int32, false);
+        BoundExpr::binary(BoundBinaryOp::Gt, var, zero)
+    }
+
+    #[test]
+    fn test_push_filter_below_project() {
+        let forwarded = BoundExpr::variable("one_row".into(), LogicalType::Int32, false);
+        let project = project_one_row(forwarded, "x");
+        let plan = PlanNode::LogicalFilter(Arc::new(Filter::new(project, positive("x"))));
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        let PlanNode::PhysicalProject(project) = &plan else {
+            panic!("expected a physical project");
+        };
+        let PlanNode::PhysicalFilter(filter) = &project.children()[0] else {
+            panic!("expected the filter below the project");
+        };
+        assert_eq!(filter.predicate.to_string(), "(one_row > Int32(Some(0)))");
+        assert!(matches!(filter.children()[0], PlanNode::PhysicalOneRow(_)));
+    }
+
+    #[test]
+    fn test_fuse_stacked_filters() {
+        let var = BoundExpr::variable("one_row".into(), LogicalType::Int32, false);
+        let ten = BoundExpr::value(10i32.into(), LogicalType::Int32, false);
+        let below_ten = BoundExpr::binary(BoundBinaryOp::Lt, var, ten);
+        let plan = PlanNode::LogicalFilter(Arc::new(Filter::new(
+            filter(positive("one_row")),
+            below_ten,
+        )));
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        let PlanNode::PhysicalFilter(filter) = &plan else {
+            panic!("expected a physical filter");
+        };
+        assert_eq!(
+            filter.predicate.to_string(),
+            "((one_row > Int32(Some(0))) AND (one_row < Int32(Some(10))))"
+        );
+        assert!(matches!(filter.children()[0], PlanNode::PhysicalOneRow(_)));
+    }
+
+    #[test]
+    fn test_keep_filter_on_computed_column() {
+        let one = BoundExpr::value(1i32.into(), LogicalType::Int32, false);
+        let var = BoundExpr::variable("one_row".into(), LogicalType::Int32, false);
+        let computed = BoundExpr::binary(BoundBinaryOp::Add, var, one);
+        let project = project_one_row(computed, "y");
+        let plan = PlanNode::LogicalFilter(Arc::new(Filter::new(project, positive("y"))));
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        let PlanNode::PhysicalFilter(filter) = &plan else {
+            panic!("expected a physical filter");
+        };
+        assert!(matches!(filter.children()[0], PlanNode::PhysicalProject(_)));
+    }
+
+    #[test]
+    fn test_prune_unused_columns() {
+        let var = || BoundExpr::variable("one_row".into(), LogicalType::Int32, false);
+        let one = BoundExpr::value(1i32.into(), LogicalType::Int32, false);
+        let exprs = vec![var(), BoundExpr::binary(BoundBinaryOp::Add, var(), one)];
+        let schema = DataSchema::new(vec![
+            DataField::new("x".into(), LogicalType::Int32, false),
+            DataField::new("y".into(), LogicalType::Int32, false),
+        ]);
+        let inner = Project::new(one_row(), exprs, Arc::new(schema));
+        let x = BoundExpr::variable("x".into(), LogicalType::Int32, false);
+        let spec = BoundSortSpec {
+            key: x.clone(),
+            ordering: SortOrdering::Ascending,
+            null_ordering: NullOrdering::Last,
+        };
+        let sort = Sort::new(PlanNode::LogicalProject(Arc::new(inner)), vec![spec]);
+        let schema = DataSchema::new(vec![DataField::new("x".into(), LogicalType::Int32, false)]);
+        let outer = Project::new(
+            PlanNode::LogicalSort(Arc::new(sort)),
+            vec![x],
+            Arc::new(schema),
+        );
+        let plan = PlanNode::LogicalProject(Arc::new(outer));
+
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        let PlanNode::PhysicalSort(sort) = &plan.children()[0] else {
+            panic!("expected a physical sort");
+        };
+        let PlanNode::PhysicalProject(inner) = &sort.children()[0] else {
+            panic!("expected a physical project");
+        };
+        // `y` is never used, so it is not computed.
+        assert_eq!(inner.exprs.len(), 1);
+        let names = inner
+            .schema()
+            .unwrap()
+            .fields()
+            .iter()
+            .map(|f| f.name())
+            .collect_vec();
+        assert_eq!(names, ["x"]);
+    }
+
+    /// Plans the number of rows per group of `group_by` of a row of `x` and `y`, sorted on
+    /// `sort_keys`.
+    fn count_sorted_rows(sort_keys: &[&str], group_by: &[&str]) -> PlanNode {
+        let var = |name: &str| BoundExpr::variable(name.into(), LogicalType::Int32, false);
+        let one = BoundExpr::value(1i32.into(), LogicalType::Int32, false);
+        let exprs = vec![
+            var("one_row"),
+            BoundExpr::binary(BoundBinaryOp::Add, var("one_row"), one),
+        ];
+        let schema = DataSchema::new(vec![
+            DataField::new("x".into(), LogicalType::Int32, false),
+            DataField::new("y".into(), LogicalType::Int32, false),
+        ]);
+        let project = Project::new(one_row(), exprs, Arc::new(schema));
+        let specs = sort_keys
+            .iter()
+            .map(|key| BoundSortSpec {
+                key: var(key),
+                ordering: SortOrdering::Ascending,
+                null_ordering: NullOrdering::Last,
+            })
+            .collect();
+        let sort = Sort::new(PlanNode::LogicalProject(Arc::new(project)), specs);
+        let count = BoundAggregate {
+            function: BoundAggregateFunction::Count,
+            arg: None,
+            distinct: false,
+            logical_type: LogicalType::Int64,
+            nullable: false,
+        };
+        let mut fields: Vec<_> = group_by
+            .iter()
+            .map(|key| DataField::new(key.to_string(), LogicalType::Int32, false))
+            .collect();
+        fields.push(DataField::new("count".into(), LogicalType::Int64, false));
+        let aggregate = Aggregate::new(
+            PlanNode::LogicalSort(Arc::new(sort)),
+            group_by.iter().map(|key| var(key)).collect(),
+            vec![count],
+            Arc::new(DataSchema::new(fields)),
+        );
+        let plan = PlanNode::LogicalAggregate(Arc::new(aggregate));
+        Optimizer::new().create_physical_plan(&plan).unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_sorted_input() {
+        // The keys of the sort cover those of the aggregate, in any order.
+        for (sort_keys, group_by) in [
+            (&["x"][..], &["x"][..]),
+            (&["x", "y"][..], &["x"][..]),
+            (&["y", "x"][..], &["x", "y"][..]),
+        ] {
+            let plan = count_sorted_rows(sort_keys, group_by);
+            assert!(
+                matches!(plan, PlanNode::PhysicalSortedAggregate(_)),
+                "{sort_keys:?}, {group_by:?}"
+            );
+        }
+        // Rows of a group are not adjacent if they are only sorted on a less significant key.
+        let unsorted = [(&["y", "x"][..], &["x"][..]), (&["x"][..], &["x", "y"][..])];
+        for (sort_keys, group_by) in unsorted {
+            let plan = count_sorted_rows(sort_keys, group_by);
+            assert!(
+                matches!(plan, PlanNode::PhysicalAggregate(_)),
+                "{sort_keys:?}, {group_by:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_match_with_where_clause() {
+        let vertex = BoundVertexPattern {
+            var: "n".into(),
+            label: None,
+            predicate: None,
+            index_lookup: None,
+        };
+        let path = BoundPathPattern {
+            mode: None,
+            expr: BoundPathPatternExpr::Pattern(BoundElementPattern::Vertex(Arc::new(vertex))),
+        };
+        let n = BoundExpr::variable("n".into(), LogicalType::Int64, false);
+        let pattern = BoundGraphPattern {
+            match_mode: None,
+            paths: vec![Arc::new(path)],
+            predicate: Some(BoundExpr::unary(BoundUnaryOp::IsNotNull, n)),
+        };
+        let schema = DataSchema::new(vec![DataField::new("n".into(), LogicalType::Int64, false)]);
+        let m = LogicalMatch::new(MatchKind::Simple, pattern, vec![], schema);
+        let plan = PlanNode::LogicalMatch(Arc::new(m));
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        let PlanNode::PhysicalFilter(filter) = &plan else {
+            panic!("expected a physical filter");
+        };
+        assert_eq!(filter.predicate.to_string(), "n IS NOT NULL");
+        assert!(matches!(
+            filter.children()[0],
+            PlanNode::PhysicalNodeScan(_)
+        ));
+    }
+
+    #[test]
+    fn test_match_with_index_lookup() {
+        let person = LabelId::new(1).unwrap();
+        let name = BoundExpr::property("n".into(), 0, "name".into(), LogicalType::String, false);
+        let alice = BoundExpr::value("alice".into(), LogicalType::String, false);
+        let vertex = BoundVertexPattern {
+            var: "n".into(),
+            label: Some(BoundLabelExpr::Label(person)),
+            predicate: Some(BoundExpr::binary(BoundBinaryOp::Eq, name, alice)),
+            index_lookup: Some(BoundIndexLookup {
+                name: "person_name".into(),
+                key: PropertyIndexKey::new(person, 0),
+                value: "alice".into(),
+            }),
+        };
+        let path = BoundPathPattern {
+            mode: None,
+            expr: BoundPathPatternExpr::Pattern(BoundElementPattern::Vertex(Arc::new(vertex))),
+        };
+        let pattern = BoundGraphPattern {
+            match_mode: None,
+            paths: vec![Arc::new(path)],
+            predicate: None,
+        };
+        let schema = DataSchema::new(vec![DataField::new("n".into(), LogicalType::Int64, false)]);
+        let m = LogicalMatch::new(MatchKind::Simple, pattern, vec![], schema);
+        let plan = PlanNode::LogicalMatch(Arc::new(m));
+        let plan = Optimizer::new()
+            .with_graph(Arc::new(MockGraph))
+            .create_physical_plan(&plan)
+            .unwrap();
+        // The predicate of the vertex pattern is still checked on the looked up vertices.
+        assert_eq!(
+            plan.explain(),
+            "PhysicalFilter: (n.name = String(Some(\"alice\")))\n  \
+             PhysicalPropertyIndexScan: n, index: person_name = String(Some(\"alice\"))\n"
+        );
+    }
+
+    #[test]
+    fn test_scan_only_used_properties() {
+        let vertex = BoundVertexPattern {
+            var: "n".into(),
+            label: Some(BoundLabelExpr::Label(LabelId::new(1).unwrap())),
+            predicate: None,
+            index_lookup: None,
+        };
+        let path = BoundPathPattern {
+            mode: None,
+            expr: BoundPathPatternExpr::Pattern(BoundElementPattern::Vertex(Arc::new(vertex))),
+        };
+        let pattern = BoundGraphPattern {
+            match_mode: None,
+            paths: vec![Arc::new(path)],
+            predicate: None,
+        };
+        let schema = DataSchema::new(vec![DataField::new("n".into(), LogicalType::Int64, false)]);
+        let m = LogicalMatch::new(MatchKind::Simple, pattern, vec![], schema);
+        let name = BoundExpr::property("n".into(), 0, "name".into(), LogicalType::String, false);
+        let age = BoundExpr::property("n".into(), 1, "age".into(), LogicalType::Int32, true);
+        let schema = DataSchema::new(vec![
+            DataField::new("name".into(), LogicalType::String, false),
+            DataField::new("age".into(), LogicalType::Int32, true),
+        ]);
+        let inner = Project::new(
+            PlanNode::LogicalMatch(Arc::new(m)),
+            vec![name, age],
+            Arc::new(schema),
+        );
+        let name = BoundExpr::variable("name".into(), LogicalType::String, false);
+        let schema = DataSchema::new(vec![DataField::new(
+            "name".into(),
+            LogicalType::String,
+            false,
+        )]);
+        let outer = Project::new(
+            PlanNode::LogicalProject(Arc::new(inner)),
+            vec![name],
+            Arc::new(schema),
+        );
+        let plan = PlanNode::LogicalProject(Arc::new(outer));
+
+        let plan = Optimizer::new().create_physical_plan(&plan).unwrap();
+        // `age` is never used, so only `name` is scanned.
+        assert_eq!(
+            plan.explain(),
+            "PhysicalProject: name\n  \
+             PhysicalProject: n.name AS name\n    \
+             PhysicalVertexPropertyScan: n, properties: [n.name]\n      \
+             PhysicalNodeScan: n, labels: [[1]]\n"
+        );
+    }
+
+    /// A graph of 100 vertices, 40 of which are `Person` vertices (label 1) with ages (property
+    /// 1) between 18 and 65.
+    #[derive(Debug)]
+    struct MockGraph;
+
+    impl GraphProvider for MockGraph {
+        fn graph_type(&self) -> minigu_catalog::provider::GraphTypeRef {
+            Arc::new(MemoryGraphTypeCatalog::new())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn vertex_property_range(
+            &self,
+            label_id: LabelId,
+            property_id: u32,
+        ) -> Option<(ScalarValue, ScalarValue)> {
+            (label_id == LabelId::new(1).unwrap() && property_id == 1)
+                .then_some((18i32.into(), 65i32.into()))
+        }
+
+        fn estimated_vertex_count(&self, label_id: Option<LabelId>) -> Option<u64> {
+            match label_id {
+                None => Some(100),
+                Some(label_id) => Some(if label_id == LabelId::new(1).unwrap() {
+                    40
+                } else {
+                    0
+                }),
+            }
+        }
+    }
+
+    /// Plans `MATCH (n:Person) WHERE n.age <op> <age>`.
+    fn match_person_by_age(op: BoundBinaryOp, age: i32) -> PlanNode {
+        let vertex = BoundVertexPattern {
+            var: "n".into(),
+            label: Some(BoundLabelExpr::Label(LabelId::new(1).unwrap())),
+            predicate: None,
+            index_lookup: None,
+        };
+        let path = BoundPathPattern {
+            mode: None,
+            expr: BoundPathPatternExpr::Pattern(BoundElementPattern::Vertex(Arc::new(vertex))),
+        };
+        let property = BoundExpr::property("n".into(), 1, "age".into(), LogicalType::Int32, true);
+        let age = BoundExpr::value(age.into(), LogicalType::Int32, false);
+        let pattern = BoundGraphPattern {
+            match_mode: None,
+            paths: vec![Arc::new(path)],
+            predicate: Some(BoundExpr::binary(op, property, age)),
+        };
+        let schema = DataSchema::new(vec![DataField::new("n".into(), LogicalType::Int64, false)]);
+        let m = LogicalMatch::new(MatchKind::Simple, pattern, vec![], schema);
+        let plan = PlanNode::LogicalMatch(Arc::new(m));
+        Optimizer::new()
+            .with_graph(Arc::new(MockGraph))
+            .create_physical_plan(&plan)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prune_scan_outside_of_range() {
+        for (op, age) in [
+            (BoundBinaryOp::Gt, 100),
+            (BoundBinaryOp::Gt, 65),
+            (BoundBinaryOp::Lt, 18),
+            (BoundBinaryOp::Eq, 70),
+        ] {
+            let plan = match_person_by_age(op, age);
+            // The scan is gone, so no vertex is read.
+            assert!(matches!(plan, PlanNode::PhysicalEmpty(_)));
+            assert!(plan.children().is_empty());
+            assert_eq!(
+                plan.schema().unwrap().fields()[0].name(),
+                "n",
+                "the schema should be kept"
+            );
+        }
+    }
+
+    #[test]
+    fn test_keep_scan_within_range() {
+        for (op, age) in [
+            (BoundBinaryOp::Ge, 65),
+            (BoundBinaryOp::Le, 18),
+            (BoundBinaryOp::Eq, 30),
+            (BoundBinaryOp::Ne, 30),
+        ] {
+            let plan = match_person_by_age(op, age);
+            let PlanNode::PhysicalFilter(filter) = &plan else {
+                panic!("expected a physical filter");
+            };
+            assert!(matches!(
+                filter.children()[0],
+                PlanNode::PhysicalNodeScan(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_estimate_scan_rows() {
+        let plan = match_person_by_age(BoundBinaryOp::Eq, 30);
+        let PlanNode::PhysicalNodeScan(scan) = &plan.children()[0] else {
+            panic!("expected a node scan");
+        };
+        assert_eq!(scan.estimated_rows, Some(40));
+
+        let person = LabelId::new(1).unwrap();
+        let company = LabelId::new(2).unwrap();
+        let estimate = |labels: Vec<Vec<LabelId>>| estimate_scan_rows(&labels, &MockGraph);
+        assert_eq!(estimate(vec![vec![]]), Some(100));
+        assert_eq!(estimate(vec![vec![person], vec![person, person]]), Some(40));
+        assert_eq!(estimate(vec![vec![person, company]]), Some(0));
+        assert_eq!(estimate(vec![vec![person], vec![company]]), Some(40));
+    }
+
+    #[test]
+    fn test_fold_keeps_overflow() {
+        let expr = BoundExpr::binary(BoundBinaryOp::Add, int8(100), int8(100));
+        let folded = fold_constants(expr);
+        assert!(matches!(folded.kind, BoundExprKind::Binary { .. }));
+    }
+}
@@ -0,0 +1,52 @@
+//! Orderings of the rows output by plans, which allow operators over sorted input to avoid
+//! holding all of it, e.g., aggregations over input sorted on their keys.
+
+use std::collections::HashSet;
+
+use crate::bound::BoundExpr;
+use crate::plan::{PlanData, PlanNode};
+
+/// Returns the expressions the rows output by `plan` are known to be sorted on, from the most
+/// significant to the least significant.
+pub fn sort_keys(plan: &PlanNode) -> Vec<BoundExpr> {
+    match plan {
+        PlanNode::PhysicalSort(sort) => sort.specs.iter().map(|s| s.key.clone()).collect(),
+        PlanNode::PhysicalFilter(_)
+        | PlanNode::PhysicalLimit(_)
+        | PlanNode::PhysicalVertexPropertyScan(_) => sort_keys(&plan.children()[0]),
+        PlanNode::PhysicalProject(project) => {
+            // The keys are kept up to the first one which is not output by the projection.
+            let schema = project.schema().expect("project should have a schema");
+            sort_keys(&project.children()[0])
+                .into_iter()
+                .map_while(|key| {
+                    project
+                        .exprs
+                        .iter()
+                        .zip(schema.fields())
+                        .find(|(e, _)| **e == key)
+                        .map(|(e, f)| {
+                            BoundExpr::variable(
+                                f.name().to_string(),
+                                e.logical_type.clone(),
+                                e.nullable,
+                            )
+                        })
+                })
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Returns whether the rows output by `plan` are sorted on `keys`, in any order, possibly followed
+/// by other keys. The rows with the same values of `keys` are then adjacent.
+pub fn is_sorted_on(plan: &PlanNode, keys: &[BoundExpr]) -> bool {
+    let keys: HashSet<_> = keys.iter().collect();
+    let sort_keys = sort_keys(plan);
+    if keys.is_empty() || sort_keys.len() < keys.len() {
+        return false;
+    }
+    let leading: HashSet<_> = sort_keys[..keys.len()].iter().collect();
+    leading == keys
+}
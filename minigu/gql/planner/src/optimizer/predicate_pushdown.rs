@@ -0,0 +1,130 @@
+//! Pushing filters down towards the scans, fusing them along the way.
+
+use std::sync::Arc;
+
+use crate::bound::{BoundBinaryOp, BoundExpr, BoundExprKind};
+use crate::plan::filter::Filter;
+use crate::plan::project::Project;
+use crate::plan::{PlanData, PlanNode};
+
+/// Creates a physical filter with `predicate` over `child`.
+///
+/// If `child` is a projection that merely forwards every column referenced by `predicate`, the
+/// filter is placed below it instead, repeatedly, so that fewer rows reach the projection. If
+/// `child` is itself a filter, the two are fused into a single filter on the conjunction of their
+/// predicates, so that rows are only evaluated in one pass. The predicate of `child` comes first,
+/// since the evaluation of a conjunction skips its right operand on the rows its left one rejects.
+pub fn push_down_filter(child: PlanNode, predicate: BoundExpr) -> PlanNode {
+    match &child {
+        PlanNode::PhysicalFilter(filter) => {
+            let input = filter.children()[0].clone();
+            let predicate =
+                BoundExpr::binary(BoundBinaryOp::And, filter.predicate.clone(), predicate);
+            return push_down_filter(input, predicate);
+        }
+        PlanNode::PhysicalProject(project) => {
+            if let Some(predicate) = rewrite_below_project(&predicate, project) {
+                let input = project.children()[0].clone();
+                let schema = project
+                    .schema()
+                    .cloned()
+                    .expect("project should have a schema");
+                let filter = push_down_filter(input, predicate);
+                let project = Project::new(filter, project.exprs.clone(), schema);
+                return PlanNode::PhysicalProject(Arc::new(project));
+            }
+        }
+        _ => (),
+    }
+    PlanNode::PhysicalFilter(Arc::new(Filter::new(child, predicate)))
+}
+
+/// Rewrites `expr`, which refers to the output columns of `project`, in terms of the input columns
+/// of `project`.
+///
+/// Returns `None` if `expr` refers to a column computed by `project`.
+fn rewrite_below_project(expr: &BoundExpr, project: &Project) -> Option<BoundExpr> {
+    let kind = match &expr.kind {
+        BoundExprKind::Value(_) => expr.kind.clone(),
+        BoundExprKind::Variable(name) => {
+            BoundExprKind::Variable(forwarded_column(name, project)?.to_string())
+        }
+        BoundExprKind::Property {
+            var,
+            property_id,
+            name,
+        } => BoundExprKind::Property {
+            var: forwarded_column(var, project)?.to_string(),
+            property_id: *property_id,
+            name: name.clone(),
+        },
+        BoundExprKind::Unary { op, child } => BoundExprKind::Unary {
+            op: op.clone(),
+            child: Box::new(rewrite_below_project(child, project)?),
+        },
+        BoundExprKind::Binary { op, lhs, rhs } => BoundExprKind::Binary {
+            op: op.clone(),
+            lhs: Box::new(rewrite_below_project(lhs, project)?),
+            rhs: Box::new(rewrite_below_project(rhs, project)?),
+        },
+        BoundExprKind::VectorDistance {
+            lhs,
+            rhs,
+            metric,
+            dimension,
+        } => BoundExprKind::VectorDistance {
+            lhs: Box::new(rewrite_below_project(lhs, project)?),
+            rhs: Box::new(rewrite_below_project(rhs, project)?),
+            metric: *metric,
+            dimension: *dimension,
+        },
+        BoundExprKind::Function {
+            name,
+            function,
+            args,
+        } => BoundExprKind::Function {
+            name: name.clone(),
+            function: function.clone(),
+            args: args
+                .iter()
+                .map(|arg| rewrite_below_project(arg, project))
+                .collect::<Option<_>>()?,
+        },
+        BoundExprKind::Field {
+            record,
+            index,
+            name,
+        } => BoundExprKind::Field {
+            record: Box::new(rewrite_below_project(record, project)?),
+            index: *index,
+            name: name.clone(),
+        },
+        BoundExprKind::ElementFunction { function, element } => BoundExprKind::ElementFunction {
+            function: *function,
+            element: Box::new(rewrite_below_project(element, project)?),
+        },
+        BoundExprKind::PathFunction { function, path } => BoundExprKind::PathFunction {
+            function: *function,
+            path: Box::new(rewrite_below_project(path, project)?),
+        },
+        BoundExprKind::Cast { child, target } => BoundExprKind::Cast {
+            child: Box::new(rewrite_below_project(child, project)?),
+            target: target.clone(),
+        },
+    };
+    Some(BoundExpr {
+        kind,
+        logical_type: expr.logical_type.clone(),
+        nullable: expr.nullable,
+    })
+}
+
+/// Returns the name of the input column that `project` outputs as `name`, if the column is passed
+/// through unchanged.
+fn forwarded_column<'a>(name: &str, project: &'a Project) -> Option<&'a str> {
+    let index = project.schema()?.get_field_index_by_name(name)?;
+    match &project.exprs[index].kind {
+        BoundExprKind::Variable(input) => Some(input),
+        _ => None,
+    }
+}
@@ -0,0 +1,156 @@
+//! Removal of scans whose filters no vertex can satisfy, according to the min/max statistics of
+//! the vertex properties.
+
+use std::sync::Arc;
+
+use minigu_catalog::provider::GraphProvider;
+use minigu_common::data_type::LogicalType;
+use minigu_common::types::PropertyId;
+use minigu_common::value::ScalarValue;
+
+use super::constant_folding::compare;
+use crate::bound::{BoundBinaryOp, BoundExpr, BoundExprKind};
+use crate::plan::empty::Empty;
+use crate::plan::filter::Filter;
+use crate::plan::project::Project;
+use crate::plan::scan::PhysicalNodeScan;
+use crate::plan::{PlanData, PlanNode};
+
+/// Replaces a filter over a node scan with an empty plan, if a conjunct of the predicate compares
+/// a property of the scanned vertices against a value outside of the range of the property.
+///
+/// Projections between `plan` and the filter are kept.
+pub fn prune_scan(plan: PlanNode, graph: &dyn GraphProvider) -> PlanNode {
+    match &plan {
+        PlanNode::PhysicalProject(project) => {
+            let child = prune_scan(project.children()[0].clone(), graph);
+            let schema = project
+                .schema()
+                .cloned()
+                .expect("project should have a schema");
+            let project = Project::new(child, project.exprs.clone(), schema);
+            PlanNode::PhysicalProject(Arc::new(project))
+        }
+        PlanNode::PhysicalFilter(filter) => {
+            let PlanNode::PhysicalNodeScan(scan) = &filter.children()[0] else {
+                return plan;
+            };
+            if is_unsatisfiable(filter, scan, graph) {
+                let empty = Empty::new(plan.schema().cloned());
+                PlanNode::PhysicalEmpty(Arc::new(empty))
+            } else {
+                plan
+            }
+        }
+        _ => plan,
+    }
+}
+
+fn is_unsatisfiable(filter: &Filter, scan: &PhysicalNodeScan, graph: &dyn GraphProvider) -> bool {
+    let mut conjuncts = vec![];
+    collect_conjuncts(&filter.predicate, &mut conjuncts);
+    conjuncts.into_iter().any(|conjunct| {
+        let Some((op, property_id, value, ty)) = property_comparison(conjunct, &scan.var) else {
+            return false;
+        };
+        // The property is resolved against a single vertex type, so it may refer to a different
+        // property of the vertices of other labels.
+        let [labels] = scan.labels.as_slice() else {
+            return false;
+        };
+        let [label_id] = labels.as_slice() else {
+            return false;
+        };
+        let Some((min, max)) = graph.vertex_property_range(*label_id, property_id) else {
+            return false;
+        };
+        may_match(&op, &min, &max, value, ty) == Some(false)
+    })
+}
+
+fn collect_conjuncts<'a>(expr: &'a BoundExpr, conjuncts: &mut Vec<&'a BoundExpr>) {
+    match &expr.kind {
+        BoundExprKind::Binary {
+            op: BoundBinaryOp::And,
+            lhs,
+            rhs,
+        } => {
+            collect_conjuncts(lhs, conjuncts);
+            collect_conjuncts(rhs, conjuncts);
+        }
+        _ => conjuncts.push(expr),
+    }
+}
+
+/// Matches `expr` against `<property of var> <op> <value>`, or its mirror image, with both sides of
+/// the same type.
+fn property_comparison<'a>(
+    expr: &'a BoundExpr,
+    var: &str,
+) -> Option<(BoundBinaryOp, PropertyId, &'a ScalarValue, &'a LogicalType)> {
+    let BoundExprKind::Binary { op, lhs, rhs } = &expr.kind else {
+        return None;
+    };
+    if lhs.logical_type != rhs.logical_type {
+        return None;
+    }
+    match (&lhs.kind, &rhs.kind) {
+        (
+            BoundExprKind::Property {
+                var: v,
+                property_id,
+                ..
+            },
+            BoundExprKind::Value(value),
+        ) if v == var => Some((op.clone(), *property_id, value, &lhs.logical_type)),
+        (
+            BoundExprKind::Value(value),
+            BoundExprKind::Property {
+                var: v,
+                property_id,
+                ..
+            },
+        ) if v == var => Some((mirror(op)?, *property_id, value, &lhs.logical_type)),
+        _ => None,
+    }
+}
+
+/// Returns the operator `op'` such that `a op b` is equivalent to `b op' a`.
+fn mirror(op: &BoundBinaryOp) -> Option<BoundBinaryOp> {
+    let mirrored = match op {
+        BoundBinaryOp::Eq => BoundBinaryOp::Eq,
+        BoundBinaryOp::Ne => BoundBinaryOp::Ne,
+        BoundBinaryOp::Lt => BoundBinaryOp::Gt,
+        BoundBinaryOp::Le => BoundBinaryOp::Ge,
+        BoundBinaryOp::Gt => BoundBinaryOp::Lt,
+        BoundBinaryOp::Ge => BoundBinaryOp::Le,
+        _ => return None,
+    };
+    Some(mirrored)
+}
+
+/// Returns whether a property whose values lie within `[min, max]` may satisfy
+/// `<property> <op> <value>`, or `None` if it cannot be decided.
+fn may_match(
+    op: &BoundBinaryOp,
+    min: &ScalarValue,
+    max: &ScalarValue,
+    value: &ScalarValue,
+    ty: &LogicalType,
+) -> Option<bool> {
+    if value.is_null() {
+        return None;
+    }
+    let min = compare(min, value, ty)?;
+    let max = compare(max, value, ty)?;
+    let result = match op {
+        BoundBinaryOp::Eq => min.is_le() && max.is_ge(),
+        BoundBinaryOp::Ne => !(min.is_eq() && max.is_eq()),
+        BoundBinaryOp::Lt => min.is_lt(),
+        BoundBinaryOp::Le => min.is_le(),
+        BoundBinaryOp::Gt => max.is_gt(),
+        BoundBinaryOp::Ge => max.is_ge(),
+        _ => return None,
+    };
+    Some(result)
+}
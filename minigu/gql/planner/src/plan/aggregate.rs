@@ -0,0 +1,49 @@
+use minigu_common::data_type::DataSchemaRef;
+use serde::Serialize;
+
+use crate::bound::{BoundAggregate, BoundExpr};
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Groups the rows of its child by the values of `group_by`, returning a row per group with these
+/// values followed by those of `aggregates` over the rows of the group.
+#[derive(Debug, Clone, Serialize)]
+pub struct Aggregate {
+    pub base: PlanBase,
+    pub group_by: Vec<BoundExpr>,
+    pub aggregates: Vec<BoundAggregate>,
+}
+
+impl Aggregate {
+    pub fn new(
+        child: PlanNode,
+        group_by: Vec<BoundExpr>,
+        aggregates: Vec<BoundAggregate>,
+        schema: DataSchemaRef,
+    ) -> Self {
+        assert!(!aggregates.is_empty());
+        assert_eq!(group_by.len() + aggregates.len(), schema.fields().len());
+        assert!(
+            group_by
+                .iter()
+                .map(|e| &e.logical_type)
+                .chain(aggregates.iter().map(|a| &a.logical_type))
+                .zip(schema.fields())
+                .all(|(ty, f)| ty == f.ty())
+        );
+        let base = PlanBase {
+            schema: Some(schema),
+            children: vec![child],
+        };
+        Self {
+            base,
+            group_by,
+            aggregates,
+        }
+    }
+}
+
+impl PlanData for Aggregate {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Deletes the vertices bound to `vars` in each row of its child, passing the rows through.
+#[derive(Debug, Clone, Serialize)]
+pub struct Delete {
+    pub base: PlanBase,
+    /// Whether the edges incident to the deleted vertices are deleted as well.
+    pub detach: bool,
+    pub vars: Vec<String>,
+}
+
+impl Delete {
+    pub fn new(child: PlanNode, detach: bool, vars: Vec<String>) -> Self {
+        assert!(!vars.is_empty());
+        let base = PlanBase {
+            schema: child.schema().cloned(),
+            children: vec![child],
+        };
+        Self { base, detach, vars }
+    }
+}
+
+impl PlanData for Delete {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Serialize;
+
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Removes duplicate rows of its child.
+#[derive(Debug, Clone, Serialize)]
+pub struct Distinct {
+    pub base: PlanBase,
+}
+
+impl Distinct {
+    pub fn new(child: PlanNode) -> Self {
+        let base = PlanBase {
+            schema: child.schema().cloned(),
+            children: vec![child],
+        };
+        Self { base }
+    }
+}
+
+impl PlanData for Distinct {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
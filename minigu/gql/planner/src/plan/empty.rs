@@ -0,0 +1,26 @@
+use minigu_common::data_type::DataSchemaRef;
+use serde::Serialize;
+
+use crate::plan::{PlanBase, PlanData};
+
+/// A plan node that produces no rows, e.g., a filter whose predicate is known to be always false.
+#[derive(Debug, Clone, Serialize)]
+pub struct Empty {
+    pub base: PlanBase,
+}
+
+impl Empty {
+    pub fn new(schema: Option<DataSchemaRef>) -> Self {
+        let base = PlanBase {
+            schema,
+            children: vec![],
+        };
+        Self { base }
+    }
+}
+
+impl PlanData for Empty {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
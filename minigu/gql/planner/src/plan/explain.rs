@@ -0,0 +1,205 @@
+//! A textual form of plans, e.g., to compare the plan of a query to an expected one in tests.
+
+use itertools::Itertools;
+use minigu_common::data_type::DataSchema;
+use minigu_common::ordering::{NullOrdering, SortOrdering};
+
+use crate::bound::BoundSortSpec;
+use crate::plan::{PlanData, PlanNode};
+
+impl PlanNode {
+    /// Returns the plan as an indented tree with a node per line, e.g.,
+    ///
+    /// ```text
+    /// PhysicalProject: n.name AS name
+    ///   PhysicalFilter: (n.age > Int32(Some(30)))
+    ///     PhysicalNodeScan: n, labels: [[1]]
+    /// ```
+    ///
+    /// Each line holds the kind of the node, followed by what it computes, if anything.
+    pub fn explain(&self) -> String {
+        let mut explained = String::new();
+        explain(self, 0, &mut explained);
+        explained
+    }
+}
+
+fn explain(node: &PlanNode, depth: usize, explained: &mut String) {
+    let kind: &'static str = node.into();
+    explained.push_str(&"  ".repeat(depth));
+    explained.push_str(kind);
+    let details = details(node);
+    if !details.is_empty() {
+        explained.push_str(": ");
+        explained.push_str(&details);
+    }
+    explained.push('\n');
+    for child in node.children() {
+        explain(child, depth + 1, explained);
+    }
+}
+
+/// Returns what `node` computes, or an empty string if this is determined by its kind.
+fn details(node: &PlanNode) -> String {
+    match node {
+        PlanNode::LogicalMatch(m) => format!("{:?}", m.kind),
+        PlanNode::LogicalFilter(filter) | PlanNode::PhysicalFilter(filter) => {
+            filter.predicate.to_string()
+        }
+        PlanNode::LogicalProject(project) | PlanNode::PhysicalProject(project) => {
+            let schema = project.schema().expect("project should have a schema");
+            named(project.exprs.iter().map(ToString::to_string), schema)
+        }
+        PlanNode::LogicalAggregate(aggregate)
+        | PlanNode::PhysicalAggregate(aggregate)
+        | PlanNode::PhysicalSortedAggregate(aggregate) => {
+            let schema = aggregate.schema().expect("aggregate should have a schema");
+            let exprs = aggregate
+                .group_by
+                .iter()
+                .map(ToString::to_string)
+                .chain(aggregate.aggregates.iter().map(ToString::to_string));
+            named(exprs, schema)
+        }
+        PlanNode::LogicalCall(call) | PlanNode::PhysicalCall(call) => format!(
+            "{}({})",
+            call.procedure.name(),
+            call.args.iter().map(|arg| format!("{arg:?}")).join(", ")
+        ),
+        PlanNode::LogicalSort(sort) | PlanNode::PhysicalSort(sort) => {
+            sort.specs.iter().map(sort_spec).join(", ")
+        }
+        PlanNode::LogicalLimit(limit) | PlanNode::PhysicalLimit(limit) => {
+            if limit.approximate {
+                format!("APPROXIMATE {}", limit.limit)
+            } else {
+                limit.limit.to_string()
+            }
+        }
+        PlanNode::LogicalVectorIndexScan(scan) | PlanNode::PhysicalVectorIndexScan(scan) => {
+            format!(
+                "{}, {} = VECTOR_DISTANCE({}, {}), limit: {}{}",
+                scan.binding,
+                scan.distance_alias,
+                scan.query,
+                scan.metric,
+                scan.limit,
+                if scan.approximate {
+                    ", approximate"
+                } else {
+                    ""
+                }
+            )
+        }
+        PlanNode::LogicalSetProperties(set) | PlanNode::PhysicalSetProperties(set) => set
+            .items
+            .iter()
+            .map(|item| format!("{}.{} = {}", item.var, item.name, item.value))
+            .join(", "),
+        PlanNode::LogicalInsert(insert) | PlanNode::PhysicalInsert(insert) => insert
+            .vertices
+            .iter()
+            .map(|vertex| {
+                format!(
+                    "{}, label: {}, properties: [{}]",
+                    vertex.var,
+                    vertex.label_id,
+                    vertex.properties.iter().join(", ")
+                )
+            })
+            .join("; "),
+        PlanNode::LogicalDelete(delete) | PlanNode::PhysicalDelete(delete) => {
+            let vars = delete.vars.join(", ");
+            if delete.detach {
+                format!("DETACH {vars}")
+            } else {
+                vars
+            }
+        }
+        PlanNode::LogicalJoin(join) | PlanNode::PhysicalJoin(join) => join.keys.join(", "),
+        PlanNode::PhysicalNodeScan(scan) => match scan.estimated_rows {
+            Some(rows) => format!(
+                "{}, labels: {:?}, estimated rows: {rows}",
+                scan.var, scan.labels
+            ),
+            None => format!("{}, labels: {:?}", scan.var, scan.labels),
+        },
+        PlanNode::PhysicalPropertyIndexScan(scan) => {
+            format!(
+                "{}, index: {} = {:?}",
+                scan.var, scan.lookup.name, scan.lookup.value
+            )
+        }
+        PlanNode::PhysicalVertexPropertyScan(scan) => {
+            format!(
+                "{}, properties: [{}]",
+                scan.var,
+                scan.properties.iter().join(", ")
+            )
+        }
+        PlanNode::LogicalOneRow(_)
+        | PlanNode::PhysicalOneRow(_)
+        | PlanNode::LogicalDistinct(_)
+        | PlanNode::PhysicalDistinct(_)
+        | PlanNode::LogicalUnion(_)
+        | PlanNode::PhysicalUnion(_)
+        | PlanNode::PhysicalEmpty(_) => String::new(),
+    }
+}
+
+/// Returns `exprs` along with the names of the fields of `schema` they compute, e.g.,
+/// `n.name AS name`, the names being omitted when they are the expressions themselves.
+fn named(exprs: impl Iterator<Item = String>, schema: &DataSchema) -> String {
+    exprs
+        .zip(schema.fields())
+        .map(|(expr, field)| {
+            if expr == field.name() {
+                expr
+            } else {
+                format!("{expr} AS {}", field.name())
+            }
+        })
+        .join(", ")
+}
+
+fn sort_spec(spec: &BoundSortSpec) -> String {
+    let ordering = match spec.ordering {
+        SortOrdering::Ascending => "ASC",
+        SortOrdering::Descending => "DESC",
+    };
+    let null_ordering = match spec.null_ordering {
+        NullOrdering::First => "NULLS FIRST",
+        NullOrdering::Last => "NULLS LAST",
+    };
+    format!("{} {ordering} {null_ordering}", spec.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use insta::assert_snapshot;
+    use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+    use minigu_common::types::LabelId;
+
+    use super::*;
+    use crate::bound::{BoundBinaryOp, BoundExpr};
+    use crate::plan::filter::Filter;
+    use crate::plan::project::Project;
+    use crate::plan::scan::PhysicalNodeScan;
+
+    #[test]
+    fn test_explain_filter_over_scan() {
+        let label = LabelId::new(1).unwrap();
+        let scan = PhysicalNodeScan::new("n", vec![vec![label]], 0);
+        let scan = PlanNode::PhysicalNodeScan(Arc::new(scan));
+        let age = || BoundExpr::property("n".into(), 1, "age".into(), LogicalType::Int32, true);
+        let thirty = BoundExpr::value(30i32.into(), LogicalType::Int32, false);
+        let predicate = BoundExpr::binary(BoundBinaryOp::Gt, age(), thirty);
+        let filter = PlanNode::PhysicalFilter(Arc::new(Filter::new(scan, predicate)));
+        let schema = DataSchema::new(vec![DataField::new("age".into(), LogicalType::Int32, true)]);
+        let project = Project::new(filter, vec![age()], Arc::new(schema));
+        let plan = PlanNode::PhysicalProject(Arc::new(project));
+        assert_snapshot!(plan.explain());
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use minigu_common::data_type::{DataField, DataSchema, LogicalType};
+use serde::Serialize;
+
+use crate::bound::BoundInsertVertex;
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Inserts the vertices of `vertices` once for each row of its child, appending their ids to the
+/// row, like scans bind vertex variables to ids.
+#[derive(Debug, Clone, Serialize)]
+pub struct Insert {
+    pub base: PlanBase,
+    pub vertices: Vec<BoundInsertVertex>,
+}
+
+impl Insert {
+    pub fn new(child: PlanNode, vertices: Vec<BoundInsertVertex>) -> Self {
+        assert!(!vertices.is_empty());
+        let mut schema = child
+            .schema()
+            .map(|schema| schema.as_ref().clone())
+            .unwrap_or_else(|| DataSchema::new(vec![]));
+        for vertex in &vertices {
+            schema.push_back(&DataField::new(
+                vertex.var.clone(),
+                LogicalType::Int64,
+                false,
+            ));
+        }
+        let base = PlanBase {
+            schema: Some(Arc::new(schema)),
+            children: vec![child],
+        };
+        Self { base, vertices }
+    }
+}
+
+impl PlanData for Insert {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
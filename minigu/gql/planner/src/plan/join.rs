@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use minigu_common::data_type::DataSchema;
+use serde::Serialize;
+
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Joins the rows of the left child with those of the right one binding the same values to their
+/// common columns, the `keys`. Without common columns, every row of the left child is joined with
+/// every row of the right one.
+///
+/// The columns of the left child are output first, followed by the other columns of the right
+/// child.
+#[derive(Debug, Clone, Serialize)]
+pub struct Join {
+    pub base: PlanBase,
+    pub keys: Vec<String>,
+}
+
+impl Join {
+    pub fn new(left: PlanNode, right: PlanNode) -> Self {
+        let left_schema = left.schema().expect("left child should have a schema");
+        let right_schema = right.schema().expect("right child should have a schema");
+        let (keys, others): (Vec<_>, Vec<_>) = right_schema
+            .fields()
+            .iter()
+            .partition(|f| left_schema.get_field_by_name(f.name()).is_some());
+        let keys = keys.into_iter().map(|f| f.name().to_string()).collect();
+        let fields = left_schema.fields().iter().chain(others).cloned().collect();
+        let base = PlanBase {
+            schema: Some(Arc::new(DataSchema::new(fields))),
+            children: vec![left, right],
+        };
+        Self { base, keys }
+    }
+}
+
+impl PlanData for Join {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
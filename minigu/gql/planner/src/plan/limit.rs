@@ -6,7 +6,9 @@
 pub struct Limit {
     pub base: PlanBase,
     pub limit: usize,
-    pub approximate: bool, // if true, enable ANN search
+    /// If true, enables ANN search when planning vector queries. Otherwise, the limit may be
+    /// enforced at chunk granularity, possibly returning a few rows beyond the limit.
+    pub approximate: bool,
 }
 
 impl Limit {
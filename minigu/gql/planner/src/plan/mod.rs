@@ -1,26 +1,44 @@
+pub mod aggregate;
 pub mod call;
+pub mod delete;
+pub mod distinct;
+pub mod empty;
+mod explain;
 pub mod filter;
+pub mod insert;
+pub mod join;
 pub mod limit;
 pub mod logical_match;
 pub mod one_row;
 pub mod project;
 pub mod scan;
+pub mod set_properties;
 pub mod sort;
+pub mod union;
 pub mod vector_index_scan;
 
 use std::sync::Arc;
 
 use minigu_common::data_type::DataSchemaRef;
 use serde::Serialize;
+use strum::IntoStaticStr;
 
+use crate::plan::aggregate::Aggregate;
 use crate::plan::call::Call;
+use crate::plan::delete::Delete;
+use crate::plan::distinct::Distinct;
+use crate::plan::empty::Empty;
 use crate::plan::filter::Filter;
+use crate::plan::insert::Insert;
+use crate::plan::join::Join;
 use crate::plan::limit::Limit;
 use crate::plan::logical_match::LogicalMatch;
 use crate::plan::one_row::OneRow;
 use crate::plan::project::Project;
-use crate::plan::scan::PhysicalNodeScan;
+use crate::plan::scan::{PhysicalNodeScan, PhysicalPropertyIndexScan, PhysicalVertexPropertyScan};
+use crate::plan::set_properties::SetProperties;
 use crate::plan::sort::Sort;
+use crate::plan::union::Union;
 use crate::plan::vector_index_scan::VectorIndexScan;
 
 #[derive(Debug, Clone, Serialize)]
@@ -55,7 +73,7 @@ fn children(&self) -> &[PlanNode] {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, IntoStaticStr)]
 pub enum PlanNode {
     LogicalMatch(Arc<LogicalMatch>),
     LogicalFilter(Arc<Filter>),
@@ -67,7 +85,14 @@ pub enum PlanNode {
     // (by inserting PhysicalSort).
     LogicalSort(Arc<Sort>),
     LogicalLimit(Arc<Limit>),
+    LogicalDistinct(Arc<Distinct>),
     LogicalVectorIndexScan(Arc<VectorIndexScan>),
+    LogicalSetProperties(Arc<SetProperties>),
+    LogicalInsert(Arc<Insert>),
+    LogicalDelete(Arc<Delete>),
+    LogicalUnion(Arc<Union>),
+    LogicalAggregate(Arc<Aggregate>),
+    LogicalJoin(Arc<Join>),
 
     PhysicalFilter(Arc<Filter>),
     PhysicalProject(Arc<Project>),
@@ -75,13 +100,25 @@ pub enum PlanNode {
     PhysicalOneRow(Arc<OneRow>),
     PhysicalSort(Arc<Sort>),
     PhysicalLimit(Arc<Limit>),
+    PhysicalDistinct(Arc<Distinct>),
     PhysicalVectorIndexScan(Arc<VectorIndexScan>),
+    PhysicalEmpty(Arc<Empty>),
+    PhysicalSetProperties(Arc<SetProperties>),
+    PhysicalInsert(Arc<Insert>),
+    PhysicalDelete(Arc<Delete>),
+    PhysicalUnion(Arc<Union>),
+    PhysicalAggregate(Arc<Aggregate>),
+    /// Like [`PlanNode::PhysicalAggregate`], over input sorted on the keys of the aggregate.
+    PhysicalSortedAggregate(Arc<Aggregate>),
+    PhysicalJoin(Arc<Join>),
     //  PhysicalNodeScan retrieves node ids based on labels during the scan phase,
     //  without immediately materializing full node attributes.
     //  During subsequent matching and computation, these ids are lazily expanded
     //  into complete attribute representations (ArrayRefs) only when required,
     //  to improve performance and reduce unnecessary data loading.
     PhysicalNodeScan(Arc<PhysicalNodeScan>),
+    PhysicalPropertyIndexScan(Arc<PhysicalPropertyIndexScan>),
+    PhysicalVertexPropertyScan(Arc<PhysicalVertexPropertyScan>),
     // PhysicalCatalogModify(Arc<PhysicalCatalogModify>)
 }
 
@@ -95,6 +132,7 @@ fn base(&self) -> &PlanBase {
             PlanNode::LogicalOneRow(node) => node.base(),
             PlanNode::LogicalSort(node) => node.base(),
             PlanNode::LogicalLimit(node) => node.base(),
+            PlanNode::LogicalDistinct(node) => node.base(),
 
             PlanNode::PhysicalFilter(node) => node.base(),
             PlanNode::PhysicalProject(node) => node.base(),
@@ -102,9 +140,26 @@ fn base(&self) -> &PlanBase {
             PlanNode::PhysicalOneRow(node) => node.base(),
             PlanNode::PhysicalSort(node) => node.base(),
             PlanNode::PhysicalLimit(node) => node.base(),
+            PlanNode::PhysicalDistinct(node) => node.base(),
             PlanNode::PhysicalNodeScan(node) => node.base(),
+            PlanNode::PhysicalPropertyIndexScan(node) => node.base(),
+            PlanNode::PhysicalVertexPropertyScan(node) => node.base(),
             PlanNode::LogicalVectorIndexScan(node) => node.base(),
             PlanNode::PhysicalVectorIndexScan(node) => node.base(),
+            PlanNode::PhysicalEmpty(node) => node.base(),
+            PlanNode::LogicalSetProperties(node) => node.base(),
+            PlanNode::PhysicalSetProperties(node) => node.base(),
+            PlanNode::LogicalDelete(node) => node.base(),
+            PlanNode::PhysicalDelete(node) => node.base(),
+            PlanNode::LogicalUnion(node) => node.base(),
+            PlanNode::PhysicalUnion(node) => node.base(),
+            PlanNode::LogicalAggregate(node) => node.base(),
+            PlanNode::PhysicalAggregate(node) => node.base(),
+            PlanNode::PhysicalSortedAggregate(node) => node.base(),
+            PlanNode::LogicalJoin(node) => node.base(),
+            PlanNode::LogicalInsert(node) => node.base(),
+            PlanNode::PhysicalInsert(node) => node.base(),
+            PlanNode::PhysicalJoin(node) => node.base(),
         }
     }
 }
@@ -4,7 +4,8 @@
 use minigu_common::types::LabelId;
 use serde::Serialize;
 
-use crate::plan::{PlanBase, PlanData};
+use crate::bound::{BoundExpr, BoundIndexLookup};
+use crate::plan::{PlanBase, PlanData, PlanNode};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct PhysicalNodeScan {
@@ -16,6 +17,8 @@ pub struct PhysicalNodeScan {
     // labels = [ [A], [B] ] LabelA or LabelB
     pub labels: Vec<Vec<LabelId>>,
     pub graph_id: i64,
+    /// The number of vertices the scan is estimated to produce, if the graph has been analyzed.
+    pub estimated_rows: Option<u64>,
 }
 
 impl PhysicalNodeScan {
@@ -32,8 +35,14 @@ pub fn new(var: &str, labels: Vec<Vec<LabelId>>, graph_id: i64) -> Self {
             var: var.to_string(),
             labels,
             graph_id,
+            estimated_rows: None,
         }
     }
+
+    pub fn with_estimated_rows(mut self, estimated_rows: u64) -> Self {
+        self.estimated_rows = Some(estimated_rows);
+        self
+    }
 }
 
 impl PlanData for PhysicalNodeScan {
@@ -41,3 +50,75 @@ fn base(&self) -> &PlanBase {
         &self.base
     }
 }
+
+/// Retrieves the ids of the vertices bound to `var` from the property index of `lookup`, instead
+/// of scanning every vertex of the indexed label.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalPropertyIndexScan {
+    pub base: PlanBase,
+    pub var: String,
+    pub lookup: BoundIndexLookup,
+}
+
+impl PhysicalPropertyIndexScan {
+    pub fn new(var: &str, lookup: BoundIndexLookup) -> Self {
+        // Like node scans, only the ids of the vertices are output.
+        let field = DataField::new(var.to_string(), LogicalType::Int64, false);
+        let schema = DataSchema::new(vec![field]);
+        let base = PlanBase {
+            schema: Some(Arc::new(schema)),
+            children: vec![],
+        };
+        Self {
+            base,
+            var: var.to_string(),
+            lookup,
+        }
+    }
+}
+
+impl PlanData for PhysicalPropertyIndexScan {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
+
+/// Appends the properties of the vertices bound to `var`, whose ids are output by the child, as
+/// columns named after the property expressions, e.g., `n.name`.
+///
+/// Only the listed properties are materialized, so that the properties which are never used are
+/// not read from the graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhysicalVertexPropertyScan {
+    pub base: PlanBase,
+    pub var: String,
+    /// The property expressions of `var` to scan.
+    pub properties: Vec<BoundExpr>,
+}
+
+impl PhysicalVertexPropertyScan {
+    pub fn new(child: PlanNode, var: &str, properties: Vec<BoundExpr>) -> Self {
+        let schema = child.schema().expect("child should have a schema");
+        let mut fields = schema.fields().to_vec();
+        fields.extend(
+            properties
+                .iter()
+                .map(|p| DataField::new(p.to_string(), p.logical_type.clone(), p.nullable)),
+        );
+        let base = PlanBase {
+            schema: Some(Arc::new(DataSchema::new(fields))),
+            children: vec![child],
+        };
+        Self {
+            base,
+            var: var.to_string(),
+            properties,
+        }
+    }
+}
+
+impl PlanData for PhysicalVertexPropertyScan {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
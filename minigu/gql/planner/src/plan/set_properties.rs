@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use crate::bound::BoundSetItem;
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Sets the properties of the vertices bound in each row of its child, passing the rows through.
+#[derive(Debug, Clone, Serialize)]
+pub struct SetProperties {
+    pub base: PlanBase,
+    pub items: Vec<BoundSetItem>,
+}
+
+impl SetProperties {
+    pub fn new(child: PlanNode, items: Vec<BoundSetItem>) -> Self {
+        assert!(!items.is_empty());
+        let base = PlanBase {
+            schema: child.schema().cloned(),
+            children: vec![child],
+        };
+        Self { base, items }
+    }
+}
+
+impl PlanData for SetProperties {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
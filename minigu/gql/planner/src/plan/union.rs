@@ -0,0 +1,30 @@
+use minigu_common::data_type::DataSchemaRef;
+use serde::Serialize;
+
+use crate::plan::{PlanBase, PlanData, PlanNode};
+
+/// Outputs the rows of all of its children, one child after another, keeping duplicates.
+///
+/// Columns are matched by position, so every child must output the columns of `schema` in order,
+/// under any names.
+#[derive(Debug, Clone, Serialize)]
+pub struct Union {
+    pub base: PlanBase,
+}
+
+impl Union {
+    pub fn new(children: Vec<PlanNode>, schema: DataSchemaRef) -> Self {
+        assert!(!children.is_empty());
+        let base = PlanBase {
+            schema: Some(schema),
+            children,
+        };
+        Self { base }
+    }
+}
+
+impl PlanData for Union {
+    fn base(&self) -> &PlanBase {
+        &self.base
+    }
+}
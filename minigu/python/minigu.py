@@ -6,10 +6,11 @@ This module provides Python bindings for the miniGU graph database.
 
 import sys
 import re
-from typing import Optional, List, Dict, Any, Union
+from typing import Optional, List, Dict, Any, Union, Callable
 from pathlib import Path
 import json
 import asyncio
+import time
 
 # Import from package __init__.py - this is the primary way to get the Rust bindings
 try:
@@ -183,6 +184,14 @@ class QueryResult:
     def __getitem__(self, index):
         return self.data[index]
 
+    @classmethod
+    def _from_dict(cls, result_dict: Dict[str, Any]) -> "QueryResult":
+        return cls(
+            result_dict.get("schema", []),
+            result_dict.get("data", []),
+            result_dict.get("metrics", {}),
+        )
+
 
 
 class _BaseMiniGU:
@@ -298,6 +307,27 @@ class _BaseMiniGU:
         else:
             raise RuntimeError("Rust bindings required for database operations")
     
+    def _execute_all_internal(self, query: str) -> List[Dict[str, Any]]:
+        """
+        Internal method to execute GQL query using Rust backend, keeping the result
+        set of each of its statements.
+        
+        Args:
+            query: GQL query statement
+            
+        Returns:
+            List of raw result dictionaries from Rust backend, one per statement
+        """
+        self._ensure_connected()
+        
+        if HAS_RUST_BINDINGS and self._rust_instance:
+            try:
+                return self._rust_instance.execute_all(query)
+            except Exception as e:
+                _handle_exception(e)
+        else:
+            raise RuntimeError("Rust bindings required for database operations")
+    
     def _create_graph_internal(self, name: str, schema: Optional[Dict] = None) -> None:
         """
         Internal method to create a graph database.
@@ -443,6 +473,22 @@ class MiniGU(_BaseMiniGU):
         metrics = result_dict.get("metrics", {})
         return QueryResult(schema, data, metrics)
     
+    def execute_all(self, query: str) -> List[QueryResult]:
+        """
+        Execute GQL query, keeping the result set of each of its statements.
+        
+        Args:
+            query: GQL query statement, whose statements are separated by NEXT
+            
+        Returns:
+            List of query results, one per statement, in order
+            
+        Example:
+            >>> db = MiniGU()
+            >>> first, second = db.execute_all("RETURN 1 AS a NEXT RETURN 2 AS b")
+        """
+        return [QueryResult._from_dict(d) for d in self._execute_all_internal(query)]
+    
     def create_graph(self, name: str, schema: Optional[Dict] = None) -> bool:
         """
         Create a graph database.
@@ -545,6 +591,27 @@ class MiniGU(_BaseMiniGU):
         else:
             raise RuntimeError("Rust bindings required for database operations")
     
+    def set_progress_callback(self, callback: Optional[Callable[[int, float], None]],
+                              interval: int = 10000) -> None:
+        """
+        Set the callable notified of the progress of long-running imports and exports.
+        
+        Args:
+            callback: Called every `interval` rows with the number of rows processed and the
+                seconds elapsed, or None to stop notifying progress
+            interval: Number of rows between two calls
+            
+        Example:
+            >>> db = MiniGU()
+            >>> db.set_progress_callback(lambda rows, secs: print(f"{rows} rows in {secs:.1f}s"))
+        """
+        self._ensure_connected()
+        
+        if HAS_RUST_BINDINGS and self._rust_instance:
+            self._rust_instance.set_progress_callback(callback, interval)
+        else:
+            raise RuntimeError("Rust bindings required for database operations")
+    
     def begin_transaction(self) -> None:
         """
         Begin a transaction.
@@ -604,6 +671,37 @@ class MiniGU(_BaseMiniGU):
         """
         raise TransactionError("Transaction functionality is not yet implemented. "
                               "This feature is planned but not yet implemented.")
+    
+    def transaction(self, func, retry: int = 1) -> Any:
+        """
+        Run func as a unit of work, re-running it on transaction conflicts.
+        
+        Args:
+            func: Callable taking this instance, whose result is returned
+            retry: Maximum number of attempts, at least 1
+            
+        Returns:
+            The result of the first successful call of func
+            
+        Raises:
+            TransactionError: Raised by the last attempt
+            ValueError: Raised when retry is less than 1
+            
+        Note:
+            Only TransactionError triggers a retry, after a delay doubling at every attempt.
+            Any other exception is raised immediately.
+        """
+        if retry < 1:
+            raise ValueError("retry must be at least 1")
+        delay = 0.001
+        for attempt in range(1, retry + 1):
+            try:
+                return func(self)
+            except TransactionError:
+                if attempt == retry:
+                    raise
+                time.sleep(delay)
+                delay *= 2
 
 class AsyncMiniGU(_BaseMiniGU):
     """
@@ -680,6 +778,18 @@ class AsyncMiniGU(_BaseMiniGU):
         metrics = result_dict.get("metrics", {})
         return QueryResult(schema, data, metrics)
     
+    async def execute_all(self, query: str) -> List[QueryResult]:
+        """
+        Execute GQL query asynchronously, keeping the result set of each of its statements.
+        
+        Args:
+            query: GQL query statement, whose statements are separated by NEXT
+            
+        Returns:
+            List of query results, one per statement, in order
+        """
+        return [QueryResult._from_dict(d) for d in self._execute_all_internal(query)]
+    
     async def create_graph(self, name: str, schema: Optional[Dict] = None) -> bool:
         """
         Create a graph database asynchronously.
@@ -5,9 +5,13 @@
 use std::path::Path;
 
 use arrow::array::*;
-use arrow::datatypes::DataType;
+use gql_parser::{quote_identifier, quote_string};
+use minigu::bulk_load::LoadOptions;
 use minigu::common::data_chunk::DataChunk;
+use minigu::common::value::{PropertyValue, ScalarValue};
 use minigu::database::{Database, DatabaseConfig};
+use minigu::progress::{Progress, ProgressCallback};
+use minigu::result::QueryResult;
 use minigu::session::Session;
 use pyo3::prelude::*;
 use pyo3::types::{PyBool, PyDict, PyList, PyString};
@@ -55,21 +59,12 @@ fn sanitize_file_path(path: &str) -> String {
         .replace("..", "")
 }
 
-// Helper function to sanitize graph names
-fn sanitize_graph_name(name: &str) -> String {
-    // Allow alphanumeric characters and underscores only
-    name.chars()
-        .filter(|c| c.is_alphanumeric() || *c == '_')
-        .collect()
-}
-
 /// PyMiniGU class that wraps the Rust Database
 #[pyclass]
 #[allow(clippy::upper_case_acronyms)]
 pub struct PyMiniGU {
     database: Option<Database>,
     session: Option<Session>,
-    current_graph: Option<String>, // Track current graph name
 }
 
 #[pymethods]
@@ -80,7 +75,6 @@ fn new() -> PyResult<Self> {
         Ok(PyMiniGU {
             database: None,
             session: None,
-            current_graph: None,
         })
     }
 
@@ -108,11 +102,10 @@ fn init(&mut self) -> PyResult<()> {
 
         self.database = Some(db);
         self.session = Some(session);
-        self.current_graph = None;
         Ok(())
     }
 
-    /// Execute a GQL query
+    /// Execute a GQL query, returning the result set of its last statement
     fn execute(&mut self, query_str: &str, py: Python) -> PyResult<PyObject> {
         // Get the session
         let session = self.session.as_mut().expect("Session not initialized");
@@ -122,54 +115,22 @@ fn execute(&mut self, query_str: &str, py: Python) -> PyResult<PyObject> {
             PyErr::new::<pyo3::exceptions::PyException, _>(format!("Query execution failed: {}", e))
         })?;
 
-        // Convert QueryResult to Python dict
-        let dict = PyDict::new(py);
-
-        // Convert schema
-        let schema_list = PyList::empty(py);
-        if let Some(schema_ref) = query_result.schema() {
-            for field in schema_ref.fields() {
-                let field_dict = PyDict::new(py);
-                field_dict.set_item("name", field.name())?;
-                field_dict.set_item("data_type", format!("{:?}", field.ty()))?;
-                schema_list.append(field_dict)?;
-            }
-        }
-
-        dict.set_item("schema", schema_list)?;
-
-        // Convert data
-        let data_list = PyList::empty(py);
-        for chunk in query_result.iter() {
-            // Convert DataChunk to Python list of lists
-            let chunk_data = convert_data_chunk(chunk)?;
-            for row in chunk_data {
-                let row_list = PyList::empty(py);
-                for value in row {
-                    row_list.append(value)?;
-                }
-                data_list.append(row_list)?;
-            }
-        }
-
-        dict.set_item("data", data_list)?;
+        query_result_to_py(py, &query_result)
+    }
 
-        // Convert metrics
-        let metrics = query_result.metrics();
-        let metrics_dict = PyDict::new(py);
-        metrics_dict.set_item("parsing_time_ms", metrics.parsing_time().as_millis() as f64)?;
-        metrics_dict.set_item(
-            "planning_time_ms",
-            metrics.planning_time().as_millis() as f64,
-        )?;
-        metrics_dict.set_item(
-            "execution_time_ms",
-            metrics.execution_time().as_millis() as f64,
-        )?;
+    /// Execute a GQL query, returning a list with the result set of each of its statements
+    fn execute_all(&mut self, query_str: &str, py: Python) -> PyResult<PyObject> {
+        let session = self.session.as_mut().expect("Session not initialized");
 
-        dict.set_item("metrics", metrics_dict)?;
+        let query_results = session.query_all(query_str).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyException, _>(format!("Query execution failed: {}", e))
+        })?;
 
-        Ok(dict.into())
+        let result_list = PyList::empty(py);
+        for query_result in &query_results {
+            result_list.append(query_result_to_py(py, query_result)?)?;
+        }
+        Ok(result_list.into())
     }
 
     /// Load data from a file
@@ -189,15 +150,19 @@ fn load_from_file(&mut self, file_path: &str) -> PyResult<()> {
         }
 
         // Use current graph or default to "default_graph"
-        let graph_name = self.current_graph.as_deref().unwrap_or("default_graph");
+        let graph_name = session
+            .current_graph()
+            .unwrap_or("default_graph")
+            .to_string();
 
         // Sanitize the path to prevent injection attacks
         let sanitized_path = sanitize_file_path(file_path);
 
         // Execute the import procedure with correct syntax (no semicolon)
         let query = format!(
-            "CALL import('{}', '{}', 'manifest.json')",
-            graph_name, sanitized_path
+            "CALL import({}, '{}', 'manifest.json')",
+            quote_string(&graph_name),
+            sanitized_path
         );
         match session.query(&query) {
             Ok(_) => {
@@ -224,7 +189,10 @@ fn load_data(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
         println!("Loading {} records", list.len());
 
         // Use current graph or default to "default_graph"
-        let graph_name = self.current_graph.as_deref().unwrap_or("default_graph");
+        let graph_name = session
+            .current_graph()
+            .unwrap_or("default_graph")
+            .to_string();
 
         // Process data in batches for better performance
         const BATCH_SIZE: usize = 1000;
@@ -388,15 +356,19 @@ fn save_to_file(&mut self, file_path: &str) -> PyResult<()> {
         })?;
 
         // Use current graph or default to "default_graph"
-        let graph_name = self.current_graph.as_deref().unwrap_or("default_graph");
+        let graph_name = session
+            .current_graph()
+            .unwrap_or("default_graph")
+            .to_string();
 
         // Sanitize the path to prevent injection attacks
         let sanitized_path = sanitize_file_path(file_path);
 
         // Execute export procedure with correct syntax (no semicolon)
         let query = format!(
-            "CALL export('{}', '{}', 'manifest.json')",
-            graph_name, sanitized_path
+            "CALL export({}, '{}', 'manifest.json')",
+            quote_string(&graph_name),
+            sanitized_path
         );
         session.query(&query).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyException, _>(format!("Export failed: {}", e))
@@ -418,41 +390,74 @@ fn create_graph(&mut self, graph_name: &str, _schema: Option<&str>) -> PyResult<
             ));
         }
 
-        // Sanitize the graph name to prevent injection
-        let sanitized_name = sanitize_graph_name(graph_name);
-
-        // Validate graph name after sanitization
-        if sanitized_name.is_empty() {
-            return Err(PyErr::new::<pyo3::exceptions::PyException, _>(
-                "Graph name contains only invalid characters",
-            ));
-        }
-
-        // Create the graph using the create_test_graph procedure
-        let query = format!("CALL create_test_graph('{}')", sanitized_name);
+        // Create the graph using the create_test_graph procedure, quoting its name so that it is
+        // taken as is, whatever characters it contains
+        let query = format!("CALL create_test_graph({})", quote_string(graph_name));
         println!("Attempting to execute query: {}", query);
 
         match session.query(&query) {
             Ok(_) => {
-                println!("Graph '{}' created successfully", sanitized_name);
-                self.current_graph = Some(sanitized_name);
+                println!("Graph '{}' created successfully", graph_name);
+                // Make the new graph the current one of the session
+                session
+                    .query(&format!(
+                        "SESSION SET GRAPH {}",
+                        quote_identifier(graph_name)
+                    ))
+                    .map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyException, _>(format!(
+                            "Failed to use graph '{}': {}",
+                            graph_name, e
+                        ))
+                    })?;
                 Ok(())
             }
             Err(e) => {
                 println!("Error executing query '{}': {}", query, e);
                 Err(PyErr::new::<pyo3::exceptions::PyException, _>(format!(
                     "Failed to create graph '{}': {}",
-                    sanitized_name, e
+                    graph_name, e
                 )))
             }
         }
     }
 
+    /// Set the callable notified of the progress of imports and exports, called every `interval`
+    /// rows with the number of rows processed and the seconds elapsed, or unset it with `None`
+    #[pyo3(signature = (callback, interval = 10_000))]
+    fn set_progress_callback(
+        &mut self,
+        callback: Option<Py<PyAny>>,
+        interval: usize,
+    ) -> PyResult<()> {
+        let session = self.session.as_mut().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyException, _>("Session not initialized")
+        })?;
+        if interval == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Progress interval must be positive",
+            ));
+        }
+        let progress = callback.map(|callback| {
+            ProgressCallback::new(interval, move |progress: Progress| {
+                Python::with_gil(|py| {
+                    // The callback cannot fail the procedure, so its errors are only reported
+                    if let Err(e) =
+                        callback.call1(py, (progress.rows, progress.elapsed.as_secs_f64()))
+                    {
+                        e.print(py);
+                    }
+                });
+            })
+        });
+        session.set_progress_callback(progress);
+        Ok(())
+    }
+
     /// Close the database connection
     fn close(&mut self) -> PyResult<()> {
         self.database = None;
         self.session = None;
-        self.current_graph = None;
         Ok(())
     }
 
@@ -472,12 +477,19 @@ fn load_csv(&mut self, path: &str) -> PyResult<()> {
         }
 
         // Use current graph or default to "default_graph"
-        let graph_name = self.current_graph.as_deref().unwrap_or("default_graph");
+        let graph_name = session
+            .current_graph()
+            .unwrap_or("default_graph")
+            .to_string();
 
         // Sanitize the path to prevent injection attacks
         let sanitized_path = sanitize_file_path(path);
 
-        let query = format!("LOAD CSV FROM \"{}\" INTO {}", sanitized_path, graph_name);
+        let query = format!(
+            "LOAD CSV FROM \"{}\" INTO {}",
+            sanitized_path,
+            quote_identifier(&graph_name)
+        );
         match session.query(&query) {
             Ok(_) => {
                 println!("CSV data loaded successfully from: {}", path);
@@ -492,9 +504,11 @@ fn load_csv(&mut self, path: &str) -> PyResult<()> {
 
     /// Load data from a JSON file
     fn load_json(&mut self, path: &str) -> PyResult<()> {
-        let session = self.session.as_mut().ok_or_else(|| {
-            PyErr::new::<pyo3::exceptions::PyException, _>("Session not initialized")
-        })?;
+        let (Some(db), Some(session)) = (self.database.as_ref(), self.session.as_ref()) else {
+            return Err(PyErr::new::<pyo3::exceptions::PyException, _>(
+                "Session not initialized",
+            ));
+        };
 
         // Validate file path
         let path_obj = Path::new(path);
@@ -506,22 +520,25 @@ fn load_json(&mut self, path: &str) -> PyResult<()> {
         }
 
         // Use current graph or default to "default_graph"
-        let graph_name = self.current_graph.as_deref().unwrap_or("default_graph");
-
-        // Sanitize the path to prevent injection attacks
-        let sanitized_path = sanitize_file_path(path);
+        let graph_name = session.current_graph().unwrap_or("default_graph");
 
-        let query = format!("LOAD JSON FROM \"{}\" INTO {}", sanitized_path, graph_name);
-        match session.query(&query) {
-            Ok(_) => {
-                println!("JSON data loaded successfully from: {}", path);
-                Ok(())
-            }
-            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyException, _>(format!(
-                "Failed to load JSON from file: {}",
-                e
-            ))),
+        // Each line of the file is a JSON object with a `label` field, loaded as a vertex
+        let report = db
+            .load_ndjson(graph_name, path_obj, &LoadOptions::default())
+            .map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyException, _>(format!(
+                    "Failed to load JSON from file: {}",
+                    e
+                ))
+            })?;
+        if let Some(error) = report.errors().first() {
+            return Err(PyErr::new::<pyo3::exceptions::PyException, _>(format!(
+                "Failed to load JSON from file: line {}: {}",
+                error.line, error.message
+            )));
         }
+        println!("JSON data loaded successfully from: {}", path);
+        Ok(())
     }
 
     /// Drop a graph
@@ -535,29 +552,24 @@ fn drop_graph(&mut self, graph_name: &str) -> PyResult<()> {
             ));
         }
 
-        // Sanitize graph name
-        let sanitized_name = sanitize_graph_name(graph_name);
-
-        // Validate graph name after sanitization
-        if sanitized_name.is_empty() {
-            return Err(PyErr::new::<pyo3::exceptions::PyException, _>(
-                "Graph name contains only invalid characters",
-            ));
-        }
-
-        let query = format!("DROP GRAPH {}", sanitized_name);
+        let query = format!("DROP GRAPH {}", quote_identifier(graph_name));
         match session.query(&query) {
             Ok(_) => {
                 // Clear current graph if it's the one being dropped
-                if self.current_graph.as_deref() == Some(&sanitized_name) {
-                    self.current_graph = None;
+                if session.current_graph() == Some(graph_name) {
+                    session.query("SESSION RESET GRAPH").map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyException, _>(format!(
+                            "Failed to reset current graph: {}",
+                            e
+                        ))
+                    })?;
                 }
-                println!("Graph '{}' dropped successfully", sanitized_name);
+                println!("Graph '{}' dropped successfully", graph_name);
                 Ok(())
             }
             Err(e) => Err(PyErr::new::<pyo3::exceptions::PyException, _>(format!(
                 "Failed to drop graph '{}': {}",
-                sanitized_name, e
+                graph_name, e
             ))),
         }
     }
@@ -573,21 +585,10 @@ fn use_graph(&mut self, graph_name: &str) -> PyResult<()> {
             ));
         }
 
-        // Sanitize graph name
-        let sanitized_name = sanitize_graph_name(graph_name);
-
-        // Validate graph name after sanitization
-        if sanitized_name.is_empty() {
-            return Err(PyErr::new::<pyo3::exceptions::PyException, _>(
-                "Graph name contains only invalid characters",
-            ));
-        }
-
-        let query = format!("USE GRAPH {}", sanitized_name);
+        let query = format!("SESSION SET GRAPH {}", quote_identifier(graph_name));
         session.query(&query).map_err(|e| {
             PyErr::new::<pyo3::exceptions::PyException, _>(format!("Failed to use graph: {}", e))
         })?;
-        self.current_graph = Some(sanitized_name);
         Ok(())
     }
 
@@ -625,46 +626,145 @@ fn get_last_error_type(&self, e: &Bound<PyAny>) -> PyResult<String> {
 
 /// Extract a value from an Arrow array at a specific index
 fn extract_value_from_array(array: &ArrayRef, index: usize) -> PyResult<PyObject> {
-    Python::with_gil(|py| match array.data_type() {
-        DataType::Int32 => {
-            let arr = array.as_any().downcast_ref::<Int32Array>().unwrap();
-            if arr.is_null(index) {
-                Ok(py.None())
-            } else {
-                Ok(arr.value(index).into_pyobject(py)?.into_any().unbind())
-            }
+    let value = ScalarValue::from_array(array.as_ref(), index);
+    Python::with_gil(|py| scalar_value_to_py(py, value))
+}
+
+/// Convert a scalar value to a Python object, with nulls mapped to `None`
+fn scalar_value_to_py(py: Python<'_>, value: ScalarValue) -> PyResult<PyObject> {
+    if value.is_null() {
+        return Ok(py.None());
+    }
+    let object = match value {
+        ScalarValue::Boolean(Some(v)) => PyBool::new(py, v).to_owned().into_any(),
+        ScalarValue::Int8(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::Int16(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::Int32(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::Int64(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::UInt8(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::UInt16(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::UInt32(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::UInt64(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::Float32(Some(v)) => v.into_inner().into_pyobject(py)?.into_any(),
+        ScalarValue::Float64(Some(v)) => v.into_inner().into_pyobject(py)?.into_any(),
+        ScalarValue::String(Some(v)) => v.into_pyobject(py)?.into_any(),
+        ScalarValue::Vector { value: Some(v), .. } => PyList::new(py, v.to_f32_vec())?.into_any(),
+        // Graph elements are returned as dicts, with their properties in a nested dict.
+        ScalarValue::Vertex(Some(v)) => {
+            let dict = PyDict::new(py);
+            dict.set_item("id", v.id())?;
+            dict.set_item("label", v.label().get())?;
+            dict.set_item("properties", properties_to_py(py, v.properties())?)?;
+            dict.into_any()
         }
-        DataType::Utf8 => {
-            let arr = array.as_any().downcast_ref::<StringArray>().unwrap();
-            if arr.is_null(index) {
-                Ok(py.None())
-            } else {
-                Ok(arr.value(index).into_pyobject(py)?.into_any().unbind())
-            }
+        ScalarValue::Edge(Some(e)) => {
+            let dict = PyDict::new(py);
+            dict.set_item("id", e.id())?;
+            dict.set_item("label", e.label().get())?;
+            dict.set_item("src", e.src())?;
+            dict.set_item("dst", e.dst())?;
+            dict.set_item("properties", properties_to_py(py, e.properties())?)?;
+            dict.into_any()
         }
-        DataType::Boolean => {
-            let arr = array.as_any().downcast_ref::<BooleanArray>().unwrap();
-            if arr.is_null(index) {
-                Ok(py.None())
-            } else {
-                let value = pyo3::types::PyBool::new(py, arr.value(index));
-                Ok(value.into_pyobject(py).map(|v| {
-                    <pyo3::Bound<'_, PyBool> as Clone>::clone(&v)
-                        .into_any()
-                        .unbind()
-                })?)
-            }
+        ScalarValue::List(Some(values)) => {
+            let values = values
+                .into_iter()
+                .map(|v| scalar_value_to_py(py, v))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, values)?.into_any()
         }
-        DataType::Float64 => {
-            let arr = array.as_any().downcast_ref::<Float64Array>().unwrap();
-            if arr.is_null(index) {
-                Ok(py.None())
-            } else {
-                Ok(arr.value(index).into_pyobject(py)?.into_any().unbind())
+        ScalarValue::Record(Some(fields)) => properties_to_py(py, &fields)?.into_any(),
+        // Paths are returned as dicts of their vertices and edges, in the order they are gone
+        // through.
+        ScalarValue::Path(Some(p)) => {
+            let vertices = p
+                .vertices()
+                .iter()
+                .map(|v| scalar_value_to_py(py, v.clone().into()))
+                .collect::<PyResult<Vec<_>>>()?;
+            let edges = p
+                .edges()
+                .iter()
+                .map(|e| scalar_value_to_py(py, e.clone().into()))
+                .collect::<PyResult<Vec<_>>>()?;
+            let dict = PyDict::new(py);
+            dict.set_item("nodes", PyList::new(py, vertices)?)?;
+            dict.set_item("relationships", PyList::new(py, edges)?)?;
+            dict.into_any()
+        }
+        _ => unreachable!("null values should have been handled"),
+    };
+    Ok(object.unbind())
+}
+
+/// Convert the properties of a graph element, or the fields of a record, to a Python dict
+fn properties_to_py<'py>(
+    py: Python<'py>,
+    properties: &[PropertyValue],
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for property in properties {
+        dict.set_item(
+            property.name(),
+            scalar_value_to_py(py, property.value().clone())?,
+        )?;
+    }
+    Ok(dict)
+}
+
+/// Convert a query result into a Python dict with its schema, data and metrics
+fn query_result_to_py(py: Python<'_>, query_result: &QueryResult) -> PyResult<PyObject> {
+    // Convert QueryResult to Python dict
+    let dict = PyDict::new(py);
+
+    // Convert schema
+    let schema_list = PyList::empty(py);
+    if let Some(schema_ref) = query_result.schema() {
+        for field in schema_ref.fields() {
+            let field_dict = PyDict::new(py);
+            field_dict.set_item("name", field.name())?;
+            field_dict.set_item("data_type", format!("{:?}", field.ty()))?;
+            field_dict.set_item("metadata", field.metadata())?;
+            schema_list.append(field_dict)?;
+        }
+    }
+
+    dict.set_item("schema", schema_list)?;
+
+    // Convert data
+    let data_list = PyList::empty(py);
+    for chunk in query_result.iter() {
+        // Convert DataChunk to Python list of lists
+        let chunk_data = convert_data_chunk(chunk)?;
+        for row in chunk_data {
+            let row_list = PyList::empty(py);
+            for value in row {
+                row_list.append(value)?;
             }
+            data_list.append(row_list)?;
         }
-        _ => Ok(py.None()),
-    })
+    }
+
+    dict.set_item("data", data_list)?;
+
+    // Convert metrics
+    let metrics = query_result.metrics();
+    let metrics_dict = PyDict::new(py);
+    metrics_dict.set_item("parsing_time_ms", metrics.parsing_time().as_millis() as f64)?;
+    metrics_dict.set_item(
+        "planning_time_ms",
+        metrics.planning_time().as_millis() as f64,
+    )?;
+    metrics_dict.set_item(
+        "execution_time_ms",
+        metrics.execution_time().as_millis() as f64,
+    )?;
+    metrics_dict.set_item("chunks_produced", metrics.chunks_produced())?;
+    metrics_dict.set_item("bytes_scanned", metrics.bytes_scanned())?;
+
+    dict.set_item("metrics", metrics_dict)?;
+
+    Ok(dict.into())
 }
 
 /// Convert a DataChunk to a Python list of lists
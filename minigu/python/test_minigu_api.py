@@ -21,6 +21,7 @@ import unittest
 import asyncio
 import sys
 import os
+import tempfile
 
 # Add the python module to the path
 sys.path.insert(0, os.path.join(os.path.dirname(__file__)))
@@ -81,6 +82,65 @@ class TestMiniGUAPI(unittest.TestCase):
         # self.assertIsNotNone(result)
         pass
 
+    def test_execute_records(self):
+        """Test that records, such as the properties of vertices, are returned as dicts."""
+        self.db.execute("CALL create_test_graph_data('test', 3)")
+        result = self.db.execute("MATCH (n) RETURN properties(n)")
+        self.assertEqual(len(result), 3)
+        for row in result:
+            self.assertIsInstance(row[0], dict)
+
+    def test_execute_all(self):
+        """Test that a query with several statements returns a result set for each."""
+        results = self.db.execute_all("RETURN 1 AS a NEXT RETURN 2 AS b, 3 AS c")
+        self.assertEqual(len(results), 2)
+        self.assertEqual([field["name"] for field in results[0].schema], ["a"])
+        self.assertEqual(list(results[1]), [[2, 3]])
+
+    def test_progress_callback(self):
+        """Test that the progress callback is called every `interval` rows of an export."""
+        calls = []
+        self.db.set_progress_callback(lambda rows, secs: calls.append(rows), interval=3)
+        # 3 vertices and 6 edges
+        self.db.execute("CALL create_test_graph_data('test', 3)")
+        self.db.execute("SESSION SET GRAPH test")
+        with tempfile.TemporaryDirectory() as path:
+            self.assertTrue(self.db.save(path))
+        self.assertEqual(calls, [3, 6, 9])
+
+    def test_transaction_retry(self):
+        """Test retrying a unit of work on transaction conflicts."""
+        attempts = []
+
+        def work(db):
+            attempts.append(db)
+            if len(attempts) < 3:
+                raise minigu.TransactionError("conflict")
+            return "done"
+
+        self.assertEqual(self.db.transaction(work, retry=3), "done")
+        self.assertEqual(len(attempts), 3)
+
+    def test_transaction_retry_gives_up(self):
+        """Test that other errors and the last conflict are raised."""
+        attempts = []
+
+        def conflict(db):
+            attempts.append(db)
+            raise minigu.TransactionError("conflict")
+
+        with self.assertRaises(minigu.TransactionError):
+            self.db.transaction(conflict, retry=2)
+        self.assertEqual(len(attempts), 2)
+
+        def fail(db):
+            attempts.append(db)
+            raise ValueError("failed")
+
+        with self.assertRaises(ValueError):
+            self.db.transaction(fail, retry=5)
+        self.assertEqual(len(attempts), 3)
+
 # Only define async tests if we're on Python 3.8+
 if sys.version_info >= (3, 8):
     class TestAsyncMiniGUAPI(unittest.IsolatedAsyncioTestCase):
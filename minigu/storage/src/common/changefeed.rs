@@ -0,0 +1,100 @@
+//! Feeds of the modifications committed to a graph, e.g., to mirror them downstream.
+
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+
+use minigu_transaction::Timestamp;
+
+use super::DeltaOp;
+
+/// The modifications committed by a transaction.
+#[derive(Debug, Clone)]
+pub struct CommittedDeltas {
+    /// The commit timestamp of the transaction.
+    pub commit_ts: Timestamp,
+    /// The modifications, in the order they were made.
+    pub deltas: Vec<DeltaOp>,
+}
+
+/// The subscribers to the modifications committed to a graph.
+///
+/// Each subscriber receives the [`CommittedDeltas`] of every transaction committed after it
+/// subscribed, in commit order, through a channel buffering a bounded number of them. A commit
+/// waits for the buffers to have room, so a slow subscriber slows the commits down rather than
+/// missing modifications. A subscriber must thus not commit to the graph while its buffer is full.
+#[derive(Debug, Default)]
+pub struct Changefeed {
+    subscribers: Mutex<Vec<SyncSender<CommittedDeltas>>>,
+}
+
+impl Changefeed {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to the modifications committed from now on, buffering those of up to `capacity`
+    /// transactions until they are received.
+    ///
+    /// The subscription ends once the returned receiver is dropped.
+    pub fn subscribe(&self, capacity: usize) -> Receiver<CommittedDeltas> {
+        let (sender, receiver) = sync_channel(capacity);
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Returns whether anyone subscribed to the modifications, in which case they should be
+    /// [`publish`](Self::publish)ed.
+    pub fn has_subscribers(&self) -> bool {
+        !self.subscribers.lock().unwrap().is_empty()
+    }
+
+    /// Sends the modifications of the transaction committed at `commit_ts` to every subscriber,
+    /// waiting for their buffers to have room. The transactions without any modification are not
+    /// sent.
+    ///
+    /// Must be called in commit order.
+    pub fn publish(&self, commit_ts: Timestamp, deltas: Vec<DeltaOp>) {
+        if deltas.is_empty() {
+            return;
+        }
+        let committed = CommittedDeltas { commit_ts, deltas };
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(committed.clone()).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish() {
+        let changefeed = Changefeed::new();
+        let before = changefeed.subscribe(2);
+        changefeed.publish(Timestamp::with_ts(1), vec![DeltaOp::DelVertex(1)]);
+        let after = changefeed.subscribe(2);
+        // Transactions without modifications are skipped.
+        changefeed.publish(Timestamp::with_ts(2), vec![]);
+        changefeed.publish(Timestamp::with_ts(3), vec![DeltaOp::DelVertex(3)]);
+
+        let commit_ts = |receiver: &Receiver<CommittedDeltas>| {
+            receiver
+                .try_iter()
+                .map(|committed| committed.commit_ts.raw())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(commit_ts(&before), [1, 3]);
+        assert_eq!(commit_ts(&after), [3]);
+
+        // Dropped receivers are unsubscribed.
+        drop(before);
+        changefeed.publish(Timestamp::with_ts(4), vec![DeltaOp::DelVertex(4)]);
+        assert!(changefeed.has_subscribers());
+        drop(after);
+        changefeed.publish(Timestamp::with_ts(5), vec![DeltaOp::DelVertex(5)]);
+        assert!(!changefeed.has_subscribers());
+    }
+}
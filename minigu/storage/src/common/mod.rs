@@ -1,5 +1,8 @@
+pub mod changefeed;
 pub mod iterators;
 pub mod model;
+pub mod property_index;
+pub mod statistics;
 pub mod wal;
 
 // Re-export commonly used types
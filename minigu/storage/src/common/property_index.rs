@@ -0,0 +1,101 @@
+//! Equality indexes of the vertices of a label by the values of one of their properties, used to
+//! look up vertices by value instead of scanning every vertex of the label.
+
+use std::collections::{BTreeSet, HashMap};
+
+use dashmap::DashMap;
+use minigu_common::types::{LabelId, PropertyIndexKey, VertexId};
+use minigu_common::value::ScalarValue;
+
+/// The property indexes of a graph.
+///
+/// Like [`PropertyStatistics`](super::statistics::PropertyStatistics), indexes only ever grow:
+/// overwriting a property, deleting a vertex or aborting a transaction leaves the entries of the
+/// previous values untouched. A lookup thus returns every vertex which may hold a value, plus some
+/// which do not, and the vertices must be checked against the versions visible to a transaction.
+#[derive(Debug, Default)]
+pub struct PropertyIndexes {
+    indexes: DashMap<PropertyIndexKey, HashMap<ScalarValue, BTreeSet<VertexId>>>,
+}
+
+impl PropertyIndexes {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the empty index of `key`. Returns `false` if the index already exists.
+    pub fn create(&self, key: PropertyIndexKey) -> bool {
+        let mut created = false;
+        self.indexes.entry(key).or_insert_with(|| {
+            created = true;
+            HashMap::new()
+        });
+        created
+    }
+
+    /// Returns whether the index of `key` exists.
+    pub fn contains(&self, key: PropertyIndexKey) -> bool {
+        self.indexes.contains_key(&key)
+    }
+
+    /// Records that the property at `index` of vertex `vid` labeled `label_id` is set to `value`.
+    ///
+    /// Nulls are not indexed, since they are never equal to any value.
+    pub fn update(&self, label_id: LabelId, vid: VertexId, index: usize, value: &ScalarValue) {
+        if value.is_null() {
+            return;
+        }
+        let key = PropertyIndexKey::new(label_id, index as _);
+        if let Some(mut entries) = self.indexes.get_mut(&key) {
+            entries.entry(value.clone()).or_default().insert(vid);
+        }
+    }
+
+    /// Records that the properties of vertex `vid` labeled `label_id` are set to `values`.
+    pub fn update_all(&self, label_id: LabelId, vid: VertexId, values: &[ScalarValue]) {
+        for (index, value) in values.iter().enumerate() {
+            self.update(label_id, vid, index, value);
+        }
+    }
+
+    /// Returns the ids of the vertices which may hold `value` according to the index of `key`, in
+    /// ascending order, or `None` if the index does not exist.
+    pub fn get(&self, key: PropertyIndexKey, value: &ScalarValue) -> Option<Vec<VertexId>> {
+        let entries = self.indexes.get(&key)?;
+        Some(
+            entries
+                .get(value)
+                .map(|vids| vids.iter().copied().collect())
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(label_id: u32, property_id: u32) -> PropertyIndexKey {
+        PropertyIndexKey::new(LabelId::new(label_id).unwrap(), property_id)
+    }
+
+    #[test]
+    fn test_update() {
+        let indexes = PropertyIndexes::new();
+        assert!(indexes.create(key(1, 0)));
+        assert!(!indexes.create(key(1, 0)));
+        let label = LabelId::new(1).unwrap();
+        indexes.update_all(label, 1, &["alice".into(), ScalarValue::Int32(Some(30))]);
+        indexes.update_all(label, 2, &["bob".into(), ScalarValue::Int32(Some(30))]);
+        indexes.update(label, 3, 0, &ScalarValue::String(None));
+        // Overwritten values stay indexed.
+        indexes.update(label, 2, 0, &"alice".into());
+        assert_eq!(indexes.get(key(1, 0), &"alice".into()), Some(vec![1, 2]));
+        assert_eq!(indexes.get(key(1, 0), &"bob".into()), Some(vec![2]));
+        assert_eq!(indexes.get(key(1, 0), &"carol".into()), Some(vec![]));
+        // Only indexed properties are recorded.
+        assert!(!indexes.contains(key(1, 1)));
+        assert_eq!(indexes.get(key(1, 1), &ScalarValue::Int32(Some(30))), None);
+    }
+}
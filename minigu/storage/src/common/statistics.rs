@@ -0,0 +1,211 @@
+//! Per-label statistics of the vertices of a graph, used by the optimizer, e.g., to skip scans
+//! that cannot produce any row.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use dashmap::DashMap;
+use minigu_common::types::LabelId;
+use minigu_common::value::ScalarValue;
+
+/// The smallest and largest non-null values of a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyRange {
+    pub min: ScalarValue,
+    pub max: ScalarValue,
+}
+
+impl PropertyRange {
+    fn new(value: &ScalarValue) -> Self {
+        Self {
+            min: value.clone(),
+            max: value.clone(),
+        }
+    }
+
+    /// Widens the range to include `value`.
+    ///
+    /// Returns `false` if `value` cannot be ordered against the range.
+    fn extend(&mut self, value: &ScalarValue) -> bool {
+        let (Some(min), Some(max)) = (compare(value, &self.min), compare(value, &self.max)) else {
+            return false;
+        };
+        if min.is_lt() {
+            self.min = value.clone();
+        }
+        if max.is_gt() {
+            self.max = value.clone();
+        }
+        true
+    }
+}
+
+/// Min/max statistics of the properties of the vertices of each label.
+///
+/// Ranges only ever grow: overwriting a property, deleting a vertex or aborting a transaction
+/// leaves them untouched. A range may thus be wider than the values actually stored, but never
+/// narrower, so a value outside of it is guaranteed not to be stored.
+#[derive(Debug, Default)]
+pub struct PropertyStatistics {
+    /// `None` if some values of the property cannot be ordered (e.g., vectors).
+    ranges: DashMap<(LabelId, usize), Option<PropertyRange>>,
+}
+
+impl PropertyStatistics {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the property at `index` of a vertex labeled `label_id` is set to `value`.
+    pub fn update(&self, label_id: LabelId, index: usize, value: &ScalarValue) {
+        if value.is_null() {
+            return;
+        }
+        let ordered = compare(value, value).is_some();
+        self.ranges
+            .entry((label_id, index))
+            .and_modify(|range| {
+                if !range.as_mut().is_some_and(|r| r.extend(value)) {
+                    *range = None;
+                }
+            })
+            .or_insert_with(|| ordered.then(|| PropertyRange::new(value)));
+    }
+
+    /// Records that the properties of a vertex labeled `label_id` are set to `values`.
+    pub fn update_all(&self, label_id: LabelId, values: &[ScalarValue]) {
+        for (index, value) in values.iter().enumerate() {
+            self.update(label_id, index, value);
+        }
+    }
+
+    /// Returns the range of the property at `index` of the vertices labeled `label_id`.
+    ///
+    /// Returns `None` if the range is unknown, either because no non-null value has been recorded,
+    /// or because the values cannot be ordered.
+    pub fn get(&self, label_id: LabelId, index: usize) -> Option<PropertyRange> {
+        self.ranges.get(&(label_id, index))?.clone()
+    }
+}
+
+/// The number of vertices of each label of a graph, as counted by
+/// [`MemoryGraph::analyze`](crate::tp::MemoryGraph::analyze).
+///
+/// Unlike property ranges, the counts are not kept up to date: they are as of the analysis.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VertexCounts {
+    total: u64,
+    by_label: HashMap<LabelId, u64>,
+}
+
+impl VertexCounts {
+    /// Counts a vertex labeled `label_id`.
+    pub fn add(&mut self, label_id: LabelId) {
+        self.total += 1;
+        *self.by_label.entry(label_id).or_default() += 1;
+    }
+
+    /// Returns the number of vertices.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the number of vertices labeled `label_id`.
+    pub fn get(&self, label_id: LabelId) -> u64 {
+        self.by_label.get(&label_id).copied().unwrap_or(0)
+    }
+}
+
+/// Compares two non-null values of the same type.
+fn compare(lhs: &ScalarValue, rhs: &ScalarValue) -> Option<Ordering> {
+    use ScalarValue::*;
+    match (lhs, rhs) {
+        (Boolean(Some(l)), Boolean(Some(r))) => Some(l.cmp(r)),
+        (Int8(Some(l)), Int8(Some(r))) => Some(l.cmp(r)),
+        (Int16(Some(l)), Int16(Some(r))) => Some(l.cmp(r)),
+        (Int32(Some(l)), Int32(Some(r))) => Some(l.cmp(r)),
+        (Int64(Some(l)), Int64(Some(r))) => Some(l.cmp(r)),
+        (UInt8(Some(l)), UInt8(Some(r))) => Some(l.cmp(r)),
+        (UInt16(Some(l)), UInt16(Some(r))) => Some(l.cmp(r)),
+        (UInt32(Some(l)), UInt32(Some(r))) => Some(l.cmp(r)),
+        (UInt64(Some(l)), UInt64(Some(r))) => Some(l.cmp(r)),
+        // NaN is the largest value of `OrderedFloat`, so ranges of floats stay conservative.
+        (Float32(Some(l)), Float32(Some(r))) => Some(l.cmp(r)),
+        (Float64(Some(l)), Float64(Some(r))) => Some(l.cmp(r)),
+        (String(Some(l)), String(Some(r))) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(id: u32) -> LabelId {
+        LabelId::new(id).unwrap()
+    }
+
+    #[test]
+    fn test_update() {
+        let stats = PropertyStatistics::new();
+        for age in [30, 10, 20] {
+            stats.update(label(1), 0, &ScalarValue::Int32(Some(age)));
+        }
+        stats.update(label(1), 0, &ScalarValue::Int32(None));
+        stats.update(label(2), 0, &ScalarValue::Int32(Some(100)));
+        assert_eq!(
+            stats.get(label(1), 0),
+            Some(PropertyRange {
+                min: ScalarValue::Int32(Some(10)),
+                max: ScalarValue::Int32(Some(30)),
+            })
+        );
+        assert_eq!(
+            stats.get(label(2), 0).unwrap().min,
+            ScalarValue::Int32(Some(100))
+        );
+        assert_eq!(stats.get(label(1), 1), None);
+    }
+
+    #[test]
+    fn test_update_all() {
+        let stats = PropertyStatistics::new();
+        stats.update_all(
+            label(1),
+            &[
+                ScalarValue::String(Some("b".into())),
+                ScalarValue::new_vector(1, None),
+            ],
+        );
+        stats.update_all(label(1), &[ScalarValue::String(Some("a".into()))]);
+        let range = stats.get(label(1), 0).unwrap();
+        assert_eq!(range.min, ScalarValue::String(Some("a".into())));
+        assert_eq!(range.max, ScalarValue::String(Some("b".into())));
+        // Only nulls have been recorded.
+        assert_eq!(stats.get(label(1), 1), None);
+    }
+
+    #[test]
+    fn test_vertex_counts() {
+        let mut counts = VertexCounts::default();
+        for id in [1, 2, 1] {
+            counts.add(label(id));
+        }
+        assert_eq!(counts.total(), 3);
+        assert_eq!(counts.get(label(1)), 2);
+        assert_eq!(counts.get(label(2)), 1);
+        assert_eq!(counts.get(label(3)), 0);
+    }
+
+    #[test]
+    fn test_unordered_values() {
+        let stats = PropertyStatistics::new();
+        stats.update(label(1), 0, &ScalarValue::Int32(Some(1)));
+        stats.update(label(1), 0, &ScalarValue::Int64(Some(2)));
+        assert_eq!(stats.get(label(1), 0), None);
+        // The range stays unknown.
+        stats.update(label(1), 0, &ScalarValue::Int32(Some(3)));
+        assert_eq!(stats.get(label(1), 0), None);
+    }
+}
@@ -23,6 +23,7 @@
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 use crc32fast::Hasher;
 use minigu_transaction::{IsolationLevel, Timestamp};
@@ -63,10 +64,59 @@ fn from_bytes(bytes: Vec<u8>) -> StorageResult<Self> {
     }
 }
 
+/// The file a [`GraphWal`] is written to.
+///
+/// Implemented by [`File`], and by in-memory files in tests, e.g., to count fsyncs.
+pub trait WalFile: Read + Write + Seek + Send + Sync {
+    /// Truncates or extends the file to `size` bytes.
+    fn set_len(&self, size: u64) -> io::Result<()>;
+
+    /// Forces the data written to the file to disk.
+    fn sync_data(&self) -> io::Result<()>;
+
+    /// Returns another handle to the file, e.g., to read it without moving the write position.
+    fn try_clone(&self) -> io::Result<Box<dyn WalFile>>;
+}
+
+impl WalFile for File {
+    fn set_len(&self, size: u64) -> io::Result<()> {
+        File::set_len(self, size)
+    }
+
+    fn sync_data(&self) -> io::Result<()> {
+        File::sync_data(self)
+    }
+
+    fn try_clone(&self) -> io::Result<Box<dyn WalFile>> {
+        Ok(Box::new(File::try_clone(self)?))
+    }
+}
+
+/// When committing a transaction forces the WAL to disk.
+///
+/// With any policy other than [`FsyncPolicy::Always`], the records of a committed transaction may
+/// only have reached the OS: they survive a crash of the process, but not of the machine (e.g., a
+/// power loss), in which case the transactions committed since the last fsync are lost on recovery.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Fsync on every commit. Committed transactions are never lost.
+    #[default]
+    Always,
+    /// Fsync on every `n`-th commit, so up to `n - 1` committed transactions can be lost.
+    EveryN(u64),
+    /// Fsync on the first commit at least this many milliseconds after the previous fsync, so the
+    /// transactions committed within the last interval can be lost.
+    Interval(u64),
+}
+
 /// Write‑ahead log in append‑only mode, tailored for an in‑memory graph store.
 pub struct GraphWal {
-    pub file: BufWriter<File>,
+    pub file: BufWriter<Box<dyn WalFile>>,
     pub path: PathBuf,
+    fsync_policy: FsyncPolicy,
+    /// The number of commits since the last fsync.
+    unsynced_commits: u64,
+    last_sync: Instant,
 }
 
 impl StorageWal for GraphWal {
@@ -93,10 +143,7 @@ fn open<P: AsRef<Path>>(path: P) -> StorageResult<Self> {
         file.seek(SeekFrom::End(0))
             .map_err(|e| StorageError::Wal(WalError::Io(e)))?;
 
-        Ok(Self {
-            file: BufWriter::new(file),
-            path: path.as_ref().to_path_buf(),
-        })
+        Ok(Self::from_file(path, Box::new(file)))
     }
 
     /// Append a record and buffer it. Call `flush` to fsync.
@@ -162,7 +209,10 @@ fn flush(&mut self) -> StorageResult<()> {
         self.file
             .get_ref()
             .sync_data()
-            .map_err(|e| StorageError::Wal(WalError::Io(e)))
+            .map_err(|e| StorageError::Wal(WalError::Io(e)))?;
+        self.unsynced_commits = 0;
+        self.last_sync = Instant::now();
+        Ok(())
     }
 
     /// Returns an iterator over WAL (Write-Ahead Log) records in the file.
@@ -269,6 +319,40 @@ fn read_all(&self) -> StorageResult<Vec<Self::Record>> {
 }
 
 impl GraphWal {
+    /// Creates a WAL written to `file`, which is located at `path`, from its current position.
+    pub fn from_file<P: AsRef<Path>>(path: P, file: Box<dyn WalFile>) -> Self {
+        Self {
+            file: BufWriter::new(file),
+            path: path.as_ref().to_path_buf(),
+            fsync_policy: FsyncPolicy::default(),
+            unsynced_commits: 0,
+            last_sync: Instant::now(),
+        }
+    }
+
+    pub fn with_fsync_policy(mut self, fsync_policy: FsyncPolicy) -> Self {
+        self.fsync_policy = fsync_policy;
+        self
+    }
+
+    /// Makes the records of a committed transaction visible to the OS, and forces them to disk if
+    /// the fsync policy requires it.
+    pub fn commit(&mut self) -> StorageResult<()> {
+        self.unsynced_commits += 1;
+        let sync = match self.fsync_policy {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::EveryN(n) => self.unsynced_commits >= n,
+            FsyncPolicy::Interval(ms) => self.last_sync.elapsed() >= Duration::from_millis(ms),
+        };
+        if sync {
+            self.flush()
+        } else {
+            self.file
+                .flush()
+                .map_err(|e| StorageError::Wal(WalError::Io(e)))
+        }
+    }
+
     /// Truncates the WAL (Write-Ahead Log) file to remove entries with LSN less than `min_lsn`.
     ///
     /// This is typically used during log compaction or checkpointing, to discard
@@ -317,9 +401,24 @@ pub fn truncate_until(&mut self, min_lsn: u64) -> StorageResult<()> {
     }
 }
 
+/// Whether committed transactions survive a restart or a crash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Durability {
+    /// Nothing is written to the WAL, and no WAL file is even created. Transactions keep their
+    /// MVCC semantics, but a graph can never be recovered past its latest checkpoint: every change
+    /// made since then is lost once the process exits. Meant for throwaway graphs, e.g., in tests
+    /// or caches.
+    None,
+    /// Every transaction is logged to the WAL and flushed when it commits or aborts.
+    #[default]
+    Wal,
+}
+
 #[derive(Debug, Clone)]
 pub struct WalManagerConfig {
     pub wal_path: PathBuf,
+    pub durability: Durability,
+    pub fsync_policy: FsyncPolicy,
 }
 
 fn default_wal_path() -> PathBuf {
@@ -331,12 +430,15 @@ impl Default for WalManagerConfig {
     fn default() -> Self {
         Self {
             wal_path: default_wal_path(),
+            durability: Durability::default(),
+            fsync_policy: FsyncPolicy::default(),
         }
     }
 }
 
 pub struct WalManager {
-    pub(super) wal: Arc<RwLock<GraphWal>>,
+    /// `None` if the durability is [`Durability::None`].
+    pub(super) wal: Option<Arc<RwLock<GraphWal>>>,
     pub(super) next_lsn: AtomicU64,
     pub(super) wal_path: PathBuf,
 }
@@ -344,8 +446,17 @@ pub struct WalManager {
 impl WalManager {
     pub fn new(config: WalManagerConfig) -> Self {
         let path = config.wal_path;
+        let wal = match config.durability {
+            Durability::None => None,
+            Durability::Wal => {
+                let wal = GraphWal::open(&path)
+                    .unwrap()
+                    .with_fsync_policy(config.fsync_policy);
+                Some(Arc::new(RwLock::new(wal)))
+            }
+        };
         Self {
-            wal: Arc::new(RwLock::new(GraphWal::open(&path).unwrap())),
+            wal,
             next_lsn: AtomicU64::new(0),
             wal_path: path.to_path_buf(),
         }
@@ -359,12 +470,56 @@ pub fn set_next_lsn(&self, lsn: u64) {
         self.next_lsn.store(lsn, Ordering::SeqCst);
     }
 
-    pub fn wal(&self) -> &Arc<RwLock<GraphWal>> {
-        &self.wal
+    /// Returns the WAL, or `None` if nothing is logged.
+    pub fn wal(&self) -> Option<&Arc<RwLock<GraphWal>>> {
+        self.wal.as_ref()
+    }
+
+    pub fn durability(&self) -> Durability {
+        if self.wal.is_some() {
+            Durability::Wal
+        } else {
+            Durability::None
+        }
+    }
+
+    /// Appends `entry` to the WAL, if any.
+    pub fn append(&self, entry: &RedoEntry) -> StorageResult<()> {
+        match &self.wal {
+            Some(wal) => wal.write().unwrap().append(entry),
+            None => Ok(()),
+        }
+    }
+
+    /// Flushes the WAL, if any.
+    pub fn flush(&self) -> StorageResult<()> {
+        match &self.wal {
+            Some(wal) => wal.write().unwrap().flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Flushes the WAL, if any, after a transaction is committed, following its fsync policy.
+    pub fn commit(&self) -> StorageResult<()> {
+        match &self.wal {
+            Some(wal) => wal.write().unwrap().commit(),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads every entry of the WAL, which has none if nothing is logged.
+    pub fn read_all(&self) -> StorageResult<Vec<RedoEntry>> {
+        match &self.wal {
+            Some(wal) => wal.read().unwrap().read_all(),
+            None => Ok(vec![]),
+        }
     }
 
     pub fn truncate_until(&self, lsn: u64) -> StorageResult<()> {
-        self.wal.write().unwrap().truncate_until(lsn)
+        match &self.wal {
+            Some(wal) => wal.write().unwrap().truncate_until(lsn),
+            None => Ok(()),
+        }
     }
 
     pub fn path(&self) -> &Path {
@@ -375,7 +530,9 @@ pub fn path(&self) -> &Path {
 #[cfg(test)]
 mod tests {
     use std::fs;
+    use std::io::Cursor;
     use std::path::PathBuf;
+    use std::sync::atomic::AtomicUsize;
 
     use minigu_common::value::ScalarValue;
     use serial_test::serial;
@@ -739,4 +896,98 @@ fn test_truncate_until() {
 
         cleanup(&path);
     }
+
+    /// An in-memory file counting its fsyncs.
+    struct MockFile {
+        data: Cursor<Vec<u8>>,
+        syncs: Arc<AtomicUsize>,
+    }
+
+    impl Read for MockFile {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.data.read(buf)
+        }
+    }
+
+    impl Write for MockFile {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.data.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for MockFile {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.data.seek(pos)
+        }
+    }
+
+    impl WalFile for MockFile {
+        fn set_len(&self, _size: u64) -> io::Result<()> {
+            unimplemented!()
+        }
+
+        fn sync_data(&self) -> io::Result<()> {
+            self.syncs.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn try_clone(&self) -> io::Result<Box<dyn WalFile>> {
+            Ok(Box::new(MockFile {
+                data: Cursor::new(self.data.get_ref().clone()),
+                syncs: self.syncs.clone(),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_fsync_every_n() {
+        let syncs = Arc::new(AtomicUsize::new(0));
+        let file = MockFile {
+            data: Cursor::new(vec![]),
+            syncs: syncs.clone(),
+        };
+        let mut wal = GraphWal::from_file("mock.wal", Box::new(file))
+            .with_fsync_policy(FsyncPolicy::EveryN(10));
+        for lsn in 0..35 {
+            let entry = RedoEntry {
+                lsn,
+                txn_id: Timestamp::with_ts(100 + lsn),
+                iso_level: IsolationLevel::Serializable,
+                op: Operation::CommitTransaction(Timestamp::with_ts(100 + lsn)),
+            };
+            wal.append(&entry).unwrap();
+            wal.commit().unwrap();
+        }
+        assert_eq!(syncs.load(Ordering::SeqCst), 3);
+        // Every record reached the file, synced or not.
+        assert_eq!(wal.read_all().unwrap().len(), 35);
+
+        // An explicit flush always syncs, and restarts the count.
+        wal.flush().unwrap();
+        assert_eq!(syncs.load(Ordering::SeqCst), 4);
+        for _ in 0..9 {
+            wal.commit().unwrap();
+        }
+        assert_eq!(syncs.load(Ordering::SeqCst), 4);
+        wal.commit().unwrap();
+        assert_eq!(syncs.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_fsync_always() {
+        let syncs = Arc::new(AtomicUsize::new(0));
+        let file = MockFile {
+            data: Cursor::new(vec![]),
+            syncs: syncs.clone(),
+        };
+        let mut wal = GraphWal::from_file("mock.wal", Box::new(file));
+        for _ in 0..3 {
+            wal.commit().unwrap();
+        }
+        assert_eq!(syncs.load(Ordering::SeqCst), 3);
+    }
 }
@@ -1,6 +1,7 @@
 use std::io;
 use std::num::NonZeroU32;
 
+use minigu_common::types::PropertyIndexKey;
 use minigu_transaction::TimestampError;
 use thiserror::Error;
 pub type StorageResult<T> = Result<T, StorageError>;
@@ -21,10 +22,30 @@ pub enum StorageError {
     Checkpoint(#[from] CheckpointError),
     #[error("Vector index error: {0}")]
     VectorIndex(#[from] VectorIndexError),
+    #[error("Property index not found: {0:?}")]
+    PropertyIndexNotFound(PropertyIndexKey),
     #[error("Feature not supported: {0}")]
     NotSupported(String),
 }
 
+impl StorageError {
+    /// Returns whether the error is caused by a conflict with a concurrent transaction, in which
+    /// case the failed transaction may succeed if it is run again.
+    pub fn is_serialization_conflict(&self) -> bool {
+        matches!(
+            self,
+            StorageError::Transaction(
+                TransactionError::WriteReadConflict(_)
+                    | TransactionError::ReadWriteConflict(_)
+                    | TransactionError::WriteWriteConflict(_)
+                    | TransactionError::VersionNotVisible(_)
+                    | TransactionError::Deadlock(_)
+                    | TransactionError::LockWaitTimeout(_)
+            )
+        )
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum WalError {
     #[error("IO error: {0}")]
@@ -51,10 +72,20 @@ pub enum TransactionError {
     VersionNotVisible(String),
     #[error("Transaction not found: {0}")]
     TransactionNotFound(String),
+    #[error("Savepoint not found: {0}")]
+    SavepointNotFound(String),
+    #[error("Read-only transaction: {0}")]
+    ReadOnly(String),
+    #[error("Deadlock: {0}")]
+    Deadlock(String),
+    #[error("Lock wait timeout: {0}")]
+    LockWaitTimeout(String),
     #[error("Transaction already committed: {0}")]
     TransactionAlreadyCommitted(String),
     #[error("Invalid state: {0}")]
     InvalidState(String),
+    #[error("Commit rejected: {0}")]
+    CommitRejected(String),
     #[error("Timestamp error: {0}")]
     Timestamp(#[from] TimestampError),
 }
@@ -105,6 +136,8 @@ pub enum CheckpointError {
     DirectoryError(String),
     #[error("Timeout waiting for active transactions to complete")]
     Timeout,
+    #[error("Invalid recovery target: {0}")]
+    InvalidRecoveryTarget(String),
 }
 
 #[derive(Error, Debug)]
@@ -7,4 +7,4 @@
 pub mod error;
 pub mod tp;
 
-pub use common::{iterators, model, wal};
+pub use common::{iterators, model, statistics, wal};
@@ -5,13 +5,15 @@
 // A checkpoint represents a consistent snapshot of the graph state at a specific point in time.
 // It can be used for backup, recovery, or state transfer purposes.
 
-use std::collections::HashMap;
-use std::env;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, thread};
 
 use crc32fast::Hasher;
 use minigu_common::types::{EdgeId, VertexId};
@@ -22,8 +24,7 @@
 use super::memory_graph::{AdjacencyContainer, MemoryGraph, VersionedEdge, VersionedVertex};
 use crate::common::model::edge::{Edge, Neighbor};
 use crate::common::model::vertex::Vertex;
-use crate::common::wal::StorageWal;
-use crate::common::wal::graph_wal::WalManagerConfig;
+use crate::common::wal::graph_wal::{Durability, Operation, RedoEntry, WalManagerConfig};
 use crate::error::{CheckpointError, StorageError, StorageResult};
 
 // @TODO: Consider making this configurable via
@@ -319,6 +320,11 @@ pub fn restore(
             current.commit_ts = serialized_vertex.commit_ts;
             drop(current);
 
+            graph.statistics.update_all(
+                serialized_vertex.data.label_id,
+                serialized_vertex.data.properties(),
+            );
+            graph.reserve_vertex_id(*vid);
             graph.vertices.insert(*vid, versioned_vertex);
         }
 
@@ -446,6 +452,9 @@ pub struct CheckpointManager {
     // Lock to ensure no transaction is trying to
     // update the graph while we are creating a checkpoint
     pub(super) checkpoint_lock: RwLock<()>,
+
+    // Lock serializing checkpoint creation between callers and the background checkpointer
+    pub(super) manager_lock: Mutex<()>,
 }
 
 impl CheckpointManager {
@@ -461,6 +470,7 @@ pub fn new(graph: Arc<MemoryGraph>, config: CheckpointManagerConfig) -> StorageR
             checkpoints: HashMap::new(),
             last_auto_checkpoint: None,
             checkpoint_lock: RwLock::new(()),
+            manager_lock: Mutex::new(()),
         };
 
         // Load existing checkpoints
@@ -545,10 +555,15 @@ pub fn create_checkpoint(&mut self, description: Option<String>) -> StorageResul
             // Create a new checkpoint
             checkpoint = GraphCheckpoint::new(&self.graph);
 
-            // Truncate the WAL, keeping only entries after the checkpoint's LSN
-            self.graph
-                .wal_manager
-                .truncate_until(checkpoint.metadata.lsn)?;
+            // Truncate the WAL, keeping the entries after the previous checkpoint's LSN so that
+            // recovery can still fall back to it if the new checkpoint file gets corrupted
+            let retained_lsn = self
+                .checkpoints
+                .values()
+                .map(|entry| entry.metadata.lsn)
+                .max()
+                .unwrap_or(checkpoint.metadata.lsn);
+            self.graph.wal_manager.truncate_until(retained_lsn)?;
         }
 
         // Generate a unique ID for the checkpoint
@@ -575,6 +590,9 @@ pub fn create_checkpoint(&mut self, description: Option<String>) -> StorageResul
 
         self.checkpoints.insert(id.clone(), entry);
 
+        // Pack the adjacency lists modified since the previous checkpoint
+        self.graph.compact_adjacency();
+
         // Update last auto checkpoint time
         self.last_auto_checkpoint = Some(SystemTime::now());
 
@@ -701,7 +719,8 @@ impl MemoryGraph {
     ///
     /// 1. **Checkpoint-based Recovery**   If a valid checkpoint exists in the configured directory,
     ///    the graph is restored from it, and all WAL entries with LSN ≥ checkpoint LSN are applied
-    ///    to reach the latest consistent state.
+    ///    to reach the latest consistent state. Checkpoints are tried from newest to oldest; one
+    ///    that is truncated or fails its checksum is skipped in favor of the previous one.
     ///
     /// 2. **WAL-only Recovery**   If no checkpoint is found, the graph is initialized empty and
     ///    recovered solely from WAL entries.
@@ -713,28 +732,88 @@ impl MemoryGraph {
     pub fn recover_from_checkpoint_and_wal(
         checkpoint_config: CheckpointManagerConfig,
         wal_config: WalManagerConfig,
+    ) -> StorageResult<Arc<Self>> {
+        Self::recover(checkpoint_config, wal_config, None)
+    }
+
+    /// Recovers a [`MemoryGraph`] to its state as of the commit timestamp `until` (inclusive).
+    ///
+    /// This works like [`MemoryGraph::recover_from_checkpoint_and_wal`], except that:
+    /// - checkpoints containing commits after `until` are skipped, and
+    /// - only the WAL entries of transactions committed at or before `until` are replayed.
+    ///
+    /// This allows undoing an accidental bad write, as long as the WAL still covers the range
+    /// between the chosen checkpoint (or the beginning of the log) and `until`. Fails if `until` is
+    /// past the latest commit found in the checkpoints and the WAL, or if it is older than every
+    /// checkpoint, since the WAL is truncated when a checkpoint is created.
+    ///
+    /// Note that the WAL itself is left untouched: it still contains the entries committed after
+    /// `until`, so a subsequent regular recovery from the same WAL would replay them again.
+    pub fn recover_to_timestamp(
+        checkpoint_config: CheckpointManagerConfig,
+        wal_config: WalManagerConfig,
+        until: Timestamp,
+    ) -> StorageResult<Arc<Self>> {
+        Self::recover(checkpoint_config, wal_config, Some(until))
+    }
+
+    fn recover(
+        checkpoint_config: CheckpointManagerConfig,
+        wal_config: WalManagerConfig,
+        until: Option<Timestamp>,
     ) -> StorageResult<Arc<Self>> {
         // Create checkpoint directory if it doesn't exist
         fs::create_dir_all(&checkpoint_config.checkpoint_dir)
             .map_err(|e| StorageError::Checkpoint(CheckpointError::Io(e)))?;
 
-        // Find the most recent checkpoint
-        let checkpoint_path = Self::find_most_recent_checkpoint(&checkpoint_config)?;
+        // Find the most recent checkpoint that can be loaded
+        let mut checkpoint = None;
+        let mut latest_checkpoint_ts = None;
+        let mut skipped_newer_checkpoint = false;
+        for path in Self::find_checkpoints_newest_first(&checkpoint_config)? {
+            match GraphCheckpoint::load_from_file(&path) {
+                Ok(loaded) => {
+                    latest_checkpoint_ts =
+                        latest_checkpoint_ts.max(Some(loaded.metadata.latest_commit_ts));
+                    // Skip checkpoints taken after the recovery target
+                    if until.is_some_and(|ts| loaded.metadata.latest_commit_ts > ts.raw()) {
+                        skipped_newer_checkpoint = true;
+                        continue;
+                    }
+                    checkpoint = Some(loaded);
+                    break;
+                }
+                Err(e) => {
+                    // Log error and fall back to the previous checkpoint
+                    eprintln!("Skipping invalid checkpoint at {:?}: {:?}", path, e);
+                }
+            }
+        }
 
         // If no checkpoint found, create a new empty graph
-        if checkpoint_path.is_none() {
-            let graph = Self::with_config_fresh(checkpoint_config.clone(), wal_config.clone());
-            graph.recover_from_wal()?;
+        let Some(checkpoint) = checkpoint else {
+            // The WAL preceding the oldest checkpoint has been truncated when it was created
+            if skipped_newer_checkpoint {
+                return Err(StorageError::Checkpoint(
+                    CheckpointError::InvalidRecoveryTarget(format!(
+                        "commit timestamp {} is older than every checkpoint, and the WAL before \
+                         them has been truncated",
+                        until.map_or(0, |ts| ts.raw())
+                    )),
+                ));
+            }
+            let graph = Self::with_config_fresh(checkpoint_config, wal_config);
+            let entries = graph.wal_manager.read_all()?;
+            graph.replay_wal_until(entries, until, latest_checkpoint_ts)?;
             return Ok(graph);
-        }
+        };
 
         // Restore from checkpoint
-        let checkpoint = GraphCheckpoint::load_from_file(checkpoint_path.unwrap())?;
         let checkpoint_lsn = checkpoint.metadata.lsn;
         let graph = checkpoint.restore(checkpoint_config, wal_config)?;
 
         // Read WAL entries with LSN >= checkpoint_lsn
-        let all_entries = graph.wal_manager.wal().read().unwrap().read_all()?;
+        let all_entries = graph.wal_manager.read_all()?;
 
         let new_entries: Vec<_> = all_entries
             .into_iter()
@@ -742,30 +821,80 @@ pub fn recover_from_checkpoint_and_wal(
             .collect();
 
         // Apply new WAL entries
-        if !new_entries.is_empty() {
-            graph.apply_wal_entries(new_entries)?;
-        }
+        graph.replay_wal_until(new_entries, until, latest_checkpoint_ts)?;
 
         Ok(graph)
     }
 
-    /// Finds the most recent checkpoint in the checkpoint directory
-    fn find_most_recent_checkpoint(
-        config: &CheckpointManagerConfig,
-    ) -> StorageResult<Option<PathBuf>> {
-        let entries = match fs::read_dir(&config.checkpoint_dir) {
-            Ok(entries) => entries,
-            Err(e) => return Err(StorageError::Checkpoint(CheckpointError::Io(e))),
+    /// Applies `entries`, restricted to the transactions committed at or before `until` if given.
+    ///
+    /// `until` must not be past the latest durable commit, i.e., the latest commit of `entries` or
+    /// `latest_checkpoint_ts`, since the state as of `until` is not known beyond it.
+    fn replay_wal_until(
+        self: &Arc<Self>,
+        entries: Vec<RedoEntry>,
+        until: Option<Timestamp>,
+        latest_checkpoint_ts: Option<u64>,
+    ) -> StorageResult<()> {
+        let Some(until) = until else {
+            if !entries.is_empty() {
+                self.apply_wal_entries(entries)?;
+            }
+            return Ok(());
         };
 
-        let mut latest_checkpoint: Option<(PathBuf, SystemTime)> = None;
+        let latest_commit_ts = entries
+            .iter()
+            .filter_map(|entry| match entry.op {
+                Operation::CommitTransaction(commit_ts) => Some(commit_ts.raw()),
+                _ => None,
+            })
+            .chain(latest_checkpoint_ts)
+            .max();
+        if latest_commit_ts.is_none_or(|ts| ts < until.raw()) {
+            return Err(StorageError::Checkpoint(
+                CheckpointError::InvalidRecoveryTarget(format!(
+                    "commit timestamp {} is past the latest durable commit",
+                    until.raw()
+                )),
+            ));
+        }
 
-        for entry in entries {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(e) => return Err(StorageError::Checkpoint(CheckpointError::Io(e))),
-            };
+        // Skipped entries must not have their LSNs reused by new writes
+        let next_lsn = entries.iter().map(|entry| entry.lsn + 1).max();
+
+        let committed: HashSet<Timestamp> = entries
+            .iter()
+            .filter_map(|entry| match entry.op {
+                Operation::CommitTransaction(commit_ts) if commit_ts <= until => Some(entry.txn_id),
+                _ => None,
+            })
+            .collect();
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| committed.contains(&entry.txn_id))
+            .collect();
+        if !entries.is_empty() {
+            self.apply_wal_entries(entries)?;
+        }
+
+        if let Some(next_lsn) = next_lsn {
+            self.wal_manager.set_next_lsn(next_lsn);
+        }
+        Ok(())
+    }
+
+    /// Lists the checkpoints in the checkpoint directory, most recently modified first
+    fn find_checkpoints_newest_first(
+        config: &CheckpointManagerConfig,
+    ) -> StorageResult<Vec<PathBuf>> {
+        let entries = fs::read_dir(&config.checkpoint_dir)
+            .map_err(|e| StorageError::Checkpoint(CheckpointError::Io(e)))?;
+
+        let mut checkpoints: Vec<(PathBuf, SystemTime)> = Vec::new();
 
+        for entry in entries {
+            let entry = entry.map_err(|e| StorageError::Checkpoint(CheckpointError::Io(e)))?;
             let path = entry.path();
 
             // Skip non-files and files that don't start with our prefix
@@ -780,33 +909,22 @@ fn find_most_recent_checkpoint(
             }
 
             // Get file metadata to check modification time
-            let metadata = match fs::metadata(&path) {
-                Ok(metadata) => metadata,
-                Err(e) => return Err(StorageError::Checkpoint(CheckpointError::Io(e))),
-            };
-
-            let modified = match metadata.modified() {
-                Ok(time) => time,
-                Err(e) => return Err(StorageError::Checkpoint(CheckpointError::Io(e))),
-            };
-
-            // Update latest checkpoint if this one is newer
-            if let Some((_, latest_time)) = &latest_checkpoint {
-                if modified > *latest_time {
-                    latest_checkpoint = Some((path, modified));
-                }
-            } else {
-                latest_checkpoint = Some((path, modified));
-            }
+            let modified = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| StorageError::Checkpoint(CheckpointError::Io(e)))?;
+
+            checkpoints.push((path, modified));
         }
 
-        Ok(latest_checkpoint.map(|(path, _)| path))
+        checkpoints.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Ok(checkpoints.into_iter().map(|(path, _)| path).collect())
     }
 
     /// Creates a checkpoint using the checkpoint manager
     pub fn create_managed_checkpoint(&self, description: Option<String>) -> StorageResult<String> {
         match &self.checkpoint_manager {
             Some(manager) => {
+                let _guard = manager.manager_lock.lock().unwrap();
                 // Need to get a mutable reference to the manager
                 // This is safe because we're only modifying the manager's internal state
                 let manager_ptr = manager as *const CheckpointManager as *mut CheckpointManager;
@@ -824,6 +942,7 @@ pub fn create_managed_checkpoint(&self, description: Option<String>) -> StorageR
     pub fn check_auto_checkpoint(&self) -> StorageResult<Option<String>> {
         match &self.checkpoint_manager {
             Some(manager) => {
+                let _guard = manager.manager_lock.lock().unwrap();
                 // Need to get a mutable reference to the manager
                 // This is safe because we're only modifying the manager's internal state
                 let manager_ptr = manager as *const CheckpointManager as *mut CheckpointManager;
@@ -832,11 +951,90 @@ pub fn check_auto_checkpoint(&self) -> StorageResult<Option<String>> {
             None => Ok(None), // No checkpoint manager, so no auto checkpoint
         }
     }
+
+    /// Starts a background thread that periodically calls [`MemoryGraph::check_auto_checkpoint`],
+    /// until the returned [`Checkpointer`] is dropped.
+    ///
+    /// Returns `None` if `auto_checkpoint_interval_secs` is 0 or the graph has no WAL, i.e., its
+    /// durability is [`Durability::None`]. Each checkpoint is taken under the checkpoint lock after
+    /// active transactions have finished, so it is a consistent snapshot; old checkpoints are
+    /// pruned according to `max_checkpoints`. Since the checkpoints are kept in the configured
+    /// directory, each graph should be given a directory of its own.
+    pub fn start_checkpointer(self: &Arc<Self>) -> Option<Checkpointer> {
+        let interval_secs = self
+            .checkpoint_manager
+            .as_ref()?
+            .config
+            .auto_checkpoint_interval_secs;
+        if interval_secs == 0 || self.wal_manager.durability() == Durability::None {
+            return None;
+        }
+        // Poll more often than the interval so that checkpoints are not delayed by a whole
+        // interval when the previous one finished slightly late.
+        let poll_interval = Duration::from_secs(interval_secs) / 4;
+        let (stop, stopped) = mpsc::channel();
+        let thread = Self::spawn_checkpointer(self, move || {
+            matches!(
+                stopped.recv_timeout(poll_interval),
+                Err(RecvTimeoutError::Timeout)
+            )
+        });
+        Some(Checkpointer {
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+
+    /// Spawns a thread calling [`MemoryGraph::check_auto_checkpoint`] each time `tick` returns
+    /// `true`, which exits once `tick` returns `false`.
+    ///
+    /// The thread only holds a weak reference to the graph, so that it does not keep the graph
+    /// alive.
+    fn spawn_checkpointer<F>(graph: &Arc<Self>, mut tick: F) -> JoinHandle<()>
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        let graph: Weak<Self> = Arc::downgrade(graph);
+        thread::Builder::new()
+            .name("minigu-checkpointer".to_string())
+            .spawn(move || {
+                while tick() {
+                    let Some(graph) = graph.upgrade() else {
+                        break;
+                    };
+                    if let Err(e) = graph.check_auto_checkpoint() {
+                        // Log error and retry at the next tick
+                        eprintln!("Failed to create auto checkpoint: {:?}", e);
+                    }
+                }
+            })
+            .expect("failed to spawn the checkpointer thread")
+    }
+}
+
+/// A handle on the background checkpointer of a graph, which stops the checkpointer when dropped.
+///
+/// See [`MemoryGraph::start_checkpointer`].
+pub struct Checkpointer {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Checkpointer {
+    fn drop(&mut self) {
+        // Disconnecting the channel wakes the checkpointer up, which then exits once done with the
+        // checkpoint in progress, if any.
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::io::Seek;
+    use std::sync::mpsc;
     use std::{env, fs};
 
     use minigu_common::value::ScalarValue;
@@ -1002,6 +1200,80 @@ fn test_checkpoint_with_corrupted_file() {
         }
     }
 
+    #[test]
+    fn test_auto_checkpointer() {
+        let checkpoint_config = CheckpointManagerConfig {
+            auto_checkpoint_interval_secs: 3600,
+            max_checkpoints: 2,
+            ..memory_graph::tests::mock_checkpoint_config()
+        };
+        let wal_config = memory_graph::tests::mock_wal_config();
+        let _cleaner = memory_graph::tests::Cleaner::new(&checkpoint_config, &wal_config);
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config.clone(), wal_config);
+
+        // Ticks are handed over one at a time, so the checkpointer is done with a tick once it
+        // takes the next one.
+        let (ticks, tick_receiver) = mpsc::sync_channel(0);
+        let checkpointer =
+            MemoryGraph::spawn_checkpointer(&graph, move || tick_receiver.recv().is_ok());
+
+        let list_checkpoint_files = || -> Vec<PathBuf> {
+            fs::read_dir(&checkpoint_config.checkpoint_dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect()
+        };
+
+        // The first tick creates a checkpoint, and the next ones wait for the interval.
+        ticks.send(()).unwrap();
+        ticks.send(()).unwrap();
+        ticks.send(()).unwrap();
+        assert_eq!(list_checkpoint_files().len(), 1);
+
+        // Checkpoints past `max_checkpoints` are pruned.
+        graph.create_managed_checkpoint(None).unwrap();
+        graph.create_managed_checkpoint(None).unwrap();
+        assert_eq!(
+            list_checkpoint_files().len(),
+            checkpoint_config.max_checkpoints
+        );
+
+        // The checkpointer exits once it runs out of ticks.
+        drop(ticks);
+        checkpointer.join().unwrap();
+    }
+
+    #[test]
+    fn test_start_checkpointer() {
+        let checkpoint_config = CheckpointManagerConfig {
+            auto_checkpoint_interval_secs: 3600,
+            ..memory_graph::tests::mock_checkpoint_config()
+        };
+        let wal_config = memory_graph::tests::mock_wal_config();
+        let _cleaner = memory_graph::tests::Cleaner::new(&checkpoint_config, &wal_config);
+
+        // Graphs without automatic checkpoints or without a WAL have no checkpointer.
+        let no_interval = CheckpointManagerConfig {
+            auto_checkpoint_interval_secs: 0,
+            ..checkpoint_config.clone()
+        };
+        let graph = MemoryGraph::with_config_fresh(no_interval, wal_config.clone());
+        assert!(graph.start_checkpointer().is_none());
+        let no_wal = WalManagerConfig {
+            durability: Durability::None,
+            ..wal_config.clone()
+        };
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config.clone(), no_wal);
+        assert!(graph.start_checkpointer().is_none());
+
+        // Dropping the handle stops the checkpointer without waiting for the next tick.
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config, wal_config);
+        let checkpointer = graph.start_checkpointer().unwrap();
+        let start = SystemTime::now();
+        drop(checkpointer);
+        assert!(start.elapsed().unwrap() < Duration::from_secs(60));
+    }
+
     #[test]
     #[ignore]
     fn test_checkpoint_manager() {
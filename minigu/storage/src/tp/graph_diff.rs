@@ -0,0 +1,327 @@
+//! Comparison of the contents of two graphs, e.g., to check that a graph survives an export and an
+//! import, or a migration, unchanged.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use minigu_common::value::ScalarValue;
+use minigu_transaction::Transaction;
+
+use super::iterators::ScanOrder;
+use super::memory_graph::MemoryGraph;
+use crate::common::model::edge::Edge;
+use crate::common::model::vertex::Vertex;
+use crate::error::StorageResult;
+
+/// The differences between the latest versions of two graphs, from the first one to the second
+/// one, as returned by [`graph_diff`] or [`graph_diff_by_content`].
+///
+/// Its [`Display`](fmt::Display) form lists the differences one per line, e.g., for failure
+/// messages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphDiff {
+    /// The vertices of the second graph only.
+    pub added_vertices: Vec<Vertex>,
+    /// The vertices of the first graph only.
+    pub removed_vertices: Vec<Vertex>,
+    /// The vertices of both graphs whose contents differ, as in the first and the second one.
+    pub changed_vertices: Vec<(Vertex, Vertex)>,
+    /// The edges of the second graph only.
+    pub added_edges: Vec<Edge>,
+    /// The edges of the first graph only.
+    pub removed_edges: Vec<Edge>,
+    /// The edges of both graphs whose contents differ, as in the first and the second one.
+    pub changed_edges: Vec<(Edge, Edge)>,
+}
+
+impl GraphDiff {
+    /// Returns whether the graphs have the same vertices and edges.
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.changed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+/// Returns the differences between the latest versions of graphs `a` and `b`, each read within a
+/// read-only transaction.
+///
+/// Vertices and edges are matched by id: those whose id only exists in one of the graphs are added
+/// or removed, and those whose label, endpoints or properties differ are changed. Each list is
+/// sorted by id.
+pub fn graph_diff(a: &MemoryGraph, b: &MemoryGraph) -> StorageResult<GraphDiff> {
+    let (vertices_a, edges_a) = read_graph(a)?;
+    let (vertices_b, edges_b) = read_graph(b)?;
+    let (added_vertices, removed_vertices, changed_vertices) = diff_by_id(vertices_a, vertices_b);
+    let (added_edges, removed_edges, changed_edges) = diff_by_id(edges_a, edges_b);
+    Ok(GraphDiff {
+        added_vertices,
+        removed_vertices,
+        changed_vertices,
+        added_edges,
+        removed_edges,
+        changed_edges,
+    })
+}
+
+/// Like [`graph_diff`], but matches the vertices and the edges by content rather than by id, e.g.,
+/// to compare a graph with its import, whose ids were assigned anew.
+///
+/// Vertices are matched by label and properties, and edges by label, properties and the contents
+/// of their endpoints. Unmatched ones are added or removed, and none are changed. Each list is
+/// sorted by content.
+///
+/// Labels are still compared by id, so the graphs should have the same graph type.
+pub fn graph_diff_by_content(a: &MemoryGraph, b: &MemoryGraph) -> StorageResult<GraphDiff> {
+    let (vertices_a, edges_a) = read_graph(a)?;
+    let (vertices_b, edges_b) = read_graph(b)?;
+    let edges_a = by_content(edges_a.into_values(), |edge| edge_key(edge, &vertices_a));
+    let edges_b = by_content(edges_b.into_values(), |edge| edge_key(edge, &vertices_b));
+    let (added_edges, removed_edges) = diff_by_content(edges_a, edges_b);
+    let vertices_a = by_content(vertices_a.into_values(), fmt_vertex);
+    let vertices_b = by_content(vertices_b.into_values(), fmt_vertex);
+    let (added_vertices, removed_vertices) = diff_by_content(vertices_a, vertices_b);
+    Ok(GraphDiff {
+        added_vertices,
+        removed_vertices,
+        added_edges,
+        removed_edges,
+        ..Default::default()
+    })
+}
+
+type Entities<T> = BTreeMap<u64, T>;
+
+/// Entities grouped by content.
+type ByContent<T> = BTreeMap<String, Vec<T>>;
+
+/// Returns the vertices and the edges of `graph`, by id.
+fn read_graph(graph: &MemoryGraph) -> StorageResult<(Entities<Vertex>, Entities<Edge>)> {
+    let txn = graph.txn_manager().begin_read_only_transaction()?;
+    let vertices: StorageResult<Entities<Vertex>> = txn
+        .iter_vertices_with_order(ScanOrder::Ascending)
+        .map(|vertex| vertex.map(|vertex| (vertex.vid(), vertex)))
+        .collect();
+    let edges: StorageResult<Entities<Edge>> = txn
+        .iter_edges_with_order(ScanOrder::Ascending)
+        .map(|edge| edge.map(|edge| (edge.eid(), edge)))
+        .collect();
+    txn.commit()?;
+    Ok((vertices?, edges?))
+}
+
+/// Returns the entities added to, removed from and changed between `a` and `b`.
+fn diff_by_id<T: PartialEq>(mut a: Entities<T>, b: Entities<T>) -> (Vec<T>, Vec<T>, Vec<(T, T)>) {
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (id, entity_b) in b {
+        match a.remove(&id) {
+            Some(entity_a) if entity_a != entity_b => changed.push((entity_a, entity_b)),
+            Some(_) => {}
+            None => added.push(entity_b),
+        }
+    }
+    (added, a.into_values().collect(), changed)
+}
+
+fn by_content<T>(entities: impl Iterator<Item = T>, key: impl Fn(&T) -> String) -> ByContent<T> {
+    let mut by_content = ByContent::new();
+    for entity in entities {
+        by_content
+            .entry(key(&entity))
+            .or_insert_with(Vec::new)
+            .push(entity);
+    }
+    by_content
+}
+
+/// Returns the entities added to and removed from `a` in `b`, each entity of `a` matching one
+/// entity of `b` with the same content at most.
+fn diff_by_content<T>(a: ByContent<T>, mut b: ByContent<T>) -> (Vec<T>, Vec<T>) {
+    let mut removed = Vec::new();
+    for (key, mut entities_a) in a {
+        let entities_b = b.remove(&key).unwrap_or_default();
+        if entities_b.len() > entities_a.len() {
+            b.insert(key, entities_b.into_iter().skip(entities_a.len()).collect());
+        } else {
+            removed.extend(entities_a.drain(entities_b.len()..));
+        }
+    }
+    (b.into_values().flatten().collect(), removed)
+}
+
+/// Returns the content of `edge`, including that of its endpoints, which are among `vertices`.
+fn edge_key(edge: &Edge, vertices: &Entities<Vertex>) -> String {
+    let endpoint = |vid| {
+        vertices
+            .get(&vid)
+            .map_or_else(|| format!("{vid}"), fmt_vertex)
+    };
+    format!(
+        "{}-[:{} {}]->{}",
+        endpoint(edge.src_id()),
+        edge.label_id(),
+        fmt_properties(edge.properties()),
+        endpoint(edge.dst_id())
+    )
+}
+
+fn fmt_properties(properties: &[ScalarValue]) -> String {
+    let properties: Vec<_> = properties.iter().map(ToString::to_string).collect();
+    format!("[{}]", properties.join(", "))
+}
+
+fn fmt_vertex(vertex: &Vertex) -> String {
+    format!(
+        "(:{} {})",
+        vertex.label_id,
+        fmt_properties(vertex.properties())
+    )
+}
+
+fn fmt_edge(edge: &Edge) -> String {
+    format!(
+        "{}-[:{} {}]->{}",
+        edge.src_id(),
+        edge.label_id(),
+        fmt_properties(edge.properties()),
+        edge.dst_id()
+    )
+}
+
+impl fmt::Display for GraphDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for vertex in &self.added_vertices {
+            writeln!(f, "+ vertex {}: {}", vertex.vid(), fmt_vertex(vertex))?;
+        }
+        for vertex in &self.removed_vertices {
+            writeln!(f, "- vertex {}: {}", vertex.vid(), fmt_vertex(vertex))?;
+        }
+        for (a, b) in &self.changed_vertices {
+            let (from, to) = (fmt_vertex(a), fmt_vertex(b));
+            writeln!(f, "~ vertex {}: {from} => {to}", a.vid())?;
+        }
+        for edge in &self.added_edges {
+            writeln!(f, "+ edge {}: {}", edge.eid(), fmt_edge(edge))?;
+        }
+        for edge in &self.removed_edges {
+            writeln!(f, "- edge {}: {}", edge.eid(), fmt_edge(edge))?;
+        }
+        for (a, b) in &self.changed_edges {
+            let (from, to) = (fmt_edge(a), fmt_edge(b));
+            writeln!(f, "~ edge {}: {from} => {to}", a.eid())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use minigu_common::types::LabelId;
+    use minigu_transaction::{GraphTxnManager, IsolationLevel};
+
+    use super::*;
+    use crate::common::model::properties::PropertyRecord;
+    use crate::tp::MemTransaction;
+    use crate::tp::memory_graph::tests::{mock_empty_graph, mock_graph};
+
+    fn modify(graph: &MemoryGraph, f: impl FnOnce(&Arc<MemTransaction>)) {
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        f(&txn);
+        txn.commit().unwrap();
+    }
+
+    #[test]
+    fn test_identical_graphs() {
+        let (a, _cleaner_a) = mock_graph();
+        let (b, _cleaner_b) = mock_graph();
+        let diff = graph_diff(&a, &b).unwrap();
+        assert!(diff.is_empty(), "{diff}");
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn test_changed_properties() {
+        let (a, _cleaner_a) = mock_graph();
+        let (b, _cleaner_b) = mock_graph();
+        modify(&b, |txn| {
+            b.set_vertex_property(txn, 1, vec![1], vec![ScalarValue::Int32(Some(26))])
+                .unwrap();
+            b.set_edge_property(txn, 3, vec![0], vec!["2023-01-01".into()])
+                .unwrap();
+        });
+        let diff = graph_diff(&a, &b).unwrap();
+        assert_eq!(diff.changed_vertices.len(), 1);
+        assert_eq!(diff.changed_edges.len(), 1);
+        assert_eq!(
+            diff.to_string(),
+            "~ vertex 1: (:1 ['Alice', 25]) => (:1 ['Alice', 26])\n\
+             ~ edge 3: 1-[:3 ['2022-06-01']]->3 => 1-[:3 ['2023-01-01']]->3\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_by_content() {
+        let (a, _cleaner_a) = mock_graph();
+        let (b, _cleaner_b) = mock_empty_graph();
+        // A copy of `a` whose ids are reversed.
+        let (vertices, edges) = read_graph(&a).unwrap();
+        modify(&b, |txn| {
+            for vertex in vertices.into_values() {
+                let vertex = Vertex::new(5 - vertex.vid(), vertex.label_id, vertex.properties);
+                b.create_vertex(txn, vertex).unwrap();
+            }
+            for edge in edges.into_values() {
+                let properties = PropertyRecord::new(edge.properties().clone());
+                let (src_id, dst_id) = (5 - edge.src_id(), 5 - edge.dst_id());
+                let edge = Edge::new(5 - edge.eid(), src_id, dst_id, edge.label_id(), properties);
+                b.create_edge(txn, edge).unwrap();
+            }
+        });
+        // The ids differ, but not the contents.
+        assert!(!graph_diff(&a, &b).unwrap().is_empty());
+        let diff = graph_diff_by_content(&a, &b).unwrap();
+        assert!(diff.is_empty(), "{diff}");
+
+        modify(&b, |txn| {
+            b.set_edge_property(txn, 2, vec![0], vec!["2023-01-01".into()])
+                .unwrap();
+        });
+        let diff = graph_diff_by_content(&a, &b).unwrap();
+        assert_eq!(
+            diff.to_string(),
+            "+ edge 2: 4-[:3 ['2023-01-01']]->2\n\
+             - edge 3: 1-[:3 ['2022-06-01']]->3\n"
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed() {
+        let (a, _cleaner_a) = mock_graph();
+        let (b, _cleaner_b) = mock_graph();
+        modify(&b, |txn| {
+            let properties = PropertyRecord::new(vec!["Eve".into(), ScalarValue::Int32(Some(24))]);
+            let eve = Vertex::new(5, LabelId::new(1).unwrap(), properties);
+            b.create_vertex(txn, eve).unwrap();
+            b.delete_edge(txn, 4).unwrap();
+        });
+        let diff = graph_diff(&a, &b).unwrap();
+        assert_eq!(
+            diff.to_string(),
+            "+ vertex 5: (:1 ['Eve', 24])\n\
+             - edge 4: 4-[:3 ['2022-07-15']]->1\n"
+        );
+        // The other way around, the vertex is removed and the edge added.
+        let diff = graph_diff(&b, &a).unwrap();
+        assert_eq!(diff.removed_vertices.len(), 1);
+        assert_eq!(diff.added_edges.len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 use crossbeam_skiplist::SkipSet;
@@ -6,21 +7,31 @@
 use crate::common::iterators::{AdjacencyIteratorTrait, Direction};
 use crate::common::model::edge::Neighbor;
 use crate::error::StorageResult;
+use crate::tp::memory_graph::{AdjacencyList, PackedCursor, PackedNeighbors};
 use crate::tp::transaction::MemTransaction;
 
 type AdjFilter<'a> = Box<dyn Fn(&Neighbor) -> bool + 'a>;
 
 const BATCH_SIZE: usize = 64;
 
+/// The neighbors of a vertex iterated over.
+enum Neighbors {
+    /// Read from the skip list in batches.
+    SkipList(Arc<SkipSet<Neighbor>>),
+    /// Read in place from the array packed by the latest compaction of the adjacency list, merged
+    /// with the writes since.
+    Packed(Arc<PackedNeighbors>, PackedCursor),
+}
+
 /// An adjacency list iterator that supports filtering (for iterating over a single vertex's
 /// adjacency list).
 pub struct AdjacencyIterator<'a> {
-    adj_list: Option<Arc<SkipSet<Neighbor>>>, // The adjacency list for the vertex
-    current_entries: Vec<Neighbor>,           // Store current batch of entries
-    current_index: usize,                     // Current index in the batch
-    txn: &'a MemTransaction,                  // Reference to the transaction
-    filters: Vec<AdjFilter<'a>>,              // List of filtering predicates
-    current_adj: Option<Neighbor>,            // Current adjacency entry
+    adj_list: Option<Neighbors>,    // The adjacency list for the vertex
+    current_entries: Vec<Neighbor>, // Store current batch of entries
+    current_index: usize,           // Current index in the batch
+    txn: &'a MemTransaction,        // Reference to the transaction
+    filters: Vec<AdjFilter<'a>>,    // List of filtering predicates
+    current_adj: Option<Neighbor>,  // Current adjacency entry
 }
 
 impl Iterator for AdjacencyIterator<'_> {
@@ -28,6 +39,17 @@ impl Iterator for AdjacencyIterator<'_> {
 
     /// Retrieves the next visible adjacency entry that satisfies all filters.
     fn next(&mut self) -> Option<Self::Item> {
+        // Packed neighbors are read in place, without batches
+        if let Some(Neighbors::Packed(neighbors, cursor)) = &mut self.adj_list {
+            while let Some(entry) = neighbors.next(cursor) {
+                if is_accepted(self.txn, &self.filters, &entry) {
+                    self.current_adj = Some(entry);
+                    return Some(Ok(entry));
+                }
+            }
+            return None;
+        }
+
         // If current batch is processed, get a new batch
         if self.current_index >= self.current_entries.len() {
             self.load_next_batch()?;
@@ -38,18 +60,7 @@ fn next(&mut self) -> Option<Self::Item> {
             let entry = &self.current_entries[self.current_index];
             self.current_index += 1;
 
-            let eid = entry.eid();
-
-            // Perform MVCC visibility check
-            let is_visible = self
-                .txn
-                .graph()
-                .edges
-                .get(&eid)
-                .map(|edge| edge.is_visible(self.txn))
-                .unwrap_or(false);
-
-            if is_visible && self.filters.iter().all(|f| f(entry)) {
+            if is_accepted(self.txn, &self.filters, entry) {
                 let adj = *entry;
                 self.current_adj = Some(adj);
                 return Some(Ok(adj));
@@ -62,9 +73,48 @@ fn next(&mut self) -> Option<Self::Item> {
     }
 }
 
+/// Returns whether the edge of `entry` is visible to `txn`, and `entry` satisfies all `filters`.
+fn is_accepted(txn: &MemTransaction, filters: &[AdjFilter<'_>], entry: &Neighbor) -> bool {
+    // Perform MVCC visibility check
+    let is_visible = txn
+        .graph()
+        .edges
+        .get(&entry.eid())
+        .map(|edge| edge.is_visible(txn))
+        .unwrap_or(false);
+    is_visible && filters.iter().all(|f| f(entry))
+}
+
+/// Merges the sorted `incoming` and `outgoing` neighbors, keeping a single copy of those in both,
+/// i.e., of self-loops.
+fn merge_packed(incoming: &[Neighbor], outgoing: &[Neighbor]) -> PackedNeighbors {
+    let mut merged = Vec::with_capacity(incoming.len() + outgoing.len());
+    let (mut i, mut o) = (0, 0);
+    while i < incoming.len() && o < outgoing.len() {
+        match incoming[i].cmp(&outgoing[o]) {
+            Ordering::Less => {
+                merged.push(incoming[i]);
+                i += 1;
+            }
+            Ordering::Greater => {
+                merged.push(outgoing[o]);
+                o += 1;
+            }
+            Ordering::Equal => {
+                merged.push(incoming[i]);
+                i += 1;
+                o += 1;
+            }
+        }
+    }
+    merged.extend_from_slice(&incoming[i..]);
+    merged.extend_from_slice(&outgoing[o..]);
+    PackedNeighbors::new(merged.into())
+}
+
 impl<'a> AdjacencyIterator<'a> {
     fn load_next_batch(&mut self) -> Option<()> {
-        if let Some(adj_list) = &self.adj_list {
+        if let Some(Neighbors::SkipList(adj_list)) = &self.adj_list {
             let mut current = if let Some(e) = self.current_entries.last() {
                 // If there is a last entry, get the next entry from the adjacency list
                 adj_list.get(e)?.next()?
@@ -98,20 +148,30 @@ fn load_next_batch(&mut self) -> Option<()> {
     pub fn new(txn: &'a MemTransaction, vid: VertexId, direction: Direction) -> Self {
         let adjacency_list = txn.graph().adjacency_list.get(&vid);
 
+        let neighbors = |list: &AdjacencyList| match list.packed() {
+            Some(packed) => Neighbors::Packed(packed, PackedCursor::default()),
+            None => Neighbors::SkipList(list.neighbors().clone()),
+        };
         let mut result = Self {
             adj_list: adjacency_list.map(|entry| match direction {
-                Direction::Incoming => entry.incoming().clone(),
-                Direction::Outgoing => entry.outgoing().clone(),
-                Direction::Both => {
-                    let combined = SkipSet::new();
-                    for neighbor in entry.incoming().iter() {
-                        combined.insert(*neighbor);
+                Direction::Incoming => neighbors(entry.incoming()),
+                Direction::Outgoing => neighbors(entry.outgoing()),
+                Direction::Both => match (entry.incoming().packed(), entry.outgoing().packed()) {
+                    (Some(incoming), Some(outgoing)) => {
+                        let merged = merge_packed(&incoming.to_vec(), &outgoing.to_vec());
+                        Neighbors::Packed(Arc::new(merged), PackedCursor::default())
                     }
-                    for neighbor in entry.outgoing().iter() {
-                        combined.insert(*neighbor);
+                    _ => {
+                        let combined = SkipSet::new();
+                        for neighbor in entry.incoming().iter() {
+                            combined.insert(*neighbor);
+                        }
+                        for neighbor in entry.outgoing().iter() {
+                            combined.insert(*neighbor);
+                        }
+                        Neighbors::SkipList(Arc::new(combined))
                     }
-                    Arc::new(combined)
-                }
+                },
             }),
             current_entries: Vec::new(),
             current_index: 0,
@@ -121,7 +181,7 @@ pub fn new(txn: &'a MemTransaction, vid: VertexId, direction: Direction) -> Self
         };
 
         // Preload the first batch of data
-        if result.adj_list.is_some() {
+        if let Some(Neighbors::SkipList(_)) = &result.adj_list {
             result.load_next_batch();
         }
 
@@ -6,6 +6,7 @@
 use crate::common::iterators::{ChunkData, EdgeIteratorTrait};
 use crate::common::model::edge::Edge;
 use crate::error::StorageResult;
+use crate::tp::iterators::ScanOrder;
 use crate::tp::memory_graph::VersionedEdge;
 use crate::tp::transaction::MemTransaction;
 
@@ -13,10 +14,47 @@
 
 /// An edge iterator that supports filtering.
 pub struct EdgeIterator<'a> {
-    inner: Iter<'a, EdgeId, VersionedEdge>, // Native DashMap iterator
-    txn: &'a MemTransaction,                // Reference to the transaction
-    filters: Vec<EdgeFilter<'a>>,           // List of filtering predicates
-    current_edge: Option<Edge>,             // Currently iterated edge
+    inner: EdgeCursor<'a>,        // Source of candidate edges
+    txn: &'a MemTransaction,      // Reference to the transaction
+    filters: Vec<EdgeFilter<'a>>, // List of filtering predicates
+    current_edge: Option<Edge>,   // Currently iterated edge
+}
+
+/// Yields the candidate edges of a [`EdgeIterator`] according to its [`ScanOrder`].
+enum EdgeCursor<'a> {
+    Unordered(Iter<'a, EdgeId, VersionedEdge>),
+    Ascending(std::vec::IntoIter<EdgeId>),
+}
+
+impl<'a> EdgeCursor<'a> {
+    fn new(txn: &'a MemTransaction, order: ScanOrder) -> Self {
+        let edges = txn.graph().edges();
+        match order {
+            ScanOrder::Unordered => EdgeCursor::Unordered(edges.iter()),
+            ScanOrder::Ascending => {
+                let mut ids: Vec<EdgeId> = edges.iter().map(|entry| *entry.key()).collect();
+                ids.sort_unstable();
+                EdgeCursor::Ascending(ids.into_iter())
+            }
+        }
+    }
+
+    /// Returns the id of the next candidate along with its version visible to `txn`.
+    fn next_candidate(&mut self, txn: &MemTransaction) -> Option<(EdgeId, StorageResult<Edge>)> {
+        match self {
+            EdgeCursor::Unordered(iter) => {
+                let entry = iter.next()?;
+                Some((*entry.key(), entry.value().get_visible(txn)))
+            }
+            EdgeCursor::Ascending(ids) => loop {
+                let id = ids.next()?;
+                // The entry may have been removed since the ids were collected.
+                if let Some(entry) = txn.graph().edges().get(&id) {
+                    return Some((id, entry.value().get_visible(txn)));
+                }
+            },
+        }
+    }
 }
 
 impl Iterator for EdgeIterator<'_> {
@@ -24,12 +62,9 @@ impl Iterator for EdgeIterator<'_> {
 
     /// Retrieves the next visible edge that satisfies all filters.
     fn next(&mut self) -> Option<Self::Item> {
-        for entry in self.inner.by_ref() {
-            let eid = *entry.key();
-            let versioned_edge = entry.value();
-
+        while let Some((eid, visible)) = self.inner.next_candidate(self.txn) {
             // Perform MVCC visibility check
-            let visible_edge = match versioned_edge.get_visible(self.txn) {
+            let visible_edge = match visible {
                 Ok(e) => e, // Skip logically deleted edges
                 _ => continue,
             };
@@ -91,8 +126,13 @@ impl MemTransaction {
     /// Returns an iterator over all edges in the graph.
     /// Filtering conditions can be applied using the `filter` method.
     pub fn iter_edges(&self) -> EdgeIterator<'_> {
+        self.iter_edges_with_order(ScanOrder::Unordered)
+    }
+
+    /// Returns an iterator over all edges in the graph, visited in the given order.
+    pub fn iter_edges_with_order(&self, order: ScanOrder) -> EdgeIterator<'_> {
         EdgeIterator {
-            inner: self.graph().edges().iter(),
+            inner: EdgeCursor::new(self, order),
             txn: self,
             filters: Vec::new(), // Initialize with an empty filter list
             current_edge: None,  // No edge selected initially
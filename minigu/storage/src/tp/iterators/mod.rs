@@ -2,6 +2,22 @@
 pub mod edge_iterator;
 pub mod vertex_iterator;
 
+/// The order in which a scan visits vertices or edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScanOrder {
+    /// Visits entries in the internal order of the underlying hash map. This is the cheapest
+    /// option, but the order is unspecified and may differ between two scans over the same data.
+    #[default]
+    Unordered,
+    /// Visits entries in ascending id order, which makes scans (and anything built on them, such
+    /// as `LIMIT`) reproducible.
+    ///
+    /// The ids are collected and sorted when the iterator is created, costing `O(n log n)` time
+    /// and `O(n)` extra memory per scan, and each entry is then looked up individually. Prefer
+    /// [`ScanOrder::Unordered`] unless a deterministic order is required, e.g., in tests.
+    Ascending,
+}
+
 // Re-export iterator types
 pub use adjacency_iterator::*;
 pub use edge_iterator::*;
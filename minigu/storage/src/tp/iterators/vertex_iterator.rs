@@ -1,3 +1,4 @@
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 use dashmap::iter::Iter;
@@ -6,18 +7,67 @@
 use crate::common::iterators::{ChunkData, VertexIteratorTrait};
 use crate::common::model::vertex::Vertex;
 use crate::error::StorageResult;
+use crate::tp::iterators::ScanOrder;
 use crate::tp::iterators::adjacency_iterator::AdjacencyIterator;
 use crate::tp::memory_graph::VersionedVertex;
-use crate::tp::transaction::MemTransaction;
+use crate::tp::transaction::{IsolationLevel, MemTransaction, VertexPredicate};
 
 type VertexFilter<'a> = Box<dyn Fn(&Vertex) -> bool + 'a>;
 
 /// A vertex iterator that supports filtering.
 pub struct VertexIterator<'a> {
-    inner: Iter<'a, VertexId, VersionedVertex>, // Native DashMap iterator
-    txn: &'a MemTransaction,                    // Reference to the transaction
-    filters: Vec<VertexFilter<'a>>,             // List of filtering predicates
-    current_vertex: Option<Vertex>,             // Currently iterated vertex
+    inner: VertexCursor<'a>,         // Source of candidate vertices
+    range: RangeInclusive<VertexId>, // Ids of the candidates to visit
+    txn: &'a MemTransaction,         // Reference to the transaction
+    filters: Vec<VertexFilter<'a>>,  // List of filtering predicates
+    current_vertex: Option<Vertex>,  // Currently iterated vertex
+}
+
+/// Yields the candidate vertices of a [`VertexIterator`] according to its [`ScanOrder`].
+enum VertexCursor<'a> {
+    Unordered(Iter<'a, VertexId, VersionedVertex>),
+    Ascending(std::vec::IntoIter<VertexId>),
+}
+
+impl<'a> VertexCursor<'a> {
+    fn new(txn: &'a MemTransaction, order: ScanOrder) -> Self {
+        let vertices = txn.graph().vertices();
+        match order {
+            ScanOrder::Unordered => VertexCursor::Unordered(vertices.iter()),
+            ScanOrder::Ascending => {
+                let mut ids: Vec<VertexId> = vertices.iter().map(|entry| *entry.key()).collect();
+                ids.sort_unstable();
+                VertexCursor::Ascending(ids.into_iter())
+            }
+        }
+    }
+
+    /// Returns the id of the next candidate in `range` along with its version visible to `txn`.
+    fn next_candidate(
+        &mut self,
+        txn: &MemTransaction,
+        range: &RangeInclusive<VertexId>,
+    ) -> Option<(VertexId, StorageResult<Vertex>)> {
+        match self {
+            VertexCursor::Unordered(iter) => loop {
+                let entry = iter.next()?;
+                // Skipped before the visibility check, which is the expensive part.
+                if range.contains(entry.key()) {
+                    return Some((*entry.key(), entry.value().get_visible(txn)));
+                }
+            },
+            VertexCursor::Ascending(ids) => loop {
+                let id = ids.next()?;
+                if !range.contains(&id) {
+                    continue;
+                }
+                // The entry may have been removed since the ids were collected.
+                if let Some(entry) = txn.graph().vertices().get(&id) {
+                    return Some((id, entry.value().get_visible(txn)));
+                }
+            },
+        }
+    }
 }
 
 impl Iterator for VertexIterator<'_> {
@@ -25,12 +75,9 @@ impl Iterator for VertexIterator<'_> {
 
     /// Retrieves the next visible vertex that satisfies all filters.
     fn next(&mut self) -> Option<Self::Item> {
-        for entry in self.inner.by_ref() {
-            let vid = *entry.key();
-            let versioned_vertex = entry.value();
-
+        while let Some((vid, visible)) = self.inner.next_candidate(self.txn, &self.range) {
             // Perform MVCC visibility check
-            let visible_vertex = match versioned_vertex.get_visible(self.txn) {
+            let visible_vertex = match visible {
                 Ok(v) => v,
                 _ => continue,
             };
@@ -94,8 +141,56 @@ impl MemTransaction {
     /// Returns an iterator over all vertices in the graph.
     /// Filtering conditions can be applied using the `filter` method.
     pub fn iter_vertices(&self) -> VertexIterator<'_> {
+        self.iter_vertices_with_order(ScanOrder::Unordered)
+    }
+
+    /// Returns an iterator over all vertices in the graph, visited in the given order.
+    pub fn iter_vertices_with_order(&self, order: ScanOrder) -> VertexIterator<'_> {
+        self.iter_vertices_in_range(order, 0..=VertexId::MAX)
+    }
+
+    /// Returns an iterator over the vertices in the graph satisfying `predicate`.
+    ///
+    /// Unlike a [`filter`](VertexIteratorTrait::filter), the predicate is recorded in the
+    /// transaction, so that a
+    /// [`Serializable`](crate::tp::transaction::IsolationLevel::Serializable) transaction fails
+    /// to commit if a concurrent transaction has meanwhile committed a vertex satisfying it.
+    pub fn iter_vertices_where(&self, predicate: VertexPredicate) -> VertexIterator<'_> {
+        self.iter_vertices_in_range_where(ScanOrder::Unordered, 0..=VertexId::MAX, predicate)
+    }
+
+    /// Returns an iterator over the vertices in the graph whose ids are in `range` and which
+    /// satisfy `predicate`, visited in the given order.
+    ///
+    /// Like [`iter_vertices_where`](Self::iter_vertices_where), the predicate is recorded in the
+    /// transaction. It should thus also check the range.
+    pub fn iter_vertices_in_range_where(
+        &self,
+        order: ScanOrder,
+        range: RangeInclusive<VertexId>,
+        predicate: VertexPredicate,
+    ) -> VertexIterator<'_> {
+        if matches!(self.isolation_level, IsolationLevel::Serializable) {
+            self.vertex_predicates
+                .write()
+                .unwrap()
+                .push(predicate.clone());
+        }
+        VertexIteratorTrait::filter(self.iter_vertices_in_range(order, range), move |v| {
+            predicate(v)
+        })
+    }
+
+    /// Returns an iterator over the vertices in the graph whose ids are in `range`, visited in the
+    /// given order.
+    pub fn iter_vertices_in_range(
+        &self,
+        order: ScanOrder,
+        range: RangeInclusive<VertexId>,
+    ) -> VertexIterator<'_> {
         VertexIterator {
-            inner: self.graph().vertices().iter(),
+            inner: VertexCursor::new(self, order),
+            range,
             txn: self,
             filters: Vec::new(), // Initialize with an empty filter list
             current_vertex: None,
@@ -1,9 +1,12 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Receiver;
 use std::sync::{Arc, RwLock, Weak};
+use std::time::Instant;
 
 use arrow::array::BooleanArray;
 use crossbeam_skiplist::SkipSet;
 use dashmap::DashMap;
-use minigu_common::types::{EdgeId, VectorIndexKey, VertexId};
+use minigu_common::types::{EdgeId, PropertyIndexKey, VectorIndexKey, VertexId};
 use minigu_common::value::{ScalarValue, VectorValue};
 use minigu_transaction::{IsolationLevel, Timestamp, Transaction};
 
@@ -13,10 +16,14 @@
 use super::vector_index::filter::create_filter_mask;
 use super::vector_index::in_mem_diskann::create_vector_index_config;
 use super::vector_index::{InMemANNAdapter, VectorIndex};
+use crate::common::changefeed::{Changefeed, CommittedDeltas};
 use crate::common::model::edge::{Edge, Neighbor};
 use crate::common::model::vertex::Vertex;
-use crate::common::wal::StorageWal;
-use crate::common::wal::graph_wal::{Operation, RedoEntry, WalManager, WalManagerConfig};
+use crate::common::property_index::PropertyIndexes;
+use crate::common::statistics::{PropertyStatistics, VertexCounts};
+use crate::common::wal::graph_wal::{
+    Durability, Operation, RedoEntry, WalManager, WalManagerConfig,
+};
 use crate::common::{DeltaOp, SetPropsOp};
 use crate::error::{
     EdgeNotFoundError, StorageError, StorageResult, TransactionError, VectorIndexError,
@@ -319,25 +326,209 @@ pub fn is_visible(&self, txn: &MemTransaction) -> bool {
     }
 }
 
+/// The neighbors of a vertex in one direction, sorted by label, neighbor and edge.
+///
+/// The neighbors are kept in a skip list, which writes update in place. A
+/// [compaction](MemoryGraph::compact_adjacency) also packs them into a contiguous array, which
+/// traversals read instead. Later writes are recorded in an overlay of the packed array, which is
+/// folded into it once it grows past [`MAX_OVERLAY_LEN`] neighbors, or by the next compaction.
+#[derive(Debug, Default)]
+pub(super) struct AdjacencyList {
+    neighbors: Arc<SkipSet<Neighbor>>,
+    /// The neighbors as of the latest compaction and the writes since, or `None` if the list was
+    /// never compacted.
+    packed: RwLock<Option<Arc<PackedNeighbors>>>,
+}
+
+/// The maximum number of neighbors written to a packed adjacency list before the writes are folded
+/// into its packed array.
+const MAX_OVERLAY_LEN: usize = 64;
+
+impl AdjacencyList {
+    pub fn insert(&self, neighbor: Neighbor) {
+        // Holding the lock keeps the packed neighbors consistent with the skip list.
+        let mut packed = self.packed.write().unwrap();
+        self.neighbors.insert(neighbor);
+        if let Some(packed) = packed.as_mut() {
+            Arc::make_mut(packed).insert(neighbor);
+        }
+        self.fold_overlay(&mut packed);
+    }
+
+    pub fn remove(&self, neighbor: &Neighbor) {
+        let mut packed = self.packed.write().unwrap();
+        self.neighbors.remove(neighbor);
+        if let Some(packed) = packed.as_mut() {
+            Arc::make_mut(packed).remove(neighbor);
+        }
+        self.fold_overlay(&mut packed);
+    }
+
+    pub fn iter(&self) -> crossbeam_skiplist::set::Iter<'_, Neighbor> {
+        self.neighbors.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.neighbors.is_empty()
+    }
+
+    /// Returns the skip list of the neighbors.
+    pub fn neighbors(&self) -> &Arc<SkipSet<Neighbor>> {
+        &self.neighbors
+    }
+
+    /// Returns the packed neighbors, or `None` if the list was never compacted.
+    pub fn packed(&self) -> Option<Arc<PackedNeighbors>> {
+        self.packed.read().unwrap().clone()
+    }
+
+    /// Packs the neighbors into a contiguous array, unless they already are and were not written
+    /// since. Returns whether they were packed.
+    pub fn compact(&self) -> bool {
+        let mut packed = self.packed.write().unwrap();
+        if packed.as_ref().is_some_and(|p| !p.has_overlay()) || self.is_empty() {
+            return false;
+        }
+        *packed = Some(Arc::new(self.pack()));
+        true
+    }
+
+    /// Folds the overlay of `packed` into its packed array if it is too large.
+    fn fold_overlay(&self, packed: &mut Option<Arc<PackedNeighbors>>) {
+        if packed
+            .as_ref()
+            .is_some_and(|p| p.overlay_len() > MAX_OVERLAY_LEN)
+        {
+            *packed = Some(Arc::new(self.pack()));
+        }
+    }
+
+    fn pack(&self) -> PackedNeighbors {
+        let mut neighbors = Vec::with_capacity(self.len());
+        neighbors.extend(self.neighbors.iter().map(|entry| *entry.value()));
+        PackedNeighbors::new(neighbors.into())
+    }
+}
+
+/// The neighbors of an adjacency list packed by a compaction, overlaid with the neighbors written
+/// to the list since.
+#[derive(Debug, Clone)]
+pub(super) struct PackedNeighbors {
+    packed: Arc<[Neighbor]>,
+    /// The neighbors inserted since the compaction, which are not in `packed`, sorted.
+    inserted: Vec<Neighbor>,
+    /// The neighbors of `packed` removed since the compaction, sorted.
+    removed: Vec<Neighbor>,
+}
+
+/// A position in [`PackedNeighbors`], i.e., in their packed array and in their inserted neighbors.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct PackedCursor {
+    packed: usize,
+    inserted: usize,
+}
+
+impl PackedNeighbors {
+    pub fn new(packed: Arc<[Neighbor]>) -> Self {
+        Self {
+            packed,
+            inserted: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, neighbor: Neighbor) {
+        if let Ok(index) = self.removed.binary_search(&neighbor) {
+            self.removed.remove(index);
+            return;
+        }
+        if self.packed.binary_search(&neighbor).is_ok() {
+            return;
+        }
+        if let Err(index) = self.inserted.binary_search(&neighbor) {
+            self.inserted.insert(index, neighbor);
+        }
+    }
+
+    fn remove(&mut self, neighbor: &Neighbor) {
+        if let Ok(index) = self.inserted.binary_search(neighbor) {
+            self.inserted.remove(index);
+            return;
+        }
+        if self.packed.binary_search(neighbor).is_err() {
+            return;
+        }
+        if let Err(index) = self.removed.binary_search(neighbor) {
+            self.removed.insert(index, *neighbor);
+        }
+    }
+
+    fn has_overlay(&self) -> bool {
+        self.overlay_len() > 0
+    }
+
+    fn overlay_len(&self) -> usize {
+        self.inserted.len() + self.removed.len()
+    }
+
+    /// Returns the neighbor at `cursor`, in sorted order, and moves `cursor` past it.
+    pub fn next(&self, cursor: &mut PackedCursor) -> Option<Neighbor> {
+        while let Some(neighbor) = self.packed.get(cursor.packed) {
+            if self.removed.binary_search(neighbor).is_err() {
+                break;
+            }
+            cursor.packed += 1;
+        }
+        match (
+            self.packed.get(cursor.packed),
+            self.inserted.get(cursor.inserted),
+        ) {
+            (Some(packed), Some(inserted)) if inserted < packed => {
+                cursor.inserted += 1;
+                Some(*inserted)
+            }
+            (Some(packed), _) => {
+                cursor.packed += 1;
+                Some(*packed)
+            }
+            (None, Some(inserted)) => {
+                cursor.inserted += 1;
+                Some(*inserted)
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Returns all the neighbors, in sorted order.
+    pub fn to_vec(&self) -> Vec<Neighbor> {
+        let mut cursor = PackedCursor::default();
+        std::iter::from_fn(|| self.next(&mut cursor)).collect()
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct AdjacencyContainer {
-    pub(super) incoming: Arc<SkipSet<Neighbor>>,
-    pub(super) outgoing: Arc<SkipSet<Neighbor>>,
+    pub(super) incoming: AdjacencyList,
+    pub(super) outgoing: AdjacencyList,
 }
 
 impl AdjacencyContainer {
     pub fn new() -> Self {
         Self {
-            incoming: Arc::new(SkipSet::new()),
-            outgoing: Arc::new(SkipSet::new()),
+            incoming: AdjacencyList::default(),
+            outgoing: AdjacencyList::default(),
         }
     }
 
-    pub fn incoming(&self) -> &Arc<SkipSet<Neighbor>> {
+    pub fn incoming(&self) -> &AdjacencyList {
         &self.incoming
     }
 
-    pub fn outgoing(&self) -> &Arc<SkipSet<Neighbor>> {
+    pub fn outgoing(&self) -> &AdjacencyList {
         &self.outgoing
     }
 }
@@ -361,6 +552,24 @@ pub struct MemoryGraph {
 
     // ---- Vector indices ----
     pub(super) vector_indices: DashMap<VectorIndexKey, Arc<RwLock<Box<dyn VectorIndex>>>>,
+
+    // ---- Property indices ----
+    pub(super) property_indexes: PropertyIndexes,
+
+    // ---- Property statistics ----
+    pub(super) statistics: PropertyStatistics,
+
+    // ---- Vertex counts of the latest analysis, with the modifications committed before it ----
+    pub(super) vertex_counts: RwLock<Option<(VertexCounts, u64)>>,
+
+    // ---- Number of modifications committed since the graph was created ----
+    pub(super) modifications: AtomicU64,
+
+    // ---- Smallest vertex id larger than the id of every vertex and every allocated id ----
+    pub(super) next_vertex_id: AtomicU64,
+
+    // ---- Subscribers to the committed modifications ----
+    pub(super) changefeed: Changefeed,
 }
 
 impl MemoryGraph {
@@ -414,6 +623,12 @@ pub fn with_config_fresh(
             wal_manager: WalManager::new(wal_config),
             checkpoint_manager: None,
             vector_indices: DashMap::new(),
+            property_indexes: PropertyIndexes::new(),
+            statistics: PropertyStatistics::new(),
+            vertex_counts: RwLock::new(None),
+            modifications: AtomicU64::new(0),
+            next_vertex_id: AtomicU64::new(1),
+            changefeed: Changefeed::new(),
         });
 
         // Initialize the checkpoint manager
@@ -431,7 +646,7 @@ pub fn with_config_fresh(
 
     /// Recovers the graph from WAL entries
     pub fn recover_from_wal(self: &Arc<Self>) -> StorageResult<()> {
-        let entries = self.wal_manager.wal().read().unwrap().read_all()?;
+        let entries = self.wal_manager.read_all()?;
         self.apply_wal_entries(entries)
     }
 
@@ -504,6 +719,96 @@ pub fn txn_manager(&self) -> &MemTxnManager {
         &self.txn_manager
     }
 
+    /// Returns the min/max statistics of the vertex properties.
+    pub fn statistics(&self) -> &PropertyStatistics {
+        &self.statistics
+    }
+
+    /// Counts the vertices of each label, keeping the counts for
+    /// [`vertex_counts`](Self::vertex_counts) until the next analysis.
+    pub fn analyze(&self) -> StorageResult<VertexCounts> {
+        // The modifications committed while counting may be missed, so they are considered as
+        // committed after the analysis.
+        let modifications = self.modifications.load(Ordering::SeqCst);
+        let txn = self.txn_manager.begin_read_only_transaction()?;
+        let counts = txn
+            .iter_vertices()
+            .try_fold(VertexCounts::default(), |mut counts, vertex| {
+                counts.add(vertex?.label_id);
+                StorageResult::Ok(counts)
+            });
+        txn.commit()?;
+        let counts = counts?;
+        *self.vertex_counts.write().unwrap() = Some((counts.clone(), modifications));
+        Ok(counts)
+    }
+
+    /// Returns the vertex counts of the latest [`analyze`](Self::analyze), if any.
+    pub fn vertex_counts(&self) -> Option<VertexCounts> {
+        let vertex_counts = self.vertex_counts.read().unwrap();
+        vertex_counts.as_ref().map(|(counts, _)| counts.clone())
+    }
+
+    /// Returns the number of vertex and edge modifications committed since the latest
+    /// [`analyze`](Self::analyze), or `None` if the graph has never been analyzed.
+    pub fn modifications_since_analysis(&self) -> Option<u64> {
+        let vertex_counts = self.vertex_counts.read().unwrap();
+        let (_, analyzed) = vertex_counts.as_ref()?;
+        Some(self.modifications.load(Ordering::SeqCst) - analyzed)
+    }
+
+    /// Packs the adjacency lists modified since the previous compaction into contiguous arrays, so
+    /// that traversals read them sequentially rather than chasing the pointers of their skip lists.
+    /// Returns the number of lists packed.
+    ///
+    /// Writes to packed lists are overlaid on their packed arrays, which traversals merge while
+    /// reading them. The overlays are folded into the arrays by the next compaction, run on demand
+    /// or after each checkpoint, or once they grow too large.
+    pub fn compact_adjacency(&self) -> usize {
+        self.adjacency_list
+            .iter()
+            .map(|entry| entry.incoming().compact() as usize + entry.outgoing().compact() as usize)
+            .sum()
+    }
+
+    /// Subscribes to the modifications committed to the graph from now on, which are received in
+    /// commit order. See [`Changefeed`] for how slow subscribers are handled.
+    pub fn subscribe(&self, capacity: usize) -> Receiver<CommittedDeltas> {
+        self.changefeed.subscribe(capacity)
+    }
+
+    /// Returns whether the transactions on the graph are logged to a WAL.
+    pub fn durability(&self) -> Durability {
+        self.wal_manager.durability()
+    }
+
+    /// Returns the smallest and largest ids of the stored vertices, or `None` if there is none.
+    ///
+    /// Every version is taken into account, so the bounds may include vertices that are not visible
+    /// to a given transaction.
+    pub fn vertex_id_bounds(&self) -> Option<(VertexId, VertexId)> {
+        self.vertices.iter().fold(None, |bounds, entry| {
+            let vid = *entry.key();
+            match bounds {
+                Some((min, max)) => Some((vid.min(min), vid.max(max))),
+                None => Some((vid, vid)),
+            }
+        })
+    }
+
+    /// Allocates the id of a new vertex, larger than the id of every vertex of the graph, whether
+    /// visible or not, and than every id allocated before. Concurrent transactions are thus never
+    /// given the same id, although the ids of the vertices they end up not creating are skipped.
+    pub fn allocate_vertex_id(&self) -> VertexId {
+        self.next_vertex_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Makes sure that the ids allocated from now on are larger than `vid`.
+    pub(super) fn reserve_vertex_id(&self, vid: VertexId) {
+        self.next_vertex_id
+            .fetch_max(vid.saturating_add(1), Ordering::SeqCst);
+    }
+
     /// Returns a reference to the vertices storage.
     pub(super) fn vertices(&self) -> &DashMap<VertexId, VersionedVertex> {
         &self.vertices
@@ -639,13 +944,47 @@ pub fn iter_adjacency<'a>(
     }
 
     // ===== Mutable graph methods =====
+    /// Runs the modification `op` of `txn`. If lock waits are
+    /// [enabled](MemTxnManager::set_lock_wait_timeout) and `op` conflicts with a running
+    /// transaction, waits for it to finish and runs `op` again, until it succeeds or the wait times
+    /// out.
+    fn wait_on_conflicts<T>(
+        &self,
+        txn: &Arc<MemTransaction>,
+        mut op: impl FnMut() -> StorageResult<T>,
+    ) -> StorageResult<T> {
+        let Some(timeout) = self.txn_manager.lock_wait_timeout() else {
+            return op();
+        };
+        let deadline = Instant::now() + timeout;
+        loop {
+            match op() {
+                Err(StorageError::Transaction(TransactionError::WriteWriteConflict(_))) => {
+                    let holder = Timestamp::with_ts(txn.blocked_by.load(Ordering::Acquire));
+                    self.txn_manager.wait_for(txn, holder, deadline)?;
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Inserts a new vertex into the graph within a transaction.
     pub fn create_vertex(
         &self,
         txn: &Arc<MemTransaction>,
         vertex: Vertex,
     ) -> StorageResult<VertexId> {
+        self.wait_on_conflicts(txn, || self.try_create_vertex(txn, vertex.clone()))
+    }
+
+    fn try_create_vertex(
+        &self,
+        txn: &Arc<MemTransaction>,
+        vertex: Vertex,
+    ) -> StorageResult<VertexId> {
+        txn.check_writable()?;
         let vid = vertex.vid();
+        self.reserve_vertex_id(vid);
         let entry = self
             .vertices
             .entry(vid)
@@ -666,6 +1005,10 @@ pub fn create_vertex(
         };
         undo_buffer.push(undo_entry.clone());
         *entry.chain.undo_ptr.write().unwrap() = Arc::downgrade(&undo_entry);
+        self.statistics
+            .update_all(vertex.label_id, vertex.properties());
+        self.property_indexes
+            .update_all(vertex.label_id, vid, vertex.properties());
 
         // Record redo entry
         let wal_entry = RedoEntry {
@@ -681,6 +1024,11 @@ pub fn create_vertex(
 
     /// Inserts a new edge into the graph within a transaction.
     pub fn create_edge(&self, txn: &Arc<MemTransaction>, edge: Edge) -> StorageResult<EdgeId> {
+        self.wait_on_conflicts(txn, || self.try_create_edge(txn, edge.clone()))
+    }
+
+    fn try_create_edge(&self, txn: &Arc<MemTransaction>, edge: Edge) -> StorageResult<EdgeId> {
+        txn.check_writable()?;
         let eid = edge.eid();
         let src_id = edge.src_id();
         let dst_id = edge.dst_id();
@@ -735,6 +1083,11 @@ pub fn create_edge(&self, txn: &Arc<MemTransaction>, edge: Edge) -> StorageResul
 
     /// Deletes a vertex from the graph within a transaction.
     pub fn delete_vertex(&self, txn: &Arc<MemTransaction>, vid: VertexId) -> StorageResult<()> {
+        self.wait_on_conflicts(txn, || self.try_delete_vertex(txn, vid))
+    }
+
+    fn try_delete_vertex(&self, txn: &Arc<MemTransaction>, vid: VertexId) -> StorageResult<()> {
+        txn.check_writable()?;
         // Atomically retrieve the versioned vertex (check existence).
         let entry = self.vertices.get(&vid).ok_or(StorageError::VertexNotFound(
             VertexNotFoundError::VertexNotFound(vid.to_string()),
@@ -747,12 +1100,12 @@ pub fn delete_vertex(&self, txn: &Arc<MemTransaction>, vid: VertexId) -> Storage
         if let Some(adjacency_container) = self.adjacency_list.get(&vid) {
             for adj in adjacency_container.incoming().iter() {
                 if self.edges.get(&adj.value().eid()).is_some() {
-                    self.delete_edge(txn, adj.value().eid())?;
+                    self.try_delete_edge(txn, adj.value().eid())?;
                 }
             }
             for adj in adjacency_container.outgoing().iter() {
                 if self.edges.get(&adj.value().eid()).is_some() {
-                    self.delete_edge(txn, adj.value().eid())?;
+                    self.try_delete_edge(txn, adj.value().eid())?;
                 }
             }
         }
@@ -784,6 +1137,11 @@ pub fn delete_vertex(&self, txn: &Arc<MemTransaction>, vid: VertexId) -> Storage
 
     /// Deletes an edge from the graph within a transaction.
     pub fn delete_edge(&self, txn: &Arc<MemTransaction>, eid: EdgeId) -> StorageResult<()> {
+        self.wait_on_conflicts(txn, || self.try_delete_edge(txn, eid))
+    }
+
+    fn try_delete_edge(&self, txn: &Arc<MemTransaction>, eid: EdgeId) -> StorageResult<()> {
+        txn.check_writable()?;
         // Atomically retrieve the versioned edge (check existence).
         let entry = self.edges.get(&eid).ok_or(StorageError::EdgeNotFound(
             EdgeNotFoundError::EdgeNotFound(eid.to_string()),
@@ -825,6 +1183,19 @@ pub fn set_vertex_property(
         indices: Vec<usize>,
         props: Vec<ScalarValue>,
     ) -> StorageResult<()> {
+        self.wait_on_conflicts(txn, || {
+            self.try_set_vertex_property(txn, vid, indices.clone(), props.clone())
+        })
+    }
+
+    fn try_set_vertex_property(
+        &self,
+        txn: &Arc<MemTransaction>,
+        vid: VertexId,
+        indices: Vec<usize>,
+        props: Vec<ScalarValue>,
+    ) -> StorageResult<()> {
+        txn.check_writable()?;
         // Atomically retrieve the versioned vertex (check existence).
         let entry = self.vertices.get(&vid).ok_or(StorageError::VertexNotFound(
             VertexNotFoundError::VertexNotFound(vid.to_string()),
@@ -839,6 +1210,11 @@ pub fn set_vertex_property(
             props.clone(),
             SetVertexProps
         );
+        let label_id = entry.chain.current.read().unwrap().data.label_id;
+        for (&index, prop) in indices.iter().zip(&props) {
+            self.statistics.update(label_id, index, prop);
+            self.property_indexes.update(label_id, vid, index, prop);
+        }
 
         // Write to WAL
         let wal_entry = RedoEntry {
@@ -860,6 +1236,19 @@ pub fn set_edge_property(
         indices: Vec<usize>,
         props: Vec<ScalarValue>,
     ) -> StorageResult<()> {
+        self.wait_on_conflicts(txn, || {
+            self.try_set_edge_property(txn, eid, indices.clone(), props.clone())
+        })
+    }
+
+    fn try_set_edge_property(
+        &self,
+        txn: &Arc<MemTransaction>,
+        eid: EdgeId,
+        indices: Vec<usize>,
+        props: Vec<ScalarValue>,
+    ) -> StorageResult<()> {
+        txn.check_writable()?;
         // Atomically retrieve the versioned edge (check existence).
         let entry = self.edges.get(&eid).ok_or(StorageError::EdgeNotFound(
             EdgeNotFoundError::EdgeNotFound(eid.to_string()),
@@ -1034,6 +1423,59 @@ pub fn build_vector_index(
         Ok(())
     }
 
+    /// Builds the property index of `key` from the latest versions of the vertices, unless it
+    /// already exists. The values written afterwards are indexed as they are written.
+    pub fn build_property_index(&self, key: PropertyIndexKey) {
+        if !self.property_indexes.create(key) {
+            return;
+        }
+        for entry in self.vertices.iter() {
+            let current = entry.chain.current.read().unwrap();
+            if current.data.label_id != key.label_id {
+                continue;
+            }
+            if let Some(value) = current.data.properties().get(key.property_id as usize) {
+                self.property_indexes.update(
+                    key.label_id,
+                    *entry.key(),
+                    key.property_id as usize,
+                    value,
+                );
+            }
+        }
+    }
+
+    /// Returns the ids of the vertices visible to `txn` which are labeled `key.label_id` and whose
+    /// property `key.property_id` is `value`, in ascending order, using the property index of
+    /// `key` instead of scanning the vertices.
+    pub fn lookup_property_index(
+        &self,
+        txn: &Arc<MemTransaction>,
+        key: PropertyIndexKey,
+        value: &ScalarValue,
+    ) -> StorageResult<Vec<VertexId>> {
+        let candidates = self
+            .property_indexes
+            .get(key, value)
+            .ok_or(StorageError::PropertyIndexNotFound(key))?;
+        // The index may hold stale entries, so the candidates are checked against the versions
+        // visible to the transaction.
+        let mut vids = Vec::with_capacity(candidates.len());
+        for vid in candidates {
+            let vertex = match self.get_vertex(txn, vid) {
+                Ok(vertex) => vertex,
+                Err(StorageError::VertexNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            if vertex.label_id == key.label_id
+                && vertex.properties().get(key.property_id as usize) == Some(value)
+            {
+                vids.push(vid);
+            }
+        }
+        Ok(vids)
+    }
+
     /// Get vector index for the specified label and property
     pub fn get_vector_index(
         &self,
@@ -1200,12 +1642,15 @@ pub fn delete_from_vector_index(
 fn check_write_conflict(commit_ts: Timestamp, txn: &Arc<MemTransaction>) -> StorageResult<()> {
     match commit_ts {
         // If the vertex is modified by other transactions, return write-write conflict
-        ts if ts.is_txn_id() && ts != txn.txn_id() => Err(StorageError::Transaction(
-            TransactionError::WriteWriteConflict(format!(
-                "Data is being modified by transaction {:?}",
-                ts
-            )),
-        )),
+        ts if ts.is_txn_id() && ts != txn.txn_id() => {
+            txn.blocked_by.store(ts.raw(), Ordering::Release);
+            Err(StorageError::Transaction(
+                TransactionError::WriteWriteConflict(format!(
+                    "Data is being modified by transaction {:?}",
+                    ts
+                )),
+            ))
+        }
         // If the vertex is committed by other transactions and its commit timestamp is greater
         // than the start timestamp of the current transaction, return version not visible
         ts if ts.is_commit_ts() && ts > txn.start_ts() => Err(StorageError::Transaction(
@@ -1220,7 +1665,9 @@ fn check_write_conflict(commit_ts: Timestamp, txn: &Arc<MemTransaction>) -> Stor
 
 #[cfg(test)]
 pub mod tests {
-    use std::fs;
+    use std::sync::Barrier;
+    use std::time::Duration;
+    use std::{fs, thread};
 
     use minigu_common::types::{LabelId, PropertyId};
     use minigu_common::value::{F32, ScalarValue, VectorValue};
@@ -1228,7 +1675,9 @@ pub mod tests {
     use {Edge, Vertex};
 
     use super::*;
+    use crate::error::CheckpointError;
     use crate::model::properties::PropertyRecord;
+    use crate::tp::transaction::CommitValidator;
 
     const PERSON: LabelId = LabelId::new(1).unwrap();
     const FRIEND: LabelId = LabelId::new(2).unwrap();
@@ -1281,7 +1730,10 @@ pub fn mock_wal_config() -> WalManagerConfig {
         let path = temp_file.path().to_owned();
         // TODO: Pass the temp file to the caller so that it can be cleaned up.
         temp_file.leak();
-        WalManagerConfig { wal_path: path }
+        WalManagerConfig {
+            wal_path: path,
+            ..Default::default()
+        }
     }
 
     pub struct Cleaner {
@@ -1593,6 +2045,42 @@ fn test_basic_commit_flow() {
         assert!(txn2.commit().is_ok());
     }
 
+    #[test]
+    fn test_allocate_vertex_id() {
+        let (graph, _cleaner) = mock_empty_graph();
+        let txn1 = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        let txn2 = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        // Concurrent transactions are given distinct ids before either creates its vertex.
+        let vid1 = graph.allocate_vertex_id();
+        let vid2 = graph.allocate_vertex_id();
+        assert_ne!(vid1, vid2);
+        graph
+            .create_vertex(&txn1, create_vertex(vid1, PERSON, vec![]))
+            .unwrap();
+        graph
+            .create_vertex(&txn2, create_vertex(vid2, PERSON, vec![]))
+            .unwrap();
+        txn1.commit().unwrap();
+        txn2.commit().unwrap();
+
+        // Ids follow the ids of the vertices created with an id of their own.
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        graph
+            .create_vertex(&txn, create_vertex(100, PERSON, vec![]))
+            .unwrap();
+        txn.abort().unwrap();
+        assert_eq!(graph.allocate_vertex_id(), 101);
+    }
+
     #[test]
     fn test_mvcc_version_chain() {
         let (graph, _cleaner) = mock_graph();
@@ -1758,6 +2246,453 @@ fn test_rollback_consistency() {
         assert!(graph.get_vertex(&txn_check, vid1).is_err());
     }
 
+    #[test]
+    fn test_rollback_to_savepoint() {
+        let (graph, _cleaner) = mock_graph();
+        let person = |vid, name: &str| {
+            create_vertex(
+                vid,
+                PERSON,
+                vec![
+                    ScalarValue::String(Some(name.to_string())),
+                    ScalarValue::Int32(Some(30)),
+                ],
+            )
+        };
+        let set_age = |txn: &Arc<MemTransaction>, age| {
+            graph
+                .set_vertex_property(txn, 1, vec![1], vec![ScalarValue::Int32(Some(age))])
+                .unwrap()
+        };
+        let changes = graph.subscribe(1);
+
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        graph.create_vertex(&txn, person(10, "Eve")).unwrap();
+        txn.savepoint("sp");
+        graph.create_vertex(&txn, person(11, "Frank")).unwrap();
+        set_age(&txn, 26);
+        set_age(&txn, 27);
+        txn.rollback_to_savepoint("sp").unwrap();
+        // The savepoint is kept after rolling back to it.
+        set_age(&txn, 28);
+        txn.rollback_to_savepoint("sp").unwrap();
+        txn.release_savepoint("sp").unwrap();
+        assert!(txn.rollback_to_savepoint("sp").is_err());
+        txn.commit().unwrap();
+
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        assert!(graph.get_vertex(&txn, 10).is_ok());
+        assert!(graph.get_vertex(&txn, 11).is_err());
+        assert_eq!(
+            graph.get_vertex(&txn, 1).unwrap().properties()[1],
+            ScalarValue::Int32(Some(25))
+        );
+        txn.abort().unwrap();
+        // Only the modifications made before the savepoint are logged.
+        let committed = changes.try_recv().unwrap();
+        assert!(matches!(
+            committed.deltas.as_slice(),
+            [DeltaOp::CreateVertex(vertex)] if vertex.vid() == 10
+        ));
+    }
+
+    #[test]
+    fn test_compact_adjacency() {
+        const NUM_VERTICES: u64 = 150;
+        const ROUNDS: usize = 5;
+        let (graph, _cleaner) = mock_empty_graph();
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        for vid in 1..=NUM_VERTICES {
+            graph
+                .create_vertex(&txn, create_vertex(vid, PERSON, vec![]))
+                .unwrap();
+        }
+        // Every vertex follows every vertex, itself included.
+        let mut eid = 0;
+        for src in 1..=NUM_VERTICES {
+            for dst in 1..=NUM_VERTICES {
+                eid += 1;
+                let edge = create_edge(eid, src, dst, FOLLOW, vec![]);
+                graph.create_edge(&txn, edge).unwrap();
+            }
+        }
+        txn.commit().unwrap();
+
+        // Expands every vertex `ROUNDS` times, returning the neighbors of the last round.
+        let expand = || {
+            let txn = graph.txn_manager().begin_read_only_transaction().unwrap();
+            let mut neighbors = Vec::new();
+            for _ in 0..ROUNDS {
+                neighbors.clear();
+                for vid in 1..=NUM_VERTICES {
+                    neighbors.extend(txn.iter_adjacency_outgoing(vid).map(Result::unwrap));
+                    neighbors.extend(txn.iter_adjacency(vid).map(Result::unwrap));
+                }
+            }
+            txn.commit().unwrap();
+            neighbors
+        };
+        // Returns the neighbors of the skip lists, which reads of the packed lists must match.
+        let skip_list_neighbors = || {
+            let mut neighbors = Vec::new();
+            for vid in 1..=NUM_VERTICES {
+                let adj = graph.adjacency_list.get(&vid).unwrap();
+                let outgoing: Vec<_> = adj.outgoing().iter().map(|entry| *entry.value()).collect();
+                let mut both: Vec<_> = adj.incoming().iter().map(|entry| *entry.value()).collect();
+                neighbors.extend_from_slice(&outgoing);
+                both.extend(outgoing);
+                both.sort();
+                both.dedup();
+                neighbors.extend(both);
+            }
+            neighbors
+        };
+        let packed_list =
+            |vid: VertexId| graph.adjacency_list.get(&vid).unwrap().outgoing().packed();
+
+        let scattered = expand();
+        // Each vertex has one outgoing neighbor per vertex, and the self-loop is seen once.
+        let num_edges = (NUM_VERTICES * NUM_VERTICES) as usize;
+        assert_eq!(
+            scattered.len(),
+            num_edges + (2 * num_edges - NUM_VERTICES as usize)
+        );
+        assert_eq!(graph.compact_adjacency(), 2 * NUM_VERTICES as usize);
+        assert_eq!(graph.compact_adjacency(), 0, "lists should be packed once");
+        assert_eq!(expand(), scattered);
+
+        // Writes are overlaid on the packed lists they modify, and seen by later reads.
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        eid += 1;
+        graph
+            .create_edge(&txn, create_edge(eid, 1, 2, FRIEND, vec![]))
+            .unwrap();
+        assert_eq!(
+            txn.iter_adjacency_outgoing(1).count(),
+            NUM_VERTICES as usize + 1
+        );
+        txn.commit().unwrap();
+        assert!(packed_list(1).unwrap().has_overlay());
+        assert!(!packed_list(3).unwrap().has_overlay());
+        let written = expand();
+        assert_eq!(written.len(), scattered.len() + 3);
+        assert_eq!(written, skip_list_neighbors());
+
+        // Removals, e.g., by the garbage collection of deleted edges, are overlaid as well.
+        let removed = Neighbor::new(FOLLOW, 2, 2);
+        graph
+            .adjacency_list
+            .get(&1)
+            .unwrap()
+            .outgoing()
+            .remove(&removed);
+        assert!(!packed_list(1).unwrap().to_vec().contains(&removed));
+        assert_eq!(expand(), skip_list_neighbors());
+
+        // Large overlays are folded into the packed arrays.
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        for dst in 1..=NUM_VERTICES {
+            eid += 1;
+            graph
+                .create_edge(&txn, create_edge(eid, 3, dst, FRIEND, vec![]))
+                .unwrap();
+        }
+        txn.commit().unwrap();
+        assert!(packed_list(3).unwrap().overlay_len() <= MAX_OVERLAY_LEN);
+        assert_eq!(expand(), skip_list_neighbors());
+
+        // Compactions fold the remaining overlays: those of the outgoing lists of vertices 1 and 3,
+        // and of the incoming lists of the neighbors of vertex 3.
+        assert_eq!(graph.compact_adjacency(), NUM_VERTICES as usize + 2);
+        assert!(!packed_list(1).unwrap().has_overlay());
+        assert_eq!(graph.compact_adjacency(), 0);
+        assert_eq!(expand(), skip_list_neighbors());
+    }
+
+    #[test]
+    fn test_commit_validator() {
+        let (graph, _cleaner) = mock_graph();
+        // Every person must have an age.
+        let validator: CommitValidator = Arc::new(|deltas| {
+            for delta in deltas {
+                if let DeltaOp::CreateVertex(vertex) = delta {
+                    let age = vertex.properties().get(1);
+                    if vertex.label_id == PERSON && age.is_none_or(|age| age.is_null()) {
+                        return Err(format!("person {} has no age", vertex.vid()));
+                    }
+                }
+            }
+            Ok(())
+        });
+
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        txn.add_commit_validator(validator.clone());
+        graph.create_vertex(&txn, create_vertex_eve()).unwrap();
+        let ageless = create_vertex(6, PERSON, vec![ScalarValue::String(Some("Ann".into()))]);
+        graph.create_vertex(&txn, ageless).unwrap();
+        let err = txn.commit().unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::Transaction(TransactionError::CommitRejected(message))
+                if message == "person 6 has no age"
+        ));
+
+        // None of the modifications of the rejected transaction were committed.
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        assert!(graph.get_vertex(&txn, 5).is_err());
+        assert!(graph.get_vertex(&txn, 6).is_err());
+        txn.add_commit_validator(validator);
+        graph.create_vertex(&txn, create_vertex_eve()).unwrap();
+        txn.commit().unwrap();
+    }
+
+    #[test]
+    fn test_analyze() {
+        let (graph, _cleaner) = mock_graph();
+        assert_eq!(graph.vertex_counts(), None);
+        assert_eq!(graph.modifications_since_analysis(), None);
+        let counts = graph.analyze().unwrap();
+        assert_eq!(counts.total(), 4);
+        assert_eq!(counts.get(PERSON), 4);
+        assert_eq!(graph.modifications_since_analysis(), Some(0));
+
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        graph.create_vertex(&txn, create_vertex_eve()).unwrap();
+        txn.commit().unwrap();
+        // The counts are kept as of the analysis.
+        assert_eq!(graph.vertex_counts(), Some(counts));
+        assert_eq!(graph.modifications_since_analysis(), Some(1));
+        assert_eq!(graph.analyze().unwrap().get(PERSON), 5);
+        assert_eq!(graph.modifications_since_analysis(), Some(0));
+    }
+
+    #[test]
+    fn test_property_index() {
+        let (graph, _cleaner) = mock_graph();
+        let key = PropertyIndexKey::new(PERSON, 1);
+        let age = |age: i32| ScalarValue::Int32(Some(age));
+        let begin = || {
+            graph
+                .txn_manager()
+                .begin_transaction(IsolationLevel::Serializable)
+                .unwrap()
+        };
+        let txn = begin();
+        assert!(matches!(
+            graph.lookup_property_index(&txn, key, &age(24)),
+            Err(StorageError::PropertyIndexNotFound(_))
+        ));
+        graph.build_property_index(key);
+        assert_eq!(
+            graph.lookup_property_index(&txn, key, &age(24)).unwrap(),
+            [3]
+        );
+        txn.abort().unwrap();
+
+        // The vertices created or modified after the index is built are looked up, but not the
+        // ones whose value is overwritten or which are deleted.
+        let txn = begin();
+        graph.create_vertex(&txn, create_vertex_eve()).unwrap();
+        graph
+            .set_vertex_property(&txn, 1, vec![1], vec![age(24)])
+            .unwrap();
+        graph
+            .set_vertex_property(&txn, 3, vec![1], vec![age(30)])
+            .unwrap();
+        assert_eq!(
+            graph.lookup_property_index(&txn, key, &age(24)).unwrap(),
+            [1, 5]
+        );
+        // Uncommitted modifications are only visible to their transaction.
+        let other = begin();
+        assert_eq!(
+            graph.lookup_property_index(&other, key, &age(24)).unwrap(),
+            [3]
+        );
+        other.abort().unwrap();
+        txn.commit().unwrap();
+
+        let txn = begin();
+        graph.delete_vertex(&txn, 5).unwrap();
+        assert_eq!(
+            graph.lookup_property_index(&txn, key, &age(24)).unwrap(),
+            [1]
+        );
+        assert_eq!(
+            graph.lookup_property_index(&txn, key, &age(30)).unwrap(),
+            [3]
+        );
+        txn.commit().unwrap();
+    }
+
+    #[test]
+    fn test_read_only_transaction() {
+        let (graph, _cleaner) = mock_graph();
+        let age =
+            |txn: &Arc<MemTransaction>| graph.get_vertex(txn, 1).unwrap().properties()[1].clone();
+
+        let reader = graph.txn_manager().begin_read_only_transaction().unwrap();
+        assert!(reader.is_read_only());
+        let writer = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        graph
+            .set_vertex_property(&writer, 1, vec![1], vec![ScalarValue::Int32(Some(26))])
+            .unwrap();
+        writer.commit().unwrap();
+        // The reader keeps reading the snapshot as of its start.
+        assert_eq!(age(&reader), ScalarValue::Int32(Some(25)));
+
+        let is_read_only_error = |result: StorageResult<()>| {
+            matches!(
+                result,
+                Err(StorageError::Transaction(TransactionError::ReadOnly(_)))
+            )
+        };
+        assert!(is_read_only_error(graph.set_vertex_property(
+            &reader,
+            1,
+            vec![1],
+            vec![ScalarValue::Int32(Some(27))]
+        )));
+        assert!(is_read_only_error(
+            graph
+                .create_vertex(&reader, create_vertex_eve())
+                .map(|_| ())
+        ));
+        assert!(is_read_only_error(graph.delete_vertex(&reader, 1)));
+        assert!(is_read_only_error(graph.delete_edge(&reader, 1)));
+        assert_eq!(age(&reader), ScalarValue::Int32(Some(25)));
+        reader.commit().unwrap();
+
+        let reader = graph.txn_manager().begin_read_only_transaction().unwrap();
+        assert_eq!(age(&reader), ScalarValue::Int32(Some(26)));
+        reader.abort().unwrap();
+    }
+
+    #[test]
+    fn test_deadlock_detection() {
+        let (graph, _cleaner) = mock_graph();
+        let timeout = Duration::from_secs(5);
+        graph.txn_manager().set_lock_wait_timeout(Some(timeout));
+        let barrier = Barrier::new(2);
+
+        // Each transaction modifies a vertex, then the one the other modified.
+        let start = Instant::now();
+        let committed: Vec<bool> = thread::scope(|s| {
+            let handles: Vec<_> = [(1, 2), (2, 1)]
+                .into_iter()
+                .map(|(first, second)| {
+                    let (graph, barrier) = (&graph, &barrier);
+                    s.spawn(move || {
+                        let txn = graph
+                            .txn_manager()
+                            .begin_transaction(IsolationLevel::Serializable)
+                            .unwrap();
+                        let set_age = |vid| {
+                            graph.set_vertex_property(
+                                &txn,
+                                vid,
+                                vec![1],
+                                vec![ScalarValue::Int32(Some(30))],
+                            )
+                        };
+                        set_age(first).unwrap();
+                        barrier.wait();
+                        match set_age(second) {
+                            Ok(()) => {
+                                txn.commit().unwrap();
+                                true
+                            }
+                            Err(e) => {
+                                assert!(matches!(
+                                    e,
+                                    StorageError::Transaction(TransactionError::Deadlock(_))
+                                ));
+                                txn.abort().unwrap();
+                                false
+                            }
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        // One transaction fails right away, letting the other one proceed.
+        assert_eq!(committed.iter().filter(|&&c| c).count(), 1);
+        assert!(start.elapsed() < timeout);
+    }
+
+    #[test]
+    fn test_lock_wait_timeout() {
+        let (graph, _cleaner) = mock_graph();
+        let timeout = Duration::from_millis(50);
+        graph.txn_manager().set_lock_wait_timeout(Some(timeout));
+        let set_age = |txn: &Arc<MemTransaction>| {
+            graph.set_vertex_property(txn, 1, vec![1], vec![ScalarValue::Int32(Some(30))])
+        };
+
+        let holder = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        set_age(&holder).unwrap();
+        let waiter = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        let start = Instant::now();
+        let err = set_age(&waiter).unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::Transaction(TransactionError::LockWaitTimeout(_))
+        ));
+        assert!(start.elapsed() >= timeout);
+        waiter.abort().unwrap();
+
+        // The waiter proceeds once the holder finishes.
+        graph
+            .txn_manager()
+            .set_lock_wait_timeout(Some(Duration::from_secs(5)));
+        let waiter = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(10));
+                holder.abort().unwrap();
+            });
+            set_age(&waiter).unwrap();
+        });
+        waiter.commit().unwrap();
+    }
+
     #[test]
     fn test_property_update_flow() {
         let (graph, _cleaner) = mock_graph();
@@ -2118,6 +3053,53 @@ fn test_garbage_collection_after_delete_vertex() {
         let _ = txn3.abort();
     }
 
+    #[test]
+    fn test_read_as_of_timestamp() {
+        let (graph, _cleaner) = mock_graph();
+        let as_of = |ts| {
+            graph
+                .txn_manager()
+                .begin_transaction_as_of(ts, IsolationLevel::Snapshot)
+        };
+        let age =
+            |txn: &Arc<MemTransaction>| graph.get_vertex(txn, 1).unwrap().properties()[1].clone();
+
+        let before = graph.txn_manager().latest_commit_ts();
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        graph
+            .set_vertex_property(&txn, 1, vec![1], vec![ScalarValue::Int32(Some(26))])
+            .unwrap();
+        let after = txn.commit().unwrap();
+
+        // A transaction as of the timestamp before the update sees the previous value, and holds
+        // back the garbage collection of the versions it reads.
+        let txn = as_of(before).unwrap();
+        assert_eq!(age(&txn), ScalarValue::Int32(Some(25)));
+        graph.txn_manager.garbage_collect(&graph).unwrap();
+        assert_eq!(age(&txn), ScalarValue::Int32(Some(25)));
+        txn.abort().unwrap();
+        let txn = as_of(after).unwrap();
+        assert_eq!(age(&txn), ScalarValue::Int32(Some(26)));
+        txn.abort().unwrap();
+
+        // The versions are collected once no transaction reads them, unless they are retained.
+        graph.txn_manager().set_version_retention(u64::MAX);
+        graph.txn_manager.garbage_collect(&graph).unwrap();
+        let txn = as_of(before).unwrap();
+        assert_eq!(age(&txn), ScalarValue::Int32(Some(25)));
+        txn.abort().unwrap();
+        graph.txn_manager().set_version_retention(0);
+        graph.txn_manager.garbage_collect(&graph).unwrap();
+        assert!(as_of(before).is_err());
+        let txn = as_of(after).unwrap();
+        assert_eq!(age(&txn), ScalarValue::Int32(Some(26)));
+        txn.abort().unwrap();
+        assert!(as_of(Timestamp::max_commit_ts()).is_err());
+    }
+
     #[test]
     fn test_delete_vertex_with_edges() {
         let (graph, _cleaner) = mock_graph();
@@ -2275,6 +3257,46 @@ fn test_wal_replay() {
         txn_after.abort().unwrap();
     }
 
+    #[test]
+    fn test_no_durability() {
+        let checkpoint_config = mock_checkpoint_config();
+        let wal_path = std::env::temp_dir().join(format!("test_no_wal_{}.log", std::process::id()));
+        let wal_config = WalManagerConfig {
+            wal_path,
+            durability: Durability::None,
+            ..Default::default()
+        };
+        let _cleaner = Cleaner::new(&checkpoint_config, &wal_config);
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config.clone(), wal_config.clone());
+
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        let vertex = create_vertex(1, PERSON, vec![ScalarValue::Int32(Some(1))]);
+        graph.create_vertex(&txn, vertex.clone()).unwrap();
+        txn.commit().unwrap();
+
+        // The write is visible to later transactions, but has not been logged
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        assert_eq!(graph.get_vertex(&txn, 1).unwrap(), vertex);
+        txn.abort().unwrap();
+        assert!(graph.wal_manager.wal().is_none());
+        assert!(graph.wal_manager.read_all().unwrap().is_empty());
+        assert!(!wal_config.wal_path.exists());
+
+        // Hence it cannot be recovered
+        let recovered = MemoryGraph::with_config_recovered(checkpoint_config, wal_config);
+        let txn = recovered
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        assert!(recovered.get_vertex(&txn, 1).is_err());
+    }
+
     #[test]
     fn test_checkpoint_and_wal_recovery() {
         // Creates a new graph
@@ -2300,7 +3322,7 @@ fn test_checkpoint_and_wal_recovery() {
         txn1.commit().unwrap();
 
         // Check the size of wal entries before checkpoint
-        let entries = graph.wal_manager.wal().read().unwrap().read_all().unwrap();
+        let entries = graph.wal_manager.read_all().unwrap();
         assert_eq!(entries.len(), 3); // txn1 begin, create vertex, commit
 
         // Create a checkpoint
@@ -2309,7 +3331,7 @@ fn test_checkpoint_and_wal_recovery() {
             .unwrap();
 
         // Check the size of wal entries after checkpoint
-        let entries = graph.wal_manager.wal().read().unwrap().read_all().unwrap();
+        let entries = graph.wal_manager.read_all().unwrap();
         assert_eq!(entries.len(), 0); // Should be empty as we truncate the WAL
 
         // Create more data (after checkpoint)
@@ -2328,20 +3350,14 @@ fn test_checkpoint_and_wal_recovery() {
         txn2.commit().unwrap();
 
         // Check the size of wal entries before recovery
-        let entries = graph.wal_manager.wal().read().unwrap().read_all().unwrap();
+        let entries = graph.wal_manager.read_all().unwrap();
         assert_eq!(entries.len(), 3); // txn2 begin, create vertex, commit
 
         // Now recover a new graph from checkpoint and WAL
         let recovered_graph = MemoryGraph::with_config_recovered(checkpoint_config, wal_config);
 
         // Check the size of wal entries after recovery
-        let entries = recovered_graph
-            .wal_manager
-            .wal()
-            .read()
-            .unwrap()
-            .read_all()
-            .unwrap();
+        let entries = recovered_graph.wal_manager.read_all().unwrap();
 
         assert_eq!(entries.len(), 3); // Should be still 3, since we didn't truncate the WAL
 
@@ -2366,6 +3382,159 @@ fn test_checkpoint_and_wal_recovery() {
         );
     }
 
+    #[test]
+    fn test_recovery_skips_corrupted_checkpoint() {
+        let checkpoint_config = mock_checkpoint_config();
+        let wal_config = mock_wal_config();
+        let _cleaner = Cleaner::new(&checkpoint_config, &wal_config);
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config.clone(), wal_config.clone());
+
+        let insert_vertex = |vid: VertexId, name: &str| {
+            let txn = graph
+                .txn_manager()
+                .begin_transaction(IsolationLevel::Serializable)
+                .unwrap();
+            let vertex = Vertex::new(
+                vid,
+                LabelId::new(1).unwrap(),
+                PropertyRecord::new(vec![ScalarValue::String(Some(name.to_string()))]),
+            );
+            graph.create_vertex(&txn, vertex).unwrap();
+            txn.commit().unwrap();
+        };
+
+        insert_vertex(1, "Before first checkpoint");
+        graph.create_managed_checkpoint(None).unwrap();
+        insert_vertex(2, "Between checkpoints");
+        // Make sure the two checkpoint files have distinct modification times
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let newest_id = graph.create_managed_checkpoint(None).unwrap();
+        insert_vertex(3, "After second checkpoint");
+
+        // Truncate the newest checkpoint file
+        let newest_path = graph
+            .checkpoint_manager
+            .as_ref()
+            .unwrap()
+            .get_checkpoint(&newest_id)
+            .unwrap()
+            .path
+            .clone();
+        let len = fs::metadata(&newest_path).unwrap().len();
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&newest_path)
+            .unwrap()
+            .set_len(len / 2)
+            .unwrap();
+
+        // Recovery falls back to the first checkpoint and replays the WAL from there
+        let recovered_graph = MemoryGraph::with_config_recovered(checkpoint_config, wal_config);
+        let txn = recovered_graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        for vid in 1..=3 {
+            assert!(recovered_graph.get_vertex(&txn, vid).is_ok());
+        }
+        txn.abort().unwrap();
+    }
+
+    #[test]
+    fn test_point_in_time_recovery() {
+        let checkpoint_config = mock_checkpoint_config();
+        let wal_config = mock_wal_config();
+        let _cleaner = Cleaner::new(&checkpoint_config, &wal_config);
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config.clone(), wal_config.clone());
+
+        let insert_vertex = |vid: VertexId| {
+            let txn = graph
+                .txn_manager()
+                .begin_transaction(IsolationLevel::Serializable)
+                .unwrap();
+            let vertex = Vertex::new(
+                vid,
+                PERSON,
+                PropertyRecord::new(vec![ScalarValue::String(Some(format!("v{vid}")))]),
+            );
+            graph.create_vertex(&txn, vertex).unwrap();
+            txn.commit().unwrap()
+        };
+
+        insert_vertex(1);
+        let target = insert_vertex(2);
+        insert_vertex(3);
+
+        // A bad write, which must be undone
+        let txn = graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        graph.delete_vertex(&txn, 1).unwrap();
+        let latest = txn.commit().unwrap();
+
+        // Nothing is known of the graph past the latest durable commit
+        let future = Timestamp::with_ts(latest.raw() + 1);
+        let result = MemoryGraph::recover_to_timestamp(
+            checkpoint_config.clone(),
+            wal_config.clone(),
+            future,
+        );
+        assert!(matches!(
+            result,
+            Err(StorageError::Checkpoint(
+                CheckpointError::InvalidRecoveryTarget(_)
+            ))
+        ));
+
+        let recovered_graph =
+            MemoryGraph::recover_to_timestamp(checkpoint_config, wal_config, target).unwrap();
+        let txn = recovered_graph
+            .txn_manager()
+            .begin_transaction(IsolationLevel::Serializable)
+            .unwrap();
+        assert!(recovered_graph.get_vertex(&txn, 1).is_ok());
+        assert!(recovered_graph.get_vertex(&txn, 2).is_ok());
+        assert!(recovered_graph.get_vertex(&txn, 3).is_err());
+        txn.abort().unwrap();
+    }
+
+    #[test]
+    fn test_point_in_time_recovery_before_checkpoints() {
+        let checkpoint_config = mock_checkpoint_config();
+        let wal_config = mock_wal_config();
+        let _cleaner = Cleaner::new(&checkpoint_config, &wal_config);
+        let graph = MemoryGraph::with_config_fresh(checkpoint_config.clone(), wal_config.clone());
+
+        let insert_vertex = |vid: VertexId| {
+            let txn = graph
+                .txn_manager()
+                .begin_transaction(IsolationLevel::Serializable)
+                .unwrap();
+            let vertex = Vertex::new(
+                vid,
+                PERSON,
+                PropertyRecord::new(vec![ScalarValue::String(Some(format!("v{vid}")))]),
+            );
+            graph.create_vertex(&txn, vertex).unwrap();
+            txn.commit().unwrap()
+        };
+
+        let target = insert_vertex(1);
+        insert_vertex(2);
+        graph.create_managed_checkpoint(None).unwrap();
+        insert_vertex(3);
+
+        // The WAL up to the checkpoint is gone, so the state as of `target` cannot be rebuilt
+        let result = MemoryGraph::recover_to_timestamp(checkpoint_config, wal_config, target);
+        assert!(matches!(
+            result,
+            Err(StorageError::Checkpoint(
+                CheckpointError::InvalidRecoveryTarget(_)
+            ))
+        ));
+    }
+
     #[test]
     fn test_vector_index_build_and_verify() -> StorageResult<()> {
         let (graph, _cleaner) = mock_empty_graph();
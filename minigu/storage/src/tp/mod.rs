@@ -1,4 +1,5 @@
 pub mod checkpoint;
+pub mod graph_diff;
 pub mod iterators;
 pub mod memory_graph;
 pub mod transaction;
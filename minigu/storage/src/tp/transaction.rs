@@ -1,4 +1,4 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock, RwLock};
 
 use dashmap::DashSet;
@@ -10,7 +10,7 @@
 pub use minigu_transaction::{IsolationLevel, Timestamp};
 
 use super::memory_graph::MemoryGraph;
-use crate::common::wal::StorageWal;
+use crate::common::model::vertex::Vertex;
 use crate::common::wal::graph_wal::{Operation, RedoEntry};
 use crate::common::{DeltaOp, SetPropsOp};
 use crate::error::{
@@ -23,11 +23,28 @@
 /// Type alias for storage-specific undo pointer
 pub type UndoPtr = GenericUndoPtr<DeltaOp>;
 
+/// A condition on vertices, by which a transaction may scan the graph.
+pub type VertexPredicate = Arc<dyn Fn(&Vertex) -> bool + Send + Sync>;
+
+/// A check of the modifications of a transaction, in the order they were made, run before it
+/// commits. Returning an error message rejects the commit, and aborts the transaction.
+pub type CommitValidator = Arc<dyn Fn(&[DeltaOp]) -> Result<(), String> + Send + Sync>;
+
+/// A named position in the modifications of a transaction, which it can roll back to.
+struct Savepoint {
+    name: String,
+    /// Length of the undo buffer when the savepoint was set
+    undo_len: usize,
+    /// Length of the redo buffer when the savepoint was set
+    redo_len: usize,
+}
+
 pub struct MemTransaction {
     graph: Arc<MemoryGraph>, // Reference to the associated in-memory graph
 
     // ---- Transaction Config ----
-    isolation_level: IsolationLevel, // Isolation level of the transaction
+    pub(super) isolation_level: IsolationLevel, // Isolation level of the transaction
+    read_only: bool,                            // Whether the transaction may not modify the graph
 
     // ---- Timestamp management ----
     /// Start timestamp assigned when the transaction begins
@@ -38,6 +55,8 @@ pub struct MemTransaction {
     // ---- Read sets ----
     pub(super) vertex_reads: DashSet<VertexId>, // Set of vertices read by this transaction
     pub(super) edge_reads: DashSet<EdgeId>,     // Set of edges read by this transaction
+    /// Predicates of the vertex scans of this transaction, used to detect phantoms
+    pub(super) vertex_predicates: RwLock<Vec<VertexPredicate>>,
 
     // ---- Undo logs ----
     pub(super) undo_buffer: RwLock<Vec<Arc<UndoEntry>>>,
@@ -45,6 +64,18 @@ pub struct MemTransaction {
     // ---- Write-ahead-log for crash recovery ----
     pub(super) redo_buffer: RwLock<Vec<RedoEntry>>,
 
+    // ---- Lock waits ----
+    /// Id of the transaction holding the entity of the last write-write conflict of this one
+    pub(super) blocked_by: AtomicU64,
+
+    // ---- Savepoints ----
+    /// Savepoints set by this transaction, in the order they were set
+    savepoints: RwLock<Vec<Savepoint>>,
+
+    // ---- Commit validators ----
+    /// Checks of the modifications of this transaction, run before it commits
+    commit_validators: RwLock<Vec<CommitValidator>>,
+
     // ---- Transaction state tracking ----
     /// Flag to track whether the transaction has been explicitly handled (committed or aborted)
     is_handled: Arc<AtomicBool>,
@@ -84,17 +115,23 @@ pub(super) fn with_memgraph(
         txn_id: Timestamp,
         start_ts: Timestamp,
         isolation_level: IsolationLevel,
+        read_only: bool,
     ) -> Self {
         Self {
             graph,
             isolation_level,
+            read_only,
             start_ts,
             commit_ts: OnceLock::new(),
             txn_id,
             vertex_reads: DashSet::new(),
             edge_reads: DashSet::new(),
+            vertex_predicates: RwLock::new(Vec::new()),
             undo_buffer: RwLock::new(Vec::new()),
             redo_buffer: RwLock::new(Vec::new()),
+            blocked_by: AtomicU64::new(0),
+            savepoints: RwLock::new(Vec::new()),
+            commit_validators: RwLock::new(Vec::new()),
             is_handled: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -147,6 +184,62 @@ pub(super) fn validate_read_sets(&self) -> StorageResult<()> {
             }
         }
 
+        self.validate_vertex_predicates()
+    }
+
+    /// Checks that no vertex modified since the transaction started satisfies the predicate of one
+    /// of its scans, in which case the scan would now return a vertex it missed (a phantom).
+    ///
+    /// Vertices that the scans did return are already covered by the vertex read set. This visits
+    /// every vertex of the graph, so it is only done for transactions which recorded a predicate.
+    fn validate_vertex_predicates(&self) -> StorageResult<()> {
+        let predicates = self.vertex_predicates.read().unwrap();
+        if predicates.is_empty() {
+            return Ok(());
+        }
+        for entry in self.graph.vertices.iter() {
+            let current = entry.chain.current.read().unwrap();
+            if current.commit_ts == self.txn_id
+                || current.commit_ts <= self.start_ts
+                || current.data.is_tombstone()
+            {
+                continue;
+            }
+            if predicates.iter().any(|predicate| predicate(&current.data)) {
+                return Err(StorageError::Transaction(
+                    TransactionError::ReadWriteConflict(format!(
+                        "Vertex {} matching a scan predicate is being modified by transaction {:?}",
+                        entry.key(),
+                        current.commit_ts
+                    )),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the transaction was begun by
+    /// [`begin_read_only_transaction`](super::txn_manager::MemTxnManager::begin_read_only_transaction),
+    /// in which case it cannot modify the graph.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Fails if the transaction is read-only.
+    pub(super) fn check_writable(&self) -> StorageResult<()> {
+        if self.read_only {
+            return Err(StorageError::Transaction(TransactionError::ReadOnly(
+                format!("transaction {:?} cannot modify the graph", self.txn_id),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Finishes a read-only transaction, which has neither modifications to apply or undo nor
+    /// entries in the WAL.
+    fn finish_read_only(&self) -> StorageResult<()> {
+        self.graph.txn_manager.finish_transaction(self)?;
+        self.is_handled.store(true, Ordering::Release);
         Ok(())
     }
 
@@ -184,10 +277,10 @@ pub(super) fn apply_deltas_for_read<T: FnMut(&UndoEntry)>(
             // Apply the delta to the vertex/edge
             callback(&undo_entry);
 
-            // If the timestamp of the entry is less than the txn_start_ts,
+            // If the timestamp of the entry is not greater than the txn_start_ts,
             // it means current version is the latest visible version,
             // no need to continue traversing the undo chain
-            if undo_entry.timestamp() < txn_start_ts {
+            if undo_entry.timestamp() <= txn_start_ts {
                 break;
             }
             undo_ptr = undo_entry.next();
@@ -206,6 +299,19 @@ pub fn commit_at(
         commit_ts: Option<Timestamp>,
         skip_wal: bool,
     ) -> StorageResult<Timestamp> {
+        // A read-only transaction reads a consistent snapshot, so it has nothing to validate, and
+        // committing it is finishing it. Its snapshot is returned in place of a commit timestamp.
+        if self.read_only {
+            self.finish_read_only()?;
+            return Ok(self.start_ts);
+        }
+
+        // Let the validators reject the modifications before any of them is committed.
+        if let Err(e) = self.run_commit_validators() {
+            self.abort()?;
+            return Err(e);
+        }
+
         let commit_ts = if let Some(commit_ts) = commit_ts {
             global_timestamp_generator()
                 .update_if_greater(commit_ts)
@@ -268,10 +374,14 @@ macro_rules! update_commit_ts {
                     DeltaOp::RemoveLabel(_) => todo!(),
                 }
             }
+            self.graph
+                .modifications
+                .fetch_add(undo_entries.len() as u64, Ordering::SeqCst);
         }
 
         // Step 4: Write redo entry and commit to WAL,
         // unless the function is called when recovering from WAL
+        let mut deltas = Vec::new();
         if !skip_wal {
             let redo_entries = self
                 .redo_buffer
@@ -284,13 +394,18 @@ macro_rules! update_commit_ts {
                     entry
                 })
                 .collect::<Vec<_>>();
+            // Keep the modifications for the subscribers to the changefeed, if any
+            if self.graph.changefeed.has_subscribers() {
+                deltas = redo_entries
+                    .iter()
+                    .filter_map(|entry| match &entry.op {
+                        Operation::Delta(delta) => Some(delta.clone()),
+                        _ => None,
+                    })
+                    .collect();
+            }
             for entry in redo_entries {
-                self.graph
-                    .wal_manager
-                    .wal()
-                    .write()
-                    .unwrap()
-                    .append(&entry)?;
+                self.graph.wal_manager.append(&entry)?;
             }
 
             // Write `Operation::CommitTransaction` to WAL
@@ -300,13 +415,8 @@ macro_rules! update_commit_ts {
                 iso_level: self.isolation_level,
                 op: Operation::CommitTransaction(commit_ts),
             };
-            self.graph
-                .wal_manager
-                .wal()
-                .write()
-                .unwrap()
-                .append(&wal_entry)?;
-            self.graph.wal_manager.wal().write().unwrap().flush()?;
+            self.graph.wal_manager.append(&wal_entry)?;
+            self.graph.wal_manager.commit()?;
         }
 
         // Step 5: Clean up transaction state and update the `latest_commit_ts`.
@@ -316,7 +426,11 @@ macro_rules! update_commit_ts {
             .store(commit_ts.raw(), Ordering::SeqCst);
         self.graph.txn_manager.finish_transaction(self)?;
 
-        // Step 6: Check if an auto checkpoint should be created
+        // Step 6: Publish the modifications to the changefeed, still holding the commit lock so
+        // that they are published in commit order
+        self.graph.changefeed.publish(commit_ts, deltas);
+
+        // Step 7: Check if an auto checkpoint should be created
         self.graph.check_auto_checkpoint()?;
 
         // Mark the transaction as handled
@@ -325,12 +439,119 @@ macro_rules! update_commit_ts {
         Ok(commit_ts)
     }
 
+    /// Adds `validator` to the checks of the modifications of the transaction, which are run in
+    /// the order they were added when it commits. The first one to fail rejects the commit with a
+    /// [`TransactionError::CommitRejected`].
+    pub fn add_commit_validator(&self, validator: CommitValidator) {
+        self.commit_validators.write().unwrap().push(validator);
+    }
+
+    fn run_commit_validators(&self) -> StorageResult<()> {
+        let validators = self.commit_validators.read().unwrap();
+        if validators.is_empty() {
+            return Ok(());
+        }
+        let deltas: Vec<_> = self
+            .redo_buffer
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|entry| match &entry.op {
+                Operation::Delta(delta) => Some(delta.clone()),
+                _ => None,
+            })
+            .collect();
+        for validator in validators.iter() {
+            validator(&deltas)
+                .map_err(|e| StorageError::Transaction(TransactionError::CommitRejected(e)))?;
+        }
+        Ok(())
+    }
+
+    /// Sets a savepoint named `name` at the current position of the transaction, which it can
+    /// then roll back to with [`rollback_to_savepoint`](Self::rollback_to_savepoint).
+    ///
+    /// A savepoint set with the name of an existing one hides it until it is released.
+    pub fn savepoint(&self, name: impl Into<String>) {
+        let undo_len = self.undo_buffer.read().unwrap().len();
+        let redo_len = self.redo_buffer.read().unwrap().len();
+        self.savepoints.write().unwrap().push(Savepoint {
+            name: name.into(),
+            undo_len,
+            redo_len,
+        });
+    }
+
+    /// Undoes the modifications made by the transaction since the savepoint named `name` was set.
+    ///
+    /// The savepoint is kept, so that the transaction can roll back to it again, but the ones set
+    /// after it are released.
+    pub fn rollback_to_savepoint(&self, name: &str) -> StorageResult<()> {
+        let mut savepoints = self.savepoints.write().unwrap();
+        let index = find_savepoint(&savepoints, name)?;
+        let savepoint = &savepoints[index];
+        let undo_entries: Vec<_> = self
+            .undo_buffer
+            .write()
+            .unwrap()
+            .drain(savepoint.undo_len..)
+            .collect();
+        // Undo the latest modifications first, so that the entities modified several times since
+        // the savepoint end up in the versions they had when it was set.
+        self.undo(undo_entries.into_iter().rev());
+        self.redo_buffer
+            .write()
+            .unwrap()
+            .truncate(savepoint.redo_len);
+        savepoints.truncate(index + 1);
+        Ok(())
+    }
+
+    /// Releases the savepoint named `name`, along with the ones set after it. The modifications
+    /// made since are kept.
+    pub fn release_savepoint(&self, name: &str) -> StorageResult<()> {
+        let mut savepoints = self.savepoints.write().unwrap();
+        let index = find_savepoint(&savepoints, name)?;
+        savepoints.truncate(index);
+        Ok(())
+    }
+
     pub fn abort_at(&self, skip_wal: bool) -> StorageResult<()> {
+        if self.read_only {
+            return self.finish_read_only();
+        }
+
         // Acquire write lock and drain the undo buffer
         let undo_entries: Vec<_> = self.undo_buffer.write().unwrap().drain(..).collect();
+        self.undo(undo_entries);
+
+        // Write `Operation::AbortTransaction` to WAL,
+        // unless the function is called when recovering from WAL
+        if !skip_wal {
+            let lsn = self.graph.wal_manager.next_lsn();
+            let wal_entry = RedoEntry {
+                lsn,
+                txn_id: self.txn_id(),
+                iso_level: self.isolation_level,
+                op: Operation::AbortTransaction,
+            };
+            self.graph.wal_manager.append(&wal_entry)?;
+            self.graph.wal_manager.flush()?;
+        }
+
+        // Remove transaction from transaction manager
+        self.graph.txn_manager.finish_transaction(self)?;
+
+        // Mark the transaction as handled
+        self.is_handled.store(true, Ordering::Release);
+
+        Ok(())
+    }
 
-        // Process all undo entries
-        for undo_entry in undo_entries.into_iter() {
+    /// Undoes the given modifications of the transaction, each restoring the version of the
+    /// entity it replaced, unless the entity was restored by a previous one.
+    fn undo(&self, undo_entries: impl IntoIterator<Item = Arc<UndoEntry>>) {
+        for undo_entry in undo_entries {
             let commit_ts = undo_entry.timestamp();
             let next = undo_entry.next();
             match undo_entry.delta() {
@@ -419,36 +640,19 @@ pub fn abort_at(&self, skip_wal: bool) -> StorageResult<()> {
                 DeltaOp::RemoveLabel(_) => todo!(),
             }
         }
-
-        // Write `Operation::AbortTransaction` to WAL,
-        // unless the function is called when recovering from WAL
-        if !skip_wal {
-            let lsn = self.graph.wal_manager.next_lsn();
-            let wal_entry = RedoEntry {
-                lsn,
-                txn_id: self.txn_id(),
-                iso_level: self.isolation_level,
-                op: Operation::AbortTransaction,
-            };
-            self.graph
-                .wal_manager
-                .wal()
-                .write()
-                .unwrap()
-                .append(&wal_entry)?;
-            self.graph.wal_manager.wal().write().unwrap().flush()?;
-        }
-
-        // Remove transaction from transaction manager
-        self.graph.txn_manager.finish_transaction(self)?;
-
-        // Mark the transaction as handled
-        self.is_handled.store(true, Ordering::Release);
-
-        Ok(())
     }
 }
 
+/// Returns the index of the latest savepoint named `name` in `savepoints`.
+fn find_savepoint(savepoints: &[Savepoint], name: &str) -> StorageResult<usize> {
+    savepoints
+        .iter()
+        .rposition(|savepoint| savepoint.name == name)
+        .ok_or_else(|| {
+            StorageError::Transaction(TransactionError::SavepointNotFound(name.to_string()))
+        })
+}
+
 impl Drop for MemTransaction {
     fn drop(&mut self) {
         // Only perform automatic rollback if:
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crossbeam_skiplist::SkipMap;
 use minigu_common::types::{EdgeId, VertexId};
@@ -13,11 +15,12 @@
 use super::transaction::{IsolationLevel, MemTransaction, UndoEntry};
 use crate::common::DeltaOp;
 use crate::common::model::edge::{Edge, Neighbor};
-use crate::common::wal::StorageWal;
 use crate::common::wal::graph_wal::{Operation, RedoEntry};
 use crate::error::{StorageError, StorageResult, TransactionError};
 
 const GC_TRIGGER_THRESHOLD: usize = 50;
+/// The interval at which a transaction waiting for another one checks whether it finished.
+const LOCK_WAIT_INTERVAL: Duration = Duration::from_millis(1);
 
 /// A manager for managing transactions.
 pub struct MemTxnManager {
@@ -25,6 +28,9 @@ pub struct MemTxnManager {
     pub(super) graph: Weak<MemoryGraph>,
     /// Active transactions' txn.
     pub(super) active_txns: SkipMap<Timestamp, Arc<MemTransaction>>,
+    /// The start timestamps of the active transactions, along with their ids, in ascending order.
+    /// They differ from the order of the ids for the transactions reading as of a past timestamp.
+    active_start_ts: SkipMap<(Timestamp, Timestamp), ()>,
     /// All transactions, running or committed.
     pub(super) committed_txns: SkipMap<Timestamp, Arc<MemTransaction>>,
     /// Commit lock to enforce serial commit order
@@ -35,6 +41,17 @@ pub struct MemTxnManager {
     watermark: AtomicU64,
     /// Last garbage collection timestamp
     last_gc_ts: AtomicU64,
+    /// The largest commit timestamp of the transactions collected so far. The versions as of the
+    /// earlier timestamps can no longer be read.
+    gc_horizon: AtomicU64,
+    /// The number of timestamps below the watermark whose versions are retained by the garbage
+    /// collection, so that they can still be read as of.
+    version_retention: AtomicU64,
+    /// How long, in milliseconds, a transaction conflicting with a running one waits for it to
+    /// finish, or 0 for not waiting.
+    lock_wait_timeout_ms: AtomicU64,
+    /// The ids of the transactions waiting for others to finish, with the ids of the latter.
+    waits_for: Mutex<HashMap<Timestamp, Timestamp>>,
 }
 
 impl Default for MemTxnManager {
@@ -42,11 +59,16 @@ fn default() -> Self {
         Self {
             graph: Weak::new(),
             active_txns: SkipMap::new(),
+            active_start_ts: SkipMap::new(),
             committed_txns: SkipMap::new(),
             commit_lock: Mutex::new(()),
             latest_commit_ts: AtomicU64::new(0),
             watermark: AtomicU64::new(0),
             last_gc_ts: AtomicU64::new(0),
+            gc_horizon: AtomicU64::new(0),
+            version_retention: AtomicU64::new(0),
+            lock_wait_timeout_ms: AtomicU64::new(0),
+            waits_for: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -66,6 +88,7 @@ fn begin_transaction(
     fn finish_transaction(&self, txn: &Self::Transaction) -> Result<(), Self::Error> {
         let txn_entry = self.active_txns.remove(&txn.txn_id());
         if let Some(txn_arc) = txn_entry {
+            self.active_start_ts.remove(&(txn.start_ts(), txn.txn_id()));
             // Check if the transaction has been committed (by checking if it has a commit_ts)
             if let Some(commit_ts) = txn.commit_ts() {
                 self.committed_txns
@@ -89,7 +112,10 @@ fn finish_transaction(&self, txn: &Self::Transaction) -> Result<(), Self::Error>
     }
 
     fn garbage_collect(&self, graph: &Self::GraphContext) -> Result<(), Self::Error> {
-        let min_read_ts = self.low_watermark().raw();
+        let min_read_ts = self
+            .low_watermark()
+            .raw()
+            .saturating_sub(self.version_retention.load(Ordering::Acquire));
         let mut expired_txns = Vec::new();
         let mut expired_undo_entries = Vec::new();
 
@@ -114,6 +140,7 @@ fn garbage_collect(&self, graph: &Self::GraphContext) -> Result<(), Self::Error>
         for txn in expired_txns {
             if let Some(commit_ts) = txn.commit_ts() {
                 self.committed_txns.remove(&commit_ts);
+                self.gc_horizon.fetch_max(commit_ts.raw(), Ordering::SeqCst);
             }
         }
 
@@ -148,6 +175,25 @@ pub fn begin_transaction_at(
         isolation_level: IsolationLevel,
         skip_wal: bool,
     ) -> Result<Arc<MemTransaction>, StorageError> {
+        self.begin(txn_id, start_ts, isolation_level, skip_wal, false)
+    }
+
+    /// Begins a read-only transaction, which reads a snapshot of the graph as of its start.
+    ///
+    /// It is cheaper than the other transactions: it neither records its reads for validation nor
+    /// logs to the WAL, and it has no modifications to undo. Modifying the graph with it fails.
+    pub fn begin_read_only_transaction(&self) -> StorageResult<Arc<MemTransaction>> {
+        self.begin(None, None, IsolationLevel::Snapshot, true, true)
+    }
+
+    fn begin(
+        &self,
+        txn_id: Option<Timestamp>,
+        start_ts: Option<Timestamp>,
+        isolation_level: IsolationLevel,
+        skip_wal: bool,
+        read_only: bool,
+    ) -> StorageResult<Arc<MemTransaction>> {
         let graph = self.graph.upgrade().ok_or_else(|| {
             StorageError::Transaction(TransactionError::InvalidState(
                 "Graph reference is no longer valid".to_string(),
@@ -192,8 +238,11 @@ pub fn begin_transaction_at(
             txn_id,
             start_ts,
             isolation_level,
+            read_only,
         ));
         self.active_txns.insert(txn.txn_id(), txn.clone());
+        self.active_start_ts
+            .insert((txn.start_ts(), txn.txn_id()), ());
         self.update_watermark();
 
         // Write `Operation::BeginTransaction` to WAL,
@@ -205,28 +254,135 @@ pub fn begin_transaction_at(
                 iso_level: *txn.isolation_level(),
                 op: Operation::BeginTransaction(txn.start_ts()),
             };
-            graph
-                .wal_manager
-                .wal()
-                .write()
-                .unwrap()
-                .append(&wal_entry)
-                .unwrap();
+            graph.wal_manager.append(&wal_entry).unwrap();
         }
 
         Ok(txn)
     }
 
+    /// Begins a transaction which reads the graph as of `ts`, i.e., which only sees the
+    /// modifications committed at or before `ts`. The transaction must not modify the graph.
+    ///
+    /// The versions as of `ts` are retained by the garbage collection until the transaction is
+    /// finished. Fails if `ts` is in the future, or if they were already collected.
+    pub fn begin_transaction_as_of(
+        &self,
+        ts: Timestamp,
+        isolation_level: IsolationLevel,
+    ) -> StorageResult<Arc<MemTransaction>> {
+        if !ts.is_commit_ts() || ts > global_timestamp_generator().current() {
+            return Err(StorageError::Transaction(TransactionError::InvalidState(
+                format!(
+                    "cannot read as of timestamp {}, which is in the future",
+                    ts.raw()
+                ),
+            )));
+        }
+        let txn = self.begin_transaction_at(None, Some(ts), isolation_level, false)?;
+        // The transaction now holds the watermark back, so the versions as of `ts` are retained
+        // unless they were collected before it began.
+        if self.gc_horizon.load(Ordering::SeqCst) > ts.raw() {
+            txn.abort()?;
+            return Err(StorageError::Transaction(TransactionError::InvalidState(
+                format!(
+                    "cannot read as of timestamp {}, whose versions were garbage collected",
+                    ts.raw()
+                ),
+            )));
+        }
+        Ok(txn)
+    }
+
+    /// Returns the commit timestamp of the latest committed transaction.
+    pub fn latest_commit_ts(&self) -> Timestamp {
+        Timestamp::with_ts(self.latest_commit_ts.load(Ordering::Acquire))
+    }
+
+    /// Sets the number of timestamps below the watermark whose versions are retained by the garbage
+    /// collection, so that transactions can still read the graph as of them. Defaults to 0.
+    pub fn set_version_retention(&self, retention: u64) {
+        self.version_retention.store(retention, Ordering::Release);
+    }
+
+    /// Sets how long a transaction modifying an entity which is being modified by a running one
+    /// waits for the latter to finish, after which it fails with
+    /// [`TransactionError::LockWaitTimeout`]. With `None`, the default, it fails right away with
+    /// [`TransactionError::WriteWriteConflict`].
+    ///
+    /// Transactions which would wait for each other fail with [`TransactionError::Deadlock`]
+    /// instead of waiting. Failed transactions should be aborted, so that the others can proceed.
+    pub fn set_lock_wait_timeout(&self, timeout: Option<Duration>) {
+        let timeout_ms = timeout.map_or(0, |timeout| timeout.as_millis() as u64);
+        self.lock_wait_timeout_ms
+            .store(timeout_ms, Ordering::Release);
+    }
+
+    /// Returns how long a transaction conflicting with a running one waits for it to finish, if it
+    /// does.
+    pub(super) fn lock_wait_timeout(&self) -> Option<Duration> {
+        match self.lock_wait_timeout_ms.load(Ordering::Acquire) {
+            0 => None,
+            timeout_ms => Some(Duration::from_millis(timeout_ms)),
+        }
+    }
+
+    /// Waits for the transaction `holder`, which modified an entity `txn` modifies, to finish,
+    /// until `deadline`.
+    ///
+    /// Fails right away if `holder` waits for `txn`, directly or not, since they would wait for
+    /// each other forever.
+    pub(super) fn wait_for(
+        &self,
+        txn: &MemTransaction,
+        holder: Timestamp,
+        deadline: Instant,
+    ) -> StorageResult<()> {
+        {
+            let mut waits_for = self.waits_for.lock().unwrap();
+            // Waits never form cycles, as the wait closing one fails instead.
+            let mut waiting = Some(holder);
+            while let Some(id) = waiting {
+                if id == txn.txn_id() {
+                    return Err(StorageError::Transaction(TransactionError::Deadlock(
+                        format!(
+                            "transaction {:?} and transaction {:?} wait for each other",
+                            txn.txn_id(),
+                            holder
+                        ),
+                    )));
+                }
+                waiting = waits_for.get(&id).copied();
+            }
+            waits_for.insert(txn.txn_id(), holder);
+        }
+        let result = loop {
+            if !self.active_txns.contains_key(&holder) {
+                break Ok(());
+            }
+            if Instant::now() >= deadline {
+                break Err(StorageError::Transaction(
+                    TransactionError::LockWaitTimeout(format!(
+                        "transaction {:?} waited too long for transaction {:?}",
+                        txn.txn_id(),
+                        holder
+                    )),
+                ));
+            }
+            thread::sleep(LOCK_WAIT_INTERVAL);
+        };
+        self.waits_for.lock().unwrap().remove(&txn.txn_id());
+        result
+    }
+
     /// Update the watermark based on currently active transactions.
     /// The watermark represents the minimum timestamp that any active transaction
     /// can see, which is crucial for determining what data can be garbage collected.
     fn update_watermark(&self) {
         let min_ts = self
-            .active_txns
+            .active_start_ts
             .front()
-            .map(|v| v.value().start_ts().raw())
-            .unwrap_or(self.latest_commit_ts.load(Ordering::Acquire))
-            .max(self.low_watermark().raw());
+            .map(|v| v.key().0.raw())
+            .unwrap_or(self.latest_commit_ts.load(Ordering::Acquire));
         self.watermark.store(min_ts, Ordering::SeqCst);
     }
 
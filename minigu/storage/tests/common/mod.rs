@@ -59,7 +59,10 @@ pub fn create_test_wal_config() -> WalManagerConfig {
         rand::random::<u32>()
     );
     let path = std::env::temp_dir().join(file_name);
-    WalManagerConfig { wal_path: path }
+    WalManagerConfig {
+        wal_path: path,
+        ..Default::default()
+    }
 }
 
 pub fn create_empty_graph() -> (Arc<MemoryGraph>, TestCleaner) {
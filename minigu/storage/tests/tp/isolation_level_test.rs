@@ -1,9 +1,13 @@
+use std::sync::Arc;
 use std::thread;
 
 use minigu_common::value::ScalarValue;
+use minigu_storage::error::{StorageError, TransactionError};
 use minigu_storage::model::edge::Edge;
 use minigu_storage::model::properties::PropertyRecord;
 use minigu_storage::model::vertex::Vertex;
+use minigu_storage::tp::MemTransaction;
+use minigu_storage::tp::transaction::VertexPredicate;
 use minigu_transaction::{GraphTxnManager, IsolationLevel, Transaction};
 
 use crate::common::*;
@@ -107,6 +111,49 @@ fn test_serializable_prevents_dirty_read_new_vertex() {
     assert!(txn1.commit().is_err()); // Should fail due to read-write conflict
 }
 
+#[test]
+fn test_serializable_reads_own_writes() {
+    let (graph, _cleaner) = create_test_graph();
+
+    // Transaction 1 creates and updates a new vertex without committing
+    let txn1 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    let carol = Vertex::new(
+        3,
+        PERSON_LABEL_ID,
+        PropertyRecord::new(vec![
+            ScalarValue::String(Some("Carol".to_string())),
+            ScalarValue::Int32(Some(28)),
+        ]),
+    );
+    graph.create_vertex(&txn1, carol).unwrap();
+    graph
+        .set_vertex_property(&txn1, 3, vec![1], vec![ScalarValue::Int32(Some(29))])
+        .unwrap();
+
+    // Transaction 1 sees its own changes, both by id and by scanning
+    let carol = graph.get_vertex(&txn1, 3).unwrap();
+    assert_eq!(carol.properties()[1], ScalarValue::Int32(Some(29)));
+    assert!(
+        txn1.iter_vertices()
+            .filter_map(|v| v.ok())
+            .any(|v| v.vid() == 3)
+    );
+
+    // Transaction 2 does not
+    let txn2 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    assert!(graph.get_vertex(&txn2, 3).is_err());
+    assert_eq!(txn2.iter_vertices().filter_map(|v| v.ok()).count(), 2);
+    txn2.abort().unwrap();
+
+    assert!(txn1.commit().is_ok());
+}
+
 // ========== NON-REPEATABLE READ TESTS ==========
 
 #[test]
@@ -226,6 +273,91 @@ fn test_serializable_prevents_phantom_read_vertices() {
     txn1.abort().unwrap();
 }
 
+/// Scans the persons older than 28 within `txn`, recording the predicate.
+fn scan_older_than_28(txn: &MemTransaction) -> Vec<Vertex> {
+    let predicate: VertexPredicate = Arc::new(|v| match v.properties()[1] {
+        ScalarValue::Int32(Some(age)) => age > 28,
+        _ => false,
+    });
+    txn.iter_vertices_where(predicate)
+        .filter_map(|v| v.ok())
+        .collect()
+}
+
+#[test]
+fn test_serializable_predicate_scan_prevents_phantom() {
+    let (graph, _cleaner) = create_test_graph();
+
+    // Transaction 1 scans the vertices matching a range predicate, then writes
+    let txn1 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    assert_eq!(scan_older_than_28(&txn1).len(), 1); // Bob (30)
+    graph
+        .set_vertex_property(&txn1, 1, vec![1], vec![ScalarValue::Int32(Some(26))])
+        .unwrap();
+
+    // Transaction 2 inserts a new vertex matching the predicate and commits
+    let txn2 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    let carol = Vertex::new(
+        3,
+        PERSON_LABEL_ID,
+        PropertyRecord::new(vec![
+            ScalarValue::String(Some("Carol".to_string())),
+            ScalarValue::Int32(Some(35)),
+        ]),
+    );
+    graph.create_vertex(&txn2, carol).unwrap();
+    txn2.commit().unwrap();
+
+    // Carol is a phantom of the scan of transaction 1, which must abort
+    let err = txn1.commit().unwrap_err();
+    assert!(matches!(
+        err,
+        StorageError::Transaction(TransactionError::ReadWriteConflict(_))
+    ));
+    let txn3 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    let alice = graph.get_vertex(&txn3, 1).unwrap();
+    assert_eq!(alice.properties()[1], ScalarValue::Int32(Some(25)));
+    txn3.abort().unwrap();
+}
+
+#[test]
+fn test_serializable_predicate_scan_allows_unrelated_insert() {
+    let (graph, _cleaner) = create_test_graph();
+
+    let txn1 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    assert_eq!(scan_older_than_28(&txn1).len(), 1);
+
+    // Transaction 2 inserts a vertex not matching the predicate
+    let txn2 = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)
+        .unwrap();
+    let dave = Vertex::new(
+        4,
+        PERSON_LABEL_ID,
+        PropertyRecord::new(vec![
+            ScalarValue::String(Some("Dave".to_string())),
+            ScalarValue::Int32(Some(20)),
+        ]),
+    );
+    graph.create_vertex(&txn2, dave).unwrap();
+    txn2.commit().unwrap();
+
+    assert!(txn1.commit().is_ok());
+}
+
 #[test]
 fn test_serializable_prevents_phantom_read_edges() {
     let (graph, _cleaner) = create_test_graph();
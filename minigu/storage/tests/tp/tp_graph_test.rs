@@ -1,4 +1,6 @@
+use minigu_common::value::ScalarValue;
 use minigu_storage::error::StorageResult;
+use minigu_storage::tp::iterators::ScanOrder;
 use minigu_transaction::{GraphTxnManager, IsolationLevel, Transaction};
 
 use crate::common::*;
@@ -199,3 +201,66 @@ fn test_graph_basic_operations() -> StorageResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_ascending_scan_order_is_deterministic() -> StorageResult<()> {
+    let (graph, _cleaner) = create_empty_graph();
+    let txn = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)?;
+
+    // Insert vertices and edges in non-sorted id order.
+    for id in [7, 3, 9, 1, 5] {
+        graph.create_vertex(&txn, create_test_vertex(id, "V", 20))?;
+    }
+    for (id, from, to) in [(40, 1, 3), (10, 3, 5), (30, 5, 7), (20, 7, 9)] {
+        graph.create_edge(&txn, create_test_edge(id, from, to, FRIEND_LABEL_ID))?;
+    }
+
+    let scan_vertices = || {
+        txn.iter_vertices_with_order(ScanOrder::Ascending)
+            .map(|v| v.map(|v| v.vid()))
+            .collect::<StorageResult<Vec<_>>>()
+    };
+    let first = scan_vertices()?;
+    assert_eq!(first, scan_vertices()?);
+    assert_eq!(first, vec![1, 3, 5, 7, 9]);
+
+    let scan_edges = || {
+        txn.iter_edges_with_order(ScanOrder::Ascending)
+            .map(|e| e.map(|e| e.eid()))
+            .collect::<StorageResult<Vec<_>>>()
+    };
+    let first = scan_edges()?;
+    assert_eq!(first, scan_edges()?);
+    assert_eq!(first, vec![10, 20, 30, 40]);
+
+    txn.abort()?;
+    Ok(())
+}
+
+#[test]
+fn test_property_statistics() -> StorageResult<()> {
+    let (graph, _cleaner) = create_test_graph();
+    let range = graph.statistics().get(PERSON_LABEL_ID, 1).unwrap();
+    assert_eq!(range.min, ScalarValue::Int32(Some(25)));
+    assert_eq!(range.max, ScalarValue::Int32(Some(30)));
+
+    let txn = graph
+        .txn_manager()
+        .begin_transaction(IsolationLevel::Serializable)?;
+    graph.create_vertex(&txn, create_test_vertex(3, "Carol", 40))?;
+    graph.set_vertex_property(&txn, 1, vec![1], vec![ScalarValue::Int32(Some(18))])?;
+    // Ranges are conservative, so deleting a vertex does not narrow them.
+    graph.delete_vertex(&txn, 2)?;
+    txn.commit()?;
+
+    let range = graph.statistics().get(PERSON_LABEL_ID, 1).unwrap();
+    assert_eq!(range.min, ScalarValue::Int32(Some(18)));
+    assert_eq!(range.max, ScalarValue::Int32(Some(40)));
+    let range = graph.statistics().get(PERSON_LABEL_ID, 0).unwrap();
+    assert_eq!(range.min, ScalarValue::String(Some("Alice".into())));
+    assert_eq!(range.max, ScalarValue::String(Some("Carol".into())));
+    assert!(graph.statistics().get(FOLLOW_LABEL_ID, 1).is_none());
+    Ok(())
+}